@@ -0,0 +1,109 @@
+//! Peak memory of building proof submission bodies
+//!
+//! Compares copying proofs into each request body, as submissions used to, with sharing them
+//! through `ChunkedBody`. Each case builds the body for a first attempt and two retries of a task
+//! with four large proofs, and reports the most memory allocated at once beyond the proofs
+//! themselves.
+//!
+//! Run with `cargo bench --bench submission_memory`.
+
+use bytes::Bytes;
+use nexus_cli_core::nexus_orchestrator::SubmitProofRequest;
+use nexus_cli_core::orchestrator::body::ChunkedBody;
+use prost::Message;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Allocator that tracks the bytes currently allocated and their peak
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const PROOF_SIZES_MIB: [usize; 3] = [4, 16, 64];
+const PROOFS_PER_TASK: usize = 4;
+const ATTEMPTS: usize = 3;
+
+/// Most memory allocated at once while running `f`, beyond what was allocated before
+fn peak_during(f: impl FnOnce()) -> usize {
+    let baseline = ALLOCATED.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    f();
+    PEAK.load(Ordering::SeqCst) - baseline
+}
+
+fn request(proof: Bytes, proofs: Vec<Bytes>) -> SubmitProofRequest {
+    SubmitProofRequest {
+        task_id: "bench".to_string(),
+        proof_hash: "hash".to_string(),
+        proof,
+        proofs,
+        signature: vec![0; 64],
+        ..Default::default()
+    }
+}
+
+/// Clone the proofs for every attempt and encode them into one buffer
+fn copying(proofs: &[Vec<u8>]) {
+    for _ in 0..ATTEMPTS {
+        let submission: Vec<Vec<u8>> = proofs.to_vec();
+        let legacy = submission.first().cloned().unwrap_or_default();
+        let request = request(
+            Bytes::from(legacy),
+            submission.into_iter().map(Bytes::from).collect(),
+        );
+        std::hint::black_box(request.encode_to_vec());
+    }
+}
+
+/// Share the proofs between attempts and send them as chunks of the body
+fn sharing(proofs: &[Bytes]) {
+    for _ in 0..ATTEMPTS {
+        let submission = proofs.to_vec();
+        let legacy = submission.first().cloned().unwrap_or_default();
+        let body = ChunkedBody::submit_proof(request(legacy, submission));
+        std::hint::black_box(reqwest::Body::from(body));
+    }
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>16} {:>16}",
+        "proof size", "copying (MiB)", "sharing (MiB)"
+    );
+    for size_mib in PROOF_SIZES_MIB {
+        let proofs: Vec<Vec<u8>> = (0..PROOFS_PER_TASK)
+            .map(|i| vec![i as u8; size_mib << 20])
+            .collect();
+        let copied = peak_during(|| copying(&proofs));
+
+        let proofs: Vec<Bytes> = proofs.into_iter().map(Bytes::from).collect();
+        let shared = peak_during(|| sharing(&proofs));
+
+        println!(
+            "{:>7}MiB {:>16.1} {:>16.1}",
+            size_mib,
+            copied as f64 / (1 << 20) as f64,
+            shared as f64 / (1 << 20) as f64
+        );
+    }
+}
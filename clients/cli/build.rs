@@ -42,6 +42,11 @@ fn main() -> Result<(), Box<dyn Error>> {
     config.out_dir(out_dir);
     // Add the experimental flag for proto3 optional fields
     config.protoc_arg("--experimental_allow_proto3_optional");
+    // Share proof bytes between retries and request bodies instead of copying them
+    config.bytes([
+        ".nexus.orchestrator.SubmitProofRequest.proof",
+        ".nexus.orchestrator.SubmitProofRequest.proofs",
+    ]);
 
     // Print current directory
     println!("Current dir: {:?}", env::current_dir()?);
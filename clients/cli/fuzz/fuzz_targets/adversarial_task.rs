@@ -0,0 +1,23 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nexus_cli_core::environment::Environment;
+use nexus_cli_core::prover::ProverError;
+use nexus_cli_core::prover::pipeline::ProvingPipeline;
+use nexus_cli_core::prover::testing::adversarial_task;
+
+fuzz_target!(|data: &[u8]| {
+    let task = adversarial_task(data);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let result = runtime.block_on(ProvingPipeline::prove_authenticated(
+        &task,
+        &Environment::default(),
+        "fuzz",
+        1,
+        None,
+        None,
+    ));
+    assert!(matches!(result, Err(ProverError::MalformedTask(_))));
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nexus_cli_core::prover::input::InputParser;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = InputParser::parse_triple_input(data);
+});
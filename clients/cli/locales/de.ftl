@@ -0,0 +1,311 @@
+# German messages of the Nexus CLI. Messages missing here are shown in English.
+
+## Command-line messages
+
+cli-error-details = Details: { $details }
+prompt-read-failed = Antwort konnte nicht gelesen werden: { $error }
+error-invalid-wallet = Ungültige Ethereum-Wallet-Adresse: { $address }. Sie muss aus 42 Hex-Zeichen bestehen und mit '0x' beginnen.
+
+config-error-missing = Konfigurationsdatei nicht gefunden. Bitte zuerst registrieren.
+config-error-unreadable = Konfigurationsdatei konnte nicht gelesen werden
+config-error-user-not-registered = Kein Benutzer registriert. Bitte zuerst einen Benutzer registrieren.
+config-error-node-not-registered = Node-Registrierung erforderlich. Bitte zuerst 'nexus-cli register-node' ausführen.
+config-error-invalid-node-id = Ungültige Node-ID in der Konfiguration. Zum Beheben 'nexus-cli register-node' ausführen.
+config-error-invalid = Die Konfigurationsdatei hat { $errors } Fehler, siehe `nexus-cli config validate`
+config-welcome = Willkommen bei der Nexus CLI!
+config-welcome-hint = Registriere zuerst deine Wallet-Adresse: nexus-cli register-user --wallet-address <deine-wallet-adresse>
+config-using-node-id = Angegebene Node-ID wird verwendet
+config-found-node-id = Node-ID in der Konfigurationsdatei gefunden
+config-incomplete = Deine Konfiguration ist unvollständig oder ungültig.
+config-incomplete-hint = Bitte registriere deinen Node. Beginne mit: nexus-cli register-node
+config-no-node = Benutzer registriert, aber kein Node gefunden
+config-no-node-hint = Registriere einen Node, um fortzufahren: nexus-cli register-node
+config-invalid-node-id = Ungültige Node-ID in der Konfigurationsdatei
+config-invalid-node-id-hint = Registriere einen neuen Node: nexus-cli register-node
+config-load-failed = Konfiguration konnte nicht geladen werden, bitte zuerst einen Benutzer registrieren
+config-save-failed = Konfiguration konnte nicht gespeichert werden
+config-no-user = Kein Benutzer registriert
+config-no-user-hint = Bitte zuerst einen Benutzer registrieren.
+
+register-invalid-wallet = Ungültige Ethereum-Wallet-Adresse
+register-invalid-wallet-hint = Sie muss aus 42 Hex-Zeichen bestehen und mit '0x' beginnen, erhalten: { $address }
+register-user-already-registered = Benutzer bereits registriert
+register-wallet-already-registered = Wallet-Adresse ist bereits registriert
+register-user-details = Benutzer-ID: { $user_id }, Wallet-Adresse: { $address }
+register-user-id = Benutzer-ID: { $user_id }
+register-user-registered = Benutzer erfolgreich registriert
+register-user-failed = Benutzer konnte nicht registriert werden
+register-user-complete = Benutzerregistrierung abgeschlossen!
+register-user-next-step = Nächster Schritt - einen Node registrieren: nexus-cli register-node
+register-replace-wallet = Die Konfiguration ist auf die Wallet { $address } registriert. Ersetzen?
+register-replace-node = Die Konfiguration hat bereits den Node { $node_id }. Ersetzen?
+register-pass-yes-or-if-not-exists = Mit --yes ersetzen oder mit --if-not-exists behalten.
+register-cancelled = Registrierung abgebrochen
+register-node-id = Node-ID: { $node_id }
+register-environment = Umgebung: { $environment }
+register-node-already-registered = Node bereits registriert
+register-node-registering = Node wird registriert
+register-node-registering-new = Neuer Node wird registriert
+register-node-failed = Node konnte nicht registriert werden
+register-node-complete = Node-Registrierung abgeschlossen!
+register-node-next-step = Node mit der ID { $node_id } erfolgreich registriert. Nächster Schritt - mit dem Beweisen beginnen: nexus-cli start
+register-nodes-registering = Nodes werden registriert
+register-nodes-details = Anzahl: { $count }, Umgebung: { $environment }
+register-nodes-registered = Node { $index } von { $count } registriert
+register-nodes-failed = Node { $index } von { $count } konnte nicht registriert werden
+register-nodes-saved = { $count } Nodes registriert, gespeichert in { $path }
+deregister-confirm = Node { $node_id } abmelden? Er sammelt keine Punkte mehr und kann nicht mehr beweisen.
+deregister-pass-yes = Mit --yes wird er abgemeldet.
+deregister-cancelled = Abmeldung abgebrochen
+deregister-no-node = Kein Node zum Abmelden: --node-id angeben oder zuerst einen Node registrieren
+deregister-node-running = Node { $node_id } wird von einer anderen laufenden Instanz bewiesen; zuerst beenden
+deregister-no-key = Auf diesem Rechner gibt es keinen Signaturschlüssel für Node { $node_id }; vom Rechner abmelden, der ihn registriert oder betrieben hat, oder mit --local-only nur hier entfernen
+deregister-deregistering = Node wird abgemeldet
+deregister-failed = Abmeldung des Nodes fehlgeschlagen
+deregister-failed-hint = Lokal wurde nichts entfernt. Wenn der Orchestrator den Node nicht mehr kennt, --local-only angeben.
+deregister-unsupported = Der Orchestrator kann keine Nodes abmelden; der Node wird nur von diesem Rechner entfernt
+deregister-complete = Node abgemeldet
+deregister-complete-details = Node { $node_id } wurde stillgelegt und von diesem Rechner entfernt
+deregister-local-details = Node { $node_id } wurde nur von diesem Rechner entfernt
+deregister-wiped = Signaturschlüssel und Einreichungsverlauf von Node { $node_id } entfernt
+
+wallet-added = Wallet hinzugefügt
+wallet-none-saved = Keine Wallets gespeichert
+wallet-none-saved-hint = Füge eine hinzu mit: nexus-cli wallet add <alias> <wallet-adresse>
+wallet-not-found = Keine Wallet unter '{ $alias }' gespeichert
+wallet-invalid-alias = Ungültiger Wallet-Alias '{ $alias }': verwende einen kurzen Namen, keine Adresse
+wallet-required = Eine Wallet-Adresse ist erforderlich: --wallet-address angeben
+wallet-select = Wallet auswählen:
+wallet-choice = Wallet
+wallet-invalid-choice = Ungültige Auswahl: { $choice }
+
+theme-selected = Farbschema ausgewählt
+theme-not-found = Kein Farbschema namens '{ $name }'. Siehe: nexus-cli theme list
+theme-skipped = Farbschema-Datei übersprungen
+theme-error-unreadable = Farbschema-Datei kann nicht gelesen werden
+theme-error-invalid = Ungültige Farbschema-Datei
+theme-error-unknown-role = Unbekannte Farbrolle in
+theme-error-invalid-color = Ungültige Farbe in
+theme-error-unknown-base = Unbekanntes Basis-Farbschema in
+
+report-unreadable = Fehlerbericht { $path } kann nicht gelesen werden: { $error }
+report-task = Fehlgeschlagene Aufgabe { $task_id }
+report-details = Programm: { $program_id }, erfasst { $captured_at } mit CLI { $version }
+report-upload-hint = An den Nexus-Support senden mit: nexus-cli report { $path } --upload
+report-uploaded = Fehlerbericht gesendet
+
+verify-proof-ok = Beweis verifiziert
+verify-proof-details = Aufgabe { $task_id }, Eingabe { $index }
+
+prove-ok = Beweis erstellt
+prove-details = Beweis-Hash { $hash }, Dauer { $seconds } s, Spitzenspeicher { $memory } GB
+prove-written = Beweis gespeichert in { $path }
+
+service-installed = Dienst installiert
+service-installed-details = { $name } beweist ab dem Systemstart; jetzt starten mit: nexus-cli service start
+service-uninstalled = Dienst entfernt
+service-started = Dienst gestartet
+service-stopped = Dienst wird beendet; die laufende Aufgabe wird zuerst abgeschlossen
+
+config-set = Einstellung gespeichert
+config-unknown-key = Unbekannte Einstellung '{ $key }'. Einstellungen: { $keys }
+config-invalid-value = Ungültiger Wert für { $key }: { $reason }
+
+update-up-to-date = Aktuell
+update-up-to-date-details = Version { $version } ist die neueste im Kanal { $channel }
+update-available = Update verfügbar
+update-available-details = Version { $latest } ist im Kanal { $channel } verfügbar (aktuell: { $version })
+update-install-hint = Installieren mit: nexus-cli update
+update-installed = Aktualisiert
+update-installed-details = Version { $latest } installiert; starte die CLI neu, um sie zu verwenden
+update-no-asset = Release { $latest } enthält keine Binärdatei für diese Plattform; lade sie von { $url } herunter
+update-unverified = Release { $latest } hat die Prüfung nicht bestanden und wurde nicht installiert: { $reason }
+update-restarting = Neustart mit der neuen Version...
+
+## Terminal UI
+
+splash-version = Version { $version }
+login-title = Anmeldung
+login-press-enter = Enter zum Anmelden
+login-press-esc = Esc zum Beenden
+
+dashboard-title-update = NEXUS PROVER v{ $version } - UPDATE VERFÜGBAR
+dashboard-title-update-to = NEXUS PROVER v{ $version } -> { $latest } UPDATE VERFÜGBAR
+dashboard-version-overridden = GESPERRTE VERSION, PRÜFUNG ÜBERGANGEN: { $message }
+dashboard-version-warning = VERSIONSWARNUNG: { $message }
+dashboard-version-notice = HINWEIS: { $message }
+dashboard-update-prompt = Version { $latest } ist verfügbar: [U] aktualisiert und startet neu, [X] blendet aus
+dashboard-update-prompt-unknown = Eine neue Version ist verfügbar: [U] aktualisiert und startet neu, [X] blendet aus
+dashboard-maintenance = Orchestrator in Wartung, zurück in etwa { $remaining }
+dashboard-maintenance-unknown = Orchestrator in Wartung, warte auf Rückkehr
+dashboard-footer = [Q] Beenden | [?] Hilfe | [C] Diagramme | [T] Farbschema | [Tab] Auswahl: { $panel } | [Leertaste] Ein/aus | [<] [>] Verschieben
+dashboard-footer-compact = [Q] Beenden | [?] Hilfe | [:] Befehle | [P] Pause
+dashboard-compact-summary = { $proofs } Beweise · { $points } Punkte
+dashboard-help-title = Hilfe (beliebige Taste schließt)
+dashboard-help-keys = Tasten
+dashboard-help-commands = Befehle (mit : eingeben)
+dashboard-help-quit = Beenden
+dashboard-help-help = Diese Hilfe anzeigen
+dashboard-help-palette = Befehlseingabe öffnen
+dashboard-help-pause = Abruf von Aufgaben pausieren oder fortsetzen
+dashboard-help-theme = Zum nächsten Farbschema wechseln
+dashboard-help-charts = Diagramme ein- oder ausblenden
+dashboard-help-select = Nächsten Bereich auswählen
+dashboard-help-toggle = Ausgewählten Bereich ein- oder ausblenden
+dashboard-help-move = Ausgewählten Bereich verschieben
+dashboard-help-update = Verfügbares Update installieren oder ausblenden
+dashboard-help-scroll = Aktivitätsprotokoll scrollen (oder Mausrad)
+dashboard-help-command-pause = Nach der aktuellen Aufgabe keine weiteren abrufen
+dashboard-help-command-resume = Wieder Aufgaben abrufen
+dashboard-help-command-difficulty = Maximale Schwierigkeit festlegen oder zur adaptiven zurückkehren
+dashboard-help-command-export = Aktivitätsprotokoll in eine Datei speichern
+dashboard-help-command-theme = Zu einem Farbschema nach Name oder zum nächsten wechseln
+dashboard-help-command-quit = Beenden
+dashboard-palette-title = Befehl (Enter führt aus, Esc bricht ab)
+dashboard-command-paused = Pausiert: keine neuen Aufgaben werden abgerufen
+dashboard-command-resumed = Aufgaben werden wieder abgerufen
+dashboard-command-difficulty = Maximale Schwierigkeit: { $difficulty }
+dashboard-command-difficulty-auto = Adaptive Schwierigkeit aktiv
+dashboard-command-invalid-difficulty = Unbekannte Schwierigkeit '{ $difficulty }'
+dashboard-command-exported = Aktivitätsprotokoll gespeichert in { $path }
+dashboard-command-export-failed = Aktivitätsprotokoll konnte nicht gespeichert werden: { $error }
+dashboard-command-theme = Farbschema: { $name }
+dashboard-command-unknown = Unbekannter Befehl '{ $command }', ? zeigt die Hilfe
+dashboard-command-no-control = In dieser Sitzung nicht verfügbar
+dashboard-panel-system-info = Systeminfo
+dashboard-panel-logs = Aktivitätsprotokoll
+dashboard-panel-charts = Diagramme
+dashboard-panel-system-metrics = Systemauslastung
+dashboard-panel-zkvm-stats = zkVM-Statistik
+dashboard-panel-points = Punkte
+dashboard-panel-leaderboard = Rangliste
+dashboard-panel-rate-limit = Ratenlimit
+dashboard-panel-network = Netzwerk
+dashboard-state-proving = BEWEISEN
+dashboard-state-waiting = WARTEN
+dashboard-state-maintenance = WARTUNG
+dashboard-state-submitting = EINREICHEN
+dashboard-proving = Beweis wird erzeugt
+dashboard-proving-eta = { $percent }% (noch { $eta })
+dashboard-proving-overdue = Dauert länger als üblich
+dashboard-submitting = Beweis wird eingereicht
+dashboard-waiting = Bereit für die nächste Aufgabe
+dashboard-waiting-countdown = Bereit für die nächste Aufgabe ({ $seconds }s)
+
+dashboard-system-info = SYSTEMINFO
+dashboard-node = Node: { $id }
+dashboard-node-disconnected = Node: Nicht verbunden
+dashboard-environment = Umgebung: { $environment }
+dashboard-version = Version: { $version }
+dashboard-uptime = Laufzeit: { $uptime }
+dashboard-threads = Threads: { $threads }
+dashboard-memory = Speicher: { $gigabytes } GB
+dashboard-gpu = GPU: { $gpu }
+dashboard-gpu-none = GPU: Keine erkannt
+
+dashboard-activity-log = AKTIVITÄTSPROTOKOLL
+dashboard-activity-log-scrolled = AKTIVITÄTSPROTOKOLL ({ $lines } zurück, nach unten scrollen für neueste)
+dashboard-starting-up = Wird gestartet...
+
+dashboard-cpu-usage = CPU-Auslastung
+dashboard-cpu-usage-pinned = CPU-Auslastung (Kerne { $cores })
+dashboard-temperature = { $celsius }°C
+dashboard-temperature-throttled = { $celsius }°C, gedrosselt
+dashboard-temperature-cooling = { $celsius }°C, kühlt ab
+dashboard-ram-usage = RAM-Auslastung
+dashboard-peak-ram = RAM-Spitze
+
+dashboard-zkvm-stats = zkVM-STATISTIK
+dashboard-tasks = Aufgaben
+dashboard-completed = Abgeschlossen
+dashboard-success = Erfolg
+dashboard-runtime = Laufzeit
+dashboard-last = Zuletzt
+dashboard-restarts = Neustarts
+dashboard-restarts-degraded = { $count } (Absturzschleife, eingeschränkt)
+dashboard-last-proof = Letzter Beweis
+dashboard-never = Nie
+dashboard-task-status-none = Keine
+dashboard-task-status-proved = Bewiesen
+dashboard-task-status-proof-failed = Beweis fehlgeschlagen
+dashboard-task-status-success = Erfolgreich
+dashboard-task-status-submit-failed = Übermittlung fehlgeschlagen
+
+dashboard-points = PUNKTE
+dashboard-points-total = Gesamt
+dashboard-points-session = Sitzung
+dashboard-points-wallet = Wallet
+dashboard-fetching = Wird abgerufen...
+dashboard-points-unavailable = Nicht verfügbar
+dashboard-leaderboard = RANGLISTE
+dashboard-leaderboard-provers = Prover
+dashboard-leaderboard-rank = Rang
+dashboard-leaderboard-unranked = noch nicht platziert
+dashboard-leaderboard-unavailable = Nicht verfügbar
+dashboard-leaderboard-supply = Aufgabenangebot
+dashboard-last-24h = Letzte 24 Std.
+
+dashboard-rate-limit = RATENLIMIT
+dashboard-rate-limit-budget = { $used } / { $allowed } pro { $window }s
+dashboard-next-fetch = Nächster Abruf
+dashboard-next-fetch-now = jetzt
+dashboard-next-fetch-in = in { $seconds }s
+dashboard-rate-limited-hour = 429er (1 Std.)
+dashboard-last-429 = Letzter 429
+dashboard-last-429-retry = { $time } (erneut in { $seconds }s)
+
+dashboard-network = NETZWERK
+dashboard-network-status = Status
+dashboard-network-health-unknown = noch keine Anfragen
+dashboard-network-health-good = gut
+dashboard-network-health-slow = langsame Antworten
+dashboard-network-health-local = Verbindung prüfen
+dashboard-network-health-server = Serverprobleme
+dashboard-network-rtt = RTT
+dashboard-network-rtt-value = Ø { $avg } ms, zuletzt { $last } ms
+dashboard-network-errors = Fehler ({ $minutes } Min.)
+dashboard-network-errors-value = { $rate } % (4xx { $client }, 5xx { $server }, keine Antwort { $network })
+
+dashboard-history = VERLAUF (LETZTE 24 STD.)
+dashboard-proofs-per-hour = Beweise pro Stunde ({ $total } gesamt)
+dashboard-avg-proof-duration = Durchschn. Beweisdauer: { $duration }
+dashboard-success-rate = Erfolgsquote: { $rate }
+
+dashboard-by-task = NACH AUFGABE
+dashboard-by-type = Nach Typ
+dashboard-by-difficulty = Nach Schwierigkeit
+dashboard-column-task = Aufgabe
+dashboard-column-done = Fertig
+dashboard-column-avg = Schnitt
+
+fleet-title = NEXUS-FLOTTE v{ $version }
+fleet-summary = { $running }/{ $nodes } Nodes aktiv · { $submitted } eingereicht · { $failed } fehlgeschlagen · { $points } Punkte
+fleet-footer = [↑↓] Auswählen · [Enter] Details · [Q] Beenden
+fleet-footer-details = [Esc] Zurück · [Q] Beenden
+fleet-column-node = Node
+fleet-column-state = Status
+fleet-column-task = Aufgabe
+fleet-column-rate = Beweise/h
+fleet-column-points = Punkte
+fleet-column-restarts = Neustarts
+fleet-column-last-error = Letzter Fehler
+fleet-state-starting = startet
+fleet-state-running = aktiv
+fleet-state-paused = pausiert
+fleet-state-off-schedule = außerhalb des Zeitplans
+fleet-state-restarting = Neustart in { $seconds }s
+fleet-state-stopped = gestoppt
+fleet-details-title = NODE { $id }
+fleet-details-process = { $state } · PID { $pid } · seit { $uptime } · { $restarts } Neustarts
+fleet-details-settings = Threads: { $threads } · Schwierigkeit: { $difficulty } · Zeitplan: { $schedule }
+fleet-details-counters = Wartend { $queued } · Abgerufen { $fetched } · Bewiesen { $proved } · Eingereicht { $submitted } · Fehler { $failed }
+fleet-details-no-stats = Noch keine Statistiken
+fleet-details-task = Aktuelle Aufgabe: { $task }
+fleet-details-points = Punkte: { $points } · Beweise pro Stunde: { $rate }
+fleet-details-last-error = Letzter Fehler: { $error }
+fleet-details-labels = Labels: { $labels }
+fleet-details-last-exit = Letztes Ende: { $exit }
+fleet-details-output = AUSGABE
+fleet-default = Standard
+fleet-schedule-always = immer
+fleet-no-output = Noch keine Ausgabe
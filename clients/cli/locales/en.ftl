@@ -0,0 +1,315 @@
+# English messages of the Nexus CLI. This catalog is complete and is the fallback for every
+# other language: translations only need to list the messages they translate.
+#
+# One message per line: `key = value`. Arguments are written as { $name }.
+
+## Command-line messages
+
+cli-error-details = Details: { $details }
+prompt-read-failed = Failed to read answer: { $error }
+error-invalid-wallet = Invalid Ethereum wallet address: { $address }. It should be a 42-character hex string starting with '0x'.
+
+config-error-missing = Configuration file not found. Please register first.
+config-error-unreadable = Failed to read configuration file
+config-error-user-not-registered = No user registered. Please register a user first.
+config-error-node-not-registered = Node registration required. Please run 'nexus-cli register-node' first.
+config-error-invalid-node-id = Invalid node ID in config. Please run 'nexus-cli register-node' to fix this.
+config-error-invalid = Configuration file has { $errors } errors, see `nexus-cli config validate`
+config-welcome = Welcome to Nexus CLI!
+config-welcome-hint = Please register your wallet address to get started: nexus-cli register-user --wallet-address <your-wallet-address>
+config-using-node-id = Using provided Node ID
+config-found-node-id = Found Node ID from config file
+config-incomplete = Your configuration is incomplete or invalid.
+config-incomplete-hint = Please register your node. Start with: nexus-cli register-node
+config-no-node = User registered, but no node found
+config-no-node-hint = Please register a node to continue: nexus-cli register-node
+config-invalid-node-id = Invalid node ID in config file
+config-invalid-node-id-hint = Please register a new node: nexus-cli register-node
+config-load-failed = Failed to load config, please register a user first
+config-save-failed = Failed to save config
+config-no-user = No user registered
+config-no-user-hint = Please register a user first.
+
+register-invalid-wallet = Invalid Ethereum wallet address
+register-invalid-wallet-hint = It should be a 42-character hex string starting with '0x', got: { $address }
+register-user-already-registered = User already registered
+register-wallet-already-registered = Wallet address is already registered
+register-user-details = User ID: { $user_id }, Wallet Address: { $address }
+register-user-id = User ID: { $user_id }
+register-user-registered = User registered successfully
+register-user-failed = Failed to register user
+register-user-complete = User registration complete!
+register-user-next-step = Next step - register a node: nexus-cli register-node
+register-replace-wallet = Config is registered to wallet { $address }. Replace it?
+register-replace-node = Config already has node { $node_id }. Replace it?
+register-pass-yes-or-if-not-exists = Pass --yes to replace it or --if-not-exists to keep it.
+register-cancelled = Registration cancelled
+register-node-id = Node ID: { $node_id }
+register-environment = Environment: { $environment }
+register-node-already-registered = Node already registered
+register-node-registering = Registering node
+register-node-registering-new = Registering a new node
+register-node-failed = Failed to register node
+register-node-complete = Node registration complete!
+register-node-next-step = Successfully registered node with ID: { $node_id }. Next step - start proving: nexus-cli start
+register-nodes-registering = Registering nodes
+register-nodes-details = Count: { $count }, Environment: { $environment }
+register-nodes-registered = Node { $index } of { $count } registered
+register-nodes-failed = Failed to register node { $index } of { $count }
+register-nodes-saved = Registered { $count } nodes, saved to { $path }
+deregister-confirm = Deregister node { $node_id }? It stops earning points and cannot prove again.
+deregister-pass-yes = Pass --yes to deregister it.
+deregister-cancelled = Deregistration cancelled
+deregister-no-node = No node to deregister: pass --node-id, or register a node first
+deregister-node-running = Node { $node_id } is being proved by another running instance; stop it first
+deregister-no-key = This machine has no signing key for node { $node_id }; deregister it from the machine that registered or ran it, or pass --local-only to only remove it here
+deregister-deregistering = Deregistering node
+deregister-failed = Failed to deregister node
+deregister-failed-hint = Nothing was removed locally. If the orchestrator no longer knows the node, pass --local-only.
+deregister-unsupported = The orchestrator cannot deregister nodes; removing the node from this machine only
+deregister-complete = Node deregistered
+deregister-complete-details = Node { $node_id } was retired and removed from this machine
+deregister-local-details = Node { $node_id } was removed from this machine only
+deregister-wiped = Removed the signing key and submission history of node { $node_id }
+
+wallet-added = Wallet added
+wallet-none-saved = No wallets saved
+wallet-none-saved-hint = Add one with: nexus-cli wallet add <alias> <wallet-address>
+wallet-not-found = No wallet saved as '{ $alias }'
+wallet-invalid-alias = Invalid wallet alias '{ $alias }': use a short name, not an address
+wallet-required = A wallet address is required: pass --wallet-address
+wallet-select = Select a wallet:
+wallet-choice = Wallet
+wallet-invalid-choice = Invalid choice: { $choice }
+
+theme-selected = Theme selected
+theme-not-found = No theme named '{ $name }'. See: nexus-cli theme list
+theme-skipped = Skipped theme file
+theme-error-unreadable = Cannot read theme file
+theme-error-invalid = Invalid theme file
+theme-error-unknown-role = Unknown color role in
+theme-error-invalid-color = Invalid color in
+theme-error-unknown-base = Unknown base theme in
+
+report-unreadable = Cannot read failure bundle { $path }: { $error }
+report-task = Failed task { $task_id }
+report-details = Program: { $program_id }, captured { $captured_at } by CLI { $version }
+report-upload-hint = Send it to Nexus support with: nexus-cli report { $path } --upload
+report-uploaded = Failure report sent
+
+verify-proof-ok = Proof verified
+verify-proof-details = Task { $task_id }, input { $index }
+
+prove-ok = Proof generated
+prove-details = Proof hash { $hash }, took { $seconds } s, peak memory { $memory } GB
+prove-written = Proof written to { $path }
+
+service-installed = Service installed
+service-installed-details = { $name } will prove at boot; start it now with: nexus-cli service start
+service-uninstalled = Service removed
+service-started = Service started
+service-stopped = Service stopping; it finishes the task in progress first
+
+config-set = Setting saved
+config-unknown-key = Unknown setting '{ $key }'. Settings: { $keys }
+config-invalid-value = Invalid value for { $key }: { $reason }
+
+update-up-to-date = Up to date
+update-up-to-date-details = Version { $version } is the latest on the { $channel } channel
+update-available = Update available
+update-available-details = Version { $latest } is available on the { $channel } channel (current: { $version })
+update-install-hint = Install it with: nexus-cli update
+update-installed = Updated
+update-installed-details = Installed version { $latest }; restart the CLI to use it
+update-no-asset = Release { $latest } has no binary for this platform; download it from { $url }
+update-unverified = Release { $latest } failed verification and was not installed: { $reason }
+update-restarting = Restarting with the new version...
+
+## Terminal UI
+
+splash-version = Version { $version }
+login-title = Login
+login-press-enter = Press Enter to login
+login-press-esc = Press Esc to exit
+
+dashboard-title = NEXUS PROVER v{ $version }
+dashboard-title-update = NEXUS PROVER v{ $version } - UPDATE AVAILABLE
+dashboard-title-update-to = NEXUS PROVER v{ $version } -> { $latest } UPDATE AVAILABLE
+dashboard-version-overridden = BLOCKED VERSION, CHECK OVERRIDDEN: { $message }
+dashboard-version-warning = VERSION WARNING: { $message }
+dashboard-version-notice = NOTICE: { $message }
+dashboard-update-prompt = Version { $latest } is available: press [U] to update and restart, [X] to dismiss
+dashboard-update-prompt-unknown = A new version is available: press [U] to update and restart, [X] to dismiss
+dashboard-maintenance = Orchestrator under maintenance, back in about { $remaining }
+dashboard-maintenance-unknown = Orchestrator under maintenance, waiting for it to come back
+dashboard-footer = [Q] Quit | [?] Help | [C] Charts | [T] Theme | [Tab] Select: { $panel } | [Space] Show/hide | [<] [>] Move
+dashboard-footer-compact = [Q] Quit | [?] Help | [:] Commands | [P] Pause
+dashboard-compact-summary = { $proofs } proofs · { $points } points
+dashboard-help-title = Help (any key to close)
+dashboard-help-keys = Keys
+dashboard-help-commands = Commands (press : to enter one)
+dashboard-help-quit = Quit
+dashboard-help-help = Show this help
+dashboard-help-palette = Open the command palette
+dashboard-help-pause = Pause or resume fetching tasks
+dashboard-help-theme = Switch to the next theme
+dashboard-help-charts = Show or hide the charts
+dashboard-help-select = Select the next panel
+dashboard-help-toggle = Show or hide the selected panel
+dashboard-help-move = Move the selected panel
+dashboard-help-update = Install or dismiss an available update
+dashboard-help-scroll = Scroll the activity log (or the mouse wheel)
+dashboard-help-command-pause = Stop fetching tasks after the current one
+dashboard-help-command-resume = Fetch tasks again
+dashboard-help-command-difficulty = Set the max task difficulty, or go back to adaptive
+dashboard-help-command-export = Save the activity log to a file
+dashboard-help-command-theme = Switch to a theme by name, or to the next one
+dashboard-help-command-quit = Quit
+dashboard-palette-title = Command (Enter to run, Esc to cancel)
+dashboard-command-paused = Paused: no new tasks will be fetched
+dashboard-command-resumed = Resumed fetching tasks
+dashboard-command-difficulty = Max difficulty set to { $difficulty }
+dashboard-command-difficulty-auto = Using adaptive difficulty
+dashboard-command-invalid-difficulty = Unknown difficulty '{ $difficulty }'
+dashboard-command-exported = Activity log saved to { $path }
+dashboard-command-export-failed = Could not save the activity log: { $error }
+dashboard-command-theme = Theme: { $name }
+dashboard-command-unknown = Unknown command '{ $command }', press ? for help
+dashboard-command-no-control = Not available in this session
+dashboard-panel-system-info = System info
+dashboard-panel-logs = Activity log
+dashboard-panel-charts = Charts
+dashboard-panel-system-metrics = System metrics
+dashboard-panel-zkvm-stats = zkVM stats
+dashboard-panel-points = Points
+dashboard-panel-leaderboard = Leaderboard
+dashboard-panel-rate-limit = Rate limit
+dashboard-panel-network = Network
+dashboard-state-proving = PROVING
+dashboard-state-waiting = WAITING
+dashboard-state-maintenance = MAINTENANCE
+dashboard-state-submitting = SUBMITTING
+dashboard-proving = Generating proof
+dashboard-proving-eta = { $percent }% (ETA { $eta })
+dashboard-proving-overdue = Taking longer than usual
+dashboard-submitting = Submitting proof
+dashboard-waiting = Ready for next task
+dashboard-waiting-countdown = Ready for next task ({ $seconds }s)
+
+dashboard-system-info = SYSTEM INFO
+dashboard-node = Node: { $id }
+dashboard-node-disconnected = Node: Disconnected
+dashboard-environment = Env: { $environment }
+dashboard-version = Version: { $version }
+dashboard-uptime = Uptime: { $uptime }
+dashboard-threads = Threads: { $threads }
+dashboard-memory = Memory: { $gigabytes } GB
+dashboard-gpu = GPU: { $gpu }
+dashboard-gpu-none = GPU: None detected
+
+dashboard-activity-log = ACTIVITY LOG
+dashboard-activity-log-scrolled = ACTIVITY LOG (scrolled back { $lines }, scroll down for newest)
+dashboard-starting-up = Starting up...
+
+dashboard-cpu-usage = CPU Usage
+dashboard-cpu-usage-pinned = CPU Usage (cores { $cores })
+dashboard-temperature = { $celsius }°C
+dashboard-temperature-throttled = { $celsius }°C, throttled
+dashboard-temperature-cooling = { $celsius }°C, cooling down
+dashboard-ram-usage = RAM Usage
+dashboard-peak-ram = Peak RAM
+
+dashboard-zkvm-stats = zkVM STATS
+dashboard-tasks = Tasks
+dashboard-completed = Completed
+dashboard-success = Success
+dashboard-runtime = Runtime
+dashboard-last = Last
+dashboard-restarts = Restarts
+dashboard-restarts-degraded = { $count } (crash loop, degraded)
+dashboard-last-proof = Last Proof
+dashboard-never = Never
+dashboard-task-status-none = None
+dashboard-task-status-proved = Proved
+dashboard-task-status-proof-failed = Proof Failed
+dashboard-task-status-success = Success
+dashboard-task-status-submit-failed = Submit Failed
+
+dashboard-points = POINTS
+dashboard-points-total = Total
+dashboard-points-session = Session
+dashboard-points-wallet = Wallet
+dashboard-fetching = Fetching...
+dashboard-points-unavailable = Unavailable
+dashboard-leaderboard = LEADERBOARD
+dashboard-leaderboard-provers = Provers
+dashboard-leaderboard-rank = Rank
+dashboard-leaderboard-unranked = not ranked yet
+dashboard-leaderboard-unavailable = Unavailable
+dashboard-leaderboard-supply = Task supply
+dashboard-last-24h = Last 24h
+
+dashboard-rate-limit = RATE LIMIT
+dashboard-rate-limit-budget = { $used } / { $allowed } per { $window }s
+dashboard-next-fetch = Next fetch
+dashboard-next-fetch-now = now
+dashboard-next-fetch-in = in { $seconds }s
+dashboard-rate-limited-hour = 429s (1h)
+dashboard-last-429 = Last 429
+dashboard-last-429-retry = { $time } (retry { $seconds }s)
+
+dashboard-network = NETWORK
+dashboard-network-status = Status
+dashboard-network-health-unknown = no requests yet
+dashboard-network-health-good = good
+dashboard-network-health-slow = slow responses
+dashboard-network-health-local = check your connection
+dashboard-network-health-server = server problems
+dashboard-network-rtt = RTT
+dashboard-network-rtt-value = { $avg } ms avg, { $last } ms last
+dashboard-network-errors = Errors ({ $minutes }m)
+dashboard-network-errors-value = { $rate }% (4xx { $client }, 5xx { $server }, no reply { $network })
+
+dashboard-history = HISTORY (LAST 24H)
+dashboard-proofs-per-hour = Proofs per hour ({ $total } total)
+dashboard-avg-proof-duration = Avg proof duration: { $duration }
+dashboard-success-rate = Success rate: { $rate }
+
+dashboard-by-task = BY TASK
+dashboard-by-type = By type
+dashboard-by-difficulty = By difficulty
+dashboard-column-task = Task
+dashboard-column-done = Done
+dashboard-column-avg = Avg
+
+fleet-title = NEXUS FLEET v{ $version }
+fleet-summary = { $running }/{ $nodes } nodes running · { $submitted } submitted · { $failed } failed · { $points } points
+fleet-footer = [↑↓] Select · [Enter] Details · [Q] Quit
+fleet-footer-details = [Esc] Back · [Q] Quit
+fleet-column-node = Node
+fleet-column-state = State
+fleet-column-task = Task
+fleet-column-rate = Proofs/h
+fleet-column-points = Points
+fleet-column-restarts = Restarts
+fleet-column-last-error = Last error
+fleet-state-starting = starting
+fleet-state-running = running
+fleet-state-paused = paused
+fleet-state-off-schedule = off schedule
+fleet-state-restarting = restarting in { $seconds }s
+fleet-state-stopped = stopped
+fleet-details-title = NODE { $id }
+fleet-details-process = { $state } · PID { $pid } · up { $uptime } · { $restarts } restarts
+fleet-details-settings = Threads: { $threads } · Difficulty: { $difficulty } · Schedule: { $schedule }
+fleet-details-counters = Queued { $queued } · Fetched { $fetched } · Proved { $proved } · Submitted { $submitted } · Failed { $failed }
+fleet-details-no-stats = No stats yet
+fleet-details-task = Current task: { $task }
+fleet-details-points = Points: { $points } · Proofs per hour: { $rate }
+fleet-details-last-error = Last error: { $error }
+fleet-details-labels = Labels: { $labels }
+fleet-details-last-exit = Last exit: { $exit }
+fleet-details-output = OUTPUT
+fleet-default = default
+fleet-schedule-always = always
+fleet-no-output = No output yet
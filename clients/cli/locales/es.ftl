@@ -0,0 +1,311 @@
+# Spanish messages of the Nexus CLI. Messages missing here are shown in English.
+
+## Command-line messages
+
+cli-error-details = Detalles: { $details }
+prompt-read-failed = No se pudo leer la respuesta: { $error }
+error-invalid-wallet = Dirección de billetera Ethereum no válida: { $address }. Debe ser una cadena hexadecimal de 42 caracteres que empiece por '0x'.
+
+config-error-missing = No se encontró el archivo de configuración. Regístrate primero.
+config-error-unreadable = No se pudo leer el archivo de configuración
+config-error-user-not-registered = No hay ningún usuario registrado. Registra un usuario primero.
+config-error-node-not-registered = Es necesario registrar un nodo. Ejecuta primero 'nexus-cli register-node'.
+config-error-invalid-node-id = ID de nodo no válido en la configuración. Ejecuta 'nexus-cli register-node' para corregirlo.
+config-error-invalid = El archivo de configuración tiene { $errors } errores, consulta `nexus-cli config validate`
+config-welcome = ¡Bienvenido a Nexus CLI!
+config-welcome-hint = Registra tu dirección de billetera para empezar: nexus-cli register-user --wallet-address <tu-direccion-de-billetera>
+config-using-node-id = Usando el ID de nodo indicado
+config-found-node-id = ID de nodo encontrado en el archivo de configuración
+config-incomplete = Tu configuración está incompleta o no es válida.
+config-incomplete-hint = Registra tu nodo. Empieza con: nexus-cli register-node
+config-no-node = Usuario registrado, pero no se encontró ningún nodo
+config-no-node-hint = Registra un nodo para continuar: nexus-cli register-node
+config-invalid-node-id = ID de nodo no válido en el archivo de configuración
+config-invalid-node-id-hint = Registra un nodo nuevo: nexus-cli register-node
+config-load-failed = No se pudo cargar la configuración, registra un usuario primero
+config-save-failed = No se pudo guardar la configuración
+config-no-user = No hay ningún usuario registrado
+config-no-user-hint = Registra un usuario primero.
+
+register-invalid-wallet = Dirección de billetera Ethereum no válida
+register-invalid-wallet-hint = Debe ser una cadena hexadecimal de 42 caracteres que empiece por '0x', se recibió: { $address }
+register-user-already-registered = El usuario ya está registrado
+register-wallet-already-registered = La dirección de billetera ya está registrada
+register-user-details = ID de usuario: { $user_id }, dirección de billetera: { $address }
+register-user-id = ID de usuario: { $user_id }
+register-user-registered = Usuario registrado correctamente
+register-user-failed = No se pudo registrar el usuario
+register-user-complete = ¡Registro de usuario completado!
+register-user-next-step = Siguiente paso - registrar un nodo: nexus-cli register-node
+register-replace-wallet = La configuración está registrada con la billetera { $address }. ¿Reemplazarla?
+register-replace-node = La configuración ya tiene el nodo { $node_id }. ¿Reemplazarlo?
+register-pass-yes-or-if-not-exists = Usa --yes para reemplazarlo o --if-not-exists para conservarlo.
+register-cancelled = Registro cancelado
+register-node-id = ID de nodo: { $node_id }
+register-environment = Entorno: { $environment }
+register-node-already-registered = El nodo ya está registrado
+register-node-registering = Registrando el nodo
+register-node-registering-new = Registrando un nodo nuevo
+register-node-failed = No se pudo registrar el nodo
+register-node-complete = ¡Registro del nodo completado!
+register-node-next-step = Nodo registrado correctamente con el ID { $node_id }. Siguiente paso - empezar a generar pruebas: nexus-cli start
+register-nodes-registering = Registrando nodos
+register-nodes-details = Cantidad: { $count }, entorno: { $environment }
+register-nodes-registered = Nodo { $index } de { $count } registrado
+register-nodes-failed = No se pudo registrar el nodo { $index } de { $count }
+register-nodes-saved = { $count } nodos registrados, guardados en { $path }
+deregister-confirm = ¿Dar de baja el nodo { $node_id }? Dejará de ganar puntos y no podrá volver a probar.
+deregister-pass-yes = Usa --yes para darlo de baja.
+deregister-cancelled = Baja cancelada
+deregister-no-node = No hay nodo que dar de baja: usa --node-id o registra un nodo primero
+deregister-node-running = Otra instancia en ejecución está probando el nodo { $node_id }; detenla primero
+deregister-no-key = Esta máquina no tiene clave de firma para el nodo { $node_id }; dalo de baja desde la máquina que lo registró o ejecutó, o usa --local-only para eliminarlo solo aquí
+deregister-deregistering = Dando de baja el nodo
+deregister-failed = No se pudo dar de baja el nodo
+deregister-failed-hint = No se eliminó nada localmente. Si el orquestador ya no conoce el nodo, usa --local-only.
+deregister-unsupported = El orquestador no puede dar de baja nodos; el nodo se eliminará solo de esta máquina
+deregister-complete = Nodo dado de baja
+deregister-complete-details = El nodo { $node_id } se retiró y se eliminó de esta máquina
+deregister-local-details = El nodo { $node_id } se eliminó solo de esta máquina
+deregister-wiped = Se eliminaron la clave de firma y el historial de envíos del nodo { $node_id }
+
+wallet-added = Billetera añadida
+wallet-none-saved = No hay billeteras guardadas
+wallet-none-saved-hint = Añade una con: nexus-cli wallet add <alias> <direccion-de-billetera>
+wallet-not-found = No hay ninguna billetera guardada como '{ $alias }'
+wallet-invalid-alias = Alias de billetera no válido '{ $alias }': usa un nombre corto, no una dirección
+wallet-required = Se necesita una dirección de billetera: usa --wallet-address
+wallet-select = Elige una billetera:
+wallet-choice = Billetera
+wallet-invalid-choice = Opción no válida: { $choice }
+
+theme-selected = Tema seleccionado
+theme-not-found = No hay ningún tema llamado '{ $name }'. Consulta: nexus-cli theme list
+theme-skipped = Archivo de tema omitido
+theme-error-unreadable = No se puede leer el archivo de tema
+theme-error-invalid = Archivo de tema no válido
+theme-error-unknown-role = Rol de color desconocido en
+theme-error-invalid-color = Color no válido en
+theme-error-unknown-base = Tema base desconocido en
+
+report-unreadable = No se puede leer el informe de fallo { $path }: { $error }
+report-task = Tarea fallida { $task_id }
+report-details = Programa: { $program_id }, capturado { $captured_at } con la CLI { $version }
+report-upload-hint = Envíalo al soporte de Nexus con: nexus-cli report { $path } --upload
+report-uploaded = Informe de fallo enviado
+
+verify-proof-ok = Prueba verificada
+verify-proof-details = Tarea { $task_id }, entrada { $index }
+
+prove-ok = Prueba generada
+prove-details = Hash de la prueba { $hash }, duración { $seconds } s, memoria máxima { $memory } GB
+prove-written = Prueba guardada en { $path }
+
+service-installed = Servicio instalado
+service-installed-details = { $name } probará al arrancar; inícielo ahora con: nexus-cli service start
+service-uninstalled = Servicio eliminado
+service-started = Servicio iniciado
+service-stopped = Deteniendo el servicio; primero termina la tarea en curso
+
+config-set = Ajuste guardado
+config-unknown-key = Ajuste desconocido '{ $key }'. Ajustes: { $keys }
+config-invalid-value = Valor no válido para { $key }: { $reason }
+
+update-up-to-date = Actualizado
+update-up-to-date-details = La versión { $version } es la más reciente del canal { $channel }
+update-available = Actualización disponible
+update-available-details = La versión { $latest } está disponible en el canal { $channel } (actual: { $version })
+update-install-hint = Instálala con: nexus-cli update
+update-installed = Actualizado
+update-installed-details = Versión { $latest } instalada; reinicia la CLI para usarla
+update-no-asset = La versión { $latest } no tiene binario para esta plataforma; descárgala de { $url }
+update-unverified = La versión { $latest } no superó la verificación y no se instaló: { $reason }
+update-restarting = Reiniciando con la nueva versión...
+
+## Terminal UI
+
+splash-version = Versión { $version }
+login-title = Inicio de sesión
+login-press-enter = Pulsa Enter para iniciar sesión
+login-press-esc = Pulsa Esc para salir
+
+dashboard-title-update = NEXUS PROVER v{ $version } - ACTUALIZACIÓN DISPONIBLE
+dashboard-title-update-to = NEXUS PROVER v{ $version } -> { $latest } ACTUALIZACIÓN DISPONIBLE
+dashboard-version-overridden = VERSIÓN BLOQUEADA, COMPROBACIÓN OMITIDA: { $message }
+dashboard-version-warning = AVISO DE VERSIÓN: { $message }
+dashboard-version-notice = NOTA: { $message }
+dashboard-update-prompt = La versión { $latest } está disponible: pulsa [U] para actualizar y reiniciar, [X] para ocultar
+dashboard-update-prompt-unknown = Hay una nueva versión disponible: pulsa [U] para actualizar y reiniciar, [X] para ocultar
+dashboard-maintenance = Orquestador en mantenimiento, vuelve en unos { $remaining }
+dashboard-maintenance-unknown = Orquestador en mantenimiento, esperando a que vuelva
+dashboard-footer = [Q] Salir | [?] Ayuda | [C] Gráficos | [T] Tema | [Tab] Selección: { $panel } | [Espacio] Mostrar/ocultar | [<] [>] Mover
+dashboard-footer-compact = [Q] Salir | [?] Ayuda | [:] Comandos | [P] Pausa
+dashboard-compact-summary = { $proofs } pruebas · { $points } puntos
+dashboard-help-title = Ayuda (cualquier tecla para cerrar)
+dashboard-help-keys = Teclas
+dashboard-help-commands = Comandos (pulsa : para escribir uno)
+dashboard-help-quit = Salir
+dashboard-help-help = Mostrar esta ayuda
+dashboard-help-palette = Abrir la paleta de comandos
+dashboard-help-pause = Pausar o reanudar la obtención de tareas
+dashboard-help-theme = Cambiar al siguiente tema
+dashboard-help-charts = Mostrar u ocultar los gráficos
+dashboard-help-select = Seleccionar el siguiente panel
+dashboard-help-toggle = Mostrar u ocultar el panel seleccionado
+dashboard-help-move = Mover el panel seleccionado
+dashboard-help-update = Instalar u ocultar una actualización disponible
+dashboard-help-scroll = Desplazar el registro de actividad (o la rueda del ratón)
+dashboard-help-command-pause = Dejar de obtener tareas tras la actual
+dashboard-help-command-resume = Volver a obtener tareas
+dashboard-help-command-difficulty = Fijar la dificultad máxima o volver a la adaptativa
+dashboard-help-command-export = Guardar el registro de actividad en un archivo
+dashboard-help-command-theme = Cambiar a un tema por nombre, o al siguiente
+dashboard-help-command-quit = Salir
+dashboard-palette-title = Comando (Enter para ejecutar, Esc para cancelar)
+dashboard-command-paused = En pausa: no se obtendrán tareas nuevas
+dashboard-command-resumed = Se vuelven a obtener tareas
+dashboard-command-difficulty = Dificultad máxima: { $difficulty }
+dashboard-command-difficulty-auto = Usando dificultad adaptativa
+dashboard-command-invalid-difficulty = Dificultad desconocida '{ $difficulty }'
+dashboard-command-exported = Registro de actividad guardado en { $path }
+dashboard-command-export-failed = No se pudo guardar el registro de actividad: { $error }
+dashboard-command-theme = Tema: { $name }
+dashboard-command-unknown = Comando desconocido '{ $command }', pulsa ? para ver la ayuda
+dashboard-command-no-control = No disponible en esta sesión
+dashboard-panel-system-info = Info del sistema
+dashboard-panel-logs = Registro de actividad
+dashboard-panel-charts = Gráficos
+dashboard-panel-system-metrics = Métricas del sistema
+dashboard-panel-zkvm-stats = Estadísticas zkVM
+dashboard-panel-points = Puntos
+dashboard-panel-leaderboard = Clasificación
+dashboard-panel-rate-limit = Límite de peticiones
+dashboard-panel-network = Red
+dashboard-state-proving = PROBANDO
+dashboard-state-waiting = ESPERANDO
+dashboard-state-maintenance = MANTENIMIENTO
+dashboard-state-submitting = ENVIANDO
+dashboard-proving = Generando prueba
+dashboard-proving-eta = { $percent }% (faltan { $eta })
+dashboard-proving-overdue = Tarda más de lo habitual
+dashboard-submitting = Enviando prueba
+dashboard-waiting = Listo para la siguiente tarea
+dashboard-waiting-countdown = Listo para la siguiente tarea ({ $seconds }s)
+
+dashboard-system-info = INFO DEL SISTEMA
+dashboard-node = Nodo: { $id }
+dashboard-node-disconnected = Nodo: Desconectado
+dashboard-environment = Entorno: { $environment }
+dashboard-version = Versión: { $version }
+dashboard-uptime = Tiempo activo: { $uptime }
+dashboard-threads = Hilos: { $threads }
+dashboard-memory = Memoria: { $gigabytes } GB
+dashboard-gpu = GPU: { $gpu }
+dashboard-gpu-none = GPU: No detectada
+
+dashboard-activity-log = REGISTRO DE ACTIVIDAD
+dashboard-activity-log-scrolled = REGISTRO DE ACTIVIDAD ({ $lines } atrás, desplaza abajo para lo más reciente)
+dashboard-starting-up = Iniciando...
+
+dashboard-cpu-usage = Uso de CPU
+dashboard-cpu-usage-pinned = Uso de CPU (núcleos { $cores })
+dashboard-temperature = { $celsius }°C
+dashboard-temperature-throttled = { $celsius }°C, limitado
+dashboard-temperature-cooling = { $celsius }°C, enfriando
+dashboard-ram-usage = Uso de RAM
+dashboard-peak-ram = RAM máxima
+
+dashboard-zkvm-stats = ESTADÍSTICAS zkVM
+dashboard-tasks = Tareas
+dashboard-completed = Completadas
+dashboard-success = Éxito
+dashboard-runtime = Tiempo
+dashboard-last = Última
+dashboard-restarts = Reinicios
+dashboard-restarts-degraded = { $count } (bucle de fallos, degradado)
+dashboard-last-proof = Última prueba
+dashboard-never = Nunca
+dashboard-task-status-none = Ninguna
+dashboard-task-status-proved = Probada
+dashboard-task-status-proof-failed = Prueba fallida
+dashboard-task-status-success = Correcta
+dashboard-task-status-submit-failed = Envío fallido
+
+dashboard-points = PUNTOS
+dashboard-points-total = Total
+dashboard-points-session = Sesión
+dashboard-points-wallet = Billetera
+dashboard-fetching = Obteniendo...
+dashboard-points-unavailable = No disponible
+dashboard-leaderboard = CLASIFICACIÓN
+dashboard-leaderboard-provers = Probadores
+dashboard-leaderboard-rank = Posición
+dashboard-leaderboard-unranked = aún sin clasificar
+dashboard-leaderboard-unavailable = No disponible
+dashboard-leaderboard-supply = Oferta de tareas
+dashboard-last-24h = Últimas 24 h
+
+dashboard-rate-limit = LÍMITE DE PETICIONES
+dashboard-rate-limit-budget = { $used } / { $allowed } cada { $window }s
+dashboard-next-fetch = Siguiente petición
+dashboard-next-fetch-now = ahora
+dashboard-next-fetch-in = en { $seconds }s
+dashboard-rate-limited-hour = 429 (1 h)
+dashboard-last-429 = Último 429
+dashboard-last-429-retry = { $time } (reintento en { $seconds }s)
+
+dashboard-network = RED
+dashboard-network-status = Estado
+dashboard-network-health-unknown = aún sin solicitudes
+dashboard-network-health-good = buena
+dashboard-network-health-slow = respuestas lentas
+dashboard-network-health-local = revisa tu conexión
+dashboard-network-health-server = problemas del servidor
+dashboard-network-rtt = RTT
+dashboard-network-rtt-value = { $avg } ms media, { $last } ms última
+dashboard-network-errors = Errores ({ $minutes } min)
+dashboard-network-errors-value = { $rate } % (4xx { $client }, 5xx { $server }, sin respuesta { $network })
+
+dashboard-history = HISTORIAL (ÚLTIMAS 24 H)
+dashboard-proofs-per-hour = Pruebas por hora ({ $total } en total)
+dashboard-avg-proof-duration = Duración media de prueba: { $duration }
+dashboard-success-rate = Tasa de éxito: { $rate }
+
+dashboard-by-task = POR TAREA
+dashboard-by-type = Por tipo
+dashboard-by-difficulty = Por dificultad
+dashboard-column-task = Tarea
+dashboard-column-done = Hechas
+dashboard-column-avg = Media
+
+fleet-title = FLOTA NEXUS v{ $version }
+fleet-summary = { $running }/{ $nodes } nodos activos · { $submitted } enviadas · { $failed } fallidas · { $points } puntos
+fleet-footer = [↑↓] Seleccionar · [Enter] Detalles · [Q] Salir
+fleet-footer-details = [Esc] Volver · [Q] Salir
+fleet-column-node = Nodo
+fleet-column-state = Estado
+fleet-column-task = Tarea
+fleet-column-rate = Pruebas/h
+fleet-column-points = Puntos
+fleet-column-restarts = Reinicios
+fleet-column-last-error = Último error
+fleet-state-starting = iniciando
+fleet-state-running = activo
+fleet-state-paused = en pausa
+fleet-state-off-schedule = fuera de horario
+fleet-state-restarting = reinicio en { $seconds }s
+fleet-state-stopped = detenido
+fleet-details-title = NODO { $id }
+fleet-details-process = { $state } · PID { $pid } · activo { $uptime } · { $restarts } reinicios
+fleet-details-settings = Hilos: { $threads } · Dificultad: { $difficulty } · Horario: { $schedule }
+fleet-details-counters = En cola { $queued } · Obtenidas { $fetched } · Probadas { $proved } · Enviadas { $submitted } · Fallidas { $failed }
+fleet-details-no-stats = Aún sin estadísticas
+fleet-details-task = Tarea actual: { $task }
+fleet-details-points = Puntos: { $points } · Pruebas por hora: { $rate }
+fleet-details-last-error = Último error: { $error }
+fleet-details-labels = Etiquetas: { $labels }
+fleet-details-last-exit = Última salida: { $exit }
+fleet-details-output = SALIDA
+fleet-default = predeterminado
+fleet-schedule-always = siempre
+fleet-no-output = Aún sin salida
@@ -0,0 +1,545 @@
+//! Task accept policy
+//!
+//! With `start --accept-policy`, the task fetcher previews each task before taking it and
+//! declines those the policy rejects, so they are offered to other nodes. A policy compares task
+//! properties with values, e.g. `difficulty<=medium && reward>=10`:
+//!
+//! * `difficulty`: the task difficulty, e.g. `small_medium` or `extra_large_2`
+//! * `reward`: the points weight of the task
+//! * `cycles`: the estimated zkVM cycles, e.g. `500k` or `2m`
+//! * `size`: the size of the inputs in bytes, e.g. `64` or `1k`
+//! * `type`: the task type, `proof_required`, `proof_hash` or `all_proof_hashes`; only compared
+//!   with `==` and `!=`
+//!
+//! Comparisons are `<`, `<=`, `>`, `>=`, `==` and `!=`, combined with `&&` and `||`, negated with
+//! `!` and grouped with parentheses. `&&` binds tighter than `||`.
+
+use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+use crate::task::TaskDescriptor;
+use std::fmt::Display;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Why an accept policy cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PolicyError {
+    #[error("Expected {expected} at position {position}, found '{found}'")]
+    Unexpected {
+        expected: &'static str,
+        found: String,
+        position: usize,
+    },
+
+    #[error("Unknown task property '{0}', expected difficulty, reward, cycles, size or type")]
+    UnknownProperty(String),
+
+    #[error("Invalid {property} '{value}'")]
+    InvalidValue {
+        property: &'static str,
+        value: String,
+    },
+
+    #[error("The task type can only be compared with == or !=")]
+    UnorderedType,
+}
+
+/// Task property a policy compares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Property {
+    Difficulty,
+    Reward,
+    Cycles,
+    Size,
+    Type,
+}
+
+impl Property {
+    fn parse(name: &str) -> Result<Self, PolicyError> {
+        match name.to_ascii_lowercase().as_str() {
+            "difficulty" => Ok(Property::Difficulty),
+            "reward" => Ok(Property::Reward),
+            "cycles" => Ok(Property::Cycles),
+            "size" => Ok(Property::Size),
+            "type" => Ok(Property::Type),
+            _ => Err(PolicyError::UnknownProperty(name.to_string())),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Property::Difficulty => "difficulty",
+            Property::Reward => "reward",
+            Property::Cycles => "cycles",
+            Property::Size => "size",
+            Property::Type => "type",
+        }
+    }
+
+    /// The value of `text` for this property; difficulties and task types compare by their
+    /// protobuf number, which orders difficulties from easiest to hardest.
+    fn value(self, text: &str) -> Result<u64, PolicyError> {
+        let invalid = || PolicyError::InvalidValue {
+            property: self.name(),
+            value: text.to_string(),
+        };
+        let upper = text.to_ascii_uppercase();
+        match self {
+            Property::Difficulty => TaskDifficulty::from_str_name(&upper)
+                .map(|difficulty| difficulty as u64)
+                .ok_or_else(invalid),
+            Property::Type => TaskType::from_str_name(&upper)
+                .map(|task_type| task_type as u64)
+                .ok_or_else(invalid),
+            Property::Reward | Property::Cycles | Property::Size => {
+                parse_amount(text).ok_or_else(invalid)
+            }
+        }
+    }
+
+    fn of(self, task: &TaskDescriptor) -> u64 {
+        match self {
+            Property::Difficulty => task.difficulty as u64,
+            Property::Reward => task.reward_weight,
+            Property::Cycles => task.expected_cycles,
+            Property::Size => task.input_size,
+            Property::Type => task.task_type as u64,
+        }
+    }
+}
+
+/// A number with an optional `k`, `m` or `g` multiplier, e.g. `1.5m`.
+fn parse_amount(text: &str) -> Option<u64> {
+    let lower = text.to_ascii_lowercase();
+    let (number, multiplier) = match lower.char_indices().last()? {
+        (i, 'k') => (&lower[..i], 1e3),
+        (i, 'm') => (&lower[..i], 1e6),
+        (i, 'g') => (&lower[..i], 1e9),
+        _ => (lower.as_str(), 1.0),
+    };
+    let value = number.parse::<f64>().ok()? * multiplier;
+    (0.0..=u64::MAX as f64)
+        .contains(&value)
+        .then(|| value.round() as u64)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    fn holds(self, left: u64, right: u64) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+        }
+    }
+
+    /// The comparison that holds exactly when this one does not.
+    fn negated(self) -> Self {
+        match self {
+            Comparison::Less => Comparison::GreaterOrEqual,
+            Comparison::LessOrEqual => Comparison::Greater,
+            Comparison::Greater => Comparison::LessOrEqual,
+            Comparison::GreaterOrEqual => Comparison::Less,
+            Comparison::Equal => Comparison::NotEqual,
+            Comparison::NotEqual => Comparison::Equal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Compare(Property, Comparison, u64),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn matches(&self, task: &TaskDescriptor) -> bool {
+        match self {
+            Expr::Compare(property, comparison, value) => {
+                comparison.holds(property.of(task), *value)
+            }
+            Expr::Not(expr) => !expr.matches(task),
+            Expr::And(left, right) => left.matches(task) && right.matches(task),
+            Expr::Or(left, right) => left.matches(task) || right.matches(task),
+        }
+    }
+
+    /// Highest difficulty number of the tasks the expression, or its negation if `negated`, can
+    /// match; `None` if it does not bound the difficulty.
+    fn max_difficulty(&self, negated: bool) -> Option<u64> {
+        match self {
+            Expr::Compare(Property::Difficulty, comparison, value) => {
+                let comparison = if negated {
+                    comparison.negated()
+                } else {
+                    *comparison
+                };
+                match comparison {
+                    Comparison::Less => Some(value.saturating_sub(1)),
+                    Comparison::LessOrEqual | Comparison::Equal => Some(*value),
+                    _ => None,
+                }
+            }
+            Expr::Compare(..) => None,
+            Expr::Not(expr) => expr.max_difficulty(!negated),
+            Expr::And(left, right) | Expr::Or(left, right) => {
+                let left = left.max_difficulty(negated);
+                let right = right.max_difficulty(negated);
+                // Negated, `&&` turns into `||` and back
+                if matches!(self, Expr::And(..)) != negated {
+                    // Both must hold, so either bound applies
+                    match (left, right) {
+                        (Some(left), Some(right)) => Some(left.min(right)),
+                        (left, right) => left.or(right),
+                    }
+                } else {
+                    left.zip(right).map(|(left, right)| left.max(right))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Compare(Comparison),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Compare(comparison) => {
+                let text = match comparison {
+                    Comparison::Less => "<",
+                    Comparison::LessOrEqual => "<=",
+                    Comparison::Greater => ">",
+                    Comparison::GreaterOrEqual => ">=",
+                    Comparison::Equal => "==",
+                    Comparison::NotEqual => "!=",
+                };
+                write!(f, "{}", text)
+            }
+            Token::And => write!(f, "&&"),
+            Token::Or => write!(f, "||"),
+            Token::Not => write!(f, "!"),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+/// Split `source` into tokens, each with the character position it starts at.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, PolicyError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let (token, len) = match (c, next) {
+            _ if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('<', Some('=')) => (Token::Compare(Comparison::LessOrEqual), 2),
+            ('>', Some('=')) => (Token::Compare(Comparison::GreaterOrEqual), 2),
+            ('=', Some('=')) => (Token::Compare(Comparison::Equal), 2),
+            ('!', Some('=')) => (Token::Compare(Comparison::NotEqual), 2),
+            ('<', _) => (Token::Compare(Comparison::Less), 1),
+            ('>', _) => (Token::Compare(Comparison::Greater), 1),
+            ('=', _) => (Token::Compare(Comparison::Equal), 1),
+            ('!', _) => (Token::Not, 1),
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            _ if c.is_ascii_alphanumeric() || c == '_' || c == '.' => {
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_alphanumeric() || **c == '_' || **c == '.')
+                    .count();
+                (Token::Word(chars[i..i + len].iter().collect()), len)
+            }
+            _ => {
+                return Err(PolicyError::Unexpected {
+                    expected: "a property, value or operator",
+                    found: c.to_string(),
+                    position: i,
+                });
+            }
+        };
+        tokens.push((token, i));
+        i += len;
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent parser over the tokens of a policy
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+    /// Length of the policy, reported as the position of a missing token
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(token, _)| token)
+    }
+
+    fn unexpected(&self, expected: &'static str) -> PolicyError {
+        match self.tokens.get(self.next) {
+            Some((token, position)) => PolicyError::Unexpected {
+                expected,
+                found: token.to_string(),
+                position: *position,
+            },
+            None => PolicyError::Unexpected {
+                expected,
+                found: "end of policy".to_string(),
+                position: self.end,
+            },
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, PolicyError> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, PolicyError> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, PolicyError> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.next += 1;
+                Ok(Expr::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::Open) => {
+                self.next += 1;
+                let expr = self.or()?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(self.unexpected("')'"));
+                }
+                self.next += 1;
+                Ok(expr)
+            }
+            _ => self.comparison(),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expr, PolicyError> {
+        let Some(Token::Word(name)) = self.peek().cloned() else {
+            return Err(self.unexpected("a task property"));
+        };
+        let property = Property::parse(&name)?;
+        self.next += 1;
+        let Some(Token::Compare(comparison)) = self.peek().cloned() else {
+            return Err(self.unexpected("a comparison"));
+        };
+        if property == Property::Type
+            && !matches!(comparison, Comparison::Equal | Comparison::NotEqual)
+        {
+            return Err(PolicyError::UnorderedType);
+        }
+        self.next += 1;
+        let Some(Token::Word(value)) = self.peek().cloned() else {
+            return Err(self.unexpected("a value"));
+        };
+        let value = property.value(&value)?;
+        self.next += 1;
+        Ok(Expr::Compare(property, comparison, value))
+    }
+}
+
+/// Which offered tasks to take, set with `start --accept-policy`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptPolicy {
+    source: String,
+    expr: Expr,
+}
+
+impl AcceptPolicy {
+    /// Whether to take the offered `task`.
+    pub fn accepts(&self, task: &TaskDescriptor) -> bool {
+        self.expr.matches(task)
+    }
+
+    /// Hardest difficulty of the tasks the policy can accept, `None` if any difficulty may be
+    /// accepted. Fetching at most this difficulty keeps the orchestrator from offering tasks
+    /// that would only be declined for it.
+    pub fn max_difficulty(&self) -> Option<TaskDifficulty> {
+        let max = self.expr.max_difficulty(false)?;
+        crate::DIFFICULTY_LEVELS
+            .iter()
+            .rev()
+            .map(|(_, difficulty)| *difficulty)
+            .find(|difficulty| *difficulty as u64 <= max)
+    }
+}
+
+impl FromStr for AcceptPolicy {
+    type Err = PolicyError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            next: 0,
+            end: source.chars().count(),
+        };
+        let expr = parser.or()?;
+        if parser.peek().is_some() {
+            return Err(parser.unexpected("'&&', '||' or the end of the policy"));
+        }
+        Ok(Self {
+            source: source.trim().to_string(),
+            expr,
+        })
+    }
+}
+
+impl Display for AcceptPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(difficulty: TaskDifficulty, reward_weight: u64) -> TaskDescriptor {
+        TaskDescriptor {
+            offer_id: "offer".to_string(),
+            difficulty,
+            task_type: TaskType::ProofRequired,
+            input_size: 36,
+            expected_cycles: 1_500_000,
+            reward_weight,
+        }
+    }
+
+    fn accepts(policy: &str, task: &TaskDescriptor) -> bool {
+        policy.parse::<AcceptPolicy>().unwrap().accepts(task)
+    }
+
+    #[test]
+    fn test_accepts_matching_tasks() {
+        let medium = task(TaskDifficulty::Medium, 12);
+        let large = task(TaskDifficulty::Large, 12);
+        let policy = "difficulty<=medium && reward>=10";
+        assert!(accepts(policy, &medium));
+        assert!(!accepts(policy, &large));
+        assert!(!accepts(policy, &task(TaskDifficulty::Small, 5)));
+
+        // `&&` binds tighter than `||`
+        assert!(accepts(
+            "reward > 100 || difficulty == MEDIUM && size < 1k",
+            &medium
+        ));
+        assert!(!accepts(
+            "(reward > 100 || difficulty == medium) && size > 1k",
+            &medium
+        ));
+        assert!(accepts("!(difficulty >= large)", &medium));
+        assert!(accepts("cycles <= 1.5m && type != proof_hash", &medium));
+        assert!(!accepts("cycles < 1.5m", &medium));
+        assert_eq!(
+            policy.parse::<AcceptPolicy>().unwrap().to_string(),
+            "difficulty<=medium && reward>=10"
+        );
+    }
+
+    #[test]
+    fn test_max_difficulty() {
+        let max = |policy: &str| policy.parse::<AcceptPolicy>().unwrap().max_difficulty();
+        assert_eq!(
+            max("difficulty<=medium && reward>=10"),
+            Some(TaskDifficulty::Medium)
+        );
+        assert_eq!(
+            max("difficulty < medium"),
+            Some(TaskDifficulty::SmallMedium)
+        );
+        assert_eq!(max("!(difficulty >= large)"), Some(TaskDifficulty::Medium));
+        assert_eq!(
+            max("difficulty == small || difficulty <= large"),
+            Some(TaskDifficulty::Large)
+        );
+        assert_eq!(
+            max("!(difficulty > medium || reward < 10)"),
+            Some(TaskDifficulty::Medium)
+        );
+        assert_eq!(max("difficulty <= medium || reward >= 10"), None);
+        assert_eq!(max("difficulty >= medium"), None);
+        assert_eq!(max("reward >= 10"), None);
+    }
+
+    #[test]
+    fn test_rejects_invalid_policies() {
+        assert_eq!(
+            "speed > 1".parse::<AcceptPolicy>(),
+            Err(PolicyError::UnknownProperty("speed".to_string()))
+        );
+        assert_eq!(
+            "difficulty <= huge".parse::<AcceptPolicy>(),
+            Err(PolicyError::InvalidValue {
+                property: "difficulty",
+                value: "huge".to_string()
+            })
+        );
+        assert_eq!(
+            "type > proof_hash".parse::<AcceptPolicy>(),
+            Err(PolicyError::UnorderedType)
+        );
+        assert_eq!(
+            "reward >= 10 &&".parse::<AcceptPolicy>(),
+            Err(PolicyError::Unexpected {
+                expected: "a task property",
+                found: "end of policy".to_string(),
+                position: 15
+            })
+        );
+        assert_eq!(
+            "reward >= 10 reward".parse::<AcceptPolicy>(),
+            Err(PolicyError::Unexpected {
+                expected: "'&&', '||' or the end of the policy",
+                found: "reward".to_string(),
+                position: 13
+            })
+        );
+        assert!("(reward >= 10".parse::<AcceptPolicy>().is_err());
+        assert!("reward >= 10 ; rm".parse::<AcceptPolicy>().is_err());
+    }
+}
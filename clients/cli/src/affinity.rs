@@ -0,0 +1,196 @@
+//! CPU core pinning for prover workers
+//!
+//! Each concurrently running proof is assigned one core from a fixed set, and its prover
+//! subprocess pins itself to that core. On NUMA servers this keeps a proof's memory local to
+//! the core proving it.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Which cores prover workers are pinned to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorePinning {
+    /// One core per worker, in the order the OS lists them (`--pin-cores`)
+    Auto,
+    /// Cores from this list, in order (`--core-list 0-15`)
+    List(Vec<usize>),
+}
+
+/// Parse a core list such as `0-3,8,10-11`. Duplicates are dropped, keeping the first occurrence.
+pub fn parse_core_list(value: &str) -> Result<Vec<usize>, String> {
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<usize>()
+            .map_err(|_| format!("'{}' is not a core number", s.trim()))
+    };
+
+    let mut cores = Vec::new();
+    for part in value.split(',') {
+        let range = match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(format!("'{}' is not an ascending range", part.trim()));
+                }
+                start..=end
+            }
+            None => {
+                let core = parse(part)?;
+                core..=core
+            }
+        };
+        for core in range {
+            if !cores.contains(&core) {
+                cores.push(core);
+            }
+        }
+    }
+    Ok(cores)
+}
+
+/// Format cores compactly, e.g. `[0, 1, 2, 3, 8]` as `0-3,8`.
+pub fn format_core_list(cores: &[usize]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < cores.len() {
+        let start = cores[i];
+        let mut end = start;
+        while i + 1 < cores.len() && cores[i + 1] == end + 1 {
+            end += 1;
+            i += 1;
+        }
+        parts.push(if start == end {
+            start.to_string()
+        } else {
+            format!("{}-{}", start, end)
+        });
+        i += 1;
+    }
+    parts.join(",")
+}
+
+/// Core assigned to each of `num_workers` worker slots. Listed cores are reused in order if
+/// there are more workers than cores.
+pub fn assign_cores(pinning: &CorePinning, num_workers: usize) -> Result<Vec<usize>, String> {
+    let available: Vec<usize> = core_affinity::get_core_ids()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|core| core.id)
+        .collect();
+    if available.is_empty() {
+        return Err("CPU core pinning is not supported on this system".to_string());
+    }
+
+    let cores = match pinning {
+        CorePinning::Auto => available,
+        CorePinning::List(list) => {
+            if let Some(missing) = list.iter().find(|core| !available.contains(core)) {
+                return Err(format!(
+                    "Core {} does not exist (available: {})",
+                    missing,
+                    format_core_list(&available)
+                ));
+            }
+            list.clone()
+        }
+    };
+    if cores.is_empty() {
+        return Err("No cores to pin workers to".to_string());
+    }
+
+    Ok((0..num_workers).map(|i| cores[i % cores.len()]).collect())
+}
+
+/// Pin the calling thread (and threads it spawns later) to a core.
+/// Returns false if the OS refused.
+pub fn pin_current_thread(core: usize) -> bool {
+    core_affinity::set_for_current(core_affinity::CoreId { id: core })
+}
+
+/// Cores handed out to concurrently running proofs
+#[derive(Debug, Clone)]
+pub struct CorePool {
+    free: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl CorePool {
+    pub fn new(cores: &[usize]) -> Self {
+        Self {
+            free: Arc::new(Mutex::new(cores.iter().copied().collect())),
+        }
+    }
+
+    /// Take the next free core until the lease is dropped, or `None` if all are taken.
+    pub fn acquire(&self) -> Option<CoreLease> {
+        let core = self.free.lock().ok()?.pop_front()?;
+        Some(CoreLease {
+            core,
+            free: Arc::clone(&self.free),
+        })
+    }
+}
+
+/// A core taken from a [`CorePool`], returned to it on drop
+#[derive(Debug)]
+pub struct CoreLease {
+    core: usize,
+    free: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl CoreLease {
+    pub fn core(&self) -> usize {
+        self.core
+    }
+}
+
+impl Drop for CoreLease {
+    fn drop(&mut self) {
+        if let Ok(mut free) = self.free.lock() {
+            free.push_back(self.core);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_core_list() {
+        assert_eq!(parse_core_list("0-3").unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!(parse_core_list("0, 2,4-5").unwrap(), vec![0, 2, 4, 5]);
+        assert_eq!(parse_core_list("3,1-3").unwrap(), vec![3, 1, 2]);
+        assert!(parse_core_list("").is_err());
+        assert!(parse_core_list("4-2").is_err());
+        assert!(parse_core_list("a-b").is_err());
+    }
+
+    #[test]
+    fn test_format_core_list() {
+        assert_eq!(format_core_list(&[0, 1, 2, 3, 8, 10, 11]), "0-3,8,10-11");
+        assert_eq!(format_core_list(&[5]), "5");
+        assert_eq!(format_core_list(&[]), "");
+    }
+
+    #[test]
+    fn test_assign_cores_wraps_list() {
+        let Some(first) = core_affinity::get_core_ids().and_then(|ids| ids.first().copied()) else {
+            return; // Pinning unsupported here
+        };
+        let cores = assign_cores(&CorePinning::List(vec![first.id]), 3).unwrap();
+        assert_eq!(cores, vec![first.id; 3]);
+        assert!(assign_cores(&CorePinning::List(vec![usize::MAX]), 1).is_err());
+    }
+
+    #[test]
+    fn test_core_pool_returns_cores_on_drop() {
+        let pool = CorePool::new(&[4, 5]);
+        let a = pool.acquire().unwrap();
+        let b = pool.acquire().unwrap();
+        assert_eq!((a.core(), b.core()), (4, 5));
+        assert!(pool.acquire().is_none());
+
+        drop(a);
+        assert_eq!(pool.acquire().unwrap().core(), 4);
+    }
+}
@@ -9,10 +9,7 @@ use serde_json::{Value, json};
 use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
-use std::{
-    env,
-    time::{SystemTime, UNIX_EPOCH},
-};
+use std::{env, time::UNIX_EPOCH};
 
 #[derive(Debug, thiserror::Error)]
 pub enum TrackError {
@@ -81,7 +78,7 @@ pub async fn track(
     // https://developers.google.com/analytics/devguides/collection/protocol/ga4/reference?client_type=firebase#payload
     // https://developers.google.com/analytics/devguides/collection/protocol/ga4/reference?client_type=firebase#payload_query_parameters
 
-    let system_time = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    let system_time = crate::clock::now().duration_since(UNIX_EPOCH)?.as_millis();
     let timezone = iana_time_zone::get_timezone().ok().map_or_else(
         || String::from("UTC"), // fallback to UTC
         |tz| tz,
@@ -0,0 +1,378 @@
+//! Chaos testing mode
+//!
+//! Hidden developer mode (`nexus-cli start --chaos`) that randomly injects faults into
+//! the runtime to exercise its resilience paths:
+//!
+//! * dropped worker events
+//! * delayed proof submissions
+//! * malformed task inputs
+//! * jumps of the monotonic clock seen by request timers
+//! * stalled worker loops that ignore shutdown, off by default
+//!
+//! Faults are only meant for the in-process mock orchestrator or a replayed recording; the CLI
+//! refuses `--chaos` against a real one, where corrupted or delayed submissions would cost points.
+//!
+//! While enabled, a watchdog asserts that the worker loop keeps making progress and
+//! that every worker has exited once shutdown is requested. Violations terminate the
+//! process with a dedicated exit code so that CI can detect them.
+
+use crate::consts::cli_consts::chaos::{
+    CHAOS_DEADLOCK_EXIT_CODE, CHAOS_LEAKED_WORKER_EXIT_CODE, SHUTDOWN_GRACE_SECS,
+    WATCHDOG_INTERVAL_SECS,
+};
+use crate::task::Task;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+static CHAOS: OnceLock<ChaosConfig> = OnceLock::new();
+static LAST_HEARTBEAT: Mutex<Option<Instant>> = Mutex::new(None);
+static ACTIVE_WORKERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Kinds of faults that can be injected
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Fault {
+    DropEvent,
+    DelaySubmission,
+    MalformedInput,
+    ClockJump,
+    StallWorker,
+}
+
+/// Injection rates and limits for chaos mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaosConfig {
+    /// Probability that a worker event is silently dropped
+    pub drop_event_rate: f64,
+    /// Probability that a proof submission is delayed
+    pub delay_submission_rate: f64,
+    /// Probability that a fetched task has its inputs corrupted
+    pub malformed_input_rate: f64,
+    /// Probability that a request timer observes a clock jump
+    pub clock_jump_rate: f64,
+    /// Probability that a worker loop iteration hangs for good, ignoring shutdown
+    pub stall_worker_rate: f64,
+    /// Upper bound for injected submission delays
+    pub max_submission_delay: Duration,
+    /// Upper bound for injected clock jumps (in either direction)
+    pub max_clock_jump: Duration,
+    /// Worker loop inactivity after which a deadlock is reported
+    pub stall_timeout: Duration,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            drop_event_rate: 0.05,
+            delay_submission_rate: 0.2,
+            malformed_input_rate: 0.1,
+            clock_jump_rate: 0.1,
+            stall_worker_rate: 0.0,
+            max_submission_delay: Duration::from_secs(30),
+            max_clock_jump: Duration::from_secs(300),
+            stall_timeout: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+impl FromStr for ChaosConfig {
+    type Err = String;
+
+    /// Parse a comma-separated list of `key=value` overrides, e.g.
+    /// `drop_events=0.5,clock_jumps=0`. The literal `default` keeps all defaults.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = ChaosConfig::default();
+        let s = s.trim();
+        if s.is_empty() || s.eq_ignore_ascii_case("default") {
+            return Ok(config);
+        }
+
+        for pair in s.split(',') {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=value, got '{}'", pair))?;
+            let key = key.trim();
+            let value = value.trim();
+            match key {
+                "drop_events" => config.drop_event_rate = parse_rate(key, value)?,
+                "delay_submissions" => config.delay_submission_rate = parse_rate(key, value)?,
+                "malformed_inputs" => config.malformed_input_rate = parse_rate(key, value)?,
+                "clock_jumps" => config.clock_jump_rate = parse_rate(key, value)?,
+                "stall_workers" => config.stall_worker_rate = parse_rate(key, value)?,
+                "max_delay_secs" => config.max_submission_delay = parse_secs(key, value)?,
+                "max_jump_secs" => config.max_clock_jump = parse_secs(key, value)?,
+                "stall_timeout_secs" => config.stall_timeout = parse_secs(key, value)?,
+                _ => return Err(format!("Unknown chaos option '{}'", key)),
+            }
+        }
+        Ok(config)
+    }
+}
+
+pub(crate) fn parse_rate(key: &str, value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(rate) if (0.0..=1.0).contains(&rate) => Ok(rate),
+        _ => Err(format!("{} must be a number between 0 and 1", key)),
+    }
+}
+
+pub(crate) fn parse_secs(key: &str, value: &str) -> Result<Duration, String> {
+    value
+        .parse::<u64>()
+        .map(Duration::from_secs)
+        .map_err(|_| format!("{} must be a whole number of seconds", key))
+}
+
+impl ChaosConfig {
+    /// Injection probability for a fault kind
+    pub fn rate(&self, fault: Fault) -> f64 {
+        match fault {
+            Fault::DropEvent => self.drop_event_rate,
+            Fault::DelaySubmission => self.delay_submission_rate,
+            Fault::MalformedInput => self.malformed_input_rate,
+            Fault::ClockJump => self.clock_jump_rate,
+            Fault::StallWorker => self.stall_worker_rate,
+        }
+    }
+
+    /// Roll the dice for a fault kind
+    pub fn should_inject(&self, fault: Fault) -> bool {
+        let rate = self.rate(fault);
+        rate > 0.0 && rand::random::<f64>() < rate
+    }
+}
+
+/// Enable chaos mode for the rest of the process lifetime.
+pub fn enable(config: ChaosConfig) {
+    let _ = CHAOS.set(config);
+    heartbeat();
+}
+
+/// Whether chaos mode is enabled
+pub fn is_enabled() -> bool {
+    CHAOS.get().is_some()
+}
+
+/// Roll the dice for a fault kind. Always false unless chaos mode is enabled.
+pub fn inject(fault: Fault) -> bool {
+    CHAOS
+        .get()
+        .is_some_and(|config| config.should_inject(fault))
+}
+
+/// Possibly sleep before a proof submission.
+pub async fn maybe_delay_submission() {
+    let Some(config) = CHAOS.get() else {
+        return;
+    };
+    if config.should_inject(Fault::DelaySubmission) {
+        let max_ms = config.max_submission_delay.as_millis() as u64;
+        let delay = Duration::from_millis(rand::random::<u64>() % max_ms.max(1));
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Possibly hang the calling worker loop forever, so that the watchdog has a deadlock to detect.
+pub async fn maybe_stall_worker() {
+    if inject(Fault::StallWorker) {
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Possibly corrupt the inputs of a freshly fetched task.
+/// Returns true if the task was modified.
+pub fn maybe_corrupt_task(task: &mut Task) -> bool {
+    if !inject(Fault::MalformedInput) {
+        return false;
+    }
+    corrupt_task(task);
+    true
+}
+
+/// Truncate every input below the minimum size the input parser accepts.
+fn corrupt_task(task: &mut Task) {
+    for input in task.public_inputs_list.iter_mut() {
+        input.truncate(input.len() / 2);
+    }
+    task.public_inputs.truncate(task.public_inputs.len() / 2);
+}
+
+/// Current instant, possibly shifted forwards or backwards to simulate a clock jump.
+pub fn now() -> Instant {
+    let now = Instant::now();
+    let Some(config) = CHAOS.get() else {
+        return now;
+    };
+    if !config.should_inject(Fault::ClockJump) {
+        return now;
+    }
+    let max_ms = config.max_clock_jump.as_millis() as u64;
+    let jump = Duration::from_millis(rand::random::<u64>() % max_ms.max(1));
+    let shifted = if rand::random::<bool>() {
+        now.checked_add(jump)
+    } else {
+        now.checked_sub(jump)
+    };
+    shifted.unwrap_or(now)
+}
+
+/// Record that the worker loop made progress.
+pub fn heartbeat() {
+    if let Ok(mut last) = LAST_HEARTBEAT.lock() {
+        *last = Some(Instant::now());
+    }
+}
+
+fn since_last_heartbeat() -> Duration {
+    LAST_HEARTBEAT
+        .lock()
+        .ok()
+        .and_then(|last| last.map(|t| t.elapsed()))
+        .unwrap_or_default()
+}
+
+/// Counts a running worker loop for the lifetime of the guard.
+pub struct WorkerGuard;
+
+impl WorkerGuard {
+    pub fn acquire() -> Self {
+        ACTIVE_WORKERS.fetch_add(1, Ordering::SeqCst);
+        WorkerGuard
+    }
+}
+
+impl Drop for WorkerGuard {
+    fn drop(&mut self) {
+        ACTIVE_WORKERS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Number of worker loops currently running
+pub fn active_workers() -> usize {
+    ACTIVE_WORKERS.load(Ordering::SeqCst)
+}
+
+/// Spawn the deadlock watchdog. No-op unless chaos mode is enabled.
+///
+/// While running, the process is terminated if the worker loop stops sending
+/// heartbeats for longer than the stall timeout. Once shutdown is requested, the
+/// process is terminated if it has not exited within the shutdown grace period.
+pub fn spawn_watchdog(mut shutdown: broadcast::Receiver<()>) {
+    let Some(config) = CHAOS.get() else {
+        return;
+    };
+    let stall_timeout = config.stall_timeout;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(WATCHDOG_INTERVAL_SECS));
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = interval.tick() => {
+                    let stalled_for = since_last_heartbeat();
+                    if stalled_for > stall_timeout {
+                        eprintln!(
+                            "[CHAOS] Deadlock detected: worker loop made no progress for {}s",
+                            stalled_for.as_secs()
+                        );
+                        std::process::exit(CHAOS_DEADLOCK_EXIT_CODE);
+                    }
+                }
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(SHUTDOWN_GRACE_SECS)).await;
+        eprintln!(
+            "[CHAOS] Shutdown did not complete within {}s ({} workers still running)",
+            SHUTDOWN_GRACE_SECS,
+            active_workers()
+        );
+        std::process::exit(CHAOS_LEAKED_WORKER_EXIT_CODE);
+    });
+}
+
+/// Assert that every worker exited after shutdown. No-op unless chaos mode is enabled.
+pub fn check_workers_exited() {
+    if !is_enabled() {
+        return;
+    }
+    let leaked = active_workers();
+    if leaked > 0 {
+        eprintln!("[CHAOS] {} workers still running after shutdown", leaked);
+        std::process::exit(CHAOS_LEAKED_WORKER_EXIT_CODE);
+    }
+    eprintln!("[CHAOS] All workers exited cleanly");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+    use crate::prover::input::InputParser;
+
+    #[test]
+    fn test_parse_default() {
+        assert_eq!(
+            "default".parse::<ChaosConfig>().unwrap(),
+            ChaosConfig::default()
+        );
+        assert_eq!("".parse::<ChaosConfig>().unwrap(), ChaosConfig::default());
+    }
+
+    #[test]
+    fn test_parse_overrides() {
+        let config: ChaosConfig =
+            "drop_events=0.5, clock_jumps=0, stall_workers=1, stall_timeout_secs=120"
+                .parse()
+                .unwrap();
+        assert_eq!(config.drop_event_rate, 0.5);
+        assert_eq!(config.clock_jump_rate, 0.0);
+        assert_eq!(config.stall_worker_rate, 1.0);
+        assert_eq!(config.stall_timeout, Duration::from_secs(120));
+        assert_eq!(
+            config.delay_submission_rate,
+            ChaosConfig::default().delay_submission_rate
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid() {
+        assert!("drop_events=2".parse::<ChaosConfig>().is_err());
+        assert!("drop_events".parse::<ChaosConfig>().is_err());
+        assert!("unknown=0.1".parse::<ChaosConfig>().is_err());
+    }
+
+    #[test]
+    fn test_rates_are_respected_at_extremes() {
+        let never: ChaosConfig = "drop_events=0".parse().unwrap();
+        let always: ChaosConfig = "drop_events=1".parse().unwrap();
+        for _ in 0..100 {
+            assert!(!never.should_inject(Fault::DropEvent));
+            assert!(always.should_inject(Fault::DropEvent));
+        }
+    }
+
+    #[test]
+    fn test_corrupted_task_is_rejected_by_parser() {
+        let mut task = Task::new(
+            "task".to_string(),
+            "fib_input_initial".to_string(),
+            vec![1; 12],
+            TaskType::ProofHash,
+            TaskDifficulty::Small,
+        );
+        corrupt_task(&mut task);
+        assert!(InputParser::parse_triple_input(&task.all_inputs()[0]).is_err());
+    }
+
+    #[test]
+    fn test_worker_guard_counts_active_workers() {
+        let before = active_workers();
+        let guard = WorkerGuard::acquire();
+        assert!(active_workers() > before);
+        drop(guard);
+        assert!(active_workers() <= before);
+    }
+}
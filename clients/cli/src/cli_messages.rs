@@ -1,7 +1,8 @@
 //! CLI command messaging system
 //!
 //! This module provides consistent messaging for CLI commands like registration,
-//! logout, and other command-line operations.
+//! logout, and other command-line operations. Callers pass localized titles and details, see
+//! [`crate::i18n`]; the `[INFO]`-style tags stay in English so that logs remain greppable.
 
 /// Print CLI command info message (for registration, logout, etc.)
 pub fn print_info(title: &str, details: &str) {
@@ -27,7 +28,10 @@ pub fn print_warn(title: &str, details: &str) {
 pub fn print_error(title: &str, details: Option<&str>) {
     println!("\x1b[1;31m[ERROR]\x1b[0m {}", title);
     if let Some(details) = details {
-        println!("\x1b[1;31m[ERROR]\x1b[0m Details: {}", details);
+        println!(
+            "\x1b[1;31m[ERROR]\x1b[0m {}",
+            crate::tr!("cli-error-details", details = details)
+        );
     }
 }
 
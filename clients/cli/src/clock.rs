@@ -0,0 +1,111 @@
+//! Clock skew detection
+//!
+//! The orchestrator validates signed submissions against its own clock, so a node whose clock is
+//! off gets its submissions rejected. Every orchestrator response carries a `Date` header;
+//! comparing it with the local clock gives the skew of the local clock, NTP style. Once the skew
+//! exceeds a tolerance, it is applied as an offset to the timestamps the CLI reports, and the
+//! worker warns about it once.
+//!
+//! The `Date` header only has a resolution of one second and is taken before the response travels
+//! back, so skews within the tolerance are treated as noise.
+
+use crate::consts::cli_consts::CLOCK_SKEW_TOLERANCE_SECS;
+use chrono::{DateTime, TimeDelta, Utc};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// Offset from the local clock to the orchestrator clock, in milliseconds
+static OFFSET_MS: AtomicI64 = AtomicI64::new(0);
+/// Whether the current offset was already reported by [`take_skew_warning`]
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Orchestrator time minus local time, in milliseconds, from an HTTP `Date` header value.
+fn skew_from_date(date: &str, local: SystemTime) -> Option<i64> {
+    let server = DateTime::parse_from_rfc2822(date).ok()?.with_timezone(&Utc);
+    let local = DateTime::<Utc>::from(local);
+    Some((server - local).num_milliseconds())
+}
+
+/// Offset to apply for a measured skew: none within the tolerance, the skew itself beyond it.
+fn offset_for_skew(skew_ms: i64) -> i64 {
+    if skew_ms.unsigned_abs() > CLOCK_SKEW_TOLERANCE_SECS * 1000 {
+        skew_ms
+    } else {
+        0
+    }
+}
+
+/// Update the clock offset from the `Date` header of an orchestrator response.
+pub fn observe_date_header(date: &str) {
+    let Some(skew_ms) = skew_from_date(date, SystemTime::now()) else {
+        return;
+    };
+    let offset = offset_for_skew(skew_ms);
+    if offset == 0 {
+        // Back in sync; warn again should the clock drift off later
+        WARNED.store(false, Ordering::Relaxed);
+    }
+    OFFSET_MS.store(offset, Ordering::Relaxed);
+}
+
+/// Offset currently applied to local timestamps, in milliseconds
+pub fn offset_ms() -> i64 {
+    OFFSET_MS.load(Ordering::Relaxed)
+}
+
+/// Current time, corrected by the clock offset
+pub fn now() -> SystemTime {
+    let offset = offset_ms();
+    let delta = Duration::from_millis(offset.unsigned_abs());
+    let now = SystemTime::now();
+    let corrected = if offset >= 0 {
+        now.checked_add(delta)
+    } else {
+        now.checked_sub(delta)
+    };
+    corrected.unwrap_or(now)
+}
+
+/// Current UTC time, corrected by the clock offset
+pub fn utc_now() -> DateTime<Utc> {
+    Utc::now() + TimeDelta::milliseconds(offset_ms())
+}
+
+/// The clock offset in seconds, the first time it is seen beyond the tolerance.
+pub fn take_skew_warning() -> Option<i64> {
+    let offset = offset_ms();
+    (offset != 0 && !WARNED.swap(true, Ordering::Relaxed)).then_some(offset / 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skew_from_date_header() {
+        let local = SystemTime::from(
+            DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+        assert_eq!(
+            skew_from_date("Sun, 01 Jun 2025 12:01:30 GMT", local),
+            Some(90_000)
+        );
+        assert_eq!(
+            skew_from_date("Sun, 01 Jun 2025 11:59:59 GMT", local),
+            Some(-1_000)
+        );
+        assert_eq!(skew_from_date("yesterday", local), None);
+    }
+
+    #[test]
+    fn test_small_skews_are_ignored() {
+        let tolerance_ms = CLOCK_SKEW_TOLERANCE_SECS as i64 * 1000;
+        assert_eq!(offset_for_skew(0), 0);
+        assert_eq!(offset_for_skew(tolerance_ms), 0);
+        assert_eq!(offset_for_skew(-tolerance_ms), 0);
+        assert_eq!(offset_for_skew(tolerance_ms + 1), tolerance_ms + 1);
+        assert_eq!(offset_for_skew(-90_000), -90_000);
+    }
+}
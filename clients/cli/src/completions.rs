@@ -0,0 +1,182 @@
+//! Shell completions and man pages
+//!
+//! `completions <SHELL>` prints a script that registers the CLI with the shell's completion
+//! system. The script asks the CLI itself for completions (`COMPLETE=<SHELL> nexus-cli ...`, see
+//! [`clap_complete::CompleteEnv`]), so values kept in the local config are completed too: node
+//! IDs, environments, themes and wallet aliases. `man` renders man pages from the same command
+//! definitions.
+
+use crate::config::{Config, get_config_path};
+use crate::environment::CUSTOM_ENVIRONMENT;
+use crate::nodes::{NodeManifest, nodes_manifest_path};
+use crate::ui::theme;
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::{Bash, EnvCompleter, Fish, Powershell, Zsh};
+use std::ffi::OsStr;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Environment variable through which the shell asks for completions
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Name the CLI is installed under, if it cannot be told from how it was invoked
+const DEFAULT_BIN_NAME: &str = "nexus-cli";
+
+/// Shell to print the completion script for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+impl Shell {
+    fn completer(self) -> &'static dyn EnvCompleter {
+        match self {
+            Shell::Bash => &Bash,
+            Shell::Zsh => &Zsh,
+            Shell::Fish => &Fish,
+            Shell::Powershell => &Powershell,
+        }
+    }
+}
+
+/// Name the CLI was invoked as, e.g. `nexus-cli`, to register completions and name man pages for.
+pub fn bin_name() -> String {
+    std::env::args_os()
+        .next()
+        .map(PathBuf::from)
+        .and_then(|path| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| DEFAULT_BIN_NAME.to_string())
+}
+
+/// Write the script registering `bin` with the completion system of `shell` to `out`.
+pub fn write_registration(shell: Shell, bin: &str, out: &mut dyn Write) -> io::Result<()> {
+    shell
+        .completer()
+        .write_registration(COMPLETE_VAR, bin, bin, bin, out)
+}
+
+/// Render the man page of `command` to `out`, or with `dir`, one page per subcommand into `dir`.
+pub fn write_man_pages(
+    command: clap::Command,
+    dir: Option<&Path>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    match dir {
+        Some(dir) => {
+            std::fs::create_dir_all(dir)?;
+            clap_mangen::generate_to(command, dir)
+        }
+        None => clap_mangen::Man::new(command).render(out),
+    }
+}
+
+/// Saved config, if there is one; completion must not fail or prompt.
+fn saved_config() -> Option<Config> {
+    Config::load_from_file(&get_config_path().ok()?).ok()
+}
+
+/// Candidates starting with `current`, without duplicates.
+fn matching(
+    current: &OsStr,
+    candidates: impl IntoIterator<Item = (String, Option<String>)>,
+) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    let mut seen = Vec::new();
+    let mut matches = Vec::new();
+    for (value, help) in candidates {
+        if !value.starts_with(current.as_ref()) || seen.contains(&value) {
+            continue;
+        }
+        seen.push(value.clone());
+        matches.push(CompletionCandidate::new(value).help(help.map(Into::into)));
+    }
+    matches
+}
+
+/// Node IDs from the config file and the nodes manifest, for `--node-id`.
+pub fn complete_node_ids(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Ok(config_path) = get_config_path() else {
+        return Vec::new();
+    };
+    let mut node_ids = Vec::new();
+    if let Ok(config) = Config::load_from_file(&config_path) {
+        if !config.node_id.is_empty() {
+            node_ids.push((config.node_id, Some("config".to_string())));
+        }
+    }
+    if let Ok(manifest) = NodeManifest::load_or_default(&nodes_manifest_path(&config_path)) {
+        node_ids.extend(
+            manifest
+                .nodes
+                .into_iter()
+                .map(|node| (node.node_id, Some(node.environment))),
+        );
+    }
+    matching(current, node_ids)
+}
+
+/// Built-in environments and those defined in the config file, for `--env`.
+pub fn complete_environments(current: &OsStr) -> Vec<CompletionCandidate> {
+    let mut environments = vec![
+        ("production".to_string(), None),
+        (CUSTOM_ENVIRONMENT.to_string(), None),
+    ];
+    if let Some(config) = saved_config() {
+        environments.extend(
+            config
+                .environments
+                .into_iter()
+                .map(|(name, definition)| (name, Some(definition.orchestrator_url))),
+        );
+    }
+    matching(current, environments)
+}
+
+/// Built-in themes and those in `~/.nexus/themes`, for `theme set` and `theme preview`.
+pub fn complete_themes(current: &OsStr) -> Vec<CompletionCandidate> {
+    let themes = match get_config_path() {
+        Ok(config_path) => theme::available_themes(&theme::themes_dir(&config_path)),
+        Err(_) => theme::builtin_themes(),
+    };
+    matching(current, themes.into_iter().map(|theme| (theme.name, None)))
+}
+
+/// Wallet aliases from the address book, for `wallet use` and `--wallet-address`.
+pub fn complete_wallets(current: &OsStr) -> Vec<CompletionCandidate> {
+    let wallets = saved_config()
+        .map(|config| config.wallets)
+        .unwrap_or_default();
+    matching(
+        current,
+        wallets
+            .into_iter()
+            .map(|(alias, address)| (alias, Some(address))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching() {
+        let candidates = vec![
+            ("12345".to_string(), Some("config".to_string())),
+            ("12399".to_string(), None),
+            ("12345".to_string(), None),
+            ("67890".to_string(), None),
+        ];
+        let values: Vec<String> = matching(OsStr::new("123"), candidates)
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(values, vec!["12345", "12399"]);
+    }
+}
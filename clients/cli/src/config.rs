@@ -1,21 +1,139 @@
 //! Application configuration.
 
 use crate::cli_messages::{print_error, print_info, print_success};
-use crate::environment::Environment;
+use crate::config_crypto;
+use crate::config_migration::{self, ConfigVersion};
+use crate::config_validation;
+use crate::environment::{Environment, EnvironmentDefinition};
+use crate::exit_code::UsageError;
+use crate::labels::Labels;
+use crate::notifications::NotificationSettings;
 use crate::orchestrator::Orchestrator;
+use crate::orchestrator::failover::OrchestratorSettings;
+use crate::storage::StorageSettings;
+use crate::task_cache::TaskCacheSettings;
+use crate::tr;
+use crate::ui::DashboardLayout;
+use crate::version::checker::UpdateSettings;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// Print a setting from the config file, for `nexus-cli config get`.
+pub fn print_setting(config_path: &Path, key: &str) -> Result<(), Box<dyn Error>> {
+    let config = Config::load_or_default(config_path)?;
+    let value = config
+        .get_setting(key)
+        .map_err(|e| UsageError(e.to_string()))?;
+    println!("{}", value);
+    Ok(())
+}
 
-/// Get the path to the Nexus config file, typically located at ~/.nexus/config.json.
-pub fn get_config_path() -> Result<PathBuf, std::io::Error> {
+/// Change a setting in the config file, for `nexus-cli config set`.
+pub fn save_setting(config_path: &Path, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    let mut config = Config::load_or_default(config_path)?;
+    config
+        .set_setting(key, value)
+        .map_err(|e| UsageError(e.to_string()))?;
+    config.save(config_path).map_err(ConfigError::from)?;
+    print_success(&tr!("config-set"), &format!("{} = {}", key, value.trim()));
+    Ok(())
+}
+
+/// Directory chosen by the Windows service, which runs under an account with its own home
+static NEXUS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `dir` instead of `~/.nexus` for the config file and the files kept next to it.
+pub fn set_nexus_dir(dir: PathBuf) {
+    let _ = NEXUS_DIR.set(dir);
+}
+
+/// Directory of the config file and the files kept next to it: the one set with
+/// [`set_nexus_dir`], else `NEXUS_HOME`, else `.nexus` in the home directory (`%USERPROFILE%` on
+/// Windows).
+pub fn nexus_dir() -> Result<PathBuf, std::io::Error> {
+    if let Some(dir) = NEXUS_DIR.get() {
+        return Ok(dir.clone());
+    }
+    if let Some(dir) = std::env::var_os("NEXUS_HOME").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
     let home_path = home::home_dir().ok_or(std::io::Error::new(
         std::io::ErrorKind::NotFound,
         "Home directory not found",
     ))?;
-    let config_path = home_path.join(".nexus").join("config.json");
-    Ok(config_path)
+    Ok(home_path.join(".nexus"))
+}
+
+/// Get the path to the Nexus config file, typically located at ~/.nexus/config.json.
+pub fn get_config_path() -> Result<PathBuf, std::io::Error> {
+    Ok(nexus_dir()?.join("config.json"))
+}
+
+/// Why the config file cannot be used to start proving
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("{}", tr!("config-error-missing"))]
+    Missing,
+
+    #[error("{}: {}", tr!("config-error-unreadable"), .0)]
+    Unreadable(#[source] std::io::Error),
+
+    #[error("{}", tr!("config-error-user-not-registered"))]
+    UserNotRegistered,
+
+    #[error("{}", tr!("config-error-node-not-registered"))]
+    NodeNotRegistered,
+
+    #[error("{}", tr!("config-error-invalid-node-id"))]
+    InvalidNodeId,
+
+    #[error("{}", tr!("config-error-invalid", errors = .0))]
+    Invalid(usize),
+}
+
+/// Keys accepted by `nexus-cli config get` and `nexus-cli config set`
+pub const SETTING_KEYS: [&str; 4] = [
+    "update.channel",
+    "task_cache.ttl_secs",
+    "task_cache.max_entries",
+    "storage.min_free_mb",
+];
+
+/// Why a setting cannot be read or changed
+#[derive(Debug, Error)]
+pub enum SettingError {
+    #[error("{}", unknown_key_message(.0))]
+    UnknownKey(String),
+
+    #[error("{}", invalid_value_message(.key, .reason))]
+    InvalidValue { key: String, reason: String },
+}
+
+fn unknown_key_message(key: &str) -> String {
+    tr!(
+        "config-unknown-key",
+        key = key,
+        keys = SETTING_KEYS.join(", ")
+    )
+}
+
+fn invalid_value_message(key: &str, reason: &str) -> String {
+    tr!("config-invalid-value", key = key, reason = reason)
+}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => ConfigError::Missing,
+            _ => ConfigError::Unreadable(error),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
@@ -35,6 +153,60 @@ pub struct Config {
     /// Node ID, resolved to a valid u64 during `Config::resolve`
     #[serde(default)]
     pub node_id: String,
+
+    /// Opt-in notification settings
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+
+    /// Address book of wallet addresses, by alias
+    #[serde(default)]
+    pub wallets: BTreeMap<String, String>,
+
+    /// Order and visibility of the dashboard panels
+    #[serde(default)]
+    pub dashboard: DashboardLayout,
+
+    /// Dashboard color theme, by name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+
+    /// Time to live and size of the submitted task cache
+    #[serde(default)]
+    pub task_cache: TaskCacheSettings,
+
+    /// Release channel for update checks and `nexus-cli update`
+    #[serde(default)]
+    pub update: UpdateSettings,
+
+    /// Node labels, set with `nexus-cli start --label key=value`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: Labels,
+
+    /// Disk quotas for the directories next to the config file, and the low disk warning
+    #[serde(default)]
+    pub storage: StorageSettings,
+
+    /// Fallback orchestrator URLs by environment
+    #[serde(default)]
+    pub orchestrator: OrchestratorSettings,
+
+    /// Self-hosted orchestrators, selected by name with `--env`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub environments: BTreeMap<String, EnvironmentDefinition>,
+
+    /// Highest proof submission nonce reserved by earlier versions, by node ID, see
+    /// [`crate::orchestrator::nonce`]. Still read, but reservations are now saved in a file of
+    /// their own
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub submission_nonces: BTreeMap<String, u64>,
+
+    /// Schema version of the file, see [`crate::config_migration`]
+    #[serde(default)]
+    pub version: ConfigVersion,
+
+    /// Settings this version does not know, e.g. written by a newer CLI; saved back unchanged
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 impl Config {
@@ -50,17 +222,58 @@ impl Config {
             wallet_address,
             node_id,
             environment: environment.to_string(),
+            notifications: NotificationSettings::default(),
+            wallets: BTreeMap::new(),
+            dashboard: DashboardLayout::default(),
+            theme: None,
+            task_cache: TaskCacheSettings::default(),
+            update: UpdateSettings::default(),
+            labels: Labels::new(),
+            storage: StorageSettings::default(),
+            orchestrator: OrchestratorSettings::default(),
+            environments: BTreeMap::new(),
+            submission_nonces: BTreeMap::new(),
+            version: ConfigVersion::default(),
+            extra: Map::new(),
         }
     }
 
     /// Loads configuration from a JSON file at the given path.
+    ///
+    /// A file written by an older CLI is migrated to the current schema first, see
+    /// [`crate::config_migration`]. If the file does not parse, the error lists every problem
+    /// found by [`crate::config_validation::validate`] rather than only the first.
     pub fn load_from_file(path: &Path) -> Result<Self, std::io::Error> {
-        let buf = fs::read(path)?;
-        let config: Config = serde_json::from_slice(&buf)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let buf = config_crypto::read_config(path)?;
+        let invalid = |e: serde_json::Error| {
+            let diagnostics = config_validation::validate(&buf);
+            let message = if config_validation::has_errors(&diagnostics) {
+                config_validation::summarize(path, &diagnostics)
+            } else {
+                format!("{}: {}", path.display(), e)
+            };
+            std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+        };
+        let mut value: serde_json::Value = serde_json::from_slice(&buf).map_err(invalid)?;
+        let migrated_from = config_migration::migrate(&mut value);
+        let config: Config = serde_json::from_value(value.clone()).map_err(invalid)?;
+        if let Some(from) = migrated_from {
+            // The migrated config is usable even if it cannot be saved; it is migrated again on
+            // the next load
+            let _ = config_migration::save_migrated(path, from, &value);
+        }
         Ok(config)
     }
 
+    /// Loads the config file, or an empty config if there is none yet.
+    pub fn load_or_default(path: &Path) -> Result<Self, ConfigError> {
+        match Config::load_from_file(path) {
+            Ok(config) => Ok(config),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(ConfigError::from(e)),
+        }
+    }
+
     /// Saves the configuration to a JSON file at the given path.
     pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
         if let Some(parent) = path.parent() {
@@ -72,7 +285,41 @@ impl Config {
                 format!("Serialization failed: {}", e),
             )
         })?;
-        fs::write(path, json)?;
+        config_crypto::write_config(path, json.as_bytes())?;
+        Ok(())
+    }
+
+    /// Value of a setting, by its dotted key, e.g. `update.channel`.
+    pub fn get_setting(&self, key: &str) -> Result<String, SettingError> {
+        match key {
+            "update.channel" => Ok(self.update.channel.to_string()),
+            "task_cache.ttl_secs" => Ok(self.task_cache.ttl_secs.to_string()),
+            "task_cache.max_entries" => Ok(self.task_cache.max_entries.to_string()),
+            "storage.min_free_mb" => Ok(self.storage.min_free_mb.to_string()),
+            _ => Err(SettingError::UnknownKey(key.to_string())),
+        }
+    }
+
+    /// Change a setting, by its dotted key, e.g. `update.channel`.
+    pub fn set_setting(&mut self, key: &str, value: &str) -> Result<(), SettingError> {
+        let invalid = |reason: String| SettingError::InvalidValue {
+            key: key.to_string(),
+            reason,
+        };
+        match key {
+            "update.channel" => self.update.channel = value.parse().map_err(invalid)?,
+            "task_cache.ttl_secs" => {
+                self.task_cache.ttl_secs = value.parse().map_err(|e| invalid(format!("{}", e)))?
+            }
+            "task_cache.max_entries" => {
+                self.task_cache.max_entries =
+                    value.parse().map_err(|e| invalid(format!("{}", e)))?
+            }
+            "storage.min_free_mb" => {
+                self.storage.min_free_mb = value.parse().map_err(|e| invalid(format!("{}", e)))?
+            }
+            _ => return Err(SettingError::UnknownKey(key.to_string())),
+        }
         Ok(())
     }
 
@@ -99,17 +346,38 @@ impl Config {
     ) -> Result<Self, Box<dyn Error>> {
         // Special case: if --node-id is provided, allow running without config file
         if let Some(node_id) = node_id_arg {
-            print_success("Using provided Node ID", &format!("Node ID: {}", node_id));
+            print_success(
+                &tr!("config-using-node-id"),
+                &tr!("register-node-id", node_id = node_id),
+            );
 
             // Get the wallet address for analytics
             let wallet_address = orchestrator.get_node(&node_id.to_string()).await?;
 
+            // Notification preferences, the dashboard layout, the task cache, update, storage and
+            // orchestrator settings, the labels, the defined environments, the submission nonces
+            // and the settings of a newer CLI still apply when a config file exists
+            let existing = Config::load_from_file(config_path).unwrap_or_default();
+
             // Create a minimal config with the provided node_id
             let config = Config {
                 user_id: "anonymous".to_string(), // Use anonymous for --node-id shortcut
                 wallet_address,
                 node_id: node_id.to_string(),
                 environment: "".to_string(),
+                notifications: existing.notifications,
+                wallets: BTreeMap::new(),
+                dashboard: existing.dashboard,
+                theme: existing.theme,
+                task_cache: existing.task_cache,
+                update: existing.update,
+                labels: existing.labels,
+                storage: existing.storage,
+                orchestrator: existing.orchestrator,
+                environments: existing.environments,
+                submission_nonces: existing.submission_nonces,
+                version: existing.version,
+                extra: existing.extra,
             };
 
             return Ok(config);
@@ -117,32 +385,29 @@ impl Config {
 
         // For normal operation without --node-id, config file is required
         if !config_path.exists() {
-            print_info(
-                "Welcome to Nexus CLI!",
-                "Please register your wallet address to get started: nexus-cli register-user --wallet-address <your-wallet-address>",
-            );
-            return Err("Configuration file not found. Please register first.".into());
+            print_info(&tr!("config-welcome"), &tr!("config-welcome-hint"));
+            return Err(ConfigError::Missing.into());
         }
 
         // Load the config file
-        let mut config = Config::load_from_file(config_path)?;
+        let mut config = Config::load_from_file(config_path).map_err(ConfigError::from)?;
 
         // Resolve node_id from config file
         let resolved_node_id = match config.resolve_node_id_from_config() {
             Ok(id_from_config) => {
                 print_success(
-                    "Found Node ID from config file",
-                    &format!("Node ID: {}", id_from_config),
+                    &tr!("config-found-node-id"),
+                    &tr!("register-node-id", node_id = id_from_config),
                 );
                 id_from_config
             }
             Err(e) => {
                 // The config is present but incomplete or invalid
                 print_error(
-                    "Your configuration is incomplete or invalid.",
-                    Some("Please register your node. Start with: nexus-cli register-node"),
+                    &tr!("config-incomplete"),
+                    Some(&tr!("config-incomplete-hint")),
                 );
-                return Err(e);
+                return Err(e.into());
             }
         };
 
@@ -157,32 +422,24 @@ impl Config {
     }
 
     /// Resolves node ID from the configuration file content
-    fn resolve_node_id_from_config(&self) -> Result<u64, Box<dyn Error>> {
+    fn resolve_node_id_from_config(&self) -> Result<u64, ConfigError> {
         if self.user_id.is_empty() {
-            return Err("User not registered in config file.".into());
+            return Err(ConfigError::UserNotRegistered);
         }
 
         if self.node_id.is_empty() {
-            print_error(
-                "User registered, but no node found",
-                Some("Please register a node to continue: nexus-cli register-node"),
-            );
-            return Err(
-                "Node registration required. Please run 'nexus-cli register-node' first.".into(),
-            );
+            print_error(&tr!("config-no-node"), Some(&tr!("config-no-node-hint")));
+            return Err(ConfigError::NodeNotRegistered);
         }
 
         match self.node_id.parse::<u64>() {
             Ok(id) => Ok(id),
             Err(_) => {
                 print_error(
-                    "Invalid node ID in config file",
-                    Some("Please register a new node: nexus-cli register-node"),
+                    &tr!("config-invalid-node-id"),
+                    Some(&tr!("config-invalid-node-id-hint")),
                 );
-                Err(
-                    "Invalid node ID in config. Please run 'nexus-cli register-node' to fix this."
-                        .into(),
-                )
+                Err(ConfigError::InvalidNodeId)
             }
         }
     }
@@ -191,6 +448,7 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::version::checker::UpdateChannel;
     use std::fs::File;
     use std::io::Write;
     use tempfile::tempdir;
@@ -202,6 +460,19 @@ mod tests {
             user_id: "test_user_id".to_string(),
             wallet_address: "0x1234567890abcdef1234567890abcdef12345678".to_string(),
             node_id: "test_node_id".to_string(),
+            notifications: NotificationSettings::default(),
+            wallets: BTreeMap::new(),
+            dashboard: DashboardLayout::default(),
+            theme: None,
+            task_cache: TaskCacheSettings::default(),
+            update: UpdateSettings::default(),
+            labels: Labels::new(),
+            storage: StorageSettings::default(),
+            orchestrator: OrchestratorSettings::default(),
+            environments: BTreeMap::new(),
+            submission_nonces: BTreeMap::new(),
+            version: ConfigVersion::default(),
+            extra: Map::new(),
         }
     }
 
@@ -268,6 +539,22 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    // A config file with settings of the wrong type should name each of them.
+    fn test_load_reports_every_invalid_setting() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, r#"{"user_id": 7, "submission_nonces": "many"}"#).unwrap();
+
+        let message = Config::load_from_file(&path).unwrap_err().to_string();
+        assert!(message.contains("user_id: Invalid value"), "{}", message);
+        assert!(
+            message.contains("submission_nonces: Invalid value"),
+            "{}",
+            message
+        );
+    }
+
     #[test]
     // Clearing the node configuration file should remove it if it exists.
     fn test_clear_node_config_removes_file() {
@@ -341,6 +628,19 @@ mod tests {
             user_id: "".to_string(),
             wallet_address: "".to_string(),
             node_id: "12345".to_string(),
+            notifications: NotificationSettings::default(),
+            wallets: BTreeMap::new(),
+            dashboard: DashboardLayout::default(),
+            theme: None,
+            task_cache: TaskCacheSettings::default(),
+            update: UpdateSettings::default(),
+            labels: Labels::new(),
+            storage: StorageSettings::default(),
+            orchestrator: OrchestratorSettings::default(),
+            environments: BTreeMap::new(),
+            submission_nonces: BTreeMap::new(),
+            version: ConfigVersion::default(),
+            extra: Map::new(),
         };
         config.save(&path).unwrap();
 
@@ -383,4 +683,54 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    // Settings of a newer CLI survive a save by this one.
+    fn test_save_keeps_unknown_fields() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let mut file = File::create(&path).unwrap();
+        writeln!(
+            file,
+            r#"{{ "node_id": "12345", "version": 99, "extra_field": {{ "nested": 1 }} }}"#
+        )
+        .unwrap();
+
+        let mut config = Config::load_from_file(&path).unwrap();
+        config.node_id = "67890".to_string();
+        config.save(&path).unwrap();
+
+        let saved: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved["node_id"], "67890");
+        assert_eq!(saved["version"], 99);
+        assert_eq!(saved["extra_field"]["nested"], 1);
+    }
+
+    #[test]
+    // Settings are read and written by their dotted keys.
+    fn test_get_and_set_settings() {
+        let mut config = get_config();
+        assert_eq!(config.get_setting("update.channel").unwrap(), "stable");
+
+        config.set_setting("update.channel", "beta").unwrap();
+        config.set_setting("task_cache.max_entries", "50").unwrap();
+        assert_eq!(config.update.channel, UpdateChannel::Beta);
+        assert_eq!(config.get_setting("task_cache.max_entries").unwrap(), "50");
+
+        assert!(matches!(
+            config.set_setting("update.channel", "canary"),
+            Err(SettingError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            config.set_setting("task_cache.ttl_secs", "-1"),
+            Err(SettingError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            config.get_setting("theme.name"),
+            Err(SettingError::UnknownKey(_))
+        ));
+        for key in SETTING_KEYS {
+            assert!(config.get_setting(key).is_ok(), "{}", key);
+        }
+    }
 }
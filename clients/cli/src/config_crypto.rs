@@ -0,0 +1,407 @@
+//! Encryption of the config file at rest
+//!
+//! On shared machines, `nexus-cli config encrypt` keeps the config file, with the wallet address
+//! and the user and node IDs, encrypted with AES-256-GCM, so other local users cannot read it.
+//! The key is derived from a passphrase with Argon2id or, with `--keychain`, is a random key kept
+//! in the OS keychain (the macOS Keychain, the Windows Credential Manager or the Secret Service on
+//! Linux).
+//!
+//! Decryption is transparent. The CLI gets the key once at startup, see [`unlock`]: from the
+//! keychain, from the `NEXUS_CONFIG_PASSPHRASE` environment variable, or by asking for the
+//! passphrase in the terminal. Every later read and write of the config file goes through
+//! [`read_config`] and [`write_config`], which encrypt again with a fresh nonce. Services and
+//! fleets, which start without a terminal, need the keychain or the environment variable.
+//! `nexus-cli config decrypt` stores the file as plain JSON again.
+
+use crate::cli_messages::print_success;
+use crate::failures::{from_hex, to_hex};
+use crate::secrets::{KeychainStore, SecretStore, SecretsError};
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Environment variable holding the passphrase of an encrypted config file
+pub const PASSPHRASE_VAR: &str = "NEXUS_CONFIG_PASSPHRASE";
+
+/// Marks an encrypted config file
+const FORMAT: &str = "nexus-encrypted-config-v1";
+
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Keys of the config files decrypted so far, by [`cache_key`]
+static KEYS: Mutex<BTreeMap<String, [u8; KEY_LEN]>> = Mutex::new(BTreeMap::new());
+
+/// Where the key of an encrypted config file comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySource {
+    /// Derived from a passphrase
+    Passphrase,
+    /// Random, kept in the OS keychain
+    Keychain,
+}
+
+/// Contents of an encrypted config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Envelope {
+    format: String,
+    key_source: KeySource,
+    /// Argon2id salt of a passphrase key, in hex
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    salt: String,
+    /// AES-GCM nonce, in hex; new for every write
+    nonce: String,
+    /// The config JSON, encrypted, in hex
+    ciphertext: String,
+}
+
+/// Why an encrypted config file cannot be read or written
+#[derive(Debug, Error)]
+pub enum ConfigCryptoError {
+    #[error(
+        "The config file is encrypted: set {} or run in a terminal to enter the passphrase",
+        PASSPHRASE_VAR
+    )]
+    Locked,
+
+    #[error("Cannot decrypt the config file: wrong passphrase, or the file was modified")]
+    Decryption,
+
+    #[error(transparent)]
+    Keychain(#[from] SecretsError),
+
+    #[error("Encrypted config file is damaged: {0}")]
+    Damaged(String),
+
+    #[error("The passphrases do not match")]
+    PassphraseMismatch,
+
+    #[error("The config file is already encrypted")]
+    AlreadyEncrypted,
+
+    #[error("The config file is not encrypted")]
+    NotEncrypted,
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl From<ConfigCryptoError> for io::Error {
+    fn from(error: ConfigCryptoError) -> Self {
+        match error {
+            ConfigCryptoError::Io(e) => e,
+            other => {
+                let kind = match other {
+                    ConfigCryptoError::Locked => io::ErrorKind::PermissionDenied,
+                    _ => io::ErrorKind::InvalidData,
+                };
+                io::Error::new(kind, other)
+            }
+        }
+    }
+}
+
+/// The envelope in `buf`, if it is an encrypted config file.
+fn parse_envelope(buf: &[u8]) -> Option<Envelope> {
+    serde_json::from_slice::<Envelope>(buf)
+        .ok()
+        .filter(|envelope| envelope.format == FORMAT)
+}
+
+/// Whether the config file at `path` is encrypted.
+pub fn is_encrypted(path: &Path) -> bool {
+    std::fs::read(path)
+        .map(|buf| parse_envelope(&buf).is_some())
+        .unwrap_or(false)
+}
+
+/// Key of the key cache for the config file at `path`.
+fn cache_key(path: &Path, envelope: &Envelope) -> String {
+    match envelope.key_source {
+        KeySource::Passphrase => format!("passphrase:{}", envelope.salt),
+        KeySource::Keychain => format!("keychain:{}", path.display()),
+    }
+}
+
+fn cached_key(cache_key: &str) -> Option<[u8; KEY_LEN]> {
+    KEYS.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(cache_key)
+        .copied()
+}
+
+fn cache(cache_key: String, key: [u8; KEY_LEN]) {
+    KEYS.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(cache_key, key);
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Derive the key for `passphrase` and `salt` with Argon2id.
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; KEY_LEN], ConfigCryptoError> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| ConfigCryptoError::Damaged(e.to_string()))?;
+    Ok(key)
+}
+
+/// Name of the keychain secret holding the key of the config file at `path`.
+fn keychain_secret(path: &Path) -> String {
+    format!("config-key:{}", path.display())
+}
+
+fn keychain_key(path: &Path) -> Result<[u8; KEY_LEN], ConfigCryptoError> {
+    let name = keychain_secret(path);
+    KeychainStore
+        .get(&name)?
+        .and_then(|hex| from_hex(&hex).ok())
+        .and_then(|key| <[u8; KEY_LEN]>::try_from(key).ok())
+        .ok_or_else(|| SecretsError::Invalid(name).into())
+}
+
+/// The passphrase from [`PASSPHRASE_VAR`], or with `prompt`, typed in the terminal.
+fn passphrase(prompt: Option<&str>) -> Result<String, ConfigCryptoError> {
+    if let Some(passphrase) = std::env::var(PASSPHRASE_VAR)
+        .ok()
+        .filter(|passphrase| !passphrase.is_empty())
+    {
+        return Ok(passphrase);
+    }
+    match prompt {
+        Some(prompt) if io::stdin().is_terminal() => Ok(rpassword::prompt_password(prompt)?),
+        _ => Err(ConfigCryptoError::Locked),
+    }
+}
+
+/// The key of the config file at `path`, asking for the passphrase if `prompt` and not cached.
+fn key_for(
+    path: &Path,
+    envelope: &Envelope,
+    prompt: bool,
+) -> Result<[u8; KEY_LEN], ConfigCryptoError> {
+    if let Some(key) = cached_key(&cache_key(path, envelope)) {
+        return Ok(key);
+    }
+    match envelope.key_source {
+        KeySource::Keychain => keychain_key(path),
+        KeySource::Passphrase => {
+            let salt = from_hex(&envelope.salt).map_err(ConfigCryptoError::Damaged)?;
+            let passphrase = passphrase(prompt.then_some("Config file passphrase: "))?;
+            derive_key(&passphrase, &salt)
+        }
+    }
+}
+
+fn cipher(key: &[u8; KEY_LEN]) -> Aes256Gcm {
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key[..]))
+}
+
+fn decrypt(envelope: &Envelope, key: &[u8; KEY_LEN]) -> Result<Vec<u8>, ConfigCryptoError> {
+    let nonce = from_hex(&envelope.nonce)
+        .ok()
+        .filter(|nonce| nonce.len() == NONCE_LEN)
+        .ok_or_else(|| ConfigCryptoError::Damaged("invalid nonce".to_string()))?;
+    let ciphertext = from_hex(&envelope.ciphertext).map_err(ConfigCryptoError::Damaged)?;
+    cipher(key)
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| ConfigCryptoError::Decryption)
+}
+
+/// Encrypt `plaintext` with `key` and a fresh nonce.
+fn encrypt(
+    plaintext: &[u8],
+    key: &[u8; KEY_LEN],
+    key_source: KeySource,
+    salt: &str,
+) -> Result<Envelope, ConfigCryptoError> {
+    let nonce = random_bytes::<NONCE_LEN>();
+    let ciphertext = cipher(key)
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| ConfigCryptoError::Damaged("encryption failed".to_string()))?;
+    Ok(Envelope {
+        format: FORMAT.to_string(),
+        key_source,
+        salt: salt.to_string(),
+        nonce: to_hex(&nonce),
+        ciphertext: to_hex(&ciphertext),
+    })
+}
+
+fn write_envelope(path: &Path, envelope: &Envelope) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(envelope)?;
+    std::fs::write(path, json)
+}
+
+/// Get the key of the config file at `path`, if it is encrypted, asking for the passphrase in the
+/// terminal if needed, so later reads and writes need no passphrase.
+///
+/// Does nothing if there is no config file or it is not encrypted.
+pub fn unlock(path: &Path) -> Result<(), ConfigCryptoError> {
+    let buf = match std::fs::read(path) {
+        Ok(buf) => buf,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    let Some(envelope) = parse_envelope(&buf) else {
+        return Ok(());
+    };
+    let key = key_for(path, &envelope, true)?;
+    decrypt(&envelope, &key)?;
+    cache(cache_key(path, &envelope), key);
+    Ok(())
+}
+
+/// Contents of the config file at `path`, decrypted if it is encrypted.
+pub fn read_config(path: &Path) -> io::Result<Vec<u8>> {
+    let buf = std::fs::read(path)?;
+    let Some(envelope) = parse_envelope(&buf) else {
+        return Ok(buf);
+    };
+    let key = key_for(path, &envelope, false)?;
+    let plaintext = decrypt(&envelope, &key)?;
+    cache(cache_key(path, &envelope), key);
+    Ok(plaintext)
+}
+
+/// Replace the config file at `path` with `plaintext`, encrypted again if the file is encrypted.
+pub fn write_config(path: &Path, plaintext: &[u8]) -> io::Result<()> {
+    let envelope = std::fs::read(path)
+        .ok()
+        .and_then(|buf| parse_envelope(&buf));
+    match envelope {
+        Some(envelope) => {
+            let key = key_for(path, &envelope, false)?;
+            let encrypted = encrypt(plaintext, &key, envelope.key_source, &envelope.salt)?;
+            write_envelope(path, &encrypted)
+        }
+        None => std::fs::write(path, plaintext),
+    }
+}
+
+/// Encrypt the config file at `path`, for `nexus-cli config encrypt`.
+///
+/// With `keychain`, a random key is stored in the OS keychain; otherwise the key is derived from
+/// a passphrase, taken from [`PASSPHRASE_VAR`] or typed twice in the terminal.
+pub fn encrypt_config(path: &Path, keychain: bool) -> Result<(), Box<dyn Error>> {
+    let plaintext = std::fs::read(path)?;
+    if parse_envelope(&plaintext).is_some() {
+        return Err(ConfigCryptoError::AlreadyEncrypted.into());
+    }
+
+    let (key, key_source, salt) = if keychain {
+        let key = random_bytes::<KEY_LEN>();
+        KeychainStore.set(&keychain_secret(path), &to_hex(&key))?;
+        (key, KeySource::Keychain, String::new())
+    } else {
+        let passphrase = match std::env::var(PASSPHRASE_VAR) {
+            Ok(passphrase) if !passphrase.is_empty() => passphrase,
+            _ => {
+                let passphrase = self::passphrase(Some("New passphrase: "))?;
+                if passphrase != self::passphrase(Some("Repeat the passphrase: "))? {
+                    return Err(ConfigCryptoError::PassphraseMismatch.into());
+                }
+                passphrase
+            }
+        };
+        let salt = random_bytes::<SALT_LEN>();
+        (
+            derive_key(&passphrase, &salt)?,
+            KeySource::Passphrase,
+            to_hex(&salt),
+        )
+    };
+
+    let envelope = encrypt(&plaintext, &key, key_source, &salt)?;
+    write_envelope(path, &envelope)?;
+    cache(cache_key(path, &envelope), key);
+    let detail = match key_source {
+        KeySource::Keychain => "key stored in the OS keychain".to_string(),
+        KeySource::Passphrase => format!(
+            "set {} for services and fleets that start without a terminal",
+            PASSPHRASE_VAR
+        ),
+    };
+    print_success("Config file encrypted", &detail);
+    Ok(())
+}
+
+/// Store the config file at `path` as plain JSON again, for `nexus-cli config decrypt`.
+pub fn decrypt_config(path: &Path) -> Result<(), Box<dyn Error>> {
+    let Some(envelope) = std::fs::read(path)
+        .map(|buf| parse_envelope(&buf))
+        .map_err(ConfigCryptoError::from)?
+    else {
+        return Err(ConfigCryptoError::NotEncrypted.into());
+    };
+    let plaintext = read_config(path)?;
+    std::fs::write(path, plaintext)?;
+    if envelope.key_source == KeySource::Keychain {
+        // The key is of no use any more; a keychain that refuses to delete it is not an error
+        let _ = KeychainStore.delete(&keychain_secret(path));
+    }
+    print_success("Config file decrypted", &path.display().to_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_config_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let salt = random_bytes::<SALT_LEN>();
+        let key = derive_key("correct horse", &salt).unwrap();
+        let plaintext = br#"{"node_id": "12345"}"#;
+        let envelope = encrypt(plaintext, &key, KeySource::Passphrase, &to_hex(&salt)).unwrap();
+        write_envelope(&path, &envelope).unwrap();
+
+        // Nothing readable is left in the file
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("12345"));
+        assert!(is_encrypted(&path));
+
+        // A wrong key is rejected
+        let wrong = derive_key("wrong horse", &salt).unwrap();
+        assert!(matches!(
+            decrypt(&envelope, &wrong),
+            Err(ConfigCryptoError::Decryption)
+        ));
+
+        // With the key cached, reads and writes are transparent, with a fresh nonce each time
+        cache(cache_key(&path, &envelope), key);
+        assert_eq!(read_config(&path).unwrap(), plaintext);
+        write_config(&path, br#"{"node_id": "67890"}"#).unwrap();
+        let rewritten = parse_envelope(&std::fs::read(&path).unwrap()).unwrap();
+        assert_ne!(rewritten.nonce, envelope.nonce);
+        assert_eq!(rewritten.salt, envelope.salt);
+        assert_eq!(read_config(&path).unwrap(), br#"{"node_id": "67890"}"#);
+    }
+
+    #[test]
+    fn test_plain_config_passes_through() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        write_config(&path, br#"{"node_id": "12345"}"#).unwrap();
+        assert!(!is_encrypted(&path));
+        assert_eq!(read_config(&path).unwrap(), br#"{"node_id": "12345"}"#);
+        unlock(&path).unwrap();
+    }
+}
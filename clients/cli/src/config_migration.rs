@@ -0,0 +1,229 @@
+//! Migration of config files written by older CLI versions
+//!
+//! The config file records the version of its schema in `version`; files written before the
+//! schema was versioned have none and count as version 0. Each change to the schema that older
+//! files do not parse under gets a migration step below, which rewrites the JSON of the previous
+//! version into the next one.
+//!
+//! [`Config::load_from_file`](crate::config::Config::load_from_file) runs the missing steps when
+//! it loads an older file, keeps a copy of the file as it was in `config.json.v<N>.bak`, where `N`
+//! is its old version, and saves the migrated file. Files from a newer CLI are loaded as they are:
+//! settings this version does not know are ignored, but kept in
+//! [`Config::extra`](crate::config::Config::extra) and written back, with the newer version, when
+//! the config is saved.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+/// A step rewriting the settings of one version into the next
+type Migration = fn(&mut Map<String, Value>);
+
+/// Migration steps, in order: step `i` migrates version `i` to version `i + 1`
+const MIGRATIONS: &[Migration] = &[node_id_as_string, add_wallet_address, nonces_by_node];
+
+/// Schema version of the config files written by this CLI
+pub const CURRENT_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Schema version of a config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ConfigVersion(pub u32);
+
+impl Default for ConfigVersion {
+    /// New configs are written in the current schema.
+    fn default() -> Self {
+        Self(CURRENT_VERSION)
+    }
+}
+
+/// Version 0 to 1: node IDs were saved as numbers before they became strings.
+fn node_id_as_string(settings: &mut Map<String, Value>) {
+    if let Some(Value::Number(node_id)) = settings.get("node_id") {
+        let node_id = node_id.to_string();
+        settings.insert("node_id".to_string(), Value::String(node_id));
+    }
+}
+
+/// Version 1 to 2: the wallet address of the user is saved next to the node.
+fn add_wallet_address(settings: &mut Map<String, Value>) {
+    // Also when cleared by hand; an empty address is resolved again from the node
+    if matches!(settings.get("wallet_address"), None | Some(Value::Null)) {
+        settings.insert("wallet_address".to_string(), Value::String(String::new()));
+    }
+}
+
+/// Version 2 to 3: the submission nonce counter is kept per node, as the one of the current node.
+fn nonces_by_node(settings: &mut Map<String, Value>) {
+    let Some(nonce) = settings.remove("submission_nonce") else {
+        return;
+    };
+    // Without a node the counter counted for nobody; new counters start past the current time
+    let node_id = match settings.get("node_id") {
+        Some(Value::String(node_id)) if !node_id.is_empty() => node_id.clone(),
+        _ => return,
+    };
+    let nonces = Map::from_iter([(node_id, nonce)]);
+    settings.insert("submission_nonces".to_string(), Value::Object(nonces));
+}
+
+/// Version of `settings`, 0 if they have none.
+fn version_of(settings: &Map<String, Value>) -> u32 {
+    settings
+        .get("version")
+        .and_then(Value::as_u64)
+        .map_or(0, |version| version.min(u32::MAX as u64) as u32)
+}
+
+/// Migrate the config JSON in `value` to the current version, in place.
+///
+/// Returns the version it was migrated from, or `None` if it was already current, newer, or not
+/// a JSON object.
+pub fn migrate(value: &mut Value) -> Option<u32> {
+    let Value::Object(settings) = value else {
+        return None;
+    };
+    let from = version_of(settings);
+    if from >= CURRENT_VERSION {
+        return None;
+    }
+    for migration in &MIGRATIONS[from as usize..] {
+        migration(settings);
+    }
+    settings.insert("version".to_string(), Value::from(CURRENT_VERSION));
+    Some(from)
+}
+
+/// Where the file at `path` is kept as it was before migrating it from version `from`.
+pub fn backup_path(path: &Path, from: u32) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".v{}.bak", from));
+    PathBuf::from(backup)
+}
+
+/// Keep a copy of the file at `path`, then replace it with the migrated `value`, encrypted again
+/// if the file is encrypted.
+///
+/// An existing backup of the same version is not overwritten, so the original survives a
+/// migration that is run again after a failed save.
+pub fn save_migrated(path: &Path, from: u32, value: &Value) -> std::io::Result<()> {
+    let backup = backup_path(path, from);
+    if !backup.exists() {
+        std::fs::copy(path, &backup)?;
+    }
+    let json = serde_json::to_string_pretty(value)?;
+    crate::config_crypto::write_config(path, json.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use serde_json::json;
+
+    #[test]
+    fn test_node_id_as_string() {
+        let mut value = json!({ "node_id": 12345, "user_id": "user" });
+        let Value::Object(settings) = &mut value else {
+            unreachable!()
+        };
+        node_id_as_string(settings);
+        assert_eq!(value["node_id"], json!("12345"));
+
+        let mut value = json!({ "node_id": "12345" });
+        let Value::Object(settings) = &mut value else {
+            unreachable!()
+        };
+        node_id_as_string(settings);
+        assert_eq!(value["node_id"], json!("12345"));
+    }
+
+    #[test]
+    fn test_add_wallet_address() {
+        let mut value = json!({ "node_id": "12345" });
+        let Value::Object(settings) = &mut value else {
+            unreachable!()
+        };
+        add_wallet_address(settings);
+        assert_eq!(value["wallet_address"], json!(""));
+
+        let mut value = json!({ "wallet_address": null });
+        let Value::Object(settings) = &mut value else {
+            unreachable!()
+        };
+        add_wallet_address(settings);
+        assert_eq!(value["wallet_address"], json!(""));
+
+        let address = "0x52908400098527886E0F7030069857D2E4169EE7";
+        let mut value = json!({ "wallet_address": address });
+        let Value::Object(settings) = &mut value else {
+            unreachable!()
+        };
+        add_wallet_address(settings);
+        assert_eq!(value["wallet_address"], json!(address));
+    }
+
+    #[test]
+    fn test_nonces_by_node() {
+        let mut value = json!({ "node_id": "12345", "submission_nonce": 2000 });
+        let Value::Object(settings) = &mut value else {
+            unreachable!()
+        };
+        nonces_by_node(settings);
+        assert_eq!(
+            value,
+            json!({ "node_id": "12345", "submission_nonces": { "12345": 2000 } })
+        );
+
+        let mut value = json!({ "node_id": "", "submission_nonce": 2000 });
+        let Value::Object(settings) = &mut value else {
+            unreachable!()
+        };
+        nonces_by_node(settings);
+        assert_eq!(value, json!({ "node_id": "" }));
+    }
+
+    #[test]
+    fn test_migrate() {
+        let mut value = json!({ "user_id": "user", "node_id": 12345 });
+        assert_eq!(migrate(&mut value), Some(0));
+        assert_eq!(
+            value,
+            json!({
+                "user_id": "user",
+                "node_id": "12345",
+                "wallet_address": "",
+                "version": CURRENT_VERSION
+            })
+        );
+
+        // Current and newer files are left alone
+        let before = value.clone();
+        assert_eq!(migrate(&mut value), None);
+        assert_eq!(value, before);
+        let mut newer = json!({ "node_id": "1", "version": CURRENT_VERSION + 1 });
+        assert_eq!(migrate(&mut newer), None);
+        assert_eq!(newer["version"], json!(CURRENT_VERSION + 1));
+    }
+
+    #[test]
+    fn test_load_migrates_and_keeps_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let original = r#"{"user_id": "user", "node_id": 12345}"#;
+        std::fs::write(&path, original).unwrap();
+
+        let config = Config::load_from_file(&path).unwrap();
+        assert_eq!(config.node_id, "12345");
+        assert_eq!(config.version, ConfigVersion(CURRENT_VERSION));
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&path, 0)).unwrap(),
+            original
+        );
+
+        // The migrated file loads without migrating again
+        let saved: Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved["version"], json!(CURRENT_VERSION));
+        assert_eq!(Config::load_from_file(&path).unwrap(), config);
+    }
+}
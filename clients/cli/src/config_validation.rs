@@ -0,0 +1,464 @@
+//! Validation of the config file
+//!
+//! [`validate`] reads the whole config file and reports every problem it finds instead of
+//! stopping at the first: JSON syntax errors with their line and column, settings of the wrong
+//! type, unknown settings (usually typos), and values the CLI cannot use, such as an unknown
+//! environment, a node ID that is not a number or a node without a user. Each problem comes with
+//! a hint to fix it.
+//!
+//! `nexus-cli config validate` prints the report and exits with a non-zero status if the file has
+//! errors, for provisioning scripts. [`Config::load_from_file`] reports parse failures the same
+//! way.
+
+use crate::config::{Config, ConfigError};
+use crate::config_migration::CURRENT_VERSION;
+use crate::environment::{CUSTOM_ENVIRONMENT, Environment};
+use crate::keys::is_valid_eth_address;
+use serde_json::{Map, Value};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Top-level settings of the config file
+const FIELDS: [&str; 16] = [
+    "environment",
+    "user_id",
+    "wallet_address",
+    "node_id",
+    "notifications",
+    "wallets",
+    "dashboard",
+    "theme",
+    "task_cache",
+    "update",
+    "labels",
+    "storage",
+    "orchestrator",
+    "environments",
+    "submission_nonces",
+    "version",
+];
+
+/// Most edits between an unknown name and a known one to suggest the known one
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// How bad a problem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The CLI cannot use the file, or the setting, as it is
+    Error,
+    /// The setting is ignored or probably not what was meant
+    Warning,
+}
+
+/// A problem found in the config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Dotted name of the setting, e.g. `environments.staging.orchestrator_url`, or empty for the
+    /// file as a whole
+    pub field: String,
+    pub message: String,
+    /// How to fix it
+    pub hint: Option<String>,
+}
+
+impl Diagnostic {
+    fn error(field: impl Into<String>, message: impl Into<String>, hint: Option<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            field: field.into(),
+            message: message.into(),
+            hint,
+        }
+    }
+
+    fn warning(field: impl Into<String>, message: impl Into<String>, hint: Option<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            field: field.into(),
+            message: message.into(),
+            hint,
+        }
+    }
+
+    fn print(&self) {
+        let label = match self.severity {
+            Severity::Error => "\x1b[1;31m[ERROR]\x1b[0m",
+            Severity::Warning => "\x1b[1;33m[WARN]\x1b[0m ",
+        };
+        println!("{} {}", label, self);
+        if let Some(hint) = &self.hint {
+            println!("        → {}", hint);
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.field, self.message)
+        }
+    }
+}
+
+/// Problems in the config file contents `json`.
+pub fn validate(json: &[u8]) -> Vec<Diagnostic> {
+    let value: Value = match serde_json::from_slice(json) {
+        Ok(value) => value,
+        Err(e) => {
+            return vec![Diagnostic::error(
+                "",
+                format!(
+                    "Not valid JSON at line {}, column {}: {}",
+                    e.line(),
+                    e.column(),
+                    strip_position(&e.to_string())
+                ),
+                Some(
+                    "Fix the JSON by hand, or run `nexus-cli logout` and register again"
+                        .to_string(),
+                ),
+            )];
+        }
+    };
+    // Files from older versions are checked as they will be loaded
+    let mut value = value;
+    crate::config_migration::migrate(&mut value);
+    let Value::Object(settings) = value else {
+        return vec![Diagnostic::error(
+            "",
+            "Expected a JSON object of settings",
+            Some("Run `nexus-cli logout` and register again".to_string()),
+        )];
+    };
+
+    let mut diagnostics = Vec::new();
+    // Settings that parse, to check their values together
+    let mut valid = Map::new();
+    for (key, value) in settings {
+        if !FIELDS.contains(&key.as_str()) {
+            let hint = match suggest(&key, FIELDS.iter().copied()) {
+                Some(known) => format!("Did you mean `{}`?", known),
+                None => format!("Known settings: {}", FIELDS.join(", ")),
+            };
+            diagnostics.push(Diagnostic::warning(
+                key,
+                "Unknown setting, ignored",
+                Some(hint),
+            ));
+            continue;
+        }
+        let single = Map::from_iter([(key.clone(), value.clone())]);
+        match serde_json::from_value::<Config>(Value::Object(single)) {
+            Ok(_) => {
+                valid.insert(key, value);
+            }
+            Err(e) => diagnostics.push(Diagnostic::error(
+                key,
+                format!("Invalid value: {}", e),
+                Some("Correct the value, or remove the setting to use its default".to_string()),
+            )),
+        }
+    }
+
+    if let Ok(config) = serde_json::from_value::<Config>(Value::Object(valid)) {
+        diagnostics.extend(check_values(&config));
+    }
+    diagnostics
+}
+
+/// Problems with the values of a config that parsed.
+fn check_values(config: &Config) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if config.version.0 > CURRENT_VERSION {
+        diagnostics.push(Diagnostic::warning(
+            "version",
+            format!(
+                "Written by a newer CLI (schema {}, this CLI knows up to {})",
+                config.version.0, CURRENT_VERSION
+            ),
+            Some(
+                "Run `nexus-cli update`; settings this version does not know are ignored"
+                    .to_string(),
+            ),
+        ));
+    }
+
+    if !config.node_id.is_empty() && config.node_id.trim().parse::<u64>().is_err() {
+        diagnostics.push(Diagnostic::error(
+            "node_id",
+            format!("Node ID '{}' is not a number", config.node_id),
+            Some("Run `nexus-cli register-node` to register a new node".to_string()),
+        ));
+    }
+    match (config.user_id.is_empty(), config.node_id.is_empty()) {
+        (true, false) => diagnostics.push(Diagnostic::error(
+            "user_id",
+            "Missing, but a node ID is set",
+            Some(
+                "Run `nexus-cli register-user --wallet-address <ADDRESS>`, or start with --node-id"
+                    .to_string(),
+            ),
+        )),
+        (false, true) => diagnostics.push(Diagnostic::warning(
+            "node_id",
+            "No node registered yet",
+            Some("Run `nexus-cli register-node`".to_string()),
+        )),
+        _ => {}
+    }
+
+    if !config.wallet_address.is_empty() && !is_valid_eth_address(&config.wallet_address) {
+        diagnostics.push(Diagnostic::error(
+            "wallet_address",
+            format!(
+                "'{}' is not a valid Ethereum address",
+                config.wallet_address
+            ),
+            Some("Run `nexus-cli register-user` with the correct address".to_string()),
+        ));
+    }
+    for (alias, address) in &config.wallets {
+        if !is_valid_eth_address(address) {
+            diagnostics.push(Diagnostic::error(
+                format!("wallets.{}", alias),
+                format!("'{}' is not a valid Ethereum address", address),
+                Some(format!(
+                    "Run `nexus-cli wallet add {} <ADDRESS>` with the correct address",
+                    alias
+                )),
+            ));
+        }
+    }
+
+    if !is_known_environment(&config.environment, config) {
+        let names = environment_names(config);
+        let hint = match suggest(&config.environment, names.iter().map(String::as_str)) {
+            Some(known) => format!("Did you mean `{}`?", known),
+            None => format!("Use one of: {}", names.join(", ")),
+        };
+        diagnostics.push(Diagnostic::error(
+            "environment",
+            format!("Unknown environment '{}'", config.environment),
+            Some(hint),
+        ));
+    }
+    for (name, definition) in &config.environments {
+        let url = definition.orchestrator_url.trim();
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            diagnostics.push(Diagnostic::error(
+                format!("environments.{}.orchestrator_url", name),
+                format!("'{}' is not an http:// or https:// URL", url),
+                Some(
+                    "Set the URL of the orchestrator, e.g. https://orchestrator.example.com"
+                        .to_string(),
+                ),
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// Whether `environment`, as saved by `register-user`, names an environment the CLI knows.
+fn is_known_environment(environment: &str, config: &Config) -> bool {
+    let environment = environment.trim();
+    environment.is_empty()
+        || Environment::from_str(environment).is_ok()
+        || environment.eq_ignore_ascii_case(CUSTOM_ENVIRONMENT)
+        // Saved as `Custom(<url>)` after registering with --orchestrator-url
+        || (environment.starts_with("Custom(") && environment.ends_with(')'))
+        || config.environments.contains_key(environment)
+}
+
+/// Environments the config file may name.
+fn environment_names(config: &Config) -> Vec<String> {
+    ["production", CUSTOM_ENVIRONMENT]
+        .iter()
+        .map(|name| name.to_string())
+        .chain(config.environments.keys().cloned())
+        .collect()
+}
+
+/// The known name closest to `name`, if it is only a few edits away.
+fn suggest<'a>(name: &str, known: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let name = name.to_lowercase();
+    known
+        .map(|candidate| (edit_distance(&name, &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between `a` and `b`, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// A serde error message without the ` at line 1 column 2` suffix.
+fn strip_position(message: &str) -> &str {
+    message
+        .rfind(" at line ")
+        .map_or(message, |position| &message[..position])
+}
+
+fn errors(diagnostics: &[Diagnostic]) -> impl Iterator<Item = &Diagnostic> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+}
+
+/// Whether any of `diagnostics` is an error.
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    errors(diagnostics).next().is_some()
+}
+
+/// The errors among `diagnostics`, in one line, for a config file that failed to load.
+pub fn summarize(path: &Path, diagnostics: &[Diagnostic]) -> String {
+    let errors: Vec<String> = errors(diagnostics).map(Diagnostic::to_string).collect();
+    format!(
+        "{}: {} (run `nexus-cli config validate` for hints)",
+        path.display(),
+        errors.join("; ")
+    )
+}
+
+/// Validate the config file and print the report, for `nexus-cli config validate`.
+///
+/// Fails if the file is missing or has errors; warnings alone pass.
+pub fn run_validate(config_path: &Path) -> Result<(), Box<dyn Error>> {
+    let json = crate::config_crypto::read_config(config_path).map_err(ConfigError::from)?;
+    let diagnostics = validate(&json);
+    for diagnostic in &diagnostics {
+        diagnostic.print();
+    }
+
+    let errors = errors(&diagnostics).count();
+    let warnings = diagnostics.len() - errors;
+    if diagnostics.is_empty() {
+        println!("{} is valid", config_path.display());
+    } else {
+        println!(
+            "\n{}: {} errors, {} warnings",
+            config_path.display(),
+            errors,
+            warnings
+        );
+    }
+    if errors > 0 {
+        return Err(ConfigError::Invalid(errors).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(diagnostics: &[Diagnostic]) -> Vec<(&str, Severity)> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.field.as_str(), diagnostic.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_config_has_no_diagnostics() {
+        let config = Config::new(
+            "user-1".to_string(),
+            "0x52908400098527886E0F7030069857D2E4169EE7".to_string(),
+            "12345".to_string(),
+            Environment::Production,
+        );
+        let json = serde_json::to_vec(&config).unwrap();
+        assert_eq!(validate(&json), vec![]);
+    }
+
+    #[test]
+    fn test_reports_all_problems_at_once() {
+        let json = br#"{
+            "environment": "prodution",
+            "node_id": "abc",
+            "node_Id": "12345",
+            "submission_nonces": "many",
+            "environments": { "staging": { "orchestrator_url": "staging.example.com" } }
+        }"#;
+        let diagnostics = validate(json);
+        assert_eq!(
+            fields(&diagnostics),
+            vec![
+                ("node_Id", Severity::Warning),
+                ("submission_nonces", Severity::Error),
+                ("node_id", Severity::Error),
+                ("user_id", Severity::Error),
+                ("environment", Severity::Error),
+                ("environments.staging.orchestrator_url", Severity::Error),
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].hint.as_deref(),
+            Some("Did you mean `node_id`?")
+        );
+        assert_eq!(
+            diagnostics[4].hint.as_deref(),
+            Some("Did you mean `production`?")
+        );
+    }
+
+    #[test]
+    fn test_reports_syntax_errors_with_position() {
+        let diagnostics = validate(b"{\n  \"node_id\": \"1\",\n}");
+        assert_eq!(diagnostics.len(), 1);
+        assert!(
+            diagnostics[0]
+                .message
+                .starts_with("Not valid JSON at line 3, column 1: "),
+            "{}",
+            diagnostics[0].message
+        );
+        assert!(diagnostics[0].message.ends_with("trailing comma"));
+    }
+
+    #[test]
+    fn test_known_fields_match_config() {
+        let mut config = Config::default();
+        config.theme = Some("dark".to_string());
+        config.labels.insert("region".to_string(), "eu".to_string());
+        config.environments.insert(
+            "staging".to_string(),
+            crate::environment::EnvironmentDefinition {
+                orchestrator_url: "https://staging.example.com".to_string(),
+            },
+        );
+        let Value::Object(settings) = serde_json::to_value(&config).unwrap() else {
+            panic!("config is not an object");
+        };
+        let mut keys: Vec<&str> = settings.keys().map(String::as_str).collect();
+        let mut expected = FIELDS.to_vec();
+        keys.sort();
+        expected.sort();
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("node_id", "node_id"), 0);
+        assert_eq!(edit_distance("nodeid", "node_id"), 1);
+        assert_eq!(edit_distance("prodution", "production"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
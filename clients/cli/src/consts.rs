@@ -13,9 +13,15 @@ pub mod cli_consts {
     /// The maximum number of events to keep in the activity logs.
     pub const MAX_ACTIVITY_LOGS: usize = 100;
 
+    /// Number of hours of proof history shown in the dashboard charts.
+    pub const CHART_HISTORY_HOURS: usize = 24;
+
     /// Maximum number of event buffer size for worker threads
     pub const EVENT_QUEUE_SIZE: usize = 100;
 
+    /// Events buffered per WebSocket subscriber before it starts skipping events
+    pub const EVENT_STREAM_BUFFER_SIZE: usize = 1024;
+
     // =============================================================================
     // PROVING CONFIGURATIONS
     // =============================================================================
@@ -29,6 +35,39 @@ pub mod cli_consts {
     /// "Reasonable" generic projection task memory requirement.
     pub const PROJECTED_MEMORY_REQUIREMENT: u64 = 4294967296; // 4gb
 
+    /// Disk space the proof cache may use before evicting the oldest proofs.
+    pub const PROOF_CACHE_MAX_BYTES: u64 = 512 * 1024 * 1024; // 512mb
+
+    /// How long submitted tasks are remembered by default, so they are not proven again.
+    pub const TASK_CACHE_TTL_SECS: u64 = 24 * 60 * 60; // 1 day
+
+    /// Number of submitted tasks remembered by default.
+    pub const TASK_CACHE_MAX_ENTRIES: usize = 10_000;
+
+    /// Clock skew against the orchestrator below which the local clock is trusted.
+    pub const CLOCK_SKEW_TOLERANCE_SECS: u64 = 5;
+
+    /// Longest key or value of a node label, in characters.
+    pub const MAX_LABEL_LEN: usize = 63;
+
+    /// Most labels a node reports.
+    pub const MAX_LABELS: usize = 16;
+
+    /// Restart policy for crashed workers
+    pub mod supervisor {
+        /// Delay before the first restart after a crash (seconds)
+        pub const INITIAL_RESTART_BACKOFF_SECS: u64 = 1;
+
+        /// Upper bound for the restart delay, also used while degraded (seconds)
+        pub const MAX_RESTART_BACKOFF_SECS: u64 = 300; // 5 minutes
+
+        /// Number of crashes within the crash-loop window that marks the worker as degraded
+        pub const CRASH_LOOP_THRESHOLD: usize = 5;
+
+        /// Window over which crashes are counted for crash-loop detection (seconds)
+        pub const CRASH_LOOP_WINDOW_SECS: u64 = 10 * 60; // 10 minutes
+    }
+
     // =============================================================================
     // DIFFICULTY CONFIGURATION
     // =============================================================================
@@ -38,12 +77,62 @@ pub mod cli_consts {
         /// Time threshold for auto-promotion (seconds)
         /// Tasks completing faster than this will promote to next difficulty level
         pub const PROMOTION_THRESHOLD_SECS: u64 = 7 * 60; // 7 minutes
+
+        /// How long a task is assumed to stay valid after it is fetched (seconds). The
+        /// orchestrator does not report it; this only decides when to warn about slow proofs.
+        pub const ASSUMED_TASK_EXPIRY_SECS: u64 = 30 * 60; // 30 minutes
+    }
+
+    // =============================================================================
+    // DOCTOR CONFIGURATION
+    // =============================================================================
+
+    /// Thresholds for `nexus-cli doctor` checks
+    pub mod doctor {
+        /// Timeout for the orchestrator reachability request (seconds)
+        pub const REACHABILITY_TIMEOUT_SECS: u64 = 10;
+
+        /// Clock skew against the orchestrator that triggers a warning (seconds)
+        pub const CLOCK_SKEW_WARN_SECS: u64 = 30;
+
+        /// Clock skew against the orchestrator that fails the check (seconds)
+        pub const CLOCK_SKEW_FAIL_SECS: u64 = 300;
+
+        /// Minimum recommended soft limit on open file descriptors
+        pub const MIN_OPEN_FILES: u64 = 1024;
     }
 
     // =============================================================================
     // NETWORK CONFIGURATION
     // =============================================================================
 
+    /// gRPC transport configuration (`--transport grpc`)
+    pub mod grpc {
+        /// Timeout for establishing the HTTP/2 connection (seconds)
+        pub const CONNECT_TIMEOUT_SECS: u64 = 10;
+
+        /// Interval between HTTP/2 and TCP keep-alive pings (seconds)
+        pub const KEEP_ALIVE_INTERVAL_SECS: u64 = 30;
+
+        /// Time to wait for a keep-alive ping acknowledgement before dropping the connection (seconds)
+        pub const KEEP_ALIVE_TIMEOUT_SECS: u64 = 10;
+
+        /// Timeout for a single unary request (seconds)
+        pub const REQUEST_TIMEOUT_SECS: u64 = 30;
+    }
+
+    /// Orchestrator endpoint failover (`orchestrator.fallback_urls`)
+    pub mod failover {
+        /// Unanswered requests in a row after which the next endpoint is tried
+        pub const FAILOVER_AFTER_FAILURES: u32 = 3;
+
+        /// Interval between health checks of the primary while on a fallback (seconds)
+        pub const PRIMARY_RECHECK_INTERVAL_SECS: u64 = 60;
+
+        /// Timeout for an endpoint health check (seconds)
+        pub const HEALTH_CHECK_TIMEOUT_SECS: u64 = 5;
+    }
+
     /// Task fetching backoff configuration
     pub mod task_fetching {
         use std::time::Duration;
@@ -58,6 +147,14 @@ pub mod cli_consts {
         /// Set to 2 minutes to align with server task creation frequency
         pub const RATE_LIMIT_INTERVAL_MS: u64 = 120_000;
 
+        /// How long the orchestrator may hold a task subscription open before answering
+        /// that no task is available (seconds)
+        pub const LONG_POLL_WAIT_SECS: u64 = 60;
+
+        /// Longest wait before previewing another task while offers keep being declined
+        /// (seconds); the wait doubles from the initial backoff with each declined offer
+        pub const MAX_DECLINE_BACKOFF_SECS: u64 = 30 * 60;
+
         /// Helper function to get initial backoff duration
         pub const fn initial_backoff() -> Duration {
             Duration::from_millis(INITIAL_BACKOFF_MS)
@@ -67,6 +164,18 @@ pub mod cli_consts {
         pub const fn rate_limit_interval() -> Duration {
             Duration::from_millis(RATE_LIMIT_INTERVAL_MS)
         }
+
+        /// Helper function to get the task subscription hold time
+        pub const fn long_poll_wait() -> Duration {
+            Duration::from_secs(LONG_POLL_WAIT_SECS)
+        }
+
+        /// Wait before previewing another task after `declined` offers in a row were declined
+        pub fn decline_backoff(declined: u32) -> Duration {
+            initial_backoff()
+                .saturating_mul(1 << declined.saturating_sub(1).min(16))
+                .min(Duration::from_secs(MAX_DECLINE_BACKOFF_SECS))
+        }
     }
 
     /// Proof submission backoff configuration
@@ -85,6 +194,15 @@ pub mod cli_consts {
         /// Less restrictive than task fetching
         pub const RATE_LIMIT_INTERVAL_MS: u64 = 100;
 
+        /// Maximum number of proofs sent in one batched submission
+        pub const MAX_BATCH_SIZE: usize = 8;
+
+        /// Longest a finished proof waits for others to share its submission (milliseconds)
+        pub const BATCH_MAX_DELAY_MS: u64 = 500;
+
+        /// Submission nonces reserved per write of the high-water mark to disk
+        pub const NONCE_RESERVATION: u64 = 100;
+
         /// Helper function to get initial backoff duration
         pub const fn initial_backoff() -> Duration {
             Duration::from_millis(INITIAL_BACKOFF_MS)
@@ -94,6 +212,11 @@ pub mod cli_consts {
         pub const fn rate_limit_interval() -> Duration {
             Duration::from_millis(RATE_LIMIT_INTERVAL_MS)
         }
+
+        /// Helper function to get the batching delay
+        pub const fn batch_max_delay() -> Duration {
+            Duration::from_millis(BATCH_MAX_DELAY_MS)
+        }
     }
 
     /// Advanced rate limiting configuration
@@ -129,5 +252,319 @@ pub mod cli_consts {
         pub const fn extra_retry_delay() -> Duration {
             Duration::from_secs(EXTRA_RETRY_DELAY_SECS)
         }
+
+        /// Number of recent rate-limit (429) responses kept for the dashboard
+        pub const RATE_LIMIT_HISTORY_SIZE: usize = 20;
+
+        /// How long rate-limit (429) responses are kept for the dashboard (seconds)
+        pub const RATE_LIMIT_HISTORY_SECS: u64 = 60 * 60; // 1 hour
+
+        /// Helper function to get the rate-limit history period
+        pub const fn rate_limit_history() -> Duration {
+            Duration::from_secs(RATE_LIMIT_HISTORY_SECS)
+        }
+
+        /// How long orchestrator requests are kept for the network quality monitor (seconds)
+        pub const NETWORK_MONITOR_WINDOW_SECS: u64 = 15 * 60; // 15 minutes
+
+        /// Most orchestrator requests kept for the network quality monitor
+        pub const NETWORK_MONITOR_MAX_SAMPLES: usize = 500;
+
+        /// Helper function to get the network quality monitor window
+        pub const fn network_monitor_window() -> Duration {
+            Duration::from_secs(NETWORK_MONITOR_WINDOW_SECS)
+        }
+    }
+
+    // =============================================================================
+    // MULTI-INSTANCE COORDINATION
+    // =============================================================================
+
+    /// Fetch schedule shared by the instances on one host, see `crate::coordinator`
+    pub mod coordination {
+        use std::time::Duration;
+
+        /// Gap between the fetch slots handed to the instances on one host (milliseconds)
+        pub const FETCH_STAGGER_MS: u64 = 2_000;
+
+        /// How long all instances hold back after a rate limit without a retry delay (seconds)
+        pub const RATE_LIMIT_HOLD_SECS: u64 = 30;
+
+        /// Longest wait for the broker to answer before fetching without it (milliseconds)
+        pub const BROKER_TIMEOUT_MS: u64 = 500;
+
+        /// Helper function to get the gap between fetch slots
+        pub const fn fetch_stagger() -> Duration {
+            Duration::from_millis(FETCH_STAGGER_MS)
+        }
+
+        /// Helper function to get the hold after a rate limit without a retry delay
+        pub const fn rate_limit_hold() -> Duration {
+            Duration::from_secs(RATE_LIMIT_HOLD_SECS)
+        }
+
+        /// Helper function to get the broker timeout
+        pub const fn broker_timeout() -> Duration {
+            Duration::from_millis(BROKER_TIMEOUT_MS)
+        }
+    }
+
+    /// Supervision of the nodes run by `fleet start`, see `crate::fleet`
+    pub mod fleet {
+        use std::time::Duration;
+
+        /// How often each node's stats and schedule are checked (seconds)
+        pub const POLL_INTERVAL_SECS: u64 = 2;
+
+        /// Lines of output kept per node for the fleet dashboard
+        pub const MAX_LOG_LINES: usize = 200;
+
+        /// Longest wait for a node's control API to answer (seconds)
+        pub const CONTROL_TIMEOUT_SECS: u64 = 5;
+
+        /// How long a stopping node may take to finish its step before it is killed (seconds)
+        pub const STOP_TIMEOUT_SECS: u64 = 30;
+
+        /// Helper function to get the poll interval
+        pub const fn poll_interval() -> Duration {
+            Duration::from_secs(POLL_INTERVAL_SECS)
+        }
+
+        /// Helper function to get the control API timeout
+        pub const fn control_timeout() -> Duration {
+            Duration::from_secs(CONTROL_TIMEOUT_SECS)
+        }
+
+        /// Helper function to get the stop timeout
+        pub const fn stop_timeout() -> Duration {
+            Duration::from_secs(STOP_TIMEOUT_SECS)
+        }
+    }
+
+    // =============================================================================
+    // CHAOS TESTING CONFIGURATION
+    // =============================================================================
+
+    /// Chaos mode watchdog configuration
+    pub mod chaos {
+        /// Exit code used when the watchdog detects a stalled worker loop
+        pub const CHAOS_DEADLOCK_EXIT_CODE: i32 = 70;
+
+        /// Exit code used when workers are still running after shutdown
+        pub const CHAOS_LEAKED_WORKER_EXIT_CODE: i32 = 71;
+
+        /// How often the watchdog checks for progress (seconds)
+        pub const WATCHDOG_INTERVAL_SECS: u64 = 10;
+
+        /// Time allowed for all workers to exit after shutdown is requested (seconds)
+        pub const SHUTDOWN_GRACE_SECS: u64 = 30;
+    }
+
+    // =============================================================================
+    // POINTS TRACKING
+    // =============================================================================
+
+    /// Points polling configuration
+    pub mod points {
+        /// How often to fetch point totals from the orchestrator (seconds)
+        pub const POINTS_POLL_INTERVAL_SECS: u64 = 300;
+
+        /// How often to fetch point totals while working towards `--stop-after-points` (seconds)
+        pub const POINTS_TARGET_POLL_INTERVAL_SECS: u64 = 60;
+
+        /// How often to fetch network statistics and the node's rank from the orchestrator
+        /// (seconds)
+        pub const NETWORK_STATS_POLL_INTERVAL_SECS: u64 = 600;
+
+        /// How often to compare session points against `--stop-after-points` (seconds)
+        pub const POINTS_TARGET_CHECK_INTERVAL_SECS: u64 = 5;
+
+        /// Length of the points history shown in the dashboard (hours)
+        pub const POINTS_HISTORY_HOURS: usize = 24;
+
+        /// How long to wait for final point totals for the session summary (seconds)
+        pub const SUMMARY_POINTS_TIMEOUT_SECS: u64 = 5;
+    }
+
+    /// Masking of identifiers with `--redact`
+    pub mod redaction {
+        /// Task IDs remembered for masking, most recent first
+        pub const RECENT_TASK_IDS: usize = 64;
+
+        /// Hex digits after `0x` for a value to be masked as a wallet address
+        pub const MIN_ADDRESS_HEX_DIGITS: usize = 16;
+    }
+
+    /// Readiness checks of `start --dry-run`
+    pub mod dry_run {
+        /// Inputs of the warm-up proof, `n,init_a,init_b` of the Fibonacci guest program
+        pub const WARM_UP_INPUTS: (u32, u32, u32) = (9, 1, 1);
+    }
+
+    /// Load tests of `start --simulate`
+    pub mod simulate {
+        /// Inputs of each synthetic task
+        pub const INPUTS_PER_TASK: usize = 3;
+
+        /// Node the synthetic tasks are proved for
+        pub const NODE_ID: u64 = 1;
+    }
+
+    /// Submission receipts and points reconciliation
+    pub mod receipts {
+        /// How often to ask the orchestrator whether submitted tasks were credited (seconds)
+        pub const RECONCILE_INTERVAL_SECS: u64 = 600;
+
+        /// Age at which a submitted task is first looked up (seconds)
+        pub const RECEIPT_MIN_AGE_SECS: u64 = 120;
+
+        /// Age after which a task still not credited is flagged as a discrepancy (seconds)
+        pub const RECEIPT_GRACE_SECS: u64 = 60 * 60; // 1 hour
+
+        /// Most tasks looked up in one request
+        pub const RECEIPT_BATCH_SIZE: usize = 100;
+
+        /// How long receipts are kept in the ledger (seconds)
+        pub const RECEIPT_TTL_SECS: u64 = 7 * 24 * 60 * 60; // 7 days
+
+        /// Most receipts kept in the ledger; the oldest are dropped first
+        pub const RECEIPT_MAX_ENTRIES: usize = 10_000;
+    }
+
+    // =============================================================================
+    // DASHBOARD
+    // =============================================================================
+
+    /// Dashboard refresh (`--ui-fps`)
+    pub mod dashboard {
+        /// Most dashboard redraws per second, unless `--ui-fps` says otherwise
+        pub const DEFAULT_UI_FPS: u32 = 10;
+
+        /// Highest value accepted for `--ui-fps`
+        pub const MAX_UI_FPS: u32 = 60;
+
+        /// How often CPU, memory and temperature are sampled for the dashboard (seconds)
+        pub const METRICS_SAMPLE_INTERVAL_SECS: u64 = 2;
+
+        /// How often the dashboard is redrawn when nothing has changed, to keep its clocks
+        /// current (milliseconds)
+        pub const IDLE_REDRAW_INTERVAL_MS: u64 = 1000;
+
+        /// Activity log lines scrolled per mouse wheel step or Page Up / Page Down
+        pub const LOG_SCROLL_LINES: usize = 3;
+
+        /// Terminals narrower than this (columns) get the compact dashboard
+        pub const COMPACT_BELOW_WIDTH: u16 = 60;
+
+        /// Terminals shorter than this (rows) get the compact dashboard
+        pub const COMPACT_BELOW_HEIGHT: u16 = 20;
+
+        /// Log lines shown under the status line of the compact dashboard
+        pub const COMPACT_LOG_LINES: usize = 3;
+    }
+
+    /// Metrics snapshots appended to a file (`--metrics-export`)
+    pub mod metrics_export {
+        /// How often a snapshot is appended, unless `--metrics-export-interval` says otherwise
+        /// (seconds)
+        pub const DEFAULT_INTERVAL_SECS: u64 = 300;
+    }
+
+    // =============================================================================
+    // NOTIFICATIONS
+    // =============================================================================
+
+    /// Webhook delivery settings
+    pub mod notifications {
+        /// Timeout for a single webhook request (seconds)
+        pub const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+        /// How often to check whether a node has stopped submitting proofs (seconds)
+        pub const WEBHOOK_IDLE_CHECK_INTERVAL_SECS: u64 = 60;
+    }
+
+    // =============================================================================
+    // POWER MANAGEMENT
+    // =============================================================================
+
+    /// Battery-aware proving (`--battery-policy`)
+    pub mod power {
+        /// Battery charge below which the battery policy kicks in (percent)
+        pub const DEFAULT_BATTERY_THRESHOLD_PERCENT: u8 = 50;
+
+        /// How often to check the battery state (seconds)
+        pub const BATTERY_POLL_INTERVAL_SECS: u64 = 30;
+    }
+
+    /// Idle-only proving (`--only-when-idle`)
+    pub mod idle {
+        /// How often to check whether the machine is in use (seconds)
+        pub const IDLE_POLL_INTERVAL_SECS: u64 = 2;
+
+        /// CPU use of other processes below which a machine without a desktop counts as idle
+        /// (percent of the whole machine)
+        pub const IDLE_CPU_THRESHOLD_PERCENT: f32 = 20.0;
+    }
+
+    /// Temperature-aware proving (`--thermal-limit`)
+    pub mod thermal {
+        /// How often to read the temperature sensors (seconds)
+        pub const THERMAL_POLL_INTERVAL_SECS: u64 = 10;
+
+        /// How far above the limit task fetching is held until the machine cools down (°C)
+        pub const THERMAL_PAUSE_MARGIN_CELSIUS: f32 = 5.0;
+
+        /// How far below the limit the temperature must fall to prove at full speed again (°C)
+        pub const THERMAL_HYSTERESIS_CELSIUS: f32 = 5.0;
+    }
+
+    // =============================================================================
+    // STORAGE
+    // =============================================================================
+
+    /// Disk space limits for the files kept under `~/.nexus/`
+    pub mod storage {
+        /// Space failure bundles may use by default (MB)
+        pub const DEFAULT_FAILURES_QUOTA_MB: u64 = 256;
+
+        /// Space the proof cache may use by default, matching its own eviction limit (MB)
+        pub const DEFAULT_PROOF_CACHE_QUOTA_MB: u64 = super::PROOF_CACHE_MAX_BYTES / (1024 * 1024);
+
+        /// Free disk space below which a warning is shown by default (MB)
+        pub const DEFAULT_MIN_FREE_DISK_MB: u64 = 1024;
+
+        /// How often to enforce quotas and check free disk space (seconds)
+        pub const STORAGE_CHECK_INTERVAL_SECS: u64 = 300;
+    }
+
+    // =============================================================================
+    // PROOF STATISTICS
+    // =============================================================================
+
+    /// Proof duration model used for progress and ETA estimates
+    pub mod stats {
+        /// Weight of the newest proof in the moving average of proof durations (0.0 to 1.0)
+        pub const PROOF_DURATION_EMA_ALPHA: f64 = 0.3;
+
+        /// Proofs of a difficulty averaged into the session baseline of its proof duration
+        pub const SLOWDOWN_BASELINE_PROOFS: u32 = 5;
+
+        /// Proofs after the baseline before a slowdown can be reported
+        pub const SLOWDOWN_MIN_RECENT_PROOFS: u32 = 3;
+
+        /// Recent proof durations, relative to the baseline, that count as a slowdown
+        pub const SLOWDOWN_RATIO: f64 = 1.5;
+
+        /// Relative proof durations below which a reported slowdown is over
+        pub const SLOWDOWN_RECOVERY_RATIO: f64 = 1.2;
+
+        /// CPU clock, relative to the baseline, below which it is suspected of a slowdown
+        pub const SLOWDOWN_CPU_CLOCK_RATIO: f64 = 0.85;
+
+        /// Share of available memory below which memory pressure is suspected of a slowdown
+        pub const SLOWDOWN_LOW_MEMORY_RATIO: f64 = 0.1;
+
+        /// Temperature rise since the baseline that is suspected of a slowdown (°C)
+        pub const SLOWDOWN_TEMPERATURE_RISE_CELSIUS: f32 = 15.0;
     }
 }
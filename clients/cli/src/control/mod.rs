@@ -0,0 +1,213 @@
+//! Remote control of a running prover
+//!
+//! [`RuntimeControl`] holds the runtime knobs the worker reads on every cycle (pause
+//! state, max difficulty, battery power saving, idle-only holds, thermal throttling and stop
+//! requests). The optional HTTP API in [`server`] exposes them, together with session stats and
+//! shutdown, to fleet management tooling.
+
+pub mod server;
+
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::power::PowerSaving;
+use crate::thermal::ThermalState;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Settings for the HTTP control API
+#[derive(Debug, Clone)]
+pub struct ControlApiConfig {
+    /// Address to listen on, e.g. 127.0.0.1:9100
+    pub addr: SocketAddr,
+    /// Bearer token required on every request
+    pub token: String,
+}
+
+/// Compare a provided token against the expected secret in constant time
+pub fn token_matches(provided: &str, expected: &str) -> bool {
+    let (a, b) = (provided.as_bytes(), expected.as_bytes());
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Runtime knobs shared between the worker and control interfaces
+#[derive(Debug, Clone)]
+pub struct RuntimeControl {
+    paused: Arc<watch::Sender<bool>>,
+    max_difficulty: Arc<watch::Sender<Option<TaskDifficulty>>>,
+    power_saving: Arc<watch::Sender<PowerSaving>>,
+    /// Whether someone is using the machine, with `--only-when-idle`
+    user_active: Arc<watch::Sender<bool>>,
+    /// Measure applied by the thermal governor, with `--thermal-limit`
+    thermal: Arc<watch::Sender<ThermalState>>,
+    /// Why the session should end once the current task is done, if it should
+    stop_reason: Arc<watch::Sender<Option<String>>>,
+}
+
+impl RuntimeControl {
+    pub fn new(max_difficulty: Option<TaskDifficulty>) -> Self {
+        Self {
+            paused: Arc::new(watch::Sender::new(false)),
+            max_difficulty: Arc::new(watch::Sender::new(max_difficulty)),
+            power_saving: Arc::new(watch::Sender::new(PowerSaving::Off)),
+            user_active: Arc::new(watch::Sender::new(false)),
+            thermal: Arc::new(watch::Sender::new(ThermalState::Normal)),
+            stop_reason: Arc::new(watch::Sender::new(None)),
+        }
+    }
+
+    /// Stop fetching new tasks once the current one is done
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    /// Resume fetching tasks
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Whether task fetching is on hold: paused, to save battery, while the machine is in use or
+    /// while it cools down
+    pub fn is_held(&self) -> bool {
+        self.is_paused()
+            || self.power_saving() == PowerSaving::Paused
+            || self.is_user_active()
+            || self.thermal_state() == ThermalState::Cooling
+    }
+
+    /// Wait until the worker is no longer held
+    pub async fn wait_until_resumed(&self) {
+        let mut paused = self.paused.subscribe();
+        let mut power_saving = self.power_saving.subscribe();
+        let mut user_active = self.user_active.subscribe();
+        let mut thermal = self.thermal.subscribe();
+        while self.is_held() {
+            // The senders live as long as `self`, so no channel can close
+            tokio::select! {
+                _ = paused.changed() => {}
+                _ = power_saving.changed() => {}
+                _ = user_active.changed() => {}
+                _ = thermal.changed() => {}
+            }
+        }
+    }
+
+    /// Override the max difficulty; `None` restores adaptive difficulty
+    pub fn set_max_difficulty(&self, difficulty: Option<TaskDifficulty>) {
+        self.max_difficulty.send_replace(difficulty);
+    }
+
+    pub fn max_difficulty(&self) -> Option<TaskDifficulty> {
+        *self.max_difficulty.borrow()
+    }
+
+    /// Apply a power-saving measure chosen by the battery monitor
+    pub fn set_power_saving(&self, power_saving: PowerSaving) {
+        self.power_saving.send_if_modified(|current| {
+            let changed = *current != power_saving;
+            *current = power_saving;
+            changed
+        });
+    }
+
+    pub fn power_saving(&self) -> PowerSaving {
+        *self.power_saving.borrow()
+    }
+
+    /// Hold proving while someone uses the machine, as seen by the idle monitor
+    pub fn set_user_active(&self, active: bool) {
+        self.user_active.send_if_modified(|current| {
+            let changed = *current != active;
+            *current = active;
+            changed
+        });
+    }
+
+    pub fn is_user_active(&self) -> bool {
+        *self.user_active.borrow()
+    }
+
+    /// Apply a measure chosen by the thermal governor
+    pub fn set_thermal_state(&self, state: ThermalState) {
+        self.thermal.send_if_modified(|current| {
+            let changed = *current != state;
+            *current = state;
+            changed
+        });
+    }
+
+    pub fn thermal_state(&self) -> ThermalState {
+        *self.thermal.borrow()
+    }
+
+    /// End the session once the current task is done, e.g. when a target is reached
+    pub fn request_stop(&self, reason: impl Into<String>) {
+        self.stop_reason.send_replace(Some(reason.into()));
+    }
+
+    pub fn stop_reason(&self) -> Option<String> {
+        self.stop_reason.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_wait_until_resumed() {
+        let control = RuntimeControl::new(None);
+        control.pause();
+        assert!(control.is_paused());
+
+        let waiter = control.clone();
+        let handle = tokio::spawn(async move { waiter.wait_until_resumed().await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!handle.is_finished());
+
+        control.resume();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("worker should resume")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_resumed_on_ac_power() {
+        let control = RuntimeControl::new(None);
+        control.set_power_saving(PowerSaving::Paused);
+        assert!(control.is_held());
+        assert!(!control.is_paused());
+
+        let waiter = control.clone();
+        let handle = tokio::spawn(async move { waiter.wait_until_resumed().await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!handle.is_finished());
+
+        control.set_power_saving(PowerSaving::Off);
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("worker should resume")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches("secret", "secret"));
+        assert!(!token_matches("secreT", "secret"));
+        assert!(!token_matches("secret2", "secret"));
+        assert!(!token_matches("", "secret"));
+    }
+
+    #[test]
+    fn test_max_difficulty_override() {
+        let control = RuntimeControl::new(Some(TaskDifficulty::Small));
+        assert_eq!(control.max_difficulty(), Some(TaskDifficulty::Small));
+        control.set_max_difficulty(None);
+        assert_eq!(control.max_difficulty(), None);
+    }
+}
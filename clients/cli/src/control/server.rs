@@ -0,0 +1,348 @@
+//! Authenticated HTTP control API
+//!
+//! Endpoints (all require `Authorization: Bearer <token>`):
+//!
+//! * `GET  /v1/stats`      - session counters, current task, points, last error, pause state,
+//!   difficulty override, network quality and submission receipts
+//! * `POST /v1/pause`      - stop fetching new tasks after the current one
+//! * `POST /v1/resume`     - resume fetching tasks
+//! * `PUT  /v1/difficulty` - set `{"max_difficulty": "large"}`, or `null` for adaptive
+//! * `POST /v1/shutdown`   - stop the prover
+
+use super::{RuntimeControl, token_matches};
+use crate::ipc::TaskQueue;
+use crate::ipc::queue::{QueueStats, QueuedTaskInfo, TaskStage};
+use crate::labels::Labels;
+use crate::network::{NetworkMonitor, NetworkStats};
+use crate::points::PointsTracker;
+use crate::receipts::{ReceiptCounts, ReceiptLedger};
+use crate::session::summary::SummaryRecorder;
+use axum::extract::{Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+
+/// Shared state handed to every request handler
+#[derive(Clone)]
+pub struct ControlApi {
+    node_id: u64,
+    token: Arc<str>,
+    control: RuntimeControl,
+    queue: TaskQueue,
+    shutdown_sender: broadcast::Sender<()>,
+    points: PointsTracker,
+    summary: SummaryRecorder,
+    receipts: Option<ReceiptLedger>,
+}
+
+impl ControlApi {
+    pub fn new(
+        node_id: u64,
+        token: String,
+        control: RuntimeControl,
+        queue: TaskQueue,
+        shutdown_sender: broadcast::Sender<()>,
+    ) -> Self {
+        Self {
+            node_id,
+            token: token.into(),
+            control,
+            queue,
+            shutdown_sender,
+            points: PointsTracker::default(),
+            summary: SummaryRecorder::new(),
+            receipts: None,
+        }
+    }
+
+    /// Report the session's points and last error from these, rather than none.
+    pub fn with_session(mut self, points: PointsTracker, summary: SummaryRecorder) -> Self {
+        self.points = points;
+        self.summary = summary;
+        self
+    }
+
+    /// Report how many submissions were credited from this ledger.
+    pub fn with_receipts(mut self, receipts: Option<ReceiptLedger>) -> Self {
+        self.receipts = receipts;
+        self
+    }
+}
+
+/// Response body of `GET /v1/stats`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusResponse {
+    pub node_id: u64,
+    pub version: String,
+    pub paused: bool,
+    /// Difficulty override, or `None` when adaptive difficulty is in effect
+    pub max_difficulty: Option<String>,
+    pub stats: QueueStats,
+    /// Task being proved, or else the oldest task held
+    #[serde(default)]
+    pub current_task: Option<QueuedTaskInfo>,
+    /// Points of the node, if fetched at least once
+    #[serde(default)]
+    pub points: Option<u64>,
+    /// Message of the most recent error of the session
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// Labels of the node, see [`crate::labels`]
+    #[serde(default)]
+    pub labels: Labels,
+    /// Round-trip times and errors of recent orchestrator requests
+    #[serde(default)]
+    pub network: NetworkStats,
+    /// Submissions credited, pending and flagged, see [`crate::receipts`]
+    #[serde(default)]
+    pub receipts: Option<ReceiptCounts>,
+}
+
+/// Request body of `PUT /v1/difficulty`
+#[derive(Debug, Deserialize)]
+struct DifficultyRequest {
+    max_difficulty: Option<String>,
+}
+
+/// Serve the control API on an already bound listener until shutdown.
+pub async fn run_control_api(
+    listener: TcpListener,
+    api: ControlApi,
+    mut shutdown: broadcast::Receiver<()>,
+) -> std::io::Result<()> {
+    axum::serve(listener, router(api))
+        .with_graceful_shutdown(async move {
+            let _ = shutdown.recv().await;
+        })
+        .await
+}
+
+fn router(api: ControlApi) -> Router {
+    Router::new()
+        .route("/v1/stats", get(stats))
+        .route("/v1/pause", post(pause))
+        .route("/v1/resume", post(resume))
+        .route("/v1/difficulty", put(set_difficulty))
+        .route("/v1/shutdown", post(shutdown))
+        .route_layer(middleware::from_fn_with_state(api.clone(), require_token))
+        .with_state(api)
+}
+
+async fn require_token(State(api): State<ControlApi>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| token_matches(token, &api.token));
+    if !authorized {
+        return error(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token");
+    }
+    next.run(request).await
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+fn status(api: &ControlApi) -> StatusResponse {
+    StatusResponse {
+        node_id: api.node_id,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        paused: api.control.is_paused(),
+        max_difficulty: api
+            .control
+            .max_difficulty()
+            .map(|difficulty| difficulty.as_str_name().to_string()),
+        stats: api.queue.stats(),
+        current_task: current_task(api.queue.list()),
+        points: api.points.snapshot(Instant::now()).node_points,
+        last_error: api.summary.snapshot().last_error,
+        labels: crate::labels::node_labels(),
+        network: NetworkMonitor::global().stats(Instant::now()),
+        receipts: api.receipts.as_ref().map(ReceiptLedger::counts),
+    }
+}
+
+fn current_task(tasks: Vec<QueuedTaskInfo>) -> Option<QueuedTaskInfo> {
+    let proving = tasks
+        .iter()
+        .position(|task| task.stage == TaskStage::Proving)
+        .unwrap_or(0);
+    tasks.into_iter().nth(proving)
+}
+
+async fn stats(State(api): State<ControlApi>) -> Json<StatusResponse> {
+    Json(status(&api))
+}
+
+async fn pause(State(api): State<ControlApi>) -> Json<StatusResponse> {
+    api.control.pause();
+    Json(status(&api))
+}
+
+async fn resume(State(api): State<ControlApi>) -> Json<StatusResponse> {
+    api.control.resume();
+    Json(status(&api))
+}
+
+async fn set_difficulty(
+    State(api): State<ControlApi>,
+    Json(body): Json<DifficultyRequest>,
+) -> Response {
+    let difficulty = match body.max_difficulty.as_deref() {
+        None => None,
+        Some(name) => match crate::validate_difficulty(name) {
+            Some(difficulty) => Some(difficulty),
+            None => {
+                return error(
+                    StatusCode::BAD_REQUEST,
+                    &format!("Invalid difficulty level: {}", name),
+                );
+            }
+        },
+    };
+    api.control.set_max_difficulty(difficulty);
+    Json(status(&api)).into_response()
+}
+
+async fn shutdown(State(api): State<ControlApi>) -> StatusCode {
+    let _ = api.shutdown_sender.send(());
+    StatusCode::ACCEPTED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::TaskDifficulty;
+
+    const TOKEN: &str = "test-token";
+
+    async fn spawn_api() -> (String, ControlApi, broadcast::Receiver<()>) {
+        let (shutdown_sender, shutdown_receiver) = broadcast::channel(1);
+        let api = ControlApi::new(
+            42,
+            TOKEN.to_string(),
+            RuntimeControl::new(None),
+            TaskQueue::new(),
+            shutdown_sender.clone(),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(run_control_api(
+            listener,
+            api.clone(),
+            shutdown_sender.subscribe(),
+        ));
+        (base_url, api, shutdown_receiver)
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_or_wrong_token() {
+        let (base_url, _api, _shutdown) = spawn_api().await;
+        let client = reqwest::Client::new();
+
+        let response = client
+            .get(format!("{}/v1/stats", base_url))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let response = client
+            .get(format!("{}/v1/stats", base_url))
+            .bearer_auth("wrong")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_pause_resume_and_difficulty() {
+        let (base_url, api, _shutdown) = spawn_api().await;
+        let client = reqwest::Client::new();
+
+        let status: StatusResponse = client
+            .post(format!("{}/v1/pause", base_url))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(status.paused);
+        assert!(api.control.is_paused());
+
+        client
+            .post(format!("{}/v1/resume", base_url))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert!(!api.control.is_paused());
+
+        let status: StatusResponse = client
+            .put(format!("{}/v1/difficulty", base_url))
+            .bearer_auth(TOKEN)
+            .json(&serde_json::json!({ "max_difficulty": "large" }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(status.max_difficulty.as_deref(), Some("LARGE"));
+        assert_eq!(api.control.max_difficulty(), Some(TaskDifficulty::Large));
+
+        let response = client
+            .put(format!("{}/v1/difficulty", base_url))
+            .bearer_auth(TOKEN)
+            .json(&serde_json::json!({ "max_difficulty": "huge" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_current_task_prefers_proving() {
+        let task = |task_id: &str, stage| QueuedTaskInfo {
+            task_id: task_id.to_string(),
+            program_id: "fib_input_initial".to_string(),
+            difficulty: "SMALL".to_string(),
+            num_inputs: 1,
+            stage,
+            age_secs: 0,
+        };
+        assert_eq!(current_task(Vec::new()), None);
+        let current = current_task(vec![
+            task("a", TaskStage::Fetched),
+            task("b", TaskStage::Proving),
+        ]);
+        assert_eq!(current.map(|task| task.task_id).as_deref(), Some("b"));
+        let current = current_task(vec![task("a", TaskStage::Fetched)]);
+        assert_eq!(current.map(|task| task.task_id).as_deref(), Some("a"));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_signals_session() {
+        let (base_url, _api, mut shutdown) = spawn_api().await;
+        let response = reqwest::Client::new()
+            .post(format!("{}/v1/shutdown", base_url))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::ACCEPTED);
+        assert!(shutdown.recv().await.is_ok());
+    }
+}
@@ -0,0 +1,143 @@
+//! Fetch broker served over a Unix socket by one instance per host
+//!
+//! Requests and replies are exchanged as newline-delimited JSON, one request per line, each
+//! answered by exactly one reply line.
+
+use super::{BrokerReply, BrokerRequest, FetchSchedule, open_lock_file, try_lock};
+use crate::consts::cli_consts::coordination;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+
+/// Broker lock of this process, once it serves the broker, held until exit
+static BROKER_LOCK: Mutex<Option<File>> = Mutex::new(None);
+
+/// Serve the broker from this process unless another instance holds the broker lock.
+///
+/// Returns whether this process serves the broker. A stale socket file left behind by an
+/// instance that exited is removed before binding.
+pub fn try_serve(socket_path: &Path, lock_path: &Path) -> bool {
+    let mut held = BROKER_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if held.is_some() {
+        return true;
+    }
+
+    let Ok(file) = open_lock_file(lock_path) else {
+        return false;
+    };
+    if !matches!(try_lock(&file), Ok(true)) {
+        return false;
+    }
+    let Ok(listener) = bind(socket_path) else {
+        return false;
+    };
+    tokio::spawn(serve(listener));
+    *held = Some(file);
+    true
+}
+
+fn bind(socket_path: &Path) -> io::Result<UnixListener> {
+    if socket_path.exists() {
+        std::fs::remove_file(socket_path)?;
+    }
+    UnixListener::bind(socket_path)
+}
+
+/// Hand out fetch slots to every instance that connects.
+async fn serve(listener: UnixListener) {
+    let schedule = Arc::new(Mutex::new(
+        FetchSchedule::new(coordination::fetch_stagger()),
+    ));
+    loop {
+        if let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(handle_connection(stream, schedule.clone()));
+        }
+    }
+}
+
+async fn handle_connection(stream: UnixStream, schedule: Arc<Mutex<FetchSchedule>>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let Ok(request) = serde_json::from_str::<BrokerRequest>(&line) else {
+            break;
+        };
+        let reply = schedule
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .handle(request, Instant::now());
+
+        let Ok(mut payload) = serde_json::to_string(&reply) else {
+            break;
+        };
+        payload.push('\n');
+        if writer.write_all(payload.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Send a single request to the broker and wait for its reply.
+pub async fn request(socket_path: &Path, request: &BrokerRequest) -> io::Result<BrokerReply> {
+    let stream = UnixStream::connect(socket_path).await?;
+    let (reader, mut writer) = stream.into_split();
+
+    let mut payload = serde_json::to_string(request)?;
+    payload.push('\n');
+    writer.write_all(payload.as_bytes()).await?;
+
+    let line = BufReader::new(reader)
+        .lines()
+        .next_line()
+        .await?
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Broker closed the connection without replying",
+            )
+        })?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_slots_over_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("coordinator.sock");
+        tokio::spawn(serve(bind(&socket_path).unwrap()));
+
+        let first = request(&socket_path, &BrokerRequest::FetchSlot)
+            .await
+            .unwrap();
+        assert_eq!(first.wait_ms, 0);
+
+        // A second instance asking right away waits for its turn
+        let second = request(&socket_path, &BrokerRequest::FetchSlot)
+            .await
+            .unwrap();
+        assert!(second.wait_ms > 0);
+        assert!(second.wait_ms <= coordination::FETCH_STAGGER_MS);
+
+        // After a rate limit, every instance waits it out
+        request(
+            &socket_path,
+            &BrokerRequest::RateLimited {
+                retry_after_ms: 60_000,
+            },
+        )
+        .await
+        .unwrap();
+        let held = request(&socket_path, &BrokerRequest::FetchSlot)
+            .await
+            .unwrap();
+        assert!(held.wait_ms > coordination::FETCH_STAGGER_MS);
+    }
+}
@@ -0,0 +1,223 @@
+//! Coordination between CLI instances on one host
+//!
+//! Instances on one host share the orchestrator's rate limit, so running several of them makes
+//! each one hit it sooner. With `--coordinate`, instances take turns fetching tasks through a small
+//! broker: the first instance to lock `coordinator.lock` next to the config file serves
+//! `coordinator.sock`, and every instance asks it for a fetch slot before polling for a task.
+//! Slots are handed out a few seconds apart, and a rate-limit response seen by any instance holds
+//! back the fetches of all of them. When the broker's instance exits, the next instance to ask for
+//! a slot takes over. Coordination is best effort: an instance that cannot reach a broker fetches
+//! on its own schedule.
+//!
+//! Independently of `--coordinate`, each session locks its node ID with [`claim_node`], so a
+//! second instance proving for the same node can be reported.
+
+#[cfg(unix)]
+mod broker;
+
+use crate::consts::cli_consts::coordination;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// File name of the broker socket, placed next to the config file (~/.nexus/).
+const SOCKET_FILE: &str = "coordinator.sock";
+
+/// File name of the lock held by the instance serving the broker.
+const LOCK_FILE: &str = "coordinator.lock";
+
+/// Node ID locked by this process, with its lock file, held until exit
+static NODE_LOCK: Mutex<Option<(u64, File)>> = Mutex::new(None);
+
+/// Lock `node_id` for this process, next to the config file.
+///
+/// Returns `Ok(false)` if another instance already holds the lock, i.e. is proving for the same
+/// node. The lock is released when the process exits or claims another node.
+pub fn claim_node(config_path: &Path, node_id: u64) -> io::Result<bool> {
+    let mut held = NODE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if matches!(&*held, Some((held_id, _)) if *held_id == node_id) {
+        return Ok(true);
+    }
+
+    let file = open_lock_file(&config_path.with_file_name(format!("node-{}.lock", node_id)))?;
+    if !try_lock(&file)? {
+        return Ok(false);
+    }
+    *held = Some((node_id, file));
+    Ok(true)
+}
+
+/// Release the node locked by this process, if any, e.g. before handing it to a restarted process.
+pub fn release_node() {
+    let mut held = NODE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    *held = None;
+}
+
+/// Open a lock file, creating it and its directory if needed.
+fn open_lock_file(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// Take an exclusive lock on `file` without blocking; `Ok(false)` if another process holds it.
+fn try_lock(file: &File) -> io::Result<bool> {
+    match file.try_lock_exclusive() {
+        Ok(()) => Ok(true),
+        Err(e) if e.raw_os_error() == fs2::lock_contended_error().raw_os_error() => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Request sent by an instance to the broker.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum BrokerRequest {
+    /// Reserve the next fetch slot.
+    FetchSlot,
+    /// The orchestrator rate-limited a fetch; hold back all instances this long.
+    RateLimited { retry_after_ms: u64 },
+}
+
+/// Reply of the broker to every request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct BrokerReply {
+    /// How long to wait before fetching
+    wait_ms: u64,
+}
+
+/// Fetch slots handed out by the broker, at least `stagger` apart
+#[derive(Debug)]
+pub struct FetchSchedule {
+    next_slot: Option<Instant>,
+    stagger: Duration,
+}
+
+impl FetchSchedule {
+    pub fn new(stagger: Duration) -> Self {
+        Self {
+            next_slot: None,
+            stagger,
+        }
+    }
+
+    /// Reserve the next free slot, returning how long to wait for it.
+    pub fn reserve(&mut self, now: Instant) -> Duration {
+        let slot = self.next_slot.map_or(now, |next| next.max(now));
+        self.next_slot = Some(slot + self.stagger);
+        slot - now
+    }
+
+    /// Hand out no slot before `until`.
+    pub fn hold_until(&mut self, until: Instant) {
+        self.next_slot = Some(self.next_slot.map_or(until, |next| next.max(until)));
+    }
+
+    /// Answer a broker request.
+    fn handle(&mut self, request: BrokerRequest, now: Instant) -> BrokerReply {
+        let wait = match request {
+            BrokerRequest::FetchSlot => self.reserve(now),
+            BrokerRequest::RateLimited { retry_after_ms } => {
+                self.hold_until(now + Duration::from_millis(retry_after_ms));
+                Duration::ZERO
+            }
+        };
+        BrokerReply {
+            wait_ms: wait.as_millis() as u64,
+        }
+    }
+}
+
+/// Client of the host's fetch broker, serving one if no instance does
+#[derive(Debug, Clone)]
+pub struct Coordinator {
+    socket_path: PathBuf,
+    lock_path: PathBuf,
+}
+
+impl Coordinator {
+    /// Coordinate with the instances sharing the directory of `config_path`.
+    pub fn new(config_path: &Path) -> Self {
+        Self {
+            socket_path: config_path.with_file_name(SOCKET_FILE),
+            lock_path: config_path.with_file_name(LOCK_FILE),
+        }
+    }
+
+    /// Wait for this instance's turn to fetch a task.
+    pub async fn wait_for_fetch_slot(&self) {
+        if let Some(reply) = self.request(BrokerRequest::FetchSlot).await {
+            tokio::time::sleep(Duration::from_millis(reply.wait_ms)).await;
+        }
+    }
+
+    /// Hold back the fetches of all instances after a rate-limit response, for `retry_after` if
+    /// the orchestrator gave one.
+    pub async fn report_rate_limit(&self, retry_after: Option<Duration>) {
+        let hold = retry_after.unwrap_or(coordination::rate_limit_hold());
+        let request = BrokerRequest::RateLimited {
+            retry_after_ms: hold.as_millis() as u64,
+        };
+        let _ = self.request(request).await;
+    }
+
+    /// Send `request` to the broker, serving it from this process if none answers.
+    #[cfg(unix)]
+    async fn request(&self, request: BrokerRequest) -> Option<BrokerReply> {
+        let timeout = coordination::broker_timeout();
+        if let Ok(Ok(reply)) =
+            tokio::time::timeout(timeout, broker::request(&self.socket_path, &request)).await
+        {
+            return Some(reply);
+        }
+        if !broker::try_serve(&self.socket_path, &self.lock_path) {
+            return None;
+        }
+        tokio::time::timeout(timeout, broker::request(&self.socket_path, &request))
+            .await
+            .ok()?
+            .ok()
+    }
+
+    /// The broker needs a Unix socket; elsewhere every instance fetches on its own schedule.
+    #[cfg(not(unix))]
+    async fn request(&self, _request: BrokerRequest) -> Option<BrokerReply> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_staggers_and_holds() {
+        let start = Instant::now();
+        let stagger = Duration::from_secs(2);
+        let mut schedule = FetchSchedule::new(stagger);
+
+        // Instances asking at once are spread out
+        assert_eq!(schedule.reserve(start), Duration::ZERO);
+        assert_eq!(schedule.reserve(start), stagger);
+        assert_eq!(schedule.reserve(start + stagger), stagger);
+
+        // Once the schedule has caught up, slots are free again
+        let later = start + Duration::from_secs(60);
+        assert_eq!(schedule.reserve(later), Duration::ZERO);
+
+        // A rate limit holds back every instance, but does not shorten a longer hold
+        schedule.hold_until(later + Duration::from_secs(30));
+        schedule.hold_until(later + Duration::from_secs(10));
+        assert_eq!(schedule.reserve(later), Duration::from_secs(30));
+    }
+}
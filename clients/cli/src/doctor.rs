@@ -0,0 +1,535 @@
+//! Environment diagnostics for `nexus-cli doctor`
+//!
+//! Runs a series of independent checks and prints PASS/WARN/FAIL with a remediation hint
+//! for anything that needs attention.
+
+use crate::config::Config;
+use crate::consts::cli_consts::PROJECTED_MEMORY_REQUIREMENT;
+use crate::consts::cli_consts::doctor::{
+    CLOCK_SKEW_FAIL_SECS, CLOCK_SKEW_WARN_SECS, MIN_OPEN_FILES, REACHABILITY_TIMEOUT_SECS,
+};
+use crate::environment::Environment;
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::version::{ConstraintType, VersionRequirements};
+use chrono::{DateTime, Utc};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Recommended total RAM per difficulty level, in GB
+const RECOMMENDED_RAM_GB: &[(TaskDifficulty, u64)] = &[
+    (TaskDifficulty::Small, 4),
+    (TaskDifficulty::SmallMedium, 4),
+    (TaskDifficulty::Medium, 8),
+    (TaskDifficulty::Large, 8),
+    (TaskDifficulty::ExtraLarge, 16),
+    (TaskDifficulty::ExtraLarge2, 16),
+    (TaskDifficulty::ExtraLarge3, 32),
+    (TaskDifficulty::ExtraLarge4, 32),
+    (TaskDifficulty::ExtraLarge5, 64),
+];
+
+/// Recommended total RAM for proving tasks of `difficulty`, in GB
+pub fn recommended_ram_gb(difficulty: TaskDifficulty) -> u64 {
+    RECOMMENDED_RAM_GB
+        .iter()
+        .find(|(d, _)| *d == difficulty)
+        .map(|(_, gb)| *gb)
+        .unwrap_or(0)
+}
+
+/// Highest difficulty whose recommended RAM fits in `total_ram_gb`, if any
+pub fn highest_supported_difficulty(total_ram_gb: f64) -> Option<TaskDifficulty> {
+    RECOMMENDED_RAM_GB
+        .iter()
+        .rev()
+        .find(|(_, gb)| total_ram_gb >= *gb as f64)
+        .map(|(difficulty, _)| *difficulty)
+}
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "\x1b[1;32m[PASS]\x1b[0m"),
+            CheckStatus::Warn => write!(f, "\x1b[1;33m[WARN]\x1b[0m"),
+            CheckStatus::Fail => write!(f, "\x1b[1;31m[FAIL]\x1b[0m"),
+        }
+    }
+}
+
+/// Result of a single check, with a remediation hint when it did not pass
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    pub(crate) fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    pub(crate) fn warn(
+        name: &'static str,
+        detail: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    pub(crate) fn fail(
+        name: &'static str,
+        detail: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn print(&self) {
+        println!("{} {:<14} {}", self.status, self.name, self.detail);
+        if let Some(hint) = &self.hint {
+            println!("       {:<14} → {}", "", hint);
+        }
+    }
+}
+
+/// Run all checks, print the report, and return whether every check passed or only warned.
+pub async fn run_doctor(
+    config_path: &Path,
+    environment: &Environment,
+    max_difficulty: Option<TaskDifficulty>,
+) -> bool {
+    println!("Running Nexus CLI diagnostics...\n");
+
+    let (reachability, server_time) = check_orchestrator(environment).await;
+    let results = vec![
+        check_config(config_path),
+        reachability,
+        check_clock_skew(server_time, Utc::now()),
+        check_memory(
+            crate::system::total_memory_gb(),
+            crate::system::num_cores(),
+            max_difficulty,
+        ),
+        check_cpu_features(),
+        check_open_files(open_file_limit()),
+        check_version(config_path).await,
+    ];
+    print_report(&results)
+}
+
+/// Print `results` and a tally, and return whether every check passed or only warned.
+pub(crate) fn print_report(results: &[CheckResult]) -> bool {
+    for result in results {
+        result.print();
+    }
+
+    let failures = results
+        .iter()
+        .filter(|r| r.status == CheckStatus::Fail)
+        .count();
+    let warnings = results
+        .iter()
+        .filter(|r| r.status == CheckStatus::Warn)
+        .count();
+    println!(
+        "\n{} checks: {} passed, {} warnings, {} failed",
+        results.len(),
+        results.len() - failures - warnings,
+        warnings,
+        failures
+    );
+    failures == 0
+}
+
+/// The config file exists, parses, and holds a usable node ID.
+fn check_config(config_path: &Path) -> CheckResult {
+    const NAME: &str = "Config";
+    if !config_path.exists() {
+        return CheckResult::warn(
+            NAME,
+            format!("No config file at {}", config_path.display()),
+            "Run `nexus-cli register-user` and `nexus-cli register-node`, or pass --node-id to `start`",
+        );
+    }
+    let config = match Config::load_from_file(config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            return CheckResult::fail(
+                NAME,
+                format!("Could not read {}: {}", config_path.display(), e),
+                "Run `nexus-cli config validate` for hints on each problem",
+            );
+        }
+    };
+    if config.node_id.is_empty() {
+        return CheckResult::warn(
+            NAME,
+            "No node ID registered",
+            "Run `nexus-cli register-node`",
+        );
+    }
+    if config.node_id.parse::<u64>().is_err() {
+        return CheckResult::fail(
+            NAME,
+            format!("Node ID '{}' is not a number", config.node_id),
+            "Run `nexus-cli logout` and register the node again",
+        );
+    }
+    CheckResult::pass(NAME, format!("Node ID {}", config.node_id))
+}
+
+/// The orchestrator answers HTTP requests. Also returns the server's `Date` header, if any.
+async fn check_orchestrator(environment: &Environment) -> (CheckResult, Option<DateTime<Utc>>) {
+    const NAME: &str = "Orchestrator";
+    let url = environment.orchestrator_url();
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(REACHABILITY_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            return (
+                CheckResult::fail(NAME, format!("HTTP client error: {}", e), "Report this bug"),
+                None,
+            );
+        }
+    };
+
+    let started = Instant::now();
+    match client.get(url).send().await {
+        Ok(response) => {
+            let server_time = response
+                .headers()
+                .get(reqwest::header::DATE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_http_date);
+            let result = CheckResult::pass(
+                NAME,
+                format!(
+                    "{} reachable in {}ms",
+                    environment,
+                    started.elapsed().as_millis()
+                ),
+            );
+            (result, server_time)
+        }
+        Err(e) => (
+            CheckResult::fail(
+                NAME,
+                format!("Cannot reach {}: {}", url, e),
+                "Check your internet connection, proxy and firewall settings",
+            ),
+            None,
+        ),
+    }
+}
+
+/// Parse an HTTP `Date` header, e.g. `Tue, 15 Nov 1994 08:12:31 GMT`.
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+/// The local clock agrees with the orchestrator's.
+fn check_clock_skew(server_time: Option<DateTime<Utc>>, local_time: DateTime<Utc>) -> CheckResult {
+    const NAME: &str = "Clock";
+    let Some(server_time) = server_time else {
+        return CheckResult::warn(
+            NAME,
+            "Could not read the orchestrator's clock",
+            "Make sure the orchestrator is reachable",
+        );
+    };
+    let skew = (local_time - server_time).num_seconds();
+    let detail = format!("Local clock is {}s off the orchestrator's", skew);
+    let hint = "Enable automatic time synchronization (NTP) in your OS settings";
+    match skew.unsigned_abs() {
+        s if s >= CLOCK_SKEW_FAIL_SECS => CheckResult::fail(NAME, detail, hint),
+        s if s >= CLOCK_SKEW_WARN_SECS => CheckResult::warn(NAME, detail, hint),
+        _ => CheckResult::pass(NAME, detail),
+    }
+}
+
+/// Total RAM covers the requested (or smallest) difficulty and at least one prover thread.
+pub(crate) fn check_memory(
+    total_ram_gb: f64,
+    num_cores: usize,
+    max_difficulty: Option<TaskDifficulty>,
+) -> CheckResult {
+    const NAME: &str = "Memory";
+    let per_thread_gb = PROJECTED_MEMORY_REQUIREMENT as f64 / 1024.0 / 1024.0 / 1024.0;
+    let threads = ((total_ram_gb * 0.75 / per_thread_gb) as usize).min(num_cores);
+
+    let Some(highest) = highest_supported_difficulty(total_ram_gb) else {
+        return CheckResult::fail(
+            NAME,
+            format!(
+                "{:.1}GB RAM is below the {}GB minimum",
+                total_ram_gb,
+                recommended_ram_gb(TaskDifficulty::Small)
+            ),
+            "Run the prover on a machine with more memory",
+        );
+    };
+    if let Some(requested) = max_difficulty {
+        let needed = recommended_ram_gb(requested);
+        if total_ram_gb < needed as f64 {
+            return CheckResult::warn(
+                NAME,
+                format!(
+                    "{:.1}GB RAM, {} recommends {}GB",
+                    total_ram_gb,
+                    requested.as_str_name(),
+                    needed
+                ),
+                format!("Use --max-difficulty {} or lower", highest.as_str_name()),
+            );
+        }
+    }
+    if threads == 0 {
+        return CheckResult::warn(
+            NAME,
+            format!(
+                "{:.1}GB RAM leaves no headroom for a prover thread",
+                total_ram_gb
+            ),
+            "Close other memory-heavy applications before starting the prover",
+        );
+    }
+    CheckResult::pass(
+        NAME,
+        format!(
+            "{:.1}GB RAM, up to {} threads, difficulty up to {}",
+            total_ram_gb,
+            threads,
+            highest.as_str_name()
+        ),
+    )
+}
+
+/// The CPU supports the SIMD extensions the prover benefits from.
+fn check_cpu_features() -> CheckResult {
+    const NAME: &str = "CPU features";
+    cfg_if::cfg_if! {
+        if #[cfg(target_arch = "x86_64")] {
+            let features: Vec<&str> = [
+                ("sse4.2", std::arch::is_x86_feature_detected!("sse4.2")),
+                ("avx2", std::arch::is_x86_feature_detected!("avx2")),
+                ("avx512f", std::arch::is_x86_feature_detected!("avx512f")),
+            ]
+            .into_iter()
+            .filter_map(|(name, present)| present.then_some(name))
+            .collect();
+            if features.contains(&"avx2") {
+                CheckResult::pass(NAME, format!("x86_64 with {}", features.join(", ")))
+            } else {
+                CheckResult::warn(
+                    NAME,
+                    "AVX2 not available",
+                    "Proving will work but be noticeably slower on this CPU",
+                )
+            }
+        } else if #[cfg(target_arch = "aarch64")] {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                CheckResult::pass(NAME, "aarch64 with neon")
+            } else {
+                CheckResult::warn(
+                    NAME,
+                    "NEON not available",
+                    "Proving will work but be noticeably slower on this CPU",
+                )
+            }
+        } else {
+            CheckResult::warn(
+                NAME,
+                format!("Unrecognized architecture {}", std::env::consts::ARCH),
+                "Proving is only tested on x86_64 and aarch64",
+            )
+        }
+    }
+}
+
+/// Soft limit on open file descriptors, if it can be determined.
+fn open_file_limit() -> Option<u64> {
+    if let Ok(limits) = std::fs::read_to_string("/proc/self/limits") {
+        return parse_proc_open_files(&limits);
+    }
+    if cfg!(unix) {
+        let output = std::process::Command::new("sh")
+            .args(["-c", "ulimit -n"])
+            .output()
+            .ok()?;
+        let limit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return if limit == "unlimited" {
+            Some(u64::MAX)
+        } else {
+            limit.parse().ok()
+        };
+    }
+    None
+}
+
+/// Extract the soft limit from the "Max open files" line of `/proc/<pid>/limits`.
+fn parse_proc_open_files(limits: &str) -> Option<u64> {
+    let line = limits.lines().find(|l| l.starts_with("Max open files"))?;
+    let soft = line
+        .trim_start_matches("Max open files")
+        .split_whitespace()
+        .next()?;
+    if soft == "unlimited" {
+        Some(u64::MAX)
+    } else {
+        soft.parse().ok()
+    }
+}
+
+/// The open file limit leaves room for network connections and logs.
+fn check_open_files(limit: Option<u64>) -> CheckResult {
+    const NAME: &str = "Open files";
+    match limit {
+        None if cfg!(windows) => CheckResult::pass(NAME, "Not limited on Windows"),
+        None => CheckResult::warn(
+            NAME,
+            "Could not determine the open file limit",
+            format!("Make sure `ulimit -n` is at least {}", MIN_OPEN_FILES),
+        ),
+        Some(limit) if limit < MIN_OPEN_FILES => CheckResult::warn(
+            NAME,
+            format!("Limit is {}", limit),
+            format!(
+                "Raise it with `ulimit -n {}` before starting",
+                MIN_OPEN_FILES
+            ),
+        ),
+        Some(u64::MAX) => CheckResult::pass(NAME, "Unlimited"),
+        Some(limit) => CheckResult::pass(NAME, format!("Limit is {}", limit)),
+    }
+}
+
+/// This build satisfies the published version requirements and is the latest release on the
+/// configured update channel.
+async fn check_version(config_path: &Path) -> CheckResult {
+    const NAME: &str = "Version";
+    let current_version = env!("CARGO_PKG_VERSION");
+    let download_hint =
+        "Download the latest release from https://github.com/nexus-xyz/nexus-cli/releases";
+
+    match VersionRequirements::fetch().await {
+        Ok(requirements) => {
+            if let Ok(Some(violation)) =
+                requirements.check_version_constraints(current_version, None, None)
+            {
+                match violation.constraint_type {
+                    ConstraintType::Blocking => {
+                        return CheckResult::fail(NAME, violation.message, download_hint);
+                    }
+                    ConstraintType::Warning => {
+                        return CheckResult::warn(NAME, violation.message, download_hint);
+                    }
+                    ConstraintType::Notice => {}
+                }
+            }
+        }
+        Err(e) => {
+            return CheckResult::warn(
+                NAME,
+                format!("Could not fetch version requirements: {}", e),
+                "Check your internet connection",
+            );
+        }
+    }
+
+    let channel = Config::load_or_default(config_path)
+        .map(|config| config.update.channel)
+        .unwrap_or_default();
+    match crate::version::checker::check_for_new_version(current_version, channel).await {
+        Some(message) => CheckResult::warn(NAME, message, download_hint),
+        None => CheckResult::pass(NAME, format!("{} is up to date", current_version)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_skew_thresholds() {
+        let now = Utc::now();
+        let skewed = |secs: i64| check_clock_skew(Some(now - chrono::Duration::seconds(secs)), now);
+        assert_eq!(skewed(2).status, CheckStatus::Pass);
+        assert_eq!(
+            skewed(-(CLOCK_SKEW_WARN_SECS as i64)).status,
+            CheckStatus::Warn
+        );
+        assert_eq!(
+            skewed(CLOCK_SKEW_FAIL_SECS as i64).status,
+            CheckStatus::Fail
+        );
+        assert_eq!(check_clock_skew(None, now).status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn test_parse_http_date() {
+        let date = parse_http_date("Tue, 15 Nov 1994 08:12:31 GMT").unwrap();
+        assert_eq!(date.to_rfc3339(), "1994-11-15T08:12:31+00:00");
+        assert!(parse_http_date("yesterday").is_none());
+    }
+
+    #[test]
+    fn test_memory_vs_difficulty() {
+        assert_eq!(check_memory(2.0, 8, None).status, CheckStatus::Fail);
+        assert_eq!(check_memory(16.0, 8, None).status, CheckStatus::Pass);
+        let low = check_memory(8.0, 8, Some(TaskDifficulty::ExtraLarge));
+        assert_eq!(low.status, CheckStatus::Warn);
+        assert!(low.hint.unwrap().contains("LARGE"));
+    }
+
+    #[test]
+    fn test_open_files() {
+        let limits = "Limit                     Soft Limit           Hard Limit           Units\n\
+                      Max open files            1024                 524288               files\n";
+        assert_eq!(parse_proc_open_files(limits), Some(1024));
+        assert_eq!(check_open_files(Some(256)).status, CheckStatus::Warn);
+        assert_eq!(check_open_files(Some(4096)).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_config_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        assert_eq!(check_config(&path).status, CheckStatus::Warn);
+
+        std::fs::write(&path, "{not json").unwrap();
+        assert_eq!(check_config(&path).status, CheckStatus::Fail);
+
+        std::fs::write(&path, r#"{"node_id": "12345"}"#).unwrap();
+        assert_eq!(check_config(&path).status, CheckStatus::Pass);
+    }
+}
@@ -1,6 +1,18 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
 
+/// Name of the environment that takes its URL from `--orchestrator-url`
+pub const CUSTOM_ENVIRONMENT: &str = "custom";
+
+/// Orchestrator deployment defined in the `environments` section of the config file, e.g. a
+/// private test deployment
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EnvironmentDefinition {
+    pub orchestrator_url: String,
+}
+
 /// Represents the different deployment environments available for the CLI.
 #[derive(Clone, PartialEq, Eq, Default)]
 pub enum Environment {
@@ -19,6 +31,14 @@ impl Environment {
             Environment::Custom { orchestrator_url } => orchestrator_url,
         }
     }
+
+    /// Key for per-environment settings in the config file: `production`, or the custom URL.
+    pub fn config_key(&self) -> String {
+        match self {
+            Environment::Production => "production".to_string(),
+            Environment::Custom { orchestrator_url } => orchestrator_url.clone(),
+        }
+    }
 }
 
 impl FromStr for Environment {
@@ -46,3 +66,92 @@ impl Debug for Environment {
         write!(f, "Environment::{}, URL: {}", self, self.orchestrator_url())
     }
 }
+
+/// Pick the environment from `--env` (or `NEXUS_ENVIRONMENT`) and `--orchestrator-url`.
+///
+/// `name` is `production`, `custom`, or one of the `defined` environments; without a name the
+/// default is production. An orchestrator URL overrides the URL of any environment, and is
+/// required for `custom`.
+pub fn resolve_environment(
+    name: Option<&str>,
+    orchestrator_url: Option<String>,
+    defined: &BTreeMap<String, EnvironmentDefinition>,
+) -> Result<Environment, String> {
+    if let Some(orchestrator_url) = orchestrator_url {
+        return Ok(Environment::Custom { orchestrator_url });
+    }
+    let Some(name) = name.map(str::trim).filter(|name| !name.is_empty()) else {
+        return Ok(Environment::default());
+    };
+    if let Ok(environment) = name.parse::<Environment>() {
+        return Ok(environment);
+    }
+    if name.eq_ignore_ascii_case(CUSTOM_ENVIRONMENT) {
+        return Err("--env custom requires --orchestrator-url".to_string());
+    }
+    match defined.get(name) {
+        Some(definition) => Ok(Environment::Custom {
+            orchestrator_url: definition.orchestrator_url.clone(),
+        }),
+        None => {
+            let mut known = vec!["production".to_string(), CUSTOM_ENVIRONMENT.to_string()];
+            known.extend(defined.keys().cloned());
+            Err(format!(
+                "Unknown environment '{}'. Available: {}. Define others under `environments` in the config file",
+                name,
+                known.join(", ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defined() -> BTreeMap<String, EnvironmentDefinition> {
+        BTreeMap::from([(
+            "staging".to_string(),
+            EnvironmentDefinition {
+                orchestrator_url: "https://staging.example".to_string(),
+            },
+        )])
+    }
+
+    fn custom(url: &str) -> Environment {
+        Environment::Custom {
+            orchestrator_url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_named_environments() {
+        assert_eq!(
+            resolve_environment(None, None, &defined()),
+            Ok(Environment::Production)
+        );
+        assert_eq!(
+            resolve_environment(Some("Production"), None, &defined()),
+            Ok(Environment::Production)
+        );
+        assert_eq!(
+            resolve_environment(Some("staging"), None, &defined()),
+            Ok(custom("https://staging.example"))
+        );
+        assert!(resolve_environment(Some("qa"), None, &defined()).is_err());
+    }
+
+    #[test]
+    fn test_orchestrator_url_overrides_environment() {
+        let url = Some("http://localhost:8080".to_string());
+        assert_eq!(
+            resolve_environment(Some("custom"), url.clone(), &defined()),
+            Ok(custom("http://localhost:8080"))
+        );
+        assert_eq!(
+            resolve_environment(Some("staging"), url, &defined()),
+            Ok(custom("http://localhost:8080"))
+        );
+        assert!(resolve_environment(Some("custom"), None, &defined()).is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! WebSocket event stream for external dashboards
+//!
+//! With `--events-ws <ADDR>`, every worker [`Event`] is serialized to JSON and broadcast
+//! to all clients connected to `ws://<ADDR>/events`. Slow clients never hold up the
+//! prover: once a client falls more than [`EVENT_STREAM_BUFFER_SIZE`] events behind,
+//! the backlog is skipped and the client receives `{"lagged": <count>}` instead.
+
+use crate::consts::cli_consts::{EVENT_QUEUE_SIZE, EVENT_STREAM_BUFFER_SIZE};
+use crate::control::token_matches;
+use crate::events::Event;
+use axum::Router;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc};
+
+/// Settings for the WebSocket event stream
+#[derive(Debug, Clone)]
+pub struct EventStreamConfig {
+    /// Address to listen on, e.g. 127.0.0.1:9200
+    pub addr: SocketAddr,
+    /// Optional token, passed as a bearer token or `?token=` query parameter
+    pub token: Option<String>,
+}
+
+#[derive(Clone)]
+struct EventStream {
+    events: broadcast::Sender<Event>,
+    token: Option<Arc<str>>,
+    shutdown: broadcast::Sender<()>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuthQuery {
+    token: Option<String>,
+}
+
+/// Bind the event stream and start broadcasting events from `event_receiver`.
+///
+/// Returns the receiver the UI should consume instead: events are forwarded to it
+/// unchanged, after being copied to WebSocket subscribers.
+pub async fn start_event_stream(
+    config: EventStreamConfig,
+    event_receiver: mpsc::Receiver<Event>,
+    shutdown_sender: &broadcast::Sender<()>,
+) -> std::io::Result<mpsc::Receiver<Event>> {
+    let listener = TcpListener::bind(config.addr).await?;
+    let (events, _) = broadcast::channel(EVENT_STREAM_BUFFER_SIZE);
+    let stream = EventStream {
+        events: events.clone(),
+        token: config.token.map(Into::into),
+        shutdown: shutdown_sender.clone(),
+    };
+
+    let mut shutdown = shutdown_sender.subscribe();
+    tokio::spawn(async move {
+        // Errors are ignored: printing here would corrupt the TUI
+        let _ = axum::serve(listener, router(stream))
+            .with_graceful_shutdown(async move {
+                let _ = shutdown.recv().await;
+            })
+            .await;
+    });
+
+    Ok(tee_events(event_receiver, events))
+}
+
+/// Copy every event to `tap` while passing it through to the returned receiver.
+fn tee_events(
+    mut source: mpsc::Receiver<Event>,
+    tap: broadcast::Sender<Event>,
+) -> mpsc::Receiver<Event> {
+    let (sender, receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        while let Some(event) = source.recv().await {
+            // Fails only when nobody is subscribed, which is fine
+            let _ = tap.send(event.clone());
+            if sender.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+fn router(stream: EventStream) -> Router {
+    Router::new()
+        .route("/events", get(subscribe))
+        .with_state(stream)
+}
+
+async fn subscribe(
+    State(stream): State<EventStream>,
+    Query(query): Query<AuthQuery>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Response {
+    if let Some(expected) = &stream.token {
+        let provided = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .or(query.token.as_deref());
+        if !provided.is_some_and(|token| token_matches(token, expected)) {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+    }
+
+    let events = stream.events.subscribe();
+    let shutdown = stream.shutdown.subscribe();
+    ws.on_upgrade(move |socket| forward_events(socket, events, shutdown))
+}
+
+async fn forward_events(
+    mut socket: WebSocket,
+    mut events: broadcast::Receiver<Event>,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    loop {
+        let received = tokio::select! {
+            _ = shutdown.recv() => break,
+            received = events.recv() => received,
+        };
+        let payload = match received {
+            Ok(event) => match serde_json::to_string(&event) {
+                Ok(payload) => payload,
+                Err(_) => continue,
+            },
+            Err(RecvError::Lagged(skipped)) => serde_json::json!({ "lagged": skipped }).to_string(),
+            Err(RecvError::Closed) => break,
+        };
+        if socket.send(Message::Text(payload.into())).await.is_err() {
+            break;
+        }
+    }
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventType;
+    use crate::logging::LogLevel;
+    use futures::StreamExt;
+
+    async fn spawn_stream(
+        token: Option<&str>,
+    ) -> (SocketAddr, mpsc::Sender<Event>, mpsc::Receiver<Event>) {
+        // Bind to an ephemeral port first to learn a free address
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let (event_sender, event_receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+        let (shutdown_sender, _) = broadcast::channel(1);
+        let passthrough = start_event_stream(
+            EventStreamConfig {
+                addr,
+                token: token.map(str::to_string),
+            },
+            event_receiver,
+            &shutdown_sender,
+        )
+        .await
+        .unwrap();
+        (addr, event_sender, passthrough)
+    }
+
+    #[tokio::test]
+    async fn test_events_are_broadcast_and_passed_through() {
+        let (addr, event_sender, mut passthrough) = spawn_stream(Some("secret")).await;
+        let (mut socket, _) =
+            tokio_tungstenite::connect_async(format!("ws://{}/events?token=secret", addr))
+                .await
+                .unwrap();
+
+        let event = Event::task_fetcher_with_level(
+            "Step 1 of 4: Fetching task...".to_string(),
+            EventType::Refresh,
+            LogLevel::Info,
+        );
+        event_sender.send(event.clone()).await.unwrap();
+
+        assert_eq!(passthrough.recv().await.unwrap(), event);
+        let message = socket.next().await.unwrap().unwrap();
+        let json: serde_json::Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+        assert_eq!(json["msg"], "Step 1 of 4: Fetching task...");
+        assert_eq!(json["event_type"], "Refresh");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_token() {
+        let (addr, _event_sender, _passthrough) = spawn_stream(Some("secret")).await;
+        let result = tokio_tungstenite::connect_async(format!("ws://{}/events", addr)).await;
+        assert!(result.is_err());
+    }
+}
@@ -3,10 +3,14 @@
 //! Types and implementations for worker events and logging
 
 use crate::logging::{LogLevel, should_log_with_env};
+use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
 use chrono::Local;
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
 use std::fmt::Display;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum Worker {
     /// Worker that fetches tasks from the orchestrator and processes them.
     TaskFetcher,
@@ -16,7 +20,7 @@ pub enum Worker {
     ProofSubmitter,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, strum::Display)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, strum::Display)]
 pub enum EventType {
     Success,
     Error,
@@ -25,16 +29,174 @@ pub enum EventType {
     StateChange,
 }
 
-/// Represents the current state in the proof pipeline
-#[derive(Debug, Copy, Clone, Eq, PartialEq, strum::Display)]
+/// Represents the current state in the proof pipeline, see [`crate::session::state_machine`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, strum::Display)]
 pub enum ProverState {
+    /// Not working on a task, e.g. paused or shutting down
+    Idle,
+    /// Waiting for a task from the orchestrator
+    Fetching,
     /// Computing the proof
     Proving,
-    /// Waiting before fetching next task (idle state)
-    Waiting,
+    /// Submitting the proof
+    Submitting,
+    /// Done with a task, waiting before fetching the next one
+    Cooldown,
+}
+
+impl ProverState {
+    /// Whether the work loop may move from this state to `next`. Any state may give up on its
+    /// task and go back to `Idle` or `Cooldown`, e.g. when a proof fails or the worker restarts.
+    pub fn can_transition_to(self, next: ProverState) -> bool {
+        use ProverState::*;
+        matches!(
+            (self, next),
+            (_, Idle)
+                | (_, Cooldown)
+                | (Idle | Cooldown, Fetching)
+                | (Fetching, Proving)
+                | (Proving, Submitting)
+        )
+    }
+}
+
+/// Rate-limit budget of the task fetcher, published on every fetch attempt
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskFetchState {
+    /// Fetch requests made in the current rate-limit window
+    pub requests_in_window: u32,
+    /// Fetch requests allowed per rate-limit window
+    pub max_requests_per_window: u32,
+    /// Length of the rate-limit window
+    pub window: Duration,
+    /// Wait imposed before the next fetch when this state was published
+    pub backoff: Duration,
+    /// When the next fetch may go out; `None` if it may go out now
+    pub next_fetch_at: Option<Instant>,
+    /// Recent HTTP 429 responses from the orchestrator, oldest first
+    pub rate_limits: Vec<RateLimitHit>,
+    /// Maintenance window announced by the orchestrator, while it lasts
+    pub maintenance: Option<MaintenanceWindow>,
+}
+
+impl TaskFetchState {
+    /// Time left until the next fetch may go out.
+    pub fn time_until_next_fetch(&self, now: Instant) -> Duration {
+        self.next_fetch_at
+            .map_or(Duration::ZERO, |at| at.saturating_duration_since(now))
+    }
+
+    /// Number of 429 responses received within `period` of `now`.
+    pub fn rate_limits_within(&self, now: Instant, period: Duration) -> usize {
+        self.rate_limits
+            .iter()
+            .filter(|hit| now.saturating_duration_since(hit.at) <= period)
+            .count()
+    }
+}
+
+/// Planned orchestrator downtime, announced by a 503 maintenance response
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaintenanceWindow {
+    /// Announcement from the orchestrator, if it sent one
+    pub message: Option<String>,
+    /// When the window is expected to end, if the orchestrator said
+    pub ends_at: Option<Instant>,
+}
+
+impl MaintenanceWindow {
+    /// Time left until the window is expected to end.
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.ends_at.map(|at| at.saturating_duration_since(now))
+    }
+}
+
+/// A rate-limit (HTTP 429) response received while fetching tasks
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitHit {
+    pub at: Instant,
+    /// Local time of the response, formatted like [`Event::timestamp`]
+    pub timestamp: String,
+    /// Retry delay requested by the orchestrator, if any
+    pub retry_after_secs: Option<u32>,
+}
+
+/// What an event reports, for consumers that track state rather than show messages
+///
+/// Carried alongside the message, so the dashboard, the session summary and notifications do not
+/// depend on its wording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EventPayload {
+    /// Started fetching a task
+    FetchStarted,
+    /// Got a task to prove
+    TaskFetched { task_id: String },
+    /// Fetching a task failed
+    FetchFailed,
+    /// Fetching a task was rate-limited by the orchestrator (HTTP 429)
+    RateLimited { retry_after_secs: Option<u32> },
+    /// Started proving a task
+    ProofStarted {
+        task_id: String,
+        #[serde(serialize_with = "serialize_difficulty")]
+        difficulty: TaskDifficulty,
+        #[serde(serialize_with = "serialize_task_type")]
+        task_type: TaskType,
+    },
+    /// Some of a task's proofs were reused from the proof cache
+    ProofCacheHit {
+        task_id: String,
+        reused: usize,
+        total: usize,
+    },
+    /// Finished proving a task
+    ProofGenerated { task_id: String },
+    /// Proving a task failed
+    ProofFailed { task_id: String },
+    /// The proof of a task was accepted by the orchestrator
+    ProofSubmitted { task_id: String },
+    /// Submitting the proof of a task failed after all retries
+    SubmissionFailed { task_id: String },
+    /// A task was fetched, proved and submitted
+    TaskCompleted {
+        task_id: String,
+        duration_secs: u64,
+        #[serde(serialize_with = "serialize_difficulty")]
+        difficulty: TaskDifficulty,
+        #[serde(serialize_with = "serialize_task_type")]
+        task_type: TaskType,
+    },
+    /// A prover subprocess crashed and the worker is restarting
+    WorkerCrashed { restarts: u32 },
+    /// Prover subprocesses keep crashing and the worker restarts less often
+    CrashLoop { recent_crashes: usize },
+    /// Proofs take much longer than earlier in the session, see [`crate::session::slowdown`]
+    ProofSlowdown {
+        #[serde(serialize_with = "serialize_difficulty")]
+        difficulty: TaskDifficulty,
+        baseline_secs: u64,
+        recent_secs: u64,
+        /// Suspected cause, e.g. a CPU clock drop, if one stands out
+        cause: Option<String>,
+    },
+}
+
+fn serialize_difficulty<S: Serializer>(
+    difficulty: &TaskDifficulty,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(difficulty.as_str_name())
 }
 
-#[derive(Debug, Clone)]
+fn serialize_task_type<S: Serializer>(
+    task_type: &TaskType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(task_type.as_str_name())
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Event {
     pub worker: Worker,
     pub msg: String,
@@ -43,6 +205,9 @@ pub struct Event {
     pub log_level: LogLevel,
     /// Optional state information for state change events
     pub prover_state: Option<ProverState>,
+    /// What the event reports, for events that change state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<EventPayload>,
 }
 
 impl PartialEq for Event {
@@ -53,6 +218,7 @@ impl PartialEq for Event {
             && self.event_type == other.event_type
             && self.log_level == other.log_level
             && self.prover_state == other.prover_state
+            && self.payload == other.payload
         // Note: We don't compare state_start_time since Instant doesn't implement Eq
     }
 }
@@ -63,25 +229,33 @@ impl Event {
     fn new(worker: Worker, msg: String, event_type: EventType, log_level: LogLevel) -> Self {
         Self {
             worker,
-            msg,
+            msg: redact(msg),
             timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
             event_type,
             log_level,
             prover_state: None,
+            payload: None,
         }
     }
 
     pub fn state_change(state: ProverState, msg: String) -> Self {
         Self {
             worker: Worker::TaskFetcher,
-            msg,
+            msg: redact(msg),
             timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
             event_type: EventType::StateChange,
             log_level: LogLevel::Info,
             prover_state: Some(state),
+            payload: None,
         }
     }
 
+    /// Attach what the event reports.
+    pub fn with_payload(mut self, payload: EventPayload) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
     pub fn task_fetcher_with_level(
         msg: String,
         event_type: EventType,
@@ -120,6 +294,14 @@ impl Event {
     }
 }
 
+/// `msg` with identifiers masked, if `--redact` is on
+fn redact(msg: String) -> String {
+    match crate::redact::redact(&msg) {
+        Cow::Borrowed(_) => msg,
+        Cow::Owned(redacted) => redacted,
+    }
+}
+
 impl Display for Event {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} [{}] {}", self.event_type, self.timestamp, self.msg)
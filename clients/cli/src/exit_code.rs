@@ -0,0 +1,194 @@
+//! Process exit codes
+//!
+//! Failures are grouped into ranges so that scripts can react to the cause without parsing
+//! error messages:
+//!
+//! | Code | Meaning                                                     |
+//! |------|-------------------------------------------------------------|
+//! | 0    | Success                                                     |
+//! | 1    | Unclassified failure                                        |
+//! | 2    | Invalid command-line arguments                              |
+//! | 10   | Config file missing, or no user or node registered in it    |
+//! | 11   | Config file unreadable or invalid                           |
+//! | 20   | Rejected by the orchestrator as unauthorized (HTTP 401/403) |
+//! | 21   | Node or user not found by the orchestrator (HTTP 404)       |
+//! | 30   | Network failure talking to the orchestrator                 |
+//! | 31   | Rate limited by the orchestrator (HTTP 429)                 |
+//! | 32   | Orchestrator server error (HTTP 5xx)                        |
+//! | 40   | Proof generation failed                                     |
+//! | 50   | This CLI version is blocked; an update is required          |
+//! | 51   | Service not available in this country                       |
+//! | 70   | Chaos mode: worker loop deadlocked                          |
+//! | 71   | Chaos mode: workers still running after shutdown            |
+
+use crate::config::ConfigError;
+use crate::orchestrator::error::OrchestratorError;
+use crate::prover::ProverError;
+use std::error::Error;
+use thiserror::Error;
+
+/// Invalid command-line input, reported with [`ExitCode::Usage`]
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct UsageError(pub String);
+
+/// Documented exit code for each class of failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Failure,
+    Usage,
+    ConfigMissing,
+    ConfigInvalid,
+    Unauthorized,
+    NotFound,
+    Network,
+    RateLimited,
+    ServerError,
+    Prover,
+    VersionBlocked,
+    RegionBlocked,
+}
+
+impl ExitCode {
+    pub const fn code(self) -> i32 {
+        match self {
+            ExitCode::Failure => 1,
+            ExitCode::Usage => 2,
+            ExitCode::ConfigMissing => 10,
+            ExitCode::ConfigInvalid => 11,
+            ExitCode::Unauthorized => 20,
+            ExitCode::NotFound => 21,
+            ExitCode::Network => 30,
+            ExitCode::RateLimited => 31,
+            ExitCode::ServerError => 32,
+            ExitCode::Prover => 40,
+            ExitCode::VersionBlocked => 50,
+            ExitCode::RegionBlocked => 51,
+        }
+    }
+
+    /// Exit the process with this code.
+    pub fn exit(self) -> ! {
+        std::process::exit(self.code())
+    }
+
+    /// Classify an error by the first recognized error in its source chain.
+    pub fn from_error(error: &(dyn Error + 'static)) -> Self {
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if let Some(code) = Self::classify(error) {
+                return code;
+            }
+            current = error.source();
+        }
+        ExitCode::Failure
+    }
+
+    fn classify(error: &(dyn Error + 'static)) -> Option<Self> {
+        if error.is::<UsageError>() {
+            return Some(ExitCode::Usage);
+        }
+        if let Some(error) = error.downcast_ref::<ConfigError>() {
+            return Some(match error {
+                ConfigError::Missing
+                | ConfigError::UserNotRegistered
+                | ConfigError::NodeNotRegistered => ExitCode::ConfigMissing,
+                ConfigError::Unreadable(_)
+                | ConfigError::InvalidNodeId
+                | ConfigError::Invalid(_) => ExitCode::ConfigInvalid,
+            });
+        }
+        if let Some(error) = error.downcast_ref::<OrchestratorError>() {
+            return Some(match error {
+                OrchestratorError::Http { status, .. } => match status {
+                    401 | 403 => ExitCode::Unauthorized,
+                    404 => ExitCode::NotFound,
+                    429 => ExitCode::RateLimited,
+                    500..=599 => ExitCode::ServerError,
+                    _ => ExitCode::Network,
+                },
+                OrchestratorError::Decode(_)
+                | OrchestratorError::MalformedResponse(_)
+                | OrchestratorError::Reqwest(_)
+                | OrchestratorError::Transport(_) => ExitCode::Network,
+                OrchestratorError::AlreadyAccepted => ExitCode::Failure,
+            });
+        }
+        if error.is::<reqwest::Error>() {
+            return Some(ExitCode::Network);
+        }
+        if error.is::<ProverError>() {
+            return Some(ExitCode::Prover);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn http_error(status: u16) -> Box<dyn Error> {
+        Box::new(OrchestratorError::Http {
+            status,
+            message: String::new(),
+            headers: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_orchestrator_errors() {
+        assert_eq!(
+            ExitCode::from_error(http_error(401).as_ref()),
+            ExitCode::Unauthorized
+        );
+        assert_eq!(
+            ExitCode::from_error(http_error(404).as_ref()),
+            ExitCode::NotFound
+        );
+        assert_eq!(
+            ExitCode::from_error(http_error(429).as_ref()),
+            ExitCode::RateLimited
+        );
+        assert_eq!(
+            ExitCode::from_error(http_error(503).as_ref()),
+            ExitCode::ServerError
+        );
+        let transport: Box<dyn Error> =
+            Box::new(OrchestratorError::Transport("refused".to_string()));
+        assert_eq!(ExitCode::from_error(transport.as_ref()), ExitCode::Network);
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let missing: Box<dyn Error> = Box::new(ConfigError::from(std::io::Error::from(
+            std::io::ErrorKind::NotFound,
+        )));
+        assert_eq!(
+            ExitCode::from_error(missing.as_ref()),
+            ExitCode::ConfigMissing
+        );
+        assert_eq!(ExitCode::from_error(missing.as_ref()).code(), 10);
+
+        let invalid: Box<dyn Error> = Box::new(ConfigError::InvalidNodeId);
+        assert_eq!(
+            ExitCode::from_error(invalid.as_ref()),
+            ExitCode::ConfigInvalid
+        );
+    }
+
+    #[test]
+    fn test_source_chain_and_fallback() {
+        #[derive(Debug, Error)]
+        #[error("proving failed")]
+        struct Wrapper(#[source] ProverError);
+
+        let wrapped: Box<dyn Error> =
+            Box::new(Wrapper(ProverError::Subprocess("killed".to_string())));
+        assert_eq!(ExitCode::from_error(wrapped.as_ref()), ExitCode::Prover);
+
+        let other: Box<dyn Error> = "something else".into();
+        assert_eq!(ExitCode::from_error(other.as_ref()), ExitCode::Failure);
+    }
+}
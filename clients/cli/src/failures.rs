@@ -0,0 +1,189 @@
+//! Failure bundles
+//!
+//! With `start --capture-failures`, every failed proof leaves a JSON bundle in
+//! `~/.nexus/failures/` holding what is needed to reproduce it: the task and its inputs, the
+//! error, and the machine and CLI version it failed on. `nexus-cli report <bundle>` shows a
+//! bundle and, with `--upload`, sends it to the orchestrator's support endpoint.
+
+use crate::cli_messages::{print_info, print_success};
+use crate::environment::Environment;
+use crate::exit_code::UsageError;
+use crate::orchestrator::OrchestratorClient;
+use crate::task::Task;
+use crate::tr;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const FAILURES_DIR: &str = "failures";
+
+/// Directory of failure bundles, `~/.nexus/failures` next to the config file.
+pub fn failures_dir(config_path: &Path) -> PathBuf {
+    config_path.with_file_name(FAILURES_DIR)
+}
+
+/// Machine a proof failed on
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SystemInfo {
+    pub os: String,
+    pub arch: String,
+    pub cpu_cores: usize,
+    pub total_memory_gb: f64,
+    /// Workers the failed proof ran on
+    pub num_workers: usize,
+}
+
+/// Everything recorded about one failed proof
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FailureBundle {
+    pub cli_version: String,
+    /// RFC 3339 time of the failure
+    pub captured_at: String,
+    pub task_id: String,
+    pub program_id: String,
+    pub task_type: String,
+    pub difficulty: String,
+    /// Public inputs of the task, hex encoded
+    pub inputs: Vec<String>,
+    pub error: String,
+    /// Orchestrator the task came from
+    pub environment: String,
+    pub system: SystemInfo,
+}
+
+impl FailureBundle {
+    /// Record a failure of `task` on this machine.
+    pub fn new(task: &Task, error: &str, environment: &Environment, num_workers: usize) -> Self {
+        Self {
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            captured_at: crate::clock::utc_now().to_rfc3339(),
+            task_id: task.task_id.clone(),
+            program_id: task.program_id.clone(),
+            task_type: task.task_type.as_str_name().to_string(),
+            difficulty: task.difficulty.as_str_name().to_string(),
+            inputs: task
+                .all_inputs()
+                .iter()
+                .map(|input| to_hex(input))
+                .collect(),
+            error: error.to_string(),
+            environment: environment.orchestrator_url().to_string(),
+            system: SystemInfo {
+                os: std::env::consts::OS.to_string(),
+                arch: std::env::consts::ARCH.to_string(),
+                cpu_cores: crate::system::num_cores(),
+                total_memory_gb: crate::system::total_memory_gb(),
+                num_workers,
+            },
+        }
+    }
+
+    /// File name of the bundle: capture time, then the task ID
+    fn file_name(&self) -> String {
+        let time: String = self
+            .captured_at
+            .chars()
+            .take(19)
+            .filter(|c| c.is_ascii_digit() || *c == 'T')
+            .collect();
+        let task_id: String = self
+            .task_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}-{}.json", time, task_id)
+    }
+
+    /// Write the bundle to `dir`, returning the path of the new file.
+    pub async fn write(&self, dir: &Path) -> io::Result<PathBuf> {
+        let json = serde_json::to_vec_pretty(self).map_err(io::Error::other)?;
+        tokio::fs::create_dir_all(dir).await?;
+        let path = dir.join(self.file_name());
+        tokio::fs::write(&path, json).await?;
+        Ok(path)
+    }
+
+    /// Read a bundle written by [`FailureBundle::write`].
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json = std::fs::read(path)?;
+        serde_json::from_slice(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Show the bundle at `path` and, if `upload` is set, send it to the orchestrator of `environment`.
+pub async fn report(
+    path: &Path,
+    environment: Environment,
+    upload: bool,
+) -> Result<(), Box<dyn Error>> {
+    let bundle = FailureBundle::load(path)
+        .map_err(|e| UsageError(tr!("report-unreadable", path = path.display(), error = e)))?;
+    print_info(
+        &tr!("report-task", task_id = bundle.task_id),
+        &tr!(
+            "report-details",
+            program_id = bundle.program_id,
+            captured_at = bundle.captured_at,
+            version = bundle.cli_version
+        ),
+    );
+    println!("{}", bundle.error);
+
+    if !upload {
+        println!("{}", tr!("report-upload-hint", path = path.display()));
+        return Ok(());
+    }
+    OrchestratorClient::new(environment)
+        .submit_failure_report(&bundle)
+        .await?;
+    print_success(&tr!("report-uploaded"), &bundle.task_id);
+    Ok(())
+}
+
+/// Lowercase hex encoding of `bytes`
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decode hex as written by [`to_hex`]
+pub(crate) fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err("Invalid hex".to_string());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| e.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+
+    #[tokio::test]
+    async fn test_write_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let task = Task::new(
+            "task/1".to_string(),
+            "fib_input_initial".to_string(),
+            vec![0x01, 0xab],
+            TaskType::ProofRequired,
+            TaskDifficulty::Small,
+        );
+        let bundle = FailureBundle::new(&task, "guest panicked", &Environment::Production, 2);
+        assert_eq!(bundle.inputs, vec!["01ab".to_string()]);
+
+        let path = bundle.write(dir.path()).await.unwrap();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(name.ends_with("-task_1.json"), "{}", name);
+        assert_eq!(FailureBundle::load(&path).unwrap(), bundle);
+    }
+}
@@ -0,0 +1,283 @@
+//! Fleet manifests
+//!
+//! A fleet manifest lists the nodes `fleet start` runs on this machine, in TOML:
+//!
+//! ```toml
+//! # Settings for every node that does not set its own
+//! [defaults]
+//! max_threads = 2
+//! max_difficulty = "medium"
+//! labels = { site = "home-lab" }
+//!
+//! [[nodes]]
+//! node_id = 12345
+//!
+//! [[nodes]]
+//! node_id = 67890
+//! max_threads = 4
+//! max_difficulty = "large"
+//! # Prove only overnight, in local time
+//! schedule = "22:00-07:00"
+//! labels = { rack = "b" }
+//! ```
+//!
+//! The labels of a node are added to the default labels, replacing those with the same key.
+
+use crate::labels::{self, Labels};
+use crate::nexus_orchestrator::TaskDifficulty;
+use chrono::NaiveTime;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("Failed to read fleet manifest {path}: {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Invalid fleet manifest: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Fleet manifest lists no nodes")]
+    Empty,
+    #[error("Node {0} is listed more than once")]
+    DuplicateNode(u64),
+    #[error("{0}")]
+    Invalid(String),
+}
+
+/// Daily window of local time to prove in, e.g. `22:00-07:00`; a window may span midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvingWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl ProvingWindow {
+    /// Whether `time` is within the window
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl FromStr for ProvingWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid schedule '{}', expected HH:MM-HH:MM", s);
+        let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+        let parse =
+            |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").map_err(|_| invalid());
+        let window = Self {
+            start: parse(start)?,
+            end: parse(end)?,
+        };
+        if window.start == window.end {
+            return Err(format!("Schedule '{}' is empty", s));
+        }
+        Ok(window)
+    }
+}
+
+/// Settings under `[defaults]`, for every node that does not set its own
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NodeDefaults {
+    pub max_threads: Option<u32>,
+    pub max_difficulty: Option<String>,
+    pub schedule: Option<String>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// A node under `[[nodes]]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FleetNode {
+    pub node_id: u64,
+    pub max_threads: Option<u32>,
+    pub max_difficulty: Option<String>,
+    pub schedule: Option<String>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// Nodes to run on this machine, as written in the manifest
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FleetManifest {
+    #[serde(default)]
+    pub defaults: NodeDefaults,
+    #[serde(default)]
+    pub nodes: Vec<FleetNode>,
+}
+
+/// A node to run, with the defaults applied
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSpec {
+    pub node_id: u64,
+    pub max_threads: Option<u32>,
+    pub max_difficulty: Option<TaskDifficulty>,
+    /// Window to prove in; outside of it the node is paused
+    pub schedule: Option<ProvingWindow>,
+    pub labels: Labels,
+}
+
+impl FleetManifest {
+    /// Read the manifest at `path`.
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| ManifestError::Read {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    /// The nodes to run, in manifest order, with the defaults applied and every setting checked.
+    pub fn node_specs(&self) -> Result<Vec<NodeSpec>, ManifestError> {
+        if self.nodes.is_empty() {
+            return Err(ManifestError::Empty);
+        }
+        let mut seen = HashSet::new();
+        self.nodes
+            .iter()
+            .map(|node| {
+                if !seen.insert(node.node_id) {
+                    return Err(ManifestError::DuplicateNode(node.node_id));
+                }
+                self.resolve(node)
+                    .map_err(|e| ManifestError::Invalid(format!("Node {}: {}", node.node_id, e)))
+            })
+            .collect()
+    }
+
+    fn resolve(&self, node: &FleetNode) -> Result<NodeSpec, String> {
+        let defaults = &self.defaults;
+        let max_threads = node.max_threads.or(defaults.max_threads);
+        if max_threads == Some(0) {
+            return Err("max_threads must be at least 1".to_string());
+        }
+        let max_difficulty = node
+            .max_difficulty
+            .as_ref()
+            .or(defaults.max_difficulty.as_ref())
+            .map(|name| {
+                crate::validate_difficulty(name)
+                    .ok_or_else(|| format!("Invalid difficulty level '{}'", name))
+            })
+            .transpose()?;
+        let schedule = node
+            .schedule
+            .as_ref()
+            .or(defaults.schedule.as_ref())
+            .map(|schedule| schedule.parse())
+            .transpose()?;
+        let updates = defaults
+            .labels
+            .iter()
+            .chain(&node.labels)
+            .map(|(key, value)| labels::parse_label(&format!("{}={}", key, value)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut labels = Labels::new();
+        labels::merge(&mut labels, updates)?;
+        Ok(NodeSpec {
+            node_id: node.node_id,
+            max_threads,
+            max_difficulty,
+            schedule,
+            labels,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+        [defaults]
+        max_threads = 2
+        max_difficulty = "medium"
+        labels = { site = "home-lab", rack = "a" }
+
+        [[nodes]]
+        node_id = 12345
+
+        [[nodes]]
+        node_id = 67890
+        max_threads = 4
+        max_difficulty = "large"
+        schedule = "22:00-07:00"
+        labels = { rack = "b" }
+    "#;
+
+    #[test]
+    fn test_nodes_fall_back_to_defaults() {
+        let manifest: FleetManifest = toml::from_str(MANIFEST).unwrap();
+        let specs = manifest.node_specs().unwrap();
+        assert_eq!(specs.len(), 2);
+
+        assert_eq!(specs[0].node_id, 12345);
+        assert_eq!(specs[0].max_threads, Some(2));
+        assert_eq!(specs[0].max_difficulty, Some(TaskDifficulty::Medium));
+        assert_eq!(specs[0].schedule, None);
+        assert_eq!(specs[0].labels["rack"], "a");
+
+        assert_eq!(specs[1].max_threads, Some(4));
+        assert_eq!(specs[1].max_difficulty, Some(TaskDifficulty::Large));
+        assert!(specs[1].schedule.is_some());
+        assert_eq!(specs[1].labels["site"], "home-lab");
+        assert_eq!(specs[1].labels["rack"], "b");
+    }
+
+    #[test]
+    fn test_invalid_manifests_are_rejected() {
+        let duplicate: FleetManifest =
+            toml::from_str("[[nodes]]\nnode_id = 1\n[[nodes]]\nnode_id = 1\n").unwrap();
+        assert!(matches!(
+            duplicate.node_specs(),
+            Err(ManifestError::DuplicateNode(1))
+        ));
+
+        let difficulty: FleetManifest =
+            toml::from_str("[[nodes]]\nnode_id = 1\nmax_difficulty = \"huge\"\n").unwrap();
+        assert!(matches!(
+            difficulty.node_specs(),
+            Err(ManifestError::Invalid(_))
+        ));
+
+        assert!(matches!(
+            FleetManifest::default().node_specs(),
+            Err(ManifestError::Empty)
+        ));
+        // A misspelled setting is an error rather than silently ignored
+        assert!(
+            toml::from_str::<FleetManifest>("[[nodes]]\nnode_id = 1\nmax_thread = 2\n").is_err()
+        );
+    }
+
+    #[test]
+    fn test_window_spans_midnight() {
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let night: ProvingWindow = "22:00-07:00".parse().unwrap();
+        assert!(night.contains(time(23, 30)));
+        assert!(night.contains(time(6, 59)));
+        assert!(!night.contains(time(7, 0)));
+        assert!(!night.contains(time(12, 0)));
+
+        let day: ProvingWindow = "09:00 - 17:30".parse().unwrap();
+        assert!(day.contains(time(9, 0)));
+        assert!(!day.contains(time(17, 30)));
+
+        assert!("9-5".parse::<ProvingWindow>().is_err());
+        assert!("10:00-10:00".parse::<ProvingWindow>().is_err());
+    }
+}
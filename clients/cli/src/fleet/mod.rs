@@ -0,0 +1,196 @@
+//! Running a fleet of nodes on one machine
+//!
+//! `nexus-cli fleet start --manifest nodes.toml` runs every node listed in a [`manifest`], each in
+//! a `start --headless` process of its own, so nodes keep their labels, keys and memory apart and
+//! a crash takes down a single node. Each process is supervised: it is restarted with a growing
+//! delay when it exits with an error, paused outside its schedule, and polled through a control
+//! API on a local port, with a random token, for the fleet dashboard. On Unix the nodes share a
+//! fetch schedule, see [`crate::coordinator`].
+
+pub mod manifest;
+mod supervisor;
+
+use crate::consts::cli_consts::fleet::MAX_LOG_LINES;
+use crate::control::server::StatusResponse;
+use crate::ui::{self, UiMode};
+use crossterm::{
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use manifest::{FleetManifest, NodeSpec};
+use ratatui::{Terminal, backend::CrosstermBackend};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use supervisor::{NodeLaunch, supervise};
+use tokio::sync::broadcast;
+
+/// What the prover process of a node is doing
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessState {
+    /// Not started yet
+    Starting,
+    /// Running as `pid` since `since`
+    Running { pid: u32, since: Instant },
+    /// Exited, described by `exit`, and started again at `at`
+    Restarting { exit: String, at: Instant },
+    /// Exited for good, after a clean exit or when the fleet stopped
+    Stopped { exit: String },
+}
+
+/// A node of the fleet, as last seen by its supervisor
+#[derive(Debug, Clone)]
+pub struct NodeStatus {
+    pub spec: NodeSpec,
+    pub process: ProcessState,
+    /// Number of times the process was restarted
+    pub restarts: u32,
+    /// Latest answer of the node's control API, while it runs
+    pub stats: Option<StatusResponse>,
+    /// Whether the node is outside its schedule, and so paused
+    pub off_schedule: bool,
+    /// Latest output of the node's process, oldest first
+    pub output: VecDeque<String>,
+}
+
+/// Status of every node, shared between the supervisors and the dashboard
+#[derive(Debug, Clone)]
+pub struct FleetStatus {
+    nodes: Arc<Mutex<Vec<NodeStatus>>>,
+    /// Bumped on every change, so the dashboard redraws only when something changed
+    version: Arc<AtomicU64>,
+}
+
+impl FleetStatus {
+    fn new(specs: &[NodeSpec]) -> Self {
+        let nodes = specs
+            .iter()
+            .map(|spec| NodeStatus {
+                spec: spec.clone(),
+                process: ProcessState::Starting,
+                restarts: 0,
+                stats: None,
+                off_schedule: false,
+                output: VecDeque::new(),
+            })
+            .collect();
+        Self {
+            nodes: Arc::new(Mutex::new(nodes)),
+            version: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Call `f` with the status of every node, in manifest order.
+    pub fn with_nodes<R>(&self, f: impl FnOnce(&[NodeStatus]) -> R) -> R {
+        f(&self.nodes.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Number of changes so far
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    fn update(&self, index: usize, f: impl FnOnce(&mut NodeStatus)) {
+        if let Some(node) = self
+            .nodes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(index)
+        {
+            f(node);
+        }
+        self.version.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn push_output(&self, index: usize, line: String) {
+        self.update(index, |node| {
+            if node.output.len() >= MAX_LOG_LINES {
+                node.output.pop_front();
+            }
+            node.output.push_back(line);
+        });
+    }
+}
+
+/// Options of `fleet start`
+#[derive(Debug, Clone, Default)]
+pub struct FleetOptions {
+    /// Global arguments to pass on to every node, e.g. `--env`
+    pub global_args: Vec<String>,
+    /// Print the output of every node instead of showing the fleet dashboard
+    pub headless: bool,
+    /// Dashboard style
+    pub ui_mode: UiMode,
+    /// Config file, for the dashboard theme
+    pub config_path: PathBuf,
+}
+
+/// Start every node in the manifest at `manifest_path` and supervise them until the user quits.
+pub async fn run_fleet(manifest_path: &Path, options: FleetOptions) -> Result<(), Box<dyn Error>> {
+    let specs = FleetManifest::load(manifest_path)?.node_specs()?;
+    let launch = NodeLaunch {
+        exe: std::env::current_exe()?,
+        global_args: options.global_args.clone(),
+        coordinate: cfg!(unix),
+        echo: options.headless,
+    };
+    let status = FleetStatus::new(&specs);
+    let (shutdown_sender, _) = broadcast::channel(1);
+    let supervisors: Vec<_> = specs
+        .into_iter()
+        .enumerate()
+        .map(|(index, spec)| {
+            tokio::spawn(supervise(
+                launch.clone(),
+                spec,
+                status.clone(),
+                index,
+                shutdown_sender.subscribe(),
+            ))
+        })
+        .collect();
+
+    if options.headless {
+        crate::print_cmd_info!(
+            "Fleet",
+            "Running {} nodes, press Ctrl+C to stop",
+            supervisors.len()
+        );
+        let _ = tokio::signal::ctrl_c().await;
+    } else {
+        run_dashboard(&status, &options).await?;
+    }
+
+    // Let every node finish its step in progress
+    crate::print_cmd_info!("Fleet", "Stopping {} nodes...", supervisors.len());
+    let _ = shutdown_sender.send(());
+    for supervisor in supervisors {
+        let _ = supervisor.await;
+    }
+    Ok(())
+}
+
+async fn run_dashboard(status: &FleetStatus, options: &FleetOptions) -> Result<(), Box<dyn Error>> {
+    let theme = ui::theme::resolve(
+        &ui::theme::themes_dir(&options.config_path),
+        crate::config::Config::load_from_file(&options.config_path)
+            .ok()
+            .and_then(|config| config.theme)
+            .as_deref(),
+    );
+
+    enable_raw_mode()?;
+    let mut stdout = std::io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = ui::run_fleet_ui(&mut terminal, status, options.ui_mode, theme).await;
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    Ok(result?)
+}
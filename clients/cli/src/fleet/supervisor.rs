@@ -0,0 +1,361 @@
+//! Supervision of the prover process of one fleet node
+
+use super::manifest::NodeSpec;
+use super::{FleetStatus, ProcessState};
+use crate::consts::cli_consts::{fleet, supervisor};
+use crate::control::server::StatusResponse;
+use chrono::Local;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Child;
+use tokio::sync::broadcast;
+
+/// How to start the prover process of a node
+#[derive(Debug, Clone)]
+pub struct NodeLaunch {
+    /// The CLI executable
+    pub exe: PathBuf,
+    /// Global arguments to pass on, e.g. `--env`
+    pub global_args: Vec<String>,
+    /// Whether the nodes share a fetch schedule
+    pub coordinate: bool,
+    /// Whether to print the output of the process, prefixed with its node ID
+    pub echo: bool,
+}
+
+impl NodeLaunch {
+    /// Command line of the process for `spec`, serving the control API on `control_addr`
+    fn args(&self, spec: &NodeSpec, control_addr: SocketAddr) -> Vec<String> {
+        let mut args = self.global_args.clone();
+        args.extend([
+            "start".to_string(),
+            "--headless".to_string(),
+            "--node-id".to_string(),
+            spec.node_id.to_string(),
+            "--control-addr".to_string(),
+            control_addr.to_string(),
+        ]);
+        if let Some(max_threads) = spec.max_threads {
+            args.extend(["--max-threads".to_string(), max_threads.to_string()]);
+        }
+        if let Some(difficulty) = spec.max_difficulty {
+            args.extend([
+                "--max-difficulty".to_string(),
+                difficulty.as_str_name().to_string(),
+            ]);
+        }
+        // The config file is shared, so node labels must not be saved in it
+        for (key, value) in &spec.labels {
+            args.extend(["--label".to_string(), format!("{}={}", key, value)]);
+        }
+        if !spec.labels.is_empty() {
+            args.push("--ephemeral-labels".to_string());
+        }
+        if self.coordinate {
+            args.push("--coordinate".to_string());
+        }
+        args
+    }
+}
+
+/// How one run of the process ended
+enum RunOutcome {
+    /// The fleet is stopping
+    Shutdown,
+    /// The process exited on its own
+    Exited {
+        exit: String,
+        success: bool,
+        ran_for: Duration,
+    },
+}
+
+/// Run the process of the node at `index` until the fleet stops, restarting it with a growing
+/// delay whenever it fails.
+pub async fn supervise(
+    launch: NodeLaunch,
+    spec: NodeSpec,
+    status: FleetStatus,
+    index: usize,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    let initial_backoff = Duration::from_secs(supervisor::INITIAL_RESTART_BACKOFF_SECS);
+    let max_backoff = Duration::from_secs(supervisor::MAX_RESTART_BACKOFF_SECS);
+    let mut backoff = initial_backoff;
+    loop {
+        let (exit, ran_for) = match run_once(&launch, &spec, &status, index, &mut shutdown).await {
+            RunOutcome::Shutdown => {
+                let exit = "stopped".to_string();
+                status.update(index, |node| node.process = ProcessState::Stopped { exit });
+                return;
+            }
+            RunOutcome::Exited {
+                exit,
+                success: true,
+                ..
+            } => {
+                status.update(index, |node| node.process = ProcessState::Stopped { exit });
+                return;
+            }
+            RunOutcome::Exited { exit, ran_for, .. } => (exit, ran_for),
+        };
+
+        // A process that ran for a while before failing is not crash-looping
+        if ran_for >= max_backoff {
+            backoff = initial_backoff;
+        }
+        let at = Instant::now() + backoff;
+        status.update(index, |node| {
+            node.process = ProcessState::Restarting { exit, at };
+            node.stats = None;
+        });
+        tokio::select! {
+            _ = shutdown.recv() => {
+                let exit = "stopped".to_string();
+                status.update(index, |node| node.process = ProcessState::Stopped { exit });
+                return;
+            }
+            _ = tokio::time::sleep(backoff) => {}
+        }
+        status.update(index, |node| node.restarts += 1);
+        backoff = (backoff * 2).min(max_backoff);
+    }
+}
+
+async fn run_once(
+    launch: &NodeLaunch,
+    spec: &NodeSpec,
+    status: &FleetStatus,
+    index: usize,
+    shutdown: &mut broadcast::Receiver<()>,
+) -> RunOutcome {
+    let failed = |exit: String| RunOutcome::Exited {
+        exit,
+        success: false,
+        ran_for: Duration::ZERO,
+    };
+    let control_addr = match free_local_addr() {
+        Ok(addr) => addr,
+        Err(e) => return failed(format!("No free port for the control API: {}", e)),
+    };
+    let token = format!("{:032x}", rand::random::<u128>());
+    let mut child = match tokio::process::Command::new(&launch.exe)
+        .args(launch.args(spec, control_addr))
+        // Passed in the environment so the token is not visible in the process list
+        .env("NEXUS_CONTROL_TOKEN", &token)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return failed(format!("Failed to start: {}", e)),
+    };
+
+    let started = Instant::now();
+    let pid = child.id().unwrap_or_default();
+    status.update(index, |node| {
+        node.process = ProcessState::Running {
+            pid,
+            since: started,
+        }
+    });
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(forward_output(
+            stdout,
+            status.clone(),
+            index,
+            spec.node_id,
+            launch.echo,
+        ));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(forward_output(
+            stderr,
+            status.clone(),
+            index,
+            spec.node_id,
+            launch.echo,
+        ));
+    }
+
+    let control = ControlClient::new(control_addr, token);
+    let mut paused_for_schedule = false;
+    let mut poll = tokio::time::interval(fleet::poll_interval());
+    loop {
+        tokio::select! {
+            _ = shutdown.recv() => {
+                stop(&mut child, &control).await;
+                return RunOutcome::Shutdown;
+            }
+            exit = child.wait() => {
+                return RunOutcome::Exited {
+                    success: exit.as_ref().is_ok_and(|exit| exit.success()),
+                    exit: match exit {
+                        Ok(exit) => exit.to_string(),
+                        Err(e) => e.to_string(),
+                    },
+                    ran_for: started.elapsed(),
+                };
+            }
+            _ = poll.tick() => {
+                // Pause the node outside its schedule; it finishes the task in progress
+                let in_window = spec
+                    .schedule
+                    .is_none_or(|window| window.contains(Local::now().time()));
+                if !in_window && !paused_for_schedule {
+                    paused_for_schedule = control.post("pause").await;
+                } else if in_window && paused_for_schedule {
+                    paused_for_schedule = !control.post("resume").await;
+                }
+                let stats = control.stats().await;
+                status.update(index, |node| {
+                    node.off_schedule = !in_window;
+                    if stats.is_some() {
+                        node.stats = stats;
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Ask the process to stop after its step in progress, and kill it if it takes too long.
+async fn stop(child: &mut Child, control: &ControlClient) {
+    control.post("shutdown").await;
+    if tokio::time::timeout(fleet::stop_timeout(), child.wait())
+        .await
+        .is_err()
+    {
+        let _ = child.kill().await;
+    }
+}
+
+/// A local address with a free port, for the control API of a process
+fn free_local_addr() -> std::io::Result<SocketAddr> {
+    std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()
+}
+
+/// Record each line the process prints, and print it too if `echo` is set.
+async fn forward_output(
+    output: impl AsyncRead + Unpin,
+    status: FleetStatus,
+    index: usize,
+    node_id: u64,
+    echo: bool,
+) {
+    let mut lines = BufReader::new(output).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let line = strip_ansi(&line);
+        if echo {
+            println!("[node {}] {}", node_id, line);
+        }
+        status.push_output(index, line);
+    }
+}
+
+/// Remove the terminal color codes of a line of output
+fn strip_ansi(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+        } else if chars.next() == Some('[') {
+            // Skip the parameters up to the final letter, e.g. `ESC [ 1 ; 32 m`
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        }
+    }
+    stripped
+}
+
+/// Client of the control API of a node's process
+struct ControlClient {
+    base_url: String,
+    token: String,
+    http: reqwest::Client,
+}
+
+impl ControlClient {
+    fn new(addr: SocketAddr, token: String) -> Self {
+        Self {
+            base_url: format!("http://{}/v1", addr),
+            token,
+            http: reqwest::Client::builder()
+                .timeout(fleet::control_timeout())
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Stats of the node, or `None` if the API does not answer, e.g. while the node starts
+    async fn stats(&self) -> Option<StatusResponse> {
+        self.http
+            .get(format!("{}/stats", self.base_url))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .ok()?
+            .error_for_status()
+            .ok()?
+            .json()
+            .await
+            .ok()
+    }
+
+    /// Send a command such as `pause`, returning whether the node accepted it
+    async fn post(&self, command: &str) -> bool {
+        self.http
+            .post(format!("{}/{}", self.base_url, command))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .is_ok_and(|response| response.status().is_success())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::TaskDifficulty;
+
+    #[test]
+    fn test_node_command_line() {
+        let launch = NodeLaunch {
+            exe: PathBuf::from("nexus-network"),
+            global_args: vec!["--env".to_string(), "beta".to_string()],
+            coordinate: true,
+            echo: false,
+        };
+        let spec = NodeSpec {
+            node_id: 12345,
+            max_threads: Some(4),
+            max_difficulty: Some(TaskDifficulty::Large),
+            schedule: None,
+            labels: [("rack".to_string(), "b".to_string())].into(),
+        };
+        let addr: SocketAddr = "127.0.0.1:9100".parse().unwrap();
+        assert_eq!(
+            launch.args(&spec, addr).join(" "),
+            "--env beta start --headless --node-id 12345 --control-addr 127.0.0.1:9100 \
+             --max-threads 4 --max-difficulty LARGE --label rack=b --ephemeral-labels --coordinate"
+        );
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi("\x1b[1;32mSuccess\x1b[0m: proof submitted"),
+            "Success: proof submitted"
+        );
+        assert_eq!(strip_ansi("plain line"), "plain line");
+    }
+}
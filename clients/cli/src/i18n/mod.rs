@@ -0,0 +1,169 @@
+//! Localization of user-facing messages
+//!
+//! Messages live in Fluent-style catalogs under `locales/<language>.ftl`, one `key = value` per
+//! line, with `{ $name }` placeholders for arguments. The English catalog is complete; other
+//! catalogs may translate any subset of it, and missing messages fall back to English.
+//!
+//! The language is chosen once at startup from `--lang`, or else from the `LC_ALL`,
+//! `LC_MESSAGES` and `LANG` environment variables. To contribute a translation, copy
+//! `locales/en.ftl`, translate the values and add the file to [`CATALOGS`].
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// Language used for messages missing from the selected catalog
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Bundled catalogs, by language code
+const CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("../../locales/en.ftl")),
+    ("de", include_str!("../../locales/de.ftl")),
+    ("es", include_str!("../../locales/es.ftl")),
+];
+
+static LANGUAGE: OnceLock<&'static str> = OnceLock::new();
+static MESSAGES: OnceLock<HashMap<&'static str, HashMap<&'static str, &'static str>>> =
+    OnceLock::new();
+
+/// Language codes with a bundled catalog.
+pub fn available_languages() -> impl Iterator<Item = &'static str> {
+    CATALOGS.iter().map(|(language, _)| *language)
+}
+
+/// Select the message language for the rest of the run.
+///
+/// `lang` takes priority over the locale environment variables. Unknown languages fall back to
+/// English. Must be called before any message is looked up; returns the selected language.
+pub fn init(lang: Option<&str>) -> &'static str {
+    let from_env = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty());
+    let language = lang
+        .map(str::to_string)
+        .or(from_env)
+        .and_then(|locale| supported_language(&locale))
+        .unwrap_or(DEFAULT_LANGUAGE);
+    *LANGUAGE.get_or_init(|| language)
+}
+
+/// Catalog language for a locale such as `de`, `de_DE.UTF-8` or `es-MX`, if one is bundled.
+pub fn supported_language(locale: &str) -> Option<&'static str> {
+    let code = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    available_languages().find(|language| *language == code)
+}
+
+/// Language selected by [`init`], English if it was never called.
+pub fn language() -> &'static str {
+    LANGUAGE.get().copied().unwrap_or(DEFAULT_LANGUAGE)
+}
+
+fn messages() -> &'static HashMap<&'static str, HashMap<&'static str, &'static str>> {
+    MESSAGES.get_or_init(|| {
+        CATALOGS
+            .iter()
+            .map(|(language, source)| (*language, parse_catalog(source)))
+            .collect()
+    })
+}
+
+/// Parse `key = value` lines, skipping blank lines and `#` comments.
+fn parse_catalog(source: &str) -> HashMap<&str, &str> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+/// Look up a message in `language`, falling back to English and then to the key itself.
+fn lookup(language: &str, key: &'static str) -> &'static str {
+    let messages = messages();
+    [language, DEFAULT_LANGUAGE]
+        .iter()
+        .find_map(|language| messages.get(language)?.get(key).copied())
+        .unwrap_or(key)
+}
+
+/// Replace `{ $name }` placeholders with their arguments. Unknown placeholders are left as is.
+fn format_message(message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut formatted = message.to_string();
+    for (name, value) in args {
+        formatted = formatted.replace(&format!("{{ ${} }}", name), &value.to_string());
+    }
+    formatted
+}
+
+/// The message `key` in the selected language, with its placeholders filled in from `args`.
+/// Use the [`tr!`](crate::tr) macro rather than calling this directly.
+pub fn message(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    format_message(lookup(language(), key), args)
+}
+
+/// Localized message by key, with optional `name = value` arguments:
+/// `tr!("dashboard-node", id = node_id)`.
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::message($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::message(
+            $key,
+            &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_language() {
+        assert_eq!(supported_language("de_DE.UTF-8"), Some("de"));
+        assert_eq!(supported_language("es-MX"), Some("es"));
+        assert_eq!(supported_language("EN"), Some("en"));
+        assert_eq!(supported_language("C"), None);
+        assert_eq!(supported_language(""), None);
+    }
+
+    #[test]
+    fn test_lookup_and_format() {
+        assert_eq!(
+            lookup("de", "dashboard-activity-log"),
+            "AKTIVITÄTSPROTOKOLL"
+        );
+        // Unknown languages and untranslated messages fall back to English, then to the key
+        assert_eq!(lookup("xx", "dashboard-activity-log"), "ACTIVITY LOG");
+        assert_eq!(lookup("en", "no-such-message"), "no-such-message");
+
+        assert_eq!(
+            format_message("Node: { $id } ({ $id })", &[("id", &42)]),
+            "Node: 42 (42)"
+        );
+    }
+
+    #[test]
+    fn test_catalogs_only_translate_english_messages() {
+        let messages = messages();
+        let english = &messages[DEFAULT_LANGUAGE];
+        for (language, catalog) in messages {
+            for key in catalog.keys() {
+                assert!(
+                    english.contains_key(key),
+                    "{} catalog has message '{}' missing from English",
+                    language,
+                    key
+                );
+            }
+        }
+    }
+}
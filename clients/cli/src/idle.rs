@@ -0,0 +1,232 @@
+//! Idle-only proving for workstations
+//!
+//! With `--only-when-idle 10m`, proving waits until nobody has used the machine for ten minutes,
+//! and stops within seconds once someone does: task fetching is held, and proofs in progress are
+//! suspended until the machine is idle again, so they finish rather than being lost.
+//!
+//! Inactivity is read from the desktop: `xprintidle` on X11, GNOME's idle monitor on Wayland and
+//! `ioreg` on macOS. Where none of these is available, e.g. on servers, the machine counts as
+//! idle while other processes use less than a fifth of its CPU.
+
+use crate::consts::cli_consts::idle::{IDLE_CPU_THRESHOLD_PERCENT, IDLE_POLL_INTERVAL_SECS};
+use crate::control::RuntimeControl;
+use crate::prover::engine::suspend_prover_processes;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tokio::sync::broadcast;
+
+/// One reading of how busy the machine is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IdleSample {
+    /// Time since the last keyboard or mouse input
+    UserIdleFor(Duration),
+    /// CPU used by processes other than the prover, in percent of the whole machine
+    OtherCpuPercent(f32),
+}
+
+/// Decides from successive samples whether the machine has been idle long enough
+#[derive(Debug)]
+struct IdleDetector {
+    min_idle: Duration,
+    /// Since when the CPU has been quiet, for CPU samples
+    quiet_since: Option<Instant>,
+}
+
+impl IdleDetector {
+    fn new(min_idle: Duration) -> Self {
+        Self {
+            min_idle,
+            quiet_since: None,
+        }
+    }
+
+    fn is_idle(&mut self, sample: IdleSample, now: Instant) -> bool {
+        match sample {
+            IdleSample::UserIdleFor(idle_for) => {
+                self.quiet_since = None;
+                idle_for >= self.min_idle
+            }
+            IdleSample::OtherCpuPercent(percent) if percent >= IDLE_CPU_THRESHOLD_PERCENT => {
+                self.quiet_since = None;
+                false
+            }
+            IdleSample::OtherCpuPercent(_) => {
+                let since = *self.quiet_since.get_or_insert(now);
+                now.saturating_duration_since(since) >= self.min_idle
+            }
+        }
+    }
+}
+
+/// Time since the last user input, or `None` if the desktop does not tell.
+pub fn user_idle_time() -> Option<Duration> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            command_output("xprintidle", &[])
+                .and_then(|output| output.trim().parse().ok())
+                .map(Duration::from_millis)
+                .or_else(|| {
+                    command_output(
+                        "gdbus",
+                        &[
+                            "call",
+                            "--session",
+                            "--dest",
+                            "org.gnome.Mutter.IdleMonitor",
+                            "--object-path",
+                            "/org/gnome/Mutter/IdleMonitor/Core",
+                            "--method",
+                            "org.gnome.Mutter.IdleMonitor.GetIdletime",
+                        ],
+                    )
+                    .and_then(|output| parse_mutter_idle_time(&output))
+                })
+        } else if #[cfg(target_os = "macos")] {
+            command_output("ioreg", &["-c", "IOHIDSystem", "-d", "4"])
+                .and_then(|output| parse_ioreg_idle_time(&output))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg_attr(not(any(target_os = "linux", target_os = "macos")), allow(dead_code))]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse the GNOME idle monitor's answer, e.g. `(uint64 12345,)`, in milliseconds.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_mutter_idle_time(output: &str) -> Option<Duration> {
+    let millis = output.split("uint64").nth(1)?;
+    let millis: String = millis
+        .trim_start()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    millis.parse().ok().map(Duration::from_millis)
+}
+
+/// Parse the `HIDIdleTime` line of `ioreg -c IOHIDSystem`, in nanoseconds.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_ioreg_idle_time(output: &str) -> Option<Duration> {
+    output
+        .lines()
+        .find(|line| line.contains("\"HIDIdleTime\""))
+        .and_then(|line| line.rsplit('=').next())
+        .and_then(|nanos| nanos.trim().parse().ok())
+        .map(Duration::from_nanos)
+}
+
+/// CPU used by processes other than this one and its prover subprocesses, in percent of the whole
+/// machine. Needs two refreshes of `system` to measure anything.
+fn other_cpu_percent(system: &mut System) -> f32 {
+    system.refresh_cpu_usage();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_cpu(),
+    );
+    let own = Pid::from_u32(std::process::id());
+    let own_usage: f32 = system
+        .processes()
+        .values()
+        .filter(|process| process.pid() == own || process.parent() == Some(own))
+        .map(|process| process.cpu_usage())
+        .sum();
+    let cpus = system.cpus().len().max(1) as f32;
+    (system.global_cpu_usage() - own_usage / cpus).max(0.0)
+}
+
+/// Hold proving while the machine is in use and resume it once it has been idle for `min_idle`.
+///
+/// The machine counts as in use until the first reading says otherwise, so no task is fetched
+/// before then.
+pub fn spawn_idle_monitor(
+    min_idle: Duration,
+    control: RuntimeControl,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    control.set_user_active(true);
+    tokio::spawn(async move {
+        let mut detector = IdleDetector::new(min_idle);
+        let mut system = Some(System::new());
+        let mut interval = tokio::time::interval(Duration::from_secs(IDLE_POLL_INTERVAL_SECS));
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = interval.tick() => {
+                    let mut probe = system.take().unwrap_or_default();
+                    let Ok((probe, sample)) = tokio::task::spawn_blocking(move || {
+                        let sample = match user_idle_time() {
+                            Some(idle_for) => IdleSample::UserIdleFor(idle_for),
+                            None => IdleSample::OtherCpuPercent(other_cpu_percent(&mut probe)),
+                        };
+                        (probe, sample)
+                    })
+                    .await
+                    else {
+                        continue;
+                    };
+                    system = Some(probe);
+
+                    let active = !detector.is_idle(sample, Instant::now());
+                    if active != control.is_user_active() {
+                        control.set_user_active(active);
+                        suspend_prover_processes(active);
+                    }
+                }
+            }
+        }
+        // Never leave proofs suspended
+        suspend_prover_processes(false);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN_IDLE: Duration = Duration::from_secs(600);
+
+    #[test]
+    fn test_user_idle_time_threshold() {
+        let mut detector = IdleDetector::new(MIN_IDLE);
+        let now = Instant::now();
+        assert!(!detector.is_idle(IdleSample::UserIdleFor(Duration::from_secs(5)), now));
+        assert!(detector.is_idle(IdleSample::UserIdleFor(MIN_IDLE), now));
+    }
+
+    #[test]
+    fn test_cpu_must_stay_quiet_for_min_idle() {
+        let mut detector = IdleDetector::new(MIN_IDLE);
+        let start = Instant::now();
+        let quiet = IdleSample::OtherCpuPercent(2.0);
+        let busy = IdleSample::OtherCpuPercent(60.0);
+
+        assert!(!detector.is_idle(quiet, start));
+        assert!(detector.is_idle(quiet, start + MIN_IDLE));
+        // Activity resets the clock
+        assert!(!detector.is_idle(busy, start + MIN_IDLE));
+        assert!(!detector.is_idle(quiet, start + MIN_IDLE * 3 / 2));
+        assert!(detector.is_idle(quiet, start + MIN_IDLE * 5 / 2));
+    }
+
+    #[test]
+    fn test_parse_idle_times() {
+        assert_eq!(
+            parse_mutter_idle_time("(uint64 12345,)\n"),
+            Some(Duration::from_millis(12345))
+        );
+        assert_eq!(parse_mutter_idle_time("Error: no such name"), None);
+        assert_eq!(
+            parse_ioreg_idle_time("    | |   \"HIDIdleTime\" = 3000000000\n"),
+            Some(Duration::from_secs(3))
+        );
+    }
+}
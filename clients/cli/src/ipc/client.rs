@@ -0,0 +1,106 @@
+//! Control socket client used by `nexus-cli queue`
+
+use super::protocol::{ControlRequest, ControlResponse};
+use crate::cli_messages::{print_error, print_info, print_success};
+use std::error::Error;
+use std::path::Path;
+
+/// Send a single request to a running prover and wait for its response.
+#[cfg(unix)]
+pub async fn send_request(
+    socket_path: &Path,
+    request: &ControlRequest,
+) -> Result<ControlResponse, Box<dyn Error>> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::UnixStream;
+
+    let stream = UnixStream::connect(socket_path).await.map_err(|e| {
+        format!(
+            "Could not connect to a running prover at {}: {}",
+            socket_path.display(),
+            e
+        )
+    })?;
+    let (reader, mut writer) = stream.into_split();
+
+    let mut payload = serde_json::to_string(request)?;
+    payload.push('\n');
+    writer.write_all(payload.as_bytes()).await?;
+
+    let mut lines = BufReader::new(reader).lines();
+    let line = lines
+        .next_line()
+        .await?
+        .ok_or("Prover closed the connection without responding")?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+/// Send a single request to a running prover and wait for its response.
+#[cfg(not(unix))]
+pub async fn send_request(
+    _socket_path: &Path,
+    _request: &ControlRequest,
+) -> Result<ControlResponse, Box<dyn Error>> {
+    Err("The control socket is only supported on Unix platforms".into())
+}
+
+/// Run a `nexus-cli queue` subcommand and print the result.
+pub async fn run_queue_command(
+    socket_path: &Path,
+    request: ControlRequest,
+) -> Result<(), Box<dyn Error>> {
+    match send_request(socket_path, &request).await? {
+        ControlResponse::Tasks { tasks } => {
+            if tasks.is_empty() {
+                print_info("Queue is empty", "");
+                return Ok(());
+            }
+            println!(
+                "{:<40} {:<12} {:<16} {:>7} {:>8}",
+                "TASK ID", "STAGE", "DIFFICULTY", "INPUTS", "AGE"
+            );
+            for task in tasks {
+                println!(
+                    "{:<40} {:<12} {:<16} {:>7} {:>7}s",
+                    task.task_id, task.stage, task.difficulty, task.num_inputs, task.age_secs
+                );
+            }
+        }
+        ControlResponse::Dropped { task_id } => {
+            print_success("Task dropped", &format!("Task ID: {}", task_id));
+        }
+        ControlResponse::Stats {
+            stats,
+            labels,
+            receipts,
+        } => {
+            println!("Queued:     {}", stats.queued);
+            println!("Fetched:    {}", stats.fetched);
+            println!("Proved:     {}", stats.proved);
+            println!("Submitted:  {}", stats.submitted);
+            println!("Failed:     {}", stats.failed);
+            println!("Dropped:    {}", stats.dropped);
+            println!("Uptime:     {}s", stats.uptime_secs);
+            if !labels.is_empty() {
+                println!("Labels:     {}", crate::labels::format_labels(&labels));
+            }
+            if let Some(receipts) = receipts {
+                println!(
+                    "Credited:   {} ({} pending)",
+                    receipts.credited, receipts.pending
+                );
+                if receipts.discrepancies > 0 {
+                    println!(
+                        "Uncredited: {} (see ~/.nexus/receipts/)",
+                        receipts.discrepancies
+                    );
+                }
+            }
+        }
+        ControlResponse::Error { message } => {
+            print_error("Queue command failed", Some(&message));
+            return Err(message.into());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+//! Local IPC control channel
+//!
+//! Lets a second `nexus-cli` process inspect and manipulate the task queue of a
+//! running prover over a Unix domain socket.
+
+pub mod client;
+pub mod protocol;
+pub mod queue;
+#[cfg(unix)]
+pub mod server;
+
+use std::path::{Path, PathBuf};
+
+/// File name of the control socket, placed next to the config file (~/.nexus/).
+const CONTROL_SOCKET_FILE: &str = "control.sock";
+
+/// Get the path of the control socket for the given config file path.
+pub fn control_socket_path(config_path: &Path) -> PathBuf {
+    config_path.with_file_name(CONTROL_SOCKET_FILE)
+}
+
+pub use queue::TaskQueue;
@@ -0,0 +1,75 @@
+//! Wire format for the control socket
+//!
+//! Requests and responses are exchanged as newline-delimited JSON, one request
+//! per line, each answered by exactly one response line.
+
+use super::queue::{QueueStats, QueuedTaskInfo};
+use crate::labels::Labels;
+use crate::receipts::ReceiptCounts;
+use serde::{Deserialize, Serialize};
+
+/// Request sent by a client to the running prover.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlRequest {
+    /// List all tasks currently held by the prover.
+    ListQueue,
+    /// Abandon a task; it will neither be proved nor submitted.
+    DropTask { task_id: String },
+    /// Aggregate counters for the current session.
+    QueueStats,
+}
+
+/// Response sent by the running prover.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ControlResponse {
+    Tasks {
+        tasks: Vec<QueuedTaskInfo>,
+    },
+    Dropped {
+        task_id: String,
+    },
+    Stats {
+        stats: QueueStats,
+        /// Labels of the node, see [`crate::labels`]
+        #[serde(default)]
+        labels: Labels,
+        /// Submissions credited, pending and flagged, see [`crate::receipts`]
+        #[serde(default)]
+        receipts: Option<ReceiptCounts>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_round_trip() {
+        let request = ControlRequest::DropTask {
+            task_id: "task-1".to_string(),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"command":"drop_task","task_id":"task-1"}"#);
+        let parsed: ControlRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn test_unit_request_format() {
+        let json = serde_json::to_string(&ControlRequest::ListQueue).unwrap();
+        assert_eq!(json, r#"{"command":"list_queue"}"#);
+    }
+
+    #[test]
+    fn test_stats_without_labels() {
+        // Stats from a prover that predates node labels
+        let json = r#"{"status":"stats","stats":{"queued":1,"fetched":2,"proved":2,"submitted":1,"failed":0,"dropped":0,"uptime_secs":60}}"#;
+        let parsed: ControlResponse = serde_json::from_str(json).unwrap();
+        assert!(matches!(parsed, ControlResponse::Stats { labels, .. } if labels.is_empty()));
+    }
+}
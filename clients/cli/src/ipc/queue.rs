@@ -0,0 +1,234 @@
+//! Shared view of the tasks held by the worker
+//!
+//! The authenticated worker records every task it fetches here and updates its
+//! stage as it moves through the pipeline. The control server reads the same
+//! state to answer `nexus-cli queue` commands.
+
+use crate::task::Task;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// Pipeline stage of a queued task
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize, strum::Display)]
+pub enum TaskStage {
+    /// Fetched from the orchestrator, not yet proving
+    Fetched,
+    /// Proof generation in progress
+    Proving,
+    /// Proof generated, submission in progress
+    Submitting,
+}
+
+/// How a task left the queue
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TaskOutcome {
+    Submitted,
+    Failed,
+    Dropped,
+}
+
+/// Snapshot of a queued task, as reported over the control socket
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedTaskInfo {
+    pub task_id: String,
+    pub program_id: String,
+    pub difficulty: String,
+    pub num_inputs: usize,
+    pub stage: TaskStage,
+    /// Seconds since the task was fetched
+    pub age_secs: u64,
+}
+
+/// Session-wide task counters
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueueStats {
+    /// Tasks currently held by the worker
+    pub queued: usize,
+    pub fetched: u64,
+    pub proved: u64,
+    pub submitted: u64,
+    pub failed: u64,
+    pub dropped: u64,
+    pub uptime_secs: u64,
+}
+
+struct QueueEntry {
+    task_id: String,
+    program_id: String,
+    difficulty: String,
+    num_inputs: usize,
+    stage: TaskStage,
+    fetched_at: Instant,
+    cancel: CancellationToken,
+}
+
+struct QueueInner {
+    entries: Vec<QueueEntry>,
+    stats: QueueStats,
+    started_at: Instant,
+}
+
+/// Cheaply clonable handle to the shared task queue
+#[derive(Clone)]
+pub struct TaskQueue {
+    inner: Arc<Mutex<QueueInner>>,
+}
+
+impl std::fmt::Debug for TaskQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskQueue").finish_non_exhaustive()
+    }
+}
+
+impl Default for TaskQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskQueue {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(QueueInner {
+                entries: Vec::new(),
+                stats: QueueStats::default(),
+                started_at: Instant::now(),
+            })),
+        }
+    }
+
+    /// Record a newly fetched task. The returned token is cancelled if the
+    /// task is dropped through the control socket.
+    pub fn push(&self, task: &Task) -> CancellationToken {
+        let cancel = CancellationToken::new();
+        let mut inner = self.inner.lock().unwrap();
+        inner.stats.fetched += 1;
+        inner.entries.push(QueueEntry {
+            task_id: task.task_id.clone(),
+            program_id: task.program_id.clone(),
+            difficulty: task.difficulty.as_str_name().to_string(),
+            num_inputs: task.all_inputs().len(),
+            stage: TaskStage::Fetched,
+            fetched_at: Instant::now(),
+            cancel: cancel.clone(),
+        });
+        cancel
+    }
+
+    /// Move a task to a new pipeline stage
+    pub fn set_stage(&self, task_id: &str, stage: TaskStage) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(entry) = inner.entries.iter_mut().find(|e| e.task_id == task_id) {
+            if stage == TaskStage::Submitting {
+                inner.stats.proved += 1;
+            }
+            entry.stage = stage;
+        }
+    }
+
+    /// Remove a task from the queue and count its outcome
+    pub fn finish(&self, task_id: &str, outcome: TaskOutcome) {
+        let mut inner = self.inner.lock().unwrap();
+        let before = inner.entries.len();
+        inner.entries.retain(|e| e.task_id != task_id);
+        if inner.entries.len() == before {
+            return;
+        }
+        match outcome {
+            TaskOutcome::Submitted => inner.stats.submitted += 1,
+            TaskOutcome::Failed => inner.stats.failed += 1,
+            TaskOutcome::Dropped => inner.stats.dropped += 1,
+        }
+    }
+
+    /// Request that a task be abandoned. Returns false if the task is unknown.
+    pub fn drop_task(&self, task_id: &str) -> bool {
+        let inner = self.inner.lock().unwrap();
+        match inner.entries.iter().find(|e| e.task_id == task_id) {
+            Some(entry) => {
+                entry.cancel.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Snapshot of all queued tasks, oldest first
+    pub fn list(&self) -> Vec<QueuedTaskInfo> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .entries
+            .iter()
+            .map(|e| QueuedTaskInfo {
+                task_id: e.task_id.clone(),
+                program_id: e.program_id.clone(),
+                difficulty: e.difficulty.clone(),
+                num_inputs: e.num_inputs,
+                stage: e.stage,
+                age_secs: e.fetched_at.elapsed().as_secs(),
+            })
+            .collect()
+    }
+
+    /// Snapshot of the session counters
+    pub fn stats(&self) -> QueueStats {
+        let inner = self.inner.lock().unwrap();
+        QueueStats {
+            queued: inner.entries.len(),
+            uptime_secs: inner.started_at.elapsed().as_secs(),
+            ..inner.stats.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+
+    fn test_task(task_id: &str) -> Task {
+        Task::new(
+            task_id.to_string(),
+            "fib_input_initial".to_string(),
+            vec![0; 12],
+            TaskType::ProofHash,
+            TaskDifficulty::Small,
+        )
+    }
+
+    #[test]
+    fn test_task_lifecycle_updates_stats() {
+        let queue = TaskQueue::new();
+        queue.push(&test_task("a"));
+        queue.set_stage("a", TaskStage::Proving);
+        queue.set_stage("a", TaskStage::Submitting);
+
+        let tasks = queue.list();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].stage, TaskStage::Submitting);
+        assert_eq!(tasks[0].difficulty, "SMALL");
+
+        queue.finish("a", TaskOutcome::Submitted);
+        let stats = queue.stats();
+        assert_eq!(stats.queued, 0);
+        assert_eq!(stats.fetched, 1);
+        assert_eq!(stats.proved, 1);
+        assert_eq!(stats.submitted, 1);
+    }
+
+    #[test]
+    fn test_drop_task_cancels_token() {
+        let queue = TaskQueue::new();
+        let token = queue.push(&test_task("a"));
+        assert!(!queue.drop_task("unknown"));
+        assert!(queue.drop_task("a"));
+        assert!(token.is_cancelled());
+
+        queue.finish("a", TaskOutcome::Dropped);
+        // Finishing twice must not double count
+        queue.finish("a", TaskOutcome::Dropped);
+        assert_eq!(queue.stats().dropped, 1);
+    }
+}
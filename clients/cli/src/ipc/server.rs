@@ -0,0 +1,198 @@
+//! Control socket server, run alongside the workers in a prover session
+
+use super::protocol::{ControlRequest, ControlResponse};
+use super::queue::TaskQueue;
+use crate::receipts::ReceiptLedger;
+use std::io::ErrorKind;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::broadcast;
+
+/// Bind the control socket and serve requests until shutdown.
+///
+/// A stale socket file left behind by a previous run is removed before binding, but the socket of
+/// an instance that is still running is left alone and binding fails. Requests are unauthenticated,
+/// so the socket is only open to its owner: it is created with mode 0600, and connections from
+/// other users are dropped.
+pub async fn run_control_server(
+    socket_path: PathBuf,
+    queue: TaskQueue,
+    receipts: Option<ReceiptLedger>,
+    mut shutdown: broadcast::Receiver<()>,
+) -> std::io::Result<()> {
+    let listener = bind(&socket_path)?;
+    let owner = std::fs::metadata(&socket_path)?.uid();
+
+    loop {
+        tokio::select! {
+            _ = shutdown.recv() => break,
+            accepted = listener.accept() => {
+                let Ok((stream, _)) = accepted else {
+                    continue;
+                };
+                if stream.peer_cred().is_ok_and(|peer| peer.uid() == owner) {
+                    tokio::spawn(handle_connection(stream, queue.clone(), receipts.clone()));
+                }
+            }
+        }
+    }
+
+    let _ = std::fs::remove_file(&socket_path);
+    Ok(())
+}
+
+fn bind(socket_path: &Path) -> std::io::Result<UnixListener> {
+    if let Some(parent) = socket_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Only a socket nobody listens on any more is stale
+    match std::os::unix::net::UnixStream::connect(socket_path) {
+        Ok(_) => {
+            return Err(std::io::Error::new(
+                ErrorKind::AddrInUse,
+                format!("{} is in use by another instance", socket_path.display()),
+            ));
+        }
+        Err(e) if e.kind() == ErrorKind::ConnectionRefused => std::fs::remove_file(socket_path)?,
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let listener = UnixListener::bind(socket_path)?;
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+async fn handle_connection(stream: UnixStream, queue: TaskQueue, receipts: Option<ReceiptLedger>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let response = match serde_json::from_str::<ControlRequest>(&line) {
+            Ok(request) => handle_request(request, &queue, receipts.as_ref()),
+            Err(e) => ControlResponse::Error {
+                message: format!("Invalid request: {}", e),
+            },
+        };
+
+        let Ok(mut payload) = serde_json::to_string(&response) else {
+            break;
+        };
+        payload.push('\n');
+        if writer.write_all(payload.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Answer a single control request against the shared queue and receipt ledger
+pub fn handle_request(
+    request: ControlRequest,
+    queue: &TaskQueue,
+    receipts: Option<&ReceiptLedger>,
+) -> ControlResponse {
+    match request {
+        ControlRequest::ListQueue => ControlResponse::Tasks {
+            tasks: queue.list(),
+        },
+        ControlRequest::DropTask { task_id } => {
+            if queue.drop_task(&task_id) {
+                ControlResponse::Dropped { task_id }
+            } else {
+                ControlResponse::Error {
+                    message: format!("Task {} is not in the queue", task_id),
+                }
+            }
+        }
+        ControlRequest::QueueStats => ControlResponse::Stats {
+            stats: queue.stats(),
+            labels: crate::labels::node_labels(),
+            receipts: receipts.map(ReceiptLedger::counts),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::client::send_request;
+    use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+    use crate::task::Task;
+
+    #[tokio::test]
+    async fn test_list_and_drop_over_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("control.sock");
+
+        let queue = TaskQueue::new();
+        let token = queue.push(&Task::new(
+            "task-1".to_string(),
+            "fib_input_initial".to_string(),
+            vec![0; 12],
+            TaskType::ProofHash,
+            TaskDifficulty::Small,
+        ));
+
+        let (shutdown_sender, shutdown_receiver) = broadcast::channel(1);
+        let server = tokio::spawn(run_control_server(
+            socket_path.clone(),
+            queue.clone(),
+            None,
+            shutdown_receiver,
+        ));
+        while !socket_path.exists() {
+            tokio::task::yield_now().await;
+        }
+
+        match send_request(&socket_path, &ControlRequest::ListQueue)
+            .await
+            .unwrap()
+        {
+            ControlResponse::Tasks { tasks } => assert_eq!(tasks[0].task_id, "task-1"),
+            other => panic!("unexpected response: {:?}", other),
+        }
+
+        let response = send_request(
+            &socket_path,
+            &ControlRequest::DropTask {
+                task_id: "task-1".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(matches!(response, ControlResponse::Dropped { .. }));
+        assert!(token.is_cancelled());
+
+        let _ = shutdown_sender.send(());
+        server.await.unwrap().unwrap();
+        assert!(!socket_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_bind_keeps_socket_of_running_instance() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("control.sock");
+
+        let _running = bind(&socket_path).unwrap();
+        let err = bind(&socket_path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::AddrInUse);
+        assert!(std::os::unix::net::UnixStream::connect(&socket_path).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_bind_replaces_stale_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("control.sock");
+
+        drop(bind(&socket_path).unwrap());
+        assert!(socket_path.exists());
+
+        let _listener = bind(&socket_path).unwrap();
+        let mode = std::fs::metadata(&socket_path)
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
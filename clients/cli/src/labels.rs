@@ -0,0 +1,139 @@
+//! Node labels
+//!
+//! Operators tag nodes with `key=value` labels, e.g. `provider=hetzner-16c` or `site=home-lab`,
+//! to tell machines apart. Labels given with `start --label` are merged into the `labels` saved in
+//! the config file, and an empty value removes a label. The labels of the running session are
+//! reported to the orchestrator with each proof's telemetry, shown in the dashboard header, and
+//! returned by `nexus-cli queue stats` and the control API.
+
+use crate::consts::cli_consts::{MAX_LABEL_LEN, MAX_LABELS};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+/// Labels by key, sorted so they are always reported and shown in the same order
+pub type Labels = BTreeMap<String, String>;
+
+/// Labels of the running session
+static NODE_LABELS: RwLock<Labels> = RwLock::new(BTreeMap::new());
+
+/// Parse a `key=value` label. Keys are ASCII letters, digits, `-`, `_` and `.`; values are
+/// printable. An empty value is allowed and removes the label in [`merge`].
+pub fn parse_label(label: &str) -> Result<(String, String), String> {
+    let (key, value) = label
+        .split_once('=')
+        .ok_or_else(|| format!("'{}' is not a key=value label", label))?;
+    let (key, value) = (key.trim(), value.trim());
+    if key.is_empty()
+        || key.len() > MAX_LABEL_LEN
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(format!(
+            "Label key '{}' must be 1-{} letters, digits, '-', '_' or '.'",
+            key, MAX_LABEL_LEN
+        ));
+    }
+    if value.chars().count() > MAX_LABEL_LEN || value.chars().any(char::is_control) {
+        return Err(format!(
+            "Label value '{}' must be at most {} printable characters",
+            value, MAX_LABEL_LEN
+        ));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Apply labels from the command line to the saved ones: set each label, or remove it if its
+/// value is empty.
+pub fn merge(labels: &mut Labels, updates: Vec<(String, String)>) -> Result<(), String> {
+    for (key, value) in updates {
+        if value.is_empty() {
+            labels.remove(&key);
+        } else {
+            labels.insert(key, value);
+        }
+    }
+    if labels.len() > MAX_LABELS {
+        return Err(format!(
+            "A node can have at most {} labels, got {}",
+            MAX_LABELS,
+            labels.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Use `labels` for the running session.
+pub fn set_node_labels(labels: Labels) {
+    *NODE_LABELS.write().unwrap_or_else(|e| e.into_inner()) = labels;
+}
+
+/// Labels of the running session
+pub fn node_labels() -> Labels {
+    NODE_LABELS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Labels as `key=value` pairs separated by spaces, e.g. for the dashboard header.
+pub fn format_labels(labels: &Labels) -> String {
+    labels
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_label() {
+        assert_eq!(
+            parse_label("provider=hetzner-16c"),
+            Ok(("provider".to_string(), "hetzner-16c".to_string()))
+        );
+        assert_eq!(
+            parse_label(" site = home lab "),
+            Ok(("site".to_string(), "home lab".to_string()))
+        );
+        assert_eq!(
+            parse_label("site="),
+            Ok(("site".to_string(), String::new()))
+        );
+        assert!(parse_label("home-lab").is_err());
+        assert!(parse_label("=home-lab").is_err());
+        assert!(parse_label("my site=home").is_err());
+        assert!(parse_label(&format!("site={}", "x".repeat(MAX_LABEL_LEN + 1))).is_err());
+        assert!(parse_label("site=home\nlab").is_err());
+    }
+
+    #[test]
+    fn test_merge_sets_and_removes_labels() {
+        let mut labels = Labels::from([
+            ("provider".to_string(), "hetzner".to_string()),
+            ("site".to_string(), "fsn1".to_string()),
+        ]);
+        merge(
+            &mut labels,
+            vec![
+                ("provider".to_string(), "home-lab".to_string()),
+                ("site".to_string(), String::new()),
+                ("cores".to_string(), "16".to_string()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(format_labels(&labels), "cores=16 provider=home-lab");
+    }
+
+    #[test]
+    fn test_merge_limits_label_count() {
+        let mut labels = Labels::new();
+        let updates = (0..=MAX_LABELS)
+            .map(|i| (format!("key{}", i), "value".to_string()))
+            .collect();
+        assert!(merge(&mut labels, updates).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright (c) 2025 Nexus. All rights reserved.
+
+//! Core of the Nexus network CLI: orchestrator client, prover pipeline, and session runtime.
+//!
+//! The `nexus-network` binary is a thin command-line wrapper around this library. Programs that
+//! want to run a prover without shelling out to the binary can start one with [`Session::builder`]:
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let session = nexus_cli_core::Session::builder()
+//!     .node_id(12345)
+//!     .max_threads(2)
+//!     .start()
+//!     .await?;
+//! session.run_headless().await
+//! # }
+//! ```
+//!
+//! Proofs are generated in subprocesses that re-run the current executable as
+//! `<exe> prove-fib-server [--core <N>]`, kept running between proofs. Host programs must hand that
+//! invocation to [`run_prove_server`] before doing anything else. The one-shot
+//! `<exe> prove-fib-subprocess --inputs <JSON> [--core <N>]` of earlier releases is still handled
+//! by [`run_prove_subprocess`].
+//!
+//! The documented modules and the items re-exported at the crate root are the stable API: the
+//! orchestrator client, the prover pipeline and the session runtime, with the types their options
+//! take. Hidden modules are shared with the binary and may change in any release.
+
+pub mod accept_policy;
+pub mod affinity;
+pub mod control;
+pub mod environment;
+pub mod event_stream;
+pub mod events;
+pub mod metrics_export;
+#[path = "proto/nexus.orchestrator.rs"]
+pub mod nexus_orchestrator;
+pub mod orchestrator;
+pub mod power;
+pub mod prover;
+pub mod session;
+pub mod task;
+
+#[doc(hidden)]
+pub mod chaos;
+#[doc(hidden)]
+pub mod cli_messages;
+#[doc(hidden)]
+pub mod completions;
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
+pub mod config_crypto;
+#[doc(hidden)]
+pub mod config_validation;
+#[doc(hidden)]
+pub mod consts;
+#[doc(hidden)]
+pub mod doctor;
+#[doc(hidden)]
+pub mod exit_code;
+#[doc(hidden)]
+pub mod failures;
+#[doc(hidden)]
+pub mod fleet;
+#[doc(hidden)]
+pub mod i18n;
+#[doc(hidden)]
+pub mod ipc;
+#[doc(hidden)]
+pub mod labels;
+#[doc(hidden)]
+pub mod network_stats;
+#[doc(hidden)]
+pub mod nodes;
+#[doc(hidden)]
+pub mod profile;
+#[doc(hidden)]
+pub mod redact;
+#[doc(hidden)]
+pub mod register;
+#[doc(hidden)]
+pub mod secrets;
+#[doc(hidden)]
+pub mod service;
+#[doc(hidden)]
+pub mod system;
+#[doc(hidden)]
+pub mod version;
+#[doc(hidden)]
+pub mod wallet;
+
+mod analytics;
+mod clock;
+mod config_migration;
+mod coordinator;
+mod idle;
+mod keys;
+mod logging;
+mod network;
+mod notifications;
+mod points;
+mod receipts;
+mod runtime;
+mod stats;
+mod storage;
+mod task_cache;
+mod thermal;
+mod ui;
+mod workers;
+
+pub use environment::Environment;
+pub use events::{Event, EventType, ProverState, Worker};
+pub use nexus_orchestrator::TaskDifficulty;
+pub use orchestrator::{Orchestrator, OrchestratorClient};
+pub use session::{Session, SessionBuilder};
+#[doc(hidden)]
+pub use ui::theme;
+pub use ui::{DashboardLayout, UiMode};
+
+use crate::prover::engine::ProvingEngine;
+use crate::prover::pool;
+use postcard::to_allocvec;
+use std::error::Error;
+use std::io::{BufRead, Write};
+
+/// All available difficulty levels as (name, enum_value) pairs
+pub const DIFFICULTY_LEVELS: &[(&str, crate::nexus_orchestrator::TaskDifficulty)] = &[
+    ("SMALL", crate::nexus_orchestrator::TaskDifficulty::Small),
+    (
+        "SMALL_MEDIUM",
+        crate::nexus_orchestrator::TaskDifficulty::SmallMedium,
+    ),
+    ("MEDIUM", crate::nexus_orchestrator::TaskDifficulty::Medium),
+    ("LARGE", crate::nexus_orchestrator::TaskDifficulty::Large),
+    (
+        "EXTRA_LARGE",
+        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge,
+    ),
+    (
+        "EXTRA_LARGE_2",
+        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge2,
+    ),
+    (
+        "EXTRA_LARGE_3",
+        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge3,
+    ),
+    (
+        "EXTRA_LARGE_4",
+        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge4,
+    ),
+    (
+        "EXTRA_LARGE_5",
+        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge5,
+    ),
+];
+
+/// Helper function to validate difficulty string and return parsed enum
+pub fn validate_difficulty(
+    difficulty_str: &str,
+) -> Option<crate::nexus_orchestrator::TaskDifficulty> {
+    let upper = difficulty_str.trim().to_ascii_uppercase();
+    DIFFICULTY_LEVELS
+        .iter()
+        .find(|(name, _)| *name == upper)
+        .map(|(_, difficulty)| *difficulty)
+}
+
+/// Helper function to print available difficulty levels dynamically from the enum
+pub fn print_available_difficulties() {
+    eprintln!("Valid difficulty levels are:");
+    for (name, _) in DIFFICULTY_LEVELS {
+        eprintln!("  {}", name);
+    }
+}
+
+/// Generate a proof for the JSON-encoded `inputs` and write it to stdout.
+///
+/// This is the body of the `prove-fib-subprocess` command the prover spawns to isolate memory
+/// usage, pinned to `core` if core pinning is enabled. Exits with
+/// `SUBPROCESS_INTERNAL_ERROR_CODE` if proving fails.
+pub fn run_prove_subprocess(inputs: &str, core: Option<usize>) -> Result<(), Box<dyn Error>> {
+    let inputs: (u32, u32, u32) = serde_json::from_str(inputs)?;
+    if let Some(core) = core {
+        // Best-effort: an unpinned proof is still a valid proof
+        affinity::pin_current_thread(core);
+    }
+    match ProvingEngine::prove_fib_subprocess(&inputs) {
+        Ok(proof) => {
+            let bytes = to_allocvec(&proof)?;
+            let mut out = std::io::stdout().lock();
+            out.write_all(&bytes)?;
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(consts::cli_consts::SUBPROCESS_INTERNAL_ERROR_CODE);
+        }
+    }
+}
+
+/// Prove the JSON-encoded inputs read line by line from stdin, writing each proof to stdout.
+///
+/// This is the body of the `prove-fib-server` command the prover spawns once per worker, pinned
+/// to `core` if core pinning is enabled. The guest program is loaded before the first inputs
+/// arrive. Returns when stdin is closed, e.g. when the CLI exits.
+pub fn run_prove_server(core: Option<usize>) -> Result<(), Box<dyn Error>> {
+    if let Some(core) = core {
+        // Best-effort: an unpinned proof is still a valid proof
+        affinity::pin_current_thread(core);
+    }
+    let prover = ProvingEngine::create_fib_prover()?;
+    let mut out = std::io::stdout().lock();
+    for line in std::io::stdin().lock().lines() {
+        let inputs: (u32, u32, u32) = serde_json::from_str(&line?)?;
+        match ProvingEngine::prove_fib_with(&prover, &inputs) {
+            Ok(proof) => pool::write_reply(&mut out, Ok(&to_allocvec(&proof)?))?,
+            Err(e) => pool::write_reply(&mut out, Err(&e.to_string()))?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nexus_orchestrator::TaskDifficulty;
+
+    #[test]
+    fn test_difficulty_validation() {
+        // Test valid difficulty levels (case-insensitive)
+        assert_eq!(validate_difficulty("small"), Some(TaskDifficulty::Small));
+        assert_eq!(validate_difficulty("SMALL"), Some(TaskDifficulty::Small));
+        assert_eq!(validate_difficulty("Small"), Some(TaskDifficulty::Small));
+
+        assert_eq!(
+            validate_difficulty("small_medium"),
+            Some(TaskDifficulty::SmallMedium)
+        );
+        assert_eq!(
+            validate_difficulty("SMALL_MEDIUM"),
+            Some(TaskDifficulty::SmallMedium)
+        );
+
+        assert_eq!(validate_difficulty("medium"), Some(TaskDifficulty::Medium));
+        assert_eq!(validate_difficulty("large"), Some(TaskDifficulty::Large));
+        assert_eq!(
+            validate_difficulty("extra_large"),
+            Some(TaskDifficulty::ExtraLarge)
+        );
+        assert_eq!(
+            validate_difficulty("extra_large_2"),
+            Some(TaskDifficulty::ExtraLarge2)
+        );
+
+        // Test invalid difficulty levels
+        assert_eq!(validate_difficulty("invalid"), None);
+        assert_eq!(validate_difficulty("small medium"), None); // space instead of underscore
+        assert_eq!(validate_difficulty(""), None);
+        assert_eq!(validate_difficulty("   "), None);
+        assert_eq!(validate_difficulty("SMALL_MEDIUM_EXTRA"), None);
+        assert_eq!(validate_difficulty("123"), None);
+    }
+
+    fn validate_difficulty(difficulty_str: &str) -> Option<TaskDifficulty> {
+        match difficulty_str.trim().to_ascii_uppercase().as_str() {
+            "SMALL" => Some(TaskDifficulty::Small),
+            "SMALL_MEDIUM" => Some(TaskDifficulty::SmallMedium),
+            "MEDIUM" => Some(TaskDifficulty::Medium),
+            "LARGE" => Some(TaskDifficulty::Large),
+            "EXTRA_LARGE" => Some(TaskDifficulty::ExtraLarge),
+            "EXTRA_LARGE_2" => Some(TaskDifficulty::ExtraLarge2),
+            _ => None,
+        }
+    }
+}
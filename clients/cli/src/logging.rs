@@ -1,7 +1,8 @@
 use log::LevelFilter;
+use serde::Serialize;
 use std::env;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub enum LogLevel {
     Trace = 0,
     Debug = 1,
@@ -1,86 +1,52 @@
 // Copyright (c) 2025 Nexus. All rights reserved.
 
-mod analytics;
-mod cli_messages;
-mod config;
-mod consts;
-mod environment;
-mod events;
-mod keys;
-mod logging;
-mod network;
-#[path = "proto/nexus.orchestrator.rs"]
-mod nexus_orchestrator;
-mod orchestrator;
-mod prover;
-mod register;
-mod runtime;
-mod session;
-pub mod system;
-mod task;
-mod ui;
-mod version;
-mod workers;
-
-use crate::config::{Config, get_config_path};
-use crate::environment::Environment;
-use crate::orchestrator::OrchestratorClient;
-use crate::prover::engine::ProvingEngine;
-use crate::register::{register_node, register_user};
-use crate::session::{run_headless_mode, run_tui_mode, setup_session};
-use crate::version::manager::validate_version_requirements;
-use clap::{ArgAction, Parser, Subcommand};
-use postcard::to_allocvec;
+use clap::{ArgAction, CommandFactory, Parser, Subcommand};
+use clap_complete::engine::ArgValueCompleter;
+use clap_complete::env::CompleteEnv;
+use nexus_cli_core::accept_policy::AcceptPolicy;
+use nexus_cli_core::affinity::{self, CorePinning};
+use nexus_cli_core::completions::{
+    self, Shell, complete_environments, complete_node_ids, complete_themes, complete_wallets,
+};
+use nexus_cli_core::config::{self, Config, ConfigError, get_config_path};
+use nexus_cli_core::config_crypto;
+use nexus_cli_core::config_validation;
+use nexus_cli_core::consts::cli_consts::dashboard::MAX_UI_FPS;
+use nexus_cli_core::consts::cli_consts::power::DEFAULT_BATTERY_THRESHOLD_PERCENT;
+use nexus_cli_core::control::ControlApiConfig;
+use nexus_cli_core::environment::resolve_environment;
+use nexus_cli_core::event_stream::EventStreamConfig;
+use nexus_cli_core::exit_code::{ExitCode, UsageError};
+use nexus_cli_core::fleet::{self, FleetOptions};
+use nexus_cli_core::ipc::control_socket_path;
+use nexus_cli_core::ipc::protocol::ControlRequest;
+use nexus_cli_core::metrics_export::MetricsExportConfig;
+use nexus_cli_core::orchestrator::mock::MockConfig;
+use nexus_cli_core::orchestrator::{OrchestratorClient, Transport};
+use nexus_cli_core::power::{BatteryConfig, BatteryPolicy};
+use nexus_cli_core::profile::ProvingProfile;
+use nexus_cli_core::prover::export::verify_exported_proof;
+use nexus_cli_core::prover::local::{GuestInput, parse_fib_inputs, prove_elf, prove_locally};
+use nexus_cli_core::register::{
+    OutputFormat, RegisterOptions, Registration, deregister_node, register_node, register_nodes,
+    register_user,
+};
+use nexus_cli_core::secrets;
+use nexus_cli_core::session::lifetime::parse_duration;
+use nexus_cli_core::version::checker::UpdateChannel;
+use nexus_cli_core::version::updater;
+use nexus_cli_core::{
+    Session, SessionBuilder, UiMode, chaos, doctor, failures, i18n, ipc, labels, network_stats,
+    nodes, orchestrator, print_available_difficulties, print_cmd_info, print_cmd_success,
+    print_cmd_warn, service, theme, tr, validate_difficulty, wallet,
+};
 use std::error::Error;
-use std::io::Write;
-use std::process::exit;
-
-/// All available difficulty levels as (name, enum_value) pairs
-const DIFFICULTY_LEVELS: &[(&str, crate::nexus_orchestrator::TaskDifficulty)] = &[
-    ("SMALL", crate::nexus_orchestrator::TaskDifficulty::Small),
-    (
-        "SMALL_MEDIUM",
-        crate::nexus_orchestrator::TaskDifficulty::SmallMedium,
-    ),
-    ("MEDIUM", crate::nexus_orchestrator::TaskDifficulty::Medium),
-    ("LARGE", crate::nexus_orchestrator::TaskDifficulty::Large),
-    (
-        "EXTRA_LARGE",
-        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge,
-    ),
-    (
-        "EXTRA_LARGE_2",
-        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge2,
-    ),
-    (
-        "EXTRA_LARGE_3",
-        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge3,
-    ),
-    (
-        "EXTRA_LARGE_4",
-        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge4,
-    ),
-    (
-        "EXTRA_LARGE_5",
-        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge5,
-    ),
-];
-
-/// Helper function to validate difficulty string and return parsed enum
-fn validate_difficulty(difficulty_str: &str) -> Option<crate::nexus_orchestrator::TaskDifficulty> {
-    let upper = difficulty_str.trim().to_ascii_uppercase();
-    DIFFICULTY_LEVELS
-        .iter()
-        .find(|(name, _)| *name == upper)
-        .map(|(_, difficulty)| *difficulty)
-}
+use std::time::Duration;
 
-/// Helper function to print available difficulty levels dynamically from the enum
-fn print_available_difficulties() {
-    eprintln!("Valid difficulty levels are:");
-    for (name, _) in DIFFICULTY_LEVELS {
-        eprintln!("  {}", name);
-    }
+/// Clap value parser for `--country`
+fn parse_country(value: &str) -> Result<String, String> {
+    orchestrator::normalize_country_code(value)
+        .ok_or_else(|| format!("'{}' is not a 2-letter country code", value))
 }
 
 #[derive(Parser)]
@@ -90,6 +56,20 @@ struct Args {
     /// Command to execute
     #[command(subcommand)]
     command: Command,
+
+    /// Language of messages, e.g. `de` or `es` (defaults to LANG, then English)
+    #[arg(long, global = true, value_name = "LANG")]
+    lang: Option<String>,
+
+    /// Orchestrator environment: `production`, `custom` with `--orchestrator-url`, or one defined
+    /// under `environments` in the config file (defaults to NEXUS_ENVIRONMENT, then production)
+    #[arg(
+        long = "env",
+        global = true,
+        value_name = "ENV",
+        add = ArgValueCompleter::new(complete_environments)
+    )]
+    env: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -97,7 +77,7 @@ enum Command {
     /// Start the prover
     Start {
         /// Node ID
-        #[arg(long, value_name = "NODE_ID")]
+        #[arg(long, value_name = "NODE_ID", add = ArgValueCompleter::new(complete_node_ids))]
         node_id: Option<u64>,
 
         /// Run without the terminal UI
@@ -112,6 +92,10 @@ enum Command {
         #[arg(long = "orchestrator-url", value_name = "URL")]
         orchestrator_url: Option<String>,
 
+        /// Orchestrator URL to fail over to when the primary is unreachable; can be repeated
+        #[arg(long = "orchestrator-fallback-url", value_name = "URL")]
+        orchestrator_fallback_urls: Vec<String>,
+
         /// Enable checking for risk of memory errors, may slow down CLI startup
         #[arg(long = "check-memory", default_value_t = false)]
         check_mem: bool,
@@ -120,82 +104,919 @@ enum Command {
         #[arg(long = "with-background", action = ArgAction::SetTrue)]
         with_background: bool,
 
+        /// Dashboard style: `plain` is ASCII-only and monochrome, for screen readers; `compact` is
+        /// a status line and the latest log lines (default: `full`, compact in small terminals).
+        /// Also shows the dashboard when the profile runs headless
+        #[arg(long = "ui", value_enum)]
+        ui: Option<UiMode>,
+
+        /// Most dashboard redraws per second (default: 10). Lower it to save CPU on small machines
+        #[arg(
+            long = "ui-fps",
+            value_name = "FPS",
+            value_parser = clap::value_parser!(u32).range(1..=MAX_UI_FPS as i64)
+        )]
+        ui_fps: Option<u32>,
+
+        /// Preset for the machine: `low-memory` proves small tasks on one worker, headless
+        #[arg(long = "profile", value_enum, default_value_t = ProvingProfile::Default)]
+        profile: ProvingProfile,
+
         /// Maximum number of tasks to process before exiting (default: unlimited)
-        #[arg(long = "max-tasks", value_name = "MAX_TASKS")]
+        #[arg(
+            long = "max-tasks",
+            visible_alias = "stop-after-tasks",
+            value_name = "MAX_TASKS"
+        )]
         max_tasks: Option<u32>,
 
+        /// Finish the current task and exit once this session has earned this many points
+        #[arg(long = "stop-after-points", value_name = "POINTS")]
+        stop_after_points: Option<u64>,
+
+        /// Finish the current task and exit with status 0 after running this long, e.g. 24h,
+        /// counted across restarts. Pair with systemd's Restart=always for a fresh start
+        #[arg(long = "max-runtime", value_name = "DURATION", value_parser = parse_duration)]
+        max_runtime: Option<Duration>,
+
+        /// Finish the current task and start again with the same arguments every so often, e.g. 6h
+        #[arg(long = "restart-interval", value_name = "DURATION", value_parser = parse_duration)]
+        restart_interval: Option<Duration>,
+
         /// Override max difficulty to request. Auto-promotion occurs when tasks complete in < 7 min
         #[arg(long = "max-difficulty", value_name = "DIFFICULTY")]
         max_difficulty: Option<String>,
+
+        /// Only take tasks matching a policy on their preview, declining others, e.g.
+        /// "difficulty<=medium && reward>=10"
+        #[arg(long = "accept-policy", value_name = "POLICY")]
+        accept_policy: Option<AcceptPolicy>,
+
+        /// Developer mode: randomly inject faults, e.g. `drop_events=0.1,clock_jumps=0.2`
+        #[arg(
+            long = "chaos",
+            value_name = "SPEC",
+            hide = true,
+            num_args = 0..=1,
+            default_missing_value = "default"
+        )]
+        chaos: Option<String>,
+
+        /// Developer mode: prove against an in-process mock orchestrator, e.g.
+        /// `latency_ms=200,rate_limits=0.1,malformed_tasks=0.05`
+        #[arg(
+            long = "mock-orchestrator",
+            value_name = "SPEC",
+            hide = true,
+            num_args = 0..=1,
+            default_missing_value = "default",
+            conflicts_with_all = ["orchestrator_url", "orchestrator_fallback_urls", "transport"]
+        )]
+        mock_orchestrator: Option<String>,
+
+        /// Record all orchestrator requests and responses to this file, for `--replay`
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["mock_orchestrator", "orchestrator_fallback_urls", "transport"]
+        )]
+        record: Option<std::path::PathBuf>,
+
+        /// Replay orchestrator responses recorded with `--record`, without network access
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = [
+                "mock_orchestrator",
+                "record",
+                "orchestrator_url",
+                "orchestrator_fallback_urls",
+                "transport"
+            ]
+        )]
+        replay: Option<std::path::PathBuf>,
+
+        /// Serve the HTTP control API on this address, e.g. 127.0.0.1:9100
+        #[arg(long = "control-addr", value_name = "ADDR")]
+        control_addr: Option<std::net::SocketAddr>,
+
+        /// Bearer token for the control API (default: $NEXUS_CONTROL_TOKEN, then the
+        /// `control-token` secret)
+        #[arg(
+            long = "control-token",
+            value_name = "TOKEN",
+            requires = "control_addr"
+        )]
+        control_token: Option<String>,
+
+        /// Broadcast every event as JSON to WebSocket clients at ws://<ADDR>/events
+        #[arg(long = "events-ws", value_name = "ADDR")]
+        events_ws: Option<std::net::SocketAddr>,
+
+        /// Require this token from WebSocket clients (bearer header or `?token=`; default: the
+        /// `events-ws-token` secret)
+        #[arg(long = "events-ws-token", value_name = "TOKEN", requires = "events_ws")]
+        events_ws_token: Option<String>,
+
+        /// Report this 2-letter country code instead of detecting it, e.g. `DE`
+        #[arg(long, value_name = "XX", value_parser = parse_country)]
+        country: Option<String>,
+
+        /// Do not look up the country via third-party services; report it as unknown (`ZZ`)
+        #[arg(long = "no-geo", action = ArgAction::SetTrue, conflicts_with = "country")]
+        no_geo: bool,
+
+        /// Protocol used to talk to the orchestrator
+        #[arg(long, value_enum, default_value_t = Transport::Http)]
+        transport: Transport,
+
+        /// Pin each prover worker to its own CPU core
+        #[arg(long = "pin-cores", action = ArgAction::SetTrue)]
+        pin_cores: bool,
+
+        /// Pin prover workers to these CPU cores, e.g. `0-15` or `0,2,4-7`
+        #[arg(long = "core-list", value_name = "CORES", value_parser = affinity::parse_core_list)]
+        core_list: Option<Vec<usize>>,
+
+        /// What to do when running on battery below `--battery-threshold`
+        #[arg(long = "battery-policy", value_enum, default_value_t = BatteryPolicy::Ignore)]
+        battery_policy: BatteryPolicy,
+
+        /// Battery charge (percent) below which `--battery-policy` applies
+        #[arg(
+            long = "battery-threshold",
+            value_name = "PERCENT",
+            default_value_t = DEFAULT_BATTERY_THRESHOLD_PERCENT,
+            value_parser = clap::value_parser!(u8).range(1..=100)
+        )]
+        battery_threshold: u8,
+
+        /// Only prove once nobody has used the machine for this long, e.g. `10m`; pause on activity
+        #[arg(long = "only-when-idle", value_name = "DURATION", value_parser = parse_duration)]
+        only_when_idle: Option<Duration>,
+
+        /// Prove on a single worker above this temperature (°C), and pause if it keeps rising
+        #[arg(long = "thermal-limit", value_name = "CELSIUS")]
+        thermal_limit: Option<f32>,
+
+        /// Also write the session summary printed on exit to this file
+        #[arg(long = "summary-file", value_name = "PATH")]
+        summary_file: Option<std::path::PathBuf>,
+
+        /// Append a snapshot of the task counters and CPU and memory use to this file, as CSV if
+        /// it ends in `.csv` and as NDJSON otherwise
+        #[arg(long = "metrics-export", value_name = "PATH")]
+        metrics_export: Option<std::path::PathBuf>,
+
+        /// How often to append a metrics snapshot, e.g. 1m (default: 5m)
+        #[arg(
+            long = "metrics-export-interval",
+            value_name = "DURATION",
+            value_parser = parse_duration,
+            requires = "metrics_export"
+        )]
+        metrics_export_interval: Option<Duration>,
+
+        /// Keep the terminal title set to the node status, e.g. for tmux or screen windows
+        #[arg(long = "terminal-title", action = ArgAction::SetTrue)]
+        terminal_title: bool,
+
+        /// Keep the node status line in this file, e.g. for the tmux status line
+        #[arg(long = "status-file", value_name = "PATH")]
+        status_file: Option<std::path::PathBuf>,
+
+        /// Write a bundle describing each failed proof to ~/.nexus/failures, for `report`
+        #[arg(long = "capture-failures", action = ArgAction::SetTrue)]
+        capture_failures: bool,
+
+        /// Write each accepted proof and its metadata to this directory, for `verify-proof`
+        #[arg(long = "export-proofs", value_name = "DIR")]
+        export_proofs: Option<std::path::PathBuf>,
+
+        /// Start even if this version is blocked by the published version requirements
+        #[arg(long = "override-version-check", action = ArgAction::SetTrue)]
+        override_version_check: bool,
+
+        /// Sign every orchestrator request, not only proof submissions, with the prover's key
+        #[arg(long = "sign-requests", action = ArgAction::SetTrue)]
+        sign_requests: bool,
+
+        /// Take turns fetching tasks with the other instances on this host that pass this flag
+        #[arg(long = "coordinate", action = ArgAction::SetTrue)]
+        coordinate: bool,
+
+        /// Label this node, e.g. `--label site=home-lab`; saved in the config, `key=` removes one
+        #[arg(long = "label", value_name = "KEY=VALUE", value_parser = labels::parse_label)]
+        labels: Vec<(String, String)>,
+
+        /// Use the `--label` labels for this session only, without saving them; set by `fleet`
+        #[arg(long = "ephemeral-labels", action = ArgAction::SetTrue, hide = true)]
+        ephemeral_labels: bool,
+
+        /// Mask node IDs, wallet addresses and task IDs in the dashboard and logs, for sharing
+        #[arg(long = "redact", action = ArgAction::SetTrue)]
+        redact: bool,
+
+        /// Check that the node is ready to prove, fetching a task and running a warm-up proof,
+        /// then print a readiness report and exit without proving
+        #[arg(long = "dry-run", action = ArgAction::SetTrue)]
+        dry_run: bool,
+
+        /// Load test this machine: prove N synthetic multi-input tasks generated locally, without
+        /// network access, then report the sustained throughput
+        #[arg(
+            long = "simulate",
+            value_name = "N",
+            value_parser = clap::value_parser!(u32).range(1..),
+            conflicts_with_all = [
+                "node_id",
+                "dry_run",
+                "max_tasks",
+                "mock_orchestrator",
+                "record",
+                "replay",
+                "orchestrator_url",
+                "orchestrator_fallback_urls",
+                "transport"
+            ]
+        )]
+        simulate: Option<u32>,
     },
     /// Register a new user
     RegisterUser {
-        /// User's public Ethereum wallet address. 42-character hex string starting with '0x'
-        #[arg(long, value_name = "WALLET_ADDRESS")]
-        wallet_address: String,
+        /// User's public Ethereum wallet address, or its alias in the wallet address book.
+        /// If omitted, pick one from the address book.
+        #[arg(
+            long,
+            value_name = "WALLET_ADDRESS",
+            add = ArgValueCompleter::new(complete_wallets)
+        )]
+        wallet_address: Option<String>,
+
+        #[command(flatten)]
+        options: RegisterOptions,
     },
     /// Register a new node to an existing user, or link an existing node to a user.
     RegisterNode {
         /// ID of the node to register. If not provided, a new node will be created.
-        #[arg(long, value_name = "NODE_ID")]
+        #[arg(long, value_name = "NODE_ID", add = ArgValueCompleter::new(complete_node_ids))]
+        node_id: Option<u64>,
+
+        /// Register this many new nodes and record them in ~/.nexus/nodes.json
+        #[arg(
+            long,
+            value_name = "N",
+            conflicts_with = "node_id",
+            value_parser = clap::value_parser!(u32).range(1..)
+        )]
+        count: Option<u32>,
+
+        #[command(flatten)]
+        options: RegisterOptions,
+    },
+    /// Retire a node and remove it from the config file and ~/.nexus/nodes.json
+    DeregisterNode {
+        /// ID of the node to retire (default: the node in the config file)
+        #[arg(long, value_name = "NODE_ID", add = ArgValueCompleter::new(complete_node_ids))]
         node_id: Option<u64>,
+
+        /// Only remove the node from this machine, without telling the orchestrator
+        #[arg(long, action = ArgAction::SetTrue)]
+        local_only: bool,
+
+        /// Also remove the node's signing key and submission history
+        #[arg(long, action = ArgAction::SetTrue)]
+        wipe: bool,
+
+        /// Deregister without asking
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+    },
+    /// Register a user and, optionally, a new node for it in one step
+    Register {
+        /// User's public Ethereum wallet address, or its alias in the wallet address book
+        #[arg(
+            long,
+            value_name = "WALLET_ADDRESS",
+            add = ArgValueCompleter::new(complete_wallets)
+        )]
+        wallet: String,
+
+        /// Also register a new node for the user
+        #[arg(long = "create-node")]
+        create_node: bool,
+
+        #[command(flatten)]
+        options: RegisterOptions,
     },
     /// Clear the node configuration and logout.
     Logout,
+    /// Inspect the task queue of a prover running in another shell
+    Queue {
+        #[command(subcommand)]
+        action: QueueAction,
+    },
+    /// Show the orchestrator's view of the nodes of a wallet
+    Nodes {
+        #[command(subcommand)]
+        action: NodesAction,
+    },
+    /// Show network-wide prover statistics and where your node ranks
+    Network {
+        #[command(subcommand)]
+        action: NetworkAction,
+    },
+    /// Manage the wallet address book
+    Wallet {
+        #[command(subcommand)]
+        action: WalletAction,
+    },
+    /// Choose the dashboard color theme
+    Theme {
+        #[command(subcommand)]
+        action: ThemeAction,
+    },
+    /// Read or change settings in the config file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Keep tokens in the OS keychain, or in private files where there is none
+    Secret {
+        #[command(subcommand)]
+        action: SecretAction,
+    },
+    /// Install the newest release of the update channel
+    Update {
+        /// Channel to update from: stable, beta or nightly (defaults to `update.channel`)
+        #[arg(long, value_name = "CHANNEL")]
+        channel: Option<UpdateChannel>,
+
+        /// Only report whether an update is available
+        #[arg(long, action = ArgAction::SetTrue)]
+        check: bool,
+    },
+    /// Show a failure bundle written by `start --capture-failures` and optionally send it to support
+    Report {
+        /// Path of the bundle, e.g. ~/.nexus/failures/<time>-<task>.json
+        bundle: std::path::PathBuf,
+
+        /// Upload the bundle to the orchestrator's support endpoint
+        #[arg(long, action = ArgAction::SetTrue)]
+        upload: bool,
+    },
+    /// Verify a proof written by `start --export-proofs` against the embedded guest program
+    VerifyProof {
+        /// The exported `.proof` file, or its `.json` metadata
+        file: std::path::PathBuf,
+    },
+    /// Prove a single input with the embedded prover, without contacting the orchestrator
+    Prove {
+        /// Embedded guest program to run
+        #[arg(long, default_value = "fib_input_initial", value_parser = ["fib_input_initial"])]
+        program: String,
+
+        /// Public inputs as `n,init_a,init_b`, e.g. "9,1,1"
+        #[arg(
+            long,
+            value_name = "N,A,B",
+            value_parser = parse_fib_inputs,
+            required_unless_present = "elf",
+            conflicts_with = "elf"
+        )]
+        input: Option<(u32, u32, u32)>,
+
+        /// Prove this RISC-V guest ELF instead of the embedded program
+        #[arg(long, value_name = "FILE", conflicts_with = "program")]
+        elf: Option<std::path::PathBuf>,
+
+        /// Public input of the ELF: `u32:9,1,1`, `hex:<bytes>` or `file:<path>`
+        #[arg(long, value_name = "INPUT", requires = "elf")]
+        public_input: Option<GuestInput>,
+
+        /// Private input of the ELF, in the same format as `--public-input`
+        #[arg(long, value_name = "INPUT", requires = "elf")]
+        private_input: Option<GuestInput>,
+
+        /// Write the postcard-encoded proof to this file
+        #[arg(long, value_name = "FILE")]
+        out: Option<std::path::PathBuf>,
+    },
+    /// Diagnose common configuration and environment problems
+    Doctor {
+        /// Custom orchestrator URL to check (overrides environment setting)
+        #[arg(long, value_name = "URL")]
+        orchestrator_url: Option<String>,
+
+        /// Check memory against this difficulty level
+        #[arg(long, value_name = "DIFFICULTY")]
+        max_difficulty: Option<String>,
+    },
+    /// Run several nodes on this machine, listed in a manifest
+    Fleet {
+        #[command(subcommand)]
+        action: FleetAction,
+    },
+    /// Run headless proving at boot as a Windows service
+    Service {
+        #[command(subcommand)]
+        action: ServiceAction,
+    },
+    /// Print a script that sets up tab completion, e.g. `source <(nexus-cli completions bash)`
+    Completions {
+        /// Shell to complete in
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Print the man page, or write one page per command to a directory
+    Man {
+        /// Write `nexus-cli.1`, `nexus-cli-start.1` and so on to this directory
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<std::path::PathBuf>,
+    },
     /// Hidden command for subprocess proof generation
     #[command(hide = true, name = "prove-fib-subprocess")]
     ProveFibSubprocess {
         /// Serialized inputs blob
         #[arg(long)]
         inputs: String,
+
+        /// Core to pin the prover to
+        #[arg(long)]
+        core: Option<usize>,
+    },
+    /// Hidden command for the long-lived prover subprocess of a worker
+    #[command(hide = true, name = "prove-fib-server")]
+    ProveFibServer {
+        /// Core to pin the prover to
+        #[arg(long)]
+        core: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+enum QueueAction {
+    /// List tasks currently held by the prover
+    List,
+    /// Drop a task so that it is neither proved nor submitted
+    Drop {
+        /// ID of the task to drop
+        task_id: String,
+    },
+    /// Show task counters for the running session
+    Stats,
+}
+
+#[derive(Subcommand, Debug)]
+enum NodesAction {
+    /// List every node registered to the wallet, with its type, last activity and points, and
+    /// whether it is known on this machine
+    List {
+        /// Wallet address or alias (default: the wallet in the config file)
+        #[arg(long, value_name = "WALLET", add = ArgValueCompleter::new(complete_wallets))]
+        wallet: Option<String>,
+
+        /// Print the nodes as JSON
+        #[arg(long, action = ArgAction::SetTrue)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum NetworkAction {
+    /// Show the number of active provers, the node's rank by points and the mix of pending tasks
+    /// by difficulty
+    Stats {
+        /// Node ID (default: the node in the config file)
+        #[arg(long, value_name = "NODE_ID", add = ArgValueCompleter::new(complete_node_ids))]
+        node_id: Option<u64>,
+
+        /// Print the statistics as JSON
+        #[arg(long, action = ArgAction::SetTrue)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum WalletAction {
+    /// Save a wallet address under an alias
+    Add {
+        /// Short name for the wallet
+        alias: String,
+        /// Public Ethereum wallet address. 42-character hex string starting with '0x'
+        wallet_address: String,
+    },
+    /// List saved wallets; `*` marks the registered one
+    List,
+    /// Register with a saved wallet, switching the config to it
+    Use {
+        /// Alias of the wallet
+        #[arg(add = ArgValueCompleter::new(complete_wallets))]
+        alias: String,
+
+        #[command(flatten)]
+        options: RegisterOptions,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ThemeAction {
+    /// List built-in themes and themes from ~/.nexus/themes; `*` marks the selected one
+    List,
+    /// Use a theme for the dashboard
+    Set {
+        /// Name of the theme
+        #[arg(add = ArgValueCompleter::new(complete_themes))]
+        name: String,
+    },
+    /// Show the colors of a theme
+    Preview {
+        /// Name of the theme
+        #[arg(add = ArgValueCompleter::new(complete_themes))]
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigAction {
+    /// Print the value of a setting, e.g. `update.channel`
+    Get {
+        /// Dotted name of the setting
+        key: String,
+    },
+    /// Change a setting, e.g. `config set update.channel beta`
+    Set {
+        /// Dotted name of the setting
+        key: String,
+        /// New value
+        value: String,
+    },
+    /// Check the config file and list every problem with a hint to fix it; exits non-zero on
+    /// errors, for provisioning scripts
+    Validate,
+    /// Encrypt the config file with a passphrase, or with a key kept in the OS keychain
+    Encrypt {
+        /// Keep the key in the OS keychain instead of asking for a passphrase
+        #[arg(long, action = ArgAction::SetTrue)]
+        keychain: bool,
+    },
+    /// Decrypt the config file back to plain JSON
+    Decrypt,
+}
+
+#[derive(Subcommand, Debug)]
+enum SecretAction {
+    /// Save a secret, typed in the terminal or read from stdin
+    Set {
+        /// Name of the secret
+        #[arg(value_parser = secrets::USER_SECRETS.to_vec())]
+        name: String,
+        /// Value of the secret; avoid it on shared machines, it shows in the process list
+        #[arg(long)]
+        value: Option<String>,
+    },
+    /// Remove a secret
+    Delete {
+        /// Name of the secret
+        #[arg(value_parser = secrets::USER_SECRETS.to_vec())]
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum FleetAction {
+    /// Start and supervise every node in the manifest
+    Start {
+        /// TOML file listing the nodes and their settings
+        #[arg(long, value_name = "PATH")]
+        manifest: std::path::PathBuf,
+
+        /// Print the output of every node instead of showing the fleet dashboard
+        #[arg(long = "headless", action = ArgAction::SetTrue)]
+        headless: bool,
+
+        /// Dashboard style: `plain` is ASCII-only and monochrome (default: `full`; `compact` is
+        /// drawn as `full`)
+        #[arg(long = "ui", value_enum)]
+        ui: Option<UiMode>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ServiceAction {
+    /// Register the service; arguments after `--` are passed to `start`, e.g. `-- --max-threads 4`
+    Install {
+        #[arg(last = true, value_name = "START_ARGS")]
+        start_args: Vec<String>,
+    },
+    /// Stop and remove the service
+    Uninstall,
+    /// Start the installed service
+    Start,
+    /// Stop the service once the task in progress is done
+    Stop,
+    /// Run as the service; invoked by the service control manager
+    #[command(hide = true)]
+    Run {
+        /// Directory of the config file to use
+        #[arg(long, value_name = "DIR")]
+        nexus_dir: std::path::PathBuf,
+
+        /// Command line to run, e.g. `start --headless`
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
     },
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
+async fn main() {
+    // Answer the shell when it asks for completions, see `completions`
+    CompleteEnv::with_factory(|| Args::command().name(completions::bin_name()))
+        .var(completions::COMPLETE_VAR)
+        .complete();
+
     // Set up panic hook to prevent core dumps
     std::panic::set_hook(Box::new(|panic_info| {
         eprintln!("Panic occurred: {}", panic_info);
         std::process::exit(1);
     }));
 
-    let nexus_environment_str = std::env::var("NEXUS_ENVIRONMENT").unwrap_or_default();
-    let environment = nexus_environment_str
-        .parse::<Environment>()
-        .unwrap_or(Environment::default());
+    // Exit with a documented code for the failure's cause, see `exit_code`
+    if let Err(e) = run().await {
+        eprintln!("Error: {}", e);
+        ExitCode::from_error(e.as_ref()).exit();
+    }
+}
+
+async fn run() -> Result<(), Box<dyn Error>> {
+    run_args(Args::parse()).await
+}
+
+/// Parse `args` as a command line, e.g. the one stored with the Windows service.
+fn parse_args(
+    args: impl IntoIterator<Item = impl Into<std::ffi::OsString>>,
+) -> Result<Args, UsageError> {
+    Args::try_parse_from(
+        std::iter::once(std::ffi::OsString::from("nexus-network"))
+            .chain(args.into_iter().map(Into::into)),
+    )
+    .map_err(|e| UsageError(e.to_string()))
+}
 
+async fn run_args(args: Args) -> Result<(), Box<dyn Error>> {
     let config_path = get_config_path()?;
 
-    let args = Args::parse();
+    if let Some(lang) = &args.lang {
+        if i18n::supported_language(lang).is_none() {
+            return Err(UsageError(format!(
+                "Unsupported language '{}'. Available: {}",
+                lang,
+                i18n::available_languages().collect::<Vec<_>>().join(", ")
+            ))
+            .into());
+        }
+    }
+    i18n::init(args.lang.as_deref());
+
+    // Ask for the key of an encrypted config file once, before anything reads it
+    if reads_config(&args.command) {
+        config_crypto::unlock(&config_path)
+            .map_err(|e| ConfigError::Unreadable(std::io::Error::from(e)))?;
+    }
+
+    // `--orchestrator-url` overrides the environment's URL for the commands that take it
+    let environment_name = args
+        .env
+        .clone()
+        .or_else(|| std::env::var("NEXUS_ENVIRONMENT").ok());
+    let orchestrator_url = match &args.command {
+        Command::Start {
+            orchestrator_url, ..
+        }
+        | Command::Doctor {
+            orchestrator_url, ..
+        } => orchestrator_url.clone(),
+        _ => None,
+    };
+    let defined_environments = Config::load_from_file(&config_path)
+        .map(|config| config.environments)
+        .unwrap_or_default();
+    let environment = resolve_environment(
+        environment_name.as_deref(),
+        orchestrator_url,
+        &defined_environments,
+    )
+    .map_err(UsageError)?;
+
     match args.command {
         Command::Start {
             node_id,
             headless,
             max_threads,
-            orchestrator_url,
+            orchestrator_url: _,
+            orchestrator_fallback_urls,
             check_mem,
             with_background,
+            ui,
+            ui_fps,
+            profile,
             max_tasks,
+            stop_after_points,
+            max_runtime,
+            restart_interval,
             max_difficulty,
+            accept_policy,
+            chaos,
+            mock_orchestrator,
+            record,
+            replay,
+            control_addr,
+            control_token,
+            events_ws,
+            events_ws_token,
+            country,
+            no_geo,
+            transport,
+            pin_cores,
+            core_list,
+            battery_policy,
+            battery_threshold,
+            only_when_idle,
+            thermal_limit,
+            summary_file,
+            metrics_export,
+            metrics_export_interval,
+            terminal_title,
+            status_file,
+            capture_failures,
+            export_proofs,
+            override_version_check,
+            sign_requests,
+            coordinate,
+            labels,
+            ephemeral_labels,
+            redact,
+            dry_run,
+            simulate,
         } => {
-            // If a custom orchestrator URL is provided, create a custom environment
-            let final_environment = if let Some(url) = orchestrator_url {
-                Environment::Custom {
-                    orchestrator_url: url,
+            if redact {
+                nexus_cli_core::redact::enable();
+            }
+            if no_geo {
+                orchestrator::pin_country(orchestrator::UNKNOWN_COUNTRY);
+            } else if let Some(country) = country {
+                orchestrator::pin_country(&country);
+            }
+            if let Some(spec) = chaos {
+                if mock_orchestrator.is_none() && replay.is_none() {
+                    return Err(UsageError(
+                        "--chaos requires --mock-orchestrator or --replay".to_string(),
+                    ));
                 }
-            } else {
-                environment
+                let chaos_config = spec
+                    .parse::<chaos::ChaosConfig>()
+                    .map_err(|e| UsageError(format!("Invalid chaos spec: {}", e)))?;
+                print_cmd_warn!(
+                    "Chaos mode enabled",
+                    "Faults will be injected at random: {:?}",
+                    chaos_config
+                );
+                chaos::enable(chaos_config);
+            }
+            let mock_orchestrator = mock_orchestrator
+                .map(|spec| {
+                    spec.parse::<MockConfig>()
+                        .map_err(|e| UsageError(format!("Invalid mock orchestrator spec: {}", e)))
+                })
+                .transpose()?;
+            let control_api = match control_addr {
+                Some(addr) => {
+                    let token = control_token
+                        .or_else(|| std::env::var("NEXUS_CONTROL_TOKEN").ok())
+                        .filter(|token| !token.trim().is_empty())
+                        .or_else(|| secrets::lookup(&config_path, secrets::CONTROL_TOKEN))
+                        .ok_or_else(|| {
+                            UsageError(
+                                "--control-addr requires --control-token, NEXUS_CONTROL_TOKEN or `nexus-cli secret set control-token`"
+                                    .to_string(),
+                            )
+                        })?;
+                    Some(ControlApiConfig { addr, token })
+                }
+                None => None,
             };
+            let event_stream = events_ws.map(|addr| EventStreamConfig {
+                addr,
+                token: events_ws_token
+                    .or_else(|| secrets::lookup(&config_path, secrets::EVENTS_WS_TOKEN)),
+            });
+            let core_pinning = match core_list {
+                Some(cores) => Some(CorePinning::List(cores)),
+                None => pin_cores.then_some(CorePinning::Auto),
+            };
+            let failure_dir = capture_failures.then(|| failures::failures_dir(&config_path));
+            let metrics_export = metrics_export.map(|path| {
+                let export = MetricsExportConfig::new(path);
+                match metrics_export_interval {
+                    Some(interval) => export.with_interval(interval),
+                    None => export,
+                }
+            });
+            let battery = (battery_policy != BatteryPolicy::Ignore).then_some(BatteryConfig {
+                policy: battery_policy,
+                threshold_percent: battery_threshold,
+            });
+            let mut builder = Session::builder()
+                .environment(environment)
+                .fallback_urls(orchestrator_fallback_urls)
+                .transport(transport)
+                .config_path(config_path)
+                .check_memory(check_mem)
+                .override_version_check(override_version_check)
+                .sign_requests(sign_requests)
+                .coordinate(coordinate)
+                .labels(labels)
+                .ephemeral_labels(ephemeral_labels)
+                .terminal_title(terminal_title);
+            if let Some(node_id) = node_id {
+                builder = builder.node_id(node_id);
+            }
+            if let Some(max_tasks) = max_tasks {
+                builder = builder.max_tasks(max_tasks);
+            }
+            if let Some(points) = stop_after_points {
+                builder = builder.stop_after_points(points);
+            }
+            if let Some(max_runtime) = max_runtime {
+                builder = builder.max_runtime(max_runtime);
+            }
+            if let Some(restart_interval) = restart_interval {
+                builder = builder.restart_interval(restart_interval);
+            }
+            if let Some(core_pinning) = core_pinning {
+                builder = builder.pin_cores(core_pinning);
+            }
+            if let Some(battery) = battery {
+                builder = builder.battery(battery);
+            }
+            if let Some(min_idle) = only_when_idle {
+                builder = builder.only_when_idle(min_idle);
+            }
+            if let Some(limit) = thermal_limit {
+                builder = builder.thermal_limit(limit);
+            }
+            if let Some(mock_orchestrator) = mock_orchestrator {
+                builder = builder.mock_orchestrator(mock_orchestrator);
+            }
+            if let Some(record) = record {
+                builder = builder.record(record);
+            }
+            if let Some(replay) = replay {
+                builder = builder.replay(replay);
+            }
+            if let Some(failure_dir) = failure_dir {
+                builder = builder.capture_failures(failure_dir);
+            }
+            if let Some(proof_export_dir) = export_proofs {
+                builder = builder.export_proofs(proof_export_dir);
+            }
+            if let Some(accept_policy) = accept_policy {
+                builder = builder.accept_policy(accept_policy);
+            }
+            if let Some(control_api) = control_api {
+                builder = builder.control_api(control_api);
+            }
+            if let Some(event_stream) = event_stream {
+                builder = builder.event_stream(event_stream);
+            }
+            if let Some(summary_file) = summary_file {
+                builder = builder.summary_file(summary_file);
+            }
+            if let Some(metrics_export) = metrics_export {
+                builder = builder.metrics_export(metrics_export);
+            }
+            if let Some(ui_fps) = ui_fps {
+                builder = builder.ui_fps(ui_fps);
+            }
+            if let Some(status_file) = status_file {
+                builder = builder.status_file(status_file);
+            }
             start(
-                node_id,
-                final_environment,
-                config_path,
-                headless,
-                max_threads,
-                check_mem,
-                with_background,
-                max_tasks,
-                max_difficulty,
+                builder,
+                StartOptions {
+                    headless: headless || (profile.headless_by_default() && ui.is_none()),
+                    profile,
+                    max_threads,
+                    max_difficulty,
+                    with_background,
+                    ui_mode: ui.unwrap_or_default(),
+                    dry_run,
+                    simulate,
+                },
             )
             .await
         }
@@ -203,64 +1024,390 @@ async fn main() -> Result<(), Box<dyn Error>> {
             print_cmd_info!("Logging out", "Clearing node configuration file...");
             Config::clear_node_config(&config_path).map_err(Into::into)
         }
-        Command::RegisterUser { wallet_address } => {
-            print_cmd_info!("Registering user", "Wallet address: {}", wallet_address);
+        Command::Queue { action } => {
+            let request = match action {
+                QueueAction::List => ControlRequest::ListQueue,
+                QueueAction::Drop { task_id } => ControlRequest::DropTask { task_id },
+                QueueAction::Stats => ControlRequest::QueueStats,
+            };
+            ipc::client::run_queue_command(&control_socket_path(&config_path), request).await
+        }
+        Command::RegisterUser {
+            wallet_address,
+            options,
+        } => {
+            let wallet_address = wallet::resolve_wallet_arg(
+                &config_path,
+                wallet_address.as_deref(),
+                options.output == OutputFormat::Text,
+            )?;
+            if options.output == OutputFormat::Text {
+                print_cmd_info!("Registering user", "Wallet address: {}", wallet_address);
+            }
+            let orchestrator = Box::new(OrchestratorClient::new(environment));
+            let registration =
+                register_user(&wallet_address, &config_path, orchestrator, options).await?;
+            options.report(&registration).map_err(Into::into)
+        }
+        Command::RegisterNode {
+            count: Some(count),
+            options,
+            ..
+        } => {
+            let orchestrator = Box::new(OrchestratorClient::new(environment));
+            let secret_store = secrets::open(&config_path);
+            let batch = register_nodes(
+                count,
+                &config_path,
+                orchestrator,
+                secret_store.as_ref(),
+                options,
+            )
+            .await?;
+            options.report(&batch).map_err(Into::into)
+        }
+        Command::RegisterNode {
+            node_id,
+            count: None,
+            options,
+        } => {
             let orchestrator = Box::new(OrchestratorClient::new(environment));
-            register_user(&wallet_address, &config_path, orchestrator).await
+            let secret_store = secrets::open(&config_path);
+            let registration = register_node(
+                node_id,
+                &config_path,
+                orchestrator,
+                secret_store.as_ref(),
+                options,
+            )
+            .await?;
+            options.report(&registration).map_err(Into::into)
         }
-        Command::RegisterNode { node_id } => {
+        Command::DeregisterNode {
+            node_id,
+            local_only,
+            wipe,
+            yes,
+            output,
+        } => {
+            let options = RegisterOptions {
+                yes,
+                if_not_exists: false,
+                output,
+            };
             let orchestrator = Box::new(OrchestratorClient::new(environment));
-            register_node(node_id, &config_path, orchestrator).await
+            let secret_store = secrets::open(&config_path);
+            let deregistration = deregister_node(
+                node_id,
+                &config_path,
+                orchestrator,
+                secret_store.as_ref(),
+                local_only,
+                wipe,
+                options,
+            )
+            .await?;
+            options.report(&deregistration).map_err(Into::into)
+        }
+        Command::Register {
+            wallet,
+            create_node,
+            options,
+        } => {
+            let wallet = wallet::resolve_wallet_arg(&config_path, Some(&wallet), false)?;
+            if options.output == OutputFormat::Text {
+                print_cmd_info!("Registering user", "Wallet address: {}", wallet);
+            }
+            let orchestrator = Box::new(OrchestratorClient::new(environment.clone()));
+            let user = register_user(&wallet, &config_path, orchestrator, options).await?;
+            let registration = if create_node {
+                let orchestrator = Box::new(OrchestratorClient::new(environment));
+                let secret_store = secrets::open(&config_path);
+                let node = register_node(
+                    None,
+                    &config_path,
+                    orchestrator,
+                    secret_store.as_ref(),
+                    options,
+                )
+                .await?;
+                Registration {
+                    created: user.created || node.created,
+                    ..node
+                }
+            } else {
+                user
+            };
+            options.report(&registration).map_err(Into::into)
+        }
+        Command::Nodes { action } => match action {
+            NodesAction::List { wallet, json } => {
+                let wallet = wallet
+                    .map(|wallet| wallet::resolve_wallet_arg(&config_path, Some(&wallet), false))
+                    .transpose()?;
+                let orchestrator = OrchestratorClient::new(environment);
+                nodes::list_nodes(&config_path, wallet, &orchestrator, json).await
+            }
+        },
+        Command::Network { action } => match action {
+            NetworkAction::Stats { node_id, json } => {
+                let orchestrator = OrchestratorClient::new(environment);
+                network_stats::show_network_stats(&config_path, node_id, &orchestrator, json).await
+            }
+        },
+        Command::Wallet { action } => match action {
+            WalletAction::Add {
+                alias,
+                wallet_address,
+            } => wallet::add_wallet(&config_path, &alias, &wallet_address),
+            WalletAction::List => wallet::list_wallets(&config_path),
+            WalletAction::Use { alias, options } => {
+                let wallet_address = wallet::lookup(&config_path, &alias)?;
+                if options.output == OutputFormat::Text {
+                    print_cmd_info!("Switching wallet", "{}: {}", alias, wallet_address);
+                }
+                let orchestrator = Box::new(OrchestratorClient::new(environment));
+                let registration =
+                    register_user(&wallet_address, &config_path, orchestrator, options).await?;
+                options.report(&registration).map_err(Into::into)
+            }
+        },
+        Command::Theme { action } => match action {
+            ThemeAction::List => theme::list_themes(&config_path),
+            ThemeAction::Set { name } => theme::set_theme(&config_path, &name),
+            ThemeAction::Preview { name } => theme::preview_theme(&config_path, &name),
+        },
+        Command::Config { action } => match action {
+            ConfigAction::Get { key } => config::print_setting(&config_path, &key),
+            ConfigAction::Set { key, value } => config::save_setting(&config_path, &key, &value),
+            ConfigAction::Validate => config_validation::run_validate(&config_path),
+            ConfigAction::Encrypt { keychain } => {
+                config_crypto::encrypt_config(&config_path, keychain)
+            }
+            ConfigAction::Decrypt => config_crypto::decrypt_config(&config_path),
+        },
+        Command::Secret { action } => match action {
+            SecretAction::Set { name, value } => secrets::set_secret(&config_path, &name, value),
+            SecretAction::Delete { name } => secrets::delete_secret(&config_path, &name),
+        },
+        Command::Update { channel, check } => updater::update(&config_path, channel, check).await,
+        Command::Report { bundle, upload } => failures::report(&bundle, environment, upload).await,
+        Command::VerifyProof { file } => {
+            let metadata = verify_exported_proof(&file)?;
+            print_cmd_success!(
+                &tr!("verify-proof-ok"),
+                "{}",
+                tr!(
+                    "verify-proof-details",
+                    task_id = metadata.task_id,
+                    index = metadata.input_index
+                )
+            );
+            Ok(())
+        }
+        Command::Prove {
+            program: _,
+            input,
+            elf,
+            public_input,
+            private_input,
+            out,
+        } => {
+            let proof = match (elf, input) {
+                (Some(elf), _) => {
+                    prove_elf(
+                        elf,
+                        private_input.unwrap_or_default(),
+                        public_input.unwrap_or_default(),
+                    )
+                    .await?
+                }
+                (None, Some(input)) => prove_locally(input).await?,
+                (None, None) => unreachable!("clap requires --input without --elf"),
+            };
+            for line in &proof.logs {
+                print!("{}", line);
+            }
+            if let Some(path) = &out {
+                std::fs::write(path, &proof.bytes)?;
+            }
+            print_cmd_success!(
+                &tr!("prove-ok"),
+                "{}",
+                tr!(
+                    "prove-details",
+                    hash = proof.proof_hash,
+                    seconds = format!("{:.2}", proof.duration.as_secs_f64()),
+                    memory = format!(
+                        "{:.2}",
+                        proof.peak_memory_bytes as f64 / 1024.0 / 1024.0 / 1024.0
+                    )
+                )
+            );
+            if let Some(path) = out {
+                println!("{}", tr!("prove-written", path = path.display()));
+            }
+            Ok(())
+        }
+        Command::Doctor {
+            orchestrator_url: _,
+            max_difficulty,
+        } => {
+            let max_difficulty = match max_difficulty {
+                Some(difficulty_str) => match validate_difficulty(&difficulty_str) {
+                    Some(difficulty) => Some(difficulty),
+                    None => {
+                        eprintln!(
+                            "Error: Invalid difficulty level '{}'",
+                            difficulty_str.trim()
+                        );
+                        print_available_difficulties();
+                        ExitCode::Usage.exit();
+                    }
+                },
+                None => None,
+            };
+            if !doctor::run_doctor(&config_path, &environment, max_difficulty).await {
+                ExitCode::Failure.exit();
+            }
+            Ok(())
         }
-        Command::ProveFibSubprocess { inputs } => {
-            let inputs: (u32, u32, u32) = serde_json::from_str(&inputs)?;
-            match ProvingEngine::prove_fib_subprocess(&inputs) {
-                Ok(proof) => {
-                    let bytes = to_allocvec(&proof)?;
-                    let mut out = std::io::stdout().lock();
-                    out.write_all(&bytes)?;
-                    Ok(())
+        Command::Fleet { action } => match action {
+            FleetAction::Start {
+                manifest,
+                headless,
+                ui,
+            } => {
+                // Every node runs against the same environment, in the same language
+                let mut global_args = Vec::new();
+                if let Some(env) = &args.env {
+                    global_args.extend(["--env".to_string(), env.clone()]);
                 }
-                Err(e) => {
-                    eprintln!("{}", e);
-                    exit(consts::cli_consts::SUBPROCESS_INTERNAL_ERROR_CODE);
+                if let Some(lang) = &args.lang {
+                    global_args.extend(["--lang".to_string(), lang.clone()]);
                 }
+                fleet::run_fleet(
+                    &manifest,
+                    FleetOptions {
+                        global_args,
+                        headless,
+                        ui_mode: ui.unwrap_or_default(),
+                        config_path,
+                    },
+                )
+                .await
+            }
+        },
+        Command::Service { action } => match action {
+            ServiceAction::Install { start_args } => {
+                // Catch mistakes now rather than when the service fails to start at boot
+                parse_args(service::start_arguments(&start_args))?;
+                service::install(&service::service_arguments(
+                    &config::nexus_dir()?,
+                    &start_args,
+                ))?;
+                print_cmd_success!(
+                    &tr!("service-installed"),
+                    "{}",
+                    tr!("service-installed-details", name = service::SERVICE_NAME)
+                );
+                Ok(())
+            }
+            ServiceAction::Uninstall => {
+                service::uninstall()?;
+                print_cmd_success!(&tr!("service-uninstalled"), "{}", service::SERVICE_NAME);
+                Ok(())
+            }
+            ServiceAction::Start => {
+                service::start()?;
+                print_cmd_success!(&tr!("service-started"), "{}", service::SERVICE_NAME);
+                Ok(())
+            }
+            ServiceAction::Stop => {
+                service::stop()?;
+                print_cmd_success!(&tr!("service-stopped"), "{}", service::SERVICE_NAME);
+                Ok(())
+            }
+            ServiceAction::Run { nexus_dir, args } => {
+                config::set_nexus_dir(nexus_dir);
+                let args = parse_args(args)?;
+                let runtime = tokio::runtime::Handle::current();
+                let session = move || runtime.block_on(run_args(args)).map_err(|e| e.to_string());
+                tokio::task::spawn_blocking(move || service::run(Box::new(session))).await??;
+                Ok(())
+            }
+        },
+        Command::Completions { shell } => {
+            completions::write_registration(shell, &completions::bin_name(), &mut std::io::stdout())
+                .map_err(Into::into)
+        }
+        Command::Man { out_dir } => {
+            let command = Args::command().name(completions::bin_name());
+            completions::write_man_pages(command, out_dir.as_deref(), &mut std::io::stdout())?;
+            if let Some(dir) = out_dir {
+                print_cmd_success!("Man pages", "Written to {}", dir.display());
             }
+            Ok(())
+        }
+        Command::ProveFibSubprocess { inputs, core } => {
+            nexus_cli_core::run_prove_subprocess(&inputs, core)
         }
+        Command::ProveFibServer { core } => nexus_cli_core::run_prove_server(core),
     }
 }
 
-/// Starts the Nexus CLI application.
+/// Whether `command` reads the config file.
 ///
-/// # Arguments
-/// * `node_id` - This client's unique identifier, if available.
-/// * `env` - The environment to connect to.
-/// * `config_path` - Path to the configuration file.
-/// * `headless` - If true, runs without the terminal UI.
-/// * `max_threads` - Optional maximum number of threads to use for proving.
-/// * `check_mem` - Whether to check risky memory usage.
-/// * `with_background` - Whether to use the alternate TUI background color.
-/// * `max_tasks` - Optional maximum number of tasks to prove.
-#[allow(clippy::too_many_arguments)]
-async fn start(
-    node_id: Option<u64>,
-    env: Environment,
-    config_path: std::path::PathBuf,
+/// The others never ask for the passphrase of an encrypted config file: prover subprocesses have
+/// no terminal, and `logout` must work when the passphrase is forgotten.
+fn reads_config(command: &Command) -> bool {
+    !matches!(
+        command,
+        Command::Logout
+            | Command::Secret { .. }
+            | Command::VerifyProof { .. }
+            | Command::Prove { .. }
+            | Command::Completions { .. }
+            | Command::Man { .. }
+            | Command::ProveFibSubprocess { .. }
+            | Command::ProveFibServer { .. }
+    )
+}
+
+/// How `start` runs a session, beyond what the flags set on its builder directly
+struct StartOptions {
+    /// Run without the terminal UI
     headless: bool,
+    /// Preset capping workers and difficulty for the machine
+    profile: ProvingProfile,
+    /// Maximum number of threads to use for proving
     max_threads: Option<u32>,
-    check_mem: bool,
-    with_background: bool,
-    max_tasks: Option<u32>,
+    /// Difficulty override, as given on the command line
     max_difficulty: Option<String>,
-) -> Result<(), Box<dyn Error>> {
-    // 1. Version checking (will internally perform country detection without race)
-    validate_version_requirements().await?;
+    /// Use the alternate TUI background color
+    with_background: bool,
+    /// Dashboard style
+    ui_mode: UiMode,
+    /// Only check that the node is ready to prove
+    dry_run: bool,
+    /// Number of synthetic tasks to prove as a load test
+    simulate: Option<u32>,
+}
 
-    // 2. Configuration resolution
-    let orchestrator_client = OrchestratorClient::new(env.clone());
-    let config = Config::resolve(node_id, &config_path, &orchestrator_client).await?;
+/// Starts the Nexus CLI application with the session configured by `builder`, after applying the
+/// profile, worker cap and difficulty override of `options`.
+async fn start(mut builder: SessionBuilder, options: StartOptions) -> Result<(), Box<dyn Error>> {
+    let StartOptions {
+        headless,
+        profile,
+        max_threads,
+        max_difficulty,
+        with_background,
+        ui_mode,
+        dry_run,
+        simulate,
+    } = options;
 
-    // 3. Session setup (authenticated worker only)
     // Parse and validate difficulty override (case-insensitive)
     let max_difficulty_parsed = if let Some(difficulty_str) = &max_difficulty {
         match validate_difficulty(difficulty_str) {
@@ -273,80 +1420,57 @@ async fn start(
                 print_available_difficulties();
                 eprintln!();
                 eprintln!("Note: Difficulty levels are case-insensitive.");
-                std::process::exit(1);
+                ExitCode::Usage.exit();
             }
         }
     } else {
         None
     };
 
-    let session = setup_session(
-        config,
-        env,
-        check_mem,
-        max_threads,
-        max_tasks,
-        max_difficulty_parsed,
-    )
-    .await?;
-
-    // 4. Run appropriate mode
-    if headless {
-        run_headless_mode(session).await
-    } else {
-        run_tui_mode(session, with_background).await
+    // Apply the profile: a requested difficulty wins, the worker cap always applies
+    let max_difficulty_parsed = max_difficulty_parsed.or(profile.default_max_difficulty());
+    if let Some(difficulty) = max_difficulty_parsed {
+        if let Some(warning) =
+            profile.difficulty_warning(difficulty, nexus_cli_core::system::total_memory_gb())
+        {
+            print_cmd_warn!("Difficulty too high", "{}", warning);
+        }
     }
-}
+    let max_threads = match (profile.max_workers(), max_threads) {
+        (Some(cap), Some(requested)) if requested > cap => {
+            print_cmd_warn!(
+                "Profile",
+                "Using {} worker instead of {}, the most the profile allows",
+                cap,
+                requested
+            );
+            Some(cap)
+        }
+        (Some(cap), None) => Some(cap),
+        (_, requested) => requested,
+    };
 
-#[cfg(test)]
-mod tests {
-    use crate::nexus_orchestrator::TaskDifficulty;
-
-    #[test]
-    fn test_difficulty_validation() {
-        // Test valid difficulty levels (case-insensitive)
-        assert_eq!(validate_difficulty("small"), Some(TaskDifficulty::Small));
-        assert_eq!(validate_difficulty("SMALL"), Some(TaskDifficulty::Small));
-        assert_eq!(validate_difficulty("Small"), Some(TaskDifficulty::Small));
-
-        assert_eq!(
-            validate_difficulty("small_medium"),
-            Some(TaskDifficulty::SmallMedium)
-        );
-        assert_eq!(
-            validate_difficulty("SMALL_MEDIUM"),
-            Some(TaskDifficulty::SmallMedium)
-        );
-
-        assert_eq!(validate_difficulty("medium"), Some(TaskDifficulty::Medium));
-        assert_eq!(validate_difficulty("large"), Some(TaskDifficulty::Large));
-        assert_eq!(
-            validate_difficulty("extra_large"),
-            Some(TaskDifficulty::ExtraLarge)
-        );
-        assert_eq!(
-            validate_difficulty("extra_large_2"),
-            Some(TaskDifficulty::ExtraLarge2)
-        );
-
-        // Test invalid difficulty levels
-        assert_eq!(validate_difficulty("invalid"), None);
-        assert_eq!(validate_difficulty("small medium"), None); // space instead of underscore
-        assert_eq!(validate_difficulty(""), None);
-        assert_eq!(validate_difficulty("   "), None);
-        assert_eq!(validate_difficulty("SMALL_MEDIUM_EXTRA"), None);
-        assert_eq!(validate_difficulty("123"), None);
+    // Version checking, configuration resolution, and session setup
+    if let Some(max_threads) = max_threads {
+        builder = builder.max_threads(max_threads);
+    }
+    if let Some(max_difficulty) = max_difficulty_parsed {
+        builder = builder.max_difficulty(max_difficulty);
+    }
+    if dry_run {
+        return builder.dry_run().await;
     }
+    if let Some(tasks) = simulate {
+        let report = builder.simulate(tasks).await?;
+        println!("\n{}", report);
+        return Ok(());
+    }
+    let session = builder.start().await?;
 
-    fn validate_difficulty(difficulty_str: &str) -> Option<TaskDifficulty> {
-        match difficulty_str.trim().to_ascii_uppercase().as_str() {
-            "SMALL" => Some(TaskDifficulty::Small),
-            "SMALL_MEDIUM" => Some(TaskDifficulty::SmallMedium),
-            "MEDIUM" => Some(TaskDifficulty::Medium),
-            "LARGE" => Some(TaskDifficulty::Large),
-            "EXTRA_LARGE" => Some(TaskDifficulty::ExtraLarge),
-            "EXTRA_LARGE_2" => Some(TaskDifficulty::ExtraLarge2),
-            _ => None,
-        }
+    // Run appropriate mode
+    if headless {
+        session.run_headless().await
+    } else {
+        session.run_tui(with_background, ui_mode).await
     }
 }
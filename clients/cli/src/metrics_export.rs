@@ -0,0 +1,270 @@
+//! Metrics snapshots for spreadsheets
+//!
+//! With `--metrics-export <PATH>`, the zkVM task counters and the CPU and memory use shown in the
+//! dashboard are appended to `PATH` every `--metrics-export-interval` (default 5m), and once more
+//! when the session shuts down. They are sampled whether or not the dashboard is shown. Files
+//! ending in `.csv` get comma-separated rows under a header line; any other file gets one JSON
+//! object per line (NDJSON).
+
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::consts::cli_consts::metrics_export::DEFAULT_INTERVAL_SECS;
+use crate::events::Event;
+use crate::ui::metrics::{SystemMetrics, ZkVMMetrics};
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use sysinfo::System;
+use tokio::sync::{broadcast, mpsc};
+
+/// Where and how often to export metrics
+#[derive(Debug, Clone)]
+pub struct MetricsExportConfig {
+    pub path: PathBuf,
+    pub interval: Duration,
+}
+
+impl MetricsExportConfig {
+    /// Export to `path` at the default interval.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            interval: Duration::from_secs(DEFAULT_INTERVAL_SECS),
+        }
+    }
+
+    /// Export every `interval` instead.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+/// File format, chosen by the extension of the export file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsFormat {
+    Csv,
+    Ndjson,
+}
+
+impl MetricsFormat {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => MetricsFormat::Csv,
+            _ => MetricsFormat::Ndjson,
+        }
+    }
+}
+
+/// One row of the export
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricsSnapshot {
+    /// When the snapshot was taken, in RFC 3339
+    pub timestamp: String,
+    pub uptime_secs: u64,
+    pub cpu_percent: f32,
+    pub ram_bytes: u64,
+    pub peak_ram_bytes: u64,
+    pub total_ram_bytes: u64,
+    pub tasks_fetched: usize,
+    pub tasks_submitted: usize,
+    pub success_rate: f64,
+    pub zkvm_runtime_secs: u64,
+    pub last_task_status: String,
+    pub worker_restarts: usize,
+    pub degraded: bool,
+}
+
+impl MetricsSnapshot {
+    const CSV_HEADER: &'static str = "timestamp,uptime_secs,cpu_percent,ram_bytes,peak_ram_bytes,\
+        total_ram_bytes,tasks_fetched,tasks_submitted,success_rate,zkvm_runtime_secs,\
+        last_task_status,worker_restarts,degraded";
+
+    pub fn new(uptime: Duration, system: &SystemMetrics, zkvm: &ZkVMMetrics) -> Self {
+        Self {
+            timestamp: crate::clock::utc_now().to_rfc3339(),
+            uptime_secs: uptime.as_secs(),
+            cpu_percent: system.cpu_percent,
+            ram_bytes: system.ram_bytes,
+            peak_ram_bytes: system.peak_ram_bytes,
+            total_ram_bytes: system.total_ram_bytes,
+            tasks_fetched: zkvm.tasks_fetched,
+            tasks_submitted: zkvm.tasks_submitted,
+            success_rate: zkvm.success_rate(),
+            zkvm_runtime_secs: zkvm.zkvm_runtime_secs,
+            last_task_status: zkvm.last_task_status.clone(),
+            worker_restarts: zkvm.worker_restarts,
+            degraded: zkvm.degraded,
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        format!(
+            "{},{},{:.1},{},{},{},{},{},{:.1},{},\"{}\",{},{}",
+            self.timestamp,
+            self.uptime_secs,
+            self.cpu_percent,
+            self.ram_bytes,
+            self.peak_ram_bytes,
+            self.total_ram_bytes,
+            self.tasks_fetched,
+            self.tasks_submitted,
+            self.success_rate,
+            self.zkvm_runtime_secs,
+            self.last_task_status.replace('"', "\"\""),
+            self.worker_restarts,
+            self.degraded
+        )
+    }
+}
+
+/// Append-only export file
+struct MetricsFile {
+    file: File,
+    format: MetricsFormat,
+}
+
+impl MetricsFile {
+    /// Open `path` for appending, starting a new CSV file with its header.
+    fn open(path: &Path) -> io::Result<Self> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let format = MetricsFormat::from_path(path);
+        if format == MetricsFormat::Csv && file.metadata()?.len() == 0 {
+            writeln!(file, "{}", MetricsSnapshot::CSV_HEADER)?;
+        }
+        Ok(Self { file, format })
+    }
+
+    fn append(&mut self, snapshot: &MetricsSnapshot) -> io::Result<()> {
+        let line = match self.format {
+            MetricsFormat::Csv => snapshot.csv_row(),
+            MetricsFormat::Ndjson => serde_json::to_string(snapshot)?,
+        };
+        writeln!(self.file, "{}", line)?;
+        self.file.flush()
+    }
+}
+
+/// CPU and memory use of the prover, sampled for each snapshot
+struct SystemSampler {
+    started_at: Instant,
+    sysinfo: System,
+    system: SystemMetrics,
+}
+
+impl SystemSampler {
+    fn new() -> Self {
+        let mut sysinfo = System::new();
+        let system = SystemMetrics::update(&mut sysinfo, 0, None);
+        Self {
+            started_at: Instant::now(),
+            sysinfo,
+            system,
+        }
+    }
+
+    fn snapshot(&mut self, zkvm: &ZkVMMetrics) -> MetricsSnapshot {
+        self.system = SystemMetrics::update(
+            &mut self.sysinfo,
+            self.system.peak_ram_bytes,
+            Some(&self.system),
+        );
+        MetricsSnapshot::new(self.started_at.elapsed(), &self.system, zkvm)
+    }
+}
+
+/// Count events from `source` towards the exported metrics, forwarding them unchanged, and append
+/// a snapshot every interval. Fails if the export file cannot be opened.
+pub fn spawn_metrics_exporter(
+    config: MetricsExportConfig,
+    mut source: mpsc::Receiver<Event>,
+    mut shutdown: broadcast::Receiver<()>,
+) -> io::Result<mpsc::Receiver<Event>> {
+    let mut file = MetricsFile::open(&config.path)?;
+    let (sender, receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        let mut sampler = SystemSampler::new();
+        let mut zkvm = ZkVMMetrics::default();
+        let mut interval = tokio::time::interval_at(
+            tokio::time::Instant::now() + config.interval,
+            config.interval,
+        );
+        let mut shut_down = false;
+        loop {
+            tokio::select! {
+                event = source.recv() => {
+                    let Some(event) = event else { break };
+                    if let Some(payload) = &event.payload {
+                        zkvm.observe(payload);
+                    }
+                    if sender.send(event).await.is_err() {
+                        break;
+                    }
+                }
+                _ = interval.tick(), if !shut_down => {
+                    // Errors are ignored: printing here would corrupt the TUI
+                    let _ = file.append(&sampler.snapshot(&zkvm));
+                }
+                _ = shutdown.recv(), if !shut_down => {
+                    // Keep forwarding the last events of the session
+                    shut_down = true;
+                    let _ = file.append(&sampler.snapshot(&zkvm));
+                }
+            }
+        }
+    });
+    Ok(receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> MetricsSnapshot {
+        let mut zkvm = ZkVMMetrics::default();
+        zkvm.tasks_fetched = 4;
+        zkvm.tasks_submitted = 3;
+        MetricsSnapshot::new(Duration::from_secs(600), &SystemMetrics::default(), &zkvm)
+    }
+
+    #[test]
+    fn test_csv_export_has_one_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("metrics.CSV");
+        for _ in 0..2 {
+            MetricsFile::open(&path)
+                .unwrap()
+                .append(&snapshot())
+                .unwrap();
+        }
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], MetricsSnapshot::CSV_HEADER);
+        let columns = MetricsSnapshot::CSV_HEADER.split(',').count();
+        assert!(
+            lines[1..]
+                .iter()
+                .all(|row| row.split(',').count() == columns)
+        );
+        assert!(lines[1].contains(",600,"));
+        assert!(lines[1].contains(",75.0,"));
+    }
+
+    #[test]
+    fn test_ndjson_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("metrics.ndjson");
+        MetricsFile::open(&path)
+            .unwrap()
+            .append(&snapshot())
+            .unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let row: serde_json::Value = serde_json::from_str(contents.trim_end()).unwrap();
+        assert_eq!(row["tasks_submitted"], 3);
+        assert_eq!(row["last_task_status"], "None");
+    }
+}
@@ -1,31 +1,39 @@
 //! Network client with built-in retry and error handling
 
 use super::error_handler::ErrorHandler;
-use super::request_timer::RequestTimer;
+use super::request_timer::{NetworkMonitor, RequestOutcome, RequestTimer};
 use crate::consts::cli_consts;
 use crate::logging::LogLevel;
 use crate::orchestrator::Orchestrator;
 use crate::orchestrator::error::OrchestratorError;
+use bytes::Bytes;
 use ed25519_dalek::{SigningKey, VerifyingKey};
 
-use std::{cmp::min, time::Duration};
+use std::{
+    cmp::min,
+    time::{Duration, Instant},
+};
 
 /// Proof submission data grouped by business concern
+///
+/// Proofs are held as [`Bytes`], so cloning a submission for a retry shares them.
 #[derive(Debug, Clone)]
 pub struct ProofSubmission {
     pub task_id: String,
     pub proof_hash: String,
-    pub proof_bytes: Vec<u8>,
+    pub proof_bytes: Bytes,
     pub task_type: crate::nexus_orchestrator::TaskType,
     pub individual_proof_hashes: Vec<String>,
-    pub proofs_bytes: Vec<Vec<u8>>, // new: full proofs array
+    pub proofs_bytes: Vec<Bytes>, // new: full proofs array
+    /// Submission nonce, see [`crate::orchestrator::nonce`]; 0 if there is none
+    pub nonce: u64,
 }
 
 impl ProofSubmission {
     pub fn new(
         task_id: String,
         proof_hash: String,
-        proof_bytes: Vec<u8>,
+        proof_bytes: Bytes,
         task_type: crate::nexus_orchestrator::TaskType,
     ) -> Self {
         Self {
@@ -35,6 +43,7 @@ impl ProofSubmission {
             task_type,
             individual_proof_hashes: Vec::new(),
             proofs_bytes: Vec::new(),
+            nonce: 0,
         }
     }
 
@@ -43,10 +52,15 @@ impl ProofSubmission {
         self
     }
 
-    pub fn with_proofs(mut self, proofs: Vec<Vec<u8>>) -> Self {
+    pub fn with_proofs(mut self, proofs: Vec<Bytes>) -> Self {
         self.proofs_bytes = proofs;
         self
     }
+
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
 }
 
 /// Network client with built-in retry and request timing
@@ -54,6 +68,7 @@ pub struct NetworkClient {
     error_handler: ErrorHandler,
     request_timer: RequestTimer,
     max_retries: u32,
+    monitor: NetworkMonitor,
 }
 
 impl NetworkClient {
@@ -62,6 +77,7 @@ impl NetworkClient {
             error_handler: ErrorHandler::new(),
             request_timer,
             max_retries,
+            monitor: NetworkMonitor::global().clone(),
         }
     }
 
@@ -78,10 +94,12 @@ impl NetworkClient {
         loop {
             // Make the request
             // Default to Large; callers can adapt or override upstream
-            match orchestrator
+            let started = Instant::now();
+            let result = orchestrator
                 .get_proof_task(node_id, verifying_key, max_difficulty)
-                .await
-            {
+                .await;
+            self.record_outcome(started, &result);
+            match result {
                 Ok(proof_task_result) => {
                     self.request_timer.record_success();
                     return Ok(proof_task_result);
@@ -89,17 +107,7 @@ impl NetworkClient {
                 Err(e) => {
                     attempts += 1;
 
-                    // Get server-provided retry delay and record failure
-                    let server_retry_delay = e
-                        .get_retry_after_seconds()
-                        .map(|secs| Duration::from_secs(secs as u64))
-                        .map(|delay| {
-                            min(
-                                delay + cli_consts::rate_limiting::extra_retry_delay(),
-                                Duration::from_secs(60 * 10),
-                            )
-                        });
-                    self.request_timer.record_failure(server_retry_delay);
+                    self.record_failure(&e);
 
                     // Check if we should retry
                     if attempts >= self.max_retries || !self.error_handler.should_retry(&e) {
@@ -110,12 +118,119 @@ impl NetworkClient {
         }
     }
 
+    /// Preview the next task with automatic retry and server-controlled timing
+    pub async fn preview_task(
+        &mut self,
+        orchestrator: &dyn Orchestrator,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Result<crate::task::TaskDescriptor, OrchestratorError> {
+        let mut attempts = 0;
+
+        loop {
+            let started = Instant::now();
+            let result = orchestrator
+                .preview_proof_task(node_id, verifying_key, max_difficulty)
+                .await;
+            self.record_outcome(started, &result);
+            match result {
+                Ok(offer) => {
+                    self.request_timer.record_success();
+                    return Ok(offer);
+                }
+                Err(e) => {
+                    attempts += 1;
+
+                    self.record_failure(&e);
+
+                    // Servers without previews will not grow them on a retry
+                    if attempts >= self.max_retries
+                        || e.is_unsupported()
+                        || !self.error_handler.should_retry(&e)
+                    {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Take the task of a previewed offer with automatic retry and server-controlled timing
+    pub async fn take_task(
+        &mut self,
+        orchestrator: &dyn Orchestrator,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        offer_id: &str,
+    ) -> Result<crate::orchestrator::client::ProofTaskResult, OrchestratorError> {
+        let mut attempts = 0;
+
+        loop {
+            let started = Instant::now();
+            let result = orchestrator
+                .take_proof_task(node_id, verifying_key, max_difficulty, offer_id)
+                .await;
+            self.record_outcome(started, &result);
+            match result {
+                Ok(proof_task_result) => {
+                    self.request_timer.record_success();
+                    return Ok(proof_task_result);
+                }
+                Err(e) => {
+                    attempts += 1;
+
+                    self.record_failure(&e);
+
+                    if attempts >= self.max_retries || !self.error_handler.should_retry(&e) {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Decline a previewed offer with automatic retry and server-controlled timing
+    pub async fn decline_task(
+        &mut self,
+        orchestrator: &dyn Orchestrator,
+        node_id: &str,
+        offer_id: &str,
+        reason: &str,
+    ) -> Result<(), OrchestratorError> {
+        let mut attempts = 0;
+
+        loop {
+            let started = Instant::now();
+            let result = orchestrator
+                .decline_proof_task(node_id, offer_id, reason)
+                .await;
+            self.record_outcome(started, &result);
+            match result {
+                Ok(()) => {
+                    self.request_timer.record_success();
+                    return Ok(());
+                }
+                Err(e) => {
+                    attempts += 1;
+
+                    self.record_failure(&e);
+
+                    if attempts >= self.max_retries || !self.error_handler.should_retry(&e) {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+    }
+
     /// Submit a proof with automatic retry and server-controlled timing
     /// Returns Ok(attempts) on success or Err((error, attempts)) on failure
     pub async fn submit_proof(
         &mut self,
         orchestrator: &dyn Orchestrator,
-        submission: ProofSubmission,
+        mut submission: ProofSubmission,
         signing_key: SigningKey,
         num_provers: usize,
     ) -> Result<u32, (OrchestratorError, u32)> {
@@ -123,7 +238,8 @@ impl NetworkClient {
 
         loop {
             // Make the request
-            match orchestrator
+            let started = Instant::now();
+            let result = orchestrator
                 .submit_proof(
                     &submission.task_id,
                     &submission.proof_hash,
@@ -133,9 +249,16 @@ impl NetworkClient {
                     num_provers,
                     submission.task_type,
                     &submission.individual_proof_hashes,
+                    submission.nonce,
                 )
-                .await
-            {
+                .await;
+            self.record_outcome(started, &result);
+            if submission.nonce != 0 && crate::orchestrator::nonce::observe_nonce_outcome(&result) {
+                // The orchestrator predates nonces and cannot verify the signature
+                submission.nonce = 0;
+                continue;
+            }
+            match result {
                 Ok(()) => {
                     attempts += 1;
                     self.request_timer.record_success();
@@ -144,17 +267,14 @@ impl NetworkClient {
                 Err(e) => {
                     attempts += 1;
 
-                    // Get server-provided retry delay and record failure
-                    let server_retry_delay = e
-                        .get_retry_after_seconds()
-                        .map(|secs| Duration::from_secs(secs as u64))
-                        .map(|delay| {
-                            min(
-                                delay + cli_consts::rate_limiting::extra_retry_delay(),
-                                Duration::from_secs(60 * 10),
-                            )
-                        });
-                    self.request_timer.record_failure(server_retry_delay);
+                    // An earlier attempt got through even though its response was lost. On the
+                    // first attempt, the proof was submitted by someone else, e.g. another session
+                    if e.is_already_accepted() && attempts > 1 {
+                        self.request_timer.record_success();
+                        return Ok(attempts);
+                    }
+
+                    self.record_failure(&e);
 
                     // Check if we should retry
                     if attempts >= self.max_retries || !self.error_handler.should_retry(&e) {
@@ -165,6 +285,69 @@ impl NetworkClient {
         }
     }
 
+    /// Submit several proofs in one request with automatic retry and server-controlled timing
+    /// Returns Ok((results, attempts)) on success or Err((error, attempts)) on failure
+    pub async fn submit_proofs_batch(
+        &mut self,
+        orchestrator: &dyn Orchestrator,
+        submissions: &[ProofSubmission],
+        signing_key: SigningKey,
+        num_provers: usize,
+    ) -> Result<(Vec<crate::nexus_orchestrator::SubmitProofResult>, u32), (OrchestratorError, u32)>
+    {
+        let mut attempts = 0;
+
+        loop {
+            let started = Instant::now();
+            let result = orchestrator
+                .submit_proofs_batch(submissions, signing_key.clone(), num_provers)
+                .await;
+            self.record_outcome(started, &result);
+            match result {
+                Ok(results) => {
+                    attempts += 1;
+                    self.request_timer.record_success();
+                    return Ok((results, attempts));
+                }
+                Err(e) => {
+                    attempts += 1;
+                    self.record_failure(&e);
+
+                    // Check if we should retry
+                    if attempts >= self.max_retries
+                        || e.is_unsupported()
+                        || !self.error_handler.should_retry(&e)
+                    {
+                        return Err((e, attempts));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Report a request that started at `started` to the network monitor
+    fn record_outcome<T>(&self, started: Instant, result: &Result<T, OrchestratorError>) {
+        let outcome = match result {
+            Ok(_) => RequestOutcome::Success,
+            Err(e) => RequestOutcome::from_error(e),
+        };
+        self.monitor.record(started.elapsed(), outcome);
+    }
+
+    /// Get server-provided retry delay and record failure
+    fn record_failure(&mut self, error: &OrchestratorError) {
+        let server_retry_delay = error
+            .get_retry_after_seconds()
+            .map(|secs| Duration::from_secs(secs as u64))
+            .map(|delay| {
+                min(
+                    delay + cli_consts::rate_limiting::extra_retry_delay(),
+                    Duration::from_secs(60 * 10),
+                )
+            });
+        self.request_timer.record_failure(server_retry_delay);
+    }
+
     /// Get error classification for logging
     pub fn classify_error(&self, error: &OrchestratorError) -> LogLevel {
         self.error_handler.classify_error(error)
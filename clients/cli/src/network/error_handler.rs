@@ -18,6 +18,9 @@ impl ErrorHandler {
             // Rate limiting - low priority
             OrchestratorError::Http { status, .. } if *status == 429 => LogLevel::Debug,
 
+            // Planned maintenance - announced once by the fetcher, not per request
+            error if error.maintenance().is_some() => LogLevel::Debug,
+
             // Server errors - temporary issues
             OrchestratorError::Http { status, .. } if (500..=599).contains(status) => {
                 LogLevel::Warn
@@ -29,6 +32,7 @@ impl ErrorHandler {
 
             // Network issues - usually temporary
             OrchestratorError::Reqwest(_) => LogLevel::Warn,
+            OrchestratorError::Transport(_) => LogLevel::Warn,
 
             // Other errors
             _ => LogLevel::Warn,
@@ -38,9 +42,17 @@ impl ErrorHandler {
     /// Determine if an error should trigger retry logic
     pub fn should_retry(&self, error: &OrchestratorError) -> bool {
         match error {
+            // Wait out planned maintenance instead of retrying into it
+            error if error.maintenance().is_some() => false,
+
             // Retry on network/connection errors
             OrchestratorError::Reqwest(_) => true,
+            OrchestratorError::Transport(_) => true,
             OrchestratorError::Decode(_) => true,
+            OrchestratorError::MalformedResponse(_) => true,
+
+            // Sending the same proof again gets the same answer
+            OrchestratorError::AlreadyAccepted => false,
 
             // HTTP errors - check status code
             OrchestratorError::Http { status, .. } => {
@@ -3,4 +3,6 @@ pub mod error_handler;
 pub mod request_timer;
 
 pub use client::{NetworkClient, ProofSubmission};
-pub use request_timer::{RequestTimer, RequestTimerConfig};
+pub use request_timer::{
+    NetworkHealth, NetworkMonitor, NetworkStats, RequestOutcome, RequestTimer, RequestTimerConfig,
+};
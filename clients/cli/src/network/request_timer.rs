@@ -2,7 +2,17 @@
 //!
 //! This module replaces the separate backoff and rate limiter components with a
 //! unified approach that prioritizes server-provided retry delays over local timing strategies.
-
+//! It also keeps the recent outcomes and round-trip times of orchestrator requests in a
+//! [`NetworkMonitor`], so users can tell problems with their own connection apart from problems
+//! on the server.
+
+use crate::consts::cli_consts::rate_limiting::{
+    NETWORK_MONITOR_MAX_SAMPLES, network_monitor_window,
+};
+use crate::orchestrator::error::OrchestratorError;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 /// Configuration for request timing behavior
@@ -75,10 +85,25 @@ impl RequestTimer {
         }
     }
 
+    /// Timing configuration of this timer
+    pub fn config(&self) -> &RequestTimerConfig {
+        &self.config
+    }
+
+    /// Number of requests made within the current time window
+    pub fn requests_in_window(&mut self) -> u32 {
+        let now = crate::chaos::now();
+        if let Some(time_window) = self.config.time_window {
+            self.request_times
+                .retain(|&time| now.duration_since(time) <= time_window);
+        }
+        self.request_times.len() as u32
+    }
+
     /// Check if a new request can proceed
     /// Server retry delay takes priority over all other constraints
     pub fn can_proceed(&mut self) -> bool {
-        let now = Instant::now();
+        let now = crate::chaos::now();
 
         // Server retry delay always takes priority
         if let Some(retry_until) = self.server_retry_until {
@@ -114,7 +139,7 @@ impl RequestTimer {
 
     /// Record a successful request
     pub fn record_success(&mut self) {
-        let now = Instant::now();
+        let now = crate::chaos::now();
         self.last_request_time = Some(now);
         if self.config.max_requests.is_some() {
             self.request_times.push(now);
@@ -130,7 +155,7 @@ impl RequestTimer {
     /// Record a failed request with optional server-provided retry delay
     /// If server_retry_delay is provided, it overrides all other timing logic
     pub fn record_failure(&mut self, server_retry_delay: Option<Duration>) {
-        let now = Instant::now();
+        let now = crate::chaos::now();
         self.last_request_time = Some(now);
 
         if self.config.max_requests.is_some() {
@@ -146,10 +171,21 @@ impl RequestTimer {
         }
     }
 
+    /// Hold off the next request for at least `delay`, keeping any longer wait already set
+    pub fn hold_off(&mut self, delay: Duration) {
+        let until = crate::chaos::now() + delay;
+        if self
+            .server_retry_until
+            .is_none_or(|retry_until| retry_until < until)
+        {
+            self.server_retry_until = Some(until);
+        }
+    }
+
     /// Get time until next request is allowed
     /// Server retry delay takes priority over all other constraints
     pub fn time_until_next(&mut self) -> Duration {
-        let now = Instant::now();
+        let now = crate::chaos::now();
 
         // Server retry delay has highest priority
         if let Some(retry_until) = self.server_retry_until {
@@ -191,6 +227,174 @@ impl RequestTimer {
     }
 }
 
+/// How an orchestrator request ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    Success,
+    /// The server rejected the request (4xx), e.g. rate limiting
+    ClientError(u16),
+    /// The server failed to handle the request (5xx), or its response was unusable
+    ServerError,
+    /// No response: connection, DNS, TLS or timeout failure
+    NetworkError,
+}
+
+impl RequestOutcome {
+    pub fn from_error(error: &OrchestratorError) -> Self {
+        match error {
+            OrchestratorError::Http { status, .. } if (400..500).contains(status) => {
+                Self::ClientError(*status)
+            }
+            OrchestratorError::AlreadyAccepted => Self::ClientError(409),
+            OrchestratorError::Http { .. }
+            | OrchestratorError::Decode(_)
+            | OrchestratorError::MalformedResponse(_) => Self::ServerError,
+            OrchestratorError::Reqwest(_) | OrchestratorError::Transport(_) => Self::NetworkError,
+        }
+    }
+}
+
+/// Network quality over the monitor window, shown in the dashboard and the control API
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkStats {
+    /// Requests made within the window
+    pub requests: usize,
+    /// Average round-trip time of requests that got a response, in milliseconds
+    pub rtt_avg_ms: Option<u64>,
+    /// Round-trip time of the latest request that got a response, in milliseconds
+    pub rtt_last_ms: Option<u64>,
+    /// Share of requests that failed, from 0.0 to 1.0
+    pub error_rate: f64,
+    /// Requests rejected by the server (4xx)
+    pub client_errors: usize,
+    /// Requests the server failed (5xx)
+    pub server_errors: usize,
+    /// Requests that got no response
+    pub network_errors: usize,
+    /// Length of the window, in seconds
+    pub window_secs: u64,
+}
+
+/// Most likely source of trouble, judged from [`NetworkStats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkHealth {
+    /// No requests in the window yet
+    Unknown,
+    Good,
+    /// Requests often get no response: likely the local network or connection
+    LocalNetwork,
+    /// The server often fails requests: likely a problem on the orchestrator's side
+    Server,
+    /// Requests succeed, but slowly
+    Slow,
+}
+
+/// Share of failed requests above which the network or server is considered unhealthy
+const UNHEALTHY_ERROR_RATE: f64 = 0.2;
+
+/// Average round-trip time above which the connection is considered slow
+const SLOW_RTT_MS: u64 = 2_000;
+
+impl NetworkStats {
+    pub fn health(&self) -> NetworkHealth {
+        if self.requests == 0 {
+            return NetworkHealth::Unknown;
+        }
+        let share = |count: usize| count as f64 / self.requests as f64;
+        if share(self.network_errors) >= UNHEALTHY_ERROR_RATE {
+            NetworkHealth::LocalNetwork
+        } else if share(self.server_errors) >= UNHEALTHY_ERROR_RATE {
+            NetworkHealth::Server
+        } else if self.rtt_avg_ms.is_some_and(|rtt| rtt >= SLOW_RTT_MS) {
+            NetworkHealth::Slow
+        } else {
+            NetworkHealth::Good
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RequestSample {
+    at: Instant,
+    /// Round-trip time, if a response arrived
+    rtt: Option<Duration>,
+    outcome: RequestOutcome,
+}
+
+/// Recent orchestrator requests, shared by the task fetcher and the proof submitter.
+///
+/// Clones share the same samples.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkMonitor {
+    samples: Arc<Mutex<VecDeque<RequestSample>>>,
+}
+
+impl NetworkMonitor {
+    /// Monitor of this process's orchestrator requests
+    pub fn global() -> &'static NetworkMonitor {
+        static MONITOR: OnceLock<NetworkMonitor> = OnceLock::new();
+        MONITOR.get_or_init(NetworkMonitor::default)
+    }
+
+    /// Record a request that took `elapsed` and ended with `outcome`.
+    pub fn record(&self, elapsed: Duration, outcome: RequestOutcome) {
+        let Ok(mut samples) = self.samples.lock() else {
+            return;
+        };
+        let now = Instant::now();
+        samples.push_back(RequestSample {
+            at: now,
+            rtt: (outcome != RequestOutcome::NetworkError).then_some(elapsed),
+            outcome,
+        });
+        while samples.len() > NETWORK_MONITOR_MAX_SAMPLES
+            || samples.front().is_some_and(|sample| {
+                now.saturating_duration_since(sample.at) > network_monitor_window()
+            })
+        {
+            samples.pop_front();
+        }
+    }
+
+    /// Network quality over the requests made within the window before `now`
+    pub fn stats(&self, now: Instant) -> NetworkStats {
+        let window = network_monitor_window();
+        let mut stats = NetworkStats {
+            window_secs: window.as_secs(),
+            ..NetworkStats::default()
+        };
+        let Ok(samples) = self.samples.lock() else {
+            return stats;
+        };
+        let recent: Vec<&RequestSample> = samples
+            .iter()
+            .filter(|sample| now.saturating_duration_since(sample.at) <= window)
+            .collect();
+        let rtts: Vec<u64> = recent
+            .iter()
+            .filter_map(|sample| sample.rtt)
+            .map(|rtt| rtt.as_millis() as u64)
+            .collect();
+
+        stats.requests = recent.len();
+        stats.rtt_last_ms = rtts.last().copied();
+        stats.rtt_avg_ms = (!rtts.is_empty()).then(|| rtts.iter().sum::<u64>() / rtts.len() as u64);
+        for sample in &recent {
+            match sample.outcome {
+                RequestOutcome::Success => {}
+                RequestOutcome::ClientError(_) => stats.client_errors += 1,
+                RequestOutcome::ServerError => stats.server_errors += 1,
+                RequestOutcome::NetworkError => stats.network_errors += 1,
+            }
+        }
+        let errors = stats.client_errors + stats.server_errors + stats.network_errors;
+        if stats.requests > 0 {
+            stats.error_rate = errors as f64 / stats.requests as f64;
+        }
+        stats
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,4 +466,81 @@ mod tests {
         // Immediate second request should be blocked by min interval
         assert!(!timer.can_proceed());
     }
+
+    #[test]
+    fn test_requests_in_window() {
+        let config = RequestTimerConfig::_requests_per_window(5, Duration::from_secs(60));
+        let mut timer = RequestTimer::new(config);
+        assert_eq!(timer.requests_in_window(), 0);
+
+        timer.record_success();
+        timer.record_failure(Some(Duration::from_secs(1)));
+        assert_eq!(timer.requests_in_window(), 2);
+        assert_eq!(timer.config().max_requests, Some(5));
+    }
+
+    #[test]
+    fn test_network_stats() {
+        let monitor = NetworkMonitor::default();
+        assert_eq!(
+            monitor.stats(Instant::now()).health(),
+            NetworkHealth::Unknown
+        );
+
+        monitor.record(Duration::from_millis(100), RequestOutcome::Success);
+        monitor.record(Duration::from_millis(300), RequestOutcome::ClientError(429));
+        monitor.record(Duration::from_secs(30), RequestOutcome::NetworkError);
+        monitor.record(Duration::from_millis(200), RequestOutcome::ServerError);
+
+        let stats = monitor.stats(Instant::now());
+        assert_eq!(stats.requests, 4);
+        // Requests without a response don't count towards the round-trip time
+        assert_eq!(stats.rtt_avg_ms, Some(200));
+        assert_eq!(stats.rtt_last_ms, Some(200));
+        assert_eq!(stats.error_rate, 0.75);
+        assert_eq!(
+            (
+                stats.client_errors,
+                stats.server_errors,
+                stats.network_errors
+            ),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_network_health() {
+        let stats = |network_errors, server_errors, rtt_avg_ms| NetworkStats {
+            requests: 10,
+            network_errors,
+            server_errors,
+            rtt_avg_ms: Some(rtt_avg_ms),
+            ..NetworkStats::default()
+        };
+        assert_eq!(stats(0, 1, 150).health(), NetworkHealth::Good);
+        assert_eq!(stats(3, 3, 150).health(), NetworkHealth::LocalNetwork);
+        assert_eq!(stats(1, 3, 150).health(), NetworkHealth::Server);
+        assert_eq!(stats(0, 0, 2_500).health(), NetworkHealth::Slow);
+    }
+
+    #[test]
+    fn test_outcome_from_error() {
+        let http = |status| OrchestratorError::Http {
+            status,
+            message: String::new(),
+            headers: Default::default(),
+        };
+        assert_eq!(
+            RequestOutcome::from_error(&http(429)),
+            RequestOutcome::ClientError(429)
+        );
+        assert_eq!(
+            RequestOutcome::from_error(&http(503)),
+            RequestOutcome::ServerError
+        );
+        assert_eq!(
+            RequestOutcome::from_error(&OrchestratorError::Transport("refused".to_string())),
+            RequestOutcome::NetworkError
+        );
+    }
 }
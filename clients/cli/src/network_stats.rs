@@ -0,0 +1,334 @@
+//! Network statistics
+//!
+//! `nexus-cli network stats` shows how many provers are active across the network, where a node
+//! ranks among them by points, and how many tasks of each difficulty are waiting to be proved.
+//! The leaderboard panel of the dashboard shows the same, refreshed in the background while the
+//! node proves. Orchestrators that do not report network statistics are asked once; the panel then
+//! shows them as unavailable.
+
+use crate::config::{Config, ConfigError};
+use crate::exit_code::UsageError;
+use crate::nexus_orchestrator::{GetNetworkStatsResponse, TaskDifficulty};
+use crate::orchestrator::Orchestrator;
+use crate::points::format_points;
+use serde::Serialize;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Tasks waiting to be proved at one difficulty
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TaskSupply {
+    pub difficulty: &'static str,
+    pub pending_tasks: u64,
+}
+
+/// Network-wide prover statistics and the standing of one node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NetworkStats {
+    pub node_id: u64,
+    /// Provers that submitted a proof in the last 24 hours
+    pub active_provers: u64,
+    /// Rank of the node by points, 1 for the most points; `None` if it is not ranked yet
+    pub rank: Option<u64>,
+    /// Number of ranked nodes
+    pub ranked_nodes: u64,
+    /// Pending tasks by difficulty, easiest first
+    pub task_supply: Vec<TaskSupply>,
+}
+
+impl NetworkStats {
+    pub fn from_response(node_id: u64, response: &GetNetworkStatsResponse) -> Self {
+        let mut supply: Vec<(TaskDifficulty, u64)> = response
+            .task_supply
+            .iter()
+            .filter_map(|supply| {
+                TaskDifficulty::try_from(supply.difficulty)
+                    .ok()
+                    .map(|difficulty| (difficulty, supply.pending_tasks))
+            })
+            .collect();
+        supply.sort_by_key(|(difficulty, _)| *difficulty as i32);
+        Self {
+            node_id,
+            active_provers: response.active_provers,
+            rank: (response.node_rank > 0).then_some(response.node_rank),
+            ranked_nodes: response.ranked_nodes,
+            task_supply: supply
+                .into_iter()
+                .map(|(difficulty, pending_tasks)| TaskSupply {
+                    difficulty: difficulty.as_str_name(),
+                    pending_tasks,
+                })
+                .collect(),
+        }
+    }
+
+    /// Share of ranked nodes at or above the node's rank, in percent, e.g. `2.5` for the top 2.5%.
+    pub fn top_percent(&self) -> Option<f64> {
+        match self.rank {
+            Some(rank) if self.ranked_nodes > 0 => {
+                Some(rank.min(self.ranked_nodes) as f64 / self.ranked_nodes as f64 * 100.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Tasks waiting to be proved, over all difficulties.
+    pub fn pending_tasks(&self) -> u64 {
+        self.task_supply
+            .iter()
+            .map(|supply| supply.pending_tasks)
+            .sum()
+    }
+
+    /// Share of the pending tasks at each difficulty, in percent.
+    pub fn supply_mix(&self) -> Vec<(&'static str, f64)> {
+        let total = self.pending_tasks();
+        self.task_supply
+            .iter()
+            .map(|supply| {
+                let share = if total == 0 {
+                    0.0
+                } else {
+                    supply.pending_tasks as f64 / total as f64 * 100.0
+                };
+                (supply.difficulty, share)
+            })
+            .collect()
+    }
+
+    /// The node's rank, e.g. `#1,234 of 56,789 (top 2.17%)`, or `not ranked yet`.
+    pub fn rank_text(&self) -> String {
+        match (self.rank, self.top_percent()) {
+            (Some(rank), Some(top)) => format!(
+                "#{} of {} (top {:.2}%)",
+                format_points(rank),
+                format_points(self.ranked_nodes),
+                top
+            ),
+            _ => "not ranked yet".to_string(),
+        }
+    }
+}
+
+impl Display for NetworkStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Network stats for node {}", self.node_id)?;
+        writeln!(
+            f,
+            "  Active provers:    {}",
+            format_points(self.active_provers)
+        )?;
+        writeln!(f, "  Rank:              {}", self.rank_text())?;
+        writeln!(
+            f,
+            "  Pending tasks:     {}",
+            format_points(self.pending_tasks())
+        )?;
+        for (supply, (_, share)) in self.task_supply.iter().zip(self.supply_mix()) {
+            writeln!(
+                f,
+                "    {:<16} {} ({:.0}%)",
+                supply.difficulty,
+                format_points(supply.pending_tasks),
+                share
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Shared, thread-safe record of the latest network statistics
+#[derive(Debug, Clone, Default)]
+pub struct NetworkStatsTracker {
+    inner: Arc<Mutex<Option<NetworkStats>>>,
+    unavailable: Arc<AtomicBool>,
+}
+
+impl NetworkStatsTracker {
+    pub fn record(&self, stats: NetworkStats) {
+        if let Ok(mut inner) = self.inner.lock() {
+            *inner = Some(stats);
+        }
+    }
+
+    /// The statistics of the most recent successful poll, if any.
+    pub fn latest(&self) -> Option<NetworkStats> {
+        self.inner.lock().ok().and_then(|inner| inner.clone())
+    }
+
+    /// Record that the orchestrator does not report network statistics.
+    pub fn mark_unavailable(&self) {
+        self.unavailable.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the orchestrator does not report network statistics.
+    pub fn unavailable(&self) -> bool {
+        self.unavailable.load(Ordering::Relaxed)
+    }
+}
+
+/// Poll the orchestrator for network statistics every `poll_interval` until shutdown, or until it
+/// turns out not to report them. Failed polls are skipped silently; the dashboard keeps showing
+/// the last known values.
+pub fn spawn_network_stats_poller(
+    orchestrator: Arc<dyn Orchestrator>,
+    node_id: u64,
+    tracker: NetworkStatsTracker,
+    poll_interval: Duration,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = interval.tick() => {
+                    poll_network_stats(orchestrator.as_ref(), node_id, &tracker).await;
+                    if tracker.unavailable() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Fetch network statistics once and record them; failures are ignored. Does nothing once the
+/// orchestrator turned out not to report network statistics.
+async fn poll_network_stats(
+    orchestrator: &dyn Orchestrator,
+    node_id: u64,
+    tracker: &NetworkStatsTracker,
+) {
+    if tracker.unavailable() {
+        return;
+    }
+    match orchestrator.get_network_stats(&node_id.to_string()).await {
+        Ok(response) => tracker.record(NetworkStats::from_response(node_id, &response)),
+        Err(e) if e.is_unsupported() => tracker.mark_unavailable(),
+        Err(_) => {}
+    }
+}
+
+/// Print the network statistics for `nexus-cli network stats`, for `node_id` or the node in the
+/// config file.
+pub async fn show_network_stats(
+    config_path: &Path,
+    node_id: Option<u64>,
+    orchestrator: &dyn Orchestrator,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let node_id = match node_id {
+        Some(node_id) => node_id,
+        None => {
+            let config = Config::load_from_file(config_path).ok();
+            match config.filter(|config| !config.node_id.is_empty()) {
+                Some(config) => config
+                    .node_id
+                    .parse::<u64>()
+                    .map_err(|_| ConfigError::InvalidNodeId)?,
+                None => {
+                    return Err(UsageError(
+                        "No node in the config file: pass --node-id".to_string(),
+                    )
+                    .into());
+                }
+            }
+        }
+    };
+    let response = orchestrator.get_network_stats(&node_id.to_string()).await?;
+    let stats = NetworkStats::from_response(node_id, &response);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{}", stats);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::DifficultySupply;
+    use crate::orchestrator::MockOrchestrator;
+    use crate::orchestrator::error::OrchestratorError;
+
+    fn response() -> GetNetworkStatsResponse {
+        GetNetworkStatsResponse {
+            active_provers: 56_789,
+            node_rank: 1_234,
+            ranked_nodes: 50_000,
+            task_supply: vec![
+                DifficultySupply {
+                    difficulty: TaskDifficulty::Large as i32,
+                    pending_tasks: 100,
+                },
+                DifficultySupply {
+                    difficulty: TaskDifficulty::Small as i32,
+                    pending_tasks: 300,
+                },
+                // Difficulties this version does not know are left out
+                DifficultySupply {
+                    difficulty: 99,
+                    pending_tasks: 1_000,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_network_stats() {
+        let stats = NetworkStats::from_response(42, &response());
+        assert_eq!(stats.rank, Some(1_234));
+        assert!((stats.top_percent().unwrap() - 2.468).abs() < 1e-9);
+        assert_eq!(stats.pending_tasks(), 400);
+        assert_eq!(stats.supply_mix(), vec![("SMALL", 75.0), ("LARGE", 25.0)]);
+        assert_eq!(
+            stats.to_string(),
+            "Network stats for node 42\n\
+             \x20 Active provers:    56,789\n\
+             \x20 Rank:              #1,234 of 50,000 (top 2.47%)\n\
+             \x20 Pending tasks:     400\n\
+             \x20   SMALL            300 (75%)\n\
+             \x20   LARGE            100 (25%)\n"
+        );
+
+        let unranked = NetworkStats::from_response(
+            42,
+            &GetNetworkStatsResponse {
+                node_rank: 0,
+                ..response()
+            },
+        );
+        assert_eq!(unranked.top_percent(), None);
+        assert_eq!(unranked.rank_text(), "not ranked yet");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_network_stats_are_not_polled_again() {
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_get_network_stats()
+            .times(1)
+            .returning(|_| {
+                Err(OrchestratorError::Http {
+                    status: 404,
+                    message: String::new(),
+                    headers: Default::default(),
+                })
+            });
+        let tracker = NetworkStatsTracker::default();
+
+        poll_network_stats(&orchestrator, 1, &tracker).await;
+        poll_network_stats(&orchestrator, 1, &tracker).await;
+
+        assert!(tracker.unavailable());
+        assert_eq!(tracker.latest(), None);
+    }
+}
@@ -0,0 +1,322 @@
+//! Nodes manifest
+//!
+//! Fleet operators register many nodes under one user with `register-node --count N`. Every
+//! node registered that way is recorded in `nodes.json` next to the config file, so the node IDs
+//! can be handed to other machines or to a multi-node start.
+//!
+//! `nodes list` shows the orchestrator's view of all nodes of the wallet, and which of them are
+//! known on this machine, to reconcile the two.
+
+use crate::config::Config;
+use crate::exit_code::UsageError;
+use crate::nexus_orchestrator::NodeType;
+use crate::orchestrator::Orchestrator;
+use crate::orchestrator::error::OrchestratorError;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const NODES_MANIFEST_FILE: &str = "nodes.json";
+
+/// Path of the nodes manifest, `~/.nexus/nodes.json` next to the config file.
+pub fn nodes_manifest_path(config_path: &Path) -> PathBuf {
+    config_path.with_file_name(NODES_MANIFEST_FILE)
+}
+
+/// A node recorded in the manifest
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct NodeEntry {
+    pub node_id: String,
+    /// User the node is registered to
+    pub user_id: String,
+    /// Environment the node was registered in
+    pub environment: String,
+}
+
+/// Nodes registered from this machine
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct NodeManifest {
+    #[serde(default)]
+    pub nodes: Vec<NodeEntry>,
+}
+
+impl NodeManifest {
+    /// Load the manifest at `path`, or an empty one if the file does not exist yet.
+    pub fn load_or_default(path: &Path) -> Result<Self, std::io::Error> {
+        match fs::read(path) {
+            Ok(buf) => serde_json::from_slice(&buf)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Save the manifest to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    /// Add a node, replacing an existing entry with the same node ID.
+    pub fn add(&mut self, entry: NodeEntry) {
+        match self.nodes.iter_mut().find(|n| n.node_id == entry.node_id) {
+            Some(existing) => *existing = entry,
+            None => self.nodes.push(entry),
+        }
+    }
+
+    /// Remove a node, returning whether it was in the manifest.
+    pub fn remove(&mut self, node_id: &str) -> bool {
+        let before = self.nodes.len();
+        self.nodes.retain(|n| n.node_id != node_id);
+        self.nodes.len() != before
+    }
+
+    /// IDs of all nodes in the manifest, in registration order.
+    pub fn node_ids(&self) -> Vec<&str> {
+        self.nodes.iter().map(|n| n.node_id.as_str()).collect()
+    }
+}
+
+/// A node of the wallet, as the orchestrator sees it
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct NodeStatus {
+    pub node_id: String,
+    /// `cli` or `web`
+    pub node_type: &'static str,
+    /// When the node was last active, in RFC 3339; `None` if never
+    pub last_seen: Option<String>,
+    pub points: u64,
+    /// Whether the node is in the config file or the nodes manifest of this machine
+    pub local: bool,
+}
+
+/// All nodes of a wallet, for `nodes list`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct NodeListing {
+    pub user_id: String,
+    pub wallet_address: String,
+    pub nodes: Vec<NodeStatus>,
+    /// Nodes of this machine that the orchestrator does not list for the wallet
+    pub unknown_local: Vec<String>,
+}
+
+/// IDs of the nodes in the config file and the nodes manifest next to it.
+pub fn local_node_ids(config_path: &Path) -> BTreeSet<String> {
+    let mut node_ids: BTreeSet<String> =
+        NodeManifest::load_or_default(&nodes_manifest_path(config_path))
+            .map(|manifest| manifest.nodes.into_iter().map(|n| n.node_id).collect())
+            .unwrap_or_default();
+    if let Ok(config) = Config::load_from_file(config_path) {
+        if !config.node_id.is_empty() {
+            node_ids.insert(config.node_id);
+        }
+    }
+    node_ids
+}
+
+fn format_last_seen(last_seen: Option<prost_types::Timestamp>) -> Option<String> {
+    let last_seen = last_seen?;
+    DateTime::<Utc>::from_timestamp(last_seen.seconds, last_seen.nanos.max(0) as u32)
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// Fetch every page of the nodes of `wallet_address` and compare them with `local` node IDs.
+pub async fn fetch_node_listing(
+    orchestrator: &dyn Orchestrator,
+    wallet_address: &str,
+    local: &BTreeSet<String>,
+) -> Result<NodeListing, OrchestratorError> {
+    let mut cursor = String::new();
+    let mut user_id;
+    let mut nodes = Vec::new();
+    loop {
+        let page = orchestrator.get_user_nodes(wallet_address, &cursor).await?;
+        user_id = page.user_id;
+        nodes.extend(page.nodes.into_iter().map(|node| NodeStatus {
+            node_type: match node.node_type() {
+                NodeType::CliProver => "cli",
+                NodeType::WebProver => "web",
+            },
+            last_seen: format_last_seen(node.last_seen),
+            points: node.points,
+            local: local.contains(&node.node_id),
+            node_id: node.node_id,
+        }));
+        // Also stop if a server hands out the same cursor again
+        if page.nodes_next_cursor.is_empty() || page.nodes_next_cursor == cursor {
+            break;
+        }
+        cursor = page.nodes_next_cursor;
+    }
+    let listed: BTreeSet<&str> = nodes.iter().map(|n| n.node_id.as_str()).collect();
+    let unknown_local = local
+        .iter()
+        .filter(|node_id| !listed.contains(node_id.as_str()))
+        .cloned()
+        .collect();
+    Ok(NodeListing {
+        user_id,
+        wallet_address: wallet_address.to_string(),
+        nodes,
+        unknown_local,
+    })
+}
+
+impl Display for NodeListing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} nodes of wallet {} (user {})",
+            self.nodes.len(),
+            self.wallet_address,
+            self.user_id
+        )?;
+        if !self.nodes.is_empty() {
+            writeln!(
+                f,
+                "  {:<12} {:<5} {:<21} {:>10}  LOCAL",
+                "NODE ID", "TYPE", "LAST SEEN", "POINTS"
+            )?;
+        }
+        for node in &self.nodes {
+            writeln!(
+                f,
+                "  {:<12} {:<5} {:<21} {:>10}  {}",
+                node.node_id,
+                node.node_type,
+                node.last_seen.as_deref().unwrap_or("never"),
+                node.points,
+                if node.local { "yes" } else { "-" }
+            )?;
+        }
+        if !self.unknown_local.is_empty() {
+            writeln!(
+                f,
+                "Not registered to this wallet, but recorded on this machine: {}",
+                self.unknown_local.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// List the nodes of `wallet` or, without it, of the wallet in the config file, for `nodes list`.
+pub async fn list_nodes(
+    config_path: &Path,
+    wallet: Option<String>,
+    orchestrator: &dyn Orchestrator,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let wallet_address = match wallet {
+        Some(wallet) => wallet,
+        None => {
+            let config = Config::load_from_file(config_path)?;
+            if !config.wallet_address.is_empty() {
+                config.wallet_address
+            } else if !config.node_id.is_empty() {
+                orchestrator.get_node(&config.node_id).await?
+            } else {
+                return Err(
+                    UsageError("No wallet in the config file: pass --wallet".to_string()).into(),
+                );
+            }
+        }
+    };
+    let listing =
+        fetch_node_listing(orchestrator, &wallet_address, &local_node_ids(config_path)).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&listing)?);
+    } else {
+        print!("{}", listing);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::{Node, UserResponse};
+    use crate::orchestrator::MockOrchestrator;
+
+    fn entry(node_id: &str) -> NodeEntry {
+        NodeEntry {
+            node_id: node_id.to_string(),
+            user_id: "user".to_string(),
+            environment: "Production".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_add_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = nodes_manifest_path(&dir.path().join(".nexus").join("config.json"));
+        assert!(path.ends_with(".nexus/nodes.json"));
+
+        let mut manifest = NodeManifest::load_or_default(&path).unwrap();
+        assert!(manifest.nodes.is_empty());
+
+        manifest.add(entry("1"));
+        manifest.add(entry("2"));
+        manifest.add(entry("1"));
+        manifest.save(&path).unwrap();
+
+        let mut loaded = NodeManifest::load_or_default(&path).unwrap();
+        assert_eq!(loaded.node_ids(), vec!["1", "2"]);
+
+        assert!(loaded.remove("1"));
+        assert!(!loaded.remove("1"));
+        assert_eq!(loaded.node_ids(), vec!["2"]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_node_listing_pages_and_reconciles() {
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_get_user_nodes()
+            .returning(|wallet, cursor| {
+                let (node_id, next) = match cursor {
+                    "" => ("1", "page-2"),
+                    _ => ("2", ""),
+                };
+                Ok(UserResponse {
+                    nodes: vec![Node {
+                        node_id: node_id.to_string(),
+                        node_type: NodeType::CliProver as i32,
+                        last_seen: (node_id == "1").then_some(prost_types::Timestamp {
+                            seconds: 1_717_243_800,
+                            nanos: 0,
+                        }),
+                        points: 100,
+                    }],
+                    nodes_next_cursor: next.to_string(),
+                    user_id: "user".to_string(),
+                    wallet_address: wallet.to_string(),
+                })
+            });
+        let local = BTreeSet::from(["2".to_string(), "3".to_string()]);
+
+        let listing = fetch_node_listing(&orchestrator, "0xabc", &local)
+            .await
+            .unwrap();
+
+        assert_eq!(listing.user_id, "user");
+        assert_eq!(listing.nodes.len(), 2);
+        assert_eq!(
+            listing.nodes[0].last_seen.as_deref(),
+            Some("2024-06-01T12:10:00Z")
+        );
+        assert!(!listing.nodes[0].local);
+        assert_eq!(listing.nodes[1].last_seen, None);
+        assert!(listing.nodes[1].local);
+        assert_eq!(listing.unknown_local, vec!["3"]);
+    }
+}
@@ -0,0 +1,293 @@
+//! Desktop notifications via the platform notification service
+
+use super::{DesktopSettings, Notification};
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::events::{Event, EventPayload, Worker};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Whether desktop notifications can be shown in this environment.
+///
+/// Always false inside containers, and on Linux/BSD when no graphical session is present.
+pub fn is_supported() -> bool {
+    if crate::system::is_container() {
+        return false;
+    }
+    if cfg!(any(target_os = "macos", target_os = "windows")) {
+        return true;
+    }
+    std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/// Show a notification without blocking the caller. Delivery errors are ignored.
+pub fn show(notification: Notification) {
+    tokio::task::spawn_blocking(move || {
+        let _ = notify_rust::Notification::new()
+            .appname("Nexus CLI")
+            .summary(&notification.title)
+            .body(&notification.body)
+            .show();
+    });
+}
+
+/// Watch worker events and show desktop notifications for the enabled triggers.
+///
+/// Returns the receiver the UI should consume instead; events pass through unchanged.
+pub fn spawn_notifier(
+    mut source: mpsc::Receiver<Event>,
+    settings: DesktopSettings,
+) -> mpsc::Receiver<Event> {
+    let (sender, receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        let mut monitor = DesktopMonitor::new(settings);
+        while let Some(event) = source.recv().await {
+            if let Some(notification) = monitor.observe(&event, Instant::now()) {
+                show(notification);
+            }
+            if sender.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+/// Notification for a newly released CLI version
+pub fn version_update(latest_version: &str) -> Notification {
+    Notification::new(
+        "Nexus CLI update available",
+        format!("Version {} is available", latest_version),
+    )
+}
+
+/// Decides which events deserve a desktop notification, from their payloads rather than their
+/// messages, which are worded for people and may change
+#[derive(Debug)]
+pub struct DesktopMonitor {
+    settings: DesktopSettings,
+    submission_failures: u32,
+    rate_limited_since: Option<Instant>,
+    rate_limit_notified: bool,
+}
+
+impl DesktopMonitor {
+    pub fn new(settings: DesktopSettings) -> Self {
+        Self {
+            settings,
+            submission_failures: 0,
+            rate_limited_since: None,
+            rate_limit_notified: false,
+        }
+    }
+
+    /// Update internal state from an event, returning a notification if one is due.
+    pub fn observe(&mut self, event: &Event, now: Instant) -> Option<Notification> {
+        match event.worker {
+            Worker::ProofSubmitter => self.observe_submission(event),
+            Worker::TaskFetcher => self.observe_fetch(event, now),
+            Worker::Prover(_) => self.observe_prover(event),
+        }
+    }
+
+    fn observe_prover(&self, event: &Event) -> Option<Notification> {
+        match &event.payload {
+            Some(EventPayload::ProofSlowdown {
+                difficulty,
+                baseline_secs,
+                recent_secs,
+                cause,
+            }) if self.settings.proof_slowdown => {
+                let mut body = format!(
+                    "{} proofs take {}s, up from {}s earlier this session",
+                    difficulty.as_str_name(),
+                    recent_secs,
+                    baseline_secs
+                );
+                if let Some(cause) = cause {
+                    body.push_str(&format!("; suspected cause: {}", cause));
+                }
+                Some(Notification::new("Proofs slowing down", body))
+            }
+            _ => None,
+        }
+    }
+
+    fn observe_submission(&mut self, event: &Event) -> Option<Notification> {
+        match &event.payload {
+            Some(EventPayload::ProofSubmitted { task_id }) => {
+                self.submission_failures = 0;
+                self.settings.proof_accepted.then(|| {
+                    Notification::new(
+                        "Proof accepted",
+                        format!("The proof for task {} was accepted", task_id),
+                    )
+                })
+            }
+            Some(EventPayload::SubmissionFailed { .. }) => {
+                self.submission_failures += 1;
+                let threshold = self.settings.submission_failure_streak;
+                (threshold > 0 && self.submission_failures == threshold).then(|| {
+                    Notification::new(
+                        "Proof submissions failing",
+                        format!("{} consecutive submissions failed", threshold),
+                    )
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn observe_fetch(&mut self, event: &Event, now: Instant) -> Option<Notification> {
+        match event.payload {
+            Some(EventPayload::TaskFetched { .. }) => {
+                self.rate_limited_since = None;
+                self.rate_limit_notified = false;
+                return None;
+            }
+            Some(EventPayload::RateLimited { .. }) => {
+                self.rate_limited_since.get_or_insert(now);
+            }
+            _ => {}
+        }
+
+        let minutes = self.settings.rate_limited_minutes;
+        let since = self.rate_limited_since?;
+        if minutes == 0
+            || self.rate_limit_notified
+            || now.duration_since(since) < Duration::from_secs(minutes * 60)
+        {
+            return None;
+        }
+        self.rate_limit_notified = true;
+        Some(Notification::new(
+            "Task fetching rate limited",
+            format!("No tasks received for over {} minutes", minutes),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventType;
+    use crate::logging::LogLevel;
+    use crate::nexus_orchestrator::TaskDifficulty;
+
+    fn submitter(msg: &str, event_type: EventType, payload: EventPayload) -> Event {
+        Event::proof_submitter_with_level(msg.to_string(), event_type, LogLevel::Info)
+            .with_payload(payload)
+    }
+
+    fn fetcher(msg: &str, event_type: EventType, payload: EventPayload) -> Event {
+        Event::task_fetcher_with_level(msg.to_string(), event_type, LogLevel::Info)
+            .with_payload(payload)
+    }
+
+    #[test]
+    fn test_proof_accepted() {
+        let mut monitor = DesktopMonitor::new(DesktopSettings::default());
+        let event = submitter(
+            "Step 4 of 4: Proof submitted successfully for task abc\n",
+            EventType::Success,
+            EventPayload::ProofSubmitted {
+                task_id: "abc".to_string(),
+            },
+        );
+        let notification = monitor.observe(&event, Instant::now()).unwrap();
+        assert_eq!(notification.title, "Proof accepted");
+        assert_eq!(notification.body, "The proof for task abc was accepted");
+
+        let mut muted = DesktopMonitor::new(DesktopSettings {
+            proof_accepted: false,
+            ..DesktopSettings::default()
+        });
+        assert!(muted.observe(&event, Instant::now()).is_none());
+    }
+
+    #[test]
+    fn test_proof_slowdown() {
+        let event = Event::prover_with_level(
+            0,
+            "SMALL proofs take 2.0x as long as earlier this session".to_string(),
+            EventType::Refresh,
+            LogLevel::Warn,
+        )
+        .with_payload(EventPayload::ProofSlowdown {
+            difficulty: TaskDifficulty::Small,
+            baseline_secs: 20,
+            recent_secs: 40,
+            cause: None,
+        });
+        let mut monitor = DesktopMonitor::new(DesktopSettings::default());
+        let notification = monitor.observe(&event, Instant::now()).unwrap();
+        assert_eq!(notification.title, "Proofs slowing down");
+        assert_eq!(
+            notification.body,
+            "SMALL proofs take 40s, up from 20s earlier this session"
+        );
+
+        let mut muted = DesktopMonitor::new(DesktopSettings {
+            proof_slowdown: false,
+            ..DesktopSettings::default()
+        });
+        assert!(muted.observe(&event, Instant::now()).is_none());
+    }
+
+    #[test]
+    fn test_failure_streak_notifies_once() {
+        let mut monitor = DesktopMonitor::new(DesktopSettings::default());
+        let failure = submitter(
+            "Failed to submit proof for task abc",
+            EventType::Error,
+            EventPayload::SubmissionFailed {
+                task_id: "abc".to_string(),
+            },
+        );
+        let now = Instant::now();
+        assert!(monitor.observe(&failure, now).is_none());
+        assert!(monitor.observe(&failure, now).is_none());
+        assert!(monitor.observe(&failure, now).is_some());
+        assert!(monitor.observe(&failure, now).is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_duration() {
+        let mut monitor = DesktopMonitor::new(DesktopSettings::default());
+        let limited = fetcher(
+            "Failed to fetch task: HTTP error with status 429: Rate limited",
+            EventType::Error,
+            EventPayload::RateLimited {
+                retry_after_secs: None,
+            },
+        );
+        let start = Instant::now();
+        assert!(monitor.observe(&limited, start).is_none());
+        assert!(
+            monitor
+                .observe(&limited, start + Duration::from_secs(5 * 60))
+                .is_none()
+        );
+        assert!(
+            monitor
+                .observe(&limited, start + Duration::from_secs(11 * 60))
+                .is_some()
+        );
+
+        // A fetched task resets the timer
+        monitor.observe(
+            &fetcher(
+                "Step 1 of 4: Got task abc",
+                EventType::Success,
+                EventPayload::TaskFetched {
+                    task_id: "abc".to_string(),
+                },
+            ),
+            start,
+        );
+        assert!(
+            monitor
+                .observe(&limited, start + Duration::from_secs(30 * 60))
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! User-facing notifications about notable prover events
+//!
+//! Notification settings live in the `notifications` section of `~/.nexus/config.json`.
+//! All notifications are opt-in.
+
+pub mod desktop;
+pub mod webhook;
+
+use serde::{Deserialize, Serialize};
+use webhook::WebhookSettings;
+
+/// A notification ready to be delivered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+}
+
+impl Notification {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// `notifications` section of the config file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct NotificationSettings {
+    /// Desktop notifications, shown in TUI mode only
+    #[serde(default)]
+    pub desktop: DesktopSettings,
+    /// Webhooks alerted about node incidents, in both TUI and headless mode
+    #[serde(default)]
+    pub webhooks: Vec<WebhookSettings>,
+}
+
+/// Per-event toggles for desktop notifications
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct DesktopSettings {
+    /// Master switch; nothing is shown unless this is set
+    pub enabled: bool,
+    /// Notify when a proof is accepted by the orchestrator
+    pub proof_accepted: bool,
+    /// Notify after this many consecutive submission failures (0 disables)
+    pub submission_failure_streak: u32,
+    /// Notify when task fetching is rate limited for this many minutes (0 disables)
+    pub rate_limited_minutes: u64,
+    /// Notify when a new CLI version is available
+    pub version_update: bool,
+    /// Notify when proofs take much longer than earlier in the session
+    pub proof_slowdown: bool,
+}
+
+impl Default for DesktopSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            proof_accepted: true,
+            submission_failure_streak: 3,
+            rate_limited_minutes: 10,
+            version_update: true,
+            proof_slowdown: true,
+        }
+    }
+}
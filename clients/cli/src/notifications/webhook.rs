@@ -0,0 +1,452 @@
+//! Webhook notifications (Discord, Slack, Telegram or generic JSON) for node incidents
+//!
+//! Each configured webhook has its own incident filters and delivery rate limit, so a
+//! fleet operator can, for example, page on error streaks while only logging idle nodes.
+
+use super::Notification;
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::consts::cli_consts::notifications::{
+    WEBHOOK_IDLE_CHECK_INTERVAL_SECS, WEBHOOK_TIMEOUT_SECS,
+};
+use crate::events::{Event, EventPayload, EventType, Worker};
+use crate::version::checker::UpdateChannel;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Chat service a webhook URL belongs to, which determines the payload shape
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookKind {
+    /// `{"content": ...}`
+    Discord,
+    /// `{"text": ...}`
+    Slack,
+    /// `{"chat_id": ..., "text": ...}` posted to the Bot API `sendMessage` URL
+    Telegram,
+    /// `{"text": ..., "title": ..., "node_id": ...}`
+    Generic,
+}
+
+/// One entry of `notifications.webhooks` in the config file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct WebhookSettings {
+    /// Webhook URL
+    pub url: String,
+    /// Payload format; inferred from the URL when omitted
+    pub kind: Option<WebhookKind>,
+    /// Chat to post to, required for Telegram
+    pub telegram_chat_id: Option<String>,
+    /// Alert after this many consecutive errors (0 disables)
+    pub error_streak: u32,
+    /// Alert when no proof was submitted for this many minutes (0 disables)
+    pub no_submissions_minutes: u64,
+    /// Alert when this CLI version is out of date
+    pub version_mismatch: bool,
+    /// Alert when proofs take much longer than earlier in the session
+    pub proof_slowdown: bool,
+    /// Message template; supports `{node_id}`, `{title}` and `{message}`
+    pub template: String,
+    /// Minimum seconds between two deliveries; alerts in between are counted and skipped
+    pub min_interval_secs: u64,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            kind: None,
+            telegram_chat_id: None,
+            error_streak: 5,
+            no_submissions_minutes: 30,
+            version_mismatch: true,
+            proof_slowdown: true,
+            template: "[Nexus node {node_id}] {title}: {message}".to_string(),
+            min_interval_secs: 300,
+        }
+    }
+}
+
+impl WebhookSettings {
+    /// Payload format, explicit or inferred from the URL
+    pub fn kind(&self) -> WebhookKind {
+        if let Some(kind) = self.kind {
+            return kind;
+        }
+        if self.url.contains("discord.com/api/webhooks") {
+            WebhookKind::Discord
+        } else if self.url.contains("hooks.slack.com") {
+            WebhookKind::Slack
+        } else if self.url.contains("api.telegram.org") {
+            WebhookKind::Telegram
+        } else {
+            WebhookKind::Generic
+        }
+    }
+
+    /// Render the message text for a notification
+    pub fn render(&self, node_id: u64, notification: &Notification) -> String {
+        self.template
+            .replace("{node_id}", &node_id.to_string())
+            .replace("{title}", &notification.title)
+            .replace("{message}", &notification.body)
+    }
+
+    /// JSON body to post for a notification
+    pub fn payload(&self, node_id: u64, notification: &Notification) -> serde_json::Value {
+        let text = self.render(node_id, notification);
+        match self.kind() {
+            WebhookKind::Discord => serde_json::json!({ "content": text }),
+            WebhookKind::Slack => serde_json::json!({ "text": text }),
+            WebhookKind::Telegram => serde_json::json!({
+                "chat_id": self.telegram_chat_id.clone().unwrap_or_default(),
+                "text": text,
+            }),
+            WebhookKind::Generic => serde_json::json!({
+                "text": text,
+                "title": notification.title,
+                "node_id": node_id,
+            }),
+        }
+    }
+}
+
+/// Tracks incidents for a single webhook according to its filters
+#[derive(Debug)]
+struct IncidentMonitor {
+    error_streak: u32,
+    streak_notified: bool,
+    last_submission: Instant,
+    idle_notified: bool,
+}
+
+impl IncidentMonitor {
+    fn new(now: Instant) -> Self {
+        Self {
+            error_streak: 0,
+            streak_notified: false,
+            last_submission: now,
+            idle_notified: false,
+        }
+    }
+
+    fn observe(
+        &mut self,
+        settings: &WebhookSettings,
+        event: &Event,
+        now: Instant,
+    ) -> Option<Notification> {
+        if let Some(EventPayload::ProofSlowdown { .. }) = event.payload {
+            return settings
+                .proof_slowdown
+                .then(|| Notification::new("Proofs slowing down", event.msg.trim()));
+        }
+        match event.event_type {
+            EventType::Success => {
+                self.error_streak = 0;
+                self.streak_notified = false;
+                if event.worker == Worker::ProofSubmitter {
+                    self.last_submission = now;
+                    self.idle_notified = false;
+                }
+                None
+            }
+            EventType::Error => {
+                self.error_streak += 1;
+                let threshold = settings.error_streak;
+                if threshold == 0 || self.streak_notified || self.error_streak < threshold {
+                    return None;
+                }
+                self.streak_notified = true;
+                Some(Notification::new(
+                    "Error streak",
+                    format!(
+                        "{} consecutive errors, last: {}",
+                        self.error_streak,
+                        event.msg.trim()
+                    ),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn check_idle(&mut self, settings: &WebhookSettings, now: Instant) -> Option<Notification> {
+        let minutes = settings.no_submissions_minutes;
+        if minutes == 0
+            || self.idle_notified
+            || now.duration_since(self.last_submission) < Duration::from_secs(minutes * 60)
+        {
+            return None;
+        }
+        self.idle_notified = true;
+        Some(Notification::new(
+            "Node stopped earning",
+            format!("No proofs submitted in the last {} minutes", minutes),
+        ))
+    }
+}
+
+/// Drops deliveries that arrive faster than the configured interval
+#[derive(Debug, Default)]
+struct DeliveryLimiter {
+    last_sent: Option<Instant>,
+    suppressed: u32,
+}
+
+impl DeliveryLimiter {
+    /// Returns the number of alerts skipped since the last delivery if this one may be
+    /// sent now, or `None` if it should be skipped.
+    fn admit(&mut self, min_interval: Duration, now: Instant) -> Option<u32> {
+        if self
+            .last_sent
+            .is_some_and(|last| now.duration_since(last) < min_interval)
+        {
+            self.suppressed += 1;
+            return None;
+        }
+        self.last_sent = Some(now);
+        Some(std::mem::take(&mut self.suppressed))
+    }
+}
+
+struct Webhook {
+    settings: WebhookSettings,
+    monitor: IncidentMonitor,
+    limiter: DeliveryLimiter,
+}
+
+impl Webhook {
+    fn deliver(&mut self, client: &reqwest::Client, node_id: u64, mut notification: Notification) {
+        let min_interval = Duration::from_secs(self.settings.min_interval_secs);
+        let Some(suppressed) = self.limiter.admit(min_interval, Instant::now()) else {
+            return;
+        };
+        if suppressed > 0 {
+            notification.body = format!(
+                "{} (+{} earlier alerts suppressed)",
+                notification.body, suppressed
+            );
+        }
+        let request = client
+            .post(&self.settings.url)
+            .json(&self.settings.payload(node_id, &notification));
+        tokio::spawn(async move {
+            // Delivery is best effort; a failing webhook must never affect proving
+            let _ = request.send().await;
+        });
+    }
+}
+
+/// Watch worker events and post incidents to the configured webhooks.
+///
+/// Returns the receiver the UI should consume instead; events pass through unchanged.
+pub fn spawn_webhook_notifier(
+    mut source: mpsc::Receiver<Event>,
+    settings: Vec<WebhookSettings>,
+    node_id: u64,
+    update_channel: UpdateChannel,
+) -> mpsc::Receiver<Event> {
+    let (sender, receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+        .build()
+        .unwrap_or_default();
+    let start = Instant::now();
+    let mut webhooks: Vec<Webhook> = settings
+        .into_iter()
+        .filter(|settings| !settings.url.is_empty())
+        .map(|settings| Webhook {
+            settings,
+            monitor: IncidentMonitor::new(start),
+            limiter: DeliveryLimiter::default(),
+        })
+        .collect();
+
+    tokio::spawn(async move {
+        // The version check is an HTTP request; events must keep flowing to the UI meanwhile
+        let mut version_check = tokio::spawn(crate::version::checker::check_for_new_version(
+            env!("CARGO_PKG_VERSION"),
+            update_channel,
+        ));
+        let mut version_checked = false;
+
+        let mut idle_check =
+            tokio::time::interval(Duration::from_secs(WEBHOOK_IDLE_CHECK_INTERVAL_SECS));
+        loop {
+            tokio::select! {
+                checked = &mut version_check, if !version_checked => {
+                    version_checked = true;
+                    if let Ok(Some(message)) = checked {
+                        for webhook in webhooks.iter_mut().filter(|w| w.settings.version_mismatch) {
+                            webhook.deliver(
+                                &client,
+                                node_id,
+                                Notification::new("Version mismatch", message.clone()),
+                            );
+                        }
+                    }
+                }
+                received = source.recv() => {
+                    let Some(event) = received else { break };
+                    let now = Instant::now();
+                    for webhook in webhooks.iter_mut() {
+                        if let Some(notification) = webhook.monitor.observe(&webhook.settings, &event, now) {
+                            webhook.deliver(&client, node_id, notification);
+                        }
+                    }
+                    if sender.send(event).await.is_err() {
+                        break;
+                    }
+                }
+                _ = idle_check.tick() => {
+                    let now = Instant::now();
+                    for webhook in webhooks.iter_mut() {
+                        if let Some(notification) = webhook.monitor.check_idle(&webhook.settings, now) {
+                            webhook.deliver(&client, node_id, notification);
+                        }
+                    }
+                }
+            }
+        }
+        version_check.abort();
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::LogLevel;
+
+    fn settings(url: &str) -> WebhookSettings {
+        WebhookSettings {
+            url: url.to_string(),
+            ..WebhookSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_kind_is_inferred_from_url() {
+        assert_eq!(
+            settings("https://discord.com/api/webhooks/1/abc").kind(),
+            WebhookKind::Discord
+        );
+        assert_eq!(
+            settings("https://hooks.slack.com/services/T/B/X").kind(),
+            WebhookKind::Slack
+        );
+        assert_eq!(
+            settings("https://api.telegram.org/bot123/sendMessage").kind(),
+            WebhookKind::Telegram
+        );
+        assert_eq!(
+            settings("https://example.com/hook").kind(),
+            WebhookKind::Generic
+        );
+
+        let mut explicit = settings("https://example.com/hook");
+        explicit.kind = Some(WebhookKind::Slack);
+        assert_eq!(explicit.kind(), WebhookKind::Slack);
+    }
+
+    #[test]
+    fn test_payload_rendering() {
+        let notification = Notification::new("Error streak", "5 consecutive errors");
+        let discord = settings("https://discord.com/api/webhooks/1/abc").payload(7, &notification);
+        assert_eq!(
+            discord["content"],
+            "[Nexus node 7] Error streak: 5 consecutive errors"
+        );
+
+        let mut telegram = settings("https://api.telegram.org/bot123/sendMessage");
+        telegram.telegram_chat_id = Some("-100".to_string());
+        telegram.template = "{title} on {node_id}".to_string();
+        let payload = telegram.payload(7, &notification);
+        assert_eq!(payload["chat_id"], "-100");
+        assert_eq!(payload["text"], "Error streak on 7");
+    }
+
+    #[test]
+    fn test_error_streak_filter() {
+        let settings = WebhookSettings {
+            error_streak: 2,
+            ..settings("https://example.com/hook")
+        };
+        let error = Event::task_fetcher_with_level(
+            "Failed to fetch task".to_string(),
+            EventType::Error,
+            LogLevel::Error,
+        );
+        let success = Event::task_fetcher_with_level(
+            "Step 1 of 4: Got task abc".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        );
+        let now = Instant::now();
+        let mut monitor = IncidentMonitor::new(now);
+
+        assert!(monitor.observe(&settings, &error, now).is_none());
+        assert!(monitor.observe(&settings, &error, now).is_some());
+        assert!(monitor.observe(&settings, &error, now).is_none());
+        monitor.observe(&settings, &success, now);
+        assert!(monitor.observe(&settings, &error, now).is_none());
+        assert!(monitor.observe(&settings, &error, now).is_some());
+    }
+
+    #[test]
+    fn test_no_submissions_filter() {
+        let settings = settings("https://example.com/hook");
+        let start = Instant::now();
+        let mut monitor = IncidentMonitor::new(start);
+        assert!(
+            monitor
+                .check_idle(&settings, start + Duration::from_secs(29 * 60))
+                .is_none()
+        );
+        assert!(
+            monitor
+                .check_idle(&settings, start + Duration::from_secs(31 * 60))
+                .is_some()
+        );
+        assert!(
+            monitor
+                .check_idle(&settings, start + Duration::from_secs(90 * 60))
+                .is_none()
+        );
+
+        let submitted = Event::proof_submitter_with_level(
+            "Step 4 of 4: Proof submitted successfully for task abc".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        );
+        let later = start + Duration::from_secs(100 * 60);
+        monitor.observe(&settings, &submitted, later);
+        assert!(
+            monitor
+                .check_idle(&settings, later + Duration::from_secs(31 * 60))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_delivery_rate_limit() {
+        let mut limiter = DeliveryLimiter::default();
+        let interval = Duration::from_secs(60);
+        let start = Instant::now();
+        assert_eq!(limiter.admit(interval, start), Some(0));
+        assert_eq!(
+            limiter.admit(interval, start + Duration::from_secs(10)),
+            None
+        );
+        assert_eq!(
+            limiter.admit(interval, start + Duration::from_secs(20)),
+            None
+        );
+        assert_eq!(
+            limiter.admit(interval, start + Duration::from_secs(61)),
+            Some(2)
+        );
+    }
+}
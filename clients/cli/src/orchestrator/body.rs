@@ -0,0 +1,159 @@
+//! Request bodies that share proof bytes instead of copying them
+//!
+//! Encoding a proof submission with [`prost::Message::encode_to_vec`] copies every proof into
+//! one buffer, on top of the copies already held for retries. [`ChunkedBody`] encodes the small
+//! fields into a buffer of their own and appends the proofs as the [`Bytes`] they are held in, so
+//! a submission costs no more memory than its proofs, however often it is retried. Protobuf allows
+//! fields in any order, so the proofs can follow the other fields.
+
+use crate::nexus_orchestrator::{SubmitProofRequest, SubmitProofsBatchRequest};
+use bytes::Bytes;
+use prost::Message;
+use prost::encoding::{WireType, encode_key, encode_varint};
+
+/// Tag of `SubmitProofRequest.proof`
+const PROOF_TAG: u32 = 5;
+
+/// Tag of `SubmitProofRequest.proofs`
+const PROOFS_TAG: u32 = 10;
+
+/// Tag of `SubmitProofsBatchRequest.submissions`
+const SUBMISSIONS_TAG: u32 = 1;
+
+/// Request body made of shared chunks, sent as a stream
+#[derive(Debug, Clone, Default)]
+pub struct ChunkedBody {
+    chunks: Vec<Bytes>,
+    len: usize,
+}
+
+impl ChunkedBody {
+    /// Encode a proof submission, sharing its proofs.
+    pub fn submit_proof(mut request: SubmitProofRequest) -> Self {
+        let proof = std::mem::take(&mut request.proof);
+        let proofs = std::mem::take(&mut request.proofs);
+        let mut body = Self::from(request.encode_to_vec());
+        // Empty scalar fields are left out, as prost does; repeated ones are always sent
+        if !proof.is_empty() {
+            body.push_bytes_field(PROOF_TAG, proof);
+        }
+        for proof in proofs {
+            body.push_bytes_field(PROOFS_TAG, proof);
+        }
+        body
+    }
+
+    /// Encode a batch of proof submissions, sharing their proofs.
+    pub fn submit_proofs_batch(request: SubmitProofsBatchRequest) -> Self {
+        let mut body = Self::default();
+        for submission in request.submissions {
+            let submission = Self::submit_proof(submission);
+            body.push_field_header(SUBMISSIONS_TAG, submission.len);
+            body.chunks.extend(submission.chunks);
+            body.len += submission.len;
+        }
+        body
+    }
+
+    /// Length of the body in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the body has no bytes
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The chunks the body is sent as, in order
+    pub fn chunks(&self) -> &[Bytes] {
+        &self.chunks
+    }
+
+    /// Copy the body into a single buffer.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.chunks.concat()
+    }
+
+    fn push(&mut self, chunk: Bytes) {
+        self.len += chunk.len();
+        self.chunks.push(chunk);
+    }
+
+    fn push_field_header(&mut self, tag: u32, len: usize) {
+        let mut header = Vec::with_capacity(8);
+        encode_key(tag, WireType::LengthDelimited, &mut header);
+        encode_varint(len as u64, &mut header);
+        self.push(header.into());
+    }
+
+    fn push_bytes_field(&mut self, tag: u32, value: Bytes) {
+        self.push_field_header(tag, value.len());
+        self.push(value);
+    }
+}
+
+impl From<Vec<u8>> for ChunkedBody {
+    fn from(body: Vec<u8>) -> Self {
+        let mut chunked = Self::default();
+        chunked.push(body.into());
+        chunked
+    }
+}
+
+impl From<ChunkedBody> for reqwest::Body {
+    fn from(body: ChunkedBody) -> Self {
+        match <[Bytes; 1]>::try_from(body.chunks) {
+            Ok([chunk]) => reqwest::Body::from(chunk),
+            Err(chunks) => reqwest::Body::wrap_stream(futures::stream::iter(
+                chunks.into_iter().map(Ok::<_, std::io::Error>),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(task_id: &str, proof: &[u8], proofs: &[&[u8]]) -> SubmitProofRequest {
+        SubmitProofRequest {
+            task_id: task_id.to_string(),
+            proof_hash: "hash".to_string(),
+            proof: Bytes::copy_from_slice(proof),
+            proofs: proofs.iter().map(|p| Bytes::copy_from_slice(p)).collect(),
+            signature: vec![7; 64],
+            nonce: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_submission_decodes_to_request() {
+        let request = request("a", &[1, 2, 3], &[&[1, 2, 3], &[4; 300]]);
+        let body = ChunkedBody::submit_proof(request.clone());
+        assert_eq!(body.len(), request.encoded_len());
+        assert_eq!(
+            SubmitProofRequest::decode(body.to_vec().as_slice()).unwrap(),
+            request
+        );
+
+        // The proofs are shared, not copied
+        let shared = request.proofs[1].as_ptr();
+        let body = ChunkedBody::submit_proof(request);
+        assert!(body.chunks().iter().any(|chunk| chunk.as_ptr() == shared));
+    }
+
+    #[test]
+    fn test_batch_decodes_to_request() {
+        let batch = SubmitProofsBatchRequest {
+            submissions: vec![request("a", &[], &[&[1; 200]]), request("b", &[9], &[&[9]])],
+        };
+        let body = ChunkedBody::submit_proofs_batch(batch.clone());
+        assert_eq!(body.len(), batch.encoded_len());
+        assert_eq!(
+            SubmitProofsBatchRequest::decode(body.to_vec().as_slice()).unwrap(),
+            batch
+        );
+    }
+}
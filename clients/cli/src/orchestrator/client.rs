@@ -3,29 +3,38 @@
 //! A client for the Nexus Orchestrator, allowing for proof task retrieval and submission.
 
 use crate::environment::Environment;
+use crate::failures::to_hex;
+use crate::network::ProofSubmission;
 use crate::nexus_orchestrator::{
-    GetProofTaskRequest, GetProofTaskResponse, NodeType, RegisterNodeRequest, RegisterNodeResponse,
-    RegisterUserRequest, SubmitProofRequest, UserResponse,
+    DeregisterNodeRequest, GetProofTaskRequest, GetProofTaskResponse, GetTaskReceiptsRequest,
+    GetTaskReceiptsResponse, NodeType, RegisterNodeRequest, RegisterNodeResponse,
+    RegisterUserRequest, SubmitProofRequest, SubmitProofResult, SubmitProofsBatchRequest,
+    SubmitProofsBatchResponse, TaskReceipt, UserResponse,
 };
 use crate::orchestrator::Orchestrator;
+use crate::orchestrator::body::ChunkedBody;
 use crate::orchestrator::error::OrchestratorError;
-use crate::system::{estimate_peak_gflops, get_memory_info};
-use crate::task::Task;
+use crate::orchestrator::signing;
+use crate::system::{detect_gpus, estimate_peak_gflops, get_memory_info};
+use crate::task::{Task, TaskDescriptor};
+use bytes::Bytes;
 use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
 use prost::Message;
-use reqwest::{Client, ClientBuilder, Response};
+use reqwest::header::CONTENT_LENGTH;
+use reqwest::{Client, ClientBuilder, RequestBuilder, Response, StatusCode};
+use sha2::{Digest, Sha256};
 use std::sync::OnceLock;
 use std::time::Duration;
 
 /// Proof payload returned by `select_proof_payload`.
 ///
 /// Tuple components (in order):
-/// 1. `Vec<u8>`: legacy single-proof bytes (set only when exactly one proof is present and the
+/// 1. `Bytes`: legacy single-proof bytes (set only when exactly one proof is present and the
 ///    server expects a single proof field; otherwise empty)
-/// 2. `Vec<Vec<u8>>`: list of full proof byte blobs for multi-input proofs (empty for
+/// 2. `Vec<Bytes>`: list of full proof byte blobs for multi-input proofs (empty for
 ///    `ProofHash`/`AllProofHashes`)
 /// 3. `Vec<String>`: list of per-input proof hashes (used for `AllProofHashes`; empty otherwise)
-pub(crate) type ProofPayload = (Vec<u8>, Vec<Vec<u8>>, Vec<String>);
+pub(crate) type ProofPayload = (Bytes, Vec<Bytes>, Vec<String>);
 
 /// Result of fetching a proof task, including both the task and its actual difficulty
 #[derive(Debug, Clone)]
@@ -34,6 +43,19 @@ pub struct ProofTaskResult {
     pub actual_difficulty: crate::nexus_orchestrator::TaskDifficulty,
 }
 
+impl TryFrom<&GetProofTaskResponse> for ProofTaskResult {
+    type Error = OrchestratorError;
+
+    fn try_from(response: &GetProofTaskResponse) -> Result<Self, Self::Error> {
+        let task = Task::try_from(response)?;
+        let actual_difficulty = task.difficulty;
+        Ok(Self {
+            task,
+            actual_difficulty,
+        })
+    }
+}
+
 impl std::fmt::Display for ProofTaskResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -45,13 +67,13 @@ impl std::fmt::Display for ProofTaskResult {
 }
 
 // Build timestamp in milliseconds since epoch
-static BUILD_TIMESTAMP: &str = match option_env!("BUILD_TIMESTAMP") {
+pub(crate) static BUILD_TIMESTAMP: &str = match option_env!("BUILD_TIMESTAMP") {
     Some(timestamp) => timestamp,
     None => "Build timestamp not available",
 };
 
 // User-Agent string with CLI version
-const USER_AGENT: &str = concat!("nexus-cli/", env!("CARGO_PKG_VERSION"));
+pub(crate) const USER_AGENT: &str = concat!("nexus-cli/", env!("CARGO_PKG_VERSION"));
 
 // Privacy-preserving country detection for network optimization.
 // Only stores 2-letter country codes (e.g., "US", "CA", "GB") to help route
@@ -59,6 +81,22 @@ const USER_AGENT: &str = concat!("nexus-cli/", env!("CARGO_PKG_VERSION"));
 // No precise location, IP addresses, or personal data is collected or stored.
 pub(crate) static COUNTRY_CODE: OnceLock<String> = OnceLock::new();
 
+/// Country code reported when geo detection is disabled (ISO 3166 "unknown or unspecified").
+pub const UNKNOWN_COUNTRY: &str = "ZZ";
+
+/// Normalize a 2-letter country code to uppercase, rejecting anything else.
+pub fn normalize_country_code(country: &str) -> Option<String> {
+    let country = country.trim().to_uppercase();
+    (country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic())).then_some(country)
+}
+
+/// Pin the reported country for the rest of the run, skipping the external geo lookups.
+///
+/// Must be called before anything detects the country; returns false if one was already set.
+pub fn pin_country(country: &str) -> bool {
+    COUNTRY_CODE.set(country.to_string()).is_ok()
+}
+
 #[derive(Debug, Clone)]
 pub struct OrchestratorClient {
     client: Client,
@@ -83,6 +121,25 @@ impl OrchestratorClient {
         self.get_country().await
     }
 
+    /// Send a failure bundle (see [`crate::failures`]) to the support endpoint.
+    pub async fn submit_failure_report(
+        &self,
+        bundle: &crate::failures::FailureBundle,
+    ) -> Result<(), OrchestratorError> {
+        let url = self.build_url("v3/support/failures");
+        let response = self
+            .client
+            .post(&url)
+            .header("User-Agent", USER_AGENT)
+            .header("X-Build-Timestamp", BUILD_TIMESTAMP)
+            .json(bundle)
+            .send()
+            .await?;
+
+        Self::handle_response_status(response).await?;
+        Ok(())
+    }
+
     fn build_url(&self, endpoint: &str) -> String {
         format!(
             "{}/{}",
@@ -109,18 +166,18 @@ impl OrchestratorClient {
     ///   one proof is present (back-compat), and `proofs` contains the vector of full proofs.
     pub(crate) fn select_proof_payload(
         task_type: crate::nexus_orchestrator::TaskType,
-        legacy_proof: Vec<u8>,
-        proofs: Vec<Vec<u8>>,
+        legacy_proof: Bytes,
+        proofs: Vec<Bytes>,
         individual_proof_hashes: &[String],
     ) -> ProofPayload {
         match task_type {
             crate::nexus_orchestrator::TaskType::ProofHash => {
                 // For ProofHash tasks, don't send proof or individual hashes
-                (Vec::new(), Vec::new(), Vec::new())
+                (Bytes::new(), Vec::new(), Vec::new())
             }
             crate::nexus_orchestrator::TaskType::AllProofHashes => {
                 // For AllProofHashes tasks, don't send proof but send all individual hashes
-                (Vec::new(), Vec::new(), individual_proof_hashes.to_vec())
+                (Bytes::new(), Vec::new(), individual_proof_hashes.to_vec())
             }
             _ => {
                 // For ProofRequired and backward compatibility:
@@ -130,7 +187,7 @@ impl OrchestratorClient {
                 let legacy = if proofs.len() == 1 {
                     legacy_proof
                 } else {
-                    Vec::new()
+                    Bytes::new()
                 };
                 (legacy, proofs, Vec::new())
             }
@@ -138,24 +195,52 @@ impl OrchestratorClient {
     }
 
     async fn handle_response_status(response: Response) -> Result<Response, OrchestratorError> {
+        if let Some(date) = response
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|value| value.to_str().ok())
+        {
+            crate::clock::observe_date_header(date);
+        }
         if !response.status().is_success() {
             return Err(OrchestratorError::from_response(response).await);
         }
         Ok(response)
     }
 
+    /// Add the request signature headers, if request signing is enabled (see [`signing`]).
+    fn signed(
+        builder: RequestBuilder,
+        method: &str,
+        endpoint: &str,
+        body: &[Bytes],
+    ) -> RequestBuilder {
+        match signing::sign_chunked_request(method, endpoint, body) {
+            Some(headers) => headers.into_iter().fold(builder, |builder, (name, value)| {
+                builder.header(name, value)
+            }),
+            None => builder,
+        }
+    }
+
+    /// Attach `body` to a POST request, streamed from its chunks, and sign the request.
+    fn with_body(builder: RequestBuilder, endpoint: &str, body: ChunkedBody) -> RequestBuilder {
+        Self::signed(builder, "POST", endpoint, body.chunks())
+            .header(CONTENT_LENGTH, body.len())
+            .body(body)
+    }
+
     async fn get_request<T: Message + Default>(
         &self,
         endpoint: &str,
     ) -> Result<T, OrchestratorError> {
         let url = self.build_url(endpoint);
-        let response = self
+        let request = self
             .client
             .get(&url)
             .header("User-Agent", USER_AGENT)
-            .header("X-Build-Timestamp", BUILD_TIMESTAMP)
-            .send()
-            .await?;
+            .header("X-Build-Timestamp", BUILD_TIMESTAMP);
+        let response = Self::signed(request, "GET", endpoint, &[]).send().await?;
 
         let response = Self::handle_response_status(response).await?;
         let response_bytes = response.bytes().await?;
@@ -165,16 +250,16 @@ impl OrchestratorClient {
     async fn post_request<T: Message + Default>(
         &self,
         endpoint: &str,
-        body: Vec<u8>,
+        body: impl Into<ChunkedBody>,
     ) -> Result<T, OrchestratorError> {
         let url = self.build_url(endpoint);
-        let response = self
+        let request = self
             .client
             .post(&url)
             .header("Content-Type", "application/octet-stream")
             .header("User-Agent", USER_AGENT)
-            .header("X-Build-Timestamp", BUILD_TIMESTAMP)
-            .body(body)
+            .header("X-Build-Timestamp", BUILD_TIMESTAMP);
+        let response = Self::with_body(request, endpoint, body.into())
             .send()
             .await?;
 
@@ -186,16 +271,16 @@ impl OrchestratorClient {
     async fn post_request_no_response(
         &self,
         endpoint: &str,
-        body: Vec<u8>,
+        body: impl Into<ChunkedBody>,
     ) -> Result<(), OrchestratorError> {
         let url = self.build_url(endpoint);
-        let response = self
+        let request = self
             .client
             .post(&url)
             .header("Content-Type", "application/octet-stream")
             .header("User-Agent", USER_AGENT)
-            .header("X-Build-Timestamp", BUILD_TIMESTAMP)
-            .body(body)
+            .header("X-Build-Timestamp", BUILD_TIMESTAMP);
+        let response = Self::with_body(request, endpoint, body.into())
             .send()
             .await?;
 
@@ -203,14 +288,18 @@ impl OrchestratorClient {
         Ok(())
     }
 
+    /// Sign the task and proof hash, and the submission nonce if there is one.
     fn create_signature(
-        &self,
         signing_key: &SigningKey,
         task_id: &str,
         proof_hash: &str,
+        nonce: u64,
     ) -> (Vec<u8>, Vec<u8>) {
-        let signature_version = 0;
-        let msg = format!("{} | {} | {}", signature_version, task_id, proof_hash);
+        let msg = if nonce == 0 {
+            format!("0 | {} | {}", task_id, proof_hash)
+        } else {
+            format!("1 | {} | {} | {}", task_id, proof_hash, nonce)
+        };
         let signature = signing_key.sign(msg.as_bytes());
         let verifying_key: VerifyingKey = signing_key.verifying_key();
 
@@ -220,6 +309,145 @@ impl OrchestratorClient {
         )
     }
 
+    /// Key identifying a proof submission across retries, so the orchestrator counts it once
+    /// however many attempts reach it.
+    pub(crate) fn idempotency_key(task_id: &str, proof_hash: &str) -> String {
+        to_hex(&Sha256::digest(format!("{} | {}", task_id, proof_hash)))
+    }
+
+    /// Build a node registration binding `verifying_key` to the new node. Shared by the HTTP and
+    /// gRPC transports.
+    pub(crate) fn register_node_request(
+        user_id: &str,
+        verifying_key: VerifyingKey,
+    ) -> RegisterNodeRequest {
+        RegisterNodeRequest {
+            node_type: NodeType::CliProver as i32,
+            user_id: user_id.to_string(),
+            ed25519_public_key: verifying_key.to_bytes().to_vec(),
+        }
+    }
+
+    /// Build a deregistration signed with the key bound to the node, and timestamped with the
+    /// orchestrator's clock so that it cannot be replayed. Shared by the HTTP and gRPC transports.
+    pub(crate) fn deregister_node_request(
+        node_id: &str,
+        signing_key: &SigningKey,
+    ) -> DeregisterNodeRequest {
+        let timestamp_ms = crate::clock::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        let message = format!("deregister | {} | {}", node_id, timestamp_ms);
+        let signature = signing_key.sign(message.as_bytes());
+        DeregisterNodeRequest {
+            node_id: node_id.to_string(),
+            ed25519_public_key: signing_key.verifying_key().to_bytes().to_vec(),
+            signature: signature.to_bytes().to_vec(),
+            timestamp_ms,
+        }
+    }
+
+    /// Build the request for a new proof task. Shared by the HTTP and gRPC transports.
+    pub(crate) fn proof_task_request(
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> GetProofTaskRequest {
+        GetProofTaskRequest {
+            node_id: node_id.to_string(),
+            node_type: NodeType::CliProver as i32,
+            ed25519_public_key: verifying_key.to_bytes().to_vec(),
+            max_difficulty: max_difficulty as i32,
+            offer_id: String::new(),
+        }
+    }
+
+    /// Build a signed proof submission with node telemetry. Shared by the HTTP and gRPC transports.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn submit_proof_request(
+        task_id: &str,
+        proof_hash: &str,
+        proof: Bytes,
+        proofs: Vec<Bytes>,
+        signing_key: &SigningKey,
+        num_provers: usize,
+        task_type: crate::nexus_orchestrator::TaskType,
+        individual_proof_hashes: &[String],
+        location: String,
+        nonce: u64,
+    ) -> SubmitProofRequest {
+        let (program_memory, total_memory) = get_memory_info();
+        let flops = estimate_peak_gflops(num_provers);
+        let (signature, public_key) =
+            Self::create_signature(signing_key, task_id, proof_hash, nonce);
+
+        // Handle different task types
+        let (proof_to_send, proofs_to_send, all_proof_hashes_to_send) =
+            OrchestratorClient::select_proof_payload(
+                task_type,
+                proof,
+                proofs,
+                individual_proof_hashes,
+            );
+
+        SubmitProofRequest {
+            task_id: task_id.to_string(),
+            node_type: NodeType::CliProver as i32,
+            proof_hash: proof_hash.to_string(),
+            proof: proof_to_send,
+            proofs: proofs_to_send,
+            node_telemetry: Some(crate::nexus_orchestrator::NodeTelemetry {
+                flops_per_sec: Some(flops as i32),
+                memory_used: Some(program_memory),
+                memory_capacity: Some(total_memory),
+                // Country code for network routing optimization (privacy-preserving)
+                location: Some(location),
+                accelerators: detect_gpus()
+                    .iter()
+                    .map(|gpu| crate::nexus_orchestrator::Accelerator {
+                        name: gpu.name.clone(),
+                        backend: gpu.backend.as_str().to_string(),
+                        vram_mb: gpu.vram_mb,
+                    })
+                    .collect(),
+                labels: crate::labels::node_labels().into_iter().collect(),
+            }),
+            ed25519_public_key: public_key,
+            signature,
+            all_proof_hashes: all_proof_hashes_to_send,
+            nonce,
+            idempotency_key: Self::idempotency_key(task_id, proof_hash),
+        }
+    }
+
+    /// Build a batched submission, signing each proof as if it were submitted on its own.
+    pub(crate) fn submit_proofs_batch_request(
+        submissions: &[ProofSubmission],
+        signing_key: &SigningKey,
+        num_provers: usize,
+        location: String,
+    ) -> SubmitProofsBatchRequest {
+        SubmitProofsBatchRequest {
+            submissions: submissions
+                .iter()
+                .map(|submission| {
+                    Self::submit_proof_request(
+                        &submission.task_id,
+                        &submission.proof_hash,
+                        submission.proof_bytes.clone(),
+                        submission.proofs_bytes.clone(),
+                        signing_key,
+                        num_provers,
+                        submission.task_type,
+                        &submission.individual_proof_hashes,
+                        location.clone(),
+                        submission.nonce,
+                    )
+                })
+                .collect(),
+        }
+    }
+
     /// Detects the user's country for network optimization purposes.
     ///
     /// Privacy Note: This only detects the country (2-letter code like "US", "CA", "GB")
@@ -264,11 +492,8 @@ impl OrchestratorClient {
         let text = response.text().await?;
 
         for line in text.lines() {
-            if let Some(country) = line.strip_prefix("loc=") {
-                let country = country.trim().to_uppercase();
-                if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) {
-                    return Ok(country);
-                }
+            if let Some(country) = line.strip_prefix("loc=").and_then(normalize_country_code) {
+                return Ok(country);
             }
         }
 
@@ -284,13 +509,7 @@ impl OrchestratorClient {
             .await?;
 
         let country = response.text().await?;
-        let country = country.trim().to_uppercase();
-
-        if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) {
-            Ok(country)
-        } else {
-            Err("Invalid country code from ipinfo.io".into())
-        }
+        normalize_country_code(&country).ok_or_else(|| "Invalid country code from ipinfo.io".into())
     }
 }
 
@@ -314,12 +533,9 @@ pub(crate) async fn detect_country_once() -> String {
     {
         if let Ok(text) = response.text().await {
             for line in text.lines() {
-                if let Some(country) = line.strip_prefix("loc=") {
-                    let country = country.trim().to_uppercase();
-                    if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) {
-                        let _ = COUNTRY_CODE.set(country.clone());
-                        return country;
-                    }
+                if let Some(country) = line.strip_prefix("loc=").and_then(normalize_country_code) {
+                    let _ = COUNTRY_CODE.set(country.clone());
+                    return country;
                 }
             }
         }
@@ -328,8 +544,7 @@ pub(crate) async fn detect_country_once() -> String {
     // Fallback to ipinfo.io
     if let Ok(response) = client.get("https://ipinfo.io/country").send().await {
         if let Ok(text) = response.text().await {
-            let country = text.trim().to_uppercase();
-            if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) {
+            if let Some(country) = normalize_country_code(&text) {
                 let _ = COUNTRY_CODE.set(country.clone());
                 return country;
             }
@@ -356,6 +571,20 @@ impl Orchestrator for OrchestratorClient {
         Ok(user_response.user_id)
     }
 
+    async fn get_user_nodes(
+        &self,
+        wallet_address: &str,
+        nodes_cursor: &str,
+    ) -> Result<UserResponse, OrchestratorError> {
+        let wallet_path = urlencoding::encode(wallet_address).into_owned();
+        let mut endpoint = format!("v3/users/{}", wallet_path);
+        if !nodes_cursor.is_empty() {
+            endpoint.push_str("?nodes_cursor=");
+            endpoint.push_str(&urlencoding::encode(nodes_cursor));
+        }
+        self.get_request(&endpoint).await
+    }
+
     /// Registers a new user with the orchestrator.
     async fn register_user(
         &self,
@@ -372,16 +601,28 @@ impl Orchestrator for OrchestratorClient {
     }
 
     /// Registers a new node with the orchestrator.
-    async fn register_node(&self, user_id: &str) -> Result<String, OrchestratorError> {
-        let request = RegisterNodeRequest {
-            node_type: NodeType::CliProver as i32,
-            user_id: user_id.to_string(),
-        };
+    async fn register_node(
+        &self,
+        user_id: &str,
+        verifying_key: VerifyingKey,
+    ) -> Result<String, OrchestratorError> {
+        let request = Self::register_node_request(user_id, verifying_key);
         let request_bytes = Self::encode_request(&request);
         let response: RegisterNodeResponse = self.post_request("v3/nodes", request_bytes).await?;
         Ok(response.node_id)
     }
 
+    async fn deregister_node(
+        &self,
+        node_id: &str,
+        signing_key: SigningKey,
+    ) -> Result<(), OrchestratorError> {
+        let request = Self::deregister_node_request(node_id, &signing_key);
+        let endpoint = format!("v3/nodes/{}/deregister", node_id);
+        self.post_request_no_response(&endpoint, Self::encode_request(&request))
+            .await
+    }
+
     /// Get the wallet address associated with a node ID.
     async fn get_node(&self, node_id: &str) -> Result<String, OrchestratorError> {
         let endpoint = format!("v3/nodes/{}", node_id);
@@ -390,76 +631,175 @@ impl Orchestrator for OrchestratorClient {
         Ok(node_response.wallet_address)
     }
 
+    /// Get the point totals of a node and its owner's wallet.
+    async fn get_node_points(
+        &self,
+        node_id: &str,
+    ) -> Result<crate::nexus_orchestrator::GetNodePointsResponse, OrchestratorError> {
+        let endpoint = format!("v3/nodes/{}/points", node_id);
+        self.get_request(&endpoint).await
+    }
+
+    /// Get network-wide prover statistics, the node's rank and the supply of pending tasks.
+    async fn get_network_stats(
+        &self,
+        node_id: &str,
+    ) -> Result<crate::nexus_orchestrator::GetNetworkStatsResponse, OrchestratorError> {
+        let endpoint = format!("v3/network/stats?node_id={}", node_id);
+        self.get_request(&endpoint).await
+    }
+
     async fn get_proof_task(
         &self,
         node_id: &str,
         verifying_key: VerifyingKey,
         max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
     ) -> Result<ProofTaskResult, OrchestratorError> {
-        let request = GetProofTaskRequest {
-            node_id: node_id.to_string(),
-            node_type: NodeType::CliProver as i32,
-            ed25519_public_key: verifying_key.to_bytes().to_vec(),
-            max_difficulty: max_difficulty as i32,
-        };
+        let request = Self::proof_task_request(node_id, verifying_key, max_difficulty);
         let request_bytes = Self::encode_request(&request);
         let response: GetProofTaskResponse = self.post_request("v3/tasks", request_bytes).await?;
+        ProofTaskResult::try_from(&response)
+    }
 
-        let task = Task::from(&response);
-        let actual_difficulty = task.difficulty;
+    async fn subscribe_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        wait: Duration,
+    ) -> Result<Option<ProofTaskResult>, OrchestratorError> {
+        // Long-poll: the server holds the request until a task is assigned or `wait` elapses
+        let request = Self::proof_task_request(node_id, verifying_key, max_difficulty);
+        let endpoint = "v3/tasks/subscribe";
+        let body = Self::encode_request(&request);
+        let request = self
+            .client
+            .post(self.build_url(endpoint))
+            .header("Content-Type", "application/octet-stream")
+            .header("User-Agent", USER_AGENT)
+            .header("X-Build-Timestamp", BUILD_TIMESTAMP)
+            .header("X-Long-Poll-Timeout", wait.as_secs().to_string())
+            // Leave the server time to answer once the hold time is up
+            .timeout(wait + Duration::from_secs(10));
+        let response = Self::with_body(request, endpoint, body.into())
+            .send()
+            .await?;
 
-        Ok(ProofTaskResult {
-            task,
-            actual_difficulty,
-        })
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+        let response = Self::handle_response_status(response).await?;
+        let response_bytes = response.bytes().await?;
+        let response: GetProofTaskResponse = Self::decode_response(&response_bytes)?;
+        ProofTaskResult::try_from(&response).map(Some)
+    }
+
+    async fn preview_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Result<TaskDescriptor, OrchestratorError> {
+        let request = Self::proof_task_request(node_id, verifying_key, max_difficulty);
+        let response: crate::nexus_orchestrator::PreviewProofTaskResponse = self
+            .post_request("v3/tasks/preview", Self::encode_request(&request))
+            .await?;
+        TaskDescriptor::try_from(&response)
+    }
+
+    async fn take_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        offer_id: &str,
+    ) -> Result<ProofTaskResult, OrchestratorError> {
+        let request = GetProofTaskRequest {
+            offer_id: offer_id.to_string(),
+            ..Self::proof_task_request(node_id, verifying_key, max_difficulty)
+        };
+        let response: GetProofTaskResponse = self
+            .post_request("v3/tasks", Self::encode_request(&request))
+            .await?;
+        ProofTaskResult::try_from(&response)
+    }
+
+    async fn decline_proof_task(
+        &self,
+        node_id: &str,
+        offer_id: &str,
+        reason: &str,
+    ) -> Result<(), OrchestratorError> {
+        let request = crate::nexus_orchestrator::DeclineProofTaskRequest {
+            node_id: node_id.to_string(),
+            offer_id: offer_id.to_string(),
+            reason: reason.to_string(),
+        };
+        self.post_request_no_response("v3/tasks/decline", Self::encode_request(&request))
+            .await
     }
 
     async fn submit_proof(
         &self,
         task_id: &str,
         proof_hash: &str,
-        proof: Vec<u8>,
-        proofs: Vec<Vec<u8>>,
+        proof: Bytes,
+        proofs: Vec<Bytes>,
         signing_key: SigningKey,
         num_provers: usize,
         task_type: crate::nexus_orchestrator::TaskType,
         individual_proof_hashes: &[String],
+        nonce: u64,
     ) -> Result<(), OrchestratorError> {
-        let (program_memory, total_memory) = get_memory_info();
-        let flops = estimate_peak_gflops(num_provers);
-        let (signature, public_key) = self.create_signature(&signing_key, task_id, proof_hash);
-
         // Detect country for network optimization (privacy-preserving: only country code, no precise location)
         let location = self.get_country().await;
-        // Handle different task types
-        let (proof_to_send, proofs_to_send, all_proof_hashes_to_send) =
-            OrchestratorClient::select_proof_payload(
-                task_type,
-                proof,
-                proofs,
-                individual_proof_hashes,
-            );
+        let request = Self::submit_proof_request(
+            task_id,
+            proof_hash,
+            proof,
+            proofs,
+            &signing_key,
+            num_provers,
+            task_type,
+            individual_proof_hashes,
+            location,
+            nonce,
+        );
+        self.post_request_no_response("v3/tasks/submit", ChunkedBody::submit_proof(request))
+            .await
+    }
 
-        let request = SubmitProofRequest {
-            task_id: task_id.to_string(),
-            node_type: NodeType::CliProver as i32,
-            proof_hash: proof_hash.to_string(),
-            proof: proof_to_send,
-            proofs: proofs_to_send,
-            node_telemetry: Some(crate::nexus_orchestrator::NodeTelemetry {
-                flops_per_sec: Some(flops as i32),
-                memory_used: Some(program_memory),
-                memory_capacity: Some(total_memory),
-                // Country code for network routing optimization (privacy-preserving)
-                location: Some(location),
-            }),
-            ed25519_public_key: public_key,
-            signature,
-            all_proof_hashes: all_proof_hashes_to_send,
+    async fn submit_proofs_batch(
+        &self,
+        submissions: &[ProofSubmission],
+        signing_key: SigningKey,
+        num_provers: usize,
+    ) -> Result<Vec<SubmitProofResult>, OrchestratorError> {
+        let location = self.get_country().await;
+        let request =
+            Self::submit_proofs_batch_request(submissions, &signing_key, num_provers, location);
+        let response: SubmitProofsBatchResponse = self
+            .post_request(
+                "v3/tasks/submit/batch",
+                ChunkedBody::submit_proofs_batch(request),
+            )
+            .await?;
+        Ok(response.results)
+    }
+
+    async fn get_task_receipts(
+        &self,
+        node_id: &str,
+        task_ids: &[String],
+    ) -> Result<Vec<TaskReceipt>, OrchestratorError> {
+        let request = GetTaskReceiptsRequest {
+            node_id: node_id.to_string(),
+            task_ids: task_ids.to_vec(),
         };
-        let request_bytes = Self::encode_request(&request);
-        self.post_request_no_response("v3/tasks/submit", request_bytes)
-            .await
+        let response: GetTaskReceiptsResponse = self
+            .post_request("v3/tasks/receipts", Self::encode_request(&request))
+            .await?;
+        Ok(response.receipts)
     }
 }
 
@@ -489,7 +829,11 @@ mod live_orchestrator_tests {
     async fn test_register_node() {
         let client = super::OrchestratorClient::new(Environment::Production);
         let user_id = "78db0be7-f603-4511-9576-c660f3c58395";
-        match client.register_node(user_id).await {
+        let signing_key = ed25519_dalek::SigningKey::generate(&mut rand::thread_rng());
+        match client
+            .register_node(user_id, signing_key.verifying_key())
+            .await
+        {
             Ok(node_id) => println!("Node registered successfully: {}", node_id),
             Err(e) => panic!("Failed to register node: {}", e),
         }
@@ -557,13 +901,64 @@ mod tests {
     use super::*;
     use crate::nexus_orchestrator::TaskType;
 
+    #[test]
+    fn test_normalize_country_code() {
+        assert_eq!(normalize_country_code(" de\n"), Some("DE".to_string()));
+        assert_eq!(normalize_country_code("USA"), None);
+        assert_eq!(normalize_country_code("1A"), None);
+        assert_eq!(normalize_country_code(""), None);
+    }
+
+    #[test]
+    fn test_idempotency_key() {
+        let key = OrchestratorClient::idempotency_key("task-1", "hash");
+        assert_eq!(key.len(), 64);
+        // Retries of a submission share its key; other proofs get their own
+        assert_eq!(key, OrchestratorClient::idempotency_key("task-1", "hash"));
+        assert_ne!(key, OrchestratorClient::idempotency_key("task-1", "other"));
+        assert_ne!(key, OrchestratorClient::idempotency_key("task-2", "hash"));
+
+        let request = OrchestratorClient::submit_proof_request(
+            "task-1",
+            "hash",
+            Bytes::new(),
+            vec![],
+            &SigningKey::generate(&mut rand_core::OsRng),
+            1,
+            TaskType::ProofHash,
+            &[],
+            "US".to_string(),
+            0,
+        );
+        assert_eq!(request.idempotency_key, key);
+    }
+
+    #[test]
+    fn test_signature_covers_nonce() {
+        use ed25519_dalek::{Signature, Verifier};
+
+        let signing_key = SigningKey::generate(&mut rand_core::OsRng);
+        let verifying_key = signing_key.verifying_key();
+        let verify = |signature: &[u8], msg: &str| {
+            let signature = Signature::from_slice(signature).unwrap();
+            verifying_key.verify(msg.as_bytes(), &signature).is_ok()
+        };
+
+        let (signature, _) = OrchestratorClient::create_signature(&signing_key, "t1", "h", 0);
+        assert!(verify(&signature, "0 | t1 | h"));
+
+        let (signature, _) = OrchestratorClient::create_signature(&signing_key, "t1", "h", 7);
+        assert!(verify(&signature, "1 | t1 | h | 7"));
+        assert!(!verify(&signature, "1 | t1 | h | 8"));
+    }
+
     #[tokio::test]
     /// select_proof_payload rules: only ProofRequired sets proof/proofs.
     async fn test_select_proof_payload() {
         // Common inputs
-        let legacy = vec![9, 9, 9];
-        let proofs_multi = vec![vec![1], vec![2]];
-        let proofs_single = vec![vec![7]];
+        let legacy = Bytes::from_static(&[9, 9, 9]);
+        let proofs_multi = vec![Bytes::from_static(&[1]), Bytes::from_static(&[2])];
+        let proofs_single = vec![Bytes::from_static(&[7])];
         let hashes = vec!["a".to_string(), "b".to_string()];
 
         // PROOF_HASH: both proof and proofs empty; no hashes
@@ -1,8 +1,10 @@
 //! Error handling for the orchestrator module
 
+use chrono::{DateTime, Utc};
 use prost::DecodeError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 use thiserror::Error;
 
 #[allow(non_snake_case)] // used for json parsing
@@ -13,6 +15,27 @@ struct RawError {
     httpCode: u16,
 }
 
+/// Body of a 503 response announcing maintenance, e.g.
+/// `{"maintenance": true, "until": "2025-06-01T12:00:00Z", "message": "Database upgrade"}`
+#[derive(Deserialize)]
+struct MaintenanceBody {
+    maintenance: bool,
+    /// RFC 3339 end of the window
+    #[serde(default)]
+    until: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// Planned downtime announced by the orchestrator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Maintenance {
+    /// Announcement to show, if the server sent one
+    pub message: Option<String>,
+    /// Time left in the window when the response arrived, if the server said
+    pub remaining: Option<Duration>,
+}
+
 #[derive(Debug, Error)]
 pub enum OrchestratorError {
     /// Failed to decode a Protobuf message from the server
@@ -30,6 +53,62 @@ pub enum OrchestratorError {
         message: String,
         headers: HashMap<String, String>,
     },
+
+    /// The connection to the orchestrator could not be set up or became unusable.
+    #[error("gRPC transport error: {0}")]
+    Transport(String),
+
+    /// The server's response decoded but lacks data the client needs.
+    #[error("Malformed response: {0}")]
+    MalformedResponse(String),
+
+    /// A proof with the same idempotency key was already accepted: a 409 naming
+    /// [`ALREADY_ACCEPTED`] over HTTP, `ALREADY_EXISTS` from a gRPC submission.
+    #[error("Proof already accepted")]
+    AlreadyAccepted,
+}
+
+/// Name of the error the orchestrator answers a repeated submission with over HTTP
+pub const ALREADY_ACCEPTED: &str = "ProofAlreadyAccepted";
+
+/// gRPC statuses are mapped onto their HTTP equivalents so that retry, backoff and
+/// rate-limit handling treat both transports the same way.
+impl From<tonic::Status> for OrchestratorError {
+    fn from(status: tonic::Status) -> Self {
+        let mut headers = HashMap::new();
+        for entry in status.metadata().iter() {
+            if let tonic::metadata::KeyAndValueRef::Ascii(key, value) = entry {
+                if let Ok(value) = value.to_str() {
+                    headers.insert(key.as_str().to_lowercase(), value.to_string());
+                }
+            }
+        }
+        OrchestratorError::Http {
+            status: http_status_for(status.code()),
+            message: status.message().to_string(),
+            headers,
+        }
+    }
+}
+
+/// HTTP status code corresponding to a gRPC status code.
+fn http_status_for(code: tonic::Code) -> u16 {
+    use tonic::Code;
+    match code {
+        Code::Ok => 200,
+        Code::InvalidArgument | Code::OutOfRange => 400,
+        Code::Unauthenticated => 401,
+        Code::PermissionDenied => 403,
+        Code::NotFound => 404,
+        Code::AlreadyExists | Code::Aborted => 409,
+        Code::FailedPrecondition => 412,
+        Code::ResourceExhausted => 429,
+        Code::Cancelled => 499,
+        Code::Unimplemented => 501,
+        Code::Unavailable => 503,
+        Code::DeadlineExceeded => 504,
+        Code::Unknown | Code::Internal | Code::DataLoss => 500,
+    }
 }
 
 impl OrchestratorError {
@@ -49,6 +128,14 @@ impl OrchestratorError {
             .await
             .unwrap_or_else(|_| "Failed to read response text".to_string());
 
+        // Other conflicts are errors like any other
+        if status == 409
+            && serde_json::from_str::<RawError>(&message)
+                .is_ok_and(|error| error.name == ALREADY_ACCEPTED)
+        {
+            return OrchestratorError::AlreadyAccepted;
+        }
+
         OrchestratorError::Http {
             status,
             message,
@@ -58,6 +145,10 @@ impl OrchestratorError {
 
     /// Get the Retry-After header value in seconds, if present
     pub fn get_retry_after_seconds(&self) -> Option<u32> {
+        // Wait out a maintenance window instead of retrying every 30 seconds
+        if let Some(remaining) = self.maintenance().and_then(|m| m.remaining) {
+            return Some(remaining.as_secs().clamp(1, u32::MAX as u64) as u32);
+        }
         match self {
             Self::Http {
                 status, headers, ..
@@ -73,6 +164,58 @@ impl OrchestratorError {
         }
     }
 
+    /// Whether the server does not offer the requested endpoint at all, as opposed to failing
+    /// the request. Used to fall back from optional endpoints such as task subscriptions.
+    pub fn is_unsupported(&self) -> bool {
+        matches!(self, Self::Http { status, .. } if matches!(status, 404 | 405 | 501))
+    }
+
+    /// The maintenance window announced by a 503 response, if this is one.
+    ///
+    /// The window ends at the body's `until`, or else after the `Retry-After` delay.
+    pub fn maintenance(&self) -> Option<Maintenance> {
+        self.maintenance_at(Utc::now())
+    }
+
+    fn maintenance_at(&self, now: DateTime<Utc>) -> Option<Maintenance> {
+        let Self::Http {
+            status: 503,
+            message,
+            headers,
+        } = self
+        else {
+            return None;
+        };
+        let body = serde_json::from_str::<MaintenanceBody>(message)
+            .ok()
+            .filter(|body| body.maintenance)?;
+        let until = body
+            .until
+            .as_deref()
+            .and_then(|until| DateTime::parse_from_rfc3339(until).ok());
+        let remaining = match until {
+            Some(until) => Some(
+                (until.with_timezone(&Utc) - now)
+                    .to_std()
+                    .unwrap_or_default(),
+            ),
+            None => headers
+                .get("retry-after")
+                .and_then(|value| value.parse::<u64>().ok())
+                .map(Duration::from_secs),
+        };
+        Some(Maintenance {
+            message: body.message.filter(|message| !message.is_empty()),
+            remaining,
+        })
+    }
+
+    /// Whether a submission was refused because a proof with the same idempotency key was
+    /// already accepted, e.g. by an attempt whose response was lost to a timeout.
+    pub fn is_already_accepted(&self) -> bool {
+        matches!(self, Self::AlreadyAccepted)
+    }
+
     pub fn to_pretty(&self) -> Option<String> {
         match self {
             Self::Http {
@@ -122,6 +265,79 @@ mod tests {
         assert_eq!(error.get_retry_after_seconds(), None);
     }
 
+    #[test]
+    fn test_grpc_status_maps_to_http() {
+        let mut status = tonic::Status::resource_exhausted("Rate limited");
+        status
+            .metadata_mut()
+            .insert("retry-after", "60".parse().unwrap());
+        let error = OrchestratorError::from(status);
+        assert!(matches!(error, OrchestratorError::Http { status: 429, .. }));
+        assert_eq!(error.get_retry_after_seconds(), Some(60));
+
+        let error = OrchestratorError::from(tonic::Status::unavailable("connection reset"));
+        assert!(matches!(error, OrchestratorError::Http { status: 503, .. }));
+    }
+
+    #[test]
+    fn test_maintenance() {
+        let error = |status, message: &str, retry_after: Option<&str>| OrchestratorError::Http {
+            status,
+            message: message.to_string(),
+            headers: retry_after
+                .map(|secs| HashMap::from([("retry-after".to_string(), secs.to_string())]))
+                .unwrap_or_default(),
+        };
+        let now = "2025-06-01T11:50:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let announced = error(
+            503,
+            r#"{"maintenance": true, "until": "2025-06-01T12:00:00Z", "message": "Upgrade"}"#,
+            None,
+        );
+        assert_eq!(
+            announced.maintenance_at(now),
+            Some(Maintenance {
+                message: Some("Upgrade".to_string()),
+                remaining: Some(Duration::from_secs(600)),
+            })
+        );
+
+        let retry_after = error(503, r#"{"maintenance": true}"#, Some("120"));
+        assert_eq!(
+            retry_after.maintenance_at(now).unwrap().remaining,
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(retry_after.get_retry_after_seconds(), Some(120));
+
+        // Plain outages are not maintenance
+        assert_eq!(error(503, "Service Unavailable", None).maintenance(), None);
+        assert_eq!(
+            error(503, r#"{"maintenance": false}"#, None).maintenance(),
+            None
+        );
+        assert_eq!(
+            error(500, r#"{"maintenance": true}"#, None).maintenance(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_unsupported() {
+        let error = |status| OrchestratorError::Http {
+            status,
+            message: String::new(),
+            headers: HashMap::new(),
+        };
+        assert!(error(404).is_unsupported());
+        assert!(error(501).is_unsupported());
+        assert!(!error(429).is_unsupported());
+        assert!(!error(503).is_unsupported());
+        // A conflict is not an acceptance unless the orchestrator says so
+        assert!(!error(409).is_already_accepted());
+        assert!(OrchestratorError::AlreadyAccepted.is_already_accepted());
+    }
+
     #[test]
     fn test_get_retry_after_seconds_invalid_value() {
         let mut headers = HashMap::new();
@@ -0,0 +1,609 @@
+//! Orchestrator failover
+//!
+//! Fallback orchestrator URLs can be listed per environment in the `orchestrator` section of the
+//! config file, keyed by `production` or by the primary URL, or given with
+//! `start --orchestrator-fallback-url`:
+//!
+//! ```json
+//! "orchestrator": {
+//!   "fallback_urls": { "production": ["https://backup.orchestrator.example"] }
+//! }
+//! ```
+//!
+//! [`FailoverOrchestrator`] sends every request to the active endpoint. After a few requests in
+//! a row go unanswered, or are answered with 502/503/504, it health-checks the other endpoints in
+//! order and switches to the first healthy one. While on a fallback it health-checks the primary
+//! every minute and switches back as soon as the primary is healthy again. Each switch is reported
+//! as an event.
+
+use super::{Orchestrator, Transport, connect};
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::consts::cli_consts::failover::{
+    FAILOVER_AFTER_FAILURES, HEALTH_CHECK_TIMEOUT_SECS, PRIMARY_RECHECK_INTERVAL_SECS,
+};
+use crate::environment::Environment;
+use crate::events::{Event, EventType};
+use crate::logging::LogLevel;
+use crate::network::{ProofSubmission, RequestOutcome};
+use crate::orchestrator::error::OrchestratorError;
+use bytes::Bytes;
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+
+/// `orchestrator` section of the config file
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct OrchestratorSettings {
+    /// Fallback URLs, tried in order, by environment (see [`Environment::config_key`])
+    pub fallback_urls: BTreeMap<String, Vec<String>>,
+}
+
+impl OrchestratorSettings {
+    /// Fallback URLs configured for `environment`
+    pub fn fallback_urls(&self, environment: &Environment) -> Vec<String> {
+        self.fallback_urls
+            .get(&environment.config_key())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Whether the orchestrator at a URL is reachable and not failing
+type HealthCheck = Arc<dyn Fn(String) -> HealthFuture + Send + Sync>;
+type HealthFuture = Pin<Box<dyn Future<Output = bool> + Send>>;
+
+struct Endpoint {
+    url: String,
+    client: Arc<dyn Orchestrator>,
+}
+
+#[derive(Debug)]
+struct FailoverState {
+    /// Index of the endpoint requests go to; 0 is the primary
+    active: usize,
+    /// Unanswered requests in a row on the active endpoint
+    consecutive_failures: u32,
+    /// Last health check of the primary while on a fallback
+    primary_checked_at: Instant,
+}
+
+/// Orchestrator client that fails over between a primary and fallback endpoints
+pub struct FailoverOrchestrator {
+    environment: Environment,
+    endpoints: Vec<Endpoint>,
+    state: Mutex<FailoverState>,
+    health_check: HealthCheck,
+    switches: broadcast::Sender<String>,
+}
+
+impl FailoverOrchestrator {
+    /// Connect to `environment` and to each of `fallback_urls` with `transport`.
+    pub fn new(
+        environment: Environment,
+        fallback_urls: Vec<String>,
+        transport: Transport,
+    ) -> Result<Self, OrchestratorError> {
+        let mut endpoints = vec![Endpoint {
+            url: environment.orchestrator_url().to_string(),
+            client: connect(environment.clone(), transport)?,
+        }];
+        for url in fallback_urls {
+            if endpoints.iter().any(|endpoint| endpoint.url == url) {
+                continue;
+            }
+            let client = connect(
+                Environment::Custom {
+                    orchestrator_url: url.clone(),
+                },
+                transport,
+            )?;
+            endpoints.push(Endpoint { url, client });
+        }
+        Ok(Self::with_endpoints(
+            environment,
+            endpoints,
+            http_health_check,
+        ))
+    }
+
+    fn with_endpoints<F, Fut>(
+        environment: Environment,
+        endpoints: Vec<Endpoint>,
+        health_check: F,
+    ) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        let health_check: HealthCheck =
+            Arc::new(move |url: String| -> HealthFuture { Box::pin(health_check(url)) });
+        let (switches, _) = broadcast::channel(16);
+        Self {
+            environment,
+            endpoints,
+            state: Mutex::new(FailoverState {
+                active: 0,
+                consecutive_failures: 0,
+                primary_checked_at: Instant::now(),
+            }),
+            health_check,
+            switches,
+        }
+    }
+
+    /// Messages describing each switch between endpoints, see [`spawn_failover_events`]
+    pub fn subscribe(&self) -> broadcast::Receiver<String> {
+        self.switches.subscribe()
+    }
+
+    /// URL requests currently go to
+    pub fn active_url(&self) -> &str {
+        &self.endpoints[self.lock().active].url
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, FailoverState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Send a request to the active endpoint, and fail over or back as needed.
+    async fn call<T, F, Fut>(&self, request: F) -> Result<T, OrchestratorError>
+    where
+        F: Fn(Arc<dyn Orchestrator>) -> Fut + Send,
+        Fut: Future<Output = Result<T, OrchestratorError>> + Send,
+        T: Send,
+    {
+        self.return_to_primary_if_healthy().await;
+        let active = self.lock().active;
+        let result = request(self.endpoints[active].client.clone()).await;
+        match &result {
+            Err(e) if is_unreachable(e) => self.record_failure(active).await,
+            _ => self.lock().consecutive_failures = 0,
+        }
+        result
+    }
+
+    async fn record_failure(&self, failed: usize) {
+        let failures = {
+            let mut state = self.lock();
+            if state.active != failed {
+                // Another request already switched endpoints
+                return;
+            }
+            state.consecutive_failures += 1;
+            state.consecutive_failures
+        };
+        if failures < FAILOVER_AFTER_FAILURES || self.endpoints.len() < 2 {
+            return;
+        }
+
+        let candidates =
+            (1..self.endpoints.len()).map(|offset| (failed + offset) % self.endpoints.len());
+        for candidate in candidates {
+            if !(self.health_check)(self.endpoints[candidate].url.clone()).await {
+                continue;
+            }
+            {
+                let mut state = self.lock();
+                if state.active != failed {
+                    return;
+                }
+                state.active = candidate;
+                state.consecutive_failures = 0;
+                state.primary_checked_at = Instant::now();
+            }
+            let _ = self.switches.send(format!(
+                "Orchestrator {} is unreachable, failed over to {}",
+                self.endpoints[failed].url, self.endpoints[candidate].url
+            ));
+            return;
+        }
+    }
+
+    /// While on a fallback, switch back once the primary passes a health check. The primary is
+    /// checked at most once per recheck interval.
+    async fn return_to_primary_if_healthy(&self) {
+        let fallback = {
+            let mut state = self.lock();
+            if state.active == 0
+                || state.primary_checked_at.elapsed()
+                    < Duration::from_secs(PRIMARY_RECHECK_INTERVAL_SECS)
+            {
+                return;
+            }
+            state.primary_checked_at = Instant::now();
+            state.active
+        };
+        if !(self.health_check)(self.endpoints[0].url.clone()).await {
+            return;
+        }
+        {
+            let mut state = self.lock();
+            if state.active != fallback {
+                return;
+            }
+            state.active = 0;
+            state.consecutive_failures = 0;
+        }
+        let _ = self.switches.send(format!(
+            "Orchestrator {} is reachable again, switched back from {}",
+            self.endpoints[0].url, self.endpoints[fallback].url
+        ));
+    }
+}
+
+/// Errors after which the endpoint is considered unreachable rather than the request rejected
+fn is_unreachable(error: &OrchestratorError) -> bool {
+    RequestOutcome::from_error(error) == RequestOutcome::NetworkError
+        || matches!(error, OrchestratorError::Http { status, .. } if matches!(status, 502..=504))
+}
+
+/// Healthy if the URL answers at all, with anything but a server error.
+async fn http_health_check(url: String) -> bool {
+    let Ok(client) = reqwest::Client::builder()
+        .timeout(Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS))
+        .build()
+    else {
+        return false;
+    };
+    client
+        .get(&url)
+        .send()
+        .await
+        .is_ok_and(|response| !response.status().is_server_error())
+}
+
+/// Pass failover messages along with the worker events, so they show up in the dashboard and
+/// the headless log.
+pub fn spawn_failover_events(
+    mut source: mpsc::Receiver<Event>,
+    mut switches: broadcast::Receiver<String>,
+) -> mpsc::Receiver<Event> {
+    let (sender, receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        let mut switches_open = true;
+        loop {
+            let event = tokio::select! {
+                event = source.recv() => match event {
+                    Some(event) => event,
+                    None => break,
+                },
+                message = switches.recv(), if switches_open => match message {
+                    Ok(message) => {
+                        Event::task_fetcher_with_level(message, EventType::Refresh, LogLevel::Warn)
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => {
+                        switches_open = false;
+                        continue;
+                    }
+                },
+            };
+            if sender.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+#[async_trait::async_trait]
+impl Orchestrator for FailoverOrchestrator {
+    fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    async fn get_user(&self, wallet_address: &str) -> Result<String, OrchestratorError> {
+        self.call(|o| async move { o.get_user(wallet_address).await })
+            .await
+    }
+
+    async fn get_user_nodes(
+        &self,
+        wallet_address: &str,
+        nodes_cursor: &str,
+    ) -> Result<crate::nexus_orchestrator::UserResponse, OrchestratorError> {
+        self.call(|o| async move { o.get_user_nodes(wallet_address, nodes_cursor).await })
+            .await
+    }
+
+    async fn register_user(
+        &self,
+        user_id: &str,
+        wallet_address: &str,
+    ) -> Result<(), OrchestratorError> {
+        self.call(|o| async move { o.register_user(user_id, wallet_address).await })
+            .await
+    }
+
+    async fn register_node(
+        &self,
+        user_id: &str,
+        verifying_key: VerifyingKey,
+    ) -> Result<String, OrchestratorError> {
+        self.call(|o| async move { o.register_node(user_id, verifying_key).await })
+            .await
+    }
+
+    async fn deregister_node(
+        &self,
+        node_id: &str,
+        signing_key: SigningKey,
+    ) -> Result<(), OrchestratorError> {
+        self.call(|o| {
+            let signing_key = signing_key.clone();
+            async move { o.deregister_node(node_id, signing_key).await }
+        })
+        .await
+    }
+
+    async fn get_node(&self, node_id: &str) -> Result<String, OrchestratorError> {
+        self.call(|o| async move { o.get_node(node_id).await })
+            .await
+    }
+
+    async fn get_node_points(
+        &self,
+        node_id: &str,
+    ) -> Result<crate::nexus_orchestrator::GetNodePointsResponse, OrchestratorError> {
+        self.call(|o| async move { o.get_node_points(node_id).await })
+            .await
+    }
+
+    async fn get_network_stats(
+        &self,
+        node_id: &str,
+    ) -> Result<crate::nexus_orchestrator::GetNetworkStatsResponse, OrchestratorError> {
+        self.call(|o| async move { o.get_network_stats(node_id).await })
+            .await
+    }
+
+    async fn get_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Result<crate::orchestrator::client::ProofTaskResult, OrchestratorError> {
+        self.call(|o| async move {
+            o.get_proof_task(node_id, verifying_key, max_difficulty)
+                .await
+        })
+        .await
+    }
+
+    async fn subscribe_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        wait: Duration,
+    ) -> Result<Option<crate::orchestrator::client::ProofTaskResult>, OrchestratorError> {
+        self.call(|o| async move {
+            o.subscribe_proof_task(node_id, verifying_key, max_difficulty, wait)
+                .await
+        })
+        .await
+    }
+
+    async fn preview_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Result<crate::task::TaskDescriptor, OrchestratorError> {
+        self.call(|o| async move {
+            o.preview_proof_task(node_id, verifying_key, max_difficulty)
+                .await
+        })
+        .await
+    }
+
+    async fn take_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        offer_id: &str,
+    ) -> Result<crate::orchestrator::client::ProofTaskResult, OrchestratorError> {
+        self.call(|o| async move {
+            o.take_proof_task(node_id, verifying_key, max_difficulty, offer_id)
+                .await
+        })
+        .await
+    }
+
+    async fn decline_proof_task(
+        &self,
+        node_id: &str,
+        offer_id: &str,
+        reason: &str,
+    ) -> Result<(), OrchestratorError> {
+        self.call(|o| async move { o.decline_proof_task(node_id, offer_id, reason).await })
+            .await
+    }
+
+    async fn submit_proof(
+        &self,
+        task_id: &str,
+        proof_hash: &str,
+        proof: Bytes,
+        proofs: Vec<Bytes>,
+        signing_key: SigningKey,
+        num_provers: usize,
+        task_type: crate::nexus_orchestrator::TaskType,
+        individual_proof_hashes: &[String],
+        nonce: u64,
+    ) -> Result<(), OrchestratorError> {
+        self.call(|o| {
+            let (proof, proofs, signing_key) = (proof.clone(), proofs.clone(), signing_key.clone());
+            async move {
+                o.submit_proof(
+                    task_id,
+                    proof_hash,
+                    proof,
+                    proofs,
+                    signing_key,
+                    num_provers,
+                    task_type,
+                    individual_proof_hashes,
+                    nonce,
+                )
+                .await
+            }
+        })
+        .await
+    }
+
+    async fn submit_proofs_batch(
+        &self,
+        submissions: &[ProofSubmission],
+        signing_key: SigningKey,
+        num_provers: usize,
+    ) -> Result<Vec<crate::nexus_orchestrator::SubmitProofResult>, OrchestratorError> {
+        self.call(|o| {
+            let signing_key = signing_key.clone();
+            async move {
+                o.submit_proofs_batch(submissions, signing_key, num_provers)
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn get_task_receipts(
+        &self,
+        node_id: &str,
+        task_ids: &[String],
+    ) -> Result<Vec<crate::nexus_orchestrator::TaskReceipt>, OrchestratorError> {
+        self.call(|o| async move { o.get_task_receipts(node_id, task_ids).await })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::MockOrchestrator;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    const PRIMARY: &str = "https://primary.example";
+    const FALLBACK: &str = "https://fallback.example";
+
+    fn unreachable() -> OrchestratorError {
+        OrchestratorError::Transport("connection refused".to_string())
+    }
+
+    /// Endpoint answering `get_node` with its own URL, or failing while `down` is set
+    fn endpoint(url: &'static str, down: Arc<AtomicBool>) -> Endpoint {
+        let mut mock = MockOrchestrator::new();
+        mock.expect_get_node().returning(move |_| {
+            if down.load(Ordering::SeqCst) {
+                Err(unreachable())
+            } else {
+                Ok(url.to_string())
+            }
+        });
+        Endpoint {
+            url: url.to_string(),
+            client: Arc::new(mock),
+        }
+    }
+
+    fn failover(primary_down: Arc<AtomicBool>) -> FailoverOrchestrator {
+        let health = primary_down.clone();
+        FailoverOrchestrator::with_endpoints(
+            Environment::default(),
+            vec![
+                endpoint(PRIMARY, primary_down),
+                endpoint(FALLBACK, Arc::new(AtomicBool::new(false))),
+            ],
+            move |url: String| {
+                let healthy = url != PRIMARY || !health.load(Ordering::SeqCst);
+                async move { healthy }
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_after_repeated_failures() {
+        let primary_down = Arc::new(AtomicBool::new(true));
+        let orchestrator = failover(primary_down);
+        let mut switches = orchestrator.subscribe();
+
+        for _ in 0..FAILOVER_AFTER_FAILURES {
+            assert!(orchestrator.get_node("1").await.is_err());
+        }
+        assert_eq!(orchestrator.active_url(), FALLBACK);
+        assert_eq!(orchestrator.get_node("1").await.unwrap(), FALLBACK);
+        assert!(switches.try_recv().unwrap().contains("failed over to"));
+    }
+
+    #[tokio::test]
+    async fn test_returns_to_primary_once_healthy() {
+        let primary_down = Arc::new(AtomicBool::new(true));
+        let orchestrator = failover(primary_down.clone());
+        for _ in 0..FAILOVER_AFTER_FAILURES {
+            let _ = orchestrator.get_node("1").await;
+        }
+        assert_eq!(orchestrator.active_url(), FALLBACK);
+
+        // Sticky: the primary is not retried before the recheck interval
+        primary_down.store(false, Ordering::SeqCst);
+        assert_eq!(orchestrator.get_node("1").await.unwrap(), FALLBACK);
+
+        orchestrator.lock().primary_checked_at =
+            Instant::now() - Duration::from_secs(PRIMARY_RECHECK_INTERVAL_SECS);
+        assert_eq!(orchestrator.get_node("1").await.unwrap(), PRIMARY);
+    }
+
+    #[tokio::test]
+    async fn test_rejections_do_not_fail_over() {
+        let mut mock = MockOrchestrator::new();
+        mock.expect_get_node().returning(|_| {
+            Err(OrchestratorError::Http {
+                status: 429,
+                message: String::new(),
+                headers: Default::default(),
+            })
+        });
+        let orchestrator = FailoverOrchestrator::with_endpoints(
+            Environment::default(),
+            vec![
+                Endpoint {
+                    url: PRIMARY.to_string(),
+                    client: Arc::new(mock),
+                },
+                endpoint(FALLBACK, Arc::new(AtomicBool::new(false))),
+            ],
+            |_| async { true },
+        );
+        for _ in 0..FAILOVER_AFTER_FAILURES * 2 {
+            assert!(orchestrator.get_node("1").await.is_err());
+        }
+        assert_eq!(orchestrator.active_url(), PRIMARY);
+    }
+
+    #[test]
+    fn test_fallback_urls_by_environment() {
+        let settings: OrchestratorSettings = serde_json::from_str(
+            r#"{"fallback_urls": {"production": ["https://backup.example"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            settings.fallback_urls(&Environment::Production),
+            vec!["https://backup.example".to_string()]
+        );
+        assert!(
+            settings
+                .fallback_urls(&Environment::Custom {
+                    orchestrator_url: "http://localhost:8080".to_string()
+                })
+                .is_empty()
+        );
+    }
+}
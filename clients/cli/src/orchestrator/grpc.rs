@@ -0,0 +1,663 @@
+//! gRPC transport for the Nexus Orchestrator
+//!
+//! An alternative to the protobuf-over-HTTP client, selected with `--transport grpc`. All
+//! requests share a single HTTP/2 channel that is kept alive between tasks. Proof tasks are
+//! received over the server-streaming `StreamProofTasks` RPC where the server supports it,
+//! falling back to unary `GetProofTask` requests otherwise.
+//!
+//! Orchestrators without the gRPC service answer every call with UNIMPLEMENTED. A call answered
+//! that way is made again over HTTP, and once HTTP serves a call the gRPC service lacks, the
+//! client sticks to HTTP.
+
+use crate::consts::cli_consts::grpc::{
+    CONNECT_TIMEOUT_SECS, KEEP_ALIVE_INTERVAL_SECS, KEEP_ALIVE_TIMEOUT_SECS, REQUEST_TIMEOUT_SECS,
+};
+use crate::environment::Environment;
+use crate::network::ProofSubmission;
+use crate::nexus_orchestrator::{
+    DeclineProofTaskRequest, DeclineProofTaskResponse, DeregisterNodeResponse,
+    GetNetworkStatsRequest, GetNetworkStatsResponse, GetNodePointsRequest, GetNodePointsResponse,
+    GetNodeRequest, GetNodeResponse, GetProofTaskRequest, GetProofTaskResponse,
+    GetTaskReceiptsRequest, GetTaskReceiptsResponse, GetUserRequest, PreviewProofTaskResponse,
+    RegisterNodeResponse, RegisterUserRequest, RegisterUserResponse, SubmitProofResponse,
+    SubmitProofResult, SubmitProofsBatchResponse, TaskDifficulty, TaskReceipt, TaskType,
+    UserResponse,
+};
+use crate::orchestrator::Orchestrator;
+use crate::orchestrator::client::{
+    BUILD_TIMESTAMP, OrchestratorClient, ProofTaskResult, USER_AGENT, detect_country_once,
+};
+use crate::orchestrator::error::OrchestratorError;
+use crate::orchestrator::signing;
+use crate::task::TaskDescriptor;
+use bytes::Bytes;
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tonic::codec::{ProstCodec, Streaming};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::MetadataValue;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use tonic::{Code, Status};
+
+const GET_USER: &str = "/nexus.orchestrator.Orchestrator/GetUser";
+const REGISTER_USER: &str = "/nexus.orchestrator.Orchestrator/RegisterUser";
+const REGISTER_NODE: &str = "/nexus.orchestrator.Orchestrator/RegisterNode";
+const DEREGISTER_NODE: &str = "/nexus.orchestrator.Orchestrator/DeregisterNode";
+const GET_NODE: &str = "/nexus.orchestrator.Orchestrator/GetNode";
+const GET_NODE_POINTS: &str = "/nexus.orchestrator.Orchestrator/GetNodePoints";
+const GET_NETWORK_STATS: &str = "/nexus.orchestrator.Orchestrator/GetNetworkStats";
+const GET_PROOF_TASK: &str = "/nexus.orchestrator.Orchestrator/GetProofTask";
+const PREVIEW_PROOF_TASK: &str = "/nexus.orchestrator.Orchestrator/PreviewProofTask";
+const DECLINE_PROOF_TASK: &str = "/nexus.orchestrator.Orchestrator/DeclineProofTask";
+const STREAM_PROOF_TASKS: &str = "/nexus.orchestrator.Orchestrator/StreamProofTasks";
+const SUBMIT_PROOF: &str = "/nexus.orchestrator.Orchestrator/SubmitProof";
+const SUBMIT_PROOFS_BATCH: &str = "/nexus.orchestrator.Orchestrator/SubmitProofsBatch";
+const GET_TASK_RECEIPTS: &str = "/nexus.orchestrator.Orchestrator/GetTaskReceipts";
+
+/// An open `StreamProofTasks` call and the request it was opened with
+struct TaskStream {
+    request: GetProofTaskRequest,
+    stream: Streaming<GetProofTaskResponse>,
+}
+
+impl std::fmt::Debug for TaskStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskStream")
+            .field("node_id", &self.request.node_id)
+            .field("max_difficulty", &self.request.max_difficulty)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GrpcOrchestratorClient {
+    channel: Channel,
+    environment: Environment,
+    /// Task stream shared by clones of this client, opened on first use
+    task_stream: Arc<Mutex<Option<TaskStream>>>,
+    /// Set once the server answers `StreamProofTasks` with UNIMPLEMENTED
+    streaming_unsupported: Arc<AtomicBool>,
+    /// Protobuf-over-HTTP client for calls the server does not serve over gRPC
+    http: OrchestratorClient,
+    /// Set once a call unsupported over gRPC succeeded over HTTP
+    http_only: Arc<AtomicBool>,
+}
+
+impl GrpcOrchestratorClient {
+    /// Create a client for the environment's orchestrator. The connection is established
+    /// lazily on the first request and re-established automatically if it drops.
+    pub fn new(environment: Environment) -> Result<Self, OrchestratorError> {
+        let url = environment.orchestrator_url().to_string();
+        let transport_error =
+            |e: tonic::transport::Error| OrchestratorError::Transport(e.to_string());
+
+        let mut endpoint = Endpoint::from_shared(url.clone())
+            .map_err(transport_error)?
+            .user_agent(USER_AGENT)
+            .map_err(transport_error)?
+            .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+            .tcp_keepalive(Some(Duration::from_secs(KEEP_ALIVE_INTERVAL_SECS)))
+            .http2_keep_alive_interval(Duration::from_secs(KEEP_ALIVE_INTERVAL_SECS))
+            .keep_alive_timeout(Duration::from_secs(KEEP_ALIVE_TIMEOUT_SECS))
+            .keep_alive_while_idle(true);
+        if url.starts_with("https://") {
+            endpoint = endpoint
+                .tls_config(ClientTlsConfig::new().with_webpki_roots())
+                .map_err(transport_error)?;
+        }
+
+        Ok(Self {
+            channel: endpoint.connect_lazy(),
+            http: OrchestratorClient::new(environment.clone()),
+            environment,
+            task_stream: Arc::new(Mutex::new(None)),
+            streaming_unsupported: Arc::new(AtomicBool::new(false)),
+            http_only: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Make a call over gRPC, or over HTTP if the server does not serve it over gRPC.
+    async fn or_http<T>(
+        &self,
+        grpc: impl Future<Output = Result<T, OrchestratorError>>,
+        http: impl Future<Output = Result<T, OrchestratorError>>,
+    ) -> Result<T, OrchestratorError> {
+        if self.http_only.load(Ordering::Relaxed) {
+            return http.await;
+        }
+        match grpc.await {
+            Err(e) if e.is_unsupported() => {
+                let result = http.await;
+                // Endpoints missing from both transports say nothing about the gRPC service
+                if !matches!(&result, Err(e) if e.is_unsupported()) {
+                    self.http_only.store(true, Ordering::Relaxed);
+                }
+                result
+            }
+            result => result,
+        }
+    }
+
+    fn request<T: prost::Message>(
+        method: &str,
+        message: T,
+        timeout: Option<Duration>,
+    ) -> tonic::Request<T> {
+        let signature = signing::request_signing_enabled()
+            .then(|| signing::sign_request("POST", method, &message.encode_to_vec()))
+            .flatten();
+        let mut request = tonic::Request::new(message);
+        request.metadata_mut().insert(
+            "x-build-timestamp",
+            MetadataValue::from_static(BUILD_TIMESTAMP),
+        );
+        for (key, value) in signature.into_iter().flatten() {
+            if let Ok(value) = value.parse() {
+                request.metadata_mut().insert(key, value);
+            }
+        }
+        if let Some(timeout) = timeout {
+            request.set_timeout(timeout);
+        }
+        request
+    }
+
+    async fn ready(&self) -> Result<tonic::client::Grpc<Channel>, Status> {
+        let mut grpc = tonic::client::Grpc::new(self.channel.clone());
+        grpc.ready()
+            .await
+            .map_err(|e| Status::unavailable(format!("gRPC channel not ready: {}", e)))?;
+        Ok(grpc)
+    }
+
+    async fn unary<Req, Resp>(
+        &self,
+        method: &'static str,
+        message: Req,
+    ) -> Result<Resp, OrchestratorError>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        Ok(self.call(method, message).await?)
+    }
+
+    /// Like [`Self::unary`], but leaves the status for the caller to interpret.
+    async fn call<Req, Resp>(&self, method: &'static str, message: Req) -> Result<Resp, Status>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let mut grpc = self.ready().await?;
+        let path = PathAndQuery::from_static(method);
+        let request = Self::request(
+            method,
+            message,
+            Some(Duration::from_secs(REQUEST_TIMEOUT_SECS)),
+        );
+        let response = grpc
+            .unary(request, path, ProstCodec::<Req, Resp>::default())
+            .await?;
+        if let Some(date) = response
+            .metadata()
+            .get("date")
+            .and_then(|value| value.to_str().ok())
+        {
+            crate::clock::observe_date_header(date);
+        }
+        Ok(response.into_inner())
+    }
+
+    /// Next task pushed over the task stream, opening (or re-opening, if the request changed)
+    /// the stream as needed. Returns `Ok(None)` if the server closed the stream.
+    async fn next_streamed_task(
+        &self,
+        request: &GetProofTaskRequest,
+    ) -> Result<Option<GetProofTaskResponse>, Status> {
+        let mut guard = self.task_stream.lock().await;
+        if guard.as_ref().is_none_or(|open| open.request != *request) {
+            let mut grpc = self.ready().await?;
+            let path = PathAndQuery::from_static(STREAM_PROOF_TASKS);
+            let stream = grpc
+                .server_streaming(
+                    Self::request(STREAM_PROOF_TASKS, request.clone(), None),
+                    path,
+                    ProstCodec::<GetProofTaskRequest, GetProofTaskResponse>::default(),
+                )
+                .await?
+                .into_inner();
+            *guard = Some(TaskStream {
+                request: request.clone(),
+                stream,
+            });
+        }
+
+        let Some(open) = guard.as_mut() else {
+            return Ok(None);
+        };
+        let result = open.stream.message().await;
+        if !matches!(result, Ok(Some(_))) {
+            // Closed or broken streams are re-opened on the next fetch
+            *guard = None;
+        }
+        result
+    }
+}
+
+#[async_trait::async_trait]
+impl Orchestrator for GrpcOrchestratorClient {
+    fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    async fn get_user(&self, wallet_address: &str) -> Result<String, OrchestratorError> {
+        let request = GetUserRequest {
+            wallet_address: wallet_address.to_string(),
+            ..Default::default()
+        };
+        let grpc = async {
+            self.unary(GET_USER, request)
+                .await
+                .map(|response: UserResponse| response.user_id)
+        };
+        self.or_http(grpc, self.http.get_user(wallet_address)).await
+    }
+
+    async fn get_user_nodes(
+        &self,
+        wallet_address: &str,
+        nodes_cursor: &str,
+    ) -> Result<UserResponse, OrchestratorError> {
+        let request = GetUserRequest {
+            wallet_address: wallet_address.to_string(),
+            nodes_cursor: nodes_cursor.to_string(),
+        };
+        self.or_http(
+            self.unary(GET_USER, request),
+            self.http.get_user_nodes(wallet_address, nodes_cursor),
+        )
+        .await
+    }
+
+    async fn register_user(
+        &self,
+        user_id: &str,
+        wallet_address: &str,
+    ) -> Result<(), OrchestratorError> {
+        let request = RegisterUserRequest {
+            uuid: user_id.to_string(),
+            wallet_address: wallet_address.to_string(),
+        };
+        let grpc = async {
+            self.unary(REGISTER_USER, request)
+                .await
+                .map(|_: RegisterUserResponse| ())
+        };
+        self.or_http(grpc, self.http.register_user(user_id, wallet_address))
+            .await
+    }
+
+    async fn register_node(
+        &self,
+        user_id: &str,
+        verifying_key: VerifyingKey,
+    ) -> Result<String, OrchestratorError> {
+        let request = OrchestratorClient::register_node_request(user_id, verifying_key);
+        let grpc = async {
+            self.unary(REGISTER_NODE, request)
+                .await
+                .map(|response: RegisterNodeResponse| response.node_id)
+        };
+        self.or_http(grpc, self.http.register_node(user_id, verifying_key))
+            .await
+    }
+
+    async fn deregister_node(
+        &self,
+        node_id: &str,
+        signing_key: SigningKey,
+    ) -> Result<(), OrchestratorError> {
+        let request = OrchestratorClient::deregister_node_request(node_id, &signing_key);
+        let grpc = async {
+            self.unary(DEREGISTER_NODE, request)
+                .await
+                .map(|_: DeregisterNodeResponse| ())
+        };
+        self.or_http(grpc, self.http.deregister_node(node_id, signing_key))
+            .await
+    }
+
+    async fn get_node(&self, node_id: &str) -> Result<String, OrchestratorError> {
+        let request = GetNodeRequest {
+            node_id: node_id.to_string(),
+        };
+        let grpc = async {
+            self.unary(GET_NODE, request)
+                .await
+                .map(|response: GetNodeResponse| response.wallet_address)
+        };
+        self.or_http(grpc, self.http.get_node(node_id)).await
+    }
+
+    async fn get_node_points(
+        &self,
+        node_id: &str,
+    ) -> Result<GetNodePointsResponse, OrchestratorError> {
+        let request = GetNodePointsRequest {
+            node_id: node_id.to_string(),
+        };
+        self.or_http(
+            self.unary(GET_NODE_POINTS, request),
+            self.http.get_node_points(node_id),
+        )
+        .await
+    }
+
+    async fn get_network_stats(
+        &self,
+        node_id: &str,
+    ) -> Result<GetNetworkStatsResponse, OrchestratorError> {
+        let request = GetNetworkStatsRequest {
+            node_id: node_id.to_string(),
+        };
+        self.or_http(
+            self.unary(GET_NETWORK_STATS, request),
+            self.http.get_network_stats(node_id),
+        )
+        .await
+    }
+
+    async fn get_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: TaskDifficulty,
+    ) -> Result<ProofTaskResult, OrchestratorError> {
+        let request =
+            OrchestratorClient::proof_task_request(node_id, verifying_key, max_difficulty);
+        let grpc = async {
+            if !self.streaming_unsupported.load(Ordering::Relaxed) {
+                match self.next_streamed_task(&request).await {
+                    Ok(Some(response)) => return ProofTaskResult::try_from(&response),
+                    // The server ended the stream; fall back to a unary request this time
+                    Ok(None) => {}
+                    Err(status) if status.code() == Code::Unimplemented => {
+                        self.streaming_unsupported.store(true, Ordering::Relaxed);
+                    }
+                    Err(status) => return Err(status.into()),
+                }
+            }
+            self.unary(GET_PROOF_TASK, request)
+                .await
+                .and_then(|response: GetProofTaskResponse| ProofTaskResult::try_from(&response))
+        };
+        self.or_http(
+            grpc,
+            self.http
+                .get_proof_task(node_id, verifying_key, max_difficulty),
+        )
+        .await
+    }
+
+    async fn subscribe_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: TaskDifficulty,
+        wait: Duration,
+    ) -> Result<Option<ProofTaskResult>, OrchestratorError> {
+        if self.http_only.load(Ordering::Relaxed) {
+            return self
+                .http
+                .subscribe_proof_task(node_id, verifying_key, max_difficulty, wait)
+                .await;
+        }
+        // Tasks already arrive over `StreamProofTasks`; just bound the wait for the next one
+        match tokio::time::timeout(
+            wait,
+            self.get_proof_task(node_id, verifying_key, max_difficulty),
+        )
+        .await
+        {
+            Ok(result) => result.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    async fn preview_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: TaskDifficulty,
+    ) -> Result<TaskDescriptor, OrchestratorError> {
+        let request =
+            OrchestratorClient::proof_task_request(node_id, verifying_key, max_difficulty);
+        let grpc = async {
+            self.unary(PREVIEW_PROOF_TASK, request)
+                .await
+                .and_then(|response: PreviewProofTaskResponse| TaskDescriptor::try_from(&response))
+        };
+        self.or_http(
+            grpc,
+            self.http
+                .preview_proof_task(node_id, verifying_key, max_difficulty),
+        )
+        .await
+    }
+
+    async fn take_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: TaskDifficulty,
+        offer_id: &str,
+    ) -> Result<ProofTaskResult, OrchestratorError> {
+        // Offered tasks are taken with a unary request, never from the task stream
+        let request = GetProofTaskRequest {
+            offer_id: offer_id.to_string(),
+            ..OrchestratorClient::proof_task_request(node_id, verifying_key, max_difficulty)
+        };
+        let grpc = async {
+            self.unary(GET_PROOF_TASK, request)
+                .await
+                .and_then(|response: GetProofTaskResponse| ProofTaskResult::try_from(&response))
+        };
+        self.or_http(
+            grpc,
+            self.http
+                .take_proof_task(node_id, verifying_key, max_difficulty, offer_id),
+        )
+        .await
+    }
+
+    async fn decline_proof_task(
+        &self,
+        node_id: &str,
+        offer_id: &str,
+        reason: &str,
+    ) -> Result<(), OrchestratorError> {
+        let request = DeclineProofTaskRequest {
+            node_id: node_id.to_string(),
+            offer_id: offer_id.to_string(),
+            reason: reason.to_string(),
+        };
+        let grpc = async {
+            self.unary(DECLINE_PROOF_TASK, request)
+                .await
+                .map(|_: DeclineProofTaskResponse| ())
+        };
+        self.or_http(
+            grpc,
+            self.http.decline_proof_task(node_id, offer_id, reason),
+        )
+        .await
+    }
+
+    async fn submit_proof(
+        &self,
+        task_id: &str,
+        proof_hash: &str,
+        proof: Bytes,
+        proofs: Vec<Bytes>,
+        signing_key: SigningKey,
+        num_provers: usize,
+        task_type: TaskType,
+        individual_proof_hashes: &[String],
+        nonce: u64,
+    ) -> Result<(), OrchestratorError> {
+        let grpc = async {
+            let location = detect_country_once().await;
+            let request = OrchestratorClient::submit_proof_request(
+                task_id,
+                proof_hash,
+                proof.clone(),
+                proofs.clone(),
+                &signing_key,
+                num_provers,
+                task_type,
+                individual_proof_hashes,
+                location,
+                nonce,
+            );
+            self.call(SUBMIT_PROOF, request)
+                .await
+                .map(|_: SubmitProofResponse| ())
+                .map_err(submission_error)
+        };
+        self.or_http(
+            grpc,
+            self.http.submit_proof(
+                task_id,
+                proof_hash,
+                proof.clone(),
+                proofs.clone(),
+                signing_key.clone(),
+                num_provers,
+                task_type,
+                individual_proof_hashes,
+                nonce,
+            ),
+        )
+        .await
+    }
+
+    async fn submit_proofs_batch(
+        &self,
+        submissions: &[ProofSubmission],
+        signing_key: SigningKey,
+        num_provers: usize,
+    ) -> Result<Vec<SubmitProofResult>, OrchestratorError> {
+        let grpc = async {
+            let location = detect_country_once().await;
+            let request = OrchestratorClient::submit_proofs_batch_request(
+                submissions,
+                &signing_key,
+                num_provers,
+                location,
+            );
+            self.unary(SUBMIT_PROOFS_BATCH, request)
+                .await
+                .map(|response: SubmitProofsBatchResponse| response.results)
+        };
+        self.or_http(
+            grpc,
+            self.http
+                .submit_proofs_batch(submissions, signing_key.clone(), num_provers),
+        )
+        .await
+    }
+
+    async fn get_task_receipts(
+        &self,
+        node_id: &str,
+        task_ids: &[String],
+    ) -> Result<Vec<TaskReceipt>, OrchestratorError> {
+        let request = GetTaskReceiptsRequest {
+            node_id: node_id.to_string(),
+            task_ids: task_ids.to_vec(),
+        };
+        let grpc = async {
+            self.unary(GET_TASK_RECEIPTS, request)
+                .await
+                .map(|response: GetTaskReceiptsResponse| response.receipts)
+        };
+        self.or_http(grpc, self.http.get_task_receipts(node_id, task_ids))
+            .await
+    }
+}
+
+/// Error for a failed submission; only `ALREADY_EXISTS` means the proof was already accepted,
+/// other conflicts such as `ABORTED` are errors like any other.
+fn submission_error(status: Status) -> OrchestratorError {
+    match status.code() {
+        Code::AlreadyExists => OrchestratorError::AlreadyAccepted,
+        _ => status.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::error_handler::ErrorHandler;
+
+    #[tokio::test]
+    async fn test_unreachable_server_is_retryable() {
+        // Nothing listens on port 9 (discard); the channel connects lazily and fails per request
+        let client = GrpcOrchestratorClient::new(Environment::Custom {
+            orchestrator_url: "http://127.0.0.1:9".to_string(),
+        })
+        .unwrap();
+        let error = client.get_node("1").await.unwrap_err();
+        assert!(ErrorHandler::new().should_retry(&error));
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_http_for_calls_unsupported_over_grpc() {
+        async fn unimplemented<T>() -> Result<T, OrchestratorError> {
+            Err(Status::unimplemented("no gRPC").into())
+        }
+        async fn answer(value: u32) -> Result<u32, OrchestratorError> {
+            Ok(value)
+        }
+        async fn not_called() -> Result<u32, OrchestratorError> {
+            panic!("gRPC called after falling back to HTTP")
+        }
+        let client = GrpcOrchestratorClient::new(Environment::Custom {
+            orchestrator_url: "http://127.0.0.1:9".to_string(),
+        })
+        .unwrap();
+
+        // An endpoint missing over both transports leaves gRPC in use
+        let result = client
+            .or_http(unimplemented::<u32>(), unimplemented())
+            .await;
+        assert!(result.unwrap_err().is_unsupported());
+        assert!(!client.http_only.load(Ordering::Relaxed));
+
+        let result = client.or_http(unimplemented(), answer(7)).await;
+        assert_eq!(result.unwrap(), 7);
+        assert!(client.http_only.load(Ordering::Relaxed));
+
+        // From then on, calls go straight to HTTP
+        let result = client.or_http(not_called(), answer(8)).await;
+        assert_eq!(result.unwrap(), 8);
+    }
+
+    #[test]
+    fn test_only_already_exists_is_already_accepted() {
+        assert!(submission_error(Status::already_exists("duplicate")).is_already_accepted());
+        assert!(!submission_error(Status::aborted("conflict")).is_already_accepted());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_url() {
+        let result = GrpcOrchestratorClient::new(Environment::Custom {
+            orchestrator_url: "not a url".to_string(),
+        });
+        assert!(matches!(result, Err(OrchestratorError::Transport(_))));
+    }
+}
@@ -0,0 +1,881 @@
+//! Built-in mock orchestrator
+//!
+//! `nexus-cli start --mock-orchestrator` serves the orchestrator's HTTP API from inside the
+//! process, so that the whole fetch, prove and submit pipeline can run offline and in CI. Tasks
+//! are small Fibonacci programs with random initial values, so proofs are rarely reused from the
+//! proof cache, and every proof is accepted, once per idempotency key; a repeated submission is
+//! answered with a 409 naming the `ProofAlreadyAccepted` error. To exercise the error paths, any
+//! response can be delayed or answered with a 429, and task requests can return malformed tasks,
+//! at rates given by a spec such as `latency_ms=200,rate_limits=0.1`.
+//!
+//! With `script=<FILE>`, the mock first hands out the tasks listed in `FILE`, in order, before
+//! falling back to random ones. The script is a JSON array of tasks:
+//!
+//! ```json
+//! [
+//!   { "task_id": "multi", "inputs": [[9, 1, 1], [10, 2, 3]] },
+//!   { "inputs": [[11, 1, 1]], "task_type": "PROOF_HASH" },
+//!   { "program_id": "unknown", "inputs": [[9, 1, 1]] }
+//! ]
+//! ```
+
+use crate::chaos::{parse_rate, parse_secs};
+use crate::nexus_orchestrator::{
+    DeclineProofTaskRequest, DeclineProofTaskResponse, DeregisterNodeRequest,
+    DeregisterNodeResponse, DifficultySupply, GetNetworkStatsResponse, GetNodePointsResponse,
+    GetNodeResponse, GetProofTaskRequest, GetProofTaskResponse, GetTaskReceiptsRequest,
+    GetTaskReceiptsResponse, Node, NodeType, PreviewProofTaskResponse, RegisterNodeRequest,
+    RegisterNodeResponse, SubmitProofRequest, SubmitProofResult, SubmitProofsBatchRequest,
+    SubmitProofsBatchResponse, Task, TaskDifficulty, TaskOffer, TaskReceipt, TaskReceiptState,
+    TaskType, UserResponse,
+};
+use crate::orchestrator::error::ALREADY_ACCEPTED;
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::{Path, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use prost::Message;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path as FilePath, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+/// Program ID of the tasks handed out by the mock
+const MOCK_PROGRAM_ID: &str = "fib_input_initial";
+
+/// Points credited for each accepted proof
+const POINTS_PER_PROOF: u64 = 100;
+
+/// Upper bound for the initial values of random tasks, low enough that no sequence overflows
+const MAX_INITIAL_VALUE: u32 = 1000;
+
+/// Latencies and fault rates of the mock orchestrator
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockConfig {
+    /// Delay added to every response
+    pub latency: Duration,
+    /// Upper bound for a random delay added on top of `latency`
+    pub jitter: Duration,
+    /// Probability that a request is answered with 429 Too Many Requests
+    pub rate_limit_rate: f64,
+    /// `Retry-After` sent with injected 429s
+    pub retry_after: Duration,
+    /// Probability that a task request returns a malformed task
+    pub malformed_task_rate: f64,
+    /// Tasks to hand out, in order, before random ones
+    pub script: Option<PathBuf>,
+    /// Number of inputs of random tasks (default: one to three)
+    pub inputs_per_task: Option<usize>,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            latency: Duration::from_millis(50),
+            jitter: Duration::ZERO,
+            rate_limit_rate: 0.0,
+            retry_after: Duration::from_secs(5),
+            malformed_task_rate: 0.0,
+            script: None,
+            inputs_per_task: None,
+        }
+    }
+}
+
+impl FromStr for MockConfig {
+    type Err = String;
+
+    /// Parse a comma-separated list of `key=value` overrides, e.g.
+    /// `latency_ms=200,rate_limits=0.1`. The literal `default` keeps all defaults.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = MockConfig::default();
+        let s = s.trim();
+        if s.is_empty() || s.eq_ignore_ascii_case("default") {
+            return Ok(config);
+        }
+
+        for pair in s.split(',') {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=value, got '{}'", pair))?;
+            let key = key.trim();
+            let value = value.trim();
+            match key {
+                "latency_ms" => config.latency = parse_millis(key, value)?,
+                "jitter_ms" => config.jitter = parse_millis(key, value)?,
+                "rate_limits" => config.rate_limit_rate = parse_rate(key, value)?,
+                "retry_after_secs" => config.retry_after = parse_secs(key, value)?,
+                "malformed_tasks" => config.malformed_task_rate = parse_rate(key, value)?,
+                "script" => config.script = Some(PathBuf::from(value)),
+                "inputs" => match value.parse::<usize>() {
+                    Ok(inputs) if inputs > 0 => config.inputs_per_task = Some(inputs),
+                    _ => return Err(format!("{} must be a positive whole number", key)),
+                },
+                _ => return Err(format!("Unknown mock orchestrator option '{}'", key)),
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn parse_millis(key: &str, value: &str) -> Result<Duration, String> {
+    value
+        .parse::<u64>()
+        .map(Duration::from_millis)
+        .map_err(|_| format!("{} must be a whole number of milliseconds", key))
+}
+
+/// Ways in which a task handed out by the mock can be broken
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Malformation {
+    /// A response body that is not a valid protobuf message
+    Undecodable,
+    /// A program the prover does not know
+    UnknownProgram,
+    /// Inputs shorter than the three `u32`s the Fibonacci program expects
+    TruncatedInputs,
+    /// A task without any inputs
+    NoInputs,
+}
+
+impl Malformation {
+    const ALL: [Malformation; 4] = [
+        Malformation::Undecodable,
+        Malformation::UnknownProgram,
+        Malformation::TruncatedInputs,
+        Malformation::NoInputs,
+    ];
+
+    fn random() -> Self {
+        Self::ALL[rand::random::<usize>() % Self::ALL.len()]
+    }
+}
+
+/// A task listed in a script
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScriptedTask {
+    /// Defaults to `mock-<n>` like random tasks
+    task_id: Option<String>,
+    #[serde(default = "default_program_id")]
+    program_id: String,
+    /// Input triples `(n, init_a, init_b)`
+    inputs: Vec<(u32, u32, u32)>,
+    /// Protobuf name of the task type, e.g. `PROOF_HASH`
+    #[serde(default = "default_task_type")]
+    task_type: String,
+}
+
+fn default_program_id() -> String {
+    MOCK_PROGRAM_ID.to_string()
+}
+
+fn default_task_type() -> String {
+    TaskType::ProofRequired.as_str_name().to_string()
+}
+
+/// Read the tasks listed in the script at `path`.
+fn load_script(path: &FilePath) -> std::io::Result<VecDeque<ScriptedTask>> {
+    let invalid = |message: String| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), message),
+        )
+    };
+    let tasks: Vec<ScriptedTask> =
+        serde_json::from_slice(&std::fs::read(path)?).map_err(|e| invalid(e.to_string()))?;
+    if let Some(task) = tasks
+        .iter()
+        .find(|task| TaskType::from_str_name(&task.task_type).is_none())
+    {
+        return Err(invalid(format!("unknown task type '{}'", task.task_type)));
+    }
+    Ok(tasks.into())
+}
+
+/// State shared by the request handlers
+struct MockState {
+    config: MockConfig,
+    /// Scripted tasks not handed out yet
+    script: Mutex<VecDeque<ScriptedTask>>,
+    next_task: AtomicU64,
+    next_node: AtomicU64,
+    accepted_proofs: AtomicU64,
+    /// Idempotency keys of accepted submissions
+    accepted_keys: Mutex<HashSet<String>>,
+    /// Keys bound to registered nodes, by node ID
+    nodes: Mutex<HashMap<String, NodeBinding>>,
+}
+
+/// The key bound to a node at registration
+struct NodeBinding {
+    key: ed25519_dalek::VerifyingKey,
+    /// Timestamp of the last deregistration accepted for the node
+    deregistered_at_ms: u64,
+}
+
+impl MockState {
+    fn lock_nodes(&self) -> std::sync::MutexGuard<'_, HashMap<String, NodeBinding>> {
+        self.nodes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Count a submission, unless one with the same idempotency key was accepted before.
+    fn accept(&self, submission: &SubmitProofRequest) -> bool {
+        let mut keys = self.accepted_keys.lock().unwrap_or_else(|e| e.into_inner());
+        if !submission.idempotency_key.is_empty()
+            && !keys.insert(submission.idempotency_key.clone())
+        {
+            return false;
+        }
+        self.accepted_proofs.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+}
+
+/// Roll the dice for a fault with probability `rate`
+fn roll(rate: f64) -> bool {
+    rate > 0.0 && rand::random::<f64>() < rate
+}
+
+/// Serve the mock orchestrator on a free local port and return its URL.
+///
+/// The server runs in the background until the Tokio runtime shuts down.
+pub async fn start_mock_orchestrator(config: MockConfig) -> std::io::Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    let script = match &config.script {
+        Some(path) => load_script(path)?,
+        None => VecDeque::new(),
+    };
+    let state = Arc::new(MockState {
+        config,
+        script: Mutex::new(script),
+        next_task: AtomicU64::new(1),
+        next_node: AtomicU64::new(1),
+        accepted_proofs: AtomicU64::new(0),
+        accepted_keys: Mutex::new(HashSet::new()),
+        nodes: Mutex::new(HashMap::new()),
+    });
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router(state)).await;
+    });
+    Ok(url)
+}
+
+fn router(state: Arc<MockState>) -> Router {
+    Router::new()
+        .route("/v3/users", post(register_user))
+        .route("/v3/users/{wallet_address}", get(get_user))
+        .route("/v3/nodes", post(register_node))
+        .route("/v3/nodes/{node_id}", get(get_node))
+        .route("/v3/nodes/{node_id}/points", get(get_node_points))
+        .route("/v3/nodes/{node_id}/deregister", post(deregister_node))
+        .route("/v3/network/stats", get(get_network_stats))
+        .route("/v3/tasks", post(get_proof_task))
+        .route("/v3/tasks/subscribe", post(subscribe_proof_task))
+        .route("/v3/tasks/preview", post(preview_proof_task))
+        .route("/v3/tasks/decline", post(decline_proof_task))
+        .route("/v3/tasks/submit", post(submit_proof))
+        .route("/v3/tasks/submit/batch", post(submit_proofs_batch))
+        .route("/v3/tasks/receipts", post(get_task_receipts))
+        .route_layer(middleware::from_fn_with_state(state.clone(), inject_faults))
+        .with_state(state)
+}
+
+/// Delay every response and answer some requests with 429 Too Many Requests.
+async fn inject_faults(
+    State(state): State<Arc<MockState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let config = &state.config;
+    let jitter_ms = config.jitter.as_millis() as u64;
+    let jitter = if jitter_ms > 0 {
+        Duration::from_millis(rand::random::<u64>() % (jitter_ms + 1))
+    } else {
+        Duration::ZERO
+    };
+    tokio::time::sleep(config.latency + jitter).await;
+
+    if roll(config.rate_limit_rate) {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(
+                header::RETRY_AFTER,
+                config.retry_after.as_secs().to_string(),
+            )],
+            "Rate limited by mock orchestrator",
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
+/// Protobuf response body
+fn proto<T: Message>(message: T) -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/octet-stream")],
+        message.encode_to_vec(),
+    )
+        .into_response()
+}
+
+fn bad_request(message: &str) -> Response {
+    (StatusCode::BAD_REQUEST, message.to_string()).into_response()
+}
+
+async fn register_user() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Every user owns all nodes registered so far, on a single page.
+async fn get_user(
+    State(state): State<Arc<MockState>>,
+    Path(wallet_address): Path<String>,
+) -> Response {
+    let nodes = (1..state.next_node.load(Ordering::Relaxed))
+        .map(|node_id| Node {
+            node_id: node_id.to_string(),
+            node_type: NodeType::CliProver as i32,
+            ..Default::default()
+        })
+        .collect();
+    proto(UserResponse {
+        nodes,
+        user_id: format!("mock-user-{}", wallet_address.to_lowercase()),
+        wallet_address,
+        ..Default::default()
+    })
+}
+
+/// Binds the key in the request to the new node.
+async fn register_node(State(state): State<Arc<MockState>>, body: Bytes) -> Response {
+    let Ok(request) = RegisterNodeRequest::decode(body) else {
+        return bad_request("Invalid RegisterNodeRequest");
+    };
+    let Some(key) = verifying_key(&request.ed25519_public_key) else {
+        return bad_request("Invalid ed25519 public key");
+    };
+    let node_id = state.next_node.fetch_add(1, Ordering::Relaxed).to_string();
+    let binding = NodeBinding {
+        key,
+        deregistered_at_ms: 0,
+    };
+    state.lock_nodes().insert(node_id.clone(), binding);
+    proto(RegisterNodeResponse { node_id })
+}
+
+/// Accepts deregistrations signed by the key bound to the node, timestamped within
+/// [`DEREGISTRATION_WINDOW_MS`] of now and after the last accepted one.
+async fn deregister_node(
+    State(state): State<Arc<MockState>>,
+    Path(node_id): Path<String>,
+    body: Bytes,
+) -> Response {
+    let Ok(request) = DeregisterNodeRequest::decode(body) else {
+        return bad_request("Invalid DeregisterNodeRequest");
+    };
+    let mut nodes = state.lock_nodes();
+    let Some(binding) = nodes.get_mut(&node_id) else {
+        return (StatusCode::NOT_FOUND, "Unknown node").into_response();
+    };
+    let message = format!(
+        "deregister | {} | {}",
+        request.node_id, request.timestamp_ms
+    );
+    let verified = verifying_key(&request.ed25519_public_key) == Some(binding.key)
+        && ed25519_dalek::Signature::from_slice(&request.signature).is_ok_and(|signature| {
+            binding
+                .key
+                .verify_strict(message.as_bytes(), &signature)
+                .is_ok()
+        });
+    if request.node_id != node_id || !verified {
+        return (StatusCode::UNAUTHORIZED, "Invalid deregistration signature").into_response();
+    }
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+    if request.timestamp_ms.abs_diff(now_ms) > DEREGISTRATION_WINDOW_MS
+        || request.timestamp_ms <= binding.deregistered_at_ms
+    {
+        return (StatusCode::UNAUTHORIZED, "Stale or replayed deregistration").into_response();
+    }
+    binding.deregistered_at_ms = request.timestamp_ms;
+    proto(DeregisterNodeResponse {})
+}
+
+/// How far a deregistration's timestamp may be off the mock's clock
+const DEREGISTRATION_WINDOW_MS: u64 = 5 * 60 * 1000;
+
+fn verifying_key(bytes: &[u8]) -> Option<ed25519_dalek::VerifyingKey> {
+    let bytes = <[u8; 32]>::try_from(bytes).ok()?;
+    ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok()
+}
+
+async fn get_node(Path(_node_id): Path<String>) -> Response {
+    proto(GetNodeResponse {
+        wallet_address: "0x0000000000000000000000000000000000000000".to_string(),
+    })
+}
+
+async fn get_node_points(
+    State(state): State<Arc<MockState>>,
+    Path(_node_id): Path<String>,
+) -> Response {
+    let points = state.accepted_proofs.load(Ordering::Relaxed) * POINTS_PER_PROOF;
+    proto(GetNodePointsResponse {
+        node_points: points,
+        wallet_points: points,
+    })
+}
+
+async fn get_network_stats(State(state): State<Arc<MockState>>) -> Response {
+    // Every registered node is an active prover, and the node asking leads the ranking
+    let nodes = state
+        .next_node
+        .load(Ordering::Relaxed)
+        .saturating_sub(1)
+        .max(1);
+    let task_supply = [
+        TaskDifficulty::Small,
+        TaskDifficulty::Medium,
+        TaskDifficulty::Large,
+    ]
+    .into_iter()
+    .map(|difficulty| DifficultySupply {
+        difficulty: difficulty as i32,
+        pending_tasks: 100,
+    })
+    .collect();
+    proto(GetNetworkStatsResponse {
+        active_provers: nodes,
+        node_rank: 1,
+        ranked_nodes: nodes,
+        task_supply,
+    })
+}
+
+async fn get_proof_task(State(state): State<Arc<MockState>>, body: Bytes) -> Response {
+    let Ok(request) = GetProofTaskRequest::decode(body) else {
+        return bad_request("Invalid GetProofTaskRequest");
+    };
+    let task_id = format!("mock-{}", state.next_task.fetch_add(1, Ordering::Relaxed));
+    let difficulty = TaskDifficulty::try_from(request.max_difficulty).unwrap_or_default();
+    let scripted = state
+        .script
+        .lock()
+        .ok()
+        .and_then(|mut script| script.pop_front());
+    if let Some(scripted) = scripted {
+        return proto(GetProofTaskResponse {
+            task: Some(scripted_task(scripted, task_id, difficulty)),
+            ..Default::default()
+        });
+    }
+    let mut task = fib_task(task_id, difficulty, state.config.inputs_per_task);
+
+    if roll(state.config.malformed_task_rate) {
+        match Malformation::random() {
+            Malformation::Undecodable => {
+                return (
+                    [(header::CONTENT_TYPE, "application/octet-stream")],
+                    vec![0xff; 16],
+                )
+                    .into_response();
+            }
+            Malformation::UnknownProgram => task.program_id = "mock_unknown_program".to_string(),
+            Malformation::TruncatedInputs => {
+                for input in task.public_inputs_list.iter_mut() {
+                    input.truncate(5);
+                }
+            }
+            Malformation::NoInputs => task.public_inputs_list.clear(),
+        }
+    }
+    proto(GetProofTaskResponse {
+        task: Some(task),
+        ..Default::default()
+    })
+}
+
+/// A task computing a short Fibonacci sequence for `num_inputs` input triples, or one to three
+fn fib_task(task_id: String, difficulty: TaskDifficulty, num_inputs: Option<usize>) -> Task {
+    let num_inputs = num_inputs.unwrap_or_else(|| 1 + rand::random::<usize>() % 3);
+    let public_inputs_list = (0..num_inputs)
+        .map(|_| {
+            let n = 10 + rand::random::<u32>() % 20;
+            let init_a = rand::random::<u32>() % MAX_INITIAL_VALUE;
+            let init_b = 1 + rand::random::<u32>() % MAX_INITIAL_VALUE;
+            [n, init_a, init_b]
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect()
+        })
+        .collect();
+    Task {
+        task_id,
+        program_id: MOCK_PROGRAM_ID.to_string(),
+        public_inputs_list,
+        task_type: TaskType::ProofRequired as i32,
+        difficulty: difficulty as i32,
+        ..Default::default()
+    }
+}
+
+fn scripted_task(scripted: ScriptedTask, task_id: String, difficulty: TaskDifficulty) -> Task {
+    let task_type = TaskType::from_str_name(&scripted.task_type).unwrap_or(TaskType::ProofRequired);
+    Task {
+        task_id: scripted.task_id.unwrap_or(task_id),
+        program_id: scripted.program_id,
+        public_inputs_list: scripted
+            .inputs
+            .iter()
+            .map(|(n, init_a, init_b)| {
+                [*n, *init_a, *init_b]
+                    .iter()
+                    .flat_map(|value| value.to_le_bytes())
+                    .collect()
+            })
+            .collect(),
+        task_type: task_type as i32,
+        difficulty: difficulty as i32,
+        ..Default::default()
+    }
+}
+
+/// Task subscriptions are not offered, so the fetcher falls back to polling.
+async fn subscribe_proof_task() -> StatusCode {
+    StatusCode::NOT_IMPLEMENTED
+}
+
+/// Describe a random task of the requested difficulty; taking the offer hands out a new one.
+async fn preview_proof_task(State(state): State<Arc<MockState>>, body: Bytes) -> Response {
+    let Ok(request) = GetProofTaskRequest::decode(body) else {
+        return bad_request("Invalid GetProofTaskRequest");
+    };
+    let difficulty = TaskDifficulty::try_from(request.max_difficulty).unwrap_or_default();
+    let num_inputs = state
+        .config
+        .inputs_per_task
+        .unwrap_or_else(|| 1 + rand::random::<usize>() % 3) as u64;
+    let weight = difficulty as u64 + 1;
+    proto(PreviewProofTaskResponse {
+        offer: Some(TaskOffer {
+            offer_id: format!("offer-{}", state.next_task.load(Ordering::Relaxed)),
+            difficulty: difficulty as i32,
+            task_type: TaskType::ProofRequired as i32,
+            // Three little-endian u32s per Fibonacci input
+            input_size: num_inputs * 12,
+            expected_cycles: num_inputs * weight * 100_000,
+            reward_weight: num_inputs * weight,
+        }),
+    })
+}
+
+async fn decline_proof_task(body: Bytes) -> Response {
+    if DeclineProofTaskRequest::decode(body).is_err() {
+        return bad_request("Invalid DeclineProofTaskRequest");
+    }
+    proto(DeclineProofTaskResponse {})
+}
+
+async fn submit_proof(State(state): State<Arc<MockState>>, body: Bytes) -> Response {
+    let Ok(request) = SubmitProofRequest::decode(body) else {
+        return bad_request("Invalid SubmitProofRequest");
+    };
+    if !state.accept(&request) {
+        let error = serde_json::json!({
+            "name": ALREADY_ACCEPTED,
+            "message": "Proof already accepted",
+            "httpCode": 409,
+        });
+        return (StatusCode::CONFLICT, error.to_string()).into_response();
+    }
+    StatusCode::OK.into_response()
+}
+
+async fn submit_proofs_batch(State(state): State<Arc<MockState>>, body: Bytes) -> Response {
+    let Ok(request) = SubmitProofsBatchRequest::decode(body) else {
+        return bad_request("Invalid SubmitProofsBatchRequest");
+    };
+    proto(SubmitProofsBatchResponse {
+        results: request
+            .submissions
+            .into_iter()
+            .map(|submission| {
+                // Duplicates are reported as accepted, but counted once
+                state.accept(&submission);
+                SubmitProofResult {
+                    task_id: submission.task_id,
+                    accepted: true,
+                    error: String::new(),
+                }
+            })
+            .collect(),
+    })
+}
+
+/// Every submitted proof is accepted, so every task looked up has been credited.
+async fn get_task_receipts(body: Bytes) -> Response {
+    let Ok(request) = GetTaskReceiptsRequest::decode(body) else {
+        return bad_request("Invalid GetTaskReceiptsRequest");
+    };
+    proto(GetTaskReceiptsResponse {
+        receipts: request
+            .task_ids
+            .into_iter()
+            .map(|task_id| TaskReceipt {
+                task_id,
+                state: TaskReceiptState::Credited as i32,
+                points: POINTS_PER_PROOF,
+                reason: String::new(),
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::Environment;
+    use crate::orchestrator::error::OrchestratorError;
+    use crate::orchestrator::{Orchestrator, OrchestratorClient};
+    use ed25519_dalek::SigningKey;
+
+    async fn client(spec: &str) -> OrchestratorClient {
+        let url = start_mock_orchestrator(spec.parse().unwrap())
+            .await
+            .unwrap();
+        OrchestratorClient::new(Environment::Custom {
+            orchestrator_url: url,
+        })
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config: MockConfig = "latency_ms=200, rate_limits=0.5,malformed_tasks=1,inputs=4"
+            .parse()
+            .unwrap();
+        assert_eq!(config.latency, Duration::from_millis(200));
+        assert_eq!(config.rate_limit_rate, 0.5);
+        assert_eq!(config.malformed_task_rate, 1.0);
+        assert_eq!(config.inputs_per_task, Some(4));
+        assert_eq!(
+            "default".parse::<MockConfig>().unwrap(),
+            MockConfig::default()
+        );
+        assert!("rate_limits=2".parse::<MockConfig>().is_err());
+        assert!("latency=5".parse::<MockConfig>().is_err());
+        assert!("inputs=0".parse::<MockConfig>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_serves_fib_tasks_and_accepts_proofs() {
+        let client = client("latency_ms=0").await;
+        let signing_key = SigningKey::generate(&mut rand::thread_rng());
+
+        let result = client
+            .get_proof_task("1", signing_key.verifying_key(), TaskDifficulty::Small)
+            .await
+            .unwrap();
+        assert_eq!(result.task.program_id, MOCK_PROGRAM_ID);
+        assert_eq!(result.actual_difficulty, TaskDifficulty::Small);
+        assert!(
+            result
+                .task
+                .all_inputs()
+                .iter()
+                .all(|input| input.len() == 12)
+        );
+
+        client
+            .submit_proof(
+                &result.task.task_id,
+                "hash",
+                Bytes::from_static(&[1, 2, 3]),
+                vec![],
+                signing_key,
+                1,
+                crate::nexus_orchestrator::TaskType::ProofRequired,
+                &[],
+                1,
+            )
+            .await
+            .unwrap();
+        let points = client.get_node_points("1").await.unwrap();
+        assert_eq!(points.node_points, POINTS_PER_PROOF);
+        let stats = client.get_network_stats("1").await.unwrap();
+        assert_eq!(stats.node_rank, 1);
+        assert_eq!(stats.task_supply.len(), 3);
+
+        // A retry of the same proof is recognized by its idempotency key
+        let retry = client
+            .submit_proof(
+                &result.task.task_id,
+                "hash",
+                Bytes::from_static(&[1, 2, 3]),
+                vec![],
+                SigningKey::generate(&mut rand::thread_rng()),
+                1,
+                crate::nexus_orchestrator::TaskType::ProofRequired,
+                &[],
+                1,
+            )
+            .await;
+        assert!(retry.unwrap_err().is_already_accepted());
+        let receipts = client
+            .get_task_receipts("1", std::slice::from_ref(&result.task.task_id))
+            .await
+            .unwrap();
+        assert_eq!(receipts[0].state(), TaskReceiptState::Credited);
+    }
+
+    #[tokio::test]
+    async fn test_deregisters_only_with_bound_key() {
+        let client = client("latency_ms=0").await;
+        let signing_key = SigningKey::generate(&mut rand::thread_rng());
+        let node_id = client
+            .register_node("user", signing_key.verifying_key())
+            .await
+            .unwrap();
+
+        // Any other key is refused, e.g. one made up for the node ID
+        let other = SigningKey::generate(&mut rand::thread_rng());
+        assert!(client.deregister_node(&node_id, other).await.is_err());
+
+        // A captured request is not accepted a second time
+        let request = OrchestratorClient::deregister_node_request(&node_id, &signing_key);
+        let url = format!(
+            "{}/v3/nodes/{}/deregister",
+            client.environment().orchestrator_url(),
+            node_id
+        );
+        let send = || {
+            reqwest::Client::new()
+                .post(&url)
+                .body(request.encode_to_vec())
+                .send()
+        };
+        assert_eq!(send().await.unwrap().status(), reqwest::StatusCode::OK);
+        assert_eq!(
+            send().await.unwrap().status(),
+            reqwest::StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_previews_offered_tasks() {
+        let client = client("latency_ms=0").await;
+        let verifying_key = SigningKey::generate(&mut rand::thread_rng()).verifying_key();
+
+        let offer = client
+            .preview_proof_task("1", verifying_key, TaskDifficulty::Medium)
+            .await
+            .unwrap();
+        assert_eq!(offer.difficulty, TaskDifficulty::Medium);
+        assert!(offer.reward_weight > 0);
+        client
+            .decline_proof_task("1", &offer.offer_id, "reward>=100")
+            .await
+            .unwrap();
+
+        let offer = client
+            .preview_proof_task("1", verifying_key, TaskDifficulty::Medium)
+            .await
+            .unwrap();
+        let result = client
+            .take_proof_task("1", verifying_key, TaskDifficulty::Medium, &offer.offer_id)
+            .await
+            .unwrap();
+        assert_eq!(result.actual_difficulty, TaskDifficulty::Medium);
+    }
+
+    #[tokio::test]
+    async fn test_serves_scripted_tasks_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("tasks.json");
+        std::fs::write(
+            &script,
+            r#"[
+                { "task_id": "first", "inputs": [[9, 1, 1], [10, 2, 3]] },
+                { "inputs": [[11, 1, 1]], "task_type": "PROOF_HASH" }
+            ]"#,
+        )
+        .unwrap();
+        let client = client(&format!("latency_ms=0,script={}", script.display())).await;
+        let signing_key = SigningKey::generate(&mut rand::thread_rng());
+        let mut tasks = Vec::new();
+        for _ in 0..3 {
+            let result = client
+                .get_proof_task("1", signing_key.verifying_key(), TaskDifficulty::Small)
+                .await
+                .unwrap();
+            tasks.push(result.task);
+        }
+
+        assert_eq!(tasks[0].task_id, "first");
+        assert_eq!(
+            tasks[0].all_inputs(),
+            &[
+                vec![9, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0],
+                vec![10, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]
+            ]
+        );
+        assert_eq!(tasks[1].task_type, TaskType::ProofHash);
+        // The script is exhausted, so random tasks follow
+        assert_eq!(tasks[2].program_id, MOCK_PROGRAM_ID);
+        assert_eq!(tasks[2].task_type, TaskType::ProofRequired);
+    }
+
+    #[test]
+    fn test_rejects_invalid_script() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("tasks.json");
+        std::fs::write(&script, r#"[{ "inputs": [], "task_type": "NOPE" }]"#).unwrap();
+        assert!(load_script(&script).is_err());
+        std::fs::write(&script, r#"[{ "inputs": [[1, 2]] }]"#).unwrap();
+        assert!(load_script(&script).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_injects_rate_limits() {
+        let client = client("latency_ms=0,rate_limits=1,retry_after_secs=7").await;
+        let signing_key = SigningKey::generate(&mut rand::thread_rng());
+        let error = client
+            .get_proof_task("1", signing_key.verifying_key(), TaskDifficulty::Small)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, OrchestratorError::Http { status: 429, .. }));
+        assert_eq!(error.get_retry_after_seconds(), Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_injects_malformed_tasks() {
+        let client = client("latency_ms=0,malformed_tasks=1").await;
+        let signing_key = SigningKey::generate(&mut rand::thread_rng());
+        for _ in 0..10 {
+            match client
+                .get_proof_task("1", signing_key.verifying_key(), TaskDifficulty::Small)
+                .await
+            {
+                Ok(result) => assert!(
+                    result.task.program_id != MOCK_PROGRAM_ID
+                        || result
+                            .task
+                            .all_inputs()
+                            .iter()
+                            .all(|input| input.len() < 12)
+                ),
+                Err(error) => assert!(matches!(error, OrchestratorError::Decode(_))),
+            }
+        }
+    }
+}
@@ -1,10 +1,43 @@
 use crate::environment::Environment;
+use crate::network::ProofSubmission;
 use crate::orchestrator::error::OrchestratorError;
+use bytes::Bytes;
 use ed25519_dalek::{SigningKey, VerifyingKey};
+use std::sync::Arc;
 
+pub mod body;
 pub(crate) mod client;
-pub use client::OrchestratorClient;
+pub use client::{OrchestratorClient, UNKNOWN_COUNTRY, normalize_country_code, pin_country};
 pub mod error;
+pub mod failover;
+pub use failover::FailoverOrchestrator;
+pub mod grpc;
+pub use grpc::GrpcOrchestratorClient;
+pub mod mock;
+pub mod nonce;
+pub mod recording;
+pub mod signing;
+
+/// Wire protocol used to talk to the orchestrator
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Transport {
+    /// Protobuf messages over HTTPS requests
+    #[default]
+    Http,
+    /// gRPC over a single long-lived HTTP/2 connection, with streamed task delivery
+    Grpc,
+}
+
+/// Create an orchestrator client for the environment using the given transport.
+pub fn connect(
+    environment: Environment,
+    transport: Transport,
+) -> Result<Arc<dyn Orchestrator>, OrchestratorError> {
+    Ok(match transport {
+        Transport::Http => Arc::new(OrchestratorClient::new(environment)),
+        Transport::Grpc => Arc::new(GrpcOrchestratorClient::new(environment)?),
+    })
+}
 
 #[cfg(test)]
 use mockall::{automock, predicate::*};
@@ -17,6 +50,14 @@ pub trait Orchestrator: Send + Sync {
     /// Get the user ID associated with a wallet address.
     async fn get_user(&self, wallet_address: &str) -> Result<String, OrchestratorError>;
 
+    /// Get the user of a wallet address with one page of its nodes, starting at `nodes_cursor`
+    /// (empty for the first page).
+    async fn get_user_nodes(
+        &self,
+        wallet_address: &str,
+        nodes_cursor: &str,
+    ) -> Result<crate::nexus_orchestrator::UserResponse, OrchestratorError>;
+
     /// Registers a new user with the orchestrator.
     async fn register_user(
         &self,
@@ -24,12 +65,36 @@ pub trait Orchestrator: Send + Sync {
         wallet_address: &str,
     ) -> Result<(), OrchestratorError>;
 
-    /// Registers a new node with the orchestrator.
-    async fn register_node(&self, user_id: &str) -> Result<String, OrchestratorError>;
+    /// Registers a new node with the orchestrator, which binds `verifying_key` to it.
+    async fn register_node(
+        &self,
+        user_id: &str,
+        verifying_key: VerifyingKey,
+    ) -> Result<String, OrchestratorError>;
+
+    /// Retires a node, signing the request with the key bound to it at registration. The node
+    /// cannot prove any more.
+    async fn deregister_node(
+        &self,
+        node_id: &str,
+        signing_key: SigningKey,
+    ) -> Result<(), OrchestratorError>;
 
     /// Get the wallet address associated with a node ID.
     async fn get_node(&self, node_id: &str) -> Result<String, OrchestratorError>;
 
+    /// Get the point totals of a node and its owner's wallet.
+    async fn get_node_points(
+        &self,
+        node_id: &str,
+    ) -> Result<crate::nexus_orchestrator::GetNodePointsResponse, OrchestratorError>;
+
+    /// Get network-wide prover statistics, the node's rank and the supply of pending tasks.
+    async fn get_network_stats(
+        &self,
+        node_id: &str,
+    ) -> Result<crate::nexus_orchestrator::GetNetworkStatsResponse, OrchestratorError>;
+
     /// Request a new proof task for the node.
     async fn get_proof_task(
         &self,
@@ -38,17 +103,251 @@ pub trait Orchestrator: Send + Sync {
         max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
     ) -> Result<crate::orchestrator::client::ProofTaskResult, OrchestratorError>;
 
-    /// Submits a proof to the orchestrator.
+    /// Wait for a proof task to be pushed to the node, instead of polling for one.
+    ///
+    /// The server may hold the request open for up to `wait`; `Ok(None)` means no task became
+    /// available in that time. Servers without task subscriptions answer with an error for
+    /// which [`OrchestratorError::is_unsupported`] is true.
+    async fn subscribe_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        wait: std::time::Duration,
+    ) -> Result<Option<crate::orchestrator::client::ProofTaskResult>, OrchestratorError>;
+
+    /// Describe the task the node would be given next, without taking it. The orchestrator holds
+    /// the task for the node until it is taken with [`Orchestrator::take_proof_task`] or declined.
+    ///
+    /// Servers without task previews answer with an error for which
+    /// [`OrchestratorError::is_unsupported`] is true.
+    async fn preview_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Result<crate::task::TaskDescriptor, OrchestratorError>;
+
+    /// Take the task of an offer from [`Orchestrator::preview_proof_task`].
+    async fn take_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        offer_id: &str,
+    ) -> Result<crate::orchestrator::client::ProofTaskResult, OrchestratorError>;
+
+    /// Turn down the task of an offer, so it is offered to other nodes.
+    async fn decline_proof_task(
+        &self,
+        node_id: &str,
+        offer_id: &str,
+        reason: &str,
+    ) -> Result<(), OrchestratorError>;
+
+    /// Submits a proof to the orchestrator. A non-zero `nonce` (see [`nonce`]) is signed along
+    /// with the proof; retries of a submission pass the same nonce.
     #[allow(clippy::too_many_arguments)]
     async fn submit_proof(
         &self,
         task_id: &str,
         proof_hash: &str,
-        proof: Vec<u8>,
-        proofs: Vec<Vec<u8>>,
+        proof: Bytes,
+        proofs: Vec<Bytes>,
         signing_key: SigningKey,
         num_provers: usize,
         task_type: crate::nexus_orchestrator::TaskType,
         individual_proof_hashes: &[String],
+        nonce: u64,
     ) -> Result<(), OrchestratorError>;
+
+    /// Submits several proofs in a single request, returning the outcome of each.
+    /// Servers without batch submission answer with an error for which
+    /// [`OrchestratorError::is_unsupported`] is true.
+    async fn submit_proofs_batch(
+        &self,
+        submissions: &[ProofSubmission],
+        signing_key: SigningKey,
+        num_provers: usize,
+    ) -> Result<Vec<crate::nexus_orchestrator::SubmitProofResult>, OrchestratorError>;
+
+    /// Look up whether points were credited for tasks the node submitted. Tasks the
+    /// orchestrator has no record of may be left out of the result. Servers without receipts
+    /// answer with an error for which [`OrchestratorError::is_unsupported`] is true.
+    async fn get_task_receipts(
+        &self,
+        node_id: &str,
+        task_ids: &[String],
+    ) -> Result<Vec<crate::nexus_orchestrator::TaskReceipt>, OrchestratorError>;
+}
+
+impl std::fmt::Debug for dyn Orchestrator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Orchestrator")
+            .field("environment", self.environment())
+            .finish()
+    }
+}
+
+/// Shared clients (e.g. the result of [`connect`]) can be used wherever an orchestrator is expected.
+#[async_trait::async_trait]
+impl<T: Orchestrator + ?Sized> Orchestrator for Arc<T> {
+    fn environment(&self) -> &Environment {
+        (**self).environment()
+    }
+
+    async fn get_user(&self, wallet_address: &str) -> Result<String, OrchestratorError> {
+        (**self).get_user(wallet_address).await
+    }
+
+    async fn get_user_nodes(
+        &self,
+        wallet_address: &str,
+        nodes_cursor: &str,
+    ) -> Result<crate::nexus_orchestrator::UserResponse, OrchestratorError> {
+        (**self).get_user_nodes(wallet_address, nodes_cursor).await
+    }
+
+    async fn register_user(
+        &self,
+        user_id: &str,
+        wallet_address: &str,
+    ) -> Result<(), OrchestratorError> {
+        (**self).register_user(user_id, wallet_address).await
+    }
+
+    async fn register_node(
+        &self,
+        user_id: &str,
+        verifying_key: VerifyingKey,
+    ) -> Result<String, OrchestratorError> {
+        (**self).register_node(user_id, verifying_key).await
+    }
+
+    async fn deregister_node(
+        &self,
+        node_id: &str,
+        signing_key: SigningKey,
+    ) -> Result<(), OrchestratorError> {
+        (**self).deregister_node(node_id, signing_key).await
+    }
+
+    async fn get_node(&self, node_id: &str) -> Result<String, OrchestratorError> {
+        (**self).get_node(node_id).await
+    }
+
+    async fn get_node_points(
+        &self,
+        node_id: &str,
+    ) -> Result<crate::nexus_orchestrator::GetNodePointsResponse, OrchestratorError> {
+        (**self).get_node_points(node_id).await
+    }
+
+    async fn get_network_stats(
+        &self,
+        node_id: &str,
+    ) -> Result<crate::nexus_orchestrator::GetNetworkStatsResponse, OrchestratorError> {
+        (**self).get_network_stats(node_id).await
+    }
+
+    async fn get_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Result<crate::orchestrator::client::ProofTaskResult, OrchestratorError> {
+        (**self)
+            .get_proof_task(node_id, verifying_key, max_difficulty)
+            .await
+    }
+
+    async fn subscribe_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        wait: std::time::Duration,
+    ) -> Result<Option<crate::orchestrator::client::ProofTaskResult>, OrchestratorError> {
+        (**self)
+            .subscribe_proof_task(node_id, verifying_key, max_difficulty, wait)
+            .await
+    }
+
+    async fn preview_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Result<crate::task::TaskDescriptor, OrchestratorError> {
+        (**self)
+            .preview_proof_task(node_id, verifying_key, max_difficulty)
+            .await
+    }
+
+    async fn take_proof_task(
+        &self,
+        node_id: &str,
+        verifying_key: VerifyingKey,
+        max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        offer_id: &str,
+    ) -> Result<crate::orchestrator::client::ProofTaskResult, OrchestratorError> {
+        (**self)
+            .take_proof_task(node_id, verifying_key, max_difficulty, offer_id)
+            .await
+    }
+
+    async fn decline_proof_task(
+        &self,
+        node_id: &str,
+        offer_id: &str,
+        reason: &str,
+    ) -> Result<(), OrchestratorError> {
+        (**self).decline_proof_task(node_id, offer_id, reason).await
+    }
+
+    async fn submit_proof(
+        &self,
+        task_id: &str,
+        proof_hash: &str,
+        proof: Bytes,
+        proofs: Vec<Bytes>,
+        signing_key: SigningKey,
+        num_provers: usize,
+        task_type: crate::nexus_orchestrator::TaskType,
+        individual_proof_hashes: &[String],
+        nonce: u64,
+    ) -> Result<(), OrchestratorError> {
+        (**self)
+            .submit_proof(
+                task_id,
+                proof_hash,
+                proof,
+                proofs,
+                signing_key,
+                num_provers,
+                task_type,
+                individual_proof_hashes,
+                nonce,
+            )
+            .await
+    }
+
+    async fn submit_proofs_batch(
+        &self,
+        submissions: &[ProofSubmission],
+        signing_key: SigningKey,
+        num_provers: usize,
+    ) -> Result<Vec<crate::nexus_orchestrator::SubmitProofResult>, OrchestratorError> {
+        (**self)
+            .submit_proofs_batch(submissions, signing_key, num_provers)
+            .await
+    }
+
+    async fn get_task_receipts(
+        &self,
+        node_id: &str,
+        task_ids: &[String],
+    ) -> Result<Vec<crate::nexus_orchestrator::TaskReceipt>, OrchestratorError> {
+        (**self).get_task_receipts(node_id, task_ids).await
+    }
 }
@@ -0,0 +1,309 @@
+//! Submission nonces
+//!
+//! Every proof submission carries a nonce from a per-node counter that only moves forward, also
+//! across restarts. The nonce is part of the signed message, so a captured submission cannot be
+//! replayed as a new one, and retries of a submission reuse its nonce, so the orchestrator can
+//! tell them apart from new submissions.
+//!
+//! Counting starts past both the reservation saved for the node and the current time in
+//! milliseconds, so nonces keep moving forward even if the reservation was lost or could not be
+//! read, e.g. after the `.nexus` directory was deleted.
+//!
+//! Rather than writing to disk on every submission, a block of nonces is reserved at a time by
+//! saving its end in `nonces/<node_id>.json` next to the config file, before any nonce of the
+//! block is used. The file belongs to the node alone and is replaced through a temporary file, so
+//! neither other writers of the config file nor a crash can lose a reservation. After a restart,
+//! counting resumes past the last reservation, skipping any nonces of it that were not used.
+//! Reservations saved in `submission_nonces` of the config file by earlier versions are still
+//! honored.
+//!
+//! Orchestrators that predate nonces verify the signature without one and reject submissions
+//! signed with a nonce. Until a submission with a nonce was accepted, a rejection of one is taken
+//! as such an orchestrator: the submission is signed again without a nonce, and so are all later
+//! ones.
+
+use crate::config::Config;
+use crate::consts::cli_consts::proof_submission::NONCE_RESERVATION;
+use crate::orchestrator::error::OrchestratorError;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+#[derive(Debug)]
+struct NonceState {
+    next: u64,
+    /// Highest nonce saved in the reservation file
+    reserved_until: u64,
+}
+
+/// Counter handing out the submission nonces of one node. Clones share the same counter.
+#[derive(Debug, Clone)]
+pub struct SubmissionNonces {
+    path: PathBuf,
+    state: Arc<Mutex<NonceState>>,
+}
+
+impl SubmissionNonces {
+    /// Resume counting for `node_id` past the reservation saved next to the config file at
+    /// `config_path` and the current time in milliseconds, whichever is later.
+    pub async fn open(config_path: PathBuf, node_id: u64) -> Self {
+        let path = reservation_path(&config_path, node_id);
+        let saved = {
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || load_reservation(&path, &config_path, node_id))
+                .await
+                .unwrap_or_default()
+        };
+        let start = saved.max(unix_millis());
+        Self {
+            path,
+            state: Arc::new(Mutex::new(NonceState {
+                next: start + 1,
+                reserved_until: start,
+            })),
+        }
+    }
+
+    /// Nonce for a new submission
+    pub async fn next(&self) -> u64 {
+        let mut state = self.state.lock().await;
+        let nonce = state.next;
+        state.next += 1;
+        if nonce > state.reserved_until {
+            let reserved_until = nonce + NONCE_RESERVATION - 1;
+            // If saving fails, the reservation is tried again for the next nonce; counting from
+            // the current time after a restart still moves forward
+            let path = self.path.clone();
+            let saved =
+                tokio::task::spawn_blocking(move || save_reservation(&path, reserved_until)).await;
+            if matches!(saved, Ok(Ok(()))) {
+                state.reserved_until = reserved_until;
+            }
+        }
+        nonce
+    }
+}
+
+/// Whether the orchestrator verifies submission nonces, as far as known
+#[derive(Debug)]
+struct NonceSupport(AtomicU8);
+
+impl NonceSupport {
+    const UNKNOWN: u8 = 0;
+    const SUPPORTED: u8 = 1;
+    const UNSUPPORTED: u8 = 2;
+
+    const fn new() -> Self {
+        Self(AtomicU8::new(Self::UNKNOWN))
+    }
+
+    fn enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed) != Self::UNSUPPORTED
+    }
+
+    /// Record the outcome of a submission signed with a nonce, returning whether to sign it again
+    /// without one.
+    fn observe(&self, result: &Result<(), OrchestratorError>) -> bool {
+        match result {
+            Ok(()) => {
+                let _ = self.0.compare_exchange(
+                    Self::UNKNOWN,
+                    Self::SUPPORTED,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                );
+                false
+            }
+            // Once a nonce was accepted, rejections are about the submission itself
+            Err(OrchestratorError::Http {
+                status: 400 | 401 | 403,
+                ..
+            }) => {
+                let _ = self.0.compare_exchange(
+                    Self::UNKNOWN,
+                    Self::UNSUPPORTED,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                );
+                self.0.load(Ordering::Relaxed) == Self::UNSUPPORTED
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+static NONCE_SUPPORT: NonceSupport = NonceSupport::new();
+
+/// Whether submissions should carry a nonce, i.e. unless the orchestrator turned out to reject
+/// them
+pub fn nonces_enabled() -> bool {
+    NONCE_SUPPORT.enabled()
+}
+
+/// Record the outcome of a submission signed with a nonce. Returns true if the orchestrator
+/// rejected it for predating nonces, and the submission should be signed again without one.
+pub fn observe_nonce_outcome(result: &Result<(), OrchestratorError>) -> bool {
+    NONCE_SUPPORT.observe(result)
+}
+
+/// `nonces/<node_id>.json` in the directory of the config file
+fn reservation_path(config_path: &Path, node_id: u64) -> PathBuf {
+    config_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("nonces")
+        .join(format!("{}.json", node_id))
+}
+
+/// The saved reservation of the node, or 0 if there is none.
+fn load_reservation(path: &Path, config_path: &Path, node_id: u64) -> u64 {
+    let saved = std::fs::read(path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<u64>(&bytes).ok())
+        .unwrap_or_default();
+    let legacy = Config::load_from_file(config_path)
+        .ok()
+        .and_then(|config| config.submission_nonces.get(&node_id.to_string()).copied())
+        .unwrap_or_default();
+    saved.max(legacy)
+}
+
+/// Write the reservation through a temporary file, so a crash never leaves a partial one.
+fn save_reservation(path: &Path, reserved_until: u64) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, reserved_until.to_string())?;
+    std::fs::rename(&tmp_path, path)
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn saved_nonce(config_path: &Path, node_id: u64) -> Option<u64> {
+        let bytes = std::fs::read(reservation_path(config_path, node_id)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    #[tokio::test]
+    async fn test_nonces_increase_across_restarts() {
+        let dir = TempDir::new().unwrap();
+        let config_path = dir.path().join("config.json");
+
+        let nonces = SubmissionNonces::open(config_path.clone(), 1).await;
+        let first = nonces.next().await;
+        assert!(first > unix_millis() - 60_000);
+        assert_eq!(nonces.next().await, first + 1);
+        // The block is reserved before its first nonce is used, without touching the config file
+        assert_eq!(
+            saved_nonce(&config_path, 1),
+            Some(first + NONCE_RESERVATION - 1)
+        );
+        assert!(!config_path.exists());
+
+        // A restart skips the rest of the reservation
+        let restarted = SubmissionNonces::open(config_path.clone(), 1).await;
+        assert!(restarted.next().await >= first + NONCE_RESERVATION);
+    }
+
+    #[tokio::test]
+    async fn test_nonces_never_go_back() {
+        let dir = TempDir::new().unwrap();
+        let config_path = dir.path().join("config.json");
+        let nonces = SubmissionNonces::open(config_path.clone(), 1).await;
+        let first = nonces.next().await;
+
+        // A lost reservation still counts forward from the current time
+        std::fs::remove_file(reservation_path(&config_path, 1)).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let restarted = SubmissionNonces::open(config_path.clone(), 1).await;
+        assert!(restarted.next().await > first);
+    }
+
+    #[tokio::test]
+    async fn test_nonces_are_counted_per_node() {
+        let dir = TempDir::new().unwrap();
+        let config_path = dir.path().join("config.json");
+        let ahead = unix_millis() + 3_600_000;
+        save_reservation(&reservation_path(&config_path, 1), ahead).unwrap();
+
+        assert_eq!(
+            SubmissionNonces::open(config_path.clone(), 1)
+                .await
+                .next()
+                .await,
+            ahead + 1
+        );
+        let other = SubmissionNonces::open(config_path.clone(), 2)
+            .await
+            .next()
+            .await;
+        assert!(other < ahead);
+        assert_eq!(
+            saved_nonce(&config_path, 2),
+            Some(other + NONCE_RESERVATION - 1)
+        );
+    }
+
+    fn http_error(status: u16) -> Result<(), OrchestratorError> {
+        Err(OrchestratorError::Http {
+            status,
+            message: String::new(),
+            headers: Default::default(),
+        })
+    }
+
+    #[test]
+    fn test_rejected_nonce_falls_back_until_one_was_accepted() {
+        let support = NonceSupport::new();
+        // Failures that are not rejections say nothing about nonces
+        assert!(!support.observe(&http_error(503)));
+        assert!(support.enabled());
+
+        assert!(support.observe(&http_error(401)));
+        assert!(!support.enabled());
+
+        let support = NonceSupport::new();
+        assert!(!support.observe(&Ok(())));
+        assert!(!support.observe(&http_error(400)));
+        assert!(support.enabled());
+    }
+
+    #[tokio::test]
+    async fn test_reservation_in_config_file_is_honored() {
+        let dir = TempDir::new().unwrap();
+        let config_path = dir.path().join("config.json");
+        let mut config = Config::default();
+        let ahead = unix_millis() + 3_600_000;
+        config.submission_nonces.insert("1".to_string(), ahead);
+        config.save(&config_path).unwrap();
+
+        let nonces = SubmissionNonces::open(config_path.clone(), 1).await;
+        assert_eq!(nonces.next().await, ahead + 1);
+        // New reservations go to the node's own file only
+        assert_eq!(
+            saved_nonce(&config_path, 1),
+            Some(ahead + NONCE_RESERVATION)
+        );
+        assert_eq!(
+            Config::load_from_file(&config_path)
+                .unwrap()
+                .submission_nonces
+                .get("1"),
+            Some(&ahead)
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Recording and replay of orchestrator traffic
+//!
+//! `nexus-cli start --record <FILE>` routes the orchestrator client through an in-process proxy
+//! that forwards every request to the real orchestrator and appends the exchange to `FILE`, one
+//! JSON object per line. `nexus-cli start --replay <FILE>` serves those responses back, in the
+//! order they were recorded, without any network access. A recording attached to a bug report
+//! reproduces the tasks and submission outcomes the reporter saw.
+
+use crate::environment::Environment;
+use crate::failures::{from_hex, to_hex};
+use axum::Router;
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+/// Headers that describe a single connection and are not forwarded or recorded
+static HOP_BY_HOP_HEADERS: [HeaderName; 4] = [
+    header::CONNECTION,
+    header::CONTENT_LENGTH,
+    header::HOST,
+    header::TRANSFER_ENCODING,
+];
+
+/// One request to the orchestrator and the response it got
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Exchange {
+    pub method: String,
+    /// Path and query of the request, e.g. `/v3/tasks`
+    pub path: String,
+    /// Request body, hex encoded
+    pub request: String,
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    /// Response body, hex encoded
+    pub response: String,
+}
+
+impl Exchange {
+    /// Key under which replay looks up responses
+    fn key(&self) -> (String, String) {
+        (self.method.clone(), self.path.clone())
+    }
+
+    fn into_response(self) -> Response {
+        let body = match from_hex(&self.response) {
+            Ok(body) => body,
+            Err(e) => return replay_error(&e),
+        };
+        let mut response = Response::builder()
+            .status(StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR));
+        for (name, value) in &self.headers {
+            response = response.header(name, value);
+        }
+        response
+            .body(Body::from(body))
+            .unwrap_or_else(|e| replay_error(&e.to_string()))
+    }
+}
+
+fn recorded_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter(|(name, _)| !HOP_BY_HOP_HEADERS.contains(*name))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+/// Path and query of a request
+fn request_path(request: &Request) -> String {
+    request
+        .uri()
+        .path_and_query()
+        .map_or_else(|| request.uri().path().to_string(), |path| path.to_string())
+}
+
+/// Serve `router` on a free local port in the background and return its URL.
+async fn serve(router: Router) -> io::Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+    Ok(url)
+}
+
+/// State of the recording proxy
+struct Recorder {
+    client: reqwest::Client,
+    upstream: Environment,
+    file: Mutex<std::fs::File>,
+}
+
+/// Start a proxy to `upstream` that appends every exchange to `path`, and return its URL.
+///
+/// The proxy runs in the background until the Tokio runtime shuts down.
+pub async fn start_recording_proxy(upstream: Environment, path: &Path) -> io::Result<String> {
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .build()
+        .map_err(io::Error::other)?;
+    let recorder = Arc::new(Recorder {
+        client,
+        upstream,
+        file: Mutex::new(file),
+    });
+    serve(Router::new().fallback(forward).with_state(recorder)).await
+}
+
+/// Forward a request to the orchestrator and record the exchange.
+async fn forward(State(recorder): State<Arc<Recorder>>, request: Request) -> Response {
+    let method = request.method().clone();
+    let path = request_path(&request);
+    let mut headers = request.headers().clone();
+    for name in &HOP_BY_HOP_HEADERS {
+        headers.remove(name);
+    }
+    let body = match axum::body::to_bytes(request.into_body(), usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+
+    let url = format!(
+        "{}{}",
+        recorder.upstream.orchestrator_url().trim_end_matches('/'),
+        path
+    );
+    let upstream = recorder
+        .client
+        .request(method.clone(), url)
+        .headers(headers)
+        .body(body.clone())
+        .send()
+        .await;
+    // Network failures are recorded as 502s so that replay reproduces them too
+    let (status, response_headers, response_body) = match upstream {
+        Ok(response) => {
+            let status = response.status();
+            let response_headers = recorded_headers(response.headers());
+            match response.bytes().await {
+                Ok(bytes) => (status, response_headers, bytes),
+                Err(e) => (
+                    StatusCode::BAD_GATEWAY,
+                    BTreeMap::new(),
+                    Bytes::from(e.to_string()),
+                ),
+            }
+        }
+        Err(e) => (
+            StatusCode::BAD_GATEWAY,
+            BTreeMap::new(),
+            Bytes::from(e.to_string()),
+        ),
+    };
+
+    let exchange = Exchange {
+        method: method.to_string(),
+        path,
+        request: to_hex(&body),
+        status: status.as_u16(),
+        headers: response_headers,
+        response: to_hex(&response_body),
+    };
+    if let Ok(line) = serde_json::to_string(&exchange) {
+        if let Ok(mut file) = recorder.file.lock() {
+            // Best-effort: a failed write must not break proving
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+    exchange.into_response()
+}
+
+/// Read the exchanges recorded in `path`, in order.
+pub fn load_recording(path: &Path) -> io::Result<Vec<Exchange>> {
+    let file = std::fs::File::open(path)?;
+    let mut exchanges = Vec::new();
+    for (index, line) in io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let exchange = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} line {}: {}", path.display(), index + 1, e),
+            )
+        })?;
+        exchanges.push(exchange);
+    }
+    Ok(exchanges)
+}
+
+/// Recorded responses not yet replayed, by method and path
+type ReplayQueues = Mutex<HashMap<(String, String), VecDeque<Exchange>>>;
+
+/// Serve the responses recorded in `path` and return the server's URL.
+///
+/// Each request gets the next unused response recorded for its method and path; once those run
+/// out, the server answers 404. The server runs in the background until the Tokio runtime
+/// shuts down.
+pub async fn start_replay_server(path: PathBuf) -> io::Result<String> {
+    let mut queues: HashMap<(String, String), VecDeque<Exchange>> = HashMap::new();
+    for exchange in load_recording(&path)? {
+        queues
+            .entry(exchange.key())
+            .or_default()
+            .push_back(exchange);
+    }
+    let queues: Arc<ReplayQueues> = Arc::new(Mutex::new(queues));
+    serve(Router::new().fallback(replay).with_state(queues)).await
+}
+
+/// Answer a request with the next response recorded for it.
+async fn replay(State(queues): State<Arc<ReplayQueues>>, request: Request) -> Response {
+    let key = (request.method().to_string(), request_path(&request));
+    let next = queues
+        .lock()
+        .ok()
+        .and_then(|mut queues| queues.get_mut(&key)?.pop_front());
+    match next {
+        Some(exchange) => exchange.into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            format!("No more recorded responses for {} {}", key.0, key.1),
+        )
+            .into_response(),
+    }
+}
+
+fn replay_error(message: &str) -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        [(header::CONTENT_TYPE, HeaderValue::from_static("text/plain"))],
+        message.to_string(),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::TaskDifficulty;
+    use crate::orchestrator::mock::{MockConfig, start_mock_orchestrator};
+    use crate::orchestrator::{Orchestrator, OrchestratorClient};
+    use ed25519_dalek::SigningKey;
+
+    fn client(orchestrator_url: String) -> OrchestratorClient {
+        OrchestratorClient::new(Environment::Custom { orchestrator_url })
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        assert_eq!(from_hex(&to_hex(&[0, 1, 0xfe])).unwrap(), vec![0, 1, 0xfe]);
+        assert!(from_hex("abc").is_err());
+        assert!(from_hex("zz").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let recording = dir.path().join("orchestrator.jsonl");
+        let signing_key = SigningKey::generate(&mut rand::thread_rng());
+
+        let mock = start_mock_orchestrator("latency_ms=0".parse::<MockConfig>().unwrap())
+            .await
+            .unwrap();
+        let proxy = start_recording_proxy(
+            Environment::Custom {
+                orchestrator_url: mock,
+            },
+            &recording,
+        )
+        .await
+        .unwrap();
+        let recorded = client(proxy)
+            .get_proof_task("1", signing_key.verifying_key(), TaskDifficulty::Small)
+            .await
+            .unwrap();
+        assert_eq!(load_recording(&recording).unwrap().len(), 1);
+
+        let replayed = client(start_replay_server(recording).await.unwrap());
+        let replayed_task = replayed
+            .get_proof_task("1", signing_key.verifying_key(), TaskDifficulty::Small)
+            .await
+            .unwrap();
+        assert_eq!(replayed_task.task, recorded.task);
+
+        // The recording holds a single task
+        let error = replayed
+            .get_proof_task("1", signing_key.verifying_key(), TaskDifficulty::Small)
+            .await
+            .unwrap_err();
+        assert!(error.is_unsupported());
+    }
+}
@@ -0,0 +1,289 @@
+//! Points tracking
+//!
+//! Periodically fetches the node's point totals from the orchestrator and keeps a rolling
+//! 24-hour history for the dashboard. Orchestrators that do not report point totals are asked
+//! once; the dashboard then shows points as unavailable and points targets have no effect.
+
+use crate::consts::cli_consts::points::{POINTS_HISTORY_HOURS, POINTS_TARGET_CHECK_INTERVAL_SECS};
+use crate::control::RuntimeControl;
+use crate::orchestrator::Orchestrator;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// Point totals as of the most recent successful poll
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PointsSnapshot {
+    /// Points earned by this node, if fetched at least once
+    pub node_points: Option<u64>,
+    /// Points earned by all nodes of the wallet
+    pub wallet_points: Option<u64>,
+    /// Points earned since this session started
+    pub session_points: u64,
+    /// Points earned in each of the last 24 hours, oldest first
+    pub hourly: Vec<u64>,
+    /// Whether the orchestrator does not report point totals
+    pub unavailable: bool,
+}
+
+#[derive(Debug, Default)]
+struct PointsInner {
+    session_start: Option<u64>,
+    node_points: Option<u64>,
+    wallet_points: Option<u64>,
+    samples: VecDeque<(Instant, u64)>,
+    unavailable: bool,
+}
+
+/// Shared, thread-safe record of point totals
+#[derive(Debug, Clone, Default)]
+pub struct PointsTracker {
+    inner: Arc<Mutex<PointsInner>>,
+}
+
+impl PointsTracker {
+    /// Record a poll result taken at `now`.
+    pub fn record(&self, node_points: u64, wallet_points: u64, now: Instant) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        inner.session_start.get_or_insert(node_points);
+        inner.node_points = Some(node_points);
+        inner.wallet_points = Some(wallet_points);
+        inner.samples.push_back((now, node_points));
+
+        let window = Duration::from_secs(POINTS_HISTORY_HOURS as u64 * 3600);
+        while inner
+            .samples
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > window)
+        {
+            inner.samples.pop_front();
+        }
+    }
+
+    /// Record that the orchestrator does not report point totals.
+    pub fn mark_unavailable(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.unavailable = true;
+        }
+    }
+
+    /// Current totals and hourly history as seen at `now`.
+    pub fn snapshot(&self, now: Instant) -> PointsSnapshot {
+        let Ok(inner) = self.inner.lock() else {
+            return PointsSnapshot::default();
+        };
+        let session_points = match (inner.session_start, inner.node_points) {
+            (Some(start), Some(current)) => current.saturating_sub(start),
+            _ => 0,
+        };
+        PointsSnapshot {
+            node_points: inner.node_points,
+            wallet_points: inner.wallet_points,
+            session_points,
+            hourly: hourly_gains(&inner.samples, now),
+            unavailable: inner.unavailable,
+        }
+    }
+}
+
+/// Points gained in each hour bucket of the history window, oldest first.
+fn hourly_gains(samples: &VecDeque<(Instant, u64)>, now: Instant) -> Vec<u64> {
+    let mut buckets = vec![0u64; POINTS_HISTORY_HOURS];
+    for ((_, previous), (at, current)) in samples.iter().zip(samples.iter().skip(1)) {
+        let hours_ago = (now.duration_since(*at).as_secs() / 3600) as usize;
+        if hours_ago < POINTS_HISTORY_HOURS {
+            buckets[POINTS_HISTORY_HOURS - 1 - hours_ago] += current.saturating_sub(*previous);
+        }
+    }
+    buckets
+}
+
+/// Poll the orchestrator for point totals every `poll_interval` until shutdown, or until it turns
+/// out not to report them. Failed polls are skipped silently; the dashboard keeps showing the last
+/// known values.
+pub fn spawn_points_poller(
+    orchestrator: Arc<dyn Orchestrator>,
+    node_id: u64,
+    tracker: PointsTracker,
+    poll_interval: Duration,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = interval.tick() => {
+                    poll_points(orchestrator.as_ref(), node_id, &tracker).await;
+                    if tracker.snapshot(Instant::now()).unavailable {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Request a stop once the session has earned `target` points. The worker finishes the task in
+/// flight before the session ends.
+pub fn spawn_points_target(
+    tracker: PointsTracker,
+    target: u64,
+    control: RuntimeControl,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(POINTS_TARGET_CHECK_INTERVAL_SECS));
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = interval.tick() => {
+                    let snapshot = tracker.snapshot(Instant::now());
+                    if snapshot.unavailable {
+                        log::warn!(
+                            "The orchestrator does not report point totals, --stop-after-points has no effect"
+                        );
+                        break;
+                    }
+                    if let Some(earned) = target_reached(&snapshot, target) {
+                        control.request_stop(format!(
+                            "Earned {} points this session (target {})",
+                            format_points(earned),
+                            format_points(target)
+                        ));
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Session points, if they reach `target`
+fn target_reached(snapshot: &PointsSnapshot, target: u64) -> Option<u64> {
+    (snapshot.session_points >= target).then_some(snapshot.session_points)
+}
+
+/// Fetch point totals once and record them; failures are ignored. Does nothing once the
+/// orchestrator turned out not to report point totals.
+pub async fn poll_points(orchestrator: &dyn Orchestrator, node_id: u64, tracker: &PointsTracker) {
+    if tracker.snapshot(Instant::now()).unavailable {
+        return;
+    }
+    match orchestrator.get_node_points(&node_id.to_string()).await {
+        Ok(points) => tracker.record(points.node_points, points.wallet_points, Instant::now()),
+        Err(e) if e.is_unsupported() => tracker.mark_unavailable(),
+        Err(_) => {}
+    }
+}
+
+/// Format a point total with thousands separators, e.g. `12,345`.
+pub fn format_points(points: u64) -> String {
+    let digits = points.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(c);
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::MockOrchestrator;
+    use crate::orchestrator::error::OrchestratorError;
+
+    #[test]
+    fn test_session_points() {
+        let tracker = PointsTracker::default();
+        let start = Instant::now();
+        assert_eq!(tracker.snapshot(start).node_points, None);
+
+        tracker.record(1_000, 5_000, start);
+        tracker.record(1_300, 5_300, start + Duration::from_secs(600));
+        let snapshot = tracker.snapshot(start + Duration::from_secs(600));
+        assert_eq!(snapshot.node_points, Some(1_300));
+        assert_eq!(snapshot.wallet_points, Some(5_300));
+        assert_eq!(snapshot.session_points, 300);
+    }
+
+    #[test]
+    fn test_hourly_buckets() {
+        let tracker = PointsTracker::default();
+        let start = Instant::now();
+        tracker.record(0, 0, start);
+        tracker.record(100, 100, start + Duration::from_secs(1800));
+        tracker.record(400, 400, start + Duration::from_secs(3 * 3600));
+
+        let snapshot = tracker.snapshot(start + Duration::from_secs(3 * 3600));
+        assert_eq!(snapshot.hourly.len(), POINTS_HISTORY_HOURS);
+        // The gain recorded 2.5 hours ago lands two buckets before the current hour
+        assert_eq!(snapshot.hourly[POINTS_HISTORY_HOURS - 1], 300);
+        assert_eq!(snapshot.hourly[POINTS_HISTORY_HOURS - 3], 100);
+        assert_eq!(snapshot.hourly.iter().sum::<u64>(), 400);
+    }
+
+    #[test]
+    fn test_points_target() {
+        let tracker = PointsTracker::default();
+        let start = Instant::now();
+        tracker.record(1_000, 1_000, start);
+        tracker.record(1_250, 1_250, start + Duration::from_secs(60));
+        let snapshot = tracker.snapshot(start + Duration::from_secs(60));
+        assert_eq!(target_reached(&snapshot, 300), None);
+        assert_eq!(target_reached(&snapshot, 250), Some(250));
+        assert_eq!(target_reached(&snapshot, 100), Some(250));
+    }
+
+    #[tokio::test]
+    async fn test_points_target_requests_stop() {
+        let tracker = PointsTracker::default();
+        let control = RuntimeControl::new(None);
+        let (shutdown_sender, _) = broadcast::channel(1);
+        tracker.record(0, 0, Instant::now());
+        tracker.record(500, 500, Instant::now());
+        spawn_points_target(tracker, 500, control.clone(), shutdown_sender.subscribe());
+
+        // The first check runs right away
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let reason = control.stop_reason().expect("stop requested");
+        assert!(reason.contains("500"));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_points_are_not_polled_again() {
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_get_node_points()
+            .times(1)
+            .returning(|_| {
+                Err(OrchestratorError::Http {
+                    status: 404,
+                    message: String::new(),
+                    headers: Default::default(),
+                })
+            });
+        let tracker = PointsTracker::default();
+
+        poll_points(&orchestrator, 1, &tracker).await;
+        poll_points(&orchestrator, 1, &tracker).await;
+        let snapshot = tracker.snapshot(Instant::now());
+        assert!(snapshot.unavailable);
+        assert_eq!(snapshot.node_points, None);
+    }
+
+    #[test]
+    fn test_format_points() {
+        assert_eq!(format_points(0), "0");
+        assert_eq!(format_points(999), "999");
+        assert_eq!(format_points(1_000), "1,000");
+        assert_eq!(format_points(12_345_678), "12,345,678");
+    }
+}
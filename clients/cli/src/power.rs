@@ -0,0 +1,265 @@
+//! Battery-aware proving for laptops
+//!
+//! With `--battery-policy pause` or `throttle`, a background monitor polls the battery and,
+//! while the machine runs on battery below the configured charge, pauses task fetching or
+//! proves with a single worker. Normal proving resumes once AC power is back or the battery
+//! is above the threshold again.
+//!
+//! Battery state is read from `/sys/class/power_supply` on Linux and `pmset` on macOS. On
+//! other systems the battery is never detected and the policy has no effect.
+
+use crate::consts::cli_consts::power::BATTERY_POLL_INTERVAL_SECS;
+use crate::control::RuntimeControl;
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// What to do when running on battery below the threshold
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BatteryPolicy {
+    /// Keep proving at full speed
+    #[default]
+    Ignore,
+    /// Stop fetching new tasks until AC power is back
+    Pause,
+    /// Prove with a single worker until AC power is back
+    Throttle,
+}
+
+/// Settings for battery-aware proving
+#[derive(Debug, Clone, Copy)]
+pub struct BatteryConfig {
+    pub policy: BatteryPolicy,
+    /// Battery charge below which the policy applies, in percent
+    pub threshold_percent: u8,
+}
+
+/// Power-saving measure currently applied to the worker
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PowerSaving {
+    #[default]
+    Off,
+    /// Task fetching is paused
+    Paused,
+    /// Proofs run on a single worker
+    Throttled,
+}
+
+/// Battery state at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatteryStatus {
+    /// Whether the machine is running from its battery rather than AC power
+    pub on_battery: bool,
+    /// Battery charge, in percent
+    pub percent: u8,
+}
+
+impl BatteryConfig {
+    /// Power-saving measure for the given battery state.
+    /// Machines without a battery are always treated as running on AC power.
+    pub fn power_saving(&self, status: Option<BatteryStatus>) -> PowerSaving {
+        let low_battery =
+            status.is_some_and(|s| s.on_battery && s.percent < self.threshold_percent);
+        match (self.policy, low_battery) {
+            (BatteryPolicy::Pause, true) => PowerSaving::Paused,
+            (BatteryPolicy::Throttle, true) => PowerSaving::Throttled,
+            _ => PowerSaving::Off,
+        }
+    }
+}
+
+/// Read the current battery state, or `None` if there is no battery or it cannot be read.
+pub fn battery_status() -> Option<BatteryStatus> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            read_power_supplies(Path::new("/sys/class/power_supply"))
+        } else if #[cfg(target_os = "macos")] {
+            let output = std::process::Command::new("pmset")
+                .args(["-g", "batt"])
+                .output()
+                .ok()?;
+            parse_pmset(&String::from_utf8_lossy(&output.stdout))
+        } else {
+            None
+        }
+    }
+}
+
+/// Read battery state from a Linux `power_supply` class directory.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn read_power_supplies(dir: &Path) -> Option<BatteryStatus> {
+    let read = |supply: &Path, attribute: &str| {
+        std::fs::read_to_string(supply.join(attribute))
+            .map(|value| value.trim().to_string())
+            .ok()
+    };
+
+    let mut battery = None;
+    let mut mains_online = None;
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let supply = entry.path();
+        match read(&supply, "type").as_deref() {
+            Some("Battery") if battery.is_none() => {
+                let Some(percent) = read(&supply, "capacity").and_then(|c| c.parse().ok()) else {
+                    continue;
+                };
+                let discharging = read(&supply, "status").as_deref() == Some("Discharging");
+                battery = Some((percent, discharging));
+            }
+            Some("Mains") => {
+                let online = read(&supply, "online").as_deref() == Some("1");
+                mains_online = Some(mains_online.unwrap_or(false) || online);
+            }
+            _ => {}
+        }
+    }
+
+    let (percent, discharging) = battery?;
+    Some(BatteryStatus {
+        // Without an AC adapter entry, fall back to the battery's own charging status
+        on_battery: mains_online.map_or(discharging, |online| !online),
+        percent,
+    })
+}
+
+/// Parse `pmset -g batt` output, e.g.
+/// `Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1)\t85%; discharging; ...`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_pmset(output: &str) -> Option<BatteryStatus> {
+    let on_battery = output.contains("'Battery Power'");
+    let percent = output
+        .split_whitespace()
+        .find_map(|word| word.strip_suffix("%;"))
+        .and_then(|percent| percent.parse().ok())?;
+    Some(BatteryStatus {
+        on_battery,
+        percent,
+    })
+}
+
+/// Poll the battery in the background and apply `config`'s policy through `control`.
+pub fn spawn_battery_monitor(
+    config: BatteryConfig,
+    control: RuntimeControl,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(BATTERY_POLL_INTERVAL_SECS));
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = interval.tick() => {
+                    let status = tokio::task::spawn_blocking(battery_status)
+                        .await
+                        .unwrap_or(None);
+                    control.set_power_saving(config.power_saving(status));
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(policy: BatteryPolicy) -> BatteryConfig {
+        BatteryConfig {
+            policy,
+            threshold_percent: 50,
+        }
+    }
+
+    #[test]
+    fn test_power_saving() {
+        let low = Some(BatteryStatus {
+            on_battery: true,
+            percent: 30,
+        });
+        let charging = Some(BatteryStatus {
+            on_battery: false,
+            percent: 30,
+        });
+        let full = Some(BatteryStatus {
+            on_battery: true,
+            percent: 80,
+        });
+
+        assert_eq!(
+            config(BatteryPolicy::Pause).power_saving(low),
+            PowerSaving::Paused
+        );
+        assert_eq!(
+            config(BatteryPolicy::Throttle).power_saving(low),
+            PowerSaving::Throttled
+        );
+        assert_eq!(
+            config(BatteryPolicy::Ignore).power_saving(low),
+            PowerSaving::Off
+        );
+        assert_eq!(
+            config(BatteryPolicy::Pause).power_saving(charging),
+            PowerSaving::Off
+        );
+        assert_eq!(
+            config(BatteryPolicy::Pause).power_saving(full),
+            PowerSaving::Off
+        );
+        assert_eq!(
+            config(BatteryPolicy::Pause).power_saving(None),
+            PowerSaving::Off
+        );
+    }
+
+    #[test]
+    fn test_parse_pmset() {
+        let on_battery = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=4653155)\t42%; discharging; 3:12 remaining present: true\n";
+        assert_eq!(
+            parse_pmset(on_battery),
+            Some(BatteryStatus {
+                on_battery: true,
+                percent: 42
+            })
+        );
+
+        let on_ac = "Now drawing from 'AC Power'\n -InternalBattery-0 (id=4653155)\t100%; charged; 0:00 remaining present: true\n";
+        assert_eq!(
+            parse_pmset(on_ac),
+            Some(BatteryStatus {
+                on_battery: false,
+                percent: 100
+            })
+        );
+
+        assert_eq!(parse_pmset("Now drawing from 'AC Power'\n"), None);
+    }
+
+    #[test]
+    fn test_read_power_supplies() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |supply: &str, attribute: &str, value: &str| {
+            let path = dir.path().join(supply);
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(path.join(attribute), value).unwrap();
+        };
+        write("BAT0", "type", "Battery\n");
+        write("BAT0", "capacity", "35\n");
+        write("BAT0", "status", "Discharging\n");
+        write("AC", "type", "Mains\n");
+        write("AC", "online", "0\n");
+
+        assert_eq!(
+            read_power_supplies(dir.path()),
+            Some(BatteryStatus {
+                on_battery: true,
+                percent: 35
+            })
+        );
+
+        write("AC", "online", "1\n");
+        assert_eq!(
+            read_power_supplies(dir.path()).map(|status| status.on_battery),
+            Some(false)
+        );
+    }
+}
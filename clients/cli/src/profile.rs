@@ -0,0 +1,120 @@
+//! Proving profiles
+//!
+//! `--profile low-memory` tunes the CLI for Raspberry Pis and small ARM VPSs, which otherwise run
+//! out of memory on their first proof:
+//!
+//! - proving uses a single worker, whatever `--max-threads` says;
+//! - small tasks are requested unless `--max-difficulty` asks for more. The SDK offers no smaller
+//!   proving parameters, so the task size is what keeps memory use down;
+//! - the CLI runs headless unless a dashboard is requested with `--ui`;
+//! - a requested difficulty that needs more memory than the machine has is warned about.
+
+use crate::doctor::{highest_supported_difficulty, recommended_ram_gb};
+use crate::nexus_orchestrator::TaskDifficulty;
+
+/// Preset for the machine the CLI runs on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProvingProfile {
+    /// Settings from the command line only
+    #[default]
+    Default,
+    /// One worker, small tasks and no dashboard, for machines with little memory
+    LowMemory,
+}
+
+impl ProvingProfile {
+    /// Most prover workers the profile allows
+    pub fn max_workers(self) -> Option<u32> {
+        match self {
+            ProvingProfile::Default => None,
+            ProvingProfile::LowMemory => Some(1),
+        }
+    }
+
+    /// Max difficulty to request when `--max-difficulty` is not given
+    pub fn default_max_difficulty(self) -> Option<TaskDifficulty> {
+        match self {
+            ProvingProfile::Default => None,
+            ProvingProfile::LowMemory => Some(TaskDifficulty::Small),
+        }
+    }
+
+    /// Whether to run without the dashboard unless one is requested
+    pub fn headless_by_default(self) -> bool {
+        self == ProvingProfile::LowMemory
+    }
+
+    /// Warning if tasks of `difficulty` need more memory than the machine's `total_ram_gb`.
+    pub fn difficulty_warning(
+        self,
+        difficulty: TaskDifficulty,
+        total_ram_gb: f64,
+    ) -> Option<String> {
+        if self == ProvingProfile::Default {
+            return None;
+        }
+        let needed = recommended_ram_gb(difficulty);
+        if total_ram_gb >= needed as f64 {
+            return None;
+        }
+        let advice = match highest_supported_difficulty(total_ram_gb) {
+            Some(highest) => format!("use --max-difficulty {} or lower", highest.as_str_name()),
+            None => "expect proofs to fail".to_string(),
+        };
+        Some(format!(
+            "{} tasks need about {}GB RAM and this machine has {:.1}GB, so they are unlikely to \
+             finish before they expire; {}",
+            difficulty.as_str_name(),
+            needed,
+            total_ram_gb,
+            advice
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_memory_defaults() {
+        let profile = ProvingProfile::LowMemory;
+        assert_eq!(profile.max_workers(), Some(1));
+        assert_eq!(
+            profile.default_max_difficulty(),
+            Some(TaskDifficulty::Small)
+        );
+        assert!(profile.headless_by_default());
+
+        let profile = ProvingProfile::Default;
+        assert_eq!(profile.max_workers(), None);
+        assert_eq!(profile.default_max_difficulty(), None);
+        assert!(!profile.headless_by_default());
+    }
+
+    #[test]
+    fn test_difficulty_warning() {
+        let profile = ProvingProfile::LowMemory;
+        assert_eq!(profile.difficulty_warning(TaskDifficulty::Small, 4.0), None);
+
+        let warning = profile
+            .difficulty_warning(TaskDifficulty::Large, 4.0)
+            .unwrap();
+        assert!(
+            warning.contains("--max-difficulty SMALL_MEDIUM"),
+            "{}",
+            warning
+        );
+
+        assert!(
+            profile
+                .difficulty_warning(TaskDifficulty::Small, 2.0)
+                .unwrap()
+                .contains("expect proofs to fail")
+        );
+        assert_eq!(
+            ProvingProfile::Default.difficulty_warning(TaskDifficulty::Large, 4.0),
+            None
+        );
+    }
+}
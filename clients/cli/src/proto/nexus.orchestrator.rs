@@ -18,6 +18,11 @@ pub struct RegisterNodeRequest {
     /// The owner of the node.
     #[prost(string, tag = "2")]
     pub user_id: ::prost::alloc::string::String,
+    /// The node's ed25519 public key. The orchestrator binds it to the node; only
+    /// this key can deregister the node. Nodes registered without a key are bound
+    /// to the key of their first accepted proof submission.
+    #[prost(bytes = "vec", tag = "3")]
+    pub ed25519_public_key: ::prost::alloc::vec::Vec<u8>,
 }
 /// Response to a node registration request.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -26,6 +31,29 @@ pub struct RegisterNodeResponse {
     #[prost(string, tag = "1")]
     pub node_id: ::prost::alloc::string::String,
 }
+/// Retire a node, e.g. when its machine is decommissioned.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeregisterNodeRequest {
+    /// The node's ID.
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+    /// The node's ed25519 public key; must be the key bound to the node.
+    #[prost(bytes = "vec", tag = "2")]
+    pub ed25519_public_key: ::prost::alloc::vec::Vec<u8>,
+    /// Signature over "deregister | <node_id> | <timestamp_ms>", made with the
+    /// node's key.
+    #[prost(bytes = "vec", tag = "3")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    /// When the request was signed, in milliseconds since the Unix epoch. Requests
+    /// more than five minutes off the orchestrator's clock, or not newer than the
+    /// last deregistration accepted for the node, are refused, so a captured
+    /// request cannot be replayed.
+    #[prost(uint64, tag = "4")]
+    pub timestamp_ms: u64,
+}
+/// Response to a node deregistration request.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeregisterNodeResponse {}
 /// A Prover task
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Task {
@@ -80,7 +108,52 @@ pub struct GetProofTaskRequest {
     /// The maximum difficulty level the client wants to handle
     #[prost(enumeration = "TaskDifficulty", tag = "4")]
     pub max_difficulty: i32,
+    /// Take the task of this offer from PreviewProofTask; empty to take any task
+    #[prost(string, tag = "5")]
+    pub offer_id: ::prost::alloc::string::String,
+}
+/// A task offered to a node, described before the node takes it
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskOffer {
+    /// Identifies the offer when taking or declining the task
+    #[prost(string, tag = "1")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "TaskDifficulty", tag = "2")]
+    pub difficulty: i32,
+    #[prost(enumeration = "TaskType", tag = "3")]
+    pub task_type: i32,
+    /// Total size of the public inputs in bytes
+    #[prost(uint64, tag = "4")]
+    pub input_size: u64,
+    /// Estimated zkVM cycles to prove all inputs
+    #[prost(uint64, tag = "5")]
+    pub expected_cycles: u64,
+    /// Points weight of the task, relative to other tasks
+    #[prost(uint64, tag = "6")]
+    pub reward_weight: u64,
+}
+/// The task a node would be given next, held for the node until taken or declined
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreviewProofTaskResponse {
+    #[prost(message, optional, tag = "1")]
+    pub offer: ::core::option::Option<TaskOffer>,
+}
+/// Turn down an offered task, so it is offered to other nodes
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeclineProofTaskRequest {
+    /// This node's ID.
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+    /// The offer from PreviewProofTask
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+    /// Why the task was declined, e.g. the accept policy it did not match
+    #[prost(string, tag = "3")]
+    pub reason: ::prost::alloc::string::String,
 }
+/// Response to declining an offered task.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeclineProofTaskResponse {}
 /// A Prover task.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetProofTaskResponse {
@@ -115,8 +188,8 @@ pub struct SubmitProofRequest {
     #[prost(message, optional, tag = "4")]
     pub node_telemetry: ::core::option::Option<NodeTelemetry>,
     /// ZK proof of the program and first set of inputs.
-    #[prost(bytes = "vec", tag = "5")]
-    pub proof: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "bytes", tag = "5")]
+    pub proof: ::prost::bytes::Bytes,
     /// The task's ID.
     #[prost(string, tag = "6")]
     pub task_id: ::prost::alloc::string::String,
@@ -136,8 +209,20 @@ pub struct SubmitProofRequest {
     pub all_proof_hashes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     /// ZK proofs of the program running on each set of inputs.
     /// To be sent on PROOF_REQUIRED tasks, empty on other task types.
-    #[prost(bytes = "vec", repeated, tag = "10")]
-    pub proofs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bytes = "bytes", repeated, tag = "10")]
+    pub proofs: ::prost::alloc::vec::Vec<::prost::bytes::Bytes>,
+    /// Per-node submission counter, strictly increasing across submissions
+    /// and reused by retries of the same submission. When non-zero, the
+    /// signature covers it: "1 | task_id | proof_hash | nonce".
+    #[prost(uint64, tag = "11")]
+    pub nonce: u64,
+    /// Hex SHA-256 of "task_id | proof_hash", the same for every attempt to
+    /// submit the same proof. A submission whose key was already accepted is
+    /// answered with 409 Conflict naming the ProofAlreadyAccepted error
+    /// (ALREADY_EXISTS over gRPC), or as accepted
+    /// within a batch, instead of being counted again.
+    #[prost(string, tag = "12")]
+    pub idempotency_key: ::prost::alloc::string::String,
 }
 /// Performance stats of a node.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -154,6 +239,26 @@ pub struct NodeTelemetry {
     /// Geo location of the node
     #[prost(string, optional, tag = "4")]
     pub location: ::core::option::Option<::prost::alloc::string::String>,
+    /// GPUs detected on the node
+    #[prost(message, repeated, tag = "5")]
+    pub accelerators: ::prost::alloc::vec::Vec<Accelerator>,
+    /// Operator-assigned labels, e.g. "site" => "home-lab"
+    #[prost(map = "string, string", tag = "6")]
+    pub labels:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+}
+/// A GPU detected on a node.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Accelerator {
+    /// Device name, e.g. "NVIDIA GeForce RTX 4090"
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// Compute backend available for the device: "cuda", "rocm" or "metal"
+    #[prost(string, tag = "2")]
+    pub backend: ::prost::alloc::string::String,
+    /// Dedicated video memory in MB, unset for unified memory
+    #[prost(uint64, optional, tag = "3")]
+    pub vram_mb: ::core::option::Option<u64>,
 }
 /// Node information
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -164,6 +269,12 @@ pub struct Node {
     /// The type of node
     #[prost(enumeration = "NodeType", tag = "2")]
     pub node_type: i32,
+    /// When the node last fetched a task or submitted a proof; unset if never
+    #[prost(message, optional, tag = "3")]
+    pub last_seen: ::core::option::Option<::prost_types::Timestamp>,
+    /// Points earned by the node
+    #[prost(uint64, tag = "4")]
+    pub points: u64,
 }
 /// Response to get a single node by ID
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -172,6 +283,16 @@ pub struct GetNodeResponse {
     #[prost(string, tag = "1")]
     pub wallet_address: ::prost::alloc::string::String,
 }
+/// Response to get the point totals of a node
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetNodePointsResponse {
+    /// Points earned by the node
+    #[prost(uint64, tag = "1")]
+    pub node_points: u64,
+    /// Points earned by all nodes of the node owner's wallet
+    #[prost(uint64, tag = "2")]
+    pub wallet_points: u64,
+}
 /// Response returning all nodes associated with a user
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UserResponse {
@@ -188,6 +309,128 @@ pub struct UserResponse {
     #[prost(string, tag = "4")]
     pub wallet_address: ::prost::alloc::string::String,
 }
+/// Get the user registered with a wallet address
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetUserRequest {
+    /// The user's wallet public address.
+    #[prost(string, tag = "1")]
+    pub wallet_address: ::prost::alloc::string::String,
+    /// `nodes_next_cursor` of the previous response, to fetch the next page of nodes
+    #[prost(string, tag = "2")]
+    pub nodes_cursor: ::prost::alloc::string::String,
+}
+/// Response to a user registration request.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RegisterUserResponse {}
+/// Get a single node by ID
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNodeRequest {
+    /// The node's ID.
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+}
+/// Get the point totals of a node
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNodePointsRequest {
+    /// The node's ID.
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+}
+/// Get network-wide prover statistics and the standing of a node
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNetworkStatsRequest {
+    /// The node's ID.
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+}
+/// Tasks waiting to be proved at one difficulty
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DifficultySupply {
+    #[prost(enumeration = "TaskDifficulty", tag = "1")]
+    pub difficulty: i32,
+    /// Number of tasks not yet assigned to a node
+    #[prost(uint64, tag = "2")]
+    pub pending_tasks: u64,
+}
+/// Network-wide prover statistics and the standing of a node
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNetworkStatsResponse {
+    /// Provers that submitted a proof in the last 24 hours
+    #[prost(uint64, tag = "1")]
+    pub active_provers: u64,
+    /// Rank of the node by points, 1 for the most points; 0 if the node is not ranked yet
+    #[prost(uint64, tag = "2")]
+    pub node_rank: u64,
+    /// Number of ranked nodes
+    #[prost(uint64, tag = "3")]
+    pub ranked_nodes: u64,
+    /// Tasks waiting to be proved, by difficulty
+    #[prost(message, repeated, tag = "4")]
+    pub task_supply: ::prost::alloc::vec::Vec<DifficultySupply>,
+}
+/// Response to a proof submission.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SubmitProofResponse {}
+/// Submit several proofs in one request.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmitProofsBatchRequest {
+    /// The proofs, each signed as if submitted on its own.
+    #[prost(message, repeated, tag = "1")]
+    pub submissions: ::prost::alloc::vec::Vec<SubmitProofRequest>,
+}
+/// Outcome of a single proof in a batch.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmitProofResult {
+    /// The task's ID.
+    #[prost(string, tag = "1")]
+    pub task_id: ::prost::alloc::string::String,
+    /// Whether the proof was accepted.
+    #[prost(bool, tag = "2")]
+    pub accepted: bool,
+    /// Why the proof was rejected; empty if accepted.
+    #[prost(string, tag = "3")]
+    pub error: ::prost::alloc::string::String,
+}
+/// Response to a batched proof submission.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmitProofsBatchResponse {
+    /// One result per submitted proof.
+    #[prost(message, repeated, tag = "1")]
+    pub results: ::prost::alloc::vec::Vec<SubmitProofResult>,
+}
+/// Ask whether submitted tasks were credited.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTaskReceiptsRequest {
+    /// The node's ID.
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+    /// The tasks to look up.
+    #[prost(string, repeated, tag = "2")]
+    pub task_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Crediting state of a single task.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskReceipt {
+    /// The task's ID.
+    #[prost(string, tag = "1")]
+    pub task_id: ::prost::alloc::string::String,
+    /// Where the task stands.
+    #[prost(enumeration = "TaskReceiptState", tag = "2")]
+    pub state: i32,
+    /// Points credited for the task; zero unless credited.
+    #[prost(uint64, tag = "3")]
+    pub points: u64,
+    /// Why the task was rejected; empty otherwise.
+    #[prost(string, tag = "4")]
+    pub reason: ::prost::alloc::string::String,
+}
+/// Response to a receipt lookup.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTaskReceiptsResponse {
+    /// One receipt per requested task that the orchestrator knows of.
+    #[prost(message, repeated, tag = "1")]
+    pub receipts: ::prost::alloc::vec::Vec<TaskReceipt>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum NodeType {
@@ -299,3 +542,40 @@ impl TaskType {
         }
     }
 }
+/// Where a submitted task stands in crediting points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TaskReceiptState {
+    /// The orchestrator has no record of a submission for the task.
+    NotFound = 0,
+    /// The submission was accepted but points have not been credited yet.
+    Pending = 1,
+    /// Points for the task were credited to the node.
+    Credited = 2,
+    /// The submission was accepted, then rejected during verification.
+    Rejected = 3,
+}
+impl TaskReceiptState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::NotFound => "NOT_FOUND",
+            Self::Pending => "PENDING",
+            Self::Credited => "CREDITED",
+            Self::Rejected => "REJECTED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "NOT_FOUND" => Some(Self::NotFound),
+            "PENDING" => Some(Self::Pending),
+            "CREDITED" => Some(Self::Credited),
+            "REJECTED" => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Content-addressed proof cache
+//!
+//! Proofs are stored on disk under the keccak hash of the program ID and the raw input bytes,
+//! so a task that repeats an already proven `(program_id, inputs)` pair reuses the proof
+//! instead of proving it again. Unreadable entries are treated as misses. Once the cache
+//! exceeds its size limit, the least recently written proofs are removed.
+
+use crate::consts::cli_consts::PROOF_CACHE_MAX_BYTES;
+use crate::storage::prune_oldest;
+use nexus_sdk::stwo::seq::Proof;
+use sha3::{Digest, Keccak256};
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct ProofCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl ProofCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_bytes: PROOF_CACHE_MAX_BYTES,
+        }
+    }
+
+    /// `~/.nexus/proof-cache`, next to the config file
+    pub fn default_dir() -> Option<PathBuf> {
+        crate::config::nexus_dir()
+            .ok()
+            .map(|dir| dir.join("proof-cache"))
+    }
+
+    /// Cache key of a program run on a set of inputs
+    pub fn key(program_id: &str, inputs: &[u8]) -> String {
+        let mut hasher = Keccak256::new();
+        hasher.update(program_id.as_bytes());
+        // Separator so that ("ab", "c") and ("a", "bc") hash differently
+        hasher.update([0u8]);
+        hasher.update(inputs);
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.proof", key))
+    }
+
+    /// Cached proof for the key, if present and readable
+    pub async fn get(&self, key: &str) -> Option<Proof> {
+        let bytes = tokio::fs::read(self.path(key)).await.ok()?;
+        postcard::from_bytes(&bytes).ok()
+    }
+
+    /// Store a proof, then trim the cache to its size limit
+    pub async fn put(&self, key: &str, proof: &Proof) -> io::Result<()> {
+        let bytes = postcard::to_allocvec(proof).map_err(io::Error::other)?;
+        tokio::fs::create_dir_all(&self.dir).await?;
+
+        // Write to a temporary file first so concurrent readers never see a partial proof
+        let tmp_path = self.dir.join(format!("{}.tmp", key));
+        tokio::fs::write(&tmp_path, &bytes).await?;
+        tokio::fs::rename(&tmp_path, self.path(key)).await?;
+
+        let dir = self.dir.clone();
+        let max_bytes = self.max_bytes;
+        tokio::task::spawn_blocking(move || evict_oldest(&dir, max_bytes))
+            .await
+            .map_err(io::Error::other)?
+    }
+}
+
+/// Remove the oldest proofs until the directory holds at most `max_bytes` of them.
+fn evict_oldest(dir: &Path, max_bytes: u64) -> io::Result<()> {
+    prune_oldest(dir, max_bytes, |path| {
+        path.extension().is_some_and(|ext| ext == "proof")
+    })
+    .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_key_depends_on_program_and_inputs() {
+        let key = ProofCache::key("fib_input_initial", &[1, 2, 3]);
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, ProofCache::key("fib_input_initial", &[1, 2, 3]));
+        assert_ne!(key, ProofCache::key("fib_input_initial", &[1, 2, 4]));
+        assert_ne!(key, ProofCache::key("other_program", &[1, 2, 3]));
+        assert_ne!(ProofCache::key("ab", b"c"), ProofCache::key("a", b"bc"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_and_corrupt_entries_are_misses() {
+        let dir = TempDir::new().unwrap();
+        let cache = ProofCache::new(dir.path());
+        let key = ProofCache::key("fib_input_initial", &[1, 2, 3]);
+        assert!(cache.get(&key).await.is_none());
+
+        std::fs::write(cache.path(&key), b"not a proof").unwrap();
+        assert!(cache.get(&key).await.is_none());
+    }
+
+    #[test]
+    fn test_evict_oldest_removes_oldest_first() {
+        let dir = TempDir::new().unwrap();
+        let now = SystemTime::now();
+        for (i, name) in ["old", "middle", "new"].iter().enumerate() {
+            let path = dir.path().join(format!("{}.proof", name));
+            let file = std::fs::File::create(&path).unwrap();
+            file.set_len(100).unwrap();
+            file.set_modified(now - Duration::from_secs(100 * (3 - i as u64)))
+                .unwrap();
+        }
+
+        evict_oldest(dir.path(), 250).unwrap();
+
+        assert!(!dir.path().join("old.proof").exists());
+        assert!(dir.path().join("middle.proof").exists());
+        assert!(dir.path().join("new.proof").exists());
+    }
+}
@@ -2,18 +2,23 @@
 
 use crate::prover::verifier;
 
+use super::pool::{self, ServerReply};
 use super::types::ProverError;
 use crate::analytics::track_likely_oom_error;
 use crate::environment::Environment;
 use crate::task::Task;
+use bytes::Bytes;
 use nexus_sdk::{
     Local, Prover,
     stwo::seq::{Proof, Stwo},
 };
 use postcard::from_bytes;
-use serde_json;
-use std::env;
-use std::process::Stdio;
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+use sysinfo::{Pid, ProcessesToUpdate, Signal, System};
+
+/// Running prover subprocesses, and whether they are suspended
+static PROVER_PROCESSES: Mutex<(HashSet<u32>, bool)> = Mutex::new((HashSet::new(), false));
 
 /// Core proving engine for ZK proof generation
 pub struct ProvingEngine;
@@ -30,9 +35,33 @@ impl ProvingEngine {
         })
     }
 
+    /// The fibonacci prover used to verify proofs, loaded on first use
+    pub fn fib_verifier() -> Result<&'static Stwo<Local>, ProverError> {
+        static VERIFIER: OnceLock<Stwo<Local>> = OnceLock::new();
+        if let Some(verifier) = VERIFIER.get() {
+            return Ok(verifier);
+        }
+        let verifier = Self::create_fib_prover()?;
+        Ok(VERIFIER.get_or_init(|| verifier))
+    }
+
     /// Subprocess entrypoint: generate proof without verification
     pub fn prove_fib_subprocess(inputs: &(u32, u32, u32)) -> Result<Proof, ProverError> {
-        let prover = Self::create_fib_prover()?;
+        Self::prove_fib(Self::create_fib_prover()?, inputs)
+    }
+
+    /// Generate proof without verification, reusing the guest program loaded by `template`
+    pub fn prove_fib_with(
+        template: &Stwo<Local>,
+        inputs: &(u32, u32, u32),
+    ) -> Result<Proof, ProverError> {
+        let prover = Stwo::<Local>::new(&template.elf).map_err(|e| {
+            ProverError::Stwo(format!("Failed to set up fib_input_initial prover: {}", e))
+        })?;
+        Self::prove_fib(prover, inputs)
+    }
+
+    fn prove_fib(prover: Stwo<Local>, inputs: &(u32, u32, u32)) -> Result<Proof, ProverError> {
         let (view, proof) = prover
             .prove_with_input::<(), (u32, u32, u32)>(&(), inputs)
             .map_err(|e| {
@@ -47,57 +76,96 @@ impl ProvingEngine {
         Ok(proof)
     }
 
-    /// Generate proof for given inputs using the fibonacci program in a subprocess
+    /// Generate proof for given inputs using the fibonacci program in a prover subprocess,
+    /// pinned to `core` if given, returning it along with its postcard encoding. The subprocess
+    /// is kept for later proofs while fewer than `num_workers` are idle.
     pub async fn prove_and_validate(
         inputs: &(u32, u32, u32),
         task: &Task,
         environment: &Environment,
         client_id: &str,
-    ) -> Result<Proof, ProverError> {
-        // Spawn a subprocess for proof generation to isolate memory usage
-        let exe_path = env::current_exe()?;
-        let mut cmd = tokio::process::Command::new(exe_path);
-        cmd.arg("prove-fib-subprocess")
-            .arg("--inputs")
-            .arg(serde_json::to_string(inputs)?)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::inherit());
-
-        let output = cmd.output().await?;
-
-        if !output.status.success() {
-            if let Some(code) = output.status.code() {
-                if code == crate::consts::cli_consts::SUBPROCESS_SUSPECTED_OOM_CODE {
-                    // 128 + 9 = 137 means external sigkill, so likely killed by kernel due to OOM; track analytics event
-                    tokio::spawn(track_likely_oom_error(
-                        task.clone(),
-                        environment.clone(),
-                        client_id.to_string(),
-                    ));
-                }
-
-                if code == crate::consts::cli_consts::SUBPROCESS_INTERNAL_ERROR_CODE {
-                    // error happened inside the subprocess, and so we know that it may be useful information to the user
-                    return Err(ProverError::Subprocess(format!(
-                        "Error while proving within subprocess, captured error: [{}]",
-                        &String::from_utf8_lossy(&output.stderr)
-                    )));
-                }
+        core: Option<usize>,
+        num_workers: usize,
+    ) -> Result<(Proof, Bytes), ProverError> {
+        // Prove in a subprocess to isolate memory usage
+        let reply = pool::prove(inputs, core, num_workers).await?;
+        if let ServerReply::Exited(status) = &reply {
+            if status.code() == Some(crate::consts::cli_consts::SUBPROCESS_SUSPECTED_OOM_CODE) {
+                // 128 + 9 = 137 means external sigkill, so likely killed by kernel due to OOM; track analytics event
+                tokio::spawn(track_likely_oom_error(
+                    task.clone(),
+                    environment.clone(),
+                    client_id.to_string(),
+                ));
             }
-
-            return Err(ProverError::Subprocess(format!(
-                "Prover subprocess failed with status: {}",
-                output.status
-            )));
         }
+        let proof_bytes = reply.into_proof()?;
+        let proof = Self::verify_fib_proof(&proof_bytes, inputs)?;
+        Ok((proof, Bytes::from(proof_bytes)))
+    }
 
-        // Deserialize proof from subprocess stdout
-        let proof: Proof = from_bytes(&output.stdout)?;
-
-        // Verify proof in main process
-        let verify_prover = Self::create_fib_prover()?;
-        verifier::ProofVerifier::verify_proof(&proof, inputs, &verify_prover)?;
+    /// Prove `inputs` in a prover subprocess and verify the proof, without a task, e.g. to check
+    /// that proving works before a session starts. The subprocess is not kept.
+    pub async fn prove_warm_up(inputs: &(u32, u32, u32)) -> Result<(), ProverError> {
+        let proof_bytes = pool::prove(inputs, None, 0).await?.into_proof()?;
+        Self::verify_fib_proof(&proof_bytes, inputs)?;
+        Ok(())
+    }
 
+    /// Decode a proof from a prover subprocess and verify it in this process.
+    fn verify_fib_proof(
+        proof_bytes: &[u8],
+        inputs: &(u32, u32, u32),
+    ) -> Result<Proof, ProverError> {
+        let proof: Proof = from_bytes(proof_bytes)?;
+        verifier::ProofVerifier::verify_proof(&proof, inputs, Self::fib_verifier()?)?;
         Ok(proof)
     }
 }
+
+/// Record a prover subprocess, suspending it straight away if proving is suspended
+pub(super) fn track_prover_process(pid: u32) {
+    let mut processes = PROVER_PROCESSES.lock().unwrap_or_else(|e| e.into_inner());
+    processes.0.insert(pid);
+    if processes.1 {
+        signal_processes(&[pid], Signal::Stop);
+    }
+}
+
+/// Forget a prover subprocess that has exited or is about to be killed
+pub(super) fn untrack_prover_process(pid: u32) {
+    let mut processes = PROVER_PROCESSES.lock().unwrap_or_else(|e| e.into_inner());
+    processes.0.remove(&pid);
+}
+
+/// Suspend the running prover subprocesses, and any started later, or resume them.
+///
+/// Suspending relies on `SIGSTOP` and `SIGCONT`, so has no effect on Windows.
+pub fn suspend_prover_processes(suspend: bool) {
+    let mut processes = PROVER_PROCESSES.lock().unwrap_or_else(|e| e.into_inner());
+    if processes.1 == suspend {
+        return;
+    }
+    processes.1 = suspend;
+    let pids: Vec<u32> = processes.0.iter().copied().collect();
+    let signal = if suspend {
+        Signal::Stop
+    } else {
+        Signal::Continue
+    };
+    signal_processes(&pids, signal);
+}
+
+fn signal_processes(pids: &[u32], signal: Signal) {
+    if pids.is_empty() {
+        return;
+    }
+    let pids: Vec<Pid> = pids.iter().copied().map(Pid::from_u32).collect();
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&pids), true);
+    for pid in pids {
+        if let Some(process) = system.process(pid) {
+            process.kill_with(signal);
+        }
+    }
+}
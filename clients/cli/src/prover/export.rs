@@ -0,0 +1,156 @@
+//! Proof export
+//!
+//! With `start --export-proofs <DIR>`, every accepted proof is written to `DIR` as
+//! `<task>-<input>.proof`, the postcard-encoded proof as submitted, next to `<task>-<input>.json`
+//! describing it. `nexus-cli verify-proof <FILE>` checks an exported proof again against the guest
+//! program embedded in the CLI.
+
+use super::engine::ProvingEngine;
+use super::input::InputParser;
+use super::types::{ProverError, ProverResult};
+use super::verifier::ProofVerifier;
+use crate::task::Task;
+use nexus_sdk::stwo::seq::Proof;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::path::{Path, PathBuf};
+
+/// Program whose proofs can be verified locally
+const FIB_PROGRAM_ID: &str = "fib_input_initial";
+
+/// Description of an exported proof, stored next to it as JSON
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProofMetadata {
+    pub task_id: String,
+    /// Position of the proven input within the task
+    pub input_index: usize,
+    pub program_id: String,
+    /// Keccak-256 of the proof file
+    pub proof_hash: String,
+    /// Proof hash submitted for the whole task
+    pub combined_hash: String,
+    /// Public inputs `(n, init_a, init_b)` the proof was generated for
+    pub inputs: (u32, u32, u32),
+    /// Keccak-256 of the raw input bytes as received from the orchestrator
+    pub inputs_digest: String,
+    pub cli_version: String,
+    /// RFC 3339 time of the export
+    pub exported_at: String,
+}
+
+/// `<dir>/<task>-<input>` without extension; the task ID is sanitized for use in a file name
+fn export_stem(dir: &Path, task_id: &str, input_index: usize) -> PathBuf {
+    let task_id: String = task_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{}-{}", task_id, input_index))
+}
+
+/// Write every proof of an accepted task to `dir`, returning the paths of the proof files.
+pub async fn export_proofs(
+    dir: &Path,
+    task: &Task,
+    result: &ProverResult,
+) -> Result<Vec<PathBuf>, ProverError> {
+    tokio::fs::create_dir_all(dir).await?;
+    let mut paths = Vec::with_capacity(result.proof_bytes.len());
+    for (input_index, (bytes, input)) in
+        result.proof_bytes.iter().zip(task.all_inputs()).enumerate()
+    {
+        let metadata = ProofMetadata {
+            task_id: task.task_id.clone(),
+            input_index,
+            program_id: task.program_id.clone(),
+            proof_hash: format!("{:x}", Keccak256::digest(bytes)),
+            combined_hash: result.combined_hash.clone(),
+            inputs: InputParser::parse_triple_input(input)?,
+            inputs_digest: format!("{:x}", Keccak256::digest(input)),
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: crate::clock::utc_now().to_rfc3339(),
+        };
+
+        let stem = export_stem(dir, &task.task_id, input_index);
+        let proof_path = stem.with_extension("proof");
+        tokio::fs::write(&proof_path, bytes).await?;
+        tokio::fs::write(
+            stem.with_extension("json"),
+            serde_json::to_vec_pretty(&metadata)?,
+        )
+        .await?;
+        paths.push(proof_path);
+    }
+    Ok(paths)
+}
+
+/// Verify an exported proof, given the path of its `.proof` or `.json` file.
+pub fn verify_exported_proof(path: &Path) -> Result<ProofMetadata, ProverError> {
+    let metadata: ProofMetadata =
+        serde_json::from_slice(&std::fs::read(path.with_extension("json"))?)?;
+    if metadata.program_id != FIB_PROGRAM_ID {
+        return Err(ProverError::MalformedTask(format!(
+            "Unsupported program ID: {}",
+            metadata.program_id
+        )));
+    }
+
+    let bytes = std::fs::read(path.with_extension("proof"))?;
+    let proof_hash = format!("{:x}", Keccak256::digest(&bytes));
+    if proof_hash != metadata.proof_hash {
+        return Err(ProverError::ExportMismatch(format!(
+            "proof hash is {}, metadata says {}",
+            proof_hash, metadata.proof_hash
+        )));
+    }
+
+    let proof: Proof = postcard::from_bytes(&bytes)?;
+    ProofVerifier::verify_proof(&proof, &metadata.inputs, ProvingEngine::fib_verifier()?)?;
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_stem() {
+        let stem = export_stem(Path::new("/tmp/proofs"), "task/42", 1);
+        assert_eq!(stem, Path::new("/tmp/proofs/task_42-1"));
+        assert_eq!(
+            stem.with_extension("proof"),
+            Path::new("/tmp/proofs/task_42-1.proof")
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_proof() {
+        let dir = tempfile::tempdir().unwrap();
+        let stem = export_stem(dir.path(), "task", 0);
+        let metadata = ProofMetadata {
+            task_id: "task".to_string(),
+            input_index: 0,
+            program_id: FIB_PROGRAM_ID.to_string(),
+            proof_hash: "00".to_string(),
+            combined_hash: "00".to_string(),
+            inputs: (10, 1, 1),
+            inputs_digest: "00".to_string(),
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: "2025-01-01T00:00:00+00:00".to_string(),
+        };
+        std::fs::write(stem.with_extension("proof"), b"not a proof").unwrap();
+        std::fs::write(
+            stem.with_extension("json"),
+            serde_json::to_vec(&metadata).unwrap(),
+        )
+        .unwrap();
+
+        let error = verify_exported_proof(&stem.with_extension("proof")).unwrap_err();
+        assert!(matches!(error, ProverError::ExportMismatch(_)));
+    }
+}
@@ -1,17 +1,28 @@
 //! High-level proving interface
 
+use super::cache::ProofCache;
 use super::pipeline::ProvingPipeline;
-use super::types::ProverError;
+use super::types::{ProverError, ProverResult};
 use crate::environment::Environment;
 use crate::task::Task;
-use nexus_sdk::stwo::seq::Proof;
 
-/// Proves a program with authenticated task inputs
+/// Proves a program with authenticated task inputs, reusing cached proofs if a cache is given
+/// and pinning proofs to `pinned_cores` if set
 pub async fn authenticated_proving(
     task: &Task,
     environment: &Environment,
     client_id: &str,
     num_workers: usize,
-) -> Result<(Vec<Proof>, String, Vec<String>), ProverError> {
-    ProvingPipeline::prove_authenticated(task, environment, client_id, num_workers).await
+    cache: Option<&ProofCache>,
+    pinned_cores: Option<&[usize]>,
+) -> Result<ProverResult, ProverError> {
+    ProvingPipeline::prove_authenticated(
+        task,
+        environment,
+        client_id,
+        num_workers,
+        cache,
+        pinned_cores,
+    )
+    .await
 }
@@ -29,3 +29,35 @@ impl InputParser {
         Ok((n, init_a, init_b))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_parse_triple_input_never_panics(
+            data in proptest::collection::vec(any::<u8>(), 0..64)
+        ) {
+            let result = InputParser::parse_triple_input(&data);
+            if data.len() < 12 {
+                prop_assert!(matches!(result, Err(ProverError::MalformedTask(_))));
+            } else {
+                prop_assert!(result.is_ok());
+            }
+        }
+
+        #[test]
+        fn test_parse_triple_input_round_trip(n: u32, init_a: u32, init_b: u32) {
+            let data: Vec<u8> = [n, init_a, init_b]
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect();
+            prop_assert_eq!(
+                InputParser::parse_triple_input(&data).unwrap(),
+                (n, init_a, init_b)
+            );
+        }
+    }
+}
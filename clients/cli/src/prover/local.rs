@@ -0,0 +1,246 @@
+//! Standalone proving
+//!
+//! `nexus-cli prove` proves a single input outside the orchestrator flow, either with the embedded
+//! guest program or with a RISC-V ELF built from `programs/`, and reports how long that took and
+//! how much memory it needed.
+
+use super::engine::ProvingEngine;
+use super::types::ProverError;
+use super::verifier::ProofVerifier;
+use crate::failures::from_hex;
+use nexus_sdk::stwo::seq::{Proof, Stwo};
+use nexus_sdk::{Local, Prover, Viewable};
+use serde::{Serialize, Serializer, ser::SerializeTuple};
+use sha3::{Digest, Keccak256};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// How often the memory of the proving process is sampled
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A proof generated by [`prove_locally`] or [`prove_elf`]
+pub struct LocalProof {
+    /// Postcard encoding of the proof, as it would be submitted
+    pub bytes: Vec<u8>,
+    /// Keccak-256 of `bytes`, as computed for submissions
+    pub proof_hash: String,
+    /// Output the guest program printed while running
+    pub logs: Vec<String>,
+    /// Time taken to generate and verify the proof
+    pub duration: Duration,
+    /// Highest memory use of the process seen while proving, in bytes
+    pub peak_memory_bytes: u64,
+}
+
+/// Input passed to a guest program, encoded the way `nexus_rt` reads it
+///
+/// Written on the command line as:
+/// - `u32:9,1,1` (or just `9,1,1`): a tuple of `u32`s, read by the guest as `(u32, u32, u32)`
+/// - `hex:deadbeef`: a byte string, read by the guest as `Vec<u8>`
+/// - `file:path/to/input.bin`: the contents of a file, read by the guest as `Vec<u8>`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum GuestInput {
+    /// No input, read by the guest as `()`
+    #[default]
+    None,
+    U32s(Vec<u32>),
+    Bytes(Vec<u8>),
+}
+
+impl Serialize for GuestInput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::None => serializer.serialize_unit(),
+            Self::U32s(values) => {
+                let mut tuple = serializer.serialize_tuple(values.len())?;
+                for value in values {
+                    tuple.serialize_element(value)?;
+                }
+                tuple.end()
+            }
+            Self::Bytes(bytes) => serializer.serialize_bytes(bytes),
+        }
+    }
+}
+
+impl FromStr for GuestInput {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (kind, data) = value.split_once(':').unwrap_or(("u32", value));
+        match kind {
+            "u32" => data
+                .split(',')
+                .map(|part| part.trim().parse::<u32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map(Self::U32s)
+                .map_err(|e| format!("'{}' is not a list of numbers: {}", data, e)),
+            "hex" => {
+                let hex = data.trim().trim_start_matches("0x");
+                from_hex(hex)
+                    .map(Self::Bytes)
+                    .map_err(|e| format!("'{}' is not hex: {}", data, e))
+            }
+            "file" => std::fs::read(data)
+                .map(Self::Bytes)
+                .map_err(|e| format!("Cannot read {}: {}", data, e)),
+            _ => Err(format!(
+                "unknown input kind '{}', expected u32:, hex: or file:",
+                kind
+            )),
+        }
+    }
+}
+
+/// Parse Fibonacci inputs written as `n,init_a,init_b`, e.g. `9,1,1`.
+pub fn parse_fib_inputs(value: &str) -> Result<(u32, u32, u32), String> {
+    match value.parse::<GuestInput>()? {
+        GuestInput::U32s(values) => match values[..] {
+            [n, init_a, init_b] => Ok((n, init_a, init_b)),
+            _ => Err(format!(
+                "expected three numbers (n,init_a,init_b), got {}",
+                values.len()
+            )),
+        },
+        _ => Err("expected three numbers (n,init_a,init_b)".to_string()),
+    }
+}
+
+/// Prove and verify `inputs` with the Fibonacci guest program in this process.
+pub async fn prove_locally(inputs: (u32, u32, u32)) -> Result<LocalProof, ProverError> {
+    measure(move || {
+        let proof = ProvingEngine::prove_fib_subprocess(&inputs)?;
+        ProofVerifier::verify_proof(&proof, &inputs, ProvingEngine::fib_verifier()?)?;
+        Ok((proof, Vec::new()))
+    })
+    .await
+}
+
+/// Prove the guest program in the ELF file at `elf` in this process.
+///
+/// The proof is not verified, since the expected output of an arbitrary guest is not known; a
+/// guest that exits with a non-zero code is reported as an error.
+pub async fn prove_elf(
+    elf: PathBuf,
+    private_input: GuestInput,
+    public_input: GuestInput,
+) -> Result<LocalProof, ProverError> {
+    measure(move || {
+        let prover = Stwo::<Local>::new_from_file(&elf).map_err(|e| {
+            ProverError::Stwo(format!(
+                "Failed to load guest program {}: {}",
+                elf.display(),
+                e
+            ))
+        })?;
+        let (view, proof) = prover
+            .prove_with_input::<GuestInput, GuestInput>(&private_input, &public_input)
+            .map_err(|e| ProverError::Stwo(format!("Failed to generate proof: {}", e)))?;
+        ProofVerifier::check_exit_code(&view)?;
+        let logs = view
+            .logs()
+            .map_err(|e| ProverError::GuestProgram(format!("Failed to read guest logs: {}", e)))?;
+        Ok((proof, logs))
+    })
+    .await
+}
+
+/// Run `prove` on a blocking thread, timing it and tracking the peak memory use of the process.
+async fn measure<F>(prove: F) -> Result<LocalProof, ProverError>
+where
+    F: FnOnce() -> Result<(Proof, Vec<String>), ProverError> + Send + 'static,
+{
+    let peak_memory = Arc::new(AtomicU64::new(0));
+    let done = Arc::new(AtomicBool::new(false));
+    let sampler = tokio::spawn(sample_peak_memory(peak_memory.clone(), done.clone()));
+
+    let start = Instant::now();
+    let result = tokio::task::spawn_blocking(prove)
+        .await
+        .map_err(ProverError::JoinError);
+    let duration = start.elapsed();
+
+    done.store(true, Ordering::Relaxed);
+    let _ = sampler.await;
+
+    let (proof, logs) = result??;
+    let bytes = postcard::to_allocvec(&proof)?;
+    Ok(LocalProof {
+        proof_hash: format!("{:x}", Keccak256::digest(&bytes)),
+        bytes,
+        logs,
+        duration,
+        peak_memory_bytes: peak_memory.load(Ordering::Relaxed),
+    })
+}
+
+/// Record the highest memory use of this process in `peak` until `done` is set.
+async fn sample_peak_memory(peak: Arc<AtomicU64>, done: Arc<AtomicBool>) {
+    let pid = Pid::from_u32(std::process::id());
+    let mut system = System::new();
+    loop {
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing().with_memory(),
+        );
+        if let Some(process) = system.process(pid) {
+            peak.fetch_max(process.memory(), Ordering::Relaxed);
+        }
+        if done.load(Ordering::Relaxed) {
+            return;
+        }
+        tokio::time::sleep(MEMORY_SAMPLE_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fib_inputs() {
+        assert_eq!(parse_fib_inputs("9,1,1"), Ok((9, 1, 1)));
+        assert_eq!(parse_fib_inputs(" 10, 2 ,3"), Ok((10, 2, 3)));
+        assert!(parse_fib_inputs("9,1").is_err());
+        assert!(parse_fib_inputs("9,1,x").is_err());
+        assert!(parse_fib_inputs("9,1,1,1").is_err());
+    }
+
+    #[test]
+    fn test_guest_input_encoding() {
+        // A u32 tuple encodes exactly like the typed tuple the guest reads
+        let input: GuestInput = "u32:9,1,1".parse().unwrap();
+        assert_eq!(
+            postcard::to_allocvec(&input).unwrap(),
+            postcard::to_allocvec(&(9u32, 1u32, 1u32)).unwrap()
+        );
+
+        let input: GuestInput = "hex:0xdead".parse().unwrap();
+        assert_eq!(input, GuestInput::Bytes(vec![0xde, 0xad]));
+        assert_eq!(
+            postcard::to_allocvec(&input).unwrap(),
+            postcard::to_allocvec(&vec![0xdeu8, 0xad]).unwrap()
+        );
+
+        assert_eq!(
+            postcard::to_allocvec(&GuestInput::None).unwrap(),
+            postcard::to_allocvec(&()).unwrap()
+        );
+        assert!("hex:abc".parse::<GuestInput>().is_err());
+        assert!("str:abc".parse::<GuestInput>().is_err());
+    }
+
+    #[test]
+    fn test_guest_input_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.bin");
+        std::fs::write(&path, [1, 2, 3]).unwrap();
+        let input: GuestInput = format!("file:{}", path.display()).parse().unwrap();
+        assert_eq!(input, GuestInput::Bytes(vec![1, 2, 3]));
+    }
+}
@@ -1,9 +1,16 @@
+pub mod cache;
 pub mod engine;
+pub mod export;
 pub mod handlers;
 pub mod input;
+pub mod local;
 pub mod pipeline;
+pub mod pool;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod types;
 pub mod verifier;
 
+pub use cache::ProofCache;
 pub use handlers::authenticated_proving;
 pub use types::{ProverError, ProverResult};
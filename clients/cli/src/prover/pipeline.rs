@@ -2,14 +2,16 @@
 
 use std::sync::Arc;
 
+use super::cache::ProofCache;
 use super::engine::ProvingEngine;
 use super::input::InputParser;
-use super::types::ProverError;
+use super::types::{ProverError, ProverResult};
+use crate::affinity::{CoreLease, CorePool};
 use crate::analytics::track_verification_failed;
 use crate::environment::Environment;
 use crate::task::Task;
+use bytes::Bytes;
 use futures::future::join_all;
-use nexus_sdk::stwo::seq::Proof;
 use sha3::{Digest, Keccak256};
 use tokio_util::sync::CancellationToken;
 
@@ -23,10 +25,20 @@ impl ProvingPipeline {
         environment: &Environment,
         client_id: &str,
         num_workers: usize,
-    ) -> Result<(Vec<Proof>, String, Vec<String>), ProverError> {
+        cache: Option<&ProofCache>,
+        pinned_cores: Option<&[usize]>,
+    ) -> Result<ProverResult, ProverError> {
         match task.program_id.as_str() {
             "fib_input_initial" => {
-                Self::prove_fib_task(task, environment, client_id, num_workers).await
+                Self::prove_fib_task(
+                    task,
+                    environment,
+                    client_id,
+                    num_workers,
+                    cache,
+                    pinned_cores,
+                )
+                .await
             }
             _ => Err(ProverError::MalformedTask(format!(
                 "Unsupported program ID: {}",
@@ -35,13 +47,15 @@ impl ProvingPipeline {
         }
     }
 
-    /// Process fibonacci proving task with multiple inputs
+    /// Process fibonacci proving task with multiple inputs, reusing cached proofs where possible
     async fn prove_fib_task(
         task: &Task,
         environment: &Environment,
         client_id: &str,
         num_workers: usize,
-    ) -> Result<(Vec<Proof>, String, Vec<String>), ProverError> {
+        cache: Option<&ProofCache>,
+        pinned_cores: Option<&[usize]>,
+    ) -> Result<ProverResult, ProverError> {
         let all_inputs = task.all_inputs();
 
         if all_inputs.is_empty() {
@@ -54,10 +68,14 @@ impl ProvingPipeline {
         let task_shared = Arc::new(task.clone());
         let environment_shared = Arc::new(environment.clone());
         let client_id_shared = Arc::new(client_id.to_string());
+        let cache_shared = cache.cloned().map(Arc::new);
 
         // Create a semaphore with a specific number of permits
         let semaphore = Arc::new(tokio::sync::Semaphore::new(num_workers));
 
+        // One core per permit when pinning is enabled
+        let core_pool = pinned_cores.map(CorePool::new);
+
         // Create cancellation token for graceful shutdown
         let cancellation_token = CancellationToken::new();
 
@@ -72,8 +90,20 @@ impl ProvingPipeline {
                 let input_data = input_data.clone();
                 let semaphore_ref = Arc::clone(&semaphore);
                 let cancellation_ref = cancellation_token.clone();
+                let cache_ref = cache_shared.clone();
+                let core_pool_ref = core_pool.clone();
 
                 tokio::spawn(async move {
+                    // Reuse the proof if these inputs were proven before
+                    let cache_key = ProofCache::key(&task_ref.program_id, &input_data);
+                    if let Some(cache) = &cache_ref {
+                        if let Some(proof) = cache.get(&cache_key).await {
+                            let proof_bytes = Bytes::from(postcard::to_allocvec(&proof)?);
+                            let proof_hash = Self::generate_proof_hash(&proof_bytes);
+                            return Ok((proof, proof_bytes, proof_hash, input_index, true));
+                        }
+                    }
+
                     // Check for cancellation before starting
                     if cancellation_ref.is_cancelled() {
                         return Err(ProverError::MalformedTask("Task cancelled".to_string()));
@@ -90,19 +120,28 @@ impl ProvingPipeline {
                     // Step 1: Parse and validate input
                     let inputs = InputParser::parse_triple_input(&input_data)?;
 
-                    // Step 2: Generate and verify proof
-                    let proof = ProvingEngine::prove_and_validate(
+                    // Step 2: Generate and verify proof, on the permit's core if pinned
+                    let core_lease = core_pool_ref.as_ref().and_then(CorePool::acquire);
+                    let (proof, proof_bytes) = ProvingEngine::prove_and_validate(
                         &inputs,
                         &task_ref,
                         &environment_ref,
                         &client_id_ref,
+                        core_lease.as_ref().map(CoreLease::core),
+                        num_workers,
                     )
                     .await?;
+                    drop(core_lease);
 
                     // Step 3: Generate proof hash
-                    let proof_hash = Self::generate_proof_hash(&proof);
+                    let proof_hash = Self::generate_proof_hash(&proof_bytes);
 
-                    Ok((proof, proof_hash, input_index))
+                    // Caching is best-effort; a full disk must not fail the task
+                    if let Some(cache) = &cache_ref {
+                        let _ = cache.put(&cache_key, &proof).await;
+                    }
+
+                    Ok((proof, proof_bytes, proof_hash, input_index, false))
                 })
             })
             .collect();
@@ -112,13 +151,19 @@ impl ProvingPipeline {
 
         // Process results and collect verification failures for batch handling
         let mut all_proofs = Vec::new();
+        let mut all_proof_bytes = Vec::new();
         let mut proof_hashes = Vec::new();
         let mut verification_failures = Vec::new();
+        let mut cache_hits = 0;
 
         for (result_index, result) in results.into_iter().enumerate() {
             match result {
-                Ok(Ok((proof, proof_hash, _input_index))) => {
+                Ok(Ok((proof, proof_bytes, proof_hash, _input_index, cached))) => {
+                    if cached {
+                        cache_hits += 1;
+                    }
                     all_proofs.push(proof);
+                    all_proof_bytes.push(proof_bytes);
                     proof_hashes.push(proof_hash);
                 }
                 Ok(Err(e)) => {
@@ -166,13 +211,18 @@ impl ProvingPipeline {
 
         let final_proof_hash = Self::combine_proof_hashes(&task_shared, &proof_hashes);
 
-        Ok((all_proofs, final_proof_hash, proof_hashes))
+        Ok(ProverResult {
+            proofs: all_proofs,
+            proof_bytes: all_proof_bytes,
+            combined_hash: final_proof_hash,
+            individual_proof_hashes: proof_hashes,
+            cache_hits,
+        })
     }
 
-    /// Generate hash for a proof
-    fn generate_proof_hash(proof: &Proof) -> String {
-        let proof_bytes = postcard::to_allocvec(proof).expect("Failed to serialize proof");
-        format!("{:x}", Keccak256::digest(&proof_bytes))
+    /// Generate hash for a proof from its encoding
+    fn generate_proof_hash(proof_bytes: &[u8]) -> String {
+        format!("{:x}", Keccak256::digest(proof_bytes))
     }
 
     /// Combine multiple proof hashes based on task type
@@ -186,3 +236,29 @@ impl ProvingPipeline {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::testing::adversarial_task;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_adversarial_tasks_are_rejected(
+            data in proptest::collection::vec(any::<u8>(), 0..64)
+        ) {
+            let task = adversarial_task(&data);
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            let result = runtime.block_on(ProvingPipeline::prove_authenticated(
+                &task,
+                &Environment::default(),
+                "test",
+                1,
+                None,
+                None,
+            ));
+            prop_assert!(matches!(result, Err(ProverError::MalformedTask(_))));
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! Long-lived prover subprocesses, reused across proofs
+//!
+//! Starting a subprocess per proof loads the guest program and sets up the prover every time,
+//! which is a large share of the work for small inputs. Instead each worker keeps a
+//! `prove-fib-server` subprocess that loads the guest program once and then proves the inputs it
+//! is sent, one at a time. Memory use stays isolated from the CLI process, and a server killed for
+//! running out of memory or aborted by a panic only fails the proof it was working on; the worker
+//! supervises these crashes and starts a new server for its next task.
+//!
+//! Requests are one JSON-encoded input triple per line on the server's stdin. Each reply on its
+//! stdout is a status byte, a little-endian `u32` length and that many bytes: the postcard-encoded
+//! proof, or the error message if proving failed.
+
+use super::engine::{track_prover_process, untrack_prover_process};
+use super::types::ProverError;
+use std::env;
+use std::io::Write;
+use std::process::{ExitStatus, Stdio};
+use std::sync::Mutex;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+
+const REPLY_PROOF: u8 = 0;
+const REPLY_ERROR: u8 = 1;
+
+/// Servers waiting for their next inputs
+static IDLE_SERVERS: Mutex<Vec<ProverServer>> = Mutex::new(Vec::new());
+
+/// How a server answered a request
+pub enum ServerReply {
+    /// Postcard-encoded proof
+    Proof(Vec<u8>),
+    /// Proving failed inside the server, which is still usable
+    Failed(String),
+    /// The server exited before replying, e.g. killed for running out of memory
+    Exited(ExitStatus),
+}
+
+impl ServerReply {
+    /// The postcard-encoded proof, or why there is none.
+    pub fn into_proof(self) -> Result<Vec<u8>, ProverError> {
+        match self {
+            ServerReply::Proof(proof_bytes) => Ok(proof_bytes),
+            // The error happened inside the subprocess, so it may be useful information to the user
+            ServerReply::Failed(message) => Err(ProverError::Subprocess(format!(
+                "Error while proving within subprocess, captured error: [{}]",
+                message
+            ))),
+            // Release builds abort on panic, so a panic while proving ends up here too
+            ServerReply::Exited(status) => Err(ProverError::Crashed(format!(
+                "exited with status: {}",
+                status
+            ))),
+        }
+    }
+}
+
+/// A `prove-fib-server` subprocess, pinned to `core` if given
+struct ProverServer {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    core: Option<usize>,
+    pid: Option<u32>,
+}
+
+impl ProverServer {
+    fn spawn(core: Option<usize>) -> Result<Self, ProverError> {
+        let exe_path = env::current_exe()?;
+        let mut cmd = tokio::process::Command::new(exe_path);
+        cmd.arg("prove-fib-server")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true);
+        if let Some(core) = core {
+            cmd.arg("--core").arg(core.to_string());
+        }
+
+        let mut child = cmd.spawn()?;
+        let pid = child.id();
+        if let Some(pid) = pid {
+            track_prover_process(pid);
+        }
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(ProverError::Subprocess(
+                "Prover server started without pipes".to_string(),
+            ));
+        };
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            core,
+            pid,
+        })
+    }
+
+    async fn send(&mut self, inputs: &(u32, u32, u32)) -> std::io::Result<()> {
+        let mut request = serde_json::to_vec(inputs)?;
+        request.push(b'\n');
+        self.stdin.write_all(&request).await?;
+        self.stdin.flush().await
+    }
+}
+
+impl Drop for ProverServer {
+    fn drop(&mut self) {
+        if let Some(pid) = self.pid {
+            untrack_prover_process(pid);
+        }
+    }
+}
+
+/// Start `num_workers` servers ahead of the first task, on `pinned_cores` if given, so the first
+/// proofs do not wait for the guest program to load.
+pub fn warm_up(num_workers: usize, pinned_cores: Option<&[usize]>) {
+    let cores: Vec<Option<usize>> = match pinned_cores {
+        Some(cores) => cores.iter().copied().map(Some).take(num_workers).collect(),
+        None => vec![None; num_workers],
+    };
+    let mut idle = IDLE_SERVERS.lock().unwrap_or_else(|e| e.into_inner());
+    for core in cores {
+        // Best-effort: a server that fails to start now is started again for the first proof
+        if let Ok(server) = ProverServer::spawn(core) {
+            idle.push(server);
+        }
+    }
+}
+
+/// Prove `inputs` on an idle server for `core`, starting one if there is none. The server is kept
+/// for later proofs unless `max_idle` servers are already waiting, e.g. after the worker count
+/// was lowered.
+pub async fn prove(
+    inputs: &(u32, u32, u32),
+    core: Option<usize>,
+    max_idle: usize,
+) -> Result<ServerReply, ProverError> {
+    let mut server = match take_idle(core) {
+        Some(mut server) => match server.send(inputs).await {
+            Ok(()) => server,
+            // The idle server has gone away; replace it
+            Err(_) => start_and_send(core, inputs).await?,
+        },
+        None => start_and_send(core, inputs).await?,
+    };
+
+    match read_reply(&mut server.stdout).await {
+        Ok(Ok(proof)) => {
+            release(server, max_idle);
+            Ok(ServerReply::Proof(proof))
+        }
+        Ok(Err(message)) => {
+            release(server, max_idle);
+            Ok(ServerReply::Failed(message))
+        }
+        Err(_) => Ok(ServerReply::Exited(server.child.wait().await?)),
+    }
+}
+
+async fn start_and_send(
+    core: Option<usize>,
+    inputs: &(u32, u32, u32),
+) -> Result<ProverServer, ProverError> {
+    let mut server = ProverServer::spawn(core)?;
+    server.send(inputs).await?;
+    Ok(server)
+}
+
+/// Stop the idle servers, e.g. before the process is replaced; busy ones stop when dropped.
+pub fn shut_down() {
+    IDLE_SERVERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+fn take_idle(core: Option<usize>) -> Option<ProverServer> {
+    let mut idle = IDLE_SERVERS.lock().unwrap_or_else(|e| e.into_inner());
+    let index = idle.iter().position(|server| server.core == core)?;
+    Some(idle.swap_remove(index))
+}
+
+fn release(server: ProverServer, max_idle: usize) {
+    let mut idle = IDLE_SERVERS.lock().unwrap_or_else(|e| e.into_inner());
+    if idle.len() < max_idle {
+        idle.push(server);
+    }
+}
+
+/// Write the reply to one request: the encoded proof, or the error message.
+pub fn write_reply(out: &mut impl Write, reply: Result<&[u8], &str>) -> std::io::Result<()> {
+    let (status, payload) = match reply {
+        Ok(proof) => (REPLY_PROOF, proof),
+        Err(message) => (REPLY_ERROR, message.as_bytes()),
+    };
+    out.write_all(&[status])?;
+    out.write_all(&(payload.len() as u32).to_le_bytes())?;
+    out.write_all(payload)?;
+    out.flush()
+}
+
+/// Read a reply written by [`write_reply`].
+async fn read_reply(
+    input: &mut (impl AsyncRead + Unpin),
+) -> std::io::Result<Result<Vec<u8>, String>> {
+    let status = input.read_u8().await?;
+    let len = input.read_u32_le().await?;
+    let mut payload = vec![0; len as usize];
+    input.read_exact(&mut payload).await?;
+    match status {
+        REPLY_PROOF => Ok(Ok(payload)),
+        _ => Ok(Err(String::from_utf8_lossy(&payload).into_owned())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reply_roundtrip() {
+        let mut buffer = Vec::new();
+        write_reply(&mut buffer, Ok(&[1, 2, 3])).unwrap();
+        write_reply(&mut buffer, Err("guest exited with code 1")).unwrap();
+
+        let mut input = buffer.as_slice();
+        assert_eq!(read_reply(&mut input).await.unwrap(), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            read_reply(&mut input).await.unwrap(),
+            Err("guest exited with code 1".to_string())
+        );
+        // A server that exits mid-reply is an I/O error, not an empty proof
+        assert!(read_reply(&mut &buffer[..2]).await.is_err());
+    }
+}
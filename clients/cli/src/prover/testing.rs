@@ -0,0 +1,45 @@
+//! Adversarial inputs for the proving pipeline
+//!
+//! Available to unit tests and, with the `testing` feature, to the fuzz targets in `fuzz/`.
+
+use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+use crate::task::Task;
+
+/// Program the pipeline knows how to prove
+const FIB_PROGRAM_ID: &str = "fib_input_initial";
+
+/// Size in bytes of a well-formed fib input, three little-endian u32s
+const TRIPLE_INPUT_LEN: usize = 12;
+
+/// Build a task that the pipeline must reject from arbitrary bytes, e.g. fuzzer input.
+///
+/// The first byte picks the shape of the task: a fib task without inputs, a fib task whose
+/// inputs are all too short, or a task for an unsupported program. No input is ever a
+/// well-formed triple, so the pipeline rejects the task before starting a prover.
+pub fn adversarial_task(data: &[u8]) -> Task {
+    let (selector, rest) = data.split_first().unwrap_or((&0, &[]));
+    let (program_id, inputs) = match selector % 3 {
+        0 => (FIB_PROGRAM_ID.to_string(), Vec::new()),
+        1 => {
+            let chunk_len = 1 + *selector as usize / 3 % (TRIPLE_INPUT_LEN - 1);
+            let inputs = rest.chunks(chunk_len).map(<[u8]>::to_vec).collect();
+            (FIB_PROGRAM_ID.to_string(), inputs)
+        }
+        _ => {
+            let mut program_id = String::from_utf8_lossy(rest).into_owned();
+            if program_id == FIB_PROGRAM_ID {
+                program_id.push_str("-unsupported");
+            }
+            (program_id, vec![rest.to_vec()])
+        }
+    };
+
+    Task {
+        task_id: "adversarial".to_string(),
+        program_id,
+        public_inputs: inputs.first().cloned().unwrap_or_default(),
+        public_inputs_list: inputs,
+        task_type: TaskType::ProofRequired,
+        difficulty: TaskDifficulty::default(),
+    }
+}
@@ -1,5 +1,6 @@
 //! Proof types and error definitions
 
+use bytes::Bytes;
 use nexus_sdk::stwo::seq::Proof;
 use thiserror::Error;
 use tokio::task::JoinError;
@@ -24,16 +25,26 @@ pub enum ProverError {
     #[error("Subprocess error: {0}")]
     Subprocess(String),
 
+    #[error("Prover subprocess crashed: {0}")]
+    Crashed(String),
+
     #[error("Serde JSON error: {0}")]
     SerdeJson(#[from] serde_json::Error),
 
     #[error("Task Join Error: {0}")]
     JoinError(JoinError),
+
+    #[error("Exported proof does not match its metadata: {0}")]
+    ExportMismatch(String),
 }
 
 /// Result of a proof generation, including combined hash for multiple inputs
 pub struct ProverResult {
     pub proofs: Vec<Proof>,
+    /// Postcard encoding of each proof, shared with submissions and exports
+    pub proof_bytes: Vec<Bytes>,
     pub combined_hash: String,
     pub individual_proof_hashes: Vec<String>,
+    /// Number of inputs whose proof was taken from the proof cache
+    pub cache_hits: usize,
 }
@@ -0,0 +1,457 @@
+//! Submission receipts and points reconciliation
+//!
+//! A submission answered with 200 only means the orchestrator accepted the proof; points are
+//! credited later, and occasionally never. Every accepted submission is recorded in a ledger at
+//! `~/.nexus/receipts/<node_id>.json`, and while a session runs, a reconciliation job regularly
+//! asks the orchestrator whether the recorded tasks were credited. Tasks rejected during
+//! verification, or still not credited an hour after submission, are flagged in the ledger and
+//! shown as warnings. `nexus-cli queue stats` and `GET /v1/stats` report the counts.
+//!
+//! Against an orchestrator without receipt lookups the job stops after its first request and
+//! tasks stay pending. Receipts are kept for a week; a missing or unreadable ledger starts empty.
+
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::consts::cli_consts::receipts::{
+    RECEIPT_BATCH_SIZE, RECEIPT_GRACE_SECS, RECEIPT_MAX_ENTRIES, RECEIPT_MIN_AGE_SECS,
+    RECEIPT_TTL_SECS, RECONCILE_INTERVAL_SECS,
+};
+use crate::events::{Event, EventPayload, EventType};
+use crate::logging::LogLevel;
+use crate::nexus_orchestrator::{TaskReceipt, TaskReceiptState};
+use crate::orchestrator::Orchestrator;
+use crate::orchestrator::error::OrchestratorError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{broadcast, mpsc};
+
+/// Where a submitted task stands
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiptState {
+    /// Accepted, points not credited yet
+    Pending,
+    /// Points were credited
+    Credited,
+    /// Rejected after it was accepted
+    Rejected,
+    /// Still not credited after the grace period
+    Uncredited,
+}
+
+impl ReceiptState {
+    /// Whether the task is flagged as a discrepancy
+    pub fn is_discrepancy(self) -> bool {
+        matches!(self, Self::Rejected | Self::Uncredited)
+    }
+}
+
+/// Ledger entry of one submitted task
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Receipt {
+    /// Submission time, in seconds since the Unix epoch
+    pub submitted_at: u64,
+    pub state: ReceiptState,
+    /// Points credited for the task
+    #[serde(default)]
+    pub points: u64,
+    /// Why the task is flagged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Number of receipts in each state
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReceiptCounts {
+    pub pending: usize,
+    pub credited: usize,
+    /// Rejected or uncredited tasks
+    pub discrepancies: usize,
+}
+
+/// A task newly flagged by reconciliation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discrepancy {
+    pub task_id: String,
+    pub reason: String,
+}
+
+/// Disk-backed record of submitted tasks and whether they were credited.
+///
+/// Clones share the same entries.
+#[derive(Debug, Clone)]
+pub struct ReceiptLedger {
+    path: PathBuf,
+    entries: Arc<Mutex<BTreeMap<String, Receipt>>>,
+}
+
+impl ReceiptLedger {
+    /// Load the ledger stored at `path`, dropping expired receipts.
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let mut entries: BTreeMap<String, Receipt> = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        prune(&mut entries, now_secs());
+        Self {
+            path,
+            entries: Arc::new(Mutex::new(entries)),
+        }
+    }
+
+    /// `~/.nexus/receipts/<node_id>.json`, next to the config file
+    pub fn default_path(node_id: u64) -> Option<PathBuf> {
+        crate::config::nexus_dir()
+            .ok()
+            .map(|dir| dir.join("receipts").join(format!("{}.json", node_id)))
+    }
+
+    /// Record that the orchestrator accepted a proof for the task, and write the ledger to disk.
+    pub fn record(&self, task_id: &str) -> io::Result<()> {
+        self.update(|entries, now| {
+            entries.insert(
+                task_id.to_string(),
+                Receipt {
+                    submitted_at: now,
+                    state: ReceiptState::Pending,
+                    points: 0,
+                    reason: None,
+                },
+            );
+        })
+    }
+
+    /// Tasks to look up at `now`: those not yet credited or rejected, oldest first
+    pub fn due(&self, now: u64) -> Vec<String> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut due: Vec<(u64, &String)> = entries
+            .iter()
+            .filter(|(_, receipt)| {
+                !is_settled(receipt.state)
+                    && now.saturating_sub(receipt.submitted_at) >= RECEIPT_MIN_AGE_SECS
+            })
+            .map(|(task_id, receipt)| (receipt.submitted_at, task_id))
+            .collect();
+        due.sort();
+        due.into_iter()
+            .map(|(_, task_id)| task_id.clone())
+            .collect()
+    }
+
+    /// Apply the orchestrator's answer for the `checked` tasks, write the ledger to disk and
+    /// return the tasks newly flagged.
+    pub fn reconcile(
+        &self,
+        checked: &[String],
+        receipts: &[TaskReceipt],
+    ) -> io::Result<Vec<Discrepancy>> {
+        let mut flagged = Vec::new();
+        self.update(|entries, now| flagged = apply(entries, checked, receipts, now))?;
+        Ok(flagged)
+    }
+
+    /// Number of receipts in each state
+    pub fn counts(&self) -> ReceiptCounts {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut counts = ReceiptCounts::default();
+        for receipt in entries.values() {
+            match receipt.state {
+                ReceiptState::Pending => counts.pending += 1,
+                ReceiptState::Credited => counts.credited += 1,
+                ReceiptState::Rejected | ReceiptState::Uncredited => counts.discrepancies += 1,
+            }
+        }
+        counts
+    }
+
+    fn update(&self, f: impl FnOnce(&mut BTreeMap<String, Receipt>, u64)) -> io::Result<()> {
+        let snapshot = {
+            let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+            let now = now_secs();
+            f(&mut entries, now);
+            prune(&mut entries, now);
+            serde_json::to_vec(&*entries).map_err(io::Error::other)?
+        };
+        save(&self.path, &snapshot)
+    }
+}
+
+/// Whether the orchestrator has given its final answer for a task
+fn is_settled(state: ReceiptState) -> bool {
+    matches!(state, ReceiptState::Credited | ReceiptState::Rejected)
+}
+
+/// Update `checked` tasks from `receipts`, returning the tasks newly flagged.
+///
+/// Tasks left out of `receipts` are treated as not credited yet.
+fn apply(
+    entries: &mut BTreeMap<String, Receipt>,
+    checked: &[String],
+    receipts: &[TaskReceipt],
+    now: u64,
+) -> Vec<Discrepancy> {
+    let mut flagged = Vec::new();
+    for task_id in checked {
+        let Some(entry) = entries
+            .get_mut(task_id)
+            .filter(|entry| !is_settled(entry.state))
+        else {
+            continue;
+        };
+        let receipt = receipts.iter().find(|receipt| &receipt.task_id == task_id);
+        let (state, reason) = match receipt.map(TaskReceipt::state) {
+            Some(TaskReceiptState::Credited) => (ReceiptState::Credited, None),
+            Some(TaskReceiptState::Rejected) => {
+                let reason = receipt
+                    .map(|receipt| receipt.reason.clone())
+                    .filter(|reason| !reason.is_empty())
+                    .unwrap_or_else(|| "rejected during verification".to_string());
+                (ReceiptState::Rejected, Some(reason))
+            }
+            state if now.saturating_sub(entry.submitted_at) >= RECEIPT_GRACE_SECS => {
+                let reason = match state {
+                    Some(TaskReceiptState::Pending) => "accepted but not credited",
+                    _ => "orchestrator has no record of the submission",
+                };
+                (ReceiptState::Uncredited, Some(reason.to_string()))
+            }
+            _ => (ReceiptState::Pending, None),
+        };
+
+        if state.is_discrepancy() && !entry.state.is_discrepancy() {
+            flagged.push(Discrepancy {
+                task_id: task_id.clone(),
+                reason: reason.clone().unwrap_or_default(),
+            });
+        }
+        entry.state = state;
+        entry.points = receipt.map(|receipt| receipt.points).unwrap_or_default();
+        entry.reason = reason;
+    }
+    flagged
+}
+
+/// Record accepted submissions from `source` and reconcile them with the orchestrator every
+/// few minutes until shutdown, adding a warning for each task flagged.
+///
+/// Returns the receiver to consume instead; events pass through unchanged.
+pub fn spawn_receipt_reconciler(
+    mut source: mpsc::Receiver<Event>,
+    ledger: ReceiptLedger,
+    orchestrator: Arc<dyn Orchestrator>,
+    node_id: u64,
+    mut shutdown: broadcast::Receiver<()>,
+) -> mpsc::Receiver<Event> {
+    let (sender, receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+
+    let recorder = ledger.clone();
+    let warnings = sender.clone();
+    tokio::spawn(async move {
+        while let Some(event) = source.recv().await {
+            let failed = match &event.payload {
+                Some(EventPayload::ProofSubmitted { task_id }) => recorder.record(task_id).err(),
+                _ => None,
+            };
+            if sender.send(event).await.is_err() {
+                break;
+            }
+            if let Some(e) = failed {
+                let warning = warning(format!("Failed to update the receipt ledger: {}", e));
+                if sender.send(warning).await.is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(RECONCILE_INTERVAL_SECS));
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = interval.tick() => {
+                    match reconcile(orchestrator.as_ref(), node_id, &ledger).await {
+                        Ok(flagged) => {
+                            for discrepancy in flagged {
+                                let msg = format!(
+                                    "No points for task {}: {}",
+                                    discrepancy.task_id, discrepancy.reason
+                                );
+                                let _ = warnings.send(warning(msg)).await;
+                            }
+                        }
+                        // The orchestrator does not offer receipts; tasks stay pending
+                        Err(ReconcileError::Orchestrator(e)) if e.is_unsupported() => break,
+                        // Transient failures are retried on the next tick
+                        Err(ReconcileError::Orchestrator(_)) => {}
+                        Err(ReconcileError::Io(e)) => {
+                            let msg = format!("Failed to update the receipt ledger: {}", e);
+                            let _ = warnings.send(warning(msg)).await;
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    receiver
+}
+
+enum ReconcileError {
+    Orchestrator(OrchestratorError),
+    Io(io::Error),
+}
+
+/// Look up every task due and update the ledger, returning the tasks newly flagged.
+async fn reconcile(
+    orchestrator: &dyn Orchestrator,
+    node_id: u64,
+    ledger: &ReceiptLedger,
+) -> Result<Vec<Discrepancy>, ReconcileError> {
+    let mut flagged = Vec::new();
+    for checked in ledger.due(now_secs()).chunks(RECEIPT_BATCH_SIZE) {
+        let receipts = orchestrator
+            .get_task_receipts(&node_id.to_string(), checked)
+            .await
+            .map_err(ReconcileError::Orchestrator)?;
+        flagged.extend(
+            ledger
+                .reconcile(checked, &receipts)
+                .map_err(ReconcileError::Io)?,
+        );
+    }
+    Ok(flagged)
+}
+
+fn warning(msg: String) -> Event {
+    Event::proof_submitter_with_level(msg, EventType::Refresh, LogLevel::Warn)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Drop expired receipts, then the oldest ones beyond the size limit.
+fn prune(entries: &mut BTreeMap<String, Receipt>, now: u64) {
+    entries.retain(|_, receipt| now.saturating_sub(receipt.submitted_at) <= RECEIPT_TTL_SECS);
+    if entries.len() > RECEIPT_MAX_ENTRIES {
+        let mut by_age: Vec<(u64, String)> = entries
+            .iter()
+            .map(|(task_id, receipt)| (receipt.submitted_at, task_id.clone()))
+            .collect();
+        by_age.sort();
+        let excess = entries.len() - RECEIPT_MAX_ENTRIES;
+        for (_, task_id) in by_age.into_iter().take(excess) {
+            entries.remove(&task_id);
+        }
+    }
+}
+
+/// Write the ledger through a temporary file, so a crash never leaves a partial ledger.
+fn save(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, bytes)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn pending(submitted_at: u64) -> Receipt {
+        Receipt {
+            submitted_at,
+            state: ReceiptState::Pending,
+            points: 0,
+            reason: None,
+        }
+    }
+
+    fn receipt(task_id: &str, state: TaskReceiptState, points: u64) -> TaskReceipt {
+        TaskReceipt {
+            task_id: task_id.to_string(),
+            state: state as i32,
+            points,
+            reason: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_apply_receipts() {
+        let now = 10_000;
+        let mut entries = BTreeMap::from([
+            ("credited".to_string(), pending(now - 600)),
+            ("rejected".to_string(), pending(now - 600)),
+            ("recent".to_string(), pending(now - 600)),
+            ("stuck".to_string(), pending(now - RECEIPT_GRACE_SECS)),
+            ("unknown".to_string(), pending(now - RECEIPT_GRACE_SECS)),
+        ]);
+        let checked: Vec<String> = entries.keys().cloned().collect();
+        let receipts = [
+            receipt("credited", TaskReceiptState::Credited, 100),
+            receipt("rejected", TaskReceiptState::Rejected, 0),
+            receipt("recent", TaskReceiptState::Pending, 0),
+            receipt("stuck", TaskReceiptState::Pending, 0),
+        ];
+
+        let flagged = apply(&mut entries, &checked, &receipts, now);
+        let flagged: Vec<&str> = flagged.iter().map(|d| d.task_id.as_str()).collect();
+        assert_eq!(flagged, ["rejected", "stuck", "unknown"]);
+        assert_eq!(entries["credited"].state, ReceiptState::Credited);
+        assert_eq!(entries["credited"].points, 100);
+        assert_eq!(entries["recent"].state, ReceiptState::Pending);
+        assert_eq!(entries["stuck"].state, ReceiptState::Uncredited);
+
+        // Flagged tasks are reported once, and cleared if they are credited after all
+        let receipts = [receipt("stuck", TaskReceiptState::Credited, 100)];
+        assert!(apply(&mut entries, &checked, &receipts, now + 600).is_empty());
+        assert_eq!(entries["stuck"].state, ReceiptState::Credited);
+        assert_eq!(entries["stuck"].reason, None);
+        assert_eq!(entries["unknown"].state, ReceiptState::Uncredited);
+        // Final answers are kept
+        assert_eq!(entries["credited"].state, ReceiptState::Credited);
+        assert_eq!(entries["rejected"].state, ReceiptState::Rejected);
+    }
+
+    #[test]
+    fn test_ledger_survives_reopening() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("receipts").join("1.json");
+
+        let ledger = ReceiptLedger::open(&path);
+        ledger.record("task-1").unwrap();
+        ledger.record("task-2").unwrap();
+        // Just-submitted tasks are not looked up yet
+        assert!(ledger.due(now_secs()).is_empty());
+        assert_eq!(ledger.due(now_secs() + RECEIPT_MIN_AGE_SECS).len(), 2);
+
+        let receipts = [receipt("task-1", TaskReceiptState::Credited, 100)];
+        let checked = ["task-1".to_string()];
+        assert!(ledger.reconcile(&checked, &receipts).unwrap().is_empty());
+
+        let reopened = ReceiptLedger::open(&path);
+        assert_eq!(
+            reopened.counts(),
+            ReceiptCounts {
+                pending: 1,
+                credited: 1,
+                discrepancies: 0,
+            }
+        );
+        assert_eq!(
+            reopened.due(now_secs() + RECEIPT_MIN_AGE_SECS),
+            ["task-2".to_string()]
+        );
+    }
+}
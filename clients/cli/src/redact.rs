@@ -0,0 +1,199 @@
+//! Redaction of identifiers in shared output
+//!
+//! With `--redact`, node IDs, wallet addresses and task IDs are masked wherever events are shown:
+//! in the dashboard, the headless log, the event stream and notifications. Masking happens once,
+//! when an event is created, so every consumer sees the same text.
+//!
+//! The node ID and wallet address are registered when the session starts and task IDs when a task
+//! is received; anything else that looks like a wallet address (`0x` and at least 16 hex digits)
+//! is masked too.
+
+use crate::consts::cli_consts::redaction::{MIN_ADDRESS_HEX_DIGITS, RECENT_TASK_IDS};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static IDENTIFIERS: RwLock<Identifiers> = RwLock::new(Identifiers::new());
+
+/// Identifiers to mask
+struct Identifiers {
+    /// Node ID and wallet address, for the whole session
+    session: Vec<String>,
+    /// Most recently received task IDs, oldest first
+    tasks: VecDeque<String>,
+}
+
+impl Identifiers {
+    const fn new() -> Self {
+        Self {
+            session: Vec::new(),
+            tasks: VecDeque::new(),
+        }
+    }
+}
+
+/// Turn on redaction for the rest of the process
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether redaction is on
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Mask `identifier`, such as the node ID or wallet address, for the rest of the session.
+pub fn protect(identifier: &str) {
+    let identifier = identifier.trim();
+    if identifier.is_empty() {
+        return;
+    }
+    let mut identifiers = IDENTIFIERS.write().unwrap_or_else(|e| e.into_inner());
+    if !identifiers.session.iter().any(|known| known == identifier) {
+        identifiers.session.push(identifier.to_string());
+    }
+}
+
+/// Mask a task ID; only the most recent task IDs are remembered.
+pub fn protect_task(task_id: &str) {
+    if !is_enabled() || task_id.is_empty() {
+        return;
+    }
+    let mut identifiers = IDENTIFIERS.write().unwrap_or_else(|e| e.into_inner());
+    if identifiers.tasks.iter().any(|known| known == task_id) {
+        return;
+    }
+    if identifiers.tasks.len() >= RECENT_TASK_IDS {
+        identifiers.tasks.pop_front();
+    }
+    identifiers.tasks.push_back(task_id.to_string());
+}
+
+/// `text` with the registered identifiers and wallet addresses masked, if redaction is on.
+pub fn redact(text: &str) -> Cow<'_, str> {
+    if !is_enabled() {
+        return Cow::Borrowed(text);
+    }
+    let identifiers = IDENTIFIERS.read().unwrap_or_else(|e| e.into_inner());
+    let known: Vec<&str> = identifiers
+        .session
+        .iter()
+        .chain(&identifiers.tasks)
+        .map(String::as_str)
+        .collect();
+    redact_with(text, &known)
+}
+
+/// A single identifier for display: masked if redaction is on.
+pub fn redact_id(identifier: &str) -> String {
+    if is_enabled() {
+        mask(identifier)
+    } else {
+        identifier.to_string()
+    }
+}
+
+/// Mask an identifier, keeping a few characters at each end, e.g. `0x1234…abcd` or `12…78`.
+pub fn mask(identifier: &str) -> String {
+    let chars: Vec<char> = identifier.chars().collect();
+    let (head, tail) = match chars.len() {
+        0..=4 => return "…".to_string(),
+        5..=10 => (2, 2),
+        _ => (6, 4),
+    };
+    let head: String = chars[..head].iter().collect();
+    let tail: String = chars[chars.len() - tail..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+/// Mask every occurrence of the `known` identifiers and of wallet addresses in `text`.
+fn redact_with<'a>(text: &'a str, known: &[&str]) -> Cow<'a, str> {
+    let mut redacted = Cow::Borrowed(text);
+
+    // Longest first, so an identifier containing another is masked whole
+    let mut known = known.to_vec();
+    known.sort_by_key(|identifier| std::cmp::Reverse(identifier.len()));
+    for identifier in known {
+        if !identifier.is_empty() && redacted.contains(identifier) {
+            redacted = Cow::Owned(redacted.replace(identifier, &mask(identifier)));
+        }
+    }
+
+    match mask_addresses(&redacted) {
+        Some(masked) => Cow::Owned(masked),
+        None => redacted,
+    }
+}
+
+/// `text` with `0x`-prefixed hex strings masked, or `None` if it has none.
+fn mask_addresses(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut out = String::new();
+    let mut copied = 0;
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        let at_word_start = i == 0 || !bytes[i - 1].is_ascii_alphanumeric();
+        if at_word_start && bytes[i] == b'0' && matches!(bytes[i + 1], b'x' | b'X') {
+            let digits = bytes[i + 2..]
+                .iter()
+                .take_while(|b| b.is_ascii_hexdigit())
+                .count();
+            if digits >= MIN_ADDRESS_HEX_DIGITS {
+                let end = i + 2 + digits;
+                out.push_str(&text[copied..i]);
+                out.push_str(&mask(&text[i..end]));
+                copied = end;
+                i = end;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    if copied == 0 {
+        return None;
+    }
+    out.push_str(&text[copied..]);
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(
+            mask("0x1234567890abcdef1234567890abcdef1234abcd"),
+            "0x1234…abcd"
+        );
+        assert_eq!(mask("12345678"), "12…78");
+        assert_eq!(mask("abc"), "…");
+    }
+
+    #[test]
+    fn test_redact_with() {
+        let known = ["12345678", "task-98765"];
+        assert_eq!(
+            redact_with("Step 1 of 4: Got task task-98765 for node 12345678", &known),
+            "Step 1 of 4: Got task ta…65 for node 12…78"
+        );
+        assert_eq!(
+            redact_with(
+                "Rewards go to 0x1234567890abcdef1234567890abcdef1234abcd.",
+                &known
+            ),
+            "Rewards go to 0x1234…abcd."
+        );
+        // Short hex values such as error codes are left alone
+        assert_eq!(
+            redact_with("Error 0x1f from prover", &known),
+            "Error 0x1f from prover"
+        );
+        assert!(matches!(
+            redact_with("Nothing to hide", &known),
+            Cow::Borrowed(_)
+        ));
+    }
+}
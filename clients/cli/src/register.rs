@@ -1,103 +1,281 @@
 //! Registering a new user and node with the orchestrator.
+//!
+//! Both steps are idempotent: registering the wallet or node that is already in the config file
+//! is a no-op. Replacing a different registration asks for confirmation, which `--yes` skips and
+//! `--if-not-exists` declines, so provisioning scripts never block on a prompt.
+//!
+//! `deregister-node` retires a node with the orchestrator and removes it from the config file and
+//! the nodes manifest, also after confirmation.
 
 use crate::cli_messages::{print_error, print_info, print_success};
-use crate::config::Config;
+use crate::config::{Config, ConfigError};
+use crate::coordinator::claim_node;
+use crate::environment::Environment;
+use crate::exit_code::UsageError;
 use crate::keys;
+use crate::nodes::{NodeEntry, NodeManifest, nodes_manifest_path};
 use crate::orchestrator::Orchestrator;
+use crate::receipts::ReceiptLedger;
+use crate::secrets::{self, SecretStore};
+use crate::tr;
+use ed25519_dalek::SigningKey;
+use serde::Serialize;
+use std::io::{BufRead, IsTerminal, Write};
 use std::path::Path;
 
+/// Output format of the registration commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable progress messages
+    #[default]
+    Text,
+    /// A single JSON object describing the registration, and nothing else
+    Json,
+}
+
+/// Options shared by the registration commands
+#[derive(Debug, Clone, Copy, Default, clap::Args)]
+pub struct RegisterOptions {
+    /// Replace an existing, different registration without asking
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+    /// Keep an existing registration instead of replacing it
+    #[arg(long = "if-not-exists", conflicts_with = "yes")]
+    pub if_not_exists: bool,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+}
+
+/// Nodes registered by `register-node --count N`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NodeBatch {
+    pub user_id: String,
+    pub node_ids: Vec<String>,
+}
+
+/// Node retired by `deregister-node`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Deregistration {
+    pub node_id: String,
+    /// Whether the orchestrator retired the node; `false` with `--local-only`,
+    /// or when the orchestrator cannot deregister nodes
+    pub deregistered: bool,
+    /// Whether the node's signing key and submission history were removed
+    pub wiped: bool,
+}
+
+/// Registration stored in the config file after a registration command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Registration {
+    pub user_id: String,
+    pub wallet_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    /// Whether anything was registered or replaced; `false` if it already existed
+    pub created: bool,
+}
+
+impl Registration {
+    fn from_config(config: &Config, created: bool) -> Self {
+        Self {
+            user_id: config.user_id.clone(),
+            wallet_address: config.wallet_address.clone(),
+            node_id: (!config.node_id.is_empty()).then(|| config.node_id.clone()),
+            created,
+        }
+    }
+}
+
+impl RegisterOptions {
+    fn info(&self, title: &str, details: &str) {
+        if self.output == OutputFormat::Text {
+            print_info(title, details);
+        }
+    }
+
+    fn success(&self, title: &str, details: &str) {
+        if self.output == OutputFormat::Text {
+            print_success(title, details);
+        }
+    }
+
+    fn error(&self, title: &str, details: Option<&str>) {
+        if self.output == OutputFormat::Text {
+            print_error(title, details);
+        }
+    }
+
+    /// Print the final registration when JSON output was requested.
+    pub fn report(&self, registration: &impl Serialize) -> Result<(), serde_json::Error> {
+        if self.output == OutputFormat::Json {
+            println!("{}", serde_json::to_string(registration)?);
+        }
+        Ok(())
+    }
+
+    /// Ask whether to replace an existing registration.
+    fn confirm_replace(&self, question: &str) -> Result<bool, UsageError> {
+        self.confirm(question, &tr!("register-pass-yes-or-if-not-exists"))
+    }
+
+    /// Ask `question`. Without `--yes`, only an interactive text-mode session can answer;
+    /// otherwise the error suggests `hint`.
+    fn confirm(&self, question: &str, hint: &str) -> Result<bool, UsageError> {
+        if self.yes {
+            return Ok(true);
+        }
+        let stdin = std::io::stdin();
+        if self.output == OutputFormat::Json || !stdin.is_terminal() {
+            return Err(UsageError(format!("{} {}", question, hint)));
+        }
+        print!("{} [y/N] ", question);
+        let _ = std::io::stdout().flush();
+        let mut answer = String::new();
+        stdin
+            .lock()
+            .read_line(&mut answer)
+            .map_err(|e| UsageError(tr!("prompt-read-failed", error = e)))?;
+        Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+    }
+}
+
+/// Config for a newly registered user. Settings unrelated to the registration, such as
+/// notifications, the wallet address book, the dashboard look, the task cache, the update
+/// channel, the node labels, the storage quotas, the fallback orchestrator URLs, the defined
+/// environments and the submission nonce, are kept from the existing config file.
+fn user_config(
+    config_path: &Path,
+    user_id: String,
+    wallet_address: &str,
+    environment: &Environment,
+) -> Config {
+    let mut config = Config::new(
+        user_id,
+        wallet_address.to_string(),
+        String::new(), // node_id is empty for now
+        environment.clone(),
+    );
+    if let Ok(existing) = Config::load_from_file(config_path) {
+        config.notifications = existing.notifications;
+        config.wallets = existing.wallets;
+        config.dashboard = existing.dashboard;
+        config.theme = existing.theme;
+        config.task_cache = existing.task_cache;
+        config.update = existing.update;
+        config.labels = existing.labels;
+        config.storage = existing.storage;
+        config.orchestrator = existing.orchestrator;
+        config.environments = existing.environments;
+        config.submission_nonces = existing.submission_nonces;
+        config.version = existing.version;
+        config.extra = existing.extra;
+    }
+    config
+}
+
 /// Registers a user with the orchestrator.
 ///
 /// # Arguments
 /// * `wallet_address` - The Ethereum wallet address of the user.
 /// * `config_path` - The path to the configuration file where user details will be saved.
 /// * `orchestrator` - The orchestrator client to communicate with the orchestrator.
+/// * `options` - How to treat an existing registration, and the output format.
 pub async fn register_user(
     wallet_address: &str,
     config_path: &Path,
     orchestrator: Box<dyn Orchestrator>,
-) -> Result<(), Box<dyn std::error::Error>> {
+    options: RegisterOptions,
+) -> Result<Registration, Box<dyn std::error::Error>> {
     // Check if the wallet address is valid.
     if !keys::is_valid_eth_address(wallet_address) {
-        print_error(
-            "Invalid Ethereum wallet address",
-            Some(&format!(
-                "It should be a 42-character hex string starting with '0x', got: {}",
-                wallet_address
+        options.error(
+            &tr!("register-invalid-wallet"),
+            Some(&tr!(
+                "register-invalid-wallet-hint",
+                address = wallet_address
             )),
         );
-        let err_msg = format!(
-            "Invalid Ethereum wallet address: {}. It should be a 42-character hex string starting with '0x'.",
-            wallet_address
-        );
-        return Err(Box::from(err_msg));
+        return Err(UsageError(tr!("error-invalid-wallet", address = wallet_address)).into());
     }
 
-    // Check if the config file exists and contains this wallet address and a user ID.
+    // Check if the config file exists and already contains a registered user.
     if config_path.exists() {
         if let Ok(config) = Config::load_from_file(config_path) {
-            if config.wallet_address.to_lowercase() == wallet_address.to_lowercase()
-                && !config.user_id.is_empty()
-            {
-                print_info(
-                    "User already registered",
-                    &format!(
-                        "User ID: {}, Wallet Address: {}",
-                        config.user_id, config.wallet_address
-                    ),
-                );
-
-                // Guide user to next step
-                print_success(
-                    "User registration complete!",
-                    "Next step - register a node: nexus-cli register-node",
-                );
-                return Ok(());
+            if !config.user_id.is_empty() {
+                let same_wallet =
+                    config.wallet_address.to_lowercase() == wallet_address.to_lowercase();
+                if same_wallet || options.if_not_exists {
+                    options.info(
+                        &tr!("register-user-already-registered"),
+                        &tr!(
+                            "register-user-details",
+                            user_id = config.user_id,
+                            address = config.wallet_address
+                        ),
+                    );
+
+                    // Guide user to next step
+                    options.success(
+                        &tr!("register-user-complete"),
+                        &tr!("register-user-next-step"),
+                    );
+                    return Ok(Registration::from_config(&config, false));
+                }
+
+                let question = tr!("register-replace-wallet", address = config.wallet_address);
+                if !options.confirm_replace(&question)? {
+                    return Err(UsageError(tr!("register-cancelled")).into());
+                }
             }
         }
     }
 
     // Check if the wallet address is already registered with the orchestrator.
     if let Ok(user_id) = orchestrator.get_user(wallet_address).await {
-        print_info(
-            "Wallet address is already registered",
-            &format!("User ID: {}, Wallet Address: {}", user_id, wallet_address),
+        options.info(
+            &tr!("register-wallet-already-registered"),
+            &tr!(
+                "register-user-details",
+                user_id = user_id,
+                address = wallet_address
+            ),
         );
-        let config = Config::new(
+        let config = user_config(
+            config_path,
             user_id,
-            wallet_address.to_string(),
-            String::new(), // node_id is empty for now
-            orchestrator.environment().clone(),
+            wallet_address,
+            orchestrator.environment(),
         );
         // Save the configuration file with the user ID and wallet address.
         config.save(config_path).inspect_err(|e| {
-            print_error("Failed to save config", Some(&e.to_string()));
+            options.error(&tr!("config-save-failed"), Some(&e.to_string()));
         })?;
 
         // Guide user to next step
-        print_success(
-            "User registration complete!",
-            "Next step - register a node: nexus-cli register-node",
+        options.success(
+            &tr!("register-user-complete"),
+            &tr!("register-user-next-step"),
         );
 
-        return Ok(());
+        return Ok(Registration::from_config(&config, true));
     }
 
     // Otherwise, register the user with the orchestrator.
     let uuid = uuid::Uuid::new_v4().to_string();
     match orchestrator.register_user(&uuid, wallet_address).await {
         Ok(_) => {
-            print_success(
-                "User registered successfully",
-                &format!("User ID: {}", uuid),
+            options.success(
+                &tr!("register-user-registered"),
+                &tr!("register-user-id", user_id = uuid),
             );
         }
         Err(e) => {
             // Check if this looks like an orchestrator traffic issue
             if let Some(pretty_error) = e.to_pretty() {
-                print_error("Failed to register user", Some(&pretty_error));
+                options.error(&tr!("register-user-failed"), Some(&pretty_error));
             } else {
-                print_error("Failed to register user", Some(&e.to_string()));
+                options.error(&tr!("register-user-failed"), Some(&e.to_string()));
             }
 
             return Err(e.into());
@@ -105,23 +283,23 @@ pub async fn register_user(
     }
 
     // Save the configuration file with the user ID and wallet address.
-    let config = Config::new(
+    let config = user_config(
+        config_path,
         uuid,
-        wallet_address.to_string(),
-        String::new(), // node_id is empty for now
-        orchestrator.environment().clone(),
+        wallet_address,
+        orchestrator.environment(),
     );
     config.save(config_path).inspect_err(|e| {
-        print_error("Failed to save config", Some(&e.to_string()));
+        options.error(&tr!("config-save-failed"), Some(&e.to_string()));
     })?;
 
     // Guide user to next step
-    print_success(
-        "User registration complete!",
-        "Next step - register a node: nexus-cli register-node",
+    options.success(
+        &tr!("register-user-complete"),
+        &tr!("register-user-next-step"),
     );
 
-    Ok(())
+    Ok(Registration::from_config(&config, true))
 }
 
 /// Registers a node with the orchestrator.
@@ -130,78 +308,306 @@ pub async fn register_user(
 /// * `node_id` - Optional node ID. If provided, it will be used to register the node.
 /// * `config_path` - The path to the configuration file where node details will be saved.
 /// * `orchestrator` - The orchestrator client to communicate with the orchestrator.
+/// * `secret_store` - Where the signing key bound to a new node is kept.
+/// * `options` - How to treat an existing registration, and the output format.
 pub async fn register_node(
     node_id: Option<u64>,
     config_path: &Path,
     orchestrator: Box<dyn Orchestrator>,
-) -> Result<(), Box<dyn std::error::Error>> {
+    secret_store: &dyn SecretStore,
+    options: RegisterOptions,
+) -> Result<Registration, Box<dyn std::error::Error>> {
     // Register a new node, or link an existing node to a user.
     // Requires: a config file with a registered user.
     // If a node_id is provided, update the config with it and use it.
     // If no node_id is provided, generate a new one.
-    let mut config = Config::load_from_file(config_path).inspect_err(|e| {
-        print_error(
-            "Failed to load config, please register a user first",
-            Some(&e.to_string()),
-        );
-    })?;
+    let mut config = Config::load_from_file(config_path)
+        .inspect_err(|e| {
+            options.error(&tr!("config-load-failed"), Some(&e.to_string()));
+        })
+        .map_err(ConfigError::from)?;
     if config.user_id.is_empty() {
-        print_error("No user registered", Some("Please register a user first."));
-        return Err(Box::from(
-            "No user registered. Please register a user first.",
-        ));
+        options.error(&tr!("config-no-user"), Some(&tr!("config-no-user-hint")));
+        return Err(ConfigError::UserNotRegistered.into());
+    }
+
+    // Keep or replace a node that is already registered
+    if !config.node_id.is_empty() {
+        let same_node = node_id.is_some_and(|id| id.to_string() == config.node_id);
+        if same_node || options.if_not_exists {
+            options.info(
+                &tr!("register-node-already-registered"),
+                &tr!("register-node-id", node_id = config.node_id),
+            );
+            return Ok(Registration::from_config(&config, false));
+        }
+        let question = tr!("register-replace-node", node_id = config.node_id);
+        if !options.confirm_replace(&question)? {
+            return Err(UsageError(tr!("register-cancelled")).into());
+        }
     }
+
     if let Some(node_id) = node_id {
         // If a node_id is provided, update the config with it.
-        println!("Registering node ID: {}", node_id);
+        options.info(
+            &tr!("register-node-registering"),
+            &tr!("register-node-id", node_id = node_id),
+        );
         config.node_id = node_id.to_string();
         config.save(config_path).inspect_err(|e| {
-            print_error("Failed to save updated config", Some(&e.to_string()));
+            options.error(&tr!("config-save-failed"), Some(&e.to_string()));
         })?;
 
         // Guide user to next step
-        print_success(
-            "Node registration complete!",
-            &format!(
-                "Successfully registered node with ID: {}. Next step - start proving: nexus-cli start",
-                node_id
-            ),
+        options.success(
+            &tr!("register-node-complete"),
+            &tr!("register-node-next-step", node_id = node_id),
         );
 
-        Ok(())
+        Ok(Registration::from_config(&config, true))
     } else {
-        println!(
-            "No node ID provided. Registering a new node in environment: {:?}",
-            orchestrator.environment()
+        options.info(
+            &tr!("register-node-registering-new"),
+            &tr!(
+                "register-environment",
+                environment = format!("{:?}", orchestrator.environment())
+            ),
         );
-        match orchestrator.register_node(&config.user_id).await {
+        match register_with_key(orchestrator.as_ref(), secret_store, &config.user_id).await {
             Ok(node_id) => {
                 // Update the config with the new node ID
                 let mut updated_config = config;
                 updated_config.node_id = node_id.clone();
                 updated_config.save(config_path).inspect_err(|e| {
-                    print_error("Failed to save updated config", Some(&e.to_string()));
+                    options.error(&tr!("config-save-failed"), Some(&e.to_string()));
                 })?;
 
                 // Guide user to next step
-                print_success(
-                    "Node registration complete!",
-                    &format!(
-                        "Successfully registered node with ID: {}. Next step - start proving: nexus-cli start",
-                        node_id
-                    ),
+                options.success(
+                    &tr!("register-node-complete"),
+                    &tr!("register-node-next-step", node_id = node_id),
                 );
 
-                Ok(())
+                Ok(Registration::from_config(&updated_config, true))
             }
             Err(e) => {
-                print_error("Failed to register node", Some(&e.to_string()));
+                options.error(&tr!("register-node-failed"), Some(&e.to_string()));
                 Err(e.into())
             }
         }
     }
 }
 
+/// Registers `count` new nodes for the user in the config file and records them in the nodes
+/// manifest. The config keeps its node, or gets the first new one if it has none.
+///
+/// # Arguments
+/// * `count` - Number of nodes to register.
+/// * `config_path` - The path to the configuration file with the registered user.
+/// * `orchestrator` - The orchestrator client to communicate with the orchestrator.
+/// * `secret_store` - Where the signing keys bound to the new nodes are kept.
+/// * `options` - The output format.
+pub async fn register_nodes(
+    count: u32,
+    config_path: &Path,
+    orchestrator: Box<dyn Orchestrator>,
+    secret_store: &dyn SecretStore,
+    options: RegisterOptions,
+) -> Result<NodeBatch, Box<dyn std::error::Error>> {
+    let mut config = Config::load_from_file(config_path)
+        .inspect_err(|e| {
+            options.error(&tr!("config-load-failed"), Some(&e.to_string()));
+        })
+        .map_err(ConfigError::from)?;
+    if config.user_id.is_empty() {
+        options.error(&tr!("config-no-user"), Some(&tr!("config-no-user-hint")));
+        return Err(ConfigError::UserNotRegistered.into());
+    }
+
+    let manifest_path = nodes_manifest_path(config_path);
+    let mut manifest = NodeManifest::load_or_default(&manifest_path)?;
+    let environment = orchestrator.environment().to_string();
+    options.info(
+        &tr!("register-nodes-registering"),
+        &tr!(
+            "register-nodes-details",
+            count = count,
+            environment = environment
+        ),
+    );
+
+    let mut node_ids = Vec::with_capacity(count as usize);
+    for i in 1..=count {
+        let node_id = register_with_key(orchestrator.as_ref(), secret_store, &config.user_id)
+            .await
+            .inspect_err(|e| {
+                options.error(
+                    &tr!("register-nodes-failed", index = i, count = count),
+                    Some(&e.to_string()),
+                );
+            })?;
+        options.success(
+            &tr!("register-nodes-registered", index = i, count = count),
+            &tr!("register-node-id", node_id = node_id),
+        );
+
+        // Save after every node so that a failure part-way keeps the nodes registered so far
+        manifest.add(NodeEntry {
+            node_id: node_id.clone(),
+            user_id: config.user_id.clone(),
+            environment: environment.clone(),
+        });
+        manifest.save(&manifest_path)?;
+        if config.node_id.is_empty() {
+            config.node_id = node_id.clone();
+            config.save(config_path)?;
+        }
+        node_ids.push(node_id);
+    }
+
+    options.success(
+        &tr!("register-node-complete"),
+        &tr!(
+            "register-nodes-saved",
+            count = count,
+            path = manifest_path.display()
+        ),
+    );
+    Ok(NodeBatch {
+        user_id: config.user_id,
+        node_ids,
+    })
+}
+
+/// Register a new node for `user_id` with a fresh signing key bound to it, and keep the key for the
+/// node: only that key can deregister the node.
+async fn register_with_key(
+    orchestrator: &dyn Orchestrator,
+    secret_store: &dyn SecretStore,
+    user_id: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let signing_key = SigningKey::generate(&mut rand_core::OsRng);
+    let node_id = orchestrator
+        .register_node(user_id, signing_key.verifying_key())
+        .await?;
+    let id = node_id
+        .parse::<u64>()
+        .map_err(|_| ConfigError::InvalidNodeId)?;
+    secrets::save_node_signing_key(secret_store, id, &signing_key)?;
+    Ok(node_id)
+}
+
+/// Retires a node with the orchestrator and removes it from this machine.
+///
+/// # Arguments
+/// * `node_id` - The node to retire; the node in the config file if not given.
+/// * `config_path` - The path to the configuration file.
+/// * `orchestrator` - The orchestrator client to communicate with the orchestrator.
+/// * `secret_store` - Where the node's signing key is kept.
+/// * `local_only` - Only remove the node locally, e.g. when the orchestrator no longer knows it.
+/// * `wipe` - Also remove the node's signing key and submission history.
+/// * `options` - Whether to ask for confirmation, and the output format.
+pub async fn deregister_node(
+    node_id: Option<u64>,
+    config_path: &Path,
+    orchestrator: Box<dyn Orchestrator>,
+    secret_store: &dyn SecretStore,
+    local_only: bool,
+    wipe: bool,
+    options: RegisterOptions,
+) -> Result<Deregistration, Box<dyn std::error::Error>> {
+    let mut config = Config::load_from_file(config_path).ok();
+    let node_id = match node_id {
+        Some(node_id) => node_id.to_string(),
+        None => match config.as_ref().filter(|config| !config.node_id.is_empty()) {
+            Some(config) => config.node_id.clone(),
+            None => return Err(UsageError(tr!("deregister-no-node")).into()),
+        },
+    };
+    let id = node_id
+        .parse::<u64>()
+        .map_err(|_| ConfigError::InvalidNodeId)?;
+
+    // A session still proving for the node would keep using it
+    if let Ok(false) = claim_node(config_path, id) {
+        return Err(UsageError(tr!("deregister-node-running", node_id = node_id)).into());
+    }
+    let question = tr!("deregister-confirm", node_id = node_id);
+    if !options.confirm(&question, &tr!("deregister-pass-yes"))? {
+        return Err(UsageError(tr!("deregister-cancelled")).into());
+    }
+
+    let mut retired = false;
+    if !local_only {
+        options.info(
+            &tr!("deregister-deregistering"),
+            &tr!("register-node-id", node_id = node_id),
+        );
+        // Only the key bound to the node can retire it; a new one would be refused
+        let Some(signing_key) = secrets::stored_node_signing_key(secret_store, id)? else {
+            return Err(UsageError(tr!("deregister-no-key", node_id = node_id)).into());
+        };
+        match orchestrator.deregister_node(&node_id, signing_key).await {
+            Ok(()) => retired = true,
+            // Orchestrators without deregistration keep the node; it just stops proving
+            Err(e) if e.is_unsupported() => {
+                options.info(
+                    &tr!("deregister-unsupported"),
+                    &tr!("register-node-id", node_id = node_id),
+                );
+            }
+            Err(e) => {
+                let details = e.to_pretty().unwrap_or_else(|| e.to_string());
+                options.error(
+                    &tr!("deregister-failed"),
+                    Some(&format!("{}\n{}", details, tr!("deregister-failed-hint"))),
+                );
+                return Err(e.into());
+            }
+        }
+    }
+
+    // Keep the user, so another node can be registered for it
+    if let Some(config) = config.as_mut().filter(|config| config.node_id == node_id) {
+        config.node_id.clear();
+        config.save(config_path).inspect_err(|e| {
+            options.error(&tr!("config-save-failed"), Some(&e.to_string()));
+        })?;
+    }
+    let manifest_path = nodes_manifest_path(config_path);
+    let mut manifest = NodeManifest::load_or_default(&manifest_path)?;
+    if manifest.remove(&node_id) {
+        manifest.save(&manifest_path)?;
+    }
+
+    if wipe {
+        secrets::delete_node_signing_key(secret_store, id)?;
+        if let Some(path) = ReceiptLedger::default_path(id) {
+            if let Err(e) = std::fs::remove_file(path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    return Err(e.into());
+                }
+            }
+        }
+        options.info(
+            &tr!("deregister-wiped", node_id = node_id),
+            &tr!("register-node-id", node_id = node_id),
+        );
+    }
+
+    let details = if retired {
+        tr!("deregister-complete-details", node_id = node_id)
+    } else {
+        tr!("deregister-local-details", node_id = node_id)
+    };
+    options.success(&tr!("deregister-complete"), &details);
+    Ok(Deregistration {
+        node_id,
+        deregistered: retired,
+        wiped: wipe,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -242,9 +648,14 @@ mod tests {
             .returning(|_, _| Ok(()));
 
         // ---- call the function under test ----
-        register_user(WALLET, &path, Box::new(orchestrator))
-            .await
-            .expect("registration should succeed");
+        register_user(
+            WALLET,
+            &path,
+            Box::new(orchestrator),
+            RegisterOptions::default(),
+        )
+        .await
+        .expect("registration should succeed");
 
         // ---- verify side-effects *inside* the sandbox ----
         let cfg = Config::load_from_file(&path).unwrap();
@@ -286,7 +697,13 @@ mod tests {
         orchestrator.expect_register_user().never();
 
         // Call the function
-        let result = register_user(wallet_address, &config_path, Box::new(orchestrator)).await;
+        let result = register_user(
+            wallet_address,
+            &config_path,
+            Box::new(orchestrator),
+            RegisterOptions::default(),
+        )
+        .await;
 
         assert!(result.is_ok(), "should succeed without making any requests");
 
@@ -298,4 +715,271 @@ mod tests {
             wallet_address.to_lowercase()
         );
     }
+
+    fn write_config(path: &Path, wallet_address: &str, node_id: &str) {
+        Config::new(
+            "existing-user-id".to_string(),
+            wallet_address.to_string(),
+            node_id.to_string(),
+            Environment::Production,
+        )
+        .save(path)
+        .unwrap();
+    }
+
+    #[tokio::test]
+    /// `--if-not-exists` keeps a different wallet that is already registered.
+    async fn if_not_exists_keeps_existing_user() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        write_config(&path, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", "");
+
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator.expect_get_user().never();
+        orchestrator.expect_register_user().never();
+
+        let options = RegisterOptions {
+            if_not_exists: true,
+            ..RegisterOptions::default()
+        };
+        let registration = register_user(
+            "0x1234567890123456789012345678901234567890",
+            &path,
+            Box::new(orchestrator),
+            options,
+        )
+        .await
+        .unwrap();
+
+        assert!(!registration.created);
+        assert_eq!(registration.user_id, "existing-user-id");
+        assert_eq!(
+            registration.wallet_address,
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+    }
+
+    #[tokio::test]
+    /// An existing node is kept with `--if-not-exists` and replaced with `--yes`.
+    async fn existing_node_is_kept_or_replaced() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        write_config(&path, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", "42");
+
+        let keep = RegisterOptions {
+            if_not_exists: true,
+            ..RegisterOptions::default()
+        };
+        let store = secrets::FileStore::new(&path);
+        let registration = register_node(
+            Some(7),
+            &path,
+            Box::new(MockOrchestrator::new()),
+            &store,
+            keep,
+        )
+        .await
+        .unwrap();
+        assert!(!registration.created);
+        assert_eq!(registration.node_id.as_deref(), Some("42"));
+
+        let replace = RegisterOptions {
+            yes: true,
+            ..RegisterOptions::default()
+        };
+        let registration = register_node(
+            Some(7),
+            &path,
+            Box::new(MockOrchestrator::new()),
+            &store,
+            replace,
+        )
+        .await
+        .unwrap();
+        assert!(registration.created);
+        assert_eq!(Config::load_from_file(&path).unwrap().node_id, "7");
+    }
+
+    #[tokio::test]
+    /// `--count` registers every node, records them in the manifest and keeps the config's node.
+    async fn registers_node_batch_into_manifest() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        write_config(&path, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", "42");
+
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_environment()
+            .return_const(Environment::Production);
+        let mut next_id = 100;
+        let bound_keys = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let bound = bound_keys.clone();
+        orchestrator
+            .expect_register_node()
+            .withf(|user_id, _| user_id == "existing-user-id")
+            .times(3)
+            .returning(move |_, key| {
+                bound.lock().unwrap().push(key);
+                next_id += 1;
+                Ok(next_id.to_string())
+            });
+
+        let store = secrets::FileStore::new(&path);
+        let batch = register_nodes(
+            3,
+            &path,
+            Box::new(orchestrator),
+            &store,
+            RegisterOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(batch.node_ids, vec!["101", "102", "103"]);
+        let manifest = NodeManifest::load_or_default(&nodes_manifest_path(&path)).unwrap();
+        assert_eq!(manifest.node_ids(), vec!["101", "102", "103"]);
+        assert_eq!(Config::load_from_file(&path).unwrap().node_id, "42");
+        // Each node keeps the key the orchestrator bound to it
+        for (node_id, key) in [101, 102, 103]
+            .into_iter()
+            .zip(bound_keys.lock().unwrap().iter())
+        {
+            let stored = secrets::stored_node_signing_key(&store, node_id).unwrap();
+            assert_eq!(stored.unwrap().verifying_key(), *key);
+        }
+    }
+
+    #[tokio::test]
+    /// Deregistering the config's node clears it, drops it from the manifest and wipes its key.
+    async fn deregisters_node_and_cleans_up() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        write_config(
+            &path,
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "900000042",
+        );
+        let manifest_path = nodes_manifest_path(&path);
+        let mut manifest = NodeManifest::default();
+        for node_id in ["900000042", "900000043"] {
+            manifest.add(NodeEntry {
+                node_id: node_id.to_string(),
+                user_id: "existing-user-id".to_string(),
+                environment: "Production".to_string(),
+            });
+        }
+        manifest.save(&manifest_path).unwrap();
+        let store = secrets::FileStore::new(&path);
+        let signing_key = secrets::node_signing_key(&store, 900000042).unwrap();
+
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_deregister_node()
+            .withf(move |node_id, key| {
+                node_id == "900000042" && key.verifying_key() == signing_key.verifying_key()
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let options = RegisterOptions {
+            yes: true,
+            ..RegisterOptions::default()
+        };
+        let deregistration = deregister_node(
+            None,
+            &path,
+            Box::new(orchestrator),
+            &store,
+            false,
+            true,
+            options,
+        )
+        .await
+        .unwrap();
+
+        assert!(deregistration.deregistered && deregistration.wiped);
+        let config = Config::load_from_file(&path).unwrap();
+        assert!(config.node_id.is_empty());
+        assert_eq!(config.user_id, "existing-user-id");
+        let manifest = NodeManifest::load_or_default(&manifest_path).unwrap();
+        assert_eq!(manifest.node_ids(), vec!["900000043"]);
+        assert_eq!(store.get("signing-key-900000042").unwrap(), None);
+    }
+
+    #[tokio::test]
+    /// An orchestrator that cannot deregister nodes still lets the node be removed locally.
+    async fn deregister_unsupported_removes_node_locally() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        write_config(
+            &path,
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "900000042",
+        );
+        let store = secrets::FileStore::new(&path);
+        secrets::node_signing_key(&store, 900000042).unwrap();
+
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_deregister_node()
+            .times(1)
+            .returning(|_, _| {
+                Err(OrchestratorError::Http {
+                    status: 404,
+                    message: String::new(),
+                    headers: Default::default(),
+                })
+            });
+        let options = RegisterOptions {
+            yes: true,
+            ..RegisterOptions::default()
+        };
+        let deregistration = deregister_node(
+            None,
+            &path,
+            Box::new(orchestrator),
+            &store,
+            false,
+            false,
+            options,
+        )
+        .await
+        .unwrap();
+
+        assert!(!deregistration.deregistered);
+        assert!(Config::load_from_file(&path).unwrap().node_id.is_empty());
+    }
+
+    #[tokio::test]
+    /// Without the node's key, deregistering fails instead of signing with a new key.
+    async fn deregister_without_key_fails() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        write_config(
+            &path,
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "900000042",
+        );
+        let store = secrets::FileStore::new(&path);
+
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator.expect_deregister_node().never();
+        let options = RegisterOptions {
+            yes: true,
+            ..RegisterOptions::default()
+        };
+        let result = deregister_node(
+            None,
+            &path,
+            Box::new(orchestrator),
+            &store,
+            false,
+            false,
+            options,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(store.get("signing-key-900000042").unwrap(), None);
+        assert_eq!(Config::load_from_file(&path).unwrap().node_id, "900000042");
+    }
 }
@@ -1,12 +1,15 @@
 //! Simplified runtime for coordinating authenticated workers
 
-use crate::environment::Environment;
-use crate::events::Event;
-use crate::orchestrator::OrchestratorClient;
+use crate::control::RuntimeControl;
+use crate::events::{Event, TaskFetchState};
+use crate::ipc::TaskQueue;
+use crate::orchestrator::Orchestrator;
+use crate::session::state_machine::TransitionHooks;
 use crate::workers::authenticated_worker::AuthenticatedWorker;
 use crate::workers::core::WorkerConfig;
 use ed25519_dalek::SigningKey;
-use tokio::sync::{broadcast, mpsc};
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc, watch};
 use tokio::task::JoinHandle;
 
 /// Start single authenticated worker
@@ -14,21 +17,19 @@ use tokio::task::JoinHandle;
 pub async fn start_authenticated_worker(
     node_id: u64,
     signing_key: SigningKey,
-    orchestrator: OrchestratorClient,
+    orchestrator: Arc<dyn Orchestrator>,
     shutdown: broadcast::Receiver<()>,
-    environment: Environment,
-    client_id: String,
+    config: WorkerConfig,
     max_tasks: Option<u32>,
-    max_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
-    num_workers: usize,
+    queue: TaskQueue,
+    control: RuntimeControl,
+    transition_hooks: TransitionHooks,
 ) -> (
     mpsc::Receiver<Event>,
     Vec<JoinHandle<()>>,
     broadcast::Sender<()>,
+    watch::Receiver<TaskFetchState>,
 ) {
-    let mut config = WorkerConfig::new(environment, client_id);
-    config.max_difficulty = max_difficulty;
-    config.num_workers = num_workers;
     let (event_sender, event_receiver) =
         mpsc::channel::<Event>(crate::consts::cli_consts::EVENT_QUEUE_SIZE);
 
@@ -43,8 +44,12 @@ pub async fn start_authenticated_worker(
         event_sender,
         max_tasks,
         shutdown_sender.clone(),
+        queue,
+        control,
+        transition_hooks,
     );
 
+    let fetch_state = worker.subscribe_fetch_state();
     let join_handles = worker.run(shutdown).await;
-    (event_receiver, join_handles, shutdown_sender)
+    (event_receiver, join_handles, shutdown_sender, fetch_state)
 }
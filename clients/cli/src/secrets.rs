@@ -0,0 +1,325 @@
+//! Secrets kept out of the config file
+//!
+//! The signing key of each node, and tokens such as the control API token, are kept in the OS
+//! keychain: the macOS Keychain, the Windows Credential Manager or the Secret Service on Linux.
+//! Headless servers often have no keychain, e.g. no Secret Service without a desktop session; there
+//! secrets fall back to files only their owner can read, in the `secrets` directory next to the
+//! config file. `NEXUS_SECRETS_BACKEND=keychain` or `file` picks a store explicitly.
+//!
+//! Secrets set with `nexus-cli secret set` are used when the matching flag and environment variable
+//! are not given, so tokens need not appear in service definitions or shell history.
+
+use crate::failures::{from_hex, to_hex};
+use ed25519_dalek::SigningKey;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Environment variable choosing the secret store, `keychain` or `file`
+pub const BACKEND_VAR: &str = "NEXUS_SECRETS_BACKEND";
+
+/// Keychain service the secrets are stored under
+pub const KEYCHAIN_SERVICE: &str = "nexus-cli";
+
+/// Secret holding the bearer token of the HTTP control API
+pub const CONTROL_TOKEN: &str = "control-token";
+
+/// Secret holding the token WebSocket event stream clients must present
+pub const EVENTS_WS_TOKEN: &str = "events-ws-token";
+
+/// Secrets that can be set with `nexus-cli secret set`
+pub const USER_SECRETS: &[&str] = &[CONTROL_TOKEN, EVENTS_WS_TOKEN];
+
+/// Why a secret cannot be read or written
+#[derive(Debug, Error)]
+pub enum SecretsError {
+    #[error("OS keychain: {0}")]
+    Keychain(String),
+
+    #[error("Secret '{0}' is not valid")]
+    Invalid(String),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Storage for secrets, by name
+pub trait SecretStore {
+    /// Short name of the store, for messages
+    fn kind(&self) -> &'static str;
+
+    /// The secret `name`, or `None` if it was never set.
+    fn get(&self, name: &str) -> Result<Option<String>, SecretsError>;
+
+    /// Set the secret `name` to `value`, replacing an existing one.
+    fn set(&self, name: &str, value: &str) -> Result<(), SecretsError>;
+
+    /// Remove the secret `name`; removing a secret that was never set is not an error.
+    fn delete(&self, name: &str) -> Result<(), SecretsError>;
+}
+
+/// Secrets in the OS keychain
+#[derive(Debug, Clone, Default)]
+pub struct KeychainStore;
+
+impl KeychainStore {
+    fn entry(&self, name: &str) -> Result<keyring::Entry, SecretsError> {
+        keyring::Entry::new(KEYCHAIN_SERVICE, name).map_err(keychain_error)
+    }
+
+    /// Whether the OS keychain can be used, e.g. the Secret Service is running.
+    pub fn is_available(&self) -> bool {
+        matches!(
+            self.entry("availability-check")
+                .map(|entry| entry.get_password()),
+            Ok(Ok(_)) | Ok(Err(keyring::Error::NoEntry))
+        )
+    }
+}
+
+fn keychain_error(error: keyring::Error) -> SecretsError {
+    SecretsError::Keychain(error.to_string())
+}
+
+impl SecretStore for KeychainStore {
+    fn kind(&self) -> &'static str {
+        "OS keychain"
+    }
+
+    fn get(&self, name: &str) -> Result<Option<String>, SecretsError> {
+        match self.entry(name)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(keychain_error(e)),
+        }
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), SecretsError> {
+        self.entry(name)?
+            .set_password(value)
+            .map_err(keychain_error)
+    }
+
+    fn delete(&self, name: &str) -> Result<(), SecretsError> {
+        match self.entry(name)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(keychain_error(e)),
+        }
+    }
+}
+
+/// Secrets in files readable only by their owner, for machines without a keychain
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    /// Store in the `secrets` directory next to the config file at `config_path`.
+    pub fn new(config_path: &Path) -> Self {
+        let dir = config_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join("secrets");
+        Self { dir }
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf, SecretsError> {
+        // Names become file names; keep them from leaving the directory
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(SecretsError::Invalid(name.to_string()));
+        }
+        Ok(self.dir.join(name))
+    }
+}
+
+impl SecretStore for FileStore {
+    fn kind(&self) -> &'static str {
+        "file"
+    }
+
+    fn get(&self, name: &str) -> Result<Option<String>, SecretsError> {
+        match std::fs::read_to_string(self.path(name)?) {
+            Ok(value) => Ok(Some(value.trim_end().to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), SecretsError> {
+        let path = self.path(name)?;
+        std::fs::create_dir_all(&self.dir)?;
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            std::fs::set_permissions(&self.dir, std::fs::Permissions::from_mode(0o700))?;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+        // `mode` only applies to new files
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+        file.write_all(value.as_bytes())?;
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> Result<(), SecretsError> {
+        match std::fs::remove_file(self.path(name)?) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// The secret store for the config file at `config_path`: the OS keychain if it can be used,
+/// otherwise files next to the config file, unless [`BACKEND_VAR`] says otherwise.
+pub fn open(config_path: &Path) -> Box<dyn SecretStore> {
+    match std::env::var(BACKEND_VAR).as_deref() {
+        Ok("file") => Box::new(FileStore::new(config_path)),
+        Ok("keychain") => Box::new(KeychainStore),
+        _ if KeychainStore.is_available() => Box::new(KeychainStore),
+        _ => Box::new(FileStore::new(config_path)),
+    }
+}
+
+/// The secret `name` from the store for `config_path`, if set; a store that cannot be read
+/// counts as not set.
+pub fn lookup(config_path: &Path, name: &str) -> Option<String> {
+    open(config_path)
+        .get(name)
+        .ok()
+        .flatten()
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn signing_key_name(node_id: u64) -> String {
+    format!("signing-key-{}", node_id)
+}
+
+/// Remove the signing key of node `node_id`, e.g. after deregistering the node.
+pub fn delete_node_signing_key(store: &dyn SecretStore, node_id: u64) -> Result<(), SecretsError> {
+    store.delete(&signing_key_name(node_id))
+}
+
+/// The signing key of node `node_id`, created and stored on first use, so the node signs with the
+/// same key in every session.
+pub fn node_signing_key(store: &dyn SecretStore, node_id: u64) -> Result<SigningKey, SecretsError> {
+    if let Some(signing_key) = stored_node_signing_key(store, node_id)? {
+        return Ok(signing_key);
+    }
+    let signing_key = SigningKey::generate(&mut rand_core::OsRng);
+    save_node_signing_key(store, node_id, &signing_key)?;
+    Ok(signing_key)
+}
+
+/// The signing key of node `node_id`, if this machine has one.
+pub fn stored_node_signing_key(
+    store: &dyn SecretStore,
+    node_id: u64,
+) -> Result<Option<SigningKey>, SecretsError> {
+    let name = signing_key_name(node_id);
+    let Some(hex) = store.get(&name)? else {
+        return Ok(None);
+    };
+    let bytes = from_hex(hex.trim()).map_err(|_| SecretsError::Invalid(name.clone()))?;
+    let bytes = <[u8; 32]>::try_from(bytes).map_err(|_| SecretsError::Invalid(name))?;
+    Ok(Some(SigningKey::from_bytes(&bytes)))
+}
+
+/// Store `signing_key` as the signing key of node `node_id`, e.g. the key bound to the node when
+/// it was registered.
+pub fn save_node_signing_key(
+    store: &dyn SecretStore,
+    node_id: u64,
+    signing_key: &SigningKey,
+) -> Result<(), SecretsError> {
+    store.set(&signing_key_name(node_id), &to_hex(signing_key.as_bytes()))
+}
+
+/// Set the secret `name`, for `nexus-cli secret set`.
+///
+/// Without `value`, it is typed in the terminal without echo, or read from the first line of
+/// stdin, e.g. `echo $TOKEN | nexus-cli secret set control-token`.
+pub fn set_secret(
+    config_path: &Path,
+    name: &str,
+    value: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let value = match value {
+        Some(value) => value,
+        None if io::stdin().is_terminal() => rpassword::prompt_password(format!("{}: ", name))?,
+        None => {
+            let mut line = String::new();
+            io::stdin().read_line(&mut line)?;
+            line
+        }
+    };
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(SecretsError::Invalid(name.to_string()).into());
+    }
+    let store = open(config_path);
+    store.set(name, value)?;
+    crate::cli_messages::print_success("Secret saved", &format!("{} in {}", name, store.kind()));
+    Ok(())
+}
+
+/// Remove the secret `name`, for `nexus-cli secret delete`.
+pub fn delete_secret(config_path: &Path, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let store = open(config_path);
+    store.delete(name)?;
+    crate::cli_messages::print_success(
+        "Secret removed",
+        &format!("{} from {}", name, store.kind()),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::new(&dir.path().join("config.json"));
+        assert_eq!(store.get(CONTROL_TOKEN).unwrap(), None);
+
+        store.set(CONTROL_TOKEN, "first").unwrap();
+        store.set(CONTROL_TOKEN, "second").unwrap();
+        assert_eq!(store.get(CONTROL_TOKEN).unwrap().as_deref(), Some("second"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let path = dir.path().join("secrets").join(CONTROL_TOKEN);
+            let mode = std::fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        store.delete(CONTROL_TOKEN).unwrap();
+        store.delete(CONTROL_TOKEN).unwrap();
+        assert_eq!(store.get(CONTROL_TOKEN).unwrap(), None);
+        assert!(store.set("../config.json", "x").is_err());
+    }
+
+    #[test]
+    fn test_node_signing_key_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::new(&dir.path().join("config.json"));
+        let first = node_signing_key(&store, 42).unwrap();
+        let again = node_signing_key(&store, 42).unwrap();
+        assert_eq!(first.to_bytes(), again.to_bytes());
+        let other = node_signing_key(&store, 43).unwrap();
+        assert_ne!(first.to_bytes(), other.to_bytes());
+    }
+}
@@ -0,0 +1,170 @@
+//! Running headless at boot as a Windows service
+//!
+//! `nexus-cli service install -- --max-threads 4` registers a service that starts proving at boot
+//! as `start --headless --max-threads 4`. `service start` and `service stop` control it and
+//! `service uninstall` removes it; all four need an administrator shell. Stopping the service
+//! drains the session like Ctrl+C does.
+//!
+//! Services run as LocalSystem, whose home directory is not the installing user's, so the
+//! service is told which `.nexus` directory to use (see [`crate::config::nexus_dir`]). Warnings
+//! and errors are written to the Windows event log under the service name, as there is no
+//! console to print them to.
+//!
+//! On other systems, run `nexus-cli start --headless` from systemd or launchd instead.
+
+cfg_if::cfg_if! {
+    if #[cfg(windows)] {
+        mod windows;
+        pub use windows::{install, run, start, stop, uninstall};
+    }
+}
+
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::OnceLock;
+use thiserror::Error;
+use tokio::sync::watch;
+
+/// Name of the service and of its event log source
+pub const SERVICE_NAME: &str = "NexusNetwork";
+
+/// Name shown in the Services console
+pub const SERVICE_DISPLAY_NAME: &str = "Nexus Network Prover";
+
+/// Description shown in the Services console
+pub const SERVICE_DESCRIPTION: &str = "Proves tasks for the Nexus network in the background.";
+
+#[derive(Debug, Error)]
+pub enum ServiceError {
+    #[error(
+        "Windows services are not available on this system; run `nexus-cli start --headless` \
+         from systemd or launchd instead"
+    )]
+    Unsupported,
+
+    #[cfg(windows)]
+    #[error("Service control failed (is this an administrator shell?): {0}")]
+    Windows(#[from] windows_service::Error),
+
+    #[error("Could not register the event log source: {0}")]
+    EventLog(String),
+
+    #[error("Could not locate the nexus-cli executable: {0}")]
+    Executable(#[source] std::io::Error),
+
+    #[error("Proving session failed: {0}")]
+    Session(String),
+}
+
+/// Command line of the session the service runs: `start --headless` with `start_args`.
+pub fn start_arguments(start_args: &[String]) -> Vec<OsString> {
+    let mut arguments: Vec<OsString> = vec!["start".into(), "--headless".into()];
+    // Headless is implied; passing it twice would be an error
+    arguments.extend(
+        start_args
+            .iter()
+            .filter(|arg| *arg != "--headless")
+            .map(OsString::from),
+    );
+    arguments
+}
+
+/// Arguments the service control manager runs the executable with, proving with `start_args`
+/// and the config in `nexus_dir`.
+pub fn service_arguments(nexus_dir: &Path, start_args: &[String]) -> Vec<OsString> {
+    let mut arguments: Vec<OsString> = vec![
+        "service".into(),
+        "run".into(),
+        "--nexus-dir".into(),
+        nexus_dir.into(),
+    ];
+    arguments.extend(start_arguments(start_args));
+    arguments
+}
+
+fn stop_sender() -> &'static watch::Sender<bool> {
+    static STOP: OnceLock<watch::Sender<bool>> = OnceLock::new();
+    STOP.get_or_init(|| watch::Sender::new(false))
+}
+
+/// Ask the running session to drain and exit, e.g. when the service is stopped.
+pub fn request_stop() {
+    stop_sender().send_replace(true);
+}
+
+/// Wait until [`request_stop`] is called; never returns outside a service.
+pub async fn stop_requested() {
+    let mut stop = stop_sender().subscribe();
+    // The sender is never dropped
+    let _ = stop.wait_for(|stop| *stop).await;
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(not(windows))] {
+        /// Register the service; only available on Windows.
+        pub fn install(_arguments: &[OsString]) -> Result<(), ServiceError> {
+            Err(ServiceError::Unsupported)
+        }
+
+        /// Remove the service; only available on Windows.
+        pub fn uninstall() -> Result<(), ServiceError> {
+            Err(ServiceError::Unsupported)
+        }
+
+        /// Start the installed service; only available on Windows.
+        pub fn start() -> Result<(), ServiceError> {
+            Err(ServiceError::Unsupported)
+        }
+
+        /// Stop the running service; only available on Windows.
+        pub fn stop() -> Result<(), ServiceError> {
+            Err(ServiceError::Unsupported)
+        }
+
+        /// Run `session` as the service; only available on Windows.
+        pub fn run(
+            _session: Box<dyn FnOnce() -> Result<(), String> + Send>,
+        ) -> Result<(), ServiceError> {
+            Err(ServiceError::Unsupported)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_service_arguments() {
+        let start_args = ["--headless", "--max-threads", "4"].map(String::from);
+        let arguments = service_arguments(&PathBuf::from("nexus"), &start_args);
+        assert_eq!(
+            arguments,
+            [
+                "service",
+                "run",
+                "--nexus-dir",
+                "nexus",
+                "start",
+                "--headless",
+                "--max-threads",
+                "4"
+            ]
+            .map(OsString::from)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stop_requested() {
+        let waiter = tokio::spawn(stop_requested());
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        request_stop();
+        tokio::time::timeout(std::time::Duration::from_secs(1), waiter)
+            .await
+            .expect("stop should be seen")
+            .unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+//! Windows service control manager integration
+
+use super::{SERVICE_DESCRIPTION, SERVICE_DISPLAY_NAME, SERVICE_NAME, ServiceError, request_stop};
+use std::ffi::{OsStr, OsString};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use windows_service::service::{
+    ServiceAccess, ServiceControl, ServiceControlAccept, ServiceErrorControl, ServiceExitCode,
+    ServiceInfo, ServiceStartType, ServiceState, ServiceStatus, ServiceType,
+};
+use windows_service::service_control_handler::{
+    self, ServiceControlHandlerResult, ServiceStatusHandle,
+};
+use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+use windows_service::{define_windows_service, service_dispatcher};
+
+/// How long stopping may take, as the task in progress is finished first
+const STOP_WAIT_HINT: Duration = Duration::from_secs(120);
+
+type Session = Box<dyn FnOnce() -> Result<(), String> + Send>;
+
+/// Session to run once the service control manager calls [`service_main`]
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+define_windows_service!(ffi_service_main, service_main);
+
+fn service_manager(access: ServiceManagerAccess) -> Result<ServiceManager, ServiceError> {
+    Ok(ServiceManager::local_computer(None::<&str>, access)?)
+}
+
+/// Register the service to start at boot with `arguments` (see [`super::service_arguments`]),
+/// and the event log source it reports errors to.
+pub fn install(arguments: &[OsString]) -> Result<(), ServiceError> {
+    let manager =
+        service_manager(ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE)?;
+    let info = ServiceInfo {
+        name: SERVICE_NAME.into(),
+        display_name: SERVICE_DISPLAY_NAME.into(),
+        service_type: ServiceType::OWN_PROCESS,
+        start_type: ServiceStartType::AutoStart,
+        error_control: ServiceErrorControl::Normal,
+        executable_path: std::env::current_exe().map_err(ServiceError::Executable)?,
+        launch_arguments: arguments.to_vec(),
+        dependencies: vec![],
+        // LocalSystem
+        account_name: None,
+        account_password: None,
+    };
+    let service = manager.create_service(&info, ServiceAccess::CHANGE_CONFIG)?;
+    service.set_description(SERVICE_DESCRIPTION)?;
+    eventlog::register(SERVICE_NAME).map_err(|e| ServiceError::EventLog(e.to_string()))?;
+    Ok(())
+}
+
+/// Stop the service if it is running, then remove it and its event log source.
+pub fn uninstall() -> Result<(), ServiceError> {
+    let manager = service_manager(ServiceManagerAccess::CONNECT)?;
+    let service = manager.open_service(
+        SERVICE_NAME,
+        ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE,
+    )?;
+    if service.query_status()?.current_state != ServiceState::Stopped {
+        service.stop()?;
+    }
+    service.delete()?;
+    // The service is gone either way; a leftover event source is harmless
+    let _ = eventlog::deregister(SERVICE_NAME);
+    Ok(())
+}
+
+/// Start the installed service.
+pub fn start() -> Result<(), ServiceError> {
+    let manager = service_manager(ServiceManagerAccess::CONNECT)?;
+    let service = manager.open_service(SERVICE_NAME, ServiceAccess::START)?;
+    service.start::<&OsStr>(&[])?;
+    Ok(())
+}
+
+/// Ask the running service to drain and stop.
+pub fn stop() -> Result<(), ServiceError> {
+    let manager = service_manager(ServiceManagerAccess::CONNECT)?;
+    let service = manager.open_service(SERVICE_NAME, ServiceAccess::STOP)?;
+    service.stop()?;
+    Ok(())
+}
+
+/// Hand the process to the service control manager and run `session` as the service. Blocks
+/// until the service has stopped.
+pub fn run(session: Session) -> Result<(), ServiceError> {
+    *SESSION.lock().unwrap_or_else(|e| e.into_inner()) = Some(session);
+    service_dispatcher::start(SERVICE_NAME, ffi_service_main)?;
+    Ok(())
+}
+
+fn service_main(_arguments: Vec<OsString>) {
+    // Without a registered source, e.g. after a manual install, errors are lost but proving works
+    let _ = eventlog::init(SERVICE_NAME, log::Level::Warn);
+    if let Err(e) = run_service() {
+        log::error!("{}", e);
+    }
+}
+
+fn report_status(
+    status_handle: ServiceStatusHandle,
+    current_state: ServiceState,
+    exit_code: ServiceExitCode,
+    wait_hint: Duration,
+) -> Result<(), ServiceError> {
+    status_handle.set_service_status(ServiceStatus {
+        service_type: ServiceType::OWN_PROCESS,
+        current_state,
+        controls_accepted: match current_state {
+            ServiceState::Running => ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+            _ => ServiceControlAccept::empty(),
+        },
+        exit_code,
+        checkpoint: 0,
+        wait_hint,
+        process_id: None,
+    })?;
+    Ok(())
+}
+
+fn run_service() -> Result<(), ServiceError> {
+    // Set once registered, for the control handler to report the stop with
+    static STATUS_HANDLE: OnceLock<ServiceStatusHandle> = OnceLock::new();
+    let status_handle = service_control_handler::register(SERVICE_NAME, |control| match control {
+        ServiceControl::Stop | ServiceControl::Shutdown => {
+            request_stop();
+            if let Some(status_handle) = STATUS_HANDLE.get() {
+                let _ = report_status(
+                    *status_handle,
+                    ServiceState::StopPending,
+                    ServiceExitCode::Win32(0),
+                    STOP_WAIT_HINT,
+                );
+            }
+            ServiceControlHandlerResult::NoError
+        }
+        ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+        _ => ServiceControlHandlerResult::NotImplemented,
+    })?;
+    let _ = STATUS_HANDLE.set(status_handle);
+
+    report_status(
+        status_handle,
+        ServiceState::Running,
+        ServiceExitCode::Win32(0),
+        Duration::default(),
+    )?;
+    let session = SESSION.lock().unwrap_or_else(|e| e.into_inner()).take();
+    let result = match session {
+        Some(session) => session(),
+        None => Ok(()),
+    };
+    let exit_code = match &result {
+        Ok(()) => ServiceExitCode::Win32(0),
+        Err(_) => ServiceExitCode::ServiceSpecific(1),
+    };
+    report_status(
+        status_handle,
+        ServiceState::Stopped,
+        exit_code,
+        Duration::default(),
+    )?;
+    result.map_err(ServiceError::Session)
+}
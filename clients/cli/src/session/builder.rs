@@ -0,0 +1,530 @@
+//! Programmatic session API
+//!
+//! [`Session::builder`] is the entry point for embedding the prover in other programs:
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use nexus_cli_core::Session;
+//!
+//! let mut session = Session::builder().node_id(12345).max_tasks(10).start().await?;
+//! while let Some(event) = session.next_event().await {
+//!     println!("{}", event);
+//! }
+//! session.wait().await;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::dry_run::run_dry_run;
+use super::simulate::{self, SimulationReport};
+use super::{SessionData, run_headless_mode, run_tui_mode, setup_session};
+use crate::accept_policy::AcceptPolicy;
+use crate::affinity::CorePinning;
+use crate::config::{Config, get_config_path};
+use crate::consts::cli_consts::simulate::NODE_ID as SIMULATED_NODE_ID;
+use crate::control::ControlApiConfig;
+use crate::environment::Environment;
+use crate::event_stream::EventStreamConfig;
+use crate::events::{Event, TaskFetchState};
+use crate::exit_code::UsageError;
+use crate::labels;
+use crate::metrics_export::MetricsExportConfig;
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::orchestrator::mock::{MockConfig, start_mock_orchestrator};
+use crate::orchestrator::recording::{start_recording_proxy, start_replay_server};
+use crate::orchestrator::{self, FailoverOrchestrator, Orchestrator, Transport};
+use crate::power::BatteryConfig;
+use crate::session::lifetime::SessionLifetime;
+use crate::session::state_machine::{TransitionHook, TransitionHooks};
+use crate::session::status_line::StatusLineConfig;
+use crate::session::summary::SessionSummary;
+use crate::ui::UiMode;
+use crate::version::manager::{VersionNotice, validate_version_requirements};
+use std::error::Error;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, watch};
+
+/// Options for starting a [`Session`]
+#[derive(Debug, Clone, Default)]
+pub struct SessionBuilder {
+    pub(super) node_id: Option<u64>,
+    pub(super) environment: Environment,
+    pub(super) transport: Transport,
+    pub(super) config_path: Option<PathBuf>,
+    pub(super) max_threads: Option<u32>,
+    pub(super) max_tasks: Option<u32>,
+    pub(super) stop_after_points: Option<u64>,
+    pub(super) lifetime: SessionLifetime,
+    pub(super) max_difficulty: Option<TaskDifficulty>,
+    pub(super) accept_policy: Option<AcceptPolicy>,
+    pub(super) check_mem: bool,
+    pub(super) core_pinning: Option<CorePinning>,
+    pub(super) battery: Option<BatteryConfig>,
+    pub(super) only_when_idle: Option<Duration>,
+    pub(super) thermal_limit: Option<f32>,
+    pub(super) failure_dir: Option<PathBuf>,
+    pub(super) proof_export_dir: Option<PathBuf>,
+    pub(super) control_api: Option<ControlApiConfig>,
+    pub(super) event_stream: Option<EventStreamConfig>,
+    pub(super) summary_file: Option<PathBuf>,
+    pub(super) metrics_export: Option<MetricsExportConfig>,
+    pub(super) mock_orchestrator: Option<MockConfig>,
+    pub(super) record_file: Option<PathBuf>,
+    pub(super) replay_file: Option<PathBuf>,
+    pub(super) override_version_check: bool,
+    pub(super) labels: Vec<(String, String)>,
+    pub(super) ephemeral_labels: bool,
+    pub(super) fallback_urls: Vec<String>,
+    pub(super) sign_requests: bool,
+    pub(super) coordinate: bool,
+    pub(super) ui_fps: Option<u32>,
+    pub(super) status_line: StatusLineConfig,
+    pub(super) transition_hooks: TransitionHooks,
+}
+
+impl SessionBuilder {
+    /// Prove as this node instead of the one registered in the config file.
+    pub fn node_id(mut self, node_id: u64) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+
+    /// Orchestrator environment (default: production).
+    pub fn environment(mut self, environment: Environment) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Protocol used to talk to the orchestrator (default: HTTP).
+    pub fn transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Config file to read (default: `~/.nexus/config.json`).
+    pub fn config_path(mut self, config_path: impl Into<PathBuf>) -> Self {
+        self.config_path = Some(config_path.into());
+        self
+    }
+
+    /// Maximum number of proving threads, capped at the number of CPU cores.
+    pub fn max_threads(mut self, max_threads: u32) -> Self {
+        self.max_threads = Some(max_threads);
+        self
+    }
+
+    /// Stop after this many tasks have been submitted.
+    pub fn max_tasks(mut self, max_tasks: u32) -> Self {
+        self.max_tasks = Some(max_tasks);
+        self
+    }
+
+    /// Stop once the session has earned this many points, after finishing the task in flight.
+    pub fn stop_after_points(mut self, points: u64) -> Self {
+        self.stop_after_points = Some(points);
+        self
+    }
+
+    /// Drain and end the session this long after it first started, e.g. for systemd to restart it.
+    pub fn max_runtime(mut self, max_runtime: Duration) -> Self {
+        self.lifetime.max_runtime = Some(max_runtime);
+        self
+    }
+
+    /// Drain and start the CLI again with the same arguments this long after the session started.
+    ///
+    /// The process is replaced, so this only suits sessions run from the CLI itself.
+    pub fn restart_interval(mut self, restart_interval: Duration) -> Self {
+        self.lifetime.restart_interval = Some(restart_interval);
+        self
+    }
+
+    /// Highest task difficulty to request.
+    pub fn max_difficulty(mut self, max_difficulty: TaskDifficulty) -> Self {
+        self.max_difficulty = Some(max_difficulty);
+        self
+    }
+
+    /// Preview each task and take only those `policy` accepts, declining the others.
+    pub fn accept_policy(mut self, policy: AcceptPolicy) -> Self {
+        self.accept_policy = Some(policy);
+        self
+    }
+
+    /// Clamp the thread count to what available memory supports.
+    pub fn check_memory(mut self, check_mem: bool) -> Self {
+        self.check_mem = check_mem;
+        self
+    }
+
+    /// Pin prover workers to CPU cores.
+    pub fn pin_cores(mut self, core_pinning: CorePinning) -> Self {
+        self.core_pinning = Some(core_pinning);
+        self
+    }
+
+    /// Pause or throttle proving while on battery power below a charge threshold.
+    pub fn battery(mut self, battery: BatteryConfig) -> Self {
+        self.battery = Some(battery);
+        self
+    }
+
+    /// Only prove once the machine has not been used for `min_idle`, and hold proving while it is
+    /// in use, see [`crate::idle`].
+    pub fn only_when_idle(mut self, min_idle: Duration) -> Self {
+        self.only_when_idle = Some(min_idle);
+        self
+    }
+
+    /// Throttle proving while the machine is at or above `limit` °C, see [`crate::thermal`].
+    pub fn thermal_limit(mut self, limit: f32) -> Self {
+        self.thermal_limit = Some(limit);
+        self
+    }
+
+    /// Write a failure bundle to `dir` for each failed proof (see [`crate::failures`]).
+    pub fn capture_failures(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.failure_dir = Some(dir.into());
+        self
+    }
+
+    /// Write each accepted proof and its metadata to `dir` (see [`crate::prover::export`]).
+    pub fn export_proofs(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.proof_export_dir = Some(dir.into());
+        self
+    }
+
+    /// Serve the HTTP control API.
+    pub fn control_api(mut self, control_api: ControlApiConfig) -> Self {
+        self.control_api = Some(control_api);
+        self
+    }
+
+    /// Broadcast events to WebSocket clients.
+    pub fn event_stream(mut self, event_stream: EventStreamConfig) -> Self {
+        self.event_stream = Some(event_stream);
+        self
+    }
+
+    /// Also write the summary printed on exit to this file.
+    pub fn summary_file(mut self, summary_file: impl Into<PathBuf>) -> Self {
+        self.summary_file = Some(summary_file.into());
+        self
+    }
+
+    /// Append snapshots of the task counters and resource use to a CSV or NDJSON file, see
+    /// [`crate::metrics_export`].
+    pub fn metrics_export(mut self, metrics_export: MetricsExportConfig) -> Self {
+        self.metrics_export = Some(metrics_export);
+        self
+    }
+
+    /// Talk to a mock orchestrator served from this process instead of `environment`.
+    ///
+    /// Nothing leaves the machine: the version check is skipped and the country is reported
+    /// as unknown.
+    pub fn mock_orchestrator(mut self, mock: MockConfig) -> Self {
+        self.mock_orchestrator = Some(mock);
+        self
+    }
+
+    /// Append every request to the orchestrator, and its response, to this file.
+    pub fn record(mut self, record_file: impl Into<PathBuf>) -> Self {
+        self.record_file = Some(record_file.into());
+        self
+    }
+
+    /// Answer orchestrator requests from a file written by [`SessionBuilder::record`] instead
+    /// of `environment`. Like [`SessionBuilder::mock_orchestrator`], this runs offline.
+    pub fn replay(mut self, replay_file: impl Into<PathBuf>) -> Self {
+        self.replay_file = Some(replay_file.into());
+        self
+    }
+
+    /// Fall back to these orchestrator URLs, in order, when the one for `environment` is
+    /// unreachable. They are tried after those configured for the environment in the config
+    /// file, see [`crate::orchestrator::failover`]. Ignored with a mock, replay or recording
+    /// orchestrator.
+    pub fn fallback_urls(mut self, urls: impl IntoIterator<Item = String>) -> Self {
+        self.fallback_urls.extend(urls);
+        self
+    }
+
+    /// Sign every orchestrator request of the session with the prover's key, not only proof
+    /// submissions, see [`crate::orchestrator::signing`].
+    pub fn sign_requests(mut self, sign_requests: bool) -> Self {
+        self.sign_requests = sign_requests;
+        self
+    }
+
+    /// Share a fetch schedule with the other instances on the host that coordinate, so they take
+    /// turns instead of tripping the orchestrator's rate limit together, see
+    /// [`crate::coordinator`]. Unix only.
+    pub fn coordinate(mut self, coordinate: bool) -> Self {
+        self.coordinate = coordinate;
+        self
+    }
+
+    /// Call `hook` on every prover state transition, see [`crate::session::state_machine`].
+    pub fn on_transition(mut self, hook: impl TransitionHook + 'static) -> Self {
+        self.transition_hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Redraw the dashboard at most this many times a second (default: 10).
+    pub fn ui_fps(mut self, ui_fps: u32) -> Self {
+        self.ui_fps = Some(ui_fps);
+        self
+    }
+
+    /// Keep the terminal title set to the node status, e.g. `nexus ✓ proving Task-123 | 42
+    /// proofs`, see [`crate::session::status_line`].
+    pub fn terminal_title(mut self, terminal_title: bool) -> Self {
+        self.status_line.terminal_title = terminal_title;
+        self
+    }
+
+    /// Keep the node status line in this file, e.g. for the tmux status line.
+    pub fn status_file(mut self, status_file: impl Into<PathBuf>) -> Self {
+        self.status_line.file = Some(status_file.into());
+        self
+    }
+
+    /// Start even if this version of the CLI is blocked by the version requirements.
+    ///
+    /// The violated requirement is still shown in the dashboard banner or the headless log.
+    pub fn override_version_check(mut self, override_check: bool) -> Self {
+        self.override_version_check = override_check;
+        self
+    }
+
+    /// Set node labels, see [`crate::labels`]. They are merged into the labels saved in the config
+    /// file, and an empty value removes a label.
+    pub fn labels(mut self, labels: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.labels.extend(labels);
+        self
+    }
+
+    /// Use the labels set with [`SessionBuilder::labels`] for this session only, without saving
+    /// them to the config file, e.g. for a node of a fleet sharing one config.
+    pub fn ephemeral_labels(mut self, ephemeral: bool) -> Self {
+        self.ephemeral_labels = ephemeral;
+        self
+    }
+
+    /// Check version requirements, resolve the node, and start the workers.
+    ///
+    /// Exits the process if this version of the CLI is blocked (see
+    /// [`SessionBuilder::override_version_check`]) or the service is not available in the current
+    /// country, matching the CLI's behavior.
+    pub async fn start(mut self) -> Result<Session, Box<dyn Error>> {
+        let connection = self.connect().await?;
+        let config_path = &connection.config_path;
+        let mut config =
+            Config::resolve(self.node_id, config_path, &connection.orchestrator).await?;
+
+        // New labels are kept for later sessions, if there is a config file to keep them in and
+        // they are not for this session only
+        if !self.labels.is_empty() {
+            labels::merge(&mut config.labels, std::mem::take(&mut self.labels))
+                .map_err(UsageError)?;
+            let saved = Config::load_from_file(config_path)
+                .ok()
+                .filter(|_| !self.ephemeral_labels);
+            if let Some(mut saved) = saved {
+                saved.labels = config.labels.clone();
+                saved.save(config_path)?;
+            }
+        }
+
+        let data = setup_session(config, connection, self).await?;
+        Ok(Session { data })
+    }
+
+    /// Check that a session could start, without starting one: resolve the node, fetch a task
+    /// without proving it, load the guest program and run a warm-up proof, then print a
+    /// readiness report, see [`crate::session::dry_run`].
+    ///
+    /// Returns the error behind the first failed check, if any. Labels are not saved.
+    pub async fn dry_run(mut self) -> Result<(), Box<dyn Error>> {
+        let connection = self.connect().await?;
+        run_dry_run(
+            self.node_id,
+            &connection.config_path,
+            connection.orchestrator,
+            connection.version_notice,
+            self.max_difficulty,
+        )
+        .await
+    }
+
+    /// Prove `tasks` synthetic tasks served from inside this process, without network access,
+    /// logging like `start --headless`, and return the throughput, see
+    /// [`crate::session::simulate`].
+    ///
+    /// The node ID, config file and orchestrator set on the builder are replaced.
+    pub async fn simulate(mut self, tasks: u32) -> Result<SimulationReport, Box<dyn Error>> {
+        let scratch_dir = simulate::scratch_dir()?;
+        self.node_id = Some(SIMULATED_NODE_ID);
+        self.config_path = Some(scratch_dir.join("config.json"));
+        self.mock_orchestrator = Some(simulate::mock_config());
+        self.record_file = None;
+        self.replay_file = None;
+        self.max_tasks = Some(tasks);
+
+        let result: Result<SimulationReport, Box<dyn Error>> = async {
+            let mut session = self.start().await?;
+            session.data.check_for_updates = false;
+            let num_workers = session.num_workers();
+            let summary = session.data.summary.clone();
+            session.run_headless().await?;
+            Ok(SimulationReport {
+                tasks,
+                num_workers,
+                summary: summary.snapshot(),
+            })
+        }
+        .await;
+        let _ = std::fs::remove_dir_all(&scratch_dir);
+        result
+    }
+
+    /// Check version requirements and connect to the orchestrator, through the mock, replay
+    /// server, recording proxy or fallbacks, if set.
+    async fn connect(&mut self) -> Result<Connection, Box<dyn Error>> {
+        let mut version_notice = None;
+        // The mock, replay server and recording proxy are served over HTTP, without fallbacks
+        let failover_allowed = self.mock_orchestrator.is_none()
+            && self.replay_file.is_none()
+            && self.record_file.is_none();
+        let (environment, transport) = if let Some(mock) = self.mock_orchestrator.take() {
+            orchestrator::pin_country(orchestrator::UNKNOWN_COUNTRY);
+            let orchestrator_url = start_mock_orchestrator(mock).await?;
+            (Environment::Custom { orchestrator_url }, Transport::Http)
+        } else if let Some(replay_file) = self.replay_file.take() {
+            orchestrator::pin_country(orchestrator::UNKNOWN_COUNTRY);
+            let orchestrator_url = start_replay_server(replay_file).await?;
+            (Environment::Custom { orchestrator_url }, Transport::Http)
+        } else {
+            version_notice = validate_version_requirements(self.override_version_check).await?;
+            match self.record_file.take() {
+                Some(record_file) => {
+                    let orchestrator_url =
+                        start_recording_proxy(self.environment.clone(), &record_file).await?;
+                    (Environment::Custom { orchestrator_url }, Transport::Http)
+                }
+                None => (self.environment.clone(), self.transport),
+            }
+        };
+
+        let config_path = match self.config_path.take() {
+            Some(path) => path,
+            None => get_config_path()?,
+        };
+        let mut fallback_urls = Vec::new();
+        if failover_allowed {
+            let configured = Config::load_from_file(&config_path)
+                .map(|saved| saved.orchestrator.fallback_urls(&environment))
+                .unwrap_or_default();
+            for url in configured
+                .into_iter()
+                .chain(std::mem::take(&mut self.fallback_urls))
+            {
+                if !fallback_urls.contains(&url) {
+                    fallback_urls.push(url);
+                }
+            }
+        }
+        let (orchestrator, failover_events) = if fallback_urls.is_empty() {
+            (orchestrator::connect(environment.clone(), transport)?, None)
+        } else {
+            let failover =
+                FailoverOrchestrator::new(environment.clone(), fallback_urls, transport)?;
+            let events = failover.subscribe();
+            (Arc::new(failover) as Arc<dyn Orchestrator>, Some(events))
+        };
+        Ok(Connection {
+            environment,
+            orchestrator,
+            failover_events,
+            config_path,
+            version_notice,
+        })
+    }
+}
+
+/// Orchestrator and config file a session runs against, before the node is resolved
+pub(super) struct Connection {
+    pub(super) environment: Environment,
+    pub(super) orchestrator: Arc<dyn Orchestrator>,
+    /// Switches between orchestrator endpoints, if there are fallbacks
+    pub(super) failover_events: Option<broadcast::Receiver<String>>,
+    pub(super) config_path: PathBuf,
+    /// Violated version requirement the session starts despite
+    pub(super) version_notice: Option<VersionNotice>,
+}
+
+/// A running prover session
+#[derive(Debug)]
+pub struct Session {
+    data: SessionData,
+}
+
+impl Session {
+    pub fn builder() -> SessionBuilder {
+        SessionBuilder::default()
+    }
+
+    /// Node this session proves for.
+    pub fn node_id(&self) -> u64 {
+        self.data.node_id
+    }
+
+    /// Number of proving workers.
+    pub fn num_workers(&self) -> usize {
+        self.data.num_workers
+    }
+
+    /// Next worker event, or `None` once the workers have stopped.
+    pub async fn next_event(&mut self) -> Option<Event> {
+        self.data.event_receiver.recv().await
+    }
+
+    /// Rate-limit budget of the task fetcher, updated after every fetch attempt.
+    pub fn fetch_state(&self) -> watch::Receiver<TaskFetchState> {
+        self.data.fetch_state.clone()
+    }
+
+    /// Session totals so far.
+    pub fn summary(&self) -> SessionSummary {
+        self.data.summary.snapshot()
+    }
+
+    /// Ask all workers to stop after their current step.
+    pub fn shutdown(&self) {
+        let _ = self.data.shutdown_sender.send(());
+    }
+
+    /// Wait for all workers to exit.
+    pub async fn wait(self) {
+        for handle in self.data.join_handles {
+            let _ = handle.await;
+        }
+    }
+
+    /// Log events to stdout until Ctrl+C or `max_tasks` is reached, like `start --headless`.
+    pub async fn run_headless(self) -> Result<(), Box<dyn Error>> {
+        run_headless_mode(self.data).await
+    }
+
+    /// Run the terminal dashboard in the given style until the user quits, like `start`.
+    pub async fn run_tui(
+        self,
+        with_background: bool,
+        ui_mode: UiMode,
+    ) -> Result<(), Box<dyn Error>> {
+        run_tui_mode(self.data, with_background, ui_mode).await
+    }
+}
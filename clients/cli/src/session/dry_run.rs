@@ -0,0 +1,223 @@
+//! Readiness checks for `start --dry-run`
+//!
+//! Goes through everything a session needs before its first proof, without starting workers:
+//! the version requirements, the node and its wallet, fetching a task, loading the guest program
+//! and a warm-up proof in a prover subprocess. The sample task is only described, never proved or
+//! submitted, so the orchestrator hands it to another node once it expires.
+//!
+//! The report uses the PASS/WARN/FAIL format of `doctor`. When a check fails, the error behind
+//! it is returned, so the exit code tells provisioning scripts what went wrong.
+
+use crate::config::Config;
+use crate::consts::cli_consts::dry_run::WARM_UP_INPUTS;
+use crate::doctor::{CheckResult, check_memory, print_report};
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::orchestrator::Orchestrator;
+use crate::orchestrator::client::ProofTaskResult;
+use crate::orchestrator::error::OrchestratorError;
+use crate::prover::engine::ProvingEngine;
+use crate::redact::redact_id;
+use crate::version::manager::VersionNotice;
+use ed25519_dalek::SigningKey;
+use std::error::Error;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Difficulty the task fetcher starts at without `--max-difficulty`
+const DEFAULT_DIFFICULTY: TaskDifficulty = TaskDifficulty::SmallMedium;
+
+/// Run the readiness checks and print the report.
+///
+/// Returns the error behind the first failed check, if any.
+pub async fn run_dry_run(
+    node_id: Option<u64>,
+    config_path: &Path,
+    orchestrator: Arc<dyn Orchestrator>,
+    version_notice: Option<VersionNotice>,
+    max_difficulty: Option<TaskDifficulty>,
+) -> Result<(), Box<dyn Error>> {
+    println!("Checking that the prover is ready, without proving any tasks...\n");
+    let difficulty = max_difficulty.unwrap_or(DEFAULT_DIFFICULTY);
+    let mut results = vec![check_version(version_notice.as_ref())];
+    // Errors behind failed checks, in order
+    let mut errors: Vec<Box<dyn Error>> = Vec::new();
+
+    match Config::resolve(node_id, config_path, &orchestrator).await {
+        Ok(config) => {
+            results.push(CheckResult::pass(
+                "Node",
+                format!(
+                    "Node {} of wallet {}",
+                    redact_id(&config.node_id),
+                    redact_id(&config.wallet_address)
+                ),
+            ));
+            match fetch_sample_task(orchestrator.as_ref(), &config.node_id, difficulty).await {
+                Ok(result) => results.push(describe_task(&result)),
+                // The orchestrator answered, so the node will get tasks once the limit resets
+                Err(e) if matches!(e, OrchestratorError::Http { status: 429, .. }) => {
+                    results.push(CheckResult::warn(
+                        "Task",
+                        format!("Rate limited by the orchestrator: {}", e),
+                        "Wait a few minutes before starting, or stop other instances of this node",
+                    ))
+                }
+                Err(e) => {
+                    results.push(CheckResult::fail(
+                        "Task",
+                        format!("Could not fetch a task: {}", e),
+                        "Run `nexus-cli doctor` to check connectivity to the orchestrator",
+                    ));
+                    errors.push(e.into());
+                }
+            }
+        }
+        Err(e) => {
+            results.push(CheckResult::fail(
+                "Node",
+                e.to_string(),
+                "Run `nexus-cli register-node`, or pass --node-id",
+            ));
+            results.push(CheckResult::warn(
+                "Task",
+                "Skipped without a node",
+                "Fix the node check first",
+            ));
+            errors.push(e);
+        }
+    }
+
+    results.push(check_memory(
+        crate::system::total_memory_gb(),
+        crate::system::num_cores(),
+        Some(difficulty),
+    ));
+
+    let loaded = tokio::task::spawn_blocking(|| ProvingEngine::fib_verifier().map(|_| ())).await;
+    match loaded {
+        Ok(Ok(())) => results.push(CheckResult::pass(
+            "Guest program",
+            "fib_input_initial loaded",
+        )),
+        Ok(Err(e)) => {
+            results.push(CheckResult::fail(
+                "Guest program",
+                e.to_string(),
+                "Reinstall the CLI with `curl https://cli.nexus.xyz/ | sh`",
+            ));
+            errors.push(e.into());
+        }
+        Err(e) => {
+            results.push(CheckResult::fail(
+                "Guest program",
+                e.to_string(),
+                "Report this bug",
+            ));
+            errors.push(e.into());
+        }
+    }
+
+    let started = Instant::now();
+    match ProvingEngine::prove_warm_up(&WARM_UP_INPUTS).await {
+        Ok(()) => results.push(CheckResult::pass(
+            "Warm-up proof",
+            format!(
+                "Proved and verified in a subprocess in {:.1}s",
+                started.elapsed().as_secs_f64()
+            ),
+        )),
+        Err(e) => {
+            results.push(CheckResult::fail(
+                "Warm-up proof",
+                e.to_string(),
+                "Check that the CLI may start subprocesses and has enough free memory",
+            ));
+            errors.push(e.into());
+        }
+    }
+
+    if print_report(&results) {
+        println!("Ready to prove.");
+    } else {
+        println!("Not ready to prove; see the failed checks above.");
+    }
+    match errors.into_iter().next() {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Violated version requirements, if the session would start despite one.
+fn check_version(version_notice: Option<&VersionNotice>) -> CheckResult {
+    const NAME: &str = "Version";
+    match version_notice {
+        Some(notice) => CheckResult::warn(NAME, notice.log_line(), "Run `nexus-cli update`"),
+        None => CheckResult::pass(
+            NAME,
+            format!(
+                "{} meets the version requirements",
+                env!("CARGO_PKG_VERSION")
+            ),
+        ),
+    }
+}
+
+/// Ask for a task as the session would, with a throwaway prover key.
+async fn fetch_sample_task(
+    orchestrator: &dyn Orchestrator,
+    node_id: &str,
+    difficulty: TaskDifficulty,
+) -> Result<ProofTaskResult, OrchestratorError> {
+    let signing_key = SigningKey::generate(&mut rand_core::OsRng);
+    orchestrator
+        .get_proof_task(node_id, signing_key.verifying_key(), difficulty)
+        .await
+}
+
+/// The sample task, to show that the node gets tasks it can prove.
+fn describe_task(result: &ProofTaskResult) -> CheckResult {
+    let task = &result.task;
+    let inputs = match task.public_inputs_list.len().max(1) {
+        1 => "1 input".to_string(),
+        n => format!("{} inputs", n),
+    };
+    CheckResult::pass(
+        "Task",
+        format!(
+            "Got task {} ({}, {}, {}); not proved",
+            redact_id(&task.task_id),
+            task.task_type.as_str_name(),
+            result.actual_difficulty.as_str_name(),
+            inputs
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doctor::CheckStatus;
+    use crate::nexus_orchestrator::TaskType;
+    use crate::task::Task;
+
+    #[test]
+    fn test_describe_task() {
+        let result = ProofTaskResult {
+            task: Task::new(
+                "task-123".to_string(),
+                "fib_input_initial".to_string(),
+                vec![9, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0],
+                TaskType::ProofRequired,
+                TaskDifficulty::Medium,
+            ),
+            actual_difficulty: TaskDifficulty::Medium,
+        };
+        let check = describe_task(&result);
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert_eq!(
+            check.detail,
+            "Got task task-123 (PROOF_REQUIRED, MEDIUM, 1 input); not proved"
+        );
+    }
+}
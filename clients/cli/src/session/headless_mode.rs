@@ -2,18 +2,30 @@
 
 use super::{
     SessionData,
-    messages::{print_session_exit_success, print_session_shutdown, print_session_starting},
+    lifetime::restart_process,
+    messages::{
+        print_session_exit_success, print_session_restarting, print_session_shutdown,
+        print_session_starting,
+    },
+    status_line::{restore_terminal_title, save_terminal_title, set_terminal_title},
+    summary::report_summary,
 };
-use crate::print_cmd_info;
+use crate::logging::LogLevel;
 use crate::version::checker::check_for_new_version;
+use crate::{print_cmd_info, print_cmd_warn};
 use std::error::Error;
+use std::sync::atomic::Ordering;
+use tokio::sync::watch;
 
 /// Runs the application in headless mode
 ///
 /// This function handles:
 /// 1. Console event logging
-/// 2. Ctrl+C shutdown handling
+/// 2. Ctrl+C and Windows service stop handling
 /// 3. Event loop management
+/// 4. Printing the session summary on exit
+/// 5. Restarting at the restart interval
+/// 6. Keeping the terminal title set to the node status, if requested
 ///
 /// # Arguments
 /// * `session` - Session data from setup
@@ -21,36 +33,88 @@ use std::error::Error;
 /// # Returns
 /// * `Ok(())` - Headless mode completed successfully
 /// * `Err` - Headless mode failed
-pub async fn run_headless_mode(mut session: SessionData) -> Result<(), Box<dyn Error>> {
+pub async fn run_headless_mode(session: SessionData) -> Result<(), Box<dyn Error>> {
+    let config_path = session.config_path.clone();
+    let restart_due = session.restart_due.clone();
+    // The session is dropped before restarting, so the new process finds its resources free
+    log_until_shutdown(session).await?;
+
+    // The restart interval was reached: start over with the same arguments
+    if restart_due.load(Ordering::SeqCst) {
+        print_session_restarting();
+        return Err(restart_process(&config_path).into());
+    }
+
+    Ok(())
+}
+
+/// Log events until shutdown, then wait for the workers and print the session summary
+async fn log_until_shutdown(mut session: SessionData) -> Result<(), Box<dyn Error>> {
     // Print session start message
     print_session_starting("headless", session.node_id);
 
+    // Version requirements the session was started despite
+    if let Some(notice) = &session.version_notice {
+        print_cmd_warn!("Version check", "{}", notice.log_line());
+    }
+
     // Check for new version and inform user
     let current_version = env!("CARGO_PKG_VERSION");
 
     // First check constraint violations
-    if let Some(message) = check_for_new_version(current_version).await {
-        // If no constraints violated, check for newer versions available
-        print_cmd_info!("Version check", "{}", message);
+    if session.check_for_updates {
+        if let Some(message) = check_for_new_version(current_version, session.update_channel).await
+        {
+            // If no constraints violated, check for newer versions available
+            print_cmd_info!("Version check", "{}", message);
+        }
     }
 
-    // Trigger shutdown on Ctrl+C
+    // Trigger shutdown on Ctrl+C, or when the Windows service is stopped
     let shutdown_sender_clone = session.shutdown_sender.clone();
     tokio::spawn(async move {
-        if tokio::signal::ctrl_c().await.is_ok() {
-            let _ = shutdown_sender_clone.send(());
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => {
+                if result.is_err() {
+                    // No console, as under the service control manager
+                    crate::service::stop_requested().await;
+                }
+            }
+            _ = crate::service::stop_requested() => {}
         }
+        let _ = shutdown_sender_clone.send(());
     });
 
     let mut shutdown_receiver = session.shutdown_sender.subscribe();
     let mut max_tasks_shutdown_receiver = session.max_tasks_shutdown_sender.subscribe();
 
+    // Without `--terminal-title`, a closed channel that never yields a title
+    let title_enabled = session.status_title.is_some();
+    let mut status_title = session
+        .status_title
+        .take()
+        .unwrap_or_else(|| watch::channel(String::new()).1);
+    if title_enabled {
+        save_terminal_title();
+        set_terminal_title(&status_title.borrow_and_update());
+    }
+
     // Event loop: log events to console until shutdown
     loop {
         tokio::select! {
             Some(event) = session.event_receiver.recv() => {
+                // Also report problems through `log`, i.e. to the event log when running as a
+                // Windows service
+                match event.log_level {
+                    LogLevel::Error => log::error!("{}", event.msg),
+                    LogLevel::Warn => log::warn!("{}", event.msg),
+                    _ => {}
+                }
                 println!("{}", event);
             }
+            Ok(()) = status_title.changed() => {
+                set_terminal_title(&status_title.borrow_and_update());
+            }
             _ = shutdown_receiver.recv() => {
                 break;
             }
@@ -60,12 +124,24 @@ pub async fn run_headless_mode(mut session: SessionData) -> Result<(), Box<dyn E
         }
     }
 
+    if title_enabled {
+        restore_terminal_title();
+    }
+
     // Wait for workers to finish
     print_session_shutdown();
     for handle in session.join_handles {
         let _ = handle.await;
     }
+    crate::chaos::check_workers_exited();
+    report_summary(
+        &session.summary,
+        &session.points,
+        session.orchestrator.as_ref(),
+        session.node_id,
+        session.summary_file.as_deref(),
+    )
+    .await;
     print_session_exit_success();
-
     Ok(())
 }
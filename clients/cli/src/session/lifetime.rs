@@ -0,0 +1,250 @@
+//! Session lifetime limits
+//!
+//! `--max-runtime` ends a session for good after a while: the workers drain and the CLI exits
+//! with status 0, so a supervisor like systemd can decide what happens next. `--restart-interval`
+//! recycles the session instead: the workers drain, and the CLI starts again in the same terminal
+//! with the same arguments. The max runtime counts from the first start, across restarts.
+
+use crate::control::RuntimeControl;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+
+/// Start of the first session, in seconds since the Unix epoch, passed on across restarts
+const SESSION_STARTED_AT_ENV: &str = "NEXUS_SESSION_STARTED_AT";
+
+/// How long a session may run, set with `--max-runtime` and `--restart-interval`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionLifetime {
+    /// End the session this long after the first start
+    pub max_runtime: Option<Duration>,
+    /// Restart the session this long after it started
+    pub restart_interval: Option<Duration>,
+}
+
+/// Which limit ends a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifetimeEnd {
+    MaxRuntime,
+    Restart,
+}
+
+impl SessionLifetime {
+    /// The limit reached first, and how long from now, given the time since the first start
+    fn next_end(&self, since_first_start: Duration) -> Option<(LifetimeEnd, Duration)> {
+        let max_runtime = self.max_runtime.map(|limit| {
+            (
+                LifetimeEnd::MaxRuntime,
+                limit.saturating_sub(since_first_start),
+            )
+        });
+        let restart = self
+            .restart_interval
+            .map(|interval| (LifetimeEnd::Restart, interval));
+        match (max_runtime, restart) {
+            // On a tie, stopping for good wins
+            (Some(stop), Some(restart)) if restart.1 < stop.1 => Some(restart),
+            (Some(stop), _) => Some(stop),
+            (None, restart) => restart,
+        }
+    }
+
+    /// Ask the workers to stop once a limit is reached. Returns a flag that is set if the session
+    /// should then restart rather than exit.
+    pub fn spawn(
+        self,
+        control: RuntimeControl,
+        mut shutdown: broadcast::Receiver<()>,
+    ) -> Arc<AtomicBool> {
+        let restart_due = Arc::new(AtomicBool::new(false));
+        let Some((end, after)) = self.next_end(since_first_start()) else {
+            return restart_due;
+        };
+        let flag = restart_due.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown.recv() => {}
+                _ = tokio::time::sleep(after) => {
+                    let reason = match end {
+                        LifetimeEnd::MaxRuntime => format!(
+                            "Reached the maximum runtime of {}",
+                            format_duration(self.max_runtime.unwrap_or_default())
+                        ),
+                        LifetimeEnd::Restart => {
+                            flag.store(true, Ordering::SeqCst);
+                            format!(
+                                "Restart interval of {} reached",
+                                format_duration(self.restart_interval.unwrap_or_default())
+                            )
+                        }
+                    };
+                    control.request_stop(reason);
+                }
+            }
+        });
+        restart_due
+    }
+}
+
+/// When the first session started: inherited from a restart, or now
+fn session_started_at() -> u64 {
+    static STARTED_AT: OnceLock<u64> = OnceLock::new();
+    *STARTED_AT.get_or_init(|| {
+        std::env::var(SESSION_STARTED_AT_ENV)
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .unwrap_or_else(unix_now)
+    })
+}
+
+fn since_first_start() -> Duration {
+    Duration::from_secs(unix_now().saturating_sub(session_started_at()))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Parse a duration like `90s`, `30m`, `6h` or `2d`; a bare number is seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let (number, unit_secs) = match s.char_indices().last() {
+        Some((i, 's')) => (&s[..i], 1),
+        Some((i, 'm')) => (&s[..i], 60),
+        Some((i, 'h')) => (&s[..i], 3600),
+        Some((i, 'd')) => (&s[..i], 86400),
+        _ => (s, 1),
+    };
+    let secs = number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit_secs))
+        .filter(|&secs| secs > 0)
+        .ok_or_else(|| format!("'{}' is not a duration, e.g. 90s, 30m, 6h or 2d", s))?;
+    Ok(Duration::from_secs(secs))
+}
+
+/// Format a duration in the largest unit that divides it, e.g. `6h`.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0 => "0s".to_string(),
+        _ if secs % 86400 == 0 => format!("{}d", secs / 86400),
+        _ if secs % 3600 == 0 => format!("{}h", secs / 3600),
+        _ if secs % 60 == 0 => format!("{}m", secs / 60),
+        _ => format!("{}s", secs),
+    }
+}
+
+/// Run the CLI again with the same arguments, e.g. after an update or at the restart interval,
+/// replacing this process where the platform allows it. The session for the config file at
+/// `config_path` must have been dropped already.
+///
+/// Only returns if the CLI could not be started again.
+pub fn restart_process(config_path: &Path) -> std::io::Error {
+    release_process_resources(config_path);
+    // argv[0] rather than `current_exe`, which on Linux still names a replaced binary
+    let program = std::env::args_os()
+        .next()
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_exe().ok())
+        .unwrap_or_else(|| PathBuf::from("nexus-cli"));
+    let mut command = Command::new(program);
+    command
+        .args(std::env::args_os().skip(1))
+        .env(SESSION_STARTED_AT_ENV, session_started_at().to_string());
+    replace_process(command)
+}
+
+/// Release what outlives the session in this process, since neither `exec` nor exiting runs
+/// destructors: the new process binds the control socket and claims the node again.
+fn release_process_resources(config_path: &Path) {
+    #[cfg(unix)]
+    let _ = std::fs::remove_file(crate::ipc::control_socket_path(config_path));
+    #[cfg(not(unix))]
+    let _ = config_path;
+    crate::coordinator::release_node();
+    crate::prover::pool::shut_down();
+    log::logger().flush();
+    let _ = std::io::stdout().flush();
+    let _ = std::io::stderr().flush();
+}
+
+#[cfg(unix)]
+fn replace_process(mut command: Command) -> std::io::Error {
+    use std::os::unix::process::CommandExt;
+    command.exec()
+}
+
+/// Without `exec`, start the new process and exit at once, so that restarts do not nest: the new
+/// process keeps the console and outlives this one. By now the session is shut down and its
+/// resources are released, so exiting without running destructors loses nothing.
+#[cfg(not(unix))]
+fn replace_process(mut command: Command) -> std::io::Error {
+    match command.spawn() {
+        Ok(_) => std::process::exit(0),
+        Err(e) => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("30m"), Ok(Duration::from_secs(1800)));
+        assert_eq!(parse_duration("6h"), Ok(6 * HOUR));
+        assert_eq!(parse_duration("2d"), Ok(48 * HOUR));
+        assert_eq!(parse_duration("120"), Ok(Duration::from_secs(120)));
+        assert!(parse_duration("0h").is_err());
+        assert!(parse_duration("6 hours").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(24 * HOUR), "1d");
+        assert_eq!(format_duration(6 * HOUR), "6h");
+        assert_eq!(format_duration(Duration::from_secs(90)), "90s");
+    }
+
+    #[test]
+    fn test_next_end() {
+        assert_eq!(SessionLifetime::default().next_end(HOUR), None);
+
+        let lifetime = SessionLifetime {
+            max_runtime: Some(24 * HOUR),
+            restart_interval: Some(6 * HOUR),
+        };
+        // The restart interval counts from this start, the max runtime from the first one
+        assert_eq!(
+            lifetime.next_end(Duration::ZERO),
+            Some((LifetimeEnd::Restart, 6 * HOUR))
+        );
+        assert_eq!(
+            lifetime.next_end(20 * HOUR),
+            Some((LifetimeEnd::MaxRuntime, 4 * HOUR))
+        );
+        assert_eq!(
+            lifetime.next_end(18 * HOUR),
+            Some((LifetimeEnd::MaxRuntime, 6 * HOUR))
+        );
+        assert_eq!(
+            lifetime.next_end(30 * HOUR),
+            Some((LifetimeEnd::MaxRuntime, Duration::ZERO))
+        );
+    }
+}
@@ -52,3 +52,8 @@ pub fn print_session_shutdown() {
 pub fn print_session_exit_success() {
     SessionMessage::success("Nexus CLI exited successfully").print();
 }
+
+/// Print session restart message, at the restart interval
+pub fn print_session_restarting() {
+    SessionMessage::info("Restart interval reached, restarting...").print();
+}
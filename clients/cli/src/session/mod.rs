@@ -1,8 +1,18 @@
+pub mod builder;
+pub mod dry_run;
 pub mod headless_mode;
+pub mod lifetime;
 pub mod messages;
 pub mod setup;
+pub mod simulate;
+pub mod slowdown;
+pub mod state_machine;
+pub mod status_line;
+pub mod summary;
 pub mod tui_mode;
 
+pub use builder::{Session, SessionBuilder};
 pub use headless_mode::run_headless_mode;
-pub use setup::{SessionData, setup_session};
+pub use setup::SessionData;
+use setup::setup_session;
 pub use tui_mode::run_tui_mode;
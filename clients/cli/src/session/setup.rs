@@ -1,15 +1,54 @@
 //! Session setup and initialization
 
+use crate::affinity::{assign_cores, format_core_list};
 use crate::analytics::set_wallet_address_for_reporting;
 use crate::config::Config;
-use crate::environment::Environment;
-use crate::events::Event;
-use crate::orchestrator::OrchestratorClient;
+use crate::consts::cli_consts::dashboard::DEFAULT_UI_FPS;
+use crate::consts::cli_consts::points::{
+    NETWORK_STATS_POLL_INTERVAL_SECS, POINTS_POLL_INTERVAL_SECS, POINTS_TARGET_POLL_INTERVAL_SECS,
+};
+use crate::control::RuntimeControl;
+use crate::control::server::{ControlApi, run_control_api};
+use crate::coordinator::{Coordinator, claim_node};
+use crate::event_stream::start_event_stream;
+use crate::events::{Event, TaskFetchState};
+use crate::idle::spawn_idle_monitor;
+use crate::ipc::{TaskQueue, control_socket_path};
+use crate::labels::{Labels, set_node_labels};
+use crate::metrics_export::spawn_metrics_exporter;
+use crate::network_stats::{NetworkStatsTracker, spawn_network_stats_poller};
+use crate::notifications::NotificationSettings;
+use crate::notifications::webhook::spawn_webhook_notifier;
+use crate::orchestrator::Orchestrator;
+use crate::orchestrator::failover::spawn_failover_events;
+use crate::orchestrator::nonce::SubmissionNonces;
+use crate::orchestrator::signing::set_request_signing_key;
+use crate::points::{PointsTracker, spawn_points_poller, spawn_points_target};
+use crate::power::spawn_battery_monitor;
+use crate::prover::ProofCache;
+use crate::prover::engine::ProvingEngine;
+use crate::prover::pool;
+use crate::receipts::{ReceiptLedger, spawn_receipt_reconciler};
 use crate::runtime::start_authenticated_worker;
+use crate::session::builder::{Connection, SessionBuilder};
+use crate::session::slowdown::spawn_slowdown_monitor;
+use crate::session::status_line::spawn_status_line;
+use crate::session::summary::{SummaryRecorder, spawn_summary_recorder};
+use crate::storage::spawn_storage_manager;
+use crate::task_cache::TaskCache;
+use crate::thermal::spawn_thermal_governor;
+use crate::ui::DashboardLayout;
+use crate::version::checker::UpdateChannel;
+use crate::version::manager::VersionNotice;
+use crate::workers::core::WorkerConfig;
 use ed25519_dalek::SigningKey;
 use std::error::Error;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
 use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, watch};
 use tokio::task::JoinHandle;
 
 /// Session data for both TUI and headless modes
@@ -26,9 +65,45 @@ pub struct SessionData {
     /// Node ID
     pub node_id: u64,
     /// Orchestrator client
-    pub orchestrator: OrchestratorClient,
+    pub orchestrator: Arc<dyn Orchestrator>,
     /// Number of workers (for display purposes)
     pub num_workers: usize,
+    /// Core each worker slot is pinned to, if core pinning is enabled
+    pub pinned_cores: Option<Vec<usize>>,
+    /// Notification preferences from the config file
+    pub notifications: NotificationSettings,
+    /// Dashboard panel layout from the config file
+    pub dashboard_layout: DashboardLayout,
+    /// Dashboard theme from the config file, by name
+    pub theme: Option<String>,
+    /// Config file, where dashboard layout and theme changes are saved
+    pub config_path: PathBuf,
+    /// Rate-limit budget published by the task fetcher
+    pub fetch_state: watch::Receiver<TaskFetchState>,
+    /// Point totals, polled in the background
+    pub points: PointsTracker,
+    /// Network statistics and the node's rank, polled in the background
+    pub network_stats: NetworkStatsTracker,
+    /// Session totals for the summary printed on exit
+    pub summary: SummaryRecorder,
+    /// Where to also write the summary, if requested
+    pub summary_file: Option<PathBuf>,
+    /// Violated version requirement the session was started despite
+    pub version_notice: Option<VersionNotice>,
+    /// Release channel checked for newer versions
+    pub update_channel: UpdateChannel,
+    /// Whether to check for newer versions when the session starts
+    pub check_for_updates: bool,
+    /// Node labels, reported with each proof and shown in the dashboard
+    pub labels: Labels,
+    /// Set once the restart interval is reached: restart after draining instead of exiting
+    pub restart_due: Arc<AtomicBool>,
+    /// Most dashboard redraws per second
+    pub ui_fps: u32,
+    /// Runtime knobs (pause, difficulty), also set from the dashboard's command palette
+    pub control: RuntimeControl,
+    /// Node status line to keep the terminal title set to, with `--terminal-title`
+    pub status_title: Option<watch::Receiver<String>>,
 }
 
 /// Clamp thread count based on available system memory
@@ -80,37 +155,110 @@ pub fn warn_memory_configuration(max_threads: Option<u32>) {
 /// Sets up an authenticated worker session
 ///
 /// This function handles all the common setup required for both TUI and headless modes:
-/// 1. Creates signing key for the prover
+/// 1. Loads or creates the signing key of the node
 /// 2. Sets up shutdown channel
 /// 3. Starts authenticated worker
-/// 4. Returns session data for mode-specific handling
+/// 4. Starts the local control socket server and, if configured, the HTTP control API
+///    and WebSocket event stream
+/// 5. Returns session data for mode-specific handling
 ///
 /// # Arguments
 /// * `config` - Resolved configuration with node_id and client_id
-/// * `env` - Environment to connect to
-/// * `max_threads` - Optional maximum number of threads for proving
-/// * `max_difficulty` - Optional override for task difficulty
+/// * `connection` - Orchestrator, config file and version notice the session runs with
+/// * `options` - Options set on the [`SessionBuilder`]
 ///
 /// # Returns
 /// * `Ok(SessionData)` - Successfully set up session
 /// * `Err` - Session setup failed
-pub async fn setup_session(
+pub(super) async fn setup_session(
     config: Config,
-    env: Environment,
-    check_mem: bool,
-    max_threads: Option<u32>,
-    max_tasks: Option<u32>,
-    max_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
+    connection: Connection,
+    options: SessionBuilder,
 ) -> Result<SessionData, Box<dyn Error>> {
+    let Connection {
+        environment: env,
+        orchestrator,
+        failover_events,
+        config_path,
+        version_notice,
+    } = connection;
+    let control_socket_path = control_socket_path(&config_path);
+    let SessionBuilder {
+        check_mem,
+        max_threads,
+        max_tasks,
+        stop_after_points,
+        lifetime,
+        max_difficulty,
+        accept_policy,
+        core_pinning,
+        battery,
+        only_when_idle,
+        thermal_limit,
+        failure_dir,
+        proof_export_dir,
+        control_api,
+        event_stream,
+        summary_file,
+        metrics_export,
+        sign_requests,
+        coordinate,
+        ui_fps,
+        status_line,
+        mut transition_hooks,
+        ..
+    } = options;
     let node_id = config.node_id.parse::<u64>()?;
     let client_id = config.user_id;
+    let notifications = config.notifications;
+    let dashboard_layout = config.dashboard;
+    let theme = config.theme;
+    let update_channel = config.update.channel;
+    let labels = config.labels;
+    let storage = config.storage;
+    set_node_labels(labels.clone());
+    let task_cache = TaskCache::default_path().map(|path| TaskCache::open(path, config.task_cache));
+    let receipts = ReceiptLedger::default_path(node_id).map(ReceiptLedger::open);
 
-    // Create a signing key for the prover
-    let mut csprng = rand_core::OsRng;
-    let signing_key: SigningKey = SigningKey::generate(&mut csprng);
+    // With `--redact`, events mask the node ID and wallet address
+    crate::redact::protect(&config.node_id);
+    crate::redact::protect(&config.wallet_address);
+
+    // Two instances proving for one node compete for its tasks and rate limit
+    if let Ok(false) = claim_node(&config_path, node_id) {
+        crate::print_cmd_warn!(
+            "Node already running",
+            "Another nexus-network instance is already proving for node {}. Both will compete for the same tasks and rate limit; stop one of them or give each a node of its own.",
+            crate::redact::redact_id(&config.node_id)
+        );
+    }
+    let coordinator = if coordinate && cfg!(unix) {
+        Some(Coordinator::new(&config_path))
+    } else {
+        if coordinate {
+            crate::print_cmd_warn!(
+                "Coordination",
+                "--coordinate needs Unix domain sockets and has no effect on this platform"
+            );
+        }
+        None
+    };
 
-    // Create orchestrator client
-    let orchestrator_client = OrchestratorClient::new(env.clone());
+    // The prover signs with the node's own key, kept in the secret store across sessions
+    let secret_store = crate::secrets::open(&config_path);
+    let signing_key = match crate::secrets::node_signing_key(secret_store.as_ref(), node_id) {
+        Ok(signing_key) => signing_key,
+        Err(e) => {
+            crate::print_cmd_warn!(
+                "Signing key",
+                "Could not use the key in the {}: {}. Signing with a key for this session only.",
+                secret_store.kind(),
+                e
+            );
+            SigningKey::generate(&mut rand_core::OsRng)
+        }
+    };
+    set_request_signing_key(sign_requests.then(|| signing_key.clone()));
 
     // Clamp the number of workers to [1, 75% of num_cores]. Leave room for other processes.
     let total_cores = crate::system::num_cores();
@@ -136,25 +284,236 @@ pub async fn setup_session(
         warn_memory_configuration(Some(num_workers as u32));
     }
 
+    // Pin workers to cores, if requested
+    let pinned_cores = match core_pinning {
+        Some(pinning) => {
+            let cores = assign_cores(&pinning, num_workers)?;
+            crate::print_cmd_info!(
+                "Core pinning",
+                "Pinning {} workers to cores {}",
+                num_workers,
+                format_core_list(&cores)
+            );
+            Some(cores)
+        }
+        None => None,
+    };
+
+    // Load the guest program into the prover subprocesses before the first task arrives
+    pool::warm_up(num_workers, pinned_cores.as_deref());
+    tokio::task::spawn_blocking(ProvingEngine::fib_verifier);
+
     // Create shutdown channel - only one shutdown signal needed
     let (shutdown_sender, _) = broadcast::channel(1);
 
     // Set wallet for reporting
     set_wallet_address_for_reporting(config.wallet_address.clone());
 
+    // Watch for stalled or leaked workers when chaos testing is enabled
+    crate::chaos::spawn_watchdog(shutdown_sender.subscribe());
+
+    // Shared task queue, inspectable from another shell via `nexus-cli queue`
+    let queue = TaskQueue::new();
+    start_control_server(
+        control_socket_path,
+        queue.clone(),
+        receipts.clone(),
+        &shutdown_sender,
+    );
+
+    // Runtime knobs (pause, difficulty) shared with the HTTP control API
+    let control = RuntimeControl::new(max_difficulty);
+    // Session tallies and point totals, also reported by the HTTP control API
+    let summary = SummaryRecorder::new();
+    let points = PointsTracker::default();
+    if let Some(api_config) = control_api {
+        // Binding is fatal: the operator explicitly asked for remote control
+        let listener = tokio::net::TcpListener::bind(api_config.addr)
+            .await
+            .map_err(|e| format!("Failed to bind control API to {}: {}", api_config.addr, e))?;
+        let api = ControlApi::new(
+            node_id,
+            api_config.token,
+            control.clone(),
+            queue.clone(),
+            shutdown_sender.clone(),
+        )
+        .with_session(points.clone(), summary.clone())
+        .with_receipts(receipts.clone());
+        let shutdown = shutdown_sender.subscribe();
+        tokio::spawn(async move {
+            // Errors are ignored: printing here would corrupt the TUI
+            let _ = run_control_api(listener, api, shutdown).await;
+        });
+    }
+
+    // Pause or throttle proving on low battery, if requested
+    if let Some(battery) = battery {
+        spawn_battery_monitor(battery, control.clone(), shutdown_sender.subscribe());
+    }
+
+    // Prove only while nobody uses the machine, if requested
+    if let Some(min_idle) = only_when_idle {
+        spawn_idle_monitor(min_idle, control.clone(), shutdown_sender.subscribe());
+    }
+
+    // Throttle proving while the machine runs hot, if requested
+    if let Some(limit) = thermal_limit {
+        if crate::system::max_temperature_celsius().is_none() {
+            crate::print_cmd_warn!(
+                "Thermal limit",
+                "No temperature sensors found, --thermal-limit has no effect"
+            );
+        }
+        spawn_thermal_governor(limit, control.clone(), shutdown_sender.subscribe());
+    }
+
+    // Tally events and time spent proving for the summary printed on exit
+    transition_hooks.push(Arc::new(summary.clone()));
+
     // Start authenticated worker (only mode we support now)
-    let (event_receiver, join_handles, max_tasks_shutdown_sender) = start_authenticated_worker(
+    let mut worker_config = WorkerConfig::new(env, client_id);
+    worker_config.max_difficulty = max_difficulty;
+    worker_config.num_workers = num_workers;
+    worker_config.pinned_cores = pinned_cores.clone();
+    worker_config.proof_cache = ProofCache::default_dir().map(ProofCache::new);
+    worker_config.failure_dir = failure_dir;
+    worker_config.proof_export_dir = proof_export_dir;
+    worker_config.task_cache = task_cache;
+    worker_config.submission_nonces =
+        Some(SubmissionNonces::open(config_path.clone(), node_id).await);
+    worker_config.coordinator = coordinator;
+    worker_config.accept_policy = accept_policy;
+    let (event_receiver, join_handles, max_tasks_shutdown_sender, fetch_state) =
+        start_authenticated_worker(
+            node_id,
+            signing_key,
+            orchestrator.clone(),
+            shutdown_sender.subscribe(),
+            worker_config,
+            max_tasks,
+            queue,
+            control.clone(),
+            transition_hooks,
+        )
+        .await;
+    let event_receiver = spawn_summary_recorder(event_receiver, summary.clone());
+
+    // Warn when proofs take much longer than earlier in the session
+    let event_receiver = spawn_slowdown_monitor(event_receiver);
+
+    // Check that accepted submissions are credited, and flag those that are not
+    let event_receiver = match receipts {
+        Some(ledger) => spawn_receipt_reconciler(
+            event_receiver,
+            ledger,
+            orchestrator.clone(),
+            node_id,
+            shutdown_sender.subscribe(),
+        ),
+        None => event_receiver,
+    };
+
+    // Append metrics snapshots to a file, if requested
+    let event_receiver = match metrics_export {
+        Some(export) => {
+            let path = export.path.clone();
+            spawn_metrics_exporter(export, event_receiver, shutdown_sender.subscribe()).map_err(
+                |e| {
+                    format!(
+                        "Failed to open metrics export file {}: {}",
+                        path.display(),
+                        e
+                    )
+                },
+            )?
+        }
+        None => event_receiver,
+    };
+
+    // Poll point totals for the dashboard and the session summary, more often when working
+    // towards a points target
+    let poll_interval_secs = match stop_after_points {
+        Some(_) => POINTS_TARGET_POLL_INTERVAL_SECS,
+        None => POINTS_POLL_INTERVAL_SECS,
+    };
+    spawn_points_poller(
+        orchestrator.clone(),
         node_id,
-        signing_key,
-        orchestrator_client.clone(),
+        points.clone(),
+        Duration::from_secs(poll_interval_secs),
         shutdown_sender.subscribe(),
-        env,
-        client_id,
-        max_tasks,
-        max_difficulty,
-        num_workers,
-    )
-    .await;
+    );
+    // Poll network statistics for the dashboard's leaderboard panel
+    let network_stats = NetworkStatsTracker::default();
+    spawn_network_stats_poller(
+        orchestrator.clone(),
+        node_id,
+        network_stats.clone(),
+        Duration::from_secs(NETWORK_STATS_POLL_INTERVAL_SECS),
+        shutdown_sender.subscribe(),
+    );
+    if let Some(target) = stop_after_points {
+        spawn_points_target(
+            points.clone(),
+            target,
+            control.clone(),
+            shutdown_sender.subscribe(),
+        );
+    }
+
+    // Drain at the max runtime or restart interval, if set
+    let restart_due = lifetime.spawn(control.clone(), shutdown_sender.subscribe());
+
+    // Keep the files next to the config file within their quotas and warn on low disk space
+    let storage_base = config_path.parent().map(PathBuf::from).unwrap_or_default();
+    let event_receiver = spawn_storage_manager(event_receiver, storage, storage_base);
+
+    // Report failovers between orchestrator endpoints
+    let event_receiver = match failover_events {
+        Some(switches) => spawn_failover_events(event_receiver, switches),
+        None => event_receiver,
+    };
+
+    // Alert configured webhooks about incidents
+    let event_receiver = if notifications.webhooks.is_empty() {
+        event_receiver
+    } else {
+        spawn_webhook_notifier(
+            event_receiver,
+            notifications.webhooks.clone(),
+            node_id,
+            update_channel,
+        )
+    };
+
+    // Mirror events to WebSocket subscribers, if requested
+    let event_receiver = match event_stream {
+        Some(stream_config) => {
+            let addr = stream_config.addr;
+            start_event_stream(stream_config, event_receiver, &shutdown_sender)
+                .await
+                .map_err(|e| format!("Failed to bind event stream to {}: {}", addr, e))?
+        }
+        None => event_receiver,
+    };
+
+    // Show the node status in the terminal title and a status file, if requested
+    let (event_receiver, status_title) = if status_line.is_enabled() {
+        let terminal_title = status_line.terminal_title;
+        let file = status_line.file.clone();
+        let (event_receiver, status_title) = spawn_status_line(event_receiver, status_line)
+            .map_err(|e| {
+                format!(
+                    "Failed to write status file {}: {}",
+                    file.unwrap_or_default().display(),
+                    e
+                )
+            })?;
+        (event_receiver, terminal_title.then_some(status_title))
+    } else {
+        (event_receiver, None)
+    };
 
     Ok(SessionData {
         event_receiver,
@@ -162,7 +521,51 @@ pub async fn setup_session(
         shutdown_sender,
         max_tasks_shutdown_sender,
         node_id,
-        orchestrator: orchestrator_client,
+        orchestrator,
         num_workers,
+        pinned_cores,
+        notifications,
+        dashboard_layout,
+        theme,
+        config_path,
+        fetch_state,
+        points,
+        network_stats,
+        summary,
+        summary_file,
+        version_notice,
+        update_channel,
+        check_for_updates: true,
+        labels,
+        restart_due,
+        ui_fps: ui_fps.unwrap_or(DEFAULT_UI_FPS),
+        control,
+        status_title,
     })
 }
+
+/// Spawn the control socket server in the background.
+/// Failure to bind is not fatal: proving continues without remote inspection.
+#[cfg(unix)]
+fn start_control_server(
+    socket_path: PathBuf,
+    queue: TaskQueue,
+    receipts: Option<ReceiptLedger>,
+    shutdown_sender: &broadcast::Sender<()>,
+) {
+    let shutdown = shutdown_sender.subscribe();
+    tokio::spawn(async move {
+        // Errors are ignored: printing here would corrupt the TUI
+        let _ =
+            crate::ipc::server::run_control_server(socket_path, queue, receipts, shutdown).await;
+    });
+}
+
+#[cfg(not(unix))]
+fn start_control_server(
+    _socket_path: PathBuf,
+    _queue: TaskQueue,
+    _receipts: Option<ReceiptLedger>,
+    _shutdown_sender: &broadcast::Sender<()>,
+) {
+}
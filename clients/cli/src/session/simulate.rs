@@ -0,0 +1,148 @@
+//! Load tests with synthetic tasks, for `start --simulate N`
+//!
+//! A headless session proves N synthetic Fibonacci tasks of several inputs each, served by the
+//! mock orchestrator (see [`crate::orchestrator::mock`]) from inside the process without added
+//! latency. Fetching, dispatching the inputs to the prover workers, verification, proof hashing
+//! and submission all take their usual paths, so the run measures the sustained throughput of the
+//! local hardware and stresses the worker code, without network access.
+//!
+//! The session works in a scratch directory instead of the config directory, so the node, its
+//! submission nonces and the node claims of real sessions are left alone.
+
+use super::summary::SessionSummary;
+use crate::consts::cli_consts::simulate::INPUTS_PER_TASK;
+use crate::orchestrator::mock::MockConfig;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Mock orchestrator serving the synthetic tasks
+pub fn mock_config() -> MockConfig {
+    MockConfig {
+        latency: Duration::ZERO,
+        inputs_per_task: Some(INPUTS_PER_TASK),
+        ..MockConfig::default()
+    }
+}
+
+/// Empty scratch directory for the config and state files of a simulated session.
+pub fn scratch_dir() -> std::io::Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("nexus-simulate-{}", std::process::id()));
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Throughput of a simulated session
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    /// Number of tasks the session was asked to prove
+    pub tasks: u32,
+    /// Number of prover workers
+    pub num_workers: usize,
+    pub summary: SessionSummary,
+}
+
+impl SimulationReport {
+    /// Inputs proved, verified and submitted.
+    pub fn inputs_proved(&self) -> u64 {
+        self.summary.tasks_submitted as u64 * INPUTS_PER_TASK as u64
+    }
+
+    /// Submitted tasks per minute over the whole session.
+    pub fn tasks_per_minute(&self) -> f64 {
+        per_second(self.summary.tasks_submitted as u64, self.summary.runtime) * 60.0
+    }
+
+    /// Proved inputs per second over the whole session.
+    pub fn inputs_per_second(&self) -> f64 {
+        per_second(self.inputs_proved(), self.summary.runtime)
+    }
+}
+
+fn per_second(count: u64, runtime: Duration) -> f64 {
+    if runtime.is_zero() {
+        0.0
+    } else {
+        count as f64 / runtime.as_secs_f64()
+    }
+}
+
+impl Display for SimulationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let summary = &self.summary;
+        let secs = summary.runtime.as_secs();
+        writeln!(f, "Simulation")?;
+        writeln!(
+            f,
+            "  Tasks:             {} of {} submitted, {} inputs each",
+            summary.tasks_submitted, self.tasks, INPUTS_PER_TASK
+        )?;
+        writeln!(f, "  Workers:           {}", self.num_workers)?;
+        writeln!(
+            f,
+            "  Runtime:           {}h {:02}m {:02}s",
+            secs / 3600,
+            (secs % 3600) / 60,
+            secs % 60
+        )?;
+        writeln!(
+            f,
+            "  Throughput:        {:.2} tasks/min, {:.2} inputs/s",
+            self.tasks_per_minute(),
+            self.inputs_per_second()
+        )?;
+        match summary.avg_task_duration() {
+            Some(avg) => writeln!(f, "  Avg task time:     {}s", avg.as_secs())?,
+            None => writeln!(f, "  Avg task time:     -")?,
+        }
+        write!(
+            f,
+            "  Errors:            {} (fetch {}, proof {}, submit {})",
+            summary.errors.total(),
+            summary.errors.fetch,
+            summary.errors.proof,
+            summary.errors.submission
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::summary::ErrorBreakdown;
+
+    #[test]
+    fn test_simulation_report() {
+        let report = SimulationReport {
+            tasks: 10,
+            num_workers: 4,
+            summary: SessionSummary {
+                runtime: Duration::from_secs(120),
+                tasks_fetched: 10,
+                tasks_proved: 10,
+                tasks_submitted: 10,
+                total_task_duration: Duration::from_secs(200),
+                time_proving: Duration::from_secs(100),
+                points_earned: None,
+                errors: ErrorBreakdown::default(),
+                last_error: None,
+            },
+        };
+        assert_eq!(report.inputs_proved(), 30);
+        assert_eq!(report.tasks_per_minute(), 5.0);
+        assert_eq!(report.inputs_per_second(), 0.25);
+        assert_eq!(
+            report.to_string(),
+            "Simulation\n\
+             \x20 Tasks:             10 of 10 submitted, 3 inputs each\n\
+             \x20 Workers:           4\n\
+             \x20 Runtime:           0h 02m 00s\n\
+             \x20 Throughput:        5.00 tasks/min, 0.25 inputs/s\n\
+             \x20 Avg task time:     20s\n\
+             \x20 Errors:            0 (fetch 0, proof 0, submit 0)"
+        );
+    }
+}
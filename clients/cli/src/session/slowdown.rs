@@ -0,0 +1,188 @@
+//! Proof slowdown warnings
+//!
+//! Watches how long proofs take and warns when they take much longer than at the start of the
+//! session, e.g. because the machine is throttling, short on memory or shared with busy
+//! neighbors, rather than leaving users to notice fewer points days later. The warning names a
+//! suspected cause by comparing the CPU clock, free memory and temperature with when the first
+//! proof of the same difficulty finished. Notifications pick the warning up from its
+//! [`EventPayload::ProofSlowdown`] payload.
+
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::consts::cli_consts::stats::{
+    SLOWDOWN_CPU_CLOCK_RATIO, SLOWDOWN_LOW_MEMORY_RATIO, SLOWDOWN_TEMPERATURE_RISE_CELSIUS,
+};
+use crate::events::{Event, EventPayload, EventType};
+use crate::logging::LogLevel;
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::stats::{Slowdown, SlowdownDetector};
+use crate::system::{SystemConditions, system_conditions};
+use std::collections::HashMap;
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+/// Proof being generated, and whether part of it came from the proof cache
+struct RunningProof {
+    difficulty: TaskDifficulty,
+    started_at: Instant,
+    cached: bool,
+}
+
+/// Tracks proof durations from worker events
+#[derive(Default)]
+struct SlowdownMonitor {
+    running: HashMap<String, RunningProof>,
+    detector: SlowdownDetector,
+    /// Conditions when the first proof of each difficulty finished
+    baselines: HashMap<TaskDifficulty, SystemConditions>,
+}
+
+impl SlowdownMonitor {
+    /// Update from one event, returning a warning to send after it if proving has slowed down.
+    fn observe(&mut self, event: &Event) -> Option<Event> {
+        match event.payload.as_ref()? {
+            EventPayload::ProofStarted {
+                task_id,
+                difficulty,
+                ..
+            } => {
+                self.running.insert(
+                    task_id.clone(),
+                    RunningProof {
+                        difficulty: *difficulty,
+                        started_at: Instant::now(),
+                        cached: false,
+                    },
+                );
+            }
+            EventPayload::ProofCacheHit { task_id, .. } => {
+                // Reused proofs finish early and would lower the baseline
+                if let Some(proof) = self.running.get_mut(task_id) {
+                    proof.cached = true;
+                }
+            }
+            EventPayload::ProofFailed { task_id } => {
+                self.running.remove(task_id);
+            }
+            EventPayload::ProofGenerated { task_id } => {
+                let proof = self.running.remove(task_id).filter(|proof| !proof.cached)?;
+                self.baselines
+                    .entry(proof.difficulty)
+                    .or_insert_with(system_conditions);
+                let slowdown = self
+                    .detector
+                    .record(proof.difficulty, proof.started_at.elapsed())?;
+                let cause = self
+                    .baselines
+                    .get(&proof.difficulty)
+                    .and_then(|baseline| suspected_cause(baseline, &system_conditions()));
+                return Some(slowdown_event(&slowdown, cause));
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Most likely reason for proofs to have slowed down since `baseline`, if any stands out
+fn suspected_cause(baseline: &SystemConditions, now: &SystemConditions) -> Option<String> {
+    if let (Some(before), Some(after)) = (baseline.cpu_mhz, now.cpu_mhz) {
+        if (after as f64) < before as f64 * SLOWDOWN_CPU_CLOCK_RATIO {
+            return Some(format!(
+                "CPU clock dropped from {} to {} MHz",
+                before, after
+            ));
+        }
+    }
+    if now.available_memory < SLOWDOWN_LOW_MEMORY_RATIO {
+        return Some(format!(
+            "memory pressure, {:.0}% of memory available",
+            now.available_memory * 100.0
+        ));
+    }
+    if let (Some(before), Some(after)) = (baseline.celsius, now.celsius) {
+        if after >= before + SLOWDOWN_TEMPERATURE_RISE_CELSIUS {
+            return Some(format!(
+                "temperature rose from {:.0}°C to {:.0}°C",
+                before, after
+            ));
+        }
+    }
+    None
+}
+
+fn slowdown_event(slowdown: &Slowdown, cause: Option<String>) -> Event {
+    let difficulty = slowdown.difficulty.as_str_name();
+    let baseline_secs = slowdown.baseline.as_secs();
+    let recent_secs = slowdown.recent.as_secs();
+    let msg = format!(
+        "{} proofs take {:.1}x as long as earlier this session ({}s, was {}s); suspected cause: {}",
+        difficulty,
+        slowdown.ratio(),
+        recent_secs,
+        baseline_secs,
+        cause
+            .as_deref()
+            .unwrap_or("other processes competing for the CPU")
+    );
+    Event::prover_with_level(0, msg, EventType::Refresh, LogLevel::Warn).with_payload(
+        EventPayload::ProofSlowdown {
+            difficulty: slowdown.difficulty,
+            baseline_secs,
+            recent_secs,
+            cause,
+        },
+    )
+}
+
+/// Watch proof durations in `source` and add a warning when proving slows down.
+///
+/// Returns the receiver to consume instead; events pass through unchanged.
+pub fn spawn_slowdown_monitor(mut source: mpsc::Receiver<Event>) -> mpsc::Receiver<Event> {
+    let (sender, receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        let mut monitor = SlowdownMonitor::default();
+        while let Some(event) = source.recv().await {
+            let warning = monitor.observe(&event);
+            if sender.send(event).await.is_err() {
+                break;
+            }
+            if let Some(warning) = warning {
+                if sender.send(warning).await.is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conditions(cpu_mhz: u64, available_memory: f64, celsius: f32) -> SystemConditions {
+        SystemConditions {
+            cpu_mhz: Some(cpu_mhz),
+            available_memory,
+            celsius: Some(celsius),
+        }
+    }
+
+    #[test]
+    fn test_suspected_cause() {
+        let baseline = conditions(3400, 0.6, 60.0);
+        assert_eq!(suspected_cause(&baseline, &baseline), None);
+        assert_eq!(
+            suspected_cause(&baseline, &conditions(2100, 0.6, 60.0)).as_deref(),
+            Some("CPU clock dropped from 3400 to 2100 MHz")
+        );
+        assert_eq!(
+            suspected_cause(&baseline, &conditions(3400, 0.05, 60.0)).as_deref(),
+            Some("memory pressure, 5% of memory available")
+        );
+        assert_eq!(
+            suspected_cause(&baseline, &conditions(3300, 0.6, 80.0)).as_deref(),
+            Some("temperature rose from 60°C to 80°C")
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Prover state machine
+//!
+//! For each task the work loop moves through Idle → Fetching → Proving → Submitting → Cooldown.
+//! [`ProverStateMachine`] is the one place that state changes: each transition is checked
+//! against [`ProverState::can_transition_to`], reported as a state change event, which drives the
+//! dashboard and the headless log, and passed to the [`TransitionHook`]s of the session, e.g. for
+//! analytics and notifications.
+
+use crate::events::{Event, EventPayload, EventType, ProverState};
+use crate::logging::LogLevel;
+use crate::workers::core::EventSender;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A change of prover state
+#[derive(Debug, Clone)]
+pub struct Transition {
+    pub from: ProverState,
+    pub to: ProverState,
+    /// How long the prover was in `from`
+    pub elapsed: Duration,
+    /// Why the state changed, as shown in the activity log
+    pub reason: String,
+}
+
+/// Called on every prover state transition, from the work loop: keep it quick
+pub trait TransitionHook: Send + Sync {
+    fn on_transition(&self, transition: &Transition);
+}
+
+impl<F: Fn(&Transition) + Send + Sync> TransitionHook for F {
+    fn on_transition(&self, transition: &Transition) {
+        self(transition)
+    }
+}
+
+/// Hooks run on every transition, in the order they were added
+#[derive(Clone, Default)]
+pub struct TransitionHooks(Vec<Arc<dyn TransitionHook>>);
+
+impl TransitionHooks {
+    pub fn push(&mut self, hook: Arc<dyn TransitionHook>) {
+        self.0.push(hook);
+    }
+
+    fn run(&self, transition: &Transition) {
+        for hook in &self.0 {
+            hook.on_transition(transition);
+        }
+    }
+}
+
+impl fmt::Debug for TransitionHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TransitionHooks({})", self.0.len())
+    }
+}
+
+/// Authoritative state of the prover, owned by the work loop
+pub struct ProverStateMachine {
+    state: ProverState,
+    entered_at: Instant,
+    events: EventSender,
+    hooks: TransitionHooks,
+}
+
+impl ProverStateMachine {
+    /// Start in [`ProverState::Idle`], reporting transitions to `events` and `hooks`.
+    pub fn new(events: EventSender, hooks: TransitionHooks) -> Self {
+        Self {
+            state: ProverState::Idle,
+            entered_at: Instant::now(),
+            events,
+            hooks,
+        }
+    }
+
+    pub fn state(&self) -> ProverState {
+        self.state
+    }
+
+    /// Move to `to`, showing `reason` in the activity log.
+    pub async fn transition(&mut self, to: ProverState, reason: impl Into<String>) {
+        self.report(Event::state_change(to, reason.into())).await;
+    }
+
+    /// Move to `to`, showing `reason` only in the headless log and at debug level. For steps that
+    /// the workers already log.
+    pub async fn transition_quietly(&mut self, to: ProverState, reason: impl Into<String>) {
+        let mut event = Event::state_change(to, reason.into());
+        event.log_level = LogLevel::Debug;
+        self.report(event).await;
+    }
+
+    /// Move to `to` like [`Self::transition`], attaching what happened.
+    pub async fn transition_with_payload(
+        &mut self,
+        to: ProverState,
+        reason: impl Into<String>,
+        payload: EventPayload,
+    ) {
+        self.report(Event::state_change(to, reason.into()).with_payload(payload))
+            .await;
+    }
+
+    async fn report(&mut self, event: Event) {
+        let Some(to) = event.prover_state else {
+            return;
+        };
+        let transition = self.advance(to, event.msg.clone(), Instant::now());
+        if let Some(transition) = &transition {
+            self.hooks.run(transition);
+        } else {
+            // Still follow the work loop, so the dashboard shows where it actually is
+            self.events
+                .send_task_event(
+                    format!("Unexpected prover state change to {}", to),
+                    EventType::Refresh,
+                    LogLevel::Debug,
+                )
+                .await;
+        }
+        self.events.send_event(event).await;
+    }
+
+    /// Enter `to` at `now`, returning the transition if it was a valid one
+    fn advance(&mut self, to: ProverState, reason: String, now: Instant) -> Option<Transition> {
+        let from = self.state;
+        let elapsed = now.saturating_duration_since(self.entered_at);
+        self.state = to;
+        self.entered_at = now;
+        from.can_transition_to(to).then_some(Transition {
+            from,
+            to,
+            elapsed,
+            reason,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_transitions_reach_events_and_hooks() {
+        let (sender, mut receiver) = mpsc::channel(16);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut hooks = TransitionHooks::default();
+        let recorded = seen.clone();
+        hooks.push(Arc::new(move |transition: &Transition| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((transition.from, transition.to));
+        }));
+        let mut machine = ProverStateMachine::new(EventSender::new(sender), hooks);
+
+        machine
+            .transition_quietly(ProverState::Fetching, "Fetching")
+            .await;
+        machine
+            .transition(ProverState::Proving, "Proving task abc")
+            .await;
+        assert_eq!(machine.state(), ProverState::Proving);
+
+        let fetching = receiver.recv().await.unwrap();
+        assert_eq!(fetching.prover_state, Some(ProverState::Fetching));
+        assert!(!fetching.should_display());
+        let proving = receiver.recv().await.unwrap();
+        assert_eq!(proving.msg, "Proving task abc");
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                (ProverState::Idle, ProverState::Fetching),
+                (ProverState::Fetching, ProverState::Proving)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unexpected_transition_skips_hooks() {
+        let (sender, mut receiver) = mpsc::channel(16);
+        let called = Arc::new(Mutex::new(0));
+        let mut hooks = TransitionHooks::default();
+        let count = called.clone();
+        hooks.push(Arc::new(move |_: &Transition| *count.lock().unwrap() += 1));
+        let mut machine = ProverStateMachine::new(EventSender::new(sender), hooks);
+
+        // Proving needs a task, so it cannot follow Idle
+        machine.transition(ProverState::Proving, "Proving").await;
+        assert_eq!(machine.state(), ProverState::Proving);
+        assert_eq!(*called.lock().unwrap(), 0);
+        assert_eq!(receiver.recv().await.unwrap().log_level, LogLevel::Debug);
+        assert_eq!(
+            receiver.recv().await.unwrap().prover_state,
+            Some(ProverState::Proving)
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! One-line node status for terminal titles and tmux
+//!
+//! Operators running many nodes in tmux or screen windows can follow them at a glance: with
+//! `--terminal-title` the window title is kept up to date, e.g. `nexus ✓ proving Task-123 | 42
+//! proofs`, and with `--status-file` the same line is written to a file for the tmux status line:
+//!
+//! ```text
+//! set -g status-right '#(cat ~/.nexus/status)'
+//! set -g status-interval 5
+//! ```
+//!
+//! The title is set with OSC 0, which tmux shows as the pane title and screen as the window's
+//! hardstatus. The previous title is saved when the session starts and restored when it ends,
+//! where the terminal supports it.
+
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::events::{Event, EventPayload, EventType, ProverState};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use tokio::sync::{mpsc, watch};
+
+/// Where to show the node status
+#[derive(Debug, Clone, Default)]
+pub struct StatusLineConfig {
+    /// Keep the terminal title up to date
+    pub terminal_title: bool,
+    /// File to keep the status line in, e.g. for the tmux status line
+    pub file: Option<PathBuf>,
+}
+
+impl StatusLineConfig {
+    /// Whether the status is shown anywhere
+    pub fn is_enabled(&self) -> bool {
+        self.terminal_title || self.file.is_some()
+    }
+}
+
+/// Status of the node, from worker events
+#[derive(Debug, Clone)]
+struct NodeStatus {
+    state: ProverState,
+    /// Task being proved or submitted
+    task_id: Option<String>,
+    /// Proofs accepted by the orchestrator this session
+    proofs: u32,
+    /// Whether the latest outcome was an error
+    failing: bool,
+    /// Set once the workers have stopped
+    stopped: bool,
+}
+
+impl NodeStatus {
+    fn new() -> Self {
+        Self {
+            state: ProverState::Idle,
+            task_id: None,
+            proofs: 0,
+            failing: false,
+            stopped: false,
+        }
+    }
+
+    /// Update from one event.
+    fn observe(&mut self, event: &Event) {
+        match event.event_type {
+            EventType::Error => self.failing = true,
+            EventType::Success => self.failing = false,
+            _ => {}
+        }
+        if let Some(state) = event.prover_state {
+            self.state = state;
+            if matches!(state, ProverState::Idle | ProverState::Cooldown) {
+                self.task_id = None;
+            }
+        }
+        match &event.payload {
+            Some(EventPayload::TaskFetched { task_id })
+            | Some(EventPayload::ProofStarted { task_id, .. }) => {
+                self.task_id = Some(task_id.clone());
+            }
+            Some(EventPayload::ProofSubmitted { .. }) => self.proofs += 1,
+            Some(EventPayload::ProofFailed { .. })
+            | Some(EventPayload::SubmissionFailed { .. })
+            | Some(EventPayload::TaskCompleted { .. }) => self.task_id = None,
+            _ => {}
+        }
+    }
+
+    /// The status as one line, e.g. `nexus ✓ proving Task-123 | 42 proofs`.
+    fn line(&self) -> String {
+        let (mark, state) = if self.stopped {
+            ("■", "stopped")
+        } else {
+            let mark = if self.failing { "✗" } else { "✓" };
+            let state = match self.state {
+                ProverState::Idle => "idle",
+                ProverState::Fetching => "fetching",
+                ProverState::Proving => "proving",
+                ProverState::Submitting => "submitting",
+                ProverState::Cooldown => "waiting",
+            };
+            (mark, state)
+        };
+        let task = match (&self.task_id, self.stopped) {
+            (Some(task_id), false) => format!(" {}", crate::redact::redact_id(task_id)),
+            _ => String::new(),
+        };
+        let proofs = match self.proofs {
+            1 => "1 proof".to_string(),
+            n => format!("{} proofs", n),
+        };
+        format!("nexus {} {}{} | {}", mark, state, task, proofs)
+    }
+}
+
+/// Replace the contents of `path` with `line`, so readers never see a partial line.
+fn write_status_file(path: &Path, line: &str) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    std::fs::write(&tmp, format!("{}\n", line))?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Write an escape sequence to stdout, if it is a terminal.
+fn write_escape(sequence: &str) {
+    let mut stdout = std::io::stdout();
+    if stdout.is_terminal() {
+        // In one write, so it cannot be split by other output
+        let _ = stdout.write_all(sequence.as_bytes());
+        let _ = stdout.flush();
+    }
+}
+
+/// Save the terminal title, to restore with [`restore_terminal_title`].
+pub fn save_terminal_title() {
+    write_escape("\x1b[22;0t");
+}
+
+/// Restore the title saved with [`save_terminal_title`].
+pub fn restore_terminal_title() {
+    write_escape("\x1b[23;0t");
+}
+
+/// Set the terminal title (OSC 0).
+///
+/// Call this from the task that owns the terminal, between frames, so the sequence is not
+/// interleaved with other output.
+pub fn set_terminal_title(title: &str) {
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    write_escape(&format!("\x1b]0;{}\x07", title));
+}
+
+/// Track the node status from `source` and keep the status file, if any, up to date.
+///
+/// Returns the receiver to consume instead, through which events pass unchanged, and the status
+/// line, for the terminal title. The status file is written once before returning, so a path
+/// that cannot be written to is reported up front.
+pub fn spawn_status_line(
+    mut source: mpsc::Receiver<Event>,
+    config: StatusLineConfig,
+) -> std::io::Result<(mpsc::Receiver<Event>, watch::Receiver<String>)> {
+    let mut status = NodeStatus::new();
+    let mut line = status.line();
+    if let Some(path) = &config.file {
+        write_status_file(path, &line)?;
+    }
+    let (title_sender, title_receiver) = watch::channel(line.clone());
+
+    let (sender, receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        let mut publish = |status: &NodeStatus| {
+            let next = status.line();
+            if next == line {
+                return;
+            }
+            line = next;
+            if let Some(path) = &config.file {
+                // Errors are ignored: printing here would corrupt the TUI
+                let _ = write_status_file(path, &line);
+            }
+            title_sender.send_replace(line.clone());
+        };
+        while let Some(event) = source.recv().await {
+            status.observe(&event);
+            publish(&status);
+            if sender.send(event).await.is_err() {
+                break;
+            }
+        }
+        status.stopped = true;
+        publish(&status);
+    });
+    Ok((receiver, title_receiver))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::LogLevel;
+    use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+
+    #[test]
+    fn test_status_line() {
+        let mut status = NodeStatus::new();
+        assert_eq!(status.line(), "nexus ✓ idle | 0 proofs");
+
+        status.observe(&Event::state_change(
+            ProverState::Proving,
+            "Proving".to_string(),
+        ));
+        status.observe(
+            &Event::prover_with_level(0, "Proving".into(), EventType::Refresh, LogLevel::Info)
+                .with_payload(EventPayload::ProofStarted {
+                    task_id: "Task-123".to_string(),
+                    difficulty: TaskDifficulty::Small,
+                    task_type: TaskType::ProofRequired,
+                }),
+        );
+        assert_eq!(status.line(), "nexus ✓ proving Task-123 | 0 proofs");
+
+        status.observe(
+            &Event::proof_submitter_with_level(
+                "Submitted".into(),
+                EventType::Success,
+                LogLevel::Info,
+            )
+            .with_payload(EventPayload::ProofSubmitted {
+                task_id: "Task-123".to_string(),
+            }),
+        );
+        status.observe(&Event::state_change(
+            ProverState::Cooldown,
+            "Waiting".to_string(),
+        ));
+        assert_eq!(status.line(), "nexus ✓ waiting | 1 proof");
+
+        status.observe(&Event::task_fetcher_with_level(
+            "Fetch failed".into(),
+            EventType::Error,
+            LogLevel::Error,
+        ));
+        assert_eq!(status.line(), "nexus ✗ waiting | 1 proof");
+
+        status.stopped = true;
+        assert_eq!(status.line(), "nexus ■ stopped | 1 proof");
+    }
+}
@@ -0,0 +1,393 @@
+//! End-of-session summary
+//!
+//! A [`SummaryRecorder`] taps the worker event stream and tallies what the session did. When
+//! the session ends the totals are printed and, with `--summary-file`, also written to disk.
+
+use super::messages::SessionMessage;
+use super::state_machine::{Transition, TransitionHook};
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::consts::cli_consts::points::SUMMARY_POINTS_TIMEOUT_SECS;
+use crate::events::{Event, EventPayload, EventType, ProverState, Worker};
+use crate::orchestrator::Orchestrator;
+use crate::points::{PointsTracker, format_points, poll_points};
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Failed steps, by where they failed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ErrorBreakdown {
+    /// Task fetches that failed, including rate-limited ones
+    pub fetch: u32,
+    /// Proofs that failed to generate
+    pub proof: u32,
+    /// Proofs that failed to submit
+    pub submission: u32,
+    /// Requests rejected with HTTP 429
+    pub rate_limited: u32,
+}
+
+impl ErrorBreakdown {
+    pub fn total(&self) -> u32 {
+        self.fetch + self.proof + self.submission
+    }
+}
+
+/// Totals for one prover session
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSummary {
+    /// How long the session ran
+    pub runtime: Duration,
+    pub tasks_fetched: u32,
+    pub tasks_proved: u32,
+    pub tasks_submitted: u32,
+    /// Sum of task durations of submitted tasks
+    pub total_task_duration: Duration,
+    /// Time spent proving, from the prover state transitions
+    pub time_proving: Duration,
+    /// Points earned during the session, if point totals could be fetched
+    pub points_earned: Option<u64>,
+    pub errors: ErrorBreakdown,
+    /// Message of the most recent error, e.g. for the fleet dashboard
+    pub last_error: Option<String>,
+}
+
+impl SessionSummary {
+    fn new() -> Self {
+        Self {
+            runtime: Duration::ZERO,
+            tasks_fetched: 0,
+            tasks_proved: 0,
+            tasks_submitted: 0,
+            total_task_duration: Duration::ZERO,
+            time_proving: Duration::ZERO,
+            points_earned: None,
+            errors: ErrorBreakdown::default(),
+            last_error: None,
+        }
+    }
+
+    /// Share of fetched tasks that were submitted, as a percentage.
+    pub fn success_rate(&self) -> f64 {
+        if self.tasks_fetched == 0 {
+            0.0
+        } else {
+            self.tasks_submitted as f64 / self.tasks_fetched as f64 * 100.0
+        }
+    }
+
+    /// Share of the runtime spent proving, as a percentage.
+    pub fn proving_share(&self) -> f64 {
+        if self.runtime.is_zero() {
+            0.0
+        } else {
+            (self.time_proving.as_secs_f64() / self.runtime.as_secs_f64() * 100.0).min(100.0)
+        }
+    }
+
+    /// Average duration of submitted tasks.
+    pub fn avg_task_duration(&self) -> Option<Duration> {
+        self.total_task_duration.checked_div(self.tasks_submitted)
+    }
+
+    /// Update the totals from one worker event.
+    fn observe(&mut self, event: &Event) {
+        match (event.worker, event.event_type) {
+            (Worker::TaskFetcher, EventType::Error) => self.errors.fetch += 1,
+            (Worker::Prover(_), EventType::Error) => self.errors.proof += 1,
+            (Worker::ProofSubmitter, EventType::Error) => self.errors.submission += 1,
+            _ => {}
+        }
+        if event.event_type == EventType::Error {
+            self.last_error = Some(event.msg.clone());
+        }
+        match &event.payload {
+            Some(EventPayload::TaskFetched { .. }) => self.tasks_fetched += 1,
+            Some(EventPayload::ProofGenerated { .. }) => self.tasks_proved += 1,
+            Some(EventPayload::ProofSubmitted { .. }) => self.tasks_submitted += 1,
+            Some(EventPayload::TaskCompleted { duration_secs, .. }) => {
+                self.total_task_duration += Duration::from_secs(*duration_secs);
+            }
+            Some(EventPayload::RateLimited { .. }) => self.errors.rate_limited += 1,
+            _ => {}
+        }
+    }
+
+    /// Write the summary to `path` as plain text.
+    pub fn write_to(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, format!("{}\n", self))
+    }
+}
+
+impl Display for SessionSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let secs = self.runtime.as_secs();
+        writeln!(f, "Session summary")?;
+        writeln!(
+            f,
+            "  Runtime:           {}h {:02}m {:02}s",
+            secs / 3600,
+            (secs % 3600) / 60,
+            secs % 60
+        )?;
+        writeln!(
+            f,
+            "  Tasks:             {} fetched, {} proved, {} submitted",
+            self.tasks_fetched, self.tasks_proved, self.tasks_submitted
+        )?;
+        writeln!(f, "  Success rate:      {:.1}%", self.success_rate())?;
+        match self.avg_task_duration() {
+            Some(avg) => writeln!(f, "  Avg task time:     {}s", avg.as_secs())?,
+            None => writeln!(f, "  Avg task time:     -")?,
+        }
+        writeln!(f, "  Time proving:      {:.0}%", self.proving_share())?;
+        match self.points_earned {
+            Some(points) => writeln!(f, "  Points earned:     +{}", format_points(points))?,
+            None => writeln!(f, "  Points earned:     unknown")?,
+        }
+        write!(
+            f,
+            "  Errors:            {} (fetch {}, proof {}, submit {}; {} rate limited)",
+            self.errors.total(),
+            self.errors.fetch,
+            self.errors.proof,
+            self.errors.submission,
+            self.errors.rate_limited
+        )
+    }
+}
+
+/// Shared tally of a running session, fed by the event stream
+#[derive(Debug, Clone)]
+pub struct SummaryRecorder {
+    started_at: Instant,
+    summary: Arc<Mutex<SessionSummary>>,
+}
+
+impl SummaryRecorder {
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            summary: Arc::new(Mutex::new(SessionSummary::new())),
+        }
+    }
+
+    /// Count one worker event.
+    pub fn observe(&self, event: &Event) {
+        if let Ok(mut summary) = self.summary.lock() {
+            summary.observe(event);
+        }
+    }
+
+    /// Totals so far.
+    pub fn snapshot(&self) -> SessionSummary {
+        let mut summary = self
+            .summary
+            .lock()
+            .map(|summary| summary.clone())
+            .unwrap_or_else(|_| SessionSummary::new());
+        summary.runtime = self.started_at.elapsed();
+        summary
+    }
+}
+
+impl Default for SummaryRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransitionHook for SummaryRecorder {
+    fn on_transition(&self, transition: &Transition) {
+        if transition.from == ProverState::Proving {
+            if let Ok(mut summary) = self.summary.lock() {
+                summary.time_proving += transition.elapsed;
+            }
+        }
+    }
+}
+
+/// Tally events from `source` into `recorder`, forwarding them unchanged.
+pub fn spawn_summary_recorder(
+    mut source: mpsc::Receiver<Event>,
+    recorder: SummaryRecorder,
+) -> mpsc::Receiver<Event> {
+    let (sender, receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        while let Some(event) = source.recv().await {
+            recorder.observe(&event);
+            if sender.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+/// Print the session summary and, if `summary_file` is set, write it there too.
+/// Point totals are refreshed first so that the last few minutes are included.
+pub async fn report_summary(
+    recorder: &SummaryRecorder,
+    points: &PointsTracker,
+    orchestrator: &dyn Orchestrator,
+    node_id: u64,
+    summary_file: Option<&Path>,
+) {
+    let _ = tokio::time::timeout(
+        Duration::from_secs(SUMMARY_POINTS_TIMEOUT_SECS),
+        poll_points(orchestrator, node_id, points),
+    )
+    .await;
+    let points = points.snapshot(Instant::now());
+
+    let mut summary = recorder.snapshot();
+    summary.points_earned = points.node_points.map(|_| points.session_points);
+    println!("{}", summary);
+
+    if let Some(path) = summary_file {
+        match summary.write_to(path) {
+            Ok(()) => {
+                SessionMessage::info(format!("Session summary written to {}", path.display()))
+                    .print()
+            }
+            Err(e) => crate::print_cmd_warn!(
+                "Session summary",
+                "Failed to write {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::LogLevel;
+    use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+
+    fn task_id(id: &str) -> String {
+        id.to_string()
+    }
+
+    #[test]
+    fn test_tallies_events() {
+        let recorder = SummaryRecorder::new();
+        let events = [
+            Event::task_fetcher_with_level(
+                "Step 1 of 4: Got task abc".to_string(),
+                EventType::Success,
+                LogLevel::Info,
+            )
+            .with_payload(EventPayload::TaskFetched {
+                task_id: task_id("abc"),
+            }),
+            Event::prover_with_level(
+                1,
+                "Proof generated for task abc (using 1 workers)".to_string(),
+                EventType::Success,
+                LogLevel::Info,
+            )
+            .with_payload(EventPayload::ProofGenerated {
+                task_id: task_id("abc"),
+            }),
+            Event::proof_submitter_with_level(
+                "Step 4 of 4: Proof submitted successfully".to_string(),
+                EventType::Success,
+                LogLevel::Info,
+            )
+            .with_payload(EventPayload::ProofSubmitted {
+                task_id: task_id("abc"),
+            }),
+            Event::state_change(
+                ProverState::Cooldown,
+                "abc completed, Task size: 1, Duration: 40s, Difficulty: SMALL, Type: PROOF_HASH"
+                    .to_string(),
+            )
+            .with_payload(EventPayload::TaskCompleted {
+                task_id: task_id("abc"),
+                duration_secs: 40,
+                difficulty: TaskDifficulty::Small,
+                task_type: TaskType::ProofHash,
+            }),
+            Event::task_fetcher_with_level(
+                "Step 1 of 4: Got task def".to_string(),
+                EventType::Success,
+                LogLevel::Info,
+            )
+            .with_payload(EventPayload::TaskFetched {
+                task_id: task_id("def"),
+            }),
+            Event::task_fetcher_with_level(
+                "Failed to fetch task: HTTP error with status 429: slow down".to_string(),
+                EventType::Error,
+                LogLevel::Warn,
+            )
+            .with_payload(EventPayload::RateLimited {
+                retry_after_secs: None,
+            }),
+            Event::prover_with_level(
+                1,
+                "Proof generation failed for task def (using 1 workers): boom".to_string(),
+                EventType::Error,
+                LogLevel::Error,
+            )
+            .with_payload(EventPayload::ProofFailed {
+                task_id: task_id("def"),
+            }),
+        ];
+        for event in &events {
+            recorder.observe(event);
+        }
+        recorder.on_transition(&Transition {
+            from: ProverState::Proving,
+            to: ProverState::Submitting,
+            elapsed: Duration::from_secs(30),
+            reason: "Submitting proof for task abc".to_string(),
+        });
+
+        let summary = recorder.snapshot();
+        assert_eq!(summary.tasks_fetched, 2);
+        assert_eq!(summary.tasks_proved, 1);
+        assert_eq!(summary.tasks_submitted, 1);
+        assert_eq!(summary.success_rate(), 50.0);
+        assert_eq!(summary.avg_task_duration(), Some(Duration::from_secs(40)));
+        assert_eq!(summary.time_proving, Duration::from_secs(30));
+        assert_eq!(
+            summary.errors,
+            ErrorBreakdown {
+                fetch: 1,
+                proof: 1,
+                submission: 0,
+                rate_limited: 1,
+            }
+        );
+        assert_eq!(
+            summary.last_error.as_deref(),
+            Some("Proof generation failed for task def (using 1 workers): boom")
+        );
+    }
+
+    #[test]
+    fn test_display_and_write() {
+        let mut summary = SessionSummary::new();
+        summary.runtime = Duration::from_secs(3725);
+        summary.points_earned = Some(1500);
+        summary.time_proving = Duration::from_secs(1862);
+        let text = summary.to_string();
+        assert!(text.contains("1h 02m 05s"));
+        assert!(text.contains("+1,500"));
+        assert!(text.contains("Avg task time:     -"));
+        assert!(text.contains("Time proving:      50%"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.txt");
+        summary.write_to(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            format!("{}\n", text)
+        );
+    }
+}
@@ -2,17 +2,27 @@
 
 use super::{
     SessionData,
-    messages::{print_session_exit_success, print_session_shutdown, print_session_starting},
+    lifetime::restart_process,
+    messages::{
+        print_session_exit_success, print_session_restarting, print_session_shutdown,
+        print_session_starting,
+    },
+    status_line::{restore_terminal_title, save_terminal_title},
+    summary::report_summary,
 };
+use crate::notifications::desktop;
 use crate::orchestrator::Orchestrator;
-use crate::ui::{self, UIConfig};
+use crate::ui::theme;
+use crate::ui::{self, UIConfig, UiExit, UiMode};
 use crate::version::checker::check_for_new_version;
+use crate::version::updater;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
+use std::sync::atomic::Ordering;
 use std::{error::Error, io};
 
 /// Runs the application in TUI mode
@@ -20,11 +30,15 @@ use std::{error::Error, io};
 /// This function handles:
 /// 1. Terminal setup and cleanup
 /// 2. UI application initialization and execution
-/// 3. Proper shutdown handling
+/// 3. Desktop notifications, if enabled in the config
+/// 4. Proper shutdown handling and the session summary
+/// 5. Installing an update accepted in the dashboard, then restarting
+/// 6. Restarting at the restart interval
 ///
 /// # Arguments
 /// * `session` - Session data from setup
 /// * `with_background` - Whether to enable background colors
+/// * `ui_mode` - Dashboard style, e.g. plain text for screen readers
 ///
 /// # Returns
 /// * `Ok(())` - TUI mode completed successfully
@@ -32,28 +46,77 @@ use std::{error::Error, io};
 pub async fn run_tui_mode(
     session: SessionData,
     with_background: bool,
+    ui_mode: UiMode,
 ) -> Result<(), Box<dyn Error>> {
+    let config_path = session.config_path.clone();
+    let update_channel = session.update_channel;
+    let restart_due = session.restart_due.clone();
+    // The session is dropped before restarting, so the new process finds its resources free
+    let exit = run_dashboard(session, with_background, ui_mode).await?;
+
+    // The update prompt was accepted: install the update, then start over with the same arguments
+    if exit == UiExit::Update {
+        updater::update(&config_path, Some(update_channel), false).await?;
+        println!("{}", crate::tr!("update-restarting"));
+        return Err(restart_process(&config_path).into());
+    }
+
+    // The restart interval was reached: start over, back in a fresh dashboard
+    if restart_due.load(Ordering::SeqCst) {
+        print_session_restarting();
+        return Err(restart_process(&config_path).into());
+    }
+
+    Ok(())
+}
+
+/// Run the dashboard until the user quits, then wait for the workers and print the session summary
+async fn run_dashboard(
+    session: SessionData,
+    with_background: bool,
+    ui_mode: UiMode,
+) -> Result<UiExit, Box<dyn Error>> {
     // Print session start message
     print_session_starting("TUI", session.node_id);
 
     // Check for new version and get version info
     let current_version = env!("CARGO_PKG_VERSION");
-    let (version_update_available, latest_version) =
-        if let Some(message) = check_for_new_version(current_version).await {
-            // Extract version from message - format: "New version v0.10.3 is available..."
-            let latest = message
-                .split_whitespace()
-                .nth(2) // "New version [VERSION] is available..."
-                .map(|v| v.to_string());
-            (true, latest)
-        } else {
-            (false, None)
-        };
+    let (version_update_available, latest_version) = if let Some(message) =
+        check_for_new_version(current_version, session.update_channel).await
+    {
+        // Extract version from message - format: "New version v0.10.3 is available..."
+        let latest = message
+            .split_whitespace()
+            .nth(2) // "New version [VERSION] is available..."
+            .map(|v| v.to_string());
+        (true, latest)
+    } else {
+        (false, None)
+    };
+
+    // Desktop notifications are opt-in and skipped where no desktop is available
+    let desktop_settings = session.notifications.desktop;
+    let desktop_enabled = desktop_settings.enabled && desktop::is_supported();
+    if desktop_enabled && desktop_settings.version_update {
+        if let Some(latest) = &latest_version {
+            desktop::show(desktop::version_update(latest));
+        }
+    }
+    let event_receiver = if desktop_enabled {
+        desktop::spawn_notifier(session.event_receiver, desktop_settings)
+    } else {
+        session.event_receiver
+    };
 
     // Terminal setup
+    if session.status_title.is_some() {
+        save_terminal_title();
+    }
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    // Without mouse support the dashboard is driven by keys alone
+    let mouse_captured = execute!(stdout, EnableMouseCapture).is_ok();
 
     // Initialize the terminal with Crossterm backend
     let backend = CrosstermBackend::new(stdout);
@@ -65,12 +128,30 @@ pub async fn run_tui_mode(
         session.num_workers,
         version_update_available,
         latest_version,
+        session.points.clone(),
+    )
+    .with_network_stats(session.network_stats.clone())
+    .with_pinned_cores(session.pinned_cores.clone())
+    .with_fetch_state(Some(session.fetch_state.clone()))
+    .with_version_notice(session.version_notice.clone())
+    .with_labels(session.labels.clone())
+    .with_ui_mode(ui_mode)
+    .with_max_fps(session.ui_fps)
+    .with_control(Some(session.control.clone()))
+    .with_status_title(session.status_title.clone())
+    .with_theme(theme::resolve(
+        &theme::themes_dir(&session.config_path),
+        session.theme.as_deref(),
+    ))
+    .with_layout(
+        session.dashboard_layout.clone(),
+        Some(session.config_path.clone()),
     );
 
     let app = ui::App::new(
         Some(session.node_id),
         session.orchestrator.environment().clone(),
-        session.event_receiver,
+        event_receiver,
         session.shutdown_sender.clone(),
         session.max_tasks_shutdown_sender.subscribe(),
         ui_config,
@@ -80,22 +161,32 @@ pub async fn run_tui_mode(
 
     // Clean up the terminal after running the application
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    if mouse_captured {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
+    if session.status_title.is_some() {
+        restore_terminal_title();
+    }
 
     // Handle the result
-    result?;
+    let exit = result?;
 
     // Wait for workers to finish
     print_session_shutdown();
     for handle in session.join_handles {
         let _ = handle.await;
     }
+    crate::chaos::check_workers_exited();
+    report_summary(
+        &session.summary,
+        &session.points,
+        session.orchestrator.as_ref(),
+        session.node_id,
+        session.summary_file.as_deref(),
+    )
+    .await;
     print_session_exit_success();
-
-    Ok(())
+    Ok(exit)
 }
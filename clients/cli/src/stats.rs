@@ -0,0 +1,237 @@
+//! Proof duration statistics
+//!
+//! Keeps an exponential moving average (EMA) of proof durations for each task difficulty, so
+//! the dashboard can show how far along the current proof probably is and when it should
+//! finish. Recent proofs weigh more than older ones, which keeps the estimate close to the
+//! machine's current load.
+//!
+//! [`SlowdownDetector`] compares the same moving average with the first proofs of the session,
+//! to notice when proving has become much slower than the machine started out.
+
+use crate::consts::cli_consts::stats::{
+    PROOF_DURATION_EMA_ALPHA, SLOWDOWN_BASELINE_PROOFS, SLOWDOWN_MIN_RECENT_PROOFS, SLOWDOWN_RATIO,
+    SLOWDOWN_RECOVERY_RATIO,
+};
+use crate::nexus_orchestrator::TaskDifficulty;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Estimated progress of a running proof
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProofProgress {
+    /// Estimated fraction of the proof done, from 0.0 to 1.0
+    pub ratio: f64,
+    /// Estimated time until the proof is done; zero once the estimate is exceeded
+    pub remaining: Duration,
+}
+
+impl ProofProgress {
+    /// Whether the proof has run past its estimated duration
+    pub fn is_overdue(&self) -> bool {
+        self.remaining.is_zero()
+    }
+}
+
+/// Moving average of proof durations, per task difficulty
+#[derive(Debug, Clone, Default)]
+pub struct ProofDurationModel {
+    /// (average duration in seconds, number of proofs) per difficulty
+    averages: HashMap<TaskDifficulty, (f64, u32)>,
+}
+
+impl ProofDurationModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record how long a proof of the given difficulty took.
+    pub fn record(&mut self, difficulty: TaskDifficulty, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        self.averages
+            .entry(difficulty)
+            .and_modify(|(average, count)| {
+                *average += PROOF_DURATION_EMA_ALPHA * (secs - *average);
+                *count += 1;
+            })
+            .or_insert((secs, 1));
+    }
+
+    /// Expected duration of a proof of the given difficulty, if one has been recorded.
+    pub fn estimate(&self, difficulty: TaskDifficulty) -> Option<Duration> {
+        self.averages
+            .get(&difficulty)
+            .map(|(average, _)| Duration::from_secs_f64(*average))
+    }
+
+    /// Number of proofs recorded for the given difficulty.
+    pub fn samples(&self, difficulty: TaskDifficulty) -> u32 {
+        self.averages
+            .get(&difficulty)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Estimated progress of a proof of the given difficulty that has run for `elapsed`.
+    /// Returns `None` until a proof of that difficulty has been recorded.
+    pub fn progress(&self, difficulty: TaskDifficulty, elapsed: Duration) -> Option<ProofProgress> {
+        let estimate = self.estimate(difficulty)?;
+        if estimate.is_zero() {
+            return None;
+        }
+        Some(ProofProgress {
+            ratio: (elapsed.as_secs_f64() / estimate.as_secs_f64()).min(1.0),
+            remaining: estimate.saturating_sub(elapsed),
+        })
+    }
+}
+
+/// Proofs of a difficulty taking much longer than at the start of the session
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slowdown {
+    pub difficulty: TaskDifficulty,
+    /// Average duration of the first proofs of the session
+    pub baseline: Duration,
+    /// Moving average of the proofs since
+    pub recent: Duration,
+}
+
+impl Slowdown {
+    /// How many times longer proofs take than at the start
+    pub fn ratio(&self) -> f64 {
+        self.recent.as_secs_f64() / self.baseline.as_secs_f64()
+    }
+}
+
+/// Baseline and recent proof durations of one difficulty, in seconds
+#[derive(Debug, Clone, Default)]
+struct DurationTrend {
+    baseline_total: f64,
+    baseline_proofs: u32,
+    recent: f64,
+    recent_proofs: u32,
+    /// Whether the current slowdown was reported
+    reported: bool,
+}
+
+/// Notices sustained rises in proof durations, per task difficulty
+///
+/// The first [`SLOWDOWN_BASELINE_PROOFS`] proofs of a difficulty set its baseline. A slowdown is
+/// reported once the moving average of later proofs reaches [`SLOWDOWN_RATIO`] times the
+/// baseline, and reported again only after it has fallen below [`SLOWDOWN_RECOVERY_RATIO`].
+#[derive(Debug, Clone, Default)]
+pub struct SlowdownDetector {
+    trends: HashMap<TaskDifficulty, DurationTrend>,
+}
+
+impl SlowdownDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record how long a proof took, returning a slowdown if it is newly detected.
+    pub fn record(&mut self, difficulty: TaskDifficulty, duration: Duration) -> Option<Slowdown> {
+        let secs = duration.as_secs_f64();
+        let trend = self.trends.entry(difficulty).or_default();
+        if trend.baseline_proofs < SLOWDOWN_BASELINE_PROOFS {
+            trend.baseline_total += secs;
+            trend.baseline_proofs += 1;
+            return None;
+        }
+
+        trend.recent = match trend.recent_proofs {
+            0 => secs,
+            _ => trend.recent + PROOF_DURATION_EMA_ALPHA * (secs - trend.recent),
+        };
+        trend.recent_proofs += 1;
+        let baseline = trend.baseline_total / trend.baseline_proofs as f64;
+        if trend.reported {
+            trend.reported = trend.recent > baseline * SLOWDOWN_RECOVERY_RATIO;
+            return None;
+        }
+        if trend.recent_proofs < SLOWDOWN_MIN_RECENT_PROOFS
+            || baseline <= 0.0
+            || trend.recent < baseline * SLOWDOWN_RATIO
+        {
+            return None;
+        }
+        trend.reported = true;
+        Some(Slowdown {
+            difficulty,
+            baseline: Duration::from_secs_f64(baseline),
+            recent: Duration::from_secs_f64(trend.recent),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moving_average() {
+        let mut model = ProofDurationModel::new();
+        assert_eq!(model.estimate(TaskDifficulty::Small), None);
+
+        model.record(TaskDifficulty::Small, Duration::from_secs(100));
+        assert_eq!(
+            model.estimate(TaskDifficulty::Small),
+            Some(Duration::from_secs(100))
+        );
+
+        // Each new proof moves the average towards it by the smoothing factor
+        model.record(TaskDifficulty::Small, Duration::from_secs(200));
+        let expected = 100.0 + PROOF_DURATION_EMA_ALPHA * 100.0;
+        let estimate = model.estimate(TaskDifficulty::Small).unwrap();
+        assert!((estimate.as_secs_f64() - expected).abs() < 1e-6);
+        assert_eq!(model.samples(TaskDifficulty::Small), 2);
+
+        // Difficulties are tracked separately
+        assert_eq!(model.estimate(TaskDifficulty::Large), None);
+        assert_eq!(model.samples(TaskDifficulty::Large), 0);
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut model = ProofDurationModel::new();
+        assert_eq!(
+            model.progress(TaskDifficulty::Medium, Duration::from_secs(10)),
+            None
+        );
+
+        model.record(TaskDifficulty::Medium, Duration::from_secs(60));
+        let progress = model
+            .progress(TaskDifficulty::Medium, Duration::from_secs(15))
+            .unwrap();
+        assert!((progress.ratio - 0.25).abs() < 1e-6);
+        assert_eq!(progress.remaining, Duration::from_secs(45));
+        assert!(!progress.is_overdue());
+
+        let overdue = model
+            .progress(TaskDifficulty::Medium, Duration::from_secs(90))
+            .unwrap();
+        assert_eq!(overdue.ratio, 1.0);
+        assert!(overdue.is_overdue());
+    }
+
+    #[test]
+    fn test_slowdown_detection() {
+        let mut detector = SlowdownDetector::new();
+        let mut record = |secs| detector.record(TaskDifficulty::Small, Duration::from_secs(secs));
+
+        for _ in 0..SLOWDOWN_BASELINE_PROOFS {
+            assert_eq!(record(100), None);
+        }
+        // A single slow proof is not enough
+        assert_eq!(record(200), None);
+        assert_eq!(record(200), None);
+        let slowdown = record(200).unwrap();
+        assert_eq!(slowdown.baseline, Duration::from_secs(100));
+        assert!((slowdown.ratio() - 2.0).abs() < 1e-6);
+
+        // Reported once, until proofs are about as fast as they were
+        assert_eq!(record(200), None);
+        for _ in 0..10 {
+            assert_eq!(record(100), None);
+        }
+        assert!((0..10).any(|_| record(250).is_some()));
+    }
+}
@@ -0,0 +1,298 @@
+//! Disk space guard
+//!
+//! Failure bundles, cached proofs and exported proofs pile up under `~/.nexus/` over a long
+//! session. While a session runs, a background task keeps each directory listed in the `storage`
+//! section of the config file under its quota by removing its oldest files, and warns in the
+//! dashboard and the headless log once free disk space falls below `min_free_mb`.
+//!
+//! ```json
+//! "storage": {
+//!   "quotas_mb": { "failures": 256, "proof-cache": 512, "proofs": 2048 },
+//!   "min_free_mb": 1024
+//! }
+//! ```
+//!
+//! Quota keys are directories relative to the one holding the config file; absolute paths work
+//! too, e.g. for a `--export-proofs` directory elsewhere.
+
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::consts::cli_consts::storage::{
+    DEFAULT_FAILURES_QUOTA_MB, DEFAULT_MIN_FREE_DISK_MB, DEFAULT_PROOF_CACHE_QUOTA_MB,
+    STORAGE_CHECK_INTERVAL_SECS,
+};
+use crate::events::{Event, EventType};
+use crate::logging::LogLevel;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use sysinfo::Disks;
+use tokio::sync::mpsc;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// `storage` section of the config file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct StorageSettings {
+    /// Space each directory may use, in MB, by path relative to the config file's directory
+    pub quotas_mb: BTreeMap<String, u64>,
+    /// Free disk space below which a warning is shown, in MB (0 disables the warning)
+    pub min_free_mb: u64,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            quotas_mb: BTreeMap::from([
+                ("failures".to_string(), DEFAULT_FAILURES_QUOTA_MB),
+                ("proof-cache".to_string(), DEFAULT_PROOF_CACHE_QUOTA_MB),
+            ]),
+            min_free_mb: DEFAULT_MIN_FREE_DISK_MB,
+        }
+    }
+}
+
+impl StorageSettings {
+    /// Directories with a quota, resolved against `base`, and their quota in bytes
+    pub fn quotas(&self, base: &Path) -> Vec<(PathBuf, u64)> {
+        self.quotas_mb
+            .iter()
+            .map(|(dir, mb)| (base.join(dir), mb.saturating_mul(BYTES_PER_MB)))
+            .collect()
+    }
+}
+
+/// What [`prune_oldest`] removed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Remove the oldest files in `dir` that `keep` selects until they take at most `max_bytes`.
+///
+/// Subdirectories are left alone. A missing directory is treated as empty.
+pub fn prune_oldest(
+    dir: &Path,
+    max_bytes: u64,
+    keep: impl Fn(&Path) -> bool,
+) -> io::Result<PruneReport> {
+    let read_dir = match std::fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(PruneReport::default()),
+        Err(e) => return Err(e),
+    };
+    let mut entries = Vec::new();
+    for entry in read_dir {
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = entry.metadata()?;
+        if metadata.is_file() && keep(&path) {
+            entries.push((metadata.modified()?, metadata.len(), path));
+        }
+    }
+
+    let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+    let mut report = PruneReport::default();
+    entries.sort_by_key(|(modified, _, _)| *modified);
+    for (_, len, path) in entries {
+        if total <= max_bytes {
+            break;
+        }
+        std::fs::remove_file(&path)?;
+        total -= len;
+        report.files += 1;
+        report.bytes += len;
+    }
+    Ok(report)
+}
+
+/// Free space on the disk holding `path`, or `None` if no disk is found for it.
+pub fn free_space(path: &Path) -> Option<u64> {
+    let path = path.canonicalize().ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// Tracks whether free space is low, so the warning is shown once per dip below the threshold.
+#[derive(Debug, Default)]
+struct LowSpaceAlarm {
+    raised: bool,
+}
+
+impl LowSpaceAlarm {
+    /// Warning to show for the given free space, if it just fell below `min_free_mb`
+    fn observe(&mut self, free_bytes: u64, min_free_mb: u64) -> Option<String> {
+        let low = min_free_mb > 0 && free_bytes < min_free_mb.saturating_mul(BYTES_PER_MB);
+        let newly_low = low && !self.raised;
+        self.raised = low;
+        newly_low.then(|| {
+            format!(
+                "Low disk space: {} MB free, below the {} MB threshold. Free up space or lower the storage quotas",
+                free_bytes / BYTES_PER_MB,
+                min_free_mb
+            )
+        })
+    }
+}
+
+/// Enforce the storage quotas under `base`, the directory of the config file, and warn about low
+/// disk space by passing a warning event along with the worker events. Stops once the workers
+/// have exited.
+pub fn spawn_storage_manager(
+    mut source: mpsc::Receiver<Event>,
+    settings: StorageSettings,
+    base: PathBuf,
+) -> mpsc::Receiver<Event> {
+    let (sender, receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(STORAGE_CHECK_INTERVAL_SECS));
+        let mut alarm = LowSpaceAlarm::default();
+        loop {
+            let event = tokio::select! {
+                event = source.recv() => match event {
+                    Some(event) => event,
+                    None => break,
+                },
+                _ = interval.tick() => {
+                    let (quota_settings, quota_base) = (settings.clone(), base.clone());
+                    let (pruned, free) = tokio::task::spawn_blocking(move || {
+                        (enforce_quotas(&quota_settings, &quota_base), free_space(&quota_base))
+                    })
+                    .await
+                    .unwrap_or_default();
+                    let mut warnings = pruned;
+                    warnings.extend(free.and_then(|free| alarm.observe(free, settings.min_free_mb)));
+                    for warning in warnings {
+                        let event = Event::task_fetcher_with_level(
+                            warning,
+                            EventType::Waiting,
+                            LogLevel::Warn,
+                        );
+                        if sender.send(event).await.is_err() {
+                            return;
+                        }
+                    }
+                    continue;
+                }
+            };
+            if sender.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+/// Prune every directory over its quota, returning a message for each one that was pruned or
+/// could not be.
+fn enforce_quotas(settings: &StorageSettings, base: &Path) -> Vec<String> {
+    let mut messages = Vec::new();
+    for (dir, max_bytes) in settings.quotas(base) {
+        // Partially written files are left to their writers
+        match prune_oldest(&dir, max_bytes, |path| {
+            path.extension().is_none_or(|ext| ext != "tmp")
+        }) {
+            Ok(report) if report.files > 0 => messages.push(format!(
+                "Removed {} old files ({} MB) from {} to stay within its {} MB quota",
+                report.files,
+                report.bytes / BYTES_PER_MB,
+                dir.display(),
+                max_bytes / BYTES_PER_MB
+            )),
+            Ok(_) => {}
+            Err(e) => messages.push(format!("Could not prune {}: {}", dir.display(), e)),
+        }
+    }
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    fn write_aged(dir: &Path, name: &str, len: usize, age_secs: u64) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, vec![0u8; len]).unwrap();
+        let modified = SystemTime::now() - Duration::from_secs(age_secs);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_prune_oldest_first() {
+        let dir = TempDir::new().unwrap();
+        let oldest = write_aged(dir.path(), "a.json", 100, 300);
+        let older = write_aged(dir.path(), "b.json", 100, 200);
+        let newest = write_aged(dir.path(), "c.json", 100, 100);
+
+        let report = prune_oldest(dir.path(), 150, |_| true).unwrap();
+        assert_eq!(
+            report,
+            PruneReport {
+                files: 2,
+                bytes: 200
+            }
+        );
+        assert!(!oldest.exists());
+        assert!(!older.exists());
+        assert!(newest.exists());
+    }
+
+    #[test]
+    fn test_prune_skips_unselected_files() {
+        let dir = TempDir::new().unwrap();
+        let partial = write_aged(dir.path(), "a.tmp", 100, 300);
+        let kept = write_aged(dir.path(), "b.proof", 100, 100);
+
+        let report = prune_oldest(dir.path(), 150, |path| {
+            path.extension().is_some_and(|ext| ext == "proof")
+        })
+        .unwrap();
+        assert_eq!(report, PruneReport::default());
+        assert!(partial.exists());
+        assert!(kept.exists());
+    }
+
+    #[test]
+    fn test_prune_missing_dir() {
+        let dir = TempDir::new().unwrap();
+        let report = prune_oldest(&dir.path().join("failures"), 0, |_| true).unwrap();
+        assert_eq!(report, PruneReport::default());
+    }
+
+    #[test]
+    fn test_quotas_resolve_against_base() {
+        let settings = StorageSettings::default();
+        let quotas = settings.quotas(Path::new("/home/prover/.nexus"));
+        assert!(quotas.contains(&(
+            PathBuf::from("/home/prover/.nexus/failures"),
+            DEFAULT_FAILURES_QUOTA_MB * BYTES_PER_MB
+        )));
+    }
+
+    #[test]
+    fn test_low_space_warns_once_per_dip() {
+        let mut alarm = LowSpaceAlarm::default();
+        assert!(alarm.observe(2048 * BYTES_PER_MB, 1024).is_none());
+        assert!(alarm.observe(512 * BYTES_PER_MB, 1024).is_some());
+        assert!(alarm.observe(256 * BYTES_PER_MB, 1024).is_none());
+        assert!(alarm.observe(2048 * BYTES_PER_MB, 1024).is_none());
+        assert!(alarm.observe(512 * BYTES_PER_MB, 1024).is_some());
+        assert!(alarm.observe(0, 0).is_none());
+    }
+}
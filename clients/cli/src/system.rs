@@ -1,12 +1,13 @@
 //! System information and performance measurements
 
 use cfg_if::cfg_if;
+use std::fmt;
 use std::hint::black_box;
-use std::process;
+use std::process::{self, Command};
 use std::sync::OnceLock;
 use std::thread::available_parallelism;
 use std::time::Instant;
-use sysinfo::{CpuRefreshKind, RefreshKind, System};
+use sysinfo::{Components, CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
 
 const NUM_TESTS: u64 = 1_000_000;
 const OPERATIONS_PER_ITERATION: u64 = 4; // sin, add, multiply, divide
@@ -15,6 +16,9 @@ const NUM_REPEATS: usize = 5; // Number of repeats to average the results
 // Cache for flops measurement - only measure once per application run
 static FLOPS_CACHE: OnceLock<f32> = OnceLock::new();
 
+// Cache for GPU detection - probing shells out to vendor tools, so only do it once
+static GPU_CACHE: OnceLock<Vec<GpuInfo>> = OnceLock::new();
+
 /// Get the number of logical cores available on the machine.
 pub fn num_cores() -> usize {
     available_parallelism().map(|n| n.get()).unwrap_or(1) // Fallback to 1 if detection fails
@@ -149,6 +153,270 @@ pub fn process_memory_gb() -> f64 {
     memory as f64 / 1024.0 / 1024.0 / 1024.0 // Convert to GB (binary)
 }
 
+/// Hottest reading of the machine's temperature sensors (hwmon on Linux), in °C, or `None` if no
+/// sensor can be read, as in most VMs and containers.
+pub fn max_temperature_celsius() -> Option<f32> {
+    Components::new_with_refreshed_list()
+        .list()
+        .iter()
+        .filter_map(|component| component.temperature())
+        .filter(|celsius| celsius.is_finite() && *celsius > 0.0)
+        .reduce(f32::max)
+}
+
+/// CPU clock, free memory and temperature at one moment, to explain changes in proving speed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemConditions {
+    /// Average clock of the CPU cores in MHz, if the OS reports it
+    pub cpu_mhz: Option<u64>,
+    /// Share of memory available to new allocations, from 0.0 to 1.0
+    pub available_memory: f64,
+    /// Hottest sensor reading in °C, see [`max_temperature_celsius`]
+    pub celsius: Option<f32>,
+}
+
+/// Read the current CPU clock, free memory and temperature.
+pub fn system_conditions() -> SystemConditions {
+    let sys = System::new_with_specifics(
+        RefreshKind::nothing()
+            .with_cpu(CpuRefreshKind::nothing().with_frequency())
+            .with_memory(MemoryRefreshKind::nothing().with_ram()),
+    );
+    let clocks: Vec<u64> = sys
+        .cpus()
+        .iter()
+        .map(|cpu| cpu.frequency())
+        .filter(|mhz| *mhz > 0)
+        .collect();
+    SystemConditions {
+        cpu_mhz: (!clocks.is_empty()).then(|| clocks.iter().sum::<u64>() / clocks.len() as u64),
+        available_memory: match sys.total_memory() {
+            0 => 1.0,
+            total => sys.available_memory() as f64 / total as f64,
+        },
+        celsius: max_temperature_celsius(),
+    }
+}
+
+/// Whether we appear to be running inside a container (Docker, Podman, Kubernetes).
+pub fn is_container() -> bool {
+    if std::env::var_os("container").is_some()
+        || std::env::var_os("KUBERNETES_SERVICE_HOST").is_some()
+        || std::path::Path::new("/.dockerenv").exists()
+        || std::path::Path::new("/run/.containerenv").exists()
+    {
+        return true;
+    }
+    std::fs::read_to_string("/proc/1/cgroup").is_ok_and(|cgroup| {
+        ["docker", "kubepods", "containerd", "libpod"]
+            .iter()
+            .any(|marker| cgroup.contains(marker))
+    })
+}
+
+/// Compute stack through which a detected GPU can be driven.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GpuBackend {
+    Cuda,
+    Rocm,
+    Metal,
+}
+
+impl GpuBackend {
+    /// Lowercase name reported in telemetry.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GpuBackend::Cuda => "cuda",
+            GpuBackend::Rocm => "rocm",
+            GpuBackend::Metal => "metal",
+        }
+    }
+}
+
+impl fmt::Display for GpuBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuBackend::Cuda => write!(f, "CUDA"),
+            GpuBackend::Rocm => write!(f, "ROCm"),
+            GpuBackend::Metal => write!(f, "Metal"),
+        }
+    }
+}
+
+/// A GPU detected on this machine.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GpuInfo {
+    pub name: String,
+    pub backend: GpuBackend,
+    /// Dedicated video memory in MB; `None` for unified memory or when unknown.
+    pub vram_mb: Option<u64>,
+}
+
+impl fmt::Display for GpuInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.vram_mb {
+            Some(mb) => write!(
+                f,
+                "{} ({}, {:.1} GB)",
+                self.name,
+                self.backend,
+                mb as f64 / 1024.0
+            ),
+            None => write!(f, "{} ({})", self.name, self.backend),
+        }
+    }
+}
+
+/// Detect GPUs usable for compute (CUDA, ROCm or Metal).
+/// The result is cached after the first detection, so subsequent calls return the cached value.
+pub fn detect_gpus() -> &'static [GpuInfo] {
+    GPU_CACHE.get_or_init(|| {
+        let mut gpus = detect_cuda_gpus();
+        gpus.extend(detect_rocm_gpus());
+        gpus.extend(detect_metal_gpus());
+        gpus
+    })
+}
+
+/// NVIDIA GPUs, as reported by `nvidia-smi` (only present when the CUDA driver is installed).
+fn detect_cuda_gpus() -> Vec<GpuInfo> {
+    Command::new("nvidia-smi")
+        .args([
+            "--query-gpu=name,memory.total",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| parse_nvidia_smi(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parse `nvidia-smi --query-gpu=name,memory.total --format=csv,noheader,nounits` output.
+fn parse_nvidia_smi(output: &str) -> Vec<GpuInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, memory) = line.rsplit_once(',')?;
+            Some(GpuInfo {
+                name: name.trim().to_string(),
+                backend: GpuBackend::Cuda,
+                vram_mb: memory.trim().parse().ok(),
+            })
+        })
+        .filter(|gpu| !gpu.name.is_empty())
+        .collect()
+}
+
+/// AMD GPUs driven by the amdgpu kernel driver with ROCm's `/dev/kfd` compute interface.
+fn detect_rocm_gpus() -> Vec<GpuInfo> {
+    cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            const AMD_VENDOR_ID: &str = "0x1002";
+
+            if !std::path::Path::new("/dev/kfd").exists() {
+                return Vec::new();
+            }
+            let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+                return Vec::new();
+            };
+
+            let mut cards: Vec<_> = entries
+                .flatten()
+                .filter(|entry| {
+                    // Skip connector entries such as `card0-DP-1`
+                    entry
+                        .file_name()
+                        .to_str()
+                        .and_then(|name| name.strip_prefix("card"))
+                        .is_some_and(|index| index.chars().all(|c| c.is_ascii_digit()))
+                })
+                .map(|entry| entry.path().join("device"))
+                .collect();
+            cards.sort();
+
+            cards
+                .into_iter()
+                .filter(|device| {
+                    std::fs::read_to_string(device.join("vendor"))
+                        .is_ok_and(|vendor| vendor.trim() == AMD_VENDOR_ID)
+                })
+                .map(|device| GpuInfo {
+                    name: std::fs::read_to_string(device.join("product_name"))
+                        .map(|name| name.trim().to_string())
+                        .ok()
+                        .filter(|name| !name.is_empty())
+                        .unwrap_or_else(|| "AMD GPU".to_string()),
+                    backend: GpuBackend::Rocm,
+                    vram_mb: std::fs::read_to_string(device.join("mem_info_vram_total"))
+                        .ok()
+                        .and_then(|bytes| bytes.trim().parse::<u64>().ok())
+                        .map(|bytes| bytes / 1_048_576),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Metal-capable GPUs, as reported by `system_profiler` on macOS.
+fn detect_metal_gpus() -> Vec<GpuInfo> {
+    cfg_if! {
+        if #[cfg(target_os = "macos")] {
+            Command::new("system_profiler")
+                .args(["SPDisplaysDataType", "-json"])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| parse_system_profiler(&String::from_utf8_lossy(&output.stdout)))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Parse `system_profiler SPDisplaysDataType -json` output.
+/// Apple Silicon GPUs share system memory and report no VRAM.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_system_profiler(output: &str) -> Vec<GpuInfo> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(output) else {
+        return Vec::new();
+    };
+    let Some(displays) = json["SPDisplaysDataType"].as_array() else {
+        return Vec::new();
+    };
+
+    displays
+        .iter()
+        .filter_map(|display| {
+            let name = display["sppci_model"].as_str()?.trim().to_string();
+            let vram_mb = display["spdisplays_vram"]
+                .as_str()
+                .or_else(|| display["spdisplays_vram_shared"].as_str())
+                .and_then(parse_vram_mb);
+            Some(GpuInfo {
+                name,
+                backend: GpuBackend::Metal,
+                vram_mb,
+            })
+        })
+        .collect()
+}
+
+/// Parse a size such as "1536 MB" or "8 GB" into MB.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_vram_mb(size: &str) -> Option<u64> {
+    let (amount, unit) = size.trim().split_once(' ')?;
+    let amount: u64 = amount.parse().ok()?;
+    match unit.trim() {
+        "MB" => Some(amount),
+        "GB" => Some(amount * 1024),
+        _ => None,
+    }
+}
+
 // We encode the memory usage to i32 type at client
 fn bytes_to_mb_i32(bytes: u64) -> i32 {
     // Convert to MB with 3 decimal places of precision
@@ -174,4 +442,30 @@ mod tests {
         assert!(mhz > 0, "Expected non-zero MHz");
         // println!("Cores: {}, Base Frequency: {} MHz", cores, mhz);
     }
+
+    #[test]
+    fn test_parse_nvidia_smi() {
+        let output = "NVIDIA GeForce RTX 4090, 24564\nTesla T4, [N/A]\n";
+        let gpus = super::parse_nvidia_smi(output);
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].name, "NVIDIA GeForce RTX 4090");
+        assert_eq!(gpus[0].backend, super::GpuBackend::Cuda);
+        assert_eq!(gpus[0].vram_mb, Some(24564));
+        assert_eq!(gpus[1].vram_mb, None);
+    }
+
+    #[test]
+    fn test_parse_system_profiler() {
+        let output = r#"{"SPDisplaysDataType": [
+            {"sppci_model": "Apple M2 Pro", "sppci_cores": "19"},
+            {"sppci_model": "AMD Radeon Pro 5500M", "spdisplays_vram": "8 GB"}
+        ]}"#;
+        let gpus = super::parse_system_profiler(output);
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].name, "Apple M2 Pro");
+        assert_eq!(gpus[0].vram_mb, None);
+        assert_eq!(gpus[1].backend, super::GpuBackend::Metal);
+        assert_eq!(gpus[1].vram_mb, Some(8192));
+        assert!(super::parse_system_profiler("not json").is_empty());
+    }
 }
@@ -95,7 +95,8 @@ impl From<&crate::nexus_orchestrator::Task> for Task {
             program_id: task.program_id.clone(),
             public_inputs: task.public_inputs_list.first().cloned().unwrap_or_default(),
             public_inputs_list: task.public_inputs_list.clone(),
-            task_type: crate::nexus_orchestrator::TaskType::try_from(task.task_type).unwrap(),
+            task_type: crate::nexus_orchestrator::TaskType::try_from(task.task_type)
+                .unwrap_or_default(),
             difficulty: crate::nexus_orchestrator::TaskDifficulty::try_from(task.difficulty)
                 .unwrap_or_default(),
         }
@@ -103,10 +104,71 @@ impl From<&crate::nexus_orchestrator::Task> for Task {
 }
 
 // From GetProofTaskResponse
-impl From<&crate::nexus_orchestrator::GetProofTaskResponse> for Task {
-    fn from(response: &crate::nexus_orchestrator::GetProofTaskResponse) -> Self {
+impl TryFrom<&crate::nexus_orchestrator::GetProofTaskResponse> for Task {
+    type Error = crate::orchestrator::error::OrchestratorError;
+
+    fn try_from(
+        response: &crate::nexus_orchestrator::GetProofTaskResponse,
+    ) -> Result<Self, Self::Error> {
         // Use the task field instead of deprecated fields
-        Task::from(response.task.as_ref().unwrap())
+        response.task.as_ref().map(Task::from).ok_or_else(|| {
+            crate::orchestrator::error::OrchestratorError::MalformedResponse(
+                "Proof task response contains no task".to_string(),
+            )
+        })
+    }
+}
+
+/// A task offered to the node, described before the node takes it
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TaskDescriptor {
+    /// Identifies the offer when taking or declining the task
+    pub offer_id: String,
+    pub difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    pub task_type: crate::nexus_orchestrator::TaskType,
+    /// Total size of the public inputs in bytes
+    pub input_size: u64,
+    /// Estimated zkVM cycles to prove all inputs
+    pub expected_cycles: u64,
+    /// Points weight of the task, relative to other tasks
+    pub reward_weight: u64,
+}
+
+impl Display for TaskDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, {} cycles, {} bytes of inputs, reward {}",
+            self.difficulty.as_str_name(),
+            self.expected_cycles,
+            self.input_size,
+            self.reward_weight
+        )
+    }
+}
+
+// From PreviewProofTaskResponse
+impl TryFrom<&crate::nexus_orchestrator::PreviewProofTaskResponse> for TaskDescriptor {
+    type Error = crate::orchestrator::error::OrchestratorError;
+
+    fn try_from(
+        response: &crate::nexus_orchestrator::PreviewProofTaskResponse,
+    ) -> Result<Self, Self::Error> {
+        let offer = response.offer.as_ref().ok_or_else(|| {
+            crate::orchestrator::error::OrchestratorError::MalformedResponse(
+                "Task preview contains no offer".to_string(),
+            )
+        })?;
+        Ok(TaskDescriptor {
+            offer_id: offer.offer_id.clone(),
+            difficulty: crate::nexus_orchestrator::TaskDifficulty::try_from(offer.difficulty)
+                .unwrap_or_default(),
+            task_type: crate::nexus_orchestrator::TaskType::try_from(offer.task_type)
+                .unwrap_or_default(),
+            input_size: offer.input_size,
+            expected_cycles: offer.expected_cycles,
+            reward_weight: offer.reward_weight,
+        })
     }
 }
 
@@ -231,4 +293,25 @@ mod tests {
 
         println!("Backward compatibility test passed");
     }
+
+    #[test]
+    fn test_task_from_malformed_response() {
+        let response = crate::nexus_orchestrator::GetProofTaskResponse::default();
+        assert!(Task::try_from(&response).is_err());
+
+        let response = crate::nexus_orchestrator::GetProofTaskResponse {
+            task: Some(crate::nexus_orchestrator::Task {
+                task_id: "task".to_string(),
+                program_id: "fib_input_initial".to_string(),
+                task_type: 42,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let task = Task::try_from(&response).unwrap();
+        assert_eq!(
+            task.task_type,
+            crate::nexus_orchestrator::TaskType::ProofRequired
+        );
+    }
 }
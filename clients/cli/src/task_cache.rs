@@ -0,0 +1,218 @@
+//! Completed task cache
+//!
+//! Remembers which tasks this machine has already submitted proofs for, so that a task offered
+//! again, e.g. after a restart, is neither proven nor submitted twice. The cache is stored in
+//! `~/.nexus/task-cache.json` as a map from task ID to the time of submission. Entries expire
+//! after a configurable time to live, and once the cache holds more than its maximum number of
+//! entries, the oldest ones are dropped. A missing or unreadable file starts an empty cache.
+
+use crate::consts::cli_consts::{TASK_CACHE_MAX_ENTRIES, TASK_CACHE_TTL_SECS};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// `task_cache` section of the config file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct TaskCacheSettings {
+    /// How long a submitted task is remembered, in seconds
+    pub ttl_secs: u64,
+    /// Most tasks remembered at once; the oldest are forgotten first (0 disables the cache)
+    pub max_entries: usize,
+}
+
+impl Default for TaskCacheSettings {
+    fn default() -> Self {
+        Self {
+            ttl_secs: TASK_CACHE_TTL_SECS,
+            max_entries: TASK_CACHE_MAX_ENTRIES,
+        }
+    }
+}
+
+/// Disk-backed set of submitted task IDs, shared by the fetcher and the submitter.
+///
+/// Clones share the same entries.
+#[derive(Debug, Clone)]
+pub struct TaskCache {
+    path: PathBuf,
+    settings: TaskCacheSettings,
+    /// Submission time of each task, in seconds since the Unix epoch
+    entries: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl TaskCache {
+    /// Load the cache stored at `path`, dropping expired entries.
+    pub fn open(path: impl Into<PathBuf>, settings: TaskCacheSettings) -> Self {
+        let path = path.into();
+        let mut entries: HashMap<String, u64> = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        prune(&mut entries, &settings, now_secs());
+        Self {
+            path,
+            settings,
+            entries: Arc::new(Mutex::new(entries)),
+        }
+    }
+
+    /// `~/.nexus/task-cache.json`, next to the config file
+    pub fn default_path() -> Option<PathBuf> {
+        crate::config::nexus_dir()
+            .ok()
+            .map(|dir| dir.join("task-cache.json"))
+    }
+
+    /// Whether a proof for the task was submitted within the time to live
+    pub fn contains(&self, task_id: &str) -> bool {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(task_id)
+            .is_some_and(|submitted_at| !is_expired(*submitted_at, &self.settings, now_secs()))
+    }
+
+    /// Remember that a proof for the task was submitted, and write the cache to disk.
+    pub fn insert(&self, task_id: &str) -> io::Result<()> {
+        if self.settings.max_entries == 0 {
+            return Ok(());
+        }
+        let snapshot = {
+            let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+            let now = now_secs();
+            entries.insert(task_id.to_string(), now);
+            prune(&mut entries, &self.settings, now);
+            serde_json::to_vec(&*entries).map_err(io::Error::other)?
+        };
+        save(&self.path, &snapshot)
+    }
+
+    /// Number of tasks currently remembered
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn is_expired(submitted_at: u64, settings: &TaskCacheSettings, now: u64) -> bool {
+    now.saturating_sub(submitted_at) > settings.ttl_secs
+}
+
+/// Drop expired entries, then the oldest ones beyond the size limit.
+fn prune(entries: &mut HashMap<String, u64>, settings: &TaskCacheSettings, now: u64) {
+    entries.retain(|_, submitted_at| !is_expired(*submitted_at, settings, now));
+    if entries.len() > settings.max_entries {
+        let mut by_age: Vec<(u64, String)> = entries
+            .iter()
+            .map(|(task_id, submitted_at)| (*submitted_at, task_id.clone()))
+            .collect();
+        by_age.sort();
+        let excess = entries.len() - settings.max_entries;
+        for (_, task_id) in by_age.into_iter().take(excess) {
+            entries.remove(&task_id);
+        }
+    }
+}
+
+/// Write the cache file through a temporary file, so a crash never leaves a partial cache.
+fn save(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, bytes)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn settings(ttl_secs: u64, max_entries: usize) -> TaskCacheSettings {
+        TaskCacheSettings {
+            ttl_secs,
+            max_entries,
+        }
+    }
+
+    #[test]
+    fn test_entries_survive_reopening() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("task-cache.json");
+
+        let cache = TaskCache::open(&path, TaskCacheSettings::default());
+        assert!(!cache.contains("task-1"));
+        cache.insert("task-1").unwrap();
+        assert!(cache.contains("task-1"));
+
+        let reopened = TaskCache::open(&path, TaskCacheSettings::default());
+        assert!(reopened.contains("task-1"));
+        assert!(!reopened.contains("task-2"));
+    }
+
+    #[test]
+    fn test_expired_entries_are_dropped_on_open() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("task-cache.json");
+        let now = now_secs();
+        let stored = HashMap::from([
+            ("old".to_string(), now - 7200),
+            ("recent".to_string(), now - 10),
+        ]);
+        std::fs::write(&path, serde_json::to_vec(&stored).unwrap()).unwrap();
+
+        let cache = TaskCache::open(&path, settings(3600, 100));
+        assert!(!cache.contains("old"));
+        assert!(cache.contains("recent"));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_prune_drops_oldest_beyond_limit() {
+        let mut entries = HashMap::from([
+            ("a".to_string(), 100),
+            ("b".to_string(), 300),
+            ("c".to_string(), 200),
+        ]);
+        prune(&mut entries, &settings(1000, 2), 400);
+        assert!(!entries.contains_key("a"));
+        assert!(entries.contains_key("b"));
+        assert!(entries.contains_key("c"));
+    }
+
+    #[test]
+    fn test_corrupt_file_starts_empty_cache() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("task-cache.json");
+        std::fs::write(&path, b"not json").unwrap();
+
+        let cache = TaskCache::open(&path, TaskCacheSettings::default());
+        assert!(cache.is_empty());
+        cache.insert("task-1").unwrap();
+        assert!(TaskCache::open(&path, TaskCacheSettings::default()).contains("task-1"));
+    }
+
+    #[test]
+    fn test_zero_max_entries_disables_cache() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("task-cache.json");
+        let cache = TaskCache::open(&path, settings(3600, 0));
+        cache.insert("task-1").unwrap();
+        assert!(!cache.contains("task-1"));
+        assert!(!path.exists());
+    }
+}
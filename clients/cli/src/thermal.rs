@@ -0,0 +1,131 @@
+//! Temperature-aware proving
+//!
+//! With `--thermal-limit 85`, a background governor reads the hottest temperature sensor and,
+//! once it reaches 85°C, proves with a single worker. If the machine keeps heating up past the
+//! limit by a few degrees, task fetching is held until it is back under the limit, so proving
+//! runs in bursts instead of continuously. Full speed resumes once the temperature has fallen a
+//! few degrees below the limit, which keeps the governor from flapping around it.
+//!
+//! Sensors are read through sysinfo (hwmon on Linux). Where none can be read, as in most VMs
+//! and containers, the limit has no effect.
+
+use crate::consts::cli_consts::thermal::{
+    THERMAL_HYSTERESIS_CELSIUS, THERMAL_PAUSE_MARGIN_CELSIUS, THERMAL_POLL_INTERVAL_SECS,
+};
+use crate::control::RuntimeControl;
+use crate::system::max_temperature_celsius;
+use std::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Measure the thermal governor currently applies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThermalState {
+    #[default]
+    Normal,
+    /// Proofs run on a single worker
+    Throttled,
+    /// Task fetching is held until the machine is back under the limit
+    Cooling,
+}
+
+/// Latest reading of the thermal governor, for the dashboard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThermalStatus {
+    /// Hottest sensor reading, in °C
+    pub celsius: f32,
+    pub state: ThermalState,
+}
+
+/// Latest status, if the governor is running and has read a sensor
+static THERMAL_STATUS: RwLock<Option<ThermalStatus>> = RwLock::new(None);
+
+/// Latest reading of the thermal governor, or `None` without `--thermal-limit` or sensors.
+pub fn thermal_status() -> Option<ThermalStatus> {
+    *THERMAL_STATUS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// State to move to from `current` at temperature `celsius`, with `limit` in °C.
+fn next_state(current: ThermalState, celsius: Option<f32>, limit: f32) -> ThermalState {
+    let Some(celsius) = celsius else {
+        return ThermalState::Normal;
+    };
+    if celsius >= limit + THERMAL_PAUSE_MARGIN_CELSIUS {
+        return ThermalState::Cooling;
+    }
+    match current {
+        ThermalState::Cooling if celsius >= limit => ThermalState::Cooling,
+        _ if celsius >= limit => ThermalState::Throttled,
+        ThermalState::Throttled | ThermalState::Cooling
+            if celsius > limit - THERMAL_HYSTERESIS_CELSIUS =>
+        {
+            ThermalState::Throttled
+        }
+        _ => ThermalState::Normal,
+    }
+}
+
+/// Poll the temperature sensors in the background and throttle proving through `control` while
+/// the machine is at or above `limit` °C.
+pub fn spawn_thermal_governor(
+    limit: f32,
+    control: RuntimeControl,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(THERMAL_POLL_INTERVAL_SECS));
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = interval.tick() => {
+                    let celsius = tokio::task::spawn_blocking(max_temperature_celsius)
+                        .await
+                        .unwrap_or(None);
+                    let state = next_state(control.thermal_state(), celsius, limit);
+                    control.set_thermal_state(state);
+                    *THERMAL_STATUS.write().unwrap_or_else(|e| e.into_inner()) =
+                        celsius.map(|celsius| ThermalStatus { celsius, state });
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIT: f32 = 85.0;
+
+    #[test]
+    fn test_throttles_at_limit_with_hysteresis() {
+        let state = next_state(ThermalState::Normal, Some(84.0), LIMIT);
+        assert_eq!(state, ThermalState::Normal);
+        let state = next_state(state, Some(86.0), LIMIT);
+        assert_eq!(state, ThermalState::Throttled);
+        // Stays throttled until well below the limit
+        let state = next_state(state, Some(82.0), LIMIT);
+        assert_eq!(state, ThermalState::Throttled);
+        let state = next_state(state, Some(79.0), LIMIT);
+        assert_eq!(state, ThermalState::Normal);
+    }
+
+    #[test]
+    fn test_cools_down_far_above_limit() {
+        let state = next_state(ThermalState::Throttled, Some(91.0), LIMIT);
+        assert_eq!(state, ThermalState::Cooling);
+        // Fetching stays held until the machine is back under the limit
+        let state = next_state(state, Some(87.0), LIMIT);
+        assert_eq!(state, ThermalState::Cooling);
+        let state = next_state(state, Some(84.0), LIMIT);
+        assert_eq!(state, ThermalState::Throttled);
+    }
+
+    #[test]
+    fn test_no_sensor_means_normal() {
+        assert_eq!(
+            next_state(ThermalState::Cooling, None, LIMIT),
+            ThermalState::Normal
+        );
+    }
+}
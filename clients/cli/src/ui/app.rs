@@ -1,16 +1,37 @@
 //! Main application state and UI loop
 //!
-//! Contains the App struct and main UI event handling logic
+//! Contains the App struct and main UI event handling logic, including the dashboard's key
+//! bindings, `?` help overlay and `:` command palette
 
+use crate::config::Config;
+use crate::consts::cli_consts::dashboard::{DEFAULT_UI_FPS, IDLE_REDRAW_INTERVAL_MS};
+use crate::control::RuntimeControl;
 use crate::environment::Environment;
-use crate::events::Event as WorkerEvent;
-use crate::ui::dashboard::{DashboardState, render_dashboard};
+use crate::events::{Event as WorkerEvent, TaskFetchState};
+use crate::labels::Labels;
+use crate::network_stats::NetworkStatsTracker;
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::points::PointsTracker;
+use crate::session::status_line;
+use crate::tr;
+use crate::ui::dashboard::Panel;
+use crate::ui::dashboard::components::footer::footer_key_at;
+use crate::ui::dashboard::renderer::{dashboard_areas, panel_at};
+use crate::ui::dashboard::state::Overlay;
+use crate::ui::dashboard::{DashboardLayout, DashboardState, render_dashboard};
 use crate::ui::login::render_login;
+use crate::ui::plain;
 use crate::ui::splash::render_splash;
-use crossterm::event::{self, Event, KeyCode};
+use crate::ui::theme::{self, Theme};
+use crate::version::manager::VersionNotice;
+use crossterm::event::{self, Event, KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
 use ratatui::{Frame, Terminal, backend::Backend};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::time::{Duration, Instant};
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, watch};
 
 /// UI configuration data grouped by concern
 #[derive(Debug, Clone)]
@@ -19,6 +40,43 @@ pub struct UIConfig {
     pub num_threads: usize,
     pub update_available: bool,
     pub latest_version: Option<String>,
+    pub points: PointsTracker,
+    /// Network statistics and the node's rank, for the leaderboard panel
+    pub network_stats: NetworkStatsTracker,
+    /// Core each worker slot is pinned to, if core pinning is enabled
+    pub pinned_cores: Option<Vec<usize>>,
+    /// Rate-limit budget published by the task fetcher
+    pub fetch_state: Option<watch::Receiver<TaskFetchState>>,
+    /// Dashboard style selected with `--ui`
+    pub ui_mode: UiMode,
+    /// Order and visibility of the dashboard panels
+    pub layout: DashboardLayout,
+    /// Config file that layout and theme changes are saved to
+    pub config_path: Option<PathBuf>,
+    /// Colors the dashboard is drawn with
+    pub theme: Theme,
+    /// Violated version requirement, shown in a banner
+    pub version_notice: Option<VersionNotice>,
+    /// Node labels, shown under the title
+    pub labels: Labels,
+    /// Most redraws per second, set with `--ui-fps`
+    pub max_fps: u32,
+    /// Runtime knobs that the command palette pauses and sets the difficulty with
+    pub control: Option<RuntimeControl>,
+    /// Node status line to set the terminal title to, with `--terminal-title`
+    pub status_title: Option<watch::Receiver<String>>,
+}
+
+/// Dashboard style, selected with `--ui`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum UiMode {
+    /// Colors, box drawing and emoji status markers
+    #[default]
+    Full,
+    /// ASCII-only and monochrome, with text status tags, for screen readers and dumb terminals
+    Plain,
+    /// One status line and the last few log lines, as used automatically in small terminals
+    Compact,
 }
 
 impl UIConfig {
@@ -27,14 +85,107 @@ impl UIConfig {
         num_threads: usize,
         update_available: bool,
         latest_version: Option<String>,
+        points: PointsTracker,
     ) -> Self {
         Self {
             with_background_color,
             num_threads,
             update_available,
             latest_version,
+            points,
+            network_stats: NetworkStatsTracker::default(),
+            pinned_cores: None,
+            fetch_state: None,
+            ui_mode: UiMode::Full,
+            layout: DashboardLayout::default(),
+            config_path: None,
+            theme: Theme::default(),
+            version_notice: None,
+            labels: Labels::new(),
+            max_fps: DEFAULT_UI_FPS,
+            control: None,
+            status_title: None,
         }
     }
+
+    /// Show the core pinning in the system panel.
+    pub fn with_pinned_cores(mut self, pinned_cores: Option<Vec<usize>>) -> Self {
+        self.pinned_cores = pinned_cores;
+        self
+    }
+
+    /// Show the task fetcher's rate-limit budget in the rate limit panel.
+    pub fn with_fetch_state(
+        mut self,
+        fetch_state: Option<watch::Receiver<TaskFetchState>>,
+    ) -> Self {
+        self.fetch_state = fetch_state;
+        self
+    }
+
+    /// Show the network statistics polled by `network_stats` in the leaderboard panel.
+    pub fn with_network_stats(mut self, network_stats: NetworkStatsTracker) -> Self {
+        self.network_stats = network_stats;
+        self
+    }
+
+    /// Show a violated version requirement in the banner above the title.
+    pub fn with_version_notice(mut self, version_notice: Option<VersionNotice>) -> Self {
+        self.version_notice = version_notice;
+        self
+    }
+
+    /// Show the node labels under the title.
+    pub fn with_labels(mut self, labels: Labels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Select the dashboard style.
+    pub fn with_ui_mode(mut self, ui_mode: UiMode) -> Self {
+        self.ui_mode = ui_mode;
+        self
+    }
+
+    /// Draw the dashboard with `theme`.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Redraw the dashboard at most `max_fps` times a second.
+    pub fn with_max_fps(mut self, max_fps: u32) -> Self {
+        self.max_fps = max_fps.max(1);
+        self
+    }
+
+    /// Let the command palette pause proving and set the max difficulty.
+    pub fn with_control(mut self, control: Option<RuntimeControl>) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// Keep the terminal title set to the node status line.
+    pub fn with_status_title(mut self, status_title: Option<watch::Receiver<String>>) -> Self {
+        self.status_title = status_title;
+        self
+    }
+
+    /// Start with the saved panel layout and save changes to it back to `config_path`.
+    pub fn with_layout(mut self, layout: DashboardLayout, config_path: Option<PathBuf>) -> Self {
+        self.layout = layout;
+        self.config_path = config_path;
+        self
+    }
+}
+
+/// Why the UI loop ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiExit {
+    /// The user quit or the session was shut down
+    Quit,
+    /// The user accepted the update prompt; install the update and restart
+    Update,
 }
 
 /// The different screens in the application.
@@ -49,6 +200,70 @@ pub enum Screen {
     Dashboard(Box<DashboardState>),
 }
 
+impl Screen {
+    /// Whether the screen animates, and so needs redrawing at the full frame rate.
+    fn is_animating(&self) -> bool {
+        matches!(self, Screen::Dashboard(state) if state.is_animating())
+    }
+}
+
+/// Decides when to redraw: as soon as something changes, but at most once per frame, and
+/// otherwise only as often as the clocks and animations on screen need.
+#[derive(Debug)]
+struct FramePacer {
+    frame_interval: Duration,
+    idle_interval: Duration,
+    last_draw: Option<Instant>,
+}
+
+impl FramePacer {
+    fn new(max_fps: u32) -> Self {
+        Self {
+            frame_interval: Duration::from_secs(1) / max_fps.max(1),
+            idle_interval: Duration::from_millis(IDLE_REDRAW_INTERVAL_MS),
+            last_draw: None,
+        }
+    }
+
+    /// Time between redraws when nothing changes
+    fn redraw_interval(&self, animating: bool) -> Duration {
+        if animating {
+            self.frame_interval
+        } else {
+            self.idle_interval.max(self.frame_interval)
+        }
+    }
+
+    /// Whether to redraw at `now`, given whether anything changed since the last frame
+    fn should_draw(&self, now: Instant, changed: bool, animating: bool) -> bool {
+        let Some(last_draw) = self.last_draw else {
+            return true;
+        };
+        let elapsed = now.saturating_duration_since(last_draw);
+        elapsed >= self.frame_interval && (changed || elapsed >= self.redraw_interval(animating))
+    }
+
+    fn drawn(&mut self, now: Instant) {
+        self.last_draw = Some(now);
+    }
+
+    /// How long to wait for input before checking again. Worker events do not wake the input
+    /// poll, so this is never longer than a frame.
+    fn poll_timeout(&self, now: Instant, changed: bool, animating: bool) -> Duration {
+        let Some(last_draw) = self.last_draw else {
+            return Duration::ZERO;
+        };
+        let interval = if changed {
+            self.frame_interval
+        } else {
+            self.redraw_interval(animating)
+        };
+        (last_draw + interval)
+            .saturating_duration_since(now)
+            .min(self.frame_interval)
+    }
+}
+
 /// Application state
 #[derive(Debug)]
 pub struct App {
@@ -73,6 +288,9 @@ pub struct App {
     /// Receives max tasks completion signal.
     max_tasks_shutdown_receiver: broadcast::Receiver<()>,
 
+    /// Receives shutdown requests from outside the UI (e.g. the control API).
+    shutdown_receiver: broadcast::Receiver<()>,
+
     /// Whether to disable background colors
     with_background_color: bool,
 
@@ -84,6 +302,45 @@ pub struct App {
 
     /// Latest version available, if any.
     latest_version: Option<String>,
+
+    /// Point totals, updated in the background.
+    points: PointsTracker,
+
+    /// Network statistics, updated in the background.
+    network_stats: NetworkStatsTracker,
+
+    /// Core each worker slot is pinned to, if core pinning is enabled.
+    pinned_cores: Option<Vec<usize>>,
+
+    /// Rate-limit budget published by the task fetcher.
+    fetch_state: Option<watch::Receiver<TaskFetchState>>,
+
+    /// Dashboard style selected with `--ui`.
+    ui_mode: UiMode,
+
+    /// Panel layout the dashboard starts with.
+    layout: DashboardLayout,
+
+    /// Config file that layout and theme changes are saved to.
+    config_path: Option<PathBuf>,
+
+    /// Theme the dashboard starts with.
+    theme: Theme,
+
+    /// Violated version requirement, shown in a banner.
+    version_notice: Option<VersionNotice>,
+
+    /// Node labels, shown under the title.
+    labels: Labels,
+
+    /// Most redraws per second.
+    max_fps: u32,
+
+    /// Runtime knobs that the command palette pauses and sets the difficulty with.
+    control: Option<RuntimeControl>,
+
+    /// Node status line the terminal title is kept set to.
+    status_title: Option<watch::Receiver<String>>,
 }
 
 impl App {
@@ -102,25 +359,55 @@ impl App {
             environment,
             current_screen: Screen::Splash,
             event_receiver,
+            shutdown_receiver: shutdown_sender.subscribe(),
             shutdown_sender,
             max_tasks_shutdown_receiver,
             with_background_color: ui_config.with_background_color,
             num_threads: ui_config.num_threads,
             version_update_available: ui_config.update_available,
             latest_version: ui_config.latest_version,
+            points: ui_config.points,
+            network_stats: ui_config.network_stats,
+            pinned_cores: ui_config.pinned_cores,
+            fetch_state: ui_config.fetch_state,
+            ui_mode: ui_config.ui_mode,
+            layout: ui_config.layout,
+            config_path: ui_config.config_path,
+            theme: ui_config.theme,
+            version_notice: ui_config.version_notice,
+            labels: ui_config.labels,
+            max_fps: ui_config.max_fps,
+            control: ui_config.control,
+            status_title: ui_config.status_title,
         }
     }
 
-    /// Handles a complete login process, transitioning to the dashboard screen.
-    #[allow(unused)]
-    pub fn login(&mut self) {
-        let node_id = Some(123); // Placeholder for node ID, replace with actual logic to get node ID
-        let ui_config = UIConfig::new(
+    /// UI configuration for a new dashboard screen.
+    fn ui_config(&self) -> UIConfig {
+        UIConfig::new(
             self.with_background_color,
             self.num_threads,
             self.version_update_available,
             self.latest_version.clone(),
-        );
+            self.points.clone(),
+        )
+        .with_network_stats(self.network_stats.clone())
+        .with_pinned_cores(self.pinned_cores.clone())
+        .with_fetch_state(self.fetch_state.clone())
+        .with_ui_mode(self.ui_mode)
+        .with_layout(self.layout.clone(), self.config_path.clone())
+        .with_theme(self.theme.clone())
+        .with_version_notice(self.version_notice.clone())
+        .with_labels(self.labels.clone())
+        .with_max_fps(self.max_fps)
+        .with_control(self.control.clone())
+    }
+
+    /// Handles a complete login process, transitioning to the dashboard screen.
+    #[allow(unused)]
+    pub fn login(&mut self) {
+        let node_id = Some(123); // Placeholder for node ID, replace with actual logic to get node ID
+        let ui_config = self.ui_config();
         let state = DashboardState::new(
             node_id,
             self.environment.clone(),
@@ -132,9 +419,12 @@ impl App {
 }
 
 /// Runs the application UI in a loop, handling events and rendering the appropriate screen.
-pub async fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::io::Result<()> {
+pub async fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::io::Result<UiExit> {
     let splash_start = Instant::now();
     let splash_duration = Duration::from_secs(2);
+    let mut pacer = FramePacer::new(app.max_fps);
+    // Whether anything changed since the last frame
+    let mut changed = true;
 
     // UI event loop
     loop {
@@ -142,7 +432,12 @@ pub async fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::i
         if app.max_tasks_shutdown_receiver.try_recv().is_ok() {
             // Send shutdown signal to workers and exit
             let _ = app.shutdown_sender.send(());
-            return Ok(());
+            return Ok(UiExit::Quit);
+        }
+
+        // Exit if shutdown was requested elsewhere, e.g. through the control API
+        if app.shutdown_receiver.try_recv().is_ok() {
+            return Ok(UiExit::Quit);
         }
 
         // Queue all incoming events for processing
@@ -150,82 +445,441 @@ pub async fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::i
             // Add event to dashboard queue if it exists
             if let Screen::Dashboard(state) = &mut app.current_screen {
                 state.add_event(event);
+                changed = true;
+            }
+        }
+
+        // Update and redraw only when something changed or the screen needs a fresh frame
+        let animating = app.current_screen.is_animating();
+        if pacer.should_draw(Instant::now(), changed, animating) {
+            match &mut app.current_screen {
+                Screen::Splash => {}
+                Screen::Login => {}
+                Screen::Dashboard(state) => {
+                    // Update the dashboard with new tick and metrics
+                    state.update();
+                }
             }
+            terminal.draw(|f| {
+                render(f, &app.current_screen);
+                if app.ui_mode == UiMode::Plain {
+                    plain::flatten(f.buffer_mut());
+                }
+            })?;
+            pacer.drawn(Instant::now());
+            changed = false;
         }
 
-        // Update the state based on the current screen
-        match &mut app.current_screen {
-            Screen::Splash => {}
-            Screen::Login => {}
-            Screen::Dashboard(state) => {
-                // Update the dashboard with new tick and metrics
-                state.update();
+        // Between frames, so the title is not written in the middle of one
+        if let Some(status_title) = &mut app.status_title {
+            if status_title.has_changed().unwrap_or(false) {
+                status_line::set_terminal_title(&status_title.borrow_and_update());
             }
         }
-        terminal.draw(|f| render(f, &app.current_screen))?;
 
         // Handle splash-to-login transition
         if let Screen::Splash = app.current_screen {
             if splash_start.elapsed() >= splash_duration {
-                let ui_config = UIConfig::new(
-                    app.with_background_color,
-                    app.num_threads,
-                    app.version_update_available,
-                    app.latest_version.clone(),
-                );
+                let ui_config = app.ui_config();
                 app.current_screen = Screen::Dashboard(Box::new(DashboardState::new(
                     app.node_id,
                     app.environment.clone(),
                     app.start_time,
                     ui_config,
                 )));
+                changed = true;
                 continue;
             }
         }
 
         // Poll for key events
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                // Skip events that are not KeyEventKind::Press
-                if key.kind == event::KeyEventKind::Release {
-                    continue;
+        let timeout = pacer.poll_timeout(Instant::now(), changed, animating);
+        if event::poll(timeout)? {
+            let event = event::read()?;
+            // Redraw at the new size
+            if let Event::Resize(..) = event {
+                changed = true;
+            }
+            match event {
+                Event::Key(key) => {
+                    changed = true;
+                    // Skip events that are not KeyEventKind::Press
+                    if key.kind == event::KeyEventKind::Release {
+                        continue;
+                    }
+                    if let Some(exit) = handle_key(&mut app, key.code) {
+                        return Ok(exit);
+                    }
+                }
+                Event::Mouse(mouse) => {
+                    let size = terminal.size()?;
+                    let area = Rect::new(0, 0, size.width, size.height);
+                    if let Some(exit) = handle_mouse(&mut app, mouse, area, &mut changed) {
+                        return Ok(exit);
+                    }
                 }
+                _ => {}
+            }
+        }
+    }
+}
 
-                // Handle exit events
-                if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
-                    // Send shutdown signal to workers
+/// Handle a key press, or a click on a key label in the footer. Returns how the UI loop should
+/// end, if it should.
+fn handle_key(app: &mut App, code: KeyCode) -> Option<UiExit> {
+    // An open overlay takes every key, so typing a command cannot quit
+    if let Screen::Dashboard(dashboard_state) = &mut app.current_screen {
+        dashboard_state.command_feedback = None;
+        if dashboard_state.overlay != Overlay::None {
+            if let Some(input) = handle_overlay_key(dashboard_state, code) {
+                let quit = run_command(
+                    dashboard_state,
+                    &input,
+                    app.control.as_ref(),
+                    app.config_path.as_deref(),
+                );
+                if quit {
                     let _ = app.shutdown_sender.send(());
-                    return Ok(());
+                    return Some(UiExit::Quit);
                 }
+            }
+            return None;
+        }
+    }
 
-                match &mut app.current_screen {
-                    Screen::Splash => {
-                        // Any key press will skip the splash screen
-                        if key.code != KeyCode::Esc && key.code != KeyCode::Char('q') {
-                            let ui_config = UIConfig::new(
-                                app.with_background_color,
-                                app.num_threads,
-                                app.version_update_available,
-                                app.latest_version.clone(),
-                            );
-                            app.current_screen = Screen::Dashboard(Box::new(DashboardState::new(
-                                app.node_id,
-                                app.environment.clone(),
-                                app.start_time,
-                                ui_config,
-                            )));
-                        }
+    // Handle exit events
+    if matches!(code, KeyCode::Esc | KeyCode::Char('q')) {
+        // Send shutdown signal to workers
+        let _ = app.shutdown_sender.send(());
+        return Some(UiExit::Quit);
+    }
+
+    match &mut app.current_screen {
+        Screen::Splash => {
+            // Any key press will skip the splash screen
+            let ui_config = app.ui_config();
+            app.current_screen = Screen::Dashboard(Box::new(DashboardState::new(
+                app.node_id,
+                app.environment.clone(),
+                app.start_time,
+                ui_config,
+            )));
+        }
+        Screen::Login => {
+            if code == KeyCode::Enter {
+                app.login();
+            }
+        }
+        Screen::Dashboard(dashboard_state) => {
+            if dashboard_state.show_update_prompt() {
+                match code {
+                    KeyCode::Char('u') => {
+                        // Stop the workers; the session installs the update and restarts
+                        let _ = app.shutdown_sender.send(());
+                        return Some(UiExit::Update);
                     }
-                    Screen::Login => {
-                        if key.code == KeyCode::Enter {
-                            app.login();
+                    KeyCode::Char('x') => {
+                        dashboard_state.update_prompt_dismissed = true;
+                        return None;
+                    }
+                    _ => {}
+                }
+            }
+            match code {
+                KeyCode::Char('?') => dashboard_state.overlay = Overlay::Help,
+                KeyCode::Char(':') => dashboard_state.overlay = Overlay::Palette(String::new()),
+                KeyCode::Char('p') => {
+                    dashboard_state.command_feedback = Some(toggle_pause(app.control.as_ref()));
+                }
+                KeyCode::PageUp => dashboard_state.scroll_logs(true),
+                KeyCode::PageDown => dashboard_state.scroll_logs(false),
+                KeyCode::Char('t') => switch_theme(dashboard_state, app.config_path.as_deref()),
+                code => {
+                    if handle_layout_key(dashboard_state, code) {
+                        if let Some(config_path) = &app.config_path {
+                            let layout = dashboard_state.layout.clone();
+                            update_config(config_path, |config| config.dashboard = layout);
                         }
                     }
-                    Screen::Dashboard(_dashboard_state) => {}
                 }
             }
         }
     }
+    None
+}
+
+/// Handle a mouse event on a terminal of `area`: the wheel scrolls the activity log, a click
+/// selects a panel or presses the footer key under it. Terminals without mouse support never
+/// send these, and every action has a key binding.
+fn handle_mouse(
+    app: &mut App,
+    mouse: MouseEvent,
+    area: Rect,
+    changed: &mut bool,
+) -> Option<UiExit> {
+    let Screen::Dashboard(state) = &mut app.current_screen else {
+        return None;
+    };
+    if state.overlay != Overlay::None {
+        return None;
+    }
+    let position = Position::new(mouse.column, mouse.row);
+    match mouse.kind {
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            if panel_at(area, state, position) == Some(Panel::Logs) {
+                state.scroll_logs(mouse.kind == MouseEventKind::ScrollUp);
+                *changed = true;
+            }
+            None
+        }
+        MouseEventKind::Down(MouseButton::Left) => {
+            *changed = true;
+            let footer = dashboard_areas(area, state).footer;
+            if let Some(code) = footer_key_at(footer, state, position) {
+                return handle_key(app, code);
+            }
+            if let Some(panel) = panel_at(area, state, position) {
+                state.selected_panel = panel;
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Apply a panel layout key binding, returning whether the layout changed.
+fn handle_layout_key(state: &mut DashboardState, code: KeyCode) -> bool {
+    let selected = state.selected_panel;
+    match code {
+        KeyCode::Char('c') => state.layout.toggle_charts(),
+        KeyCode::Char(' ') => state.layout.toggle(selected),
+        KeyCode::Char('<') | KeyCode::Char(',') => state.layout.move_panel(selected, false),
+        KeyCode::Char('>') | KeyCode::Char('.') => state.layout.move_panel(selected, true),
+        KeyCode::Tab => {
+            state.selected_panel = state.layout.next_panel(selected);
+            return false;
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// A command entered in the dashboard's command palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PaletteCommand {
+    /// Stop fetching new tasks once the current one is done
+    Pause,
+    Resume,
+    /// Max difficulty to fetch; `None` restores adaptive difficulty
+    Difficulty(Option<TaskDifficulty>),
+    /// Write the activity log to this file, or to one under `~/.nexus/logs`
+    ExportLogs(Option<PathBuf>),
+    /// Switch to the named theme, or to the next one
+    Theme(Option<String>),
+    Help,
+    Quit,
+}
+
+impl FromStr for PaletteCommand {
+    /// Message to show in the footer
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        let (command, argument) = match input.split_once(char::is_whitespace) {
+            Some((command, argument)) => (command, Some(argument.trim().to_string())),
+            None => (input, None),
+        };
+        match (command.to_ascii_lowercase().as_str(), argument) {
+            ("pause", None) => Ok(Self::Pause),
+            ("resume", None) => Ok(Self::Resume),
+            ("difficulty", Some(level)) if level.eq_ignore_ascii_case("auto") => {
+                Ok(Self::Difficulty(None))
+            }
+            ("difficulty", Some(level)) => crate::validate_difficulty(&level)
+                .map(|difficulty| Self::Difficulty(Some(difficulty)))
+                .ok_or_else(|| tr!("dashboard-command-invalid-difficulty", difficulty = level)),
+            ("export", path) => Ok(Self::ExportLogs(path.map(PathBuf::from))),
+            ("theme", name) => Ok(Self::Theme(name)),
+            ("help", None) => Ok(Self::Help),
+            ("quit" | "q", None) => Ok(Self::Quit),
+            _ => Err(tr!("dashboard-command-unknown", command = input)),
+        }
+    }
+}
+
+/// Apply a key press to the open overlay. Returns the command entered in the palette, if one was.
+fn handle_overlay_key(state: &mut DashboardState, code: KeyCode) -> Option<String> {
+    // Any key closes the help
+    if !matches!(state.overlay, Overlay::Palette(_)) {
+        state.overlay = Overlay::None;
+        return None;
+    }
+    let Overlay::Palette(input) = &mut state.overlay else {
+        return None;
+    };
+    match code {
+        KeyCode::Char(c) => {
+            input.push(c);
+            return None;
+        }
+        KeyCode::Backspace => {
+            input.pop();
+            return None;
+        }
+        KeyCode::Enter | KeyCode::Esc => {}
+        _ => return None,
+    }
+    let entered = std::mem::take(input);
+    state.overlay = Overlay::None;
+    (code == KeyCode::Enter && !entered.trim().is_empty()).then_some(entered)
+}
+
+/// Run a command entered in the palette, showing its outcome in the footer. Returns whether the
+/// user asked to quit.
+fn run_command(
+    state: &mut DashboardState,
+    input: &str,
+    control: Option<&RuntimeControl>,
+    config_path: Option<&Path>,
+) -> bool {
+    let command = match input.parse::<PaletteCommand>() {
+        Ok(command) => command,
+        Err(message) => {
+            state.command_feedback = Some(message);
+            return false;
+        }
+    };
+    let feedback = match (command, control) {
+        (PaletteCommand::Quit, _) => return true,
+        (PaletteCommand::Help, _) => {
+            state.overlay = Overlay::Help;
+            return false;
+        }
+        (PaletteCommand::Pause | PaletteCommand::Resume | PaletteCommand::Difficulty(_), None) => {
+            tr!("dashboard-command-no-control")
+        }
+        (PaletteCommand::Pause, Some(control)) => {
+            control.pause();
+            tr!("dashboard-command-paused")
+        }
+        (PaletteCommand::Resume, Some(control)) => {
+            control.resume();
+            tr!("dashboard-command-resumed")
+        }
+        (PaletteCommand::Difficulty(difficulty), Some(control)) => {
+            control.set_max_difficulty(difficulty);
+            match difficulty {
+                Some(difficulty) => tr!(
+                    "dashboard-command-difficulty",
+                    difficulty = difficulty.as_str_name()
+                ),
+                None => tr!("dashboard-command-difficulty-auto"),
+            }
+        }
+        (PaletteCommand::ExportLogs(path), _) => {
+            let path = path.unwrap_or_else(|| default_export_path(config_path));
+            match export_logs(state, &path) {
+                Ok(()) => tr!("dashboard-command-exported", path = path.display()),
+                Err(e) => tr!("dashboard-command-export-failed", error = e),
+            }
+        }
+        (PaletteCommand::Theme(None), _) => {
+            switch_theme(state, config_path);
+            tr!("dashboard-command-theme", name = state.theme.name)
+        }
+        (PaletteCommand::Theme(Some(name)), _) => {
+            match available_themes(config_path)
+                .into_iter()
+                .find(|theme| theme.name.eq_ignore_ascii_case(&name))
+            {
+                Some(theme) => {
+                    apply_theme(state, theme, config_path);
+                    tr!("dashboard-command-theme", name = state.theme.name)
+                }
+                None => tr!("theme-not-found", name = name),
+            }
+        }
+    };
+    state.command_feedback = Some(feedback);
+    false
+}
+
+/// Pause or resume proving with `P`, returning the message to show in the footer.
+fn toggle_pause(control: Option<&RuntimeControl>) -> String {
+    match control {
+        None => tr!("dashboard-command-no-control"),
+        Some(control) if control.is_paused() => {
+            control.resume();
+            tr!("dashboard-command-resumed")
+        }
+        Some(control) => {
+            control.pause();
+            tr!("dashboard-command-paused")
+        }
+    }
+}
+
+/// File the activity log is exported to when no path is given: `~/.nexus/logs/activity-<time>.log`
+fn default_export_path(config_path: Option<&Path>) -> PathBuf {
+    let file_name = format!(
+        "activity-{}.log",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    match config_path {
+        Some(config_path) => config_path.with_file_name("logs").join(file_name),
+        None => PathBuf::from(file_name),
+    }
+}
+
+/// Write the activity log shown in the dashboard to `path`, oldest event first.
+fn export_logs(state: &DashboardState, path: &Path) -> std::io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    for event in &state.activity_logs {
+        writeln!(file, "{}", event)?;
+    }
+    file.flush()
+}
+
+/// Themes to choose from: the built-in ones and those in the themes directory.
+fn available_themes(config_path: Option<&Path>) -> Vec<Theme> {
+    match config_path {
+        Some(config_path) => theme::available_themes(&theme::themes_dir(config_path)),
+        None => theme::builtin_themes(),
+    }
+}
+
+/// Switch to the next available theme and remember it in the config file.
+fn switch_theme(state: &mut DashboardState, config_path: Option<&Path>) {
+    let themes = available_themes(config_path);
+    let next = themes
+        .iter()
+        .position(|theme| theme.name == state.theme.name)
+        .map_or(0, |index| (index + 1) % themes.len());
+    if let Some(next) = themes.into_iter().nth(next) {
+        apply_theme(state, next, config_path);
+    }
+}
+
+/// Draw the dashboard with `theme` and remember it in the config file.
+fn apply_theme(state: &mut DashboardState, theme: Theme, config_path: Option<&Path>) {
+    if let Some(config_path) = config_path {
+        let name = theme.name.clone();
+        update_config(config_path, |config| config.theme = Some(name));
+    }
+    state.theme = theme;
+}
+
+/// Save a dashboard setting to the config file, if there is one. A setting that cannot be saved
+/// still applies for the rest of the session.
+fn update_config(config_path: &Path, update: impl FnOnce(&mut Config)) {
+    if let Ok(mut config) = Config::load_from_file(config_path) {
+        update(&mut config);
+        let _ = config.save(config_path);
+    }
 }
 
 /// Renders the current screen based on the application state.
@@ -236,3 +890,70 @@ fn render(f: &mut Frame, screen: &Screen) {
         Screen::Dashboard(state) => render_dashboard(f, state),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_palette_command() {
+        let parse = |input: &str| input.parse::<PaletteCommand>();
+        assert_eq!(parse("pause"), Ok(PaletteCommand::Pause));
+        assert_eq!(parse(" Resume "), Ok(PaletteCommand::Resume));
+        assert_eq!(
+            parse("difficulty large"),
+            Ok(PaletteCommand::Difficulty(Some(TaskDifficulty::Large)))
+        );
+        assert_eq!(
+            parse("difficulty auto"),
+            Ok(PaletteCommand::Difficulty(None))
+        );
+        assert_eq!(parse("export"), Ok(PaletteCommand::ExportLogs(None)));
+        assert_eq!(
+            parse("export /tmp/nexus logs.txt"),
+            Ok(PaletteCommand::ExportLogs(Some(PathBuf::from(
+                "/tmp/nexus logs.txt"
+            ))))
+        );
+        assert_eq!(
+            parse("theme dracula"),
+            Ok(PaletteCommand::Theme(Some("dracula".to_string())))
+        );
+        assert!(parse("difficulty huge").is_err());
+        assert!(parse("pause now").is_err());
+        assert!(parse("launch").is_err());
+    }
+
+    #[test]
+    fn test_pacer_redraws_changes_at_most_once_per_frame() {
+        let mut pacer = FramePacer::new(10);
+        let start = Instant::now();
+        assert!(pacer.should_draw(start, false, false));
+        pacer.drawn(start);
+
+        let early = start + Duration::from_millis(50);
+        assert!(!pacer.should_draw(early, true, false));
+        assert_eq!(
+            pacer.poll_timeout(early, true, false),
+            Duration::from_millis(50)
+        );
+        assert!(pacer.should_draw(start + Duration::from_millis(100), true, false));
+    }
+
+    #[test]
+    fn test_pacer_redraws_idle_screen_slowly() {
+        let mut pacer = FramePacer::new(10);
+        let start = Instant::now();
+        pacer.drawn(start);
+
+        let later = start + Duration::from_millis(500);
+        assert!(!pacer.should_draw(later, false, false));
+        assert!(pacer.should_draw(later, false, true));
+        assert!(pacer.should_draw(start + Duration::from_secs(1), false, false));
+        // Waiting for input never outlasts a frame, so worker events are picked up
+        assert_eq!(
+            pacer.poll_timeout(start, false, false),
+            Duration::from_millis(100)
+        );
+    }
+}
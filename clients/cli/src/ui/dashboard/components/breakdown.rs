@@ -0,0 +1,82 @@
+//! Dashboard task breakdown component
+//!
+//! Renders task counts and average durations by task type and difficulty, shown next to the
+//! history charts
+
+use super::super::state::DashboardState;
+use crate::tr;
+use crate::ui::metrics::TaskBreakdown;
+use ratatui::Frame;
+use ratatui::layout::Constraint;
+use ratatui::prelude::{Modifier, Style};
+use ratatui::widgets::{Block, BorderType, Borders, Padding, Row, Table};
+
+/// Render completed vs started tasks and average duration for each task type and difficulty.
+pub fn render_breakdown_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
+    let metrics = &state.zkvm_metrics;
+
+    let section = |title: String| {
+        Row::new(vec![title, String::new(), String::new()]).style(
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let row = |name: &'static str, breakdown: &TaskBreakdown| {
+        Row::new(vec![
+            name.to_string(),
+            format!("{} / {}", breakdown.submitted, breakdown.fetched),
+            breakdown
+                .avg_duration_secs()
+                .map(|secs| format!("{}s", secs))
+                .unwrap_or_else(|| "-".to_string()),
+        ])
+    };
+
+    let mut rows = vec![section(tr!("dashboard-by-type"))];
+    rows.extend(
+        metrics
+            .by_task_type
+            .iter()
+            .map(|(task_type, breakdown)| row(task_type.as_str_name(), breakdown)),
+    );
+    rows.push(Row::new(vec![""; 3]));
+    rows.push(section(tr!("dashboard-by-difficulty")));
+    rows.extend(
+        metrics
+            .by_difficulty
+            .iter()
+            .map(|(difficulty, breakdown)| row(difficulty.as_str_name(), breakdown)),
+    );
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(9),
+            Constraint::Length(6),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            tr!("dashboard-column-task"),
+            tr!("dashboard-column-done"),
+            tr!("dashboard-column-avg"),
+        ])
+        .style(
+            Style::default()
+                .fg(theme.label)
+                .add_modifier(Modifier::UNDERLINED),
+        ),
+    )
+    .block(
+        Block::default()
+            .title(tr!("dashboard-by-task"))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border))
+            .padding(Padding::horizontal(1)),
+    );
+    f.render_widget(table, area);
+}
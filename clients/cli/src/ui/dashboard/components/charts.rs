@@ -0,0 +1,110 @@
+//! Dashboard charts component
+//!
+//! Renders hourly proof history charts, toggled in place of the activity logs
+
+use super::super::history::HourStats;
+use super::super::state::DashboardState;
+use crate::tr;
+use crate::ui::theme::Theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::prelude::Style;
+use ratatui::text::Span;
+use ratatui::widgets::{BarChart, Block, BorderType, Borders, Padding, Sparkline};
+use std::time::Instant;
+
+/// Render proofs per hour, average proof duration, and success rate over the last 24h.
+pub fn render_charts_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
+    let hourly = state.history.hourly(Instant::now());
+
+    let block = Block::default()
+        .title(tr!("dashboard-history"))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.border))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+        ])
+        .split(inner);
+
+    render_proofs_chart(f, chunks[0], &hourly, theme);
+
+    let durations: Vec<u64> = hourly.iter().map(HourStats::avg_duration_secs).collect();
+    let latest_duration = durations.iter().rev().find(|d| **d > 0).copied();
+    let duration_title = match latest_duration {
+        Some(secs) => tr!(
+            "dashboard-avg-proof-duration",
+            duration = format!("{}s", secs)
+        ),
+        None => tr!("dashboard-avg-proof-duration", duration = "-"),
+    };
+    let duration_sparkline = Sparkline::default()
+        .block(Block::default().title(Span::styled(
+            duration_title,
+            Style::default().fg(theme.label),
+        )))
+        .data(&durations)
+        .style(Style::default().fg(theme.info));
+    f.render_widget(duration_sparkline, chunks[1]);
+
+    let success_rates: Vec<u64> = hourly.iter().map(HourStats::success_rate_percent).collect();
+    let window = hourly
+        .iter()
+        .fold(HourStats::default(), |acc, h| HourStats {
+            proofs: acc.proofs + h.proofs,
+            failures: acc.failures + h.failures,
+            total_duration_secs: acc.total_duration_secs + h.total_duration_secs,
+        });
+    let success_title = if window.proofs + window.failures > 0 {
+        tr!(
+            "dashboard-success-rate",
+            rate = format!("{}%", window.success_rate_percent())
+        )
+    } else {
+        tr!("dashboard-success-rate", rate = "-")
+    };
+    let success_sparkline = Sparkline::default()
+        .block(Block::default().title(Span::styled(
+            success_title,
+            Style::default().fg(theme.label),
+        )))
+        .data(&success_rates)
+        .max(100)
+        .style(Style::default().fg(theme.success));
+    f.render_widget(success_sparkline, chunks[2]);
+}
+
+/// Render the proofs per hour bar chart, shrinking bars to fit the available width.
+fn render_proofs_chart(
+    f: &mut Frame,
+    area: ratatui::layout::Rect,
+    hourly: &[HourStats],
+    theme: &Theme,
+) {
+    let total: u64 = hourly.iter().map(|h| h.proofs).sum();
+    let data: Vec<(&str, u64)> = hourly.iter().map(|h| ("", h.proofs)).collect();
+    let bar_width = (area.width / hourly.len().max(1) as u16)
+        .saturating_sub(1)
+        .max(1);
+
+    let chart = BarChart::default()
+        .block(Block::default().title(Span::styled(
+            tr!("dashboard-proofs-per-hour", total = total),
+            Style::default().fg(theme.label),
+        )))
+        .data(&data)
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(theme.primary))
+        .value_style(Style::default().fg(theme.background).bg(theme.primary));
+    f.render_widget(chart, area);
+}
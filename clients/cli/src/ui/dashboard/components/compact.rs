@@ -0,0 +1,73 @@
+//! Compact dashboard
+//!
+//! A status line and the last few activity log lines, for terminals too small for the panels
+//! and for `--ui compact`
+
+use super::super::state::DashboardState;
+use super::header::prover_status;
+use super::logs::log_line;
+use crate::points::format_points;
+use crate::tr;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use std::time::Instant;
+
+/// Render the status line: prover state and progress, node, proofs and points.
+pub fn render_status_line(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let theme = &state.theme;
+    let (state_text, progress_text, color, _) = prover_status(state);
+    let node = match state.node_id {
+        Some(id) => tr!(
+            "dashboard-node",
+            id = crate::redact::redact_id(&id.to_string())
+        ),
+        None => tr!("dashboard-node-disconnected"),
+    };
+    let points = state
+        .points
+        .snapshot(Instant::now())
+        .node_points
+        .map(format_points)
+        .unwrap_or_else(|| "-".to_string());
+    let summary = tr!(
+        "dashboard-compact-summary",
+        proofs = state.zkvm_metrics.tasks_submitted,
+        points = points
+    );
+
+    let line = Line::from(vec![
+        Span::styled(
+            format!("[{}] ", state_text),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(progress_text, Style::default().fg(theme.text)),
+        Span::styled(" | ", Style::default().fg(theme.muted)),
+        Span::styled(node, Style::default().fg(theme.info)),
+        Span::styled(" | ", Style::default().fg(theme.muted)),
+        Span::styled(summary, Style::default().fg(theme.highlight)),
+    ]);
+    f.render_widget(Paragraph::new(line), area);
+}
+
+/// Render the newest activity log lines that fit in `area`, one line each and newest first, like
+/// the activity log panel.
+pub fn render_logs(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let lines: Vec<Line> = state
+        .activity_logs
+        .iter()
+        .filter(|event| event.should_display())
+        .rev()
+        .skip(state.log_scroll)
+        .take(area.height as usize)
+        .map(|event| log_line(event, state))
+        .collect();
+    let paragraph = if lines.is_empty() {
+        Paragraph::new(Line::from(tr!("dashboard-starting-up")))
+    } else {
+        Paragraph::new(lines)
+    };
+    f.render_widget(paragraph, area);
+}
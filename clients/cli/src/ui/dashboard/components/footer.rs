@@ -1,17 +1,39 @@
 //! Dashboard footer component
 //!
-//! Renders footer with quit instructions and version info
+//! Renders footer with key bindings and the selected panel. Each `[KEY]` in the footer can also
+//! be clicked.
 
+use super::super::state::DashboardState;
+use crossterm::event::KeyCode;
 use ratatui::Frame;
-use ratatui::layout::Alignment;
-use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::layout::{Alignment, Position, Rect};
+use ratatui::prelude::{Modifier, Style};
+use ratatui::text::Span;
 use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
 
-/// Render enhanced footer.
-pub fn render_footer(f: &mut Frame, area: ratatui::layout::Rect) {
-    let footer_text = "[Q] Quit | Nexus Prover Dashboard".to_string();
+/// Text shown in the footer at `area`, and whether it is the outcome of a palette command. The
+/// one-row footer of the compact dashboard lists fewer keys.
+fn footer_text(area: Rect, state: &DashboardState) -> (String, bool) {
+    match &state.command_feedback {
+        Some(feedback) => (feedback.clone(), true),
+        None if area.height < 2 => (crate::tr!("dashboard-footer-compact"), false),
+        None => (
+            crate::tr!("dashboard-footer", panel = state.selected_panel.name()),
+            false,
+        ),
+    }
+}
 
-    let footer_color = Color::Cyan;
+/// Render enhanced footer.
+pub fn render_footer(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
+    // The outcome of a palette command replaces the key bindings until the next key press
+    let (footer_text, is_feedback) = footer_text(area, state);
+    let footer_color = if is_feedback {
+        theme.highlight
+    } else {
+        theme.primary
+    };
 
     let footer = Paragraph::new(footer_text)
         .alignment(Alignment::Center)
@@ -22,8 +44,97 @@ pub fn render_footer(f: &mut Frame, area: ratatui::layout::Rect) {
         )
         .block(
             Block::default()
-                .borders(Borders::TOP)
+                .borders(if area.height < 2 {
+                    Borders::NONE
+                } else {
+                    Borders::TOP
+                })
                 .border_type(BorderType::Thick),
         );
     f.render_widget(footer, area);
 }
+
+/// The key whose `[KEY]` label in the footer at `area` was clicked at `position`, if any.
+pub fn footer_key_at(area: Rect, state: &DashboardState, position: Position) -> Option<KeyCode> {
+    let (text, is_feedback) = footer_text(area, state);
+    // The text sits on the last row, below the top border if there is one
+    if is_feedback || area.height == 0 || position.y != area.bottom() - 1 {
+        return None;
+    }
+    let left = area.x
+        + area
+            .width
+            .saturating_sub(Span::raw(text.as_str()).width() as u16)
+            / 2;
+    let column = position.x.checked_sub(left)?;
+    key_targets(&text)
+        .into_iter()
+        .find(|(start, end, _)| (*start..*end).contains(&column))
+        .map(|(_, _, key)| key)
+}
+
+/// Click targets in footer text: each `[KEY]` label with the description after it, as start and
+/// end columns and the key it stands for.
+fn key_targets(text: &str) -> Vec<(u16, u16, KeyCode)> {
+    let width = |s: &str| Span::raw(s).width() as u16;
+    let mut targets = Vec::new();
+    let mut rest = text;
+    let mut offset = 0;
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find(']').map(|close| open + close) else {
+            break;
+        };
+        let label = &rest[open + 1..close];
+        // The target runs until the next label or separator
+        let end = rest[close..]
+            .find(['[', '|'])
+            .map_or(rest.len(), |next| close + next);
+        let start_col = offset + width(&rest[..open]);
+        let end_col = offset + width(&rest[..end]);
+        if let Some(key) = label_key(label) {
+            targets.push((start_col, end_col, key));
+        }
+        offset = end_col;
+        rest = &rest[end..];
+    }
+    targets
+}
+
+/// Key for a footer label: a single character, `Tab`, or the localized name of the space bar.
+fn label_key(label: &str) -> Option<KeyCode> {
+    let mut chars = label.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(KeyCode::Char(c.to_ascii_lowercase())),
+        _ if label == "Tab" => Some(KeyCode::Tab),
+        (Some(_), Some(_)) => Some(KeyCode::Char(' ')),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_targets() {
+        let text = "[Q] Quit | [Tab] Select: Logs | [Space] Show/hide | [<] [>] Move";
+        let targets = key_targets(text);
+        let keys: Vec<KeyCode> = targets.iter().map(|(_, _, key)| *key).collect();
+        assert_eq!(
+            keys,
+            vec![
+                KeyCode::Char('q'),
+                KeyCode::Tab,
+                KeyCode::Char(' '),
+                KeyCode::Char('<'),
+                KeyCode::Char('>'),
+            ]
+        );
+        // "[Q] Quit " is clickable, the separator is not
+        assert_eq!((targets[0].0, targets[0].1), (0, 9));
+        assert_eq!(targets[1].0, 11);
+        // Adjacent labels split the space between them
+        assert_eq!(targets[3].1, targets[4].0);
+        assert_eq!(targets[4].1, text.len() as u16);
+    }
+}
@@ -3,15 +3,133 @@
 //! Renders the title and progress gauge
 
 use super::super::state::DashboardState;
-use crate::events::ProverState;
+use crate::events::{MaintenanceWindow, ProverState};
+use crate::labels::format_labels;
+use crate::tr;
+use crate::ui::UiMode;
+use crate::version::ConstraintType;
+use crate::version::manager::VersionNotice;
 
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
-use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::prelude::{Modifier, Style};
+use ratatui::style::Color;
+use ratatui::text::Line;
 use ratatui::widgets::{Block, BorderType, Borders, Gauge, Paragraph};
+use std::time::Instant;
+
+/// Rows above the title for the version, update and maintenance banners.
+fn banner_rows(state: &DashboardState) -> u16 {
+    state.version_notice.is_some() as u16
+        + state.show_update_prompt() as u16
+        + state.task_fetch_state.maintenance.is_some() as u16
+}
+
+/// Height of the header, including the banners above the title.
+pub fn header_height(state: &DashboardState) -> u16 {
+    4 + banner_rows(state)
+}
+
+/// Render the banner offering to install the new version.
+fn render_update_prompt(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let text = match &state.latest_version {
+        Some(latest) => tr!("dashboard-update-prompt", latest = latest),
+        None => tr!("dashboard-update-prompt-unknown"),
+    };
+    let banner = Paragraph::new(text).alignment(Alignment::Center).style(
+        Style::default()
+            .fg(state.theme.highlight)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_widget(banner, area);
+}
+
+/// Render the banner for orchestrator maintenance, with the time left if known.
+fn render_maintenance_banner(
+    f: &mut Frame,
+    area: ratatui::layout::Rect,
+    state: &DashboardState,
+    window: &MaintenanceWindow,
+) {
+    let mut text = match window.remaining(Instant::now()) {
+        Some(remaining) => tr!(
+            "dashboard-maintenance",
+            remaining = format_countdown(remaining.as_secs())
+        ),
+        None => tr!("dashboard-maintenance-unknown"),
+    };
+    if let Some(message) = &window.message {
+        text = format!("{} - {}", text, message.replace('\n', " "));
+    }
+    let banner = Paragraph::new(text).alignment(Alignment::Center).style(
+        Style::default()
+            .fg(state.theme.warning)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_widget(banner, area);
+}
+
+/// Format seconds as `1h 05m`, `4m 09s` or `9s`.
+fn format_countdown(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Render the banner for a version requirement the session was started despite.
+fn render_version_banner(
+    f: &mut Frame,
+    area: ratatui::layout::Rect,
+    state: &DashboardState,
+    notice: &VersionNotice,
+) {
+    let theme = &state.theme;
+    let message = notice.violation.message.replace('\n', " ");
+    let (text, color) = match notice.violation.constraint_type {
+        ConstraintType::Blocking => (
+            tr!("dashboard-version-overridden", message = message),
+            theme.error,
+        ),
+        ConstraintType::Warning => (
+            tr!("dashboard-version-warning", message = message),
+            theme.warning,
+        ),
+        ConstraintType::Notice => (
+            tr!("dashboard-version-notice", message = message),
+            theme.info,
+        ),
+    };
+    let banner = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(color).add_modifier(Modifier::BOLD));
+    f.render_widget(banner, area);
+}
 
 /// Render enhanced header with title and stage progress.
 pub fn render_header(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
+    // A violated version requirement, the update prompt and maintenance each get a banner row
+    // above the title
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(banner_rows(state)), Constraint::Fill(1)])
+        .split(area);
+    let mut banner_area = chunks[0];
+    banner_area.height = 1;
+    if let Some(notice) = &state.version_notice {
+        render_version_banner(f, banner_area, state, notice);
+        banner_area.y += 1;
+    }
+    if state.show_update_prompt() {
+        render_update_prompt(f, banner_area, state);
+        banner_area.y += 1;
+    }
+    if let Some(window) = &state.task_fetch_state.maintenance {
+        render_maintenance_banner(f, banner_area, state, window);
+    }
+    let area = chunks[1];
     let header_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(2), Constraint::Length(2)])
@@ -21,15 +139,30 @@ pub fn render_header(f: &mut Frame, area: ratatui::layout::Rect, state: &Dashboa
     let version = env!("CARGO_PKG_VERSION");
     let (title_text, title_color) = if state.update_available {
         let text = if let Some(latest) = &state.latest_version {
-            format!("NEXUS PROVER v{} -> {} UPDATE AVAILABLE", version, latest)
+            tr!(
+                "dashboard-title-update-to",
+                version = version,
+                latest = latest
+            )
         } else {
-            format!("NEXUS PROVER v{} - UPDATE AVAILABLE", version)
+            tr!("dashboard-title-update", version = version)
         };
-        (text, Color::LightYellow)
+        (text, theme.highlight)
     } else {
-        (format!("NEXUS PROVER v{}", version), Color::Cyan)
+        (tr!("dashboard-title", version = version), theme.primary)
     };
 
+    let mut title_block = Block::default()
+        .borders(Borders::BOTTOM)
+        .border_type(BorderType::Thick);
+    // Node labels sit on the border under the title
+    if !state.labels.is_empty() {
+        title_block = title_block.title_bottom(
+            Line::from(format!(" {} ", format_labels(&state.labels)))
+                .style(Style::default().fg(theme.muted))
+                .centered(),
+        );
+    }
     let title = Paragraph::new(title_text)
         .alignment(Alignment::Center)
         .style(
@@ -37,62 +170,24 @@ pub fn render_header(f: &mut Frame, area: ratatui::layout::Rect, state: &Dashboa
                 .fg(title_color)
                 .add_modifier(Modifier::BOLD),
         )
-        .block(
-            Block::default()
-                .borders(Borders::BOTTOM)
-                .border_type(BorderType::Thick),
-        );
+        .block(title_block);
     f.render_widget(title, header_chunks[0]);
 
-    // Gauge logic: proving takes priority, then task fetching countdown
-    let (progress_text, gauge_color, progress_percent) = {
-        // Check if we're currently proving
-        match state.current_prover_state() {
-            ProverState::Proving => {
-                // Animated proving gauge - loops every 20 ticks for smooth animation
-                let progress = ((state.tick % 20) as f64 / 20.0 * 100.0) as u16;
-                (
-                    "PROVING - Generating proof".to_string(),
-                    Color::LightGreen,
-                    progress,
-                )
-            }
-            ProverState::Waiting => {
-                // Task fetching countdown logic
-                let fetch_info = &state.task_fetch_info;
-                if !fetch_info.can_fetch_now && fetch_info.backoff_duration_secs > 0 {
-                    let remaining_secs = fetch_info
-                        .backoff_duration_secs
-                        .saturating_sub(fetch_info.time_since_last_fetch_secs);
-                    let progress = if fetch_info.backoff_duration_secs > 0 {
-                        ((fetch_info.time_since_last_fetch_secs as f64
-                            / fetch_info.backoff_duration_secs as f64)
-                            * 100.0) as u16
-                    } else {
-                        100
-                    };
-                    let display_text = if remaining_secs > 0 {
-                        format!("WAITING - Ready for next task ({}s)", remaining_secs)
-                    } else {
-                        "WAITING - Ready for next task".to_string()
-                    };
-                    (display_text, Color::LightBlue, progress.min(100))
-                } else {
-                    (
-                        "WAITING - Ready for next task".to_string(),
-                        Color::LightBlue,
-                        100,
-                    )
-                }
-            }
-        }
-    };
+    let (state_text, progress_text, gauge_color, progress_percent) = prover_status(state);
+
+    // Plain mode has no gauge to show progress, so the state is a text tag
+    if state.ui_mode == UiMode::Plain {
+        let status = Paragraph::new(format!("[{}] {}", state_text, progress_text))
+            .block(Block::default().borders(Borders::BOTTOM));
+        f.render_widget(status, header_chunks[1]);
+        return;
+    }
 
     let gauge = Gauge::default()
         .block(
             Block::default()
                 .borders(Borders::BOTTOM)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(theme.muted)),
         )
         .gauge_style(
             Style::default()
@@ -100,7 +195,98 @@ pub fn render_header(f: &mut Frame, area: ratatui::layout::Rect, state: &Dashboa
                 .add_modifier(Modifier::BOLD),
         )
         .percent(progress_percent)
-        .label(progress_text);
+        .label(format!("{} - {}", state_text, progress_text));
 
     f.render_widget(gauge, header_chunks[1]);
 }
+
+/// State of the prover for the gauge: its name, progress text, color and percent done.
+/// Proving takes priority, then the task fetching countdown.
+pub fn prover_status(state: &DashboardState) -> (String, String, Color, u16) {
+    let theme = &state.theme;
+    // Check if we're currently proving
+    match state.current_prover_state() {
+        ProverState::Proving => {
+            let (progress_text, progress_percent) = match state.proving_progress() {
+                // Estimated progress from recent proofs of the same difficulty
+                Some(progress) if progress.is_overdue() => (tr!("dashboard-proving-overdue"), 99),
+                Some(progress) => {
+                    let percent = (progress.ratio * 100.0) as u16;
+                    let remaining_secs = progress.remaining.as_secs();
+                    (
+                        tr!(
+                            "dashboard-proving-eta",
+                            percent = percent,
+                            eta = format!("{}m {:02}s", remaining_secs / 60, remaining_secs % 60)
+                        ),
+                        percent.min(99),
+                    )
+                }
+                None => {
+                    // No estimate yet: animated proving gauge - loops every 20 ticks
+                    let progress = ((state.tick % 20) as f64 / 20.0 * 100.0) as u16;
+                    (tr!("dashboard-proving"), progress)
+                }
+            };
+            (
+                tr!("dashboard-state-proving"),
+                progress_text,
+                theme.active,
+                progress_percent,
+            )
+        }
+        ProverState::Submitting => (
+            tr!("dashboard-state-submitting"),
+            tr!("dashboard-submitting"),
+            theme.active,
+            100,
+        ),
+        ProverState::Idle | ProverState::Fetching | ProverState::Cooldown => {
+            // Task fetching countdown logic
+            let fetch_info = &state.task_fetch_info;
+            if let Some(window) = &state.task_fetch_state.maintenance {
+                let display_text = match window.remaining(Instant::now()) {
+                    Some(remaining) if remaining.as_secs() > 0 => {
+                        tr!("dashboard-waiting-countdown", seconds = remaining.as_secs())
+                    }
+                    _ => tr!("dashboard-waiting"),
+                };
+                (
+                    tr!("dashboard-state-maintenance"),
+                    display_text,
+                    theme.warning,
+                    0,
+                )
+            } else if !fetch_info.can_fetch_now && fetch_info.backoff_duration_secs > 0 {
+                let remaining_secs = fetch_info
+                    .backoff_duration_secs
+                    .saturating_sub(fetch_info.time_since_last_fetch_secs);
+                let progress = if fetch_info.backoff_duration_secs > 0 {
+                    ((fetch_info.time_since_last_fetch_secs as f64
+                        / fetch_info.backoff_duration_secs as f64)
+                        * 100.0) as u16
+                } else {
+                    100
+                };
+                let display_text = if remaining_secs > 0 {
+                    tr!("dashboard-waiting-countdown", seconds = remaining_secs)
+                } else {
+                    tr!("dashboard-waiting")
+                };
+                (
+                    tr!("dashboard-state-waiting"),
+                    display_text,
+                    theme.info,
+                    progress.min(100),
+                )
+            } else {
+                (
+                    tr!("dashboard-state-waiting"),
+                    tr!("dashboard-waiting"),
+                    theme.info,
+                    100,
+                )
+            }
+        }
+    }
+}
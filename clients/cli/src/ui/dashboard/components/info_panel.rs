@@ -3,94 +3,113 @@
 //! Renders system information panel
 
 use crate::environment::Environment;
+use crate::tr;
 
 use super::super::state::DashboardState;
 use ratatui::Frame;
-use ratatui::prelude::{Color, Style};
+use ratatui::prelude::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Padding, Paragraph, Wrap};
 
 /// Render enhanced info panel with better styling.
 pub fn render_info_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
     let mut info_lines = Vec::new();
 
     // Node information with enhanced formatting
     let node_text = if let Some(id) = state.node_id {
-        format!("Node: {}", id)
+        tr!(
+            "dashboard-node",
+            id = crate::redact::redact_id(&id.to_string())
+        )
     } else {
-        "Node: Disconnected".to_string()
+        tr!("dashboard-node-disconnected")
     };
     info_lines.push(Line::from(vec![Span::styled(
         node_text,
-        Style::default().fg(Color::LightBlue),
+        Style::default().fg(theme.info),
     )]));
 
     // Environment with color coding
     let env_color = match state.environment {
-        Environment::Production => Color::Green,
+        Environment::Production => theme.success,
         Environment::Custom {
             orchestrator_url: _,
-        } => Color::Yellow,
+        } => theme.warning,
     };
     info_lines.push(Line::from(vec![Span::styled(
-        format!("Env: {}", state.environment),
+        tr!("dashboard-environment", environment = state.environment),
         Style::default().fg(env_color),
     )]));
 
     // Version info
     let version = env!("CARGO_PKG_VERSION");
     info_lines.push(Line::from(vec![Span::styled(
-        format!("Version: {}", version),
-        Style::default().fg(Color::Cyan),
+        tr!("dashboard-version", version = version),
+        Style::default().fg(theme.primary),
     )]));
 
     // Uptime with better formatting
     let uptime = state.start_time.elapsed();
-    let uptime_string = if uptime.as_secs() >= 86400 {
+    let uptime_text = if uptime.as_secs() >= 86400 {
         format!(
-            "Uptime: {}d {}h {}m",
+            "{}d {}h {}m",
             uptime.as_secs() / 86400,
             (uptime.as_secs() % 86400) / 3600,
             (uptime.as_secs() % 3600) / 60
         )
     } else if uptime.as_secs() >= 3600 {
         format!(
-            "Uptime: {}h {}m {}s",
+            "{}h {}m {}s",
             uptime.as_secs() / 3600,
             (uptime.as_secs() % 3600) / 60,
             uptime.as_secs() % 60
         )
     } else {
-        format!(
-            "Uptime: {}m {}s",
-            uptime.as_secs() / 60,
-            uptime.as_secs() % 60
-        )
+        format!("{}m {}s", uptime.as_secs() / 60, uptime.as_secs() % 60)
     };
+    let uptime_string = tr!("dashboard-uptime", uptime = uptime_text);
     info_lines.push(Line::from(vec![Span::styled(
         uptime_string,
-        Style::default().fg(Color::LightGreen),
+        Style::default().fg(theme.active),
     )]));
 
     // Threads info
     info_lines.push(Line::from(vec![Span::styled(
-        format!("Threads: {}", state.num_threads),
-        Style::default().fg(Color::LightYellow),
+        tr!("dashboard-threads", threads = state.num_threads),
+        Style::default().fg(theme.highlight),
     )]));
 
     // Total memory
     info_lines.push(Line::from(vec![Span::styled(
-        format!("Memory: {:.1} GB", state.total_ram_gb),
-        Style::default().fg(Color::LightCyan),
+        tr!(
+            "dashboard-memory",
+            gigabytes = format!("{:.1}", state.total_ram_gb)
+        ),
+        Style::default().fg(theme.info),
     )]));
 
+    // Detected GPUs, one line each
+    if state.gpus.is_empty() {
+        info_lines.push(Line::from(vec![Span::styled(
+            tr!("dashboard-gpu-none"),
+            Style::default().fg(theme.label),
+        )]));
+    }
+    for gpu in &state.gpus {
+        info_lines.push(Line::from(vec![Span::styled(
+            tr!("dashboard-gpu", gpu = gpu),
+            Style::default().fg(theme.accent),
+        )]));
+    }
+
     // Note: Task ID removed from system info as requested
 
     let info_block = Block::default()
-        .title("SYSTEM INFO")
+        .title(tr!("dashboard-system-info"))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.border))
         .padding(Padding::uniform(1));
 
     let info_paragraph = Paragraph::new(info_lines)
@@ -4,15 +4,17 @@
 
 use super::super::state::DashboardState;
 use super::super::utils::{clean_http_error_message, format_compact_timestamp, get_worker_color};
-use crate::events::EventType;
+use crate::events::{Event as WorkerEvent, EventType};
 use crate::logging::LogLevel;
+use crate::ui::UiMode;
 use ratatui::Frame;
-use ratatui::prelude::{Color, Style};
+use ratatui::prelude::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Padding, Paragraph, Wrap};
 
 /// Render enhanced logs panel with better event formatting.
 pub fn render_logs_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
     // Calculate how many log lines can fit in the available area
     // Account for borders and padding (subtract 3 for top/bottom borders + padding)
     let max_logs = (area.height.saturating_sub(3)) as usize;
@@ -23,48 +25,77 @@ pub fn render_logs_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
         .iter()
         .filter(|event| event.should_display())
         .rev()
+        .skip(state.log_scroll)
         .take(log_count) // Show as many logs as fit in terminal
-        .map(|event| {
-            let status_icon = match (event.event_type, event.log_level) {
-                (EventType::Success, _) => "✅",
-                (EventType::Error, LogLevel::Error) => "❌",
-                (EventType::Error, LogLevel::Warn) => "",
-                (EventType::Error, _) => "❌",
-                (EventType::Refresh, _) => "",
-                (EventType::Waiting, _) => "",
-                (EventType::StateChange, _) => "", // StateChange events shouldn't be displayed, but add for completeness
-            };
-
-            let worker_color = get_worker_color(&event.worker);
-            let compact_time = format_compact_timestamp(&event.timestamp);
-            let cleaned_msg = clean_http_error_message(&event.msg);
-
-            // Don't truncate - let ratatui handle wrapping naturally
-            Line::from(vec![
-                Span::raw(format!("{} ", status_icon)),
-                Span::styled(
-                    format!("{} ", compact_time),
-                    Style::default().fg(Color::DarkGray),
-                ),
-                Span::styled(cleaned_msg, Style::default().fg(worker_color)),
-            ])
-        })
+        .map(|event| log_line(event, state))
         .collect();
 
     let log_paragraph = if log_lines.is_empty() {
-        Paragraph::new(vec![Line::from("Starting up...")])
+        Paragraph::new(vec![Line::from(crate::tr!("dashboard-starting-up"))])
     } else {
         Paragraph::new(log_lines)
     };
 
+    let title = if state.log_scroll > 0 {
+        crate::tr!("dashboard-activity-log-scrolled", lines = state.log_scroll)
+    } else {
+        crate::tr!("dashboard-activity-log")
+    };
     let logs_block = Block::default()
-        .title("ACTIVITY LOG")
+        .title(title)
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.border))
         .padding(Padding::uniform(1));
 
     let log_widget = log_paragraph.block(logs_block).wrap(Wrap { trim: true });
 
     f.render_widget(log_widget, area);
 }
+
+/// An activity log line: status marker, time and message.
+pub fn log_line(event: &WorkerEvent, state: &DashboardState) -> Line<'static> {
+    let theme = &state.theme;
+    let status_icon = if state.ui_mode == UiMode::Plain {
+        plain_status_tag(event.event_type, event.log_level)
+    } else {
+        status_icon(event.event_type, event.log_level)
+    };
+
+    let worker_color = get_worker_color(&event.worker, theme);
+    let compact_time = format_compact_timestamp(&event.timestamp);
+    let cleaned_msg = clean_http_error_message(&event.msg);
+
+    // Don't truncate - let ratatui handle wrapping naturally
+    Line::from(vec![
+        Span::raw(format!("{} ", status_icon)),
+        Span::styled(
+            format!("{} ", compact_time),
+            Style::default().fg(theme.muted),
+        ),
+        Span::styled(cleaned_msg, Style::default().fg(worker_color)),
+    ])
+}
+
+/// Emoji marker for an activity log line.
+fn status_icon(event_type: EventType, log_level: LogLevel) -> &'static str {
+    match (event_type, log_level) {
+        (EventType::Success, _) => "✅",
+        (EventType::Error, LogLevel::Error) => "❌",
+        (EventType::Error, LogLevel::Warn) => "",
+        (EventType::Error, _) => "❌",
+        (EventType::Refresh, _) => "",
+        (EventType::Waiting, _) => "",
+        (EventType::StateChange, _) => "", // StateChange events shouldn't be displayed, but add for completeness
+    }
+}
+
+/// Text tag replacing the emoji marker in `--ui plain`.
+fn plain_status_tag(event_type: EventType, log_level: LogLevel) -> &'static str {
+    match (event_type, log_level) {
+        (EventType::Success, _) => "[OK]",
+        (EventType::Error, LogLevel::Warn) => "[WARN]",
+        (EventType::Error, _) => "[ERROR]",
+        (EventType::Refresh, _) | (EventType::Waiting, _) | (EventType::StateChange, _) => "[INFO]",
+    }
+}
@@ -1,28 +1,30 @@
 //! Dashboard metrics components
 //!
-//! Renders system and zkVM metrics
+//! Renders system, zkVM, points, rate-limit and network metrics
 
 use super::super::state::DashboardState;
 use super::super::utils::format_compact_timestamp;
+use crate::consts::cli_consts::rate_limiting;
+use crate::network::NetworkHealth;
+use crate::points::format_points;
+use crate::thermal::ThermalState;
+use crate::tr;
+use crate::ui::theme::Theme;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::prelude::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Gauge, Padding, Paragraph, Wrap};
+use ratatui::widgets::{Block, BorderType, Borders, Gauge, Padding, Paragraph, Sparkline, Wrap};
+use std::time::Instant;
 
-/// Render enhanced metrics section with better layout.
-pub fn render_metrics_section(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
-    let metrics_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(area);
-
-    render_system_metrics(f, metrics_chunks[0], state);
-    render_zkvm_metrics(f, metrics_chunks[1], state);
+/// Gray "Label: " prefix of a metric line.
+fn label(text: String, theme: &Theme) -> Span<'static> {
+    Span::styled(format!("{}: ", text), Style::default().fg(theme.label))
 }
 
 /// Render enhanced system metrics with better gauges.
 pub fn render_system_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
     let metrics = &state.system_metrics;
 
     // Responsive gauge layout - each gauge gets equal space
@@ -35,20 +37,50 @@ pub fn render_system_metrics(f: &mut Frame, area: ratatui::layout::Rect, state:
         ])
         .split(area);
 
+    // Show the worker → core mapping when pinned
+    let cpu_title = match &state.pinned_cores {
+        Some(cores) => tr!(
+            "dashboard-cpu-usage-pinned",
+            cores = cores
+                .iter()
+                .enumerate()
+                .map(|(worker, core)| format!("W{}→{}", worker + 1, core))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        None => tr!("dashboard-cpu-usage"),
+    };
+    // Temperature and throttling, with `--thermal-limit`
+    let cpu_title = match state.thermal {
+        Some(thermal) => {
+            let celsius = format!("{:.0}", thermal.celsius);
+            let temperature = match thermal.state {
+                ThermalState::Normal => tr!("dashboard-temperature", celsius = celsius),
+                ThermalState::Throttled => {
+                    tr!("dashboard-temperature-throttled", celsius = celsius)
+                }
+                ThermalState::Cooling => tr!("dashboard-temperature-cooling", celsius = celsius),
+            };
+            format!("{} - {}", cpu_title, temperature)
+        }
+        None => cpu_title,
+    };
+    let cpu_color = match state.thermal.map(|thermal| thermal.state) {
+        Some(ThermalState::Throttled) => theme.warning,
+        Some(ThermalState::Cooling) => theme.error,
+        _ => metrics.cpu_color(theme),
+    };
+
     // CPU gauge with enhanced styling
     let cpu_gauge = Gauge::default()
         .block(
             Block::default()
-                .title("CPU Usage")
+                .title(cpu_title)
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(metrics.cpu_color())),
-        )
-        .gauge_style(
-            Style::default()
-                .fg(metrics.cpu_color())
-                .add_modifier(Modifier::BOLD),
+                .border_style(Style::default().fg(cpu_color)),
         )
+        .gauge_style(Style::default().fg(cpu_color).add_modifier(Modifier::BOLD))
         .percent((metrics.cpu_percent as u16).min(100))
         .label(format!("{:.1}%", metrics.cpu_percent));
 
@@ -56,14 +88,14 @@ pub fn render_system_metrics(f: &mut Frame, area: ratatui::layout::Rect, state:
     let ram_gauge = Gauge::default()
         .block(
             Block::default()
-                .title("RAM Usage")
+                .title(tr!("dashboard-ram-usage"))
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(metrics.ram_color())),
+                .border_style(Style::default().fg(metrics.ram_color(theme))),
         )
         .gauge_style(
             Style::default()
-                .fg(metrics.ram_color())
+                .fg(metrics.ram_color(theme))
                 .add_modifier(Modifier::BOLD),
         )
         .percent((metrics.ram_ratio() * 100.0) as u16)
@@ -77,16 +109,12 @@ pub fn render_system_metrics(f: &mut Frame, area: ratatui::layout::Rect, state:
     let peak_gauge = Gauge::default()
         .block(
             Block::default()
-                .title("Peak RAM")
+                .title(tr!("dashboard-peak-ram"))
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::LightBlue)),
-        )
-        .gauge_style(
-            Style::default()
-                .fg(Color::LightBlue)
-                .add_modifier(Modifier::BOLD),
+                .border_style(Style::default().fg(theme.info)),
         )
+        .gauge_style(Style::default().fg(theme.info).add_modifier(Modifier::BOLD))
         .percent((metrics.peak_ram_ratio() * 100.0) as u16)
         .label(metrics.format_peak_ram());
 
@@ -97,40 +125,27 @@ pub fn render_system_metrics(f: &mut Frame, area: ratatui::layout::Rect, state:
 
 /// Render enhanced zkVM metrics panel.
 pub fn render_zkvm_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
     let metrics = &state.zkvm_metrics;
     let mut zkvm_lines = Vec::new();
 
-    // // Points display - most prominent metric
-    // TODO: Add points display back in when we have a way to get points
-    // zkvm_lines.push(Line::from(vec![
-    //     Span::styled("Points: ", Style::default().fg(Color::Gray)),
-    //     Span::styled(
-    //         metrics.format_points(),
-    //         Style::default()
-    //             .fg(Color::LightYellow)
-    //             .add_modifier(Modifier::BOLD),
-    //     ),
-    // ]));
-
     // TODO: Add zkVM KHz display here, once we have a way to measure it locally.
 
     // Tasks statistics
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Tasks: ", Style::default().fg(Color::Gray)),
+        label(tr!("dashboard-tasks"), theme),
         Span::styled(
             format!("{}", metrics.tasks_fetched),
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         ),
     ]));
 
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Completed: ", Style::default().fg(Color::Gray)),
+        label(tr!("dashboard-completed"), theme),
         Span::styled(
             format!("{} / {}", metrics.tasks_submitted, metrics.tasks_fetched),
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.success)
                 .add_modifier(Modifier::BOLD),
         ),
     ]));
@@ -138,48 +153,80 @@ pub fn render_zkvm_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &D
     // Success rate with color coding
     let success_text = format!("{:.1}%", metrics.success_rate());
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Success: ", Style::default().fg(Color::Gray)),
+        label(tr!("dashboard-success"), theme),
         Span::styled(
             success_text,
             Style::default()
-                .fg(metrics.success_rate_color())
+                .fg(metrics.success_rate_color(theme))
                 .add_modifier(Modifier::BOLD),
         ),
     ]));
 
     // Runtime information
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Runtime: ", Style::default().fg(Color::Gray)),
-        Span::styled(metrics.format_runtime(), Style::default().fg(Color::Cyan)),
+        label(tr!("dashboard-runtime"), theme),
+        Span::styled(metrics.format_runtime(), Style::default().fg(theme.primary)),
     ]));
 
     // Last task info
     let status_color = match metrics.last_task_status.as_str() {
-        "Success" => Color::Green,
-        "Failed" => Color::Red,
-        _ => Color::Gray,
+        "Success" => theme.success,
+        "Failed" => theme.error,
+        _ => theme.label,
+    };
+    let status_text = match metrics.last_task_status.as_str() {
+        "None" => tr!("dashboard-task-status-none"),
+        "Proved" => tr!("dashboard-task-status-proved"),
+        "Proof Failed" => tr!("dashboard-task-status-proof-failed"),
+        "Success" => tr!("dashboard-task-status-success"),
+        "Submit Failed" => tr!("dashboard-task-status-submit-failed"),
+        other => other.to_string(),
     };
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Last: ", Style::default().fg(Color::Gray)),
-        Span::styled(&metrics.last_task_status, Style::default().fg(status_color)),
+        label(tr!("dashboard-last"), theme),
+        Span::styled(status_text, Style::default().fg(status_color)),
     ]));
 
+    if metrics.worker_restarts > 0 {
+        let restarts_text = if metrics.degraded {
+            tr!(
+                "dashboard-restarts-degraded",
+                count = metrics.worker_restarts
+            )
+        } else {
+            format!("{}", metrics.worker_restarts)
+        };
+        zkvm_lines.push(Line::from(vec![
+            label(tr!("dashboard-restarts"), theme),
+            Span::styled(
+                restarts_text,
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(if metrics.degraded {
+                        Modifier::BOLD
+                    } else {
+                        Modifier::empty()
+                    }),
+            ),
+        ]));
+    }
+
     // Show timestamp of last successful submission instead of duration
     let last_submission_text = if let Some(timestamp) = state.last_submission_timestamp() {
         format_compact_timestamp(timestamp)
     } else {
-        "Never".to_string()
+        tr!("dashboard-never")
     };
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Last Proof: ", Style::default().fg(Color::Gray)),
-        Span::styled(last_submission_text, Style::default().fg(Color::Yellow)),
+        label(tr!("dashboard-last-proof"), theme),
+        Span::styled(last_submission_text, Style::default().fg(theme.warning)),
     ]));
 
     let zkvm_block = Block::default()
-        .title("zkVM STATS")
+        .title(tr!("dashboard-zkvm-stats"))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.border))
         .padding(Padding::uniform(1));
 
     let zkvm_paragraph = Paragraph::new(zkvm_lines)
@@ -187,3 +234,285 @@ pub fn render_zkvm_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &D
         .wrap(Wrap { trim: true });
     f.render_widget(zkvm_paragraph, area);
 }
+
+/// Render point totals fetched from the orchestrator with a 24-hour sparkline.
+pub fn render_points_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
+    let snapshot = state.points.snapshot(Instant::now());
+
+    let block = Block::default()
+        .title(tr!("dashboard-points"))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.highlight))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Fill(1)])
+        .split(inner);
+
+    let total_text = match snapshot.node_points {
+        Some(points) => format_points(points),
+        None if snapshot.unavailable => tr!("dashboard-points-unavailable"),
+        None => tr!("dashboard-fetching"),
+    };
+    let wallet_text = snapshot
+        .wallet_points
+        .map(format_points)
+        .unwrap_or_else(|| "-".to_string());
+    let points_lines = vec![
+        Line::from(vec![
+            label(tr!("dashboard-points-total"), theme),
+            Span::styled(
+                total_text,
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            label(tr!("dashboard-points-session"), theme),
+            Span::styled(
+                format!("+{}", format_points(snapshot.session_points)),
+                Style::default().fg(theme.success),
+            ),
+        ]),
+        Line::from(vec![
+            label(tr!("dashboard-points-wallet"), theme),
+            Span::styled(wallet_text, Style::default().fg(theme.text)),
+        ]),
+    ];
+    f.render_widget(Paragraph::new(points_lines), chunks[0]);
+
+    let sparkline = Sparkline::default()
+        .block(Block::default().title(Span::styled(
+            tr!("dashboard-last-24h"),
+            Style::default().fg(theme.label),
+        )))
+        .data(&snapshot.hourly)
+        .style(Style::default().fg(theme.highlight));
+    f.render_widget(sparkline, chunks[1]);
+}
+
+/// Render the network statistics: active provers, the node's rank and the pending task mix.
+pub fn render_leaderboard_panel(
+    f: &mut Frame,
+    area: ratatui::layout::Rect,
+    state: &DashboardState,
+) {
+    let theme = &state.theme;
+    let block = Block::default()
+        .title(tr!("dashboard-leaderboard"))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.highlight))
+        .padding(Padding::horizontal(1));
+
+    let Some(stats) = state.network_stats.latest() else {
+        let message = if state.network_stats.unavailable() {
+            tr!("dashboard-leaderboard-unavailable")
+        } else {
+            tr!("dashboard-fetching")
+        };
+        let fetching =
+            Paragraph::new(Span::styled(message, Style::default().fg(theme.label))).block(block);
+        f.render_widget(fetching, area);
+        return;
+    };
+
+    let rank_text = match (stats.rank, stats.top_percent()) {
+        (Some(rank), Some(top)) => format!("#{} (top {:.1}%)", format_points(rank), top),
+        _ => tr!("dashboard-leaderboard-unranked"),
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            label(tr!("dashboard-leaderboard-provers"), theme),
+            Span::styled(
+                format_points(stats.active_provers),
+                Style::default().fg(theme.text),
+            ),
+        ]),
+        Line::from(vec![
+            label(tr!("dashboard-leaderboard-rank"), theme),
+            Span::styled(
+                rank_text,
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(label(tr!("dashboard-leaderboard-supply"), theme)),
+    ];
+    lines.extend(stats.supply_mix().into_iter().map(|(difficulty, share)| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {} ", difficulty),
+                Style::default().fg(theme.label),
+            ),
+            Span::styled(format!("{:.0}%", share), Style::default().fg(theme.text)),
+        ])
+    }));
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Render the task fetch budget: requests used vs allowed, next fetch ETA and recent 429s.
+pub fn render_rate_limit_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
+    let fetch_state = &state.task_fetch_state;
+    let now = Instant::now();
+
+    let block = Block::default()
+        .title(tr!("dashboard-rate-limit"))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.accent))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Fill(1)])
+        .split(inner);
+
+    // Requests used in the current window vs the allowed budget
+    let used = fetch_state.requests_in_window;
+    let allowed = fetch_state.max_requests_per_window;
+    let ratio = if allowed > 0 {
+        (used as f64 / allowed as f64).min(1.0)
+    } else {
+        0.0
+    };
+    let budget_color = match ratio {
+        r if r >= 0.9 => theme.error,
+        r if r >= 0.6 => theme.warning,
+        _ => theme.success,
+    };
+    let budget_gauge = Gauge::default()
+        .gauge_style(Style::default().fg(budget_color))
+        .ratio(ratio)
+        .label(tr!(
+            "dashboard-rate-limit-budget",
+            used = used,
+            allowed = allowed,
+            window = fetch_state.window.as_secs()
+        ));
+    f.render_widget(budget_gauge, chunks[0]);
+
+    let remaining = fetch_state.time_until_next_fetch(now);
+    let (next_text, next_color) = if remaining.is_zero() {
+        (tr!("dashboard-next-fetch-now"), theme.success)
+    } else {
+        (
+            tr!("dashboard-next-fetch-in", seconds = remaining.as_secs() + 1),
+            theme.warning,
+        )
+    };
+
+    let recent_count = fetch_state.rate_limits_within(now, rate_limiting::rate_limit_history());
+    let last_text = match fetch_state.rate_limits.last() {
+        Some(hit) => {
+            let time = hit.timestamp.split(' ').nth(1).unwrap_or(&hit.timestamp);
+            match hit.retry_after_secs {
+                Some(secs) => tr!("dashboard-last-429-retry", time = time, seconds = secs),
+                None => time.to_string(),
+            }
+        }
+        None => tr!("dashboard-never"),
+    };
+
+    let lines = vec![
+        Line::from(vec![
+            label(tr!("dashboard-next-fetch"), theme),
+            Span::styled(
+                next_text,
+                Style::default().fg(next_color).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            label(tr!("dashboard-rate-limited-hour"), theme),
+            Span::styled(
+                recent_count.to_string(),
+                Style::default().fg(if recent_count > 0 {
+                    theme.error
+                } else {
+                    theme.success
+                }),
+            ),
+        ]),
+        Line::from(vec![
+            label(tr!("dashboard-last-429"), theme),
+            Span::styled(last_text, Style::default().fg(theme.text)),
+        ]),
+    ];
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), chunks[1]);
+}
+
+/// Render orchestrator round-trip times and errors, and whether trouble looks local or remote.
+pub fn render_network_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let theme = &state.theme;
+    let stats = state.network.stats(Instant::now());
+
+    let block = Block::default()
+        .title(tr!("dashboard-network"))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.accent))
+        .padding(Padding::horizontal(1));
+
+    let (health_text, health_color) = match stats.health() {
+        NetworkHealth::Unknown => (tr!("dashboard-network-health-unknown"), theme.text),
+        NetworkHealth::Good => (tr!("dashboard-network-health-good"), theme.success),
+        NetworkHealth::Slow => (tr!("dashboard-network-health-slow"), theme.warning),
+        NetworkHealth::LocalNetwork => (tr!("dashboard-network-health-local"), theme.error),
+        NetworkHealth::Server => (tr!("dashboard-network-health-server"), theme.error),
+    };
+    let rtt_text = match (stats.rtt_avg_ms, stats.rtt_last_ms) {
+        (Some(avg), Some(last)) => tr!("dashboard-network-rtt-value", avg = avg, last = last),
+        _ => "-".to_string(),
+    };
+    let errors_color = match stats.error_rate {
+        r if r >= 0.2 => theme.error,
+        r if r > 0.0 => theme.warning,
+        _ => theme.success,
+    };
+
+    let lines = vec![
+        Line::from(vec![
+            label(tr!("dashboard-network-status"), theme),
+            Span::styled(
+                health_text,
+                Style::default()
+                    .fg(health_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            label(tr!("dashboard-network-rtt"), theme),
+            Span::styled(rtt_text, Style::default().fg(theme.text)),
+        ]),
+        Line::from(vec![
+            label(
+                tr!("dashboard-network-errors", minutes = stats.window_secs / 60),
+                theme,
+            ),
+            Span::styled(
+                tr!(
+                    "dashboard-network-errors-value",
+                    rate = format!("{:.0}", stats.error_rate * 100.0),
+                    client = stats.client_errors,
+                    server = stats.server_errors,
+                    network = stats.network_errors
+                ),
+                Style::default().fg(errors_color),
+            ),
+        ]),
+    ];
+    f.render_widget(
+        Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+        area,
+    );
+}
@@ -2,8 +2,12 @@
 //!
 //! Contains all individual rendering components
 
+pub mod breakdown;
+pub mod charts;
+pub mod compact;
 pub mod footer;
 pub mod header;
 pub mod info_panel;
 pub mod logs;
 pub mod metrics;
+pub mod overlay;
@@ -0,0 +1,124 @@
+//! Dashboard overlays
+//!
+//! Renders the key binding help and the command palette over the dashboard
+
+use super::super::state::{DashboardState, Overlay};
+use crate::tr;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::prelude::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+
+/// Key bindings listed in the help, with the locale key of their description
+const KEY_BINDINGS: &[(&str, &str)] = &[
+    ("Q / Esc", "dashboard-help-quit"),
+    ("?", "dashboard-help-help"),
+    (":", "dashboard-help-palette"),
+    ("P", "dashboard-help-pause"),
+    ("T", "dashboard-help-theme"),
+    ("C", "dashboard-help-charts"),
+    ("Tab", "dashboard-help-select"),
+    ("Space", "dashboard-help-toggle"),
+    ("< >", "dashboard-help-move"),
+    ("U / X", "dashboard-help-update"),
+    ("PgUp / PgDn", "dashboard-help-scroll"),
+];
+
+/// Palette commands listed in the help, with the locale key of their description
+const COMMANDS: &[(&str, &str)] = &[
+    ("pause", "dashboard-help-command-pause"),
+    ("resume", "dashboard-help-command-resume"),
+    (
+        "difficulty <LEVEL|auto>",
+        "dashboard-help-command-difficulty",
+    ),
+    ("export [FILE]", "dashboard-help-command-export"),
+    ("theme [NAME]", "dashboard-help-command-theme"),
+    ("quit", "dashboard-help-command-quit"),
+];
+
+/// Render the open overlay, if any.
+pub fn render_overlay(f: &mut Frame, state: &DashboardState) {
+    match &state.overlay {
+        Overlay::None => {}
+        Overlay::Help => render_help(f, state),
+        Overlay::Palette(input) => render_palette(f, state, input),
+    }
+}
+
+/// `width` x `height` area in the middle of `area`, clipped to it.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+fn render_help(f: &mut Frame, state: &DashboardState) {
+    let theme = &state.theme;
+    let heading = |text: String| {
+        Line::from(Span::styled(
+            text,
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    let entry = |keys: &str, description: &'static str| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {:<26}", keys),
+                Style::default().fg(theme.highlight),
+            ),
+            Span::styled(tr!(description), Style::default().fg(theme.text)),
+        ])
+    };
+
+    let mut lines = vec![heading(tr!("dashboard-help-keys"))];
+    lines.extend(
+        KEY_BINDINGS
+            .iter()
+            .map(|(keys, description)| entry(keys, description)),
+    );
+    lines.push(Line::default());
+    lines.push(heading(tr!("dashboard-help-commands")));
+    lines.extend(
+        COMMANDS
+            .iter()
+            .map(|(command, description)| entry(command, description)),
+    );
+
+    let area = centered(f.area(), 76, lines.len() as u16 + 2);
+    let help = Paragraph::new(lines).block(
+        Block::default()
+            .title(tr!("dashboard-help-title"))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.primary)),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(help, area);
+}
+
+fn render_palette(f: &mut Frame, state: &DashboardState, input: &str) {
+    let theme = &state.theme;
+    let area = centered(f.area(), 60, 3);
+    let palette = Paragraph::new(Line::from(vec![
+        Span::styled(":", Style::default().fg(theme.highlight)),
+        Span::styled(input.to_string(), Style::default().fg(theme.text)),
+        Span::styled("_", Style::default().fg(theme.muted)),
+    ]))
+    .block(
+        Block::default()
+            .title(tr!("dashboard-palette-title"))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.primary)),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(palette, area);
+}
@@ -0,0 +1,128 @@
+//! Rolling per-hour proof history backing the dashboard charts
+
+use crate::consts::cli_consts::CHART_HISTORY_HOURS;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Proof statistics for a single hour.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HourStats {
+    /// Proofs submitted successfully.
+    pub proofs: u64,
+    /// Proofs that failed to generate or submit.
+    pub failures: u64,
+    /// Sum of task durations of successful proofs, in seconds.
+    pub total_duration_secs: u64,
+}
+
+impl HourStats {
+    /// Average task duration of successful proofs, in seconds.
+    pub fn avg_duration_secs(&self) -> u64 {
+        self.total_duration_secs
+            .checked_div(self.proofs)
+            .unwrap_or(0)
+    }
+
+    /// Share of attempted proofs that succeeded, as a whole percentage.
+    pub fn success_rate_percent(&self) -> u64 {
+        (self.proofs * 100)
+            .checked_div(self.proofs + self.failures)
+            .unwrap_or(0)
+    }
+}
+
+/// In-memory ring buffer of hourly proof statistics for the current session.
+#[derive(Debug)]
+pub struct ProofHistory {
+    started_at: Instant,
+    /// (hours since `started_at`, stats) for hours with any activity, oldest first
+    buckets: VecDeque<(u64, HourStats)>,
+}
+
+impl ProofHistory {
+    pub fn new(started_at: Instant) -> Self {
+        Self {
+            started_at,
+            buckets: VecDeque::with_capacity(CHART_HISTORY_HOURS),
+        }
+    }
+
+    /// Record a successful proof that took `duration_secs` from fetch to submission.
+    pub fn record_proof(&mut self, now: Instant, duration_secs: u64) {
+        let bucket = self.bucket_mut(now);
+        bucket.proofs += 1;
+        bucket.total_duration_secs += duration_secs;
+    }
+
+    /// Record a failed proof generation or submission.
+    pub fn record_failure(&mut self, now: Instant) {
+        self.bucket_mut(now).failures += 1;
+    }
+
+    /// Stats for each of the last [`CHART_HISTORY_HOURS`] hours, oldest first.
+    pub fn hourly(&self, now: Instant) -> Vec<HourStats> {
+        let current = self.hour_index(now);
+        let first = (current + 1).saturating_sub(CHART_HISTORY_HOURS as u64);
+        let mut hourly = vec![HourStats::default(); CHART_HISTORY_HOURS];
+        for (hour, stats) in &self.buckets {
+            if (first..=current).contains(hour) {
+                let offset = CHART_HISTORY_HOURS as u64 - 1 - (current - hour);
+                hourly[offset as usize] = *stats;
+            }
+        }
+        hourly
+    }
+
+    fn hour_index(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.started_at).as_secs() / 3600
+    }
+
+    fn bucket_mut(&mut self, now: Instant) -> &mut HourStats {
+        let hour = self.hour_index(now);
+        if self.buckets.back().is_none_or(|(last, _)| *last != hour) {
+            self.buckets.push_back((hour, HourStats::default()));
+            while self.buckets.len() > CHART_HISTORY_HOURS {
+                self.buckets.pop_front();
+            }
+        }
+        let (_, stats) = self.buckets.back_mut().expect("bucket was just ensured");
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_hourly_stats() {
+        let start = Instant::now();
+        let mut history = ProofHistory::new(start);
+        history.record_proof(start, 100);
+        history.record_proof(start + Duration::from_secs(60), 200);
+        history.record_failure(start + Duration::from_secs(2 * 3600));
+
+        let hourly = history.hourly(start + Duration::from_secs(2 * 3600));
+        assert_eq!(hourly.len(), CHART_HISTORY_HOURS);
+        let first = hourly[CHART_HISTORY_HOURS - 3];
+        assert_eq!(first.proofs, 2);
+        assert_eq!(first.avg_duration_secs(), 150);
+        assert_eq!(first.success_rate_percent(), 100);
+        let last = hourly[CHART_HISTORY_HOURS - 1];
+        assert_eq!(last.failures, 1);
+        assert_eq!(last.success_rate_percent(), 0);
+    }
+
+    #[test]
+    fn test_old_hours_fall_out_of_window() {
+        let start = Instant::now();
+        let mut history = ProofHistory::new(start);
+        history.record_proof(start, 10);
+        let later = start + Duration::from_secs(30 * 3600);
+        history.record_proof(later, 10);
+
+        let hourly = history.hourly(later);
+        assert_eq!(hourly.iter().map(|h| h.proofs).sum::<u64>(), 1);
+    }
+}
@@ -0,0 +1,305 @@
+//! Dashboard layout
+//!
+//! The dashboard body is made of panels arranged in two rows: wide panels on top and metric
+//! cards below. Which panels are shown, and their order within a row, is described by a
+//! [`DashboardLayout`]. It is saved in the config file and can be changed while the dashboard
+//! is running.
+
+use super::components::{breakdown, charts, info_panel, logs, metrics};
+use super::state::DashboardState;
+use crate::tr;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use serde::{Deserialize, Serialize};
+
+/// A dashboard panel that can be shown, hidden and moved
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Panel {
+    SystemInfo,
+    Logs,
+    Charts,
+    SystemMetrics,
+    ZkvmStats,
+    Points,
+    Leaderboard,
+    RateLimit,
+    Network,
+}
+
+/// Row of the dashboard body a panel is placed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelRow {
+    /// Wide panels: system info, activity log and charts
+    Main,
+    /// Metric cards along the bottom
+    Metrics,
+}
+
+impl Panel {
+    /// All panels, in their default order.
+    pub const ALL: [Panel; 9] = [
+        Panel::SystemInfo,
+        Panel::Logs,
+        Panel::Charts,
+        Panel::SystemMetrics,
+        Panel::ZkvmStats,
+        Panel::Points,
+        Panel::Leaderboard,
+        Panel::RateLimit,
+        Panel::Network,
+    ];
+
+    pub fn row(self) -> PanelRow {
+        match self {
+            Panel::SystemInfo | Panel::Logs | Panel::Charts => PanelRow::Main,
+            Panel::SystemMetrics
+            | Panel::ZkvmStats
+            | Panel::Points
+            | Panel::Leaderboard
+            | Panel::RateLimit
+            | Panel::Network => PanelRow::Metrics,
+        }
+    }
+
+    /// Share of its row the panel takes, relative to the other visible panels.
+    fn width(self) -> u16 {
+        match self {
+            Panel::SystemInfo => 3,
+            Panel::Logs | Panel::Charts => 7,
+            Panel::SystemMetrics
+            | Panel::ZkvmStats
+            | Panel::Points
+            | Panel::Leaderboard
+            | Panel::RateLimit
+            | Panel::Network => 1,
+        }
+    }
+
+    /// Localized panel name, shown in the footer when the panel is selected.
+    pub fn name(self) -> String {
+        match self {
+            Panel::SystemInfo => tr!("dashboard-panel-system-info"),
+            Panel::Logs => tr!("dashboard-panel-logs"),
+            Panel::Charts => tr!("dashboard-panel-charts"),
+            Panel::SystemMetrics => tr!("dashboard-panel-system-metrics"),
+            Panel::ZkvmStats => tr!("dashboard-panel-zkvm-stats"),
+            Panel::Points => tr!("dashboard-panel-points"),
+            Panel::Leaderboard => tr!("dashboard-panel-leaderboard"),
+            Panel::RateLimit => tr!("dashboard-panel-rate-limit"),
+            Panel::Network => tr!("dashboard-panel-network"),
+        }
+    }
+
+    pub(super) fn render(self, f: &mut Frame, area: Rect, state: &DashboardState) {
+        match self {
+            Panel::SystemInfo => info_panel::render_info_panel(f, area, state),
+            Panel::Logs => logs::render_logs_panel(f, area, state),
+            Panel::Charts => {
+                let chart_chunks = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(area);
+                charts::render_charts_panel(f, chart_chunks[0], state);
+                breakdown::render_breakdown_panel(f, chart_chunks[1], state);
+            }
+            Panel::SystemMetrics => metrics::render_system_metrics(f, area, state),
+            Panel::ZkvmStats => metrics::render_zkvm_metrics(f, area, state),
+            Panel::Points => metrics::render_points_panel(f, area, state),
+            Panel::Leaderboard => metrics::render_leaderboard_panel(f, area, state),
+            Panel::RateLimit => metrics::render_rate_limit_panel(f, area, state),
+            Panel::Network => metrics::render_network_panel(f, area, state),
+        }
+    }
+}
+
+/// A panel and whether it is shown
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelSlot {
+    pub panel: Panel,
+    pub visible: bool,
+}
+
+/// Order and visibility of the dashboard panels, saved as `dashboard` in the config file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DashboardLayout {
+    #[serde(default)]
+    pub panels: Vec<PanelSlot>,
+}
+
+impl Default for DashboardLayout {
+    /// Every panel in its default order; the charts are hidden until swapped in for the log, and
+    /// the leaderboard until shown.
+    fn default() -> Self {
+        Self {
+            panels: Panel::ALL
+                .iter()
+                .map(|&panel| PanelSlot {
+                    panel,
+                    visible: !matches!(panel, Panel::Charts | Panel::Leaderboard),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl DashboardLayout {
+    /// The layout with every panel listed exactly once. Duplicates in a hand-edited config are
+    /// dropped, and panels added in newer versions are appended with their default visibility.
+    pub fn normalized(self) -> Self {
+        let mut panels: Vec<PanelSlot> = Vec::with_capacity(Panel::ALL.len());
+        for slot in self.panels {
+            if !panels.iter().any(|s| s.panel == slot.panel) {
+                panels.push(slot);
+            }
+        }
+        for slot in Self::default().panels {
+            if !panels.iter().any(|s| s.panel == slot.panel) {
+                panels.push(slot);
+            }
+        }
+        Self { panels }
+    }
+
+    pub fn is_visible(&self, panel: Panel) -> bool {
+        self.panels
+            .iter()
+            .any(|slot| slot.panel == panel && slot.visible)
+    }
+
+    pub fn set_visible(&mut self, panel: Panel, visible: bool) {
+        if let Some(slot) = self.panels.iter_mut().find(|slot| slot.panel == panel) {
+            slot.visible = visible;
+        }
+    }
+
+    /// Show `panel` if it is hidden, hide it otherwise.
+    pub fn toggle(&mut self, panel: Panel) {
+        self.set_visible(panel, !self.is_visible(panel));
+    }
+
+    /// Swap the activity log for the charts, or back.
+    pub fn toggle_charts(&mut self) {
+        let show_charts = !self.is_visible(Panel::Charts);
+        self.set_visible(Panel::Charts, show_charts);
+        self.set_visible(Panel::Logs, !show_charts);
+    }
+
+    /// Move `panel` one place earlier (`forward == false`) or later within its row.
+    pub fn move_panel(&mut self, panel: Panel, forward: bool) {
+        let Some(index) = self.panels.iter().position(|slot| slot.panel == panel) else {
+            return;
+        };
+        let same_row = |slot: &PanelSlot| slot.panel.row() == panel.row();
+        let neighbor = if forward {
+            self.panels[index + 1..]
+                .iter()
+                .position(same_row)
+                .map(|offset| index + 1 + offset)
+        } else {
+            self.panels[..index].iter().rposition(same_row)
+        };
+        if let Some(neighbor) = neighbor {
+            self.panels.swap(index, neighbor);
+        }
+    }
+
+    /// The panel after `panel` in layout order, wrapping around, to move the selection to.
+    pub fn next_panel(&self, panel: Panel) -> Panel {
+        let index = self
+            .panels
+            .iter()
+            .position(|slot| slot.panel == panel)
+            .map_or(0, |index| index + 1);
+        self.panels
+            .get(index % self.panels.len().max(1))
+            .map_or(panel, |slot| slot.panel)
+    }
+
+    /// Visible panels of `row`, in layout order.
+    pub fn visible_in(&self, row: PanelRow) -> Vec<Panel> {
+        self.panels
+            .iter()
+            .filter(|slot| slot.visible && slot.panel.row() == row)
+            .map(|slot| slot.panel)
+            .collect()
+    }
+}
+
+/// Areas of `panels` placed side by side in `area`.
+pub fn row_areas(area: Rect, panels: &[Panel]) -> Vec<(Panel, Rect)> {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(panels.iter().map(|panel| Constraint::Fill(panel.width())))
+        .split(area);
+    panels.iter().copied().zip(chunks.iter().copied()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_and_move() {
+        let mut layout = DashboardLayout::default();
+        assert_eq!(
+            layout.visible_in(PanelRow::Main),
+            vec![Panel::SystemInfo, Panel::Logs]
+        );
+
+        layout.toggle_charts();
+        assert_eq!(
+            layout.visible_in(PanelRow::Main),
+            vec![Panel::SystemInfo, Panel::Charts]
+        );
+
+        layout.toggle(Panel::Points);
+        // Moving within a row skips panels of the other row
+        layout.move_panel(Panel::SystemMetrics, false);
+        layout.move_panel(Panel::RateLimit, true);
+        assert_eq!(
+            layout.visible_in(PanelRow::Metrics),
+            vec![
+                Panel::SystemMetrics,
+                Panel::ZkvmStats,
+                Panel::Network,
+                Panel::RateLimit
+            ]
+        );
+        layout.move_panel(Panel::SystemMetrics, true);
+        assert_eq!(
+            layout.visible_in(PanelRow::Metrics),
+            vec![
+                Panel::ZkvmStats,
+                Panel::SystemMetrics,
+                Panel::Network,
+                Panel::RateLimit
+            ]
+        );
+
+        assert_eq!(layout.next_panel(Panel::SystemInfo), Panel::Logs);
+        assert_eq!(layout.next_panel(Panel::Network), Panel::RateLimit);
+        assert_eq!(layout.next_panel(Panel::RateLimit), Panel::SystemInfo);
+    }
+
+    #[test]
+    fn test_normalized_and_serde() {
+        let saved =
+            r#"{"panels":[{"panel":"logs","visible":true},{"panel":"logs","visible":false}]}"#;
+        let layout = serde_json::from_str::<DashboardLayout>(saved)
+            .unwrap()
+            .normalized();
+        assert_eq!(layout.panels.len(), Panel::ALL.len());
+        assert_eq!(layout.panels[0].panel, Panel::Logs);
+        assert!(layout.is_visible(Panel::Logs));
+        assert!(!layout.is_visible(Panel::Charts));
+        assert!(!layout.is_visible(Panel::Leaderboard));
+
+        let json = serde_json::to_string(&layout).unwrap();
+        assert!(json.contains(r#""panel":"zkvm_stats""#));
+        assert_eq!(
+            serde_json::from_str::<DashboardLayout>(&json).unwrap(),
+            layout
+        );
+    }
+}
@@ -3,11 +3,14 @@
 //! Split into logical modules for better maintainability
 
 pub mod components;
+pub mod history;
+pub mod layout;
 pub mod renderer;
 pub mod state;
 pub mod updaters;
 pub mod utils;
 
 // Re-export main types and functions for external use
+pub use layout::{DashboardLayout, Panel};
 pub use renderer::render_dashboard;
 pub use state::DashboardState;
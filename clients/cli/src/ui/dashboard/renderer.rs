@@ -1,40 +1,148 @@
 //! Dashboard main renderer
 
-use super::components::{footer, header, info_panel, logs, metrics};
+use super::components::{compact, footer, header, overlay};
+use super::layout::{Panel, PanelRow, row_areas};
 use super::state::DashboardState;
+use crate::consts::cli_consts::dashboard::COMPACT_LOG_LINES;
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::prelude::{Color, Style};
+use ratatui::layout::{Constraint, Direction, Layout, Position, Rect};
+use ratatui::prelude::Style;
 use ratatui::widgets::Block;
 
+/// Where the parts of the dashboard go on screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DashboardAreas {
+    pub header: Rect,
+    /// Visible panels, by row and in layout order
+    pub panels: Vec<(Panel, Rect)>,
+    pub footer: Rect,
+}
+
+/// Lay the dashboard out in `area`, the whole terminal.
+pub fn dashboard_areas(area: Rect, state: &DashboardState) -> DashboardAreas {
+    if state.is_compact(area) {
+        return compact_areas(area);
+    }
+
+    // Rows without visible panels are left out and the remaining rows take their space
+    let main_panels = state.layout.visible_in(PanelRow::Main);
+    let metric_panels = state.layout.visible_in(PanelRow::Metrics);
+    let metrics_height = if main_panels.is_empty() {
+        Constraint::Fill(1)
+    } else {
+        Constraint::Percentage(35)
+    };
+    let mut constraints = vec![Constraint::Length(header::header_height(state))];
+    if !main_panels.is_empty() {
+        constraints.push(Constraint::Fill(1));
+    }
+    if !metric_panels.is_empty() {
+        constraints.push(metrics_height);
+    }
+    constraints.push(Constraint::Length(2));
+
+    let main_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .margin(1)
+        .split(area);
+
+    let mut panels = Vec::new();
+    let mut next_chunk = 1;
+    for row in [main_panels, metric_panels] {
+        if !row.is_empty() {
+            panels.extend(row_areas(main_chunks[next_chunk], &row));
+            next_chunk += 1;
+        }
+    }
+    DashboardAreas {
+        header: main_chunks[0],
+        panels,
+        footer: main_chunks[next_chunk],
+    }
+}
+
+/// Compact layout: the status line in place of the header, then the newest log lines, with the
+/// footer on the last row.
+fn compact_areas(area: Rect) -> DashboardAreas {
+    let [header, logs, _, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(COMPACT_LOG_LINES as u16),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+    DashboardAreas {
+        header,
+        panels: vec![(Panel::Logs, logs)],
+        footer,
+    }
+}
+
+/// The visible panel at `position` on a terminal of `area`, for mouse clicks.
+pub fn panel_at(area: Rect, state: &DashboardState, position: Position) -> Option<Panel> {
+    dashboard_areas(area, state)
+        .panels
+        .into_iter()
+        .find(|(_, rect)| rect.contains(position))
+        .map(|(panel, _)| panel)
+}
+
 pub fn render_dashboard(f: &mut Frame, state: &DashboardState) {
     if state.with_background_color {
         f.render_widget(
-            Block::default().style(Style::default().bg(Color::Rgb(16, 20, 24))),
+            Block::default().style(Style::default().bg(state.theme.background)),
             f.area(),
         );
     }
 
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(4),
-            Constraint::Fill(1),
-            Constraint::Percentage(35),
-            Constraint::Length(2),
-        ])
-        .margin(1)
-        .split(f.area());
+    let areas = dashboard_areas(f.area(), state);
+    if state.is_compact(f.area()) {
+        compact::render_status_line(f, areas.header, state);
+        for (_, area) in areas.panels {
+            compact::render_logs(f, area, state);
+        }
+    } else {
+        header::render_header(f, areas.header, state);
+        for (panel, area) in areas.panels {
+            panel.render(f, area, state);
+        }
+    }
+    footer::render_footer(f, areas.footer, state);
+    overlay::render_overlay(f, state);
+}
 
-    header::render_header(f, main_chunks[0], state);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::Environment;
+    use crate::points::PointsTracker;
+    use crate::ui::{UIConfig, UiMode};
+    use std::time::Instant;
+
+    fn dashboard(ui_mode: UiMode) -> DashboardState {
+        let config =
+            UIConfig::new(false, 1, false, None, PointsTracker::default()).with_ui_mode(ui_mode);
+        DashboardState::new(Some(1), Environment::default(), Instant::now(), config)
+    }
 
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
-        .split(main_chunks[1]);
+    #[test]
+    fn test_compact_layout_in_small_terminals() {
+        let state = dashboard(UiMode::Full);
+        let large = Rect::new(0, 0, 120, 40);
+        assert!(dashboard_areas(large, &state).panels.len() > 1);
 
-    info_panel::render_info_panel(f, content_chunks[0], state);
-    logs::render_logs_panel(f, content_chunks[1], state);
-    metrics::render_metrics_section(f, main_chunks[2], state);
-    footer::render_footer(f, main_chunks[3]);
+        let small = Rect::new(0, 0, 80, 10);
+        let areas = dashboard_areas(small, &state);
+        assert_eq!(areas.header, Rect::new(0, 0, 80, 1));
+        assert_eq!(
+            areas.panels,
+            vec![(Panel::Logs, Rect::new(0, 1, 80, COMPACT_LOG_LINES as u16))]
+        );
+        assert_eq!(areas.footer, Rect::new(0, 9, 80, 1));
+
+        // `--ui compact` forces it at any size
+        let state = dashboard(UiMode::Compact);
+        assert_eq!(dashboard_areas(large, &state).panels.len(), 1);
+    }
 }
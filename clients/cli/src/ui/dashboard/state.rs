@@ -2,15 +2,30 @@
 //!
 //! Contains the main dashboard state struct and related enums
 
+use super::history::ProofHistory;
+use super::layout::{DashboardLayout, Panel};
 use crate::consts::cli_consts::MAX_ACTIVITY_LOGS;
+use crate::consts::cli_consts::dashboard::{
+    COMPACT_BELOW_HEIGHT, COMPACT_BELOW_WIDTH, LOG_SCROLL_LINES,
+};
 use crate::environment::Environment;
-use crate::events::{Event as WorkerEvent, ProverState};
-use crate::ui::app::UIConfig;
+use crate::events::{Event as WorkerEvent, ProverState, TaskFetchState};
+use crate::labels::Labels;
+use crate::network::NetworkMonitor;
+use crate::network_stats::NetworkStatsTracker;
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::points::PointsTracker;
+use crate::stats::{ProofDurationModel, ProofProgress};
+use crate::thermal::ThermalStatus;
+use crate::ui::app::{UIConfig, UiMode};
 use crate::ui::metrics::{SystemMetrics, TaskFetchInfo, ZkVMMetrics};
+use crate::ui::theme::Theme;
+use crate::version::manager::VersionNotice;
 
 use std::collections::VecDeque;
 use std::time::Instant;
 use sysinfo::System;
+use tokio::sync::watch;
 
 /// State for tracking fetching operations
 #[derive(Debug, Clone)]
@@ -20,6 +35,17 @@ pub enum FetchingState {
     Timeout,
 }
 
+/// Overlay drawn over the dashboard, which takes every key press while open
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Overlay {
+    #[default]
+    None,
+    /// Key bindings and palette commands, opened with `?`
+    Help,
+    /// Command palette opened with `:`, holding the command typed so far
+    Palette(String),
+}
+
 /// Enhanced dashboard state with real-time metrics and animations.
 #[derive(Debug)]
 pub struct DashboardState {
@@ -37,16 +63,32 @@ pub struct DashboardState {
     pub total_ram_gb: f64,
     /// Number of worker threads being used for proving.
     pub num_threads: usize,
+    /// GPUs detected on this machine.
+    pub gpus: Vec<crate::system::GpuInfo>,
+    /// Core each worker slot is pinned to, if core pinning is enabled.
+    pub pinned_cores: Option<Vec<usize>>,
     /// Queue of events waiting to be processed
     pub pending_events: VecDeque<WorkerEvent>,
     /// Activity logs for display (last 50 events)
     pub activity_logs: VecDeque<WorkerEvent>,
+    /// Activity log lines scrolled back from the newest, with the mouse wheel or Page Up
+    pub log_scroll: usize,
     /// Whether a new version is available.
     pub update_available: bool,
     /// The latest version string, if known.
     pub latest_version: Option<String>,
+    /// Violated version requirement the session was started despite, shown in a banner.
+    pub version_notice: Option<VersionNotice>,
+    /// Whether the update prompt was dismissed with `X`
+    pub update_prompt_dismissed: bool,
+    /// Node labels, shown under the title
+    pub labels: Labels,
     /// Whether to enable background colors
     pub with_background_color: bool,
+    /// Dashboard style selected with `--ui`
+    pub ui_mode: UiMode,
+    /// Colors the dashboard is drawn with
+    pub theme: Theme,
 
     /// System metrics (CPU, RAM, etc.)
     pub system_metrics: SystemMetrics,
@@ -54,8 +96,32 @@ pub struct DashboardState {
     pub zkvm_metrics: ZkVMMetrics,
     /// Task fetch information for accurate timing
     pub task_fetch_info: TaskFetchInfo,
+    /// Latest rate-limit budget published by the task fetcher
+    pub task_fetch_state: TaskFetchState,
+    /// Point totals fetched from the orchestrator
+    pub points: PointsTracker,
+    /// Network statistics and the node's rank, for the leaderboard panel
+    pub network_stats: NetworkStatsTracker,
+    /// Round-trip times and errors of recent orchestrator requests
+    pub network: NetworkMonitor,
+    /// Temperature and throttling, with `--thermal-limit`
+    pub thermal: Option<ThermalStatus>,
+    /// When system metrics and temperature were last sampled
+    pub metrics_sampled_at: Option<Instant>,
+    /// Hourly proof statistics for the charts pane
+    pub history: ProofHistory,
+    /// Moving average of proof durations, for the proving progress estimate
+    pub proof_durations: ProofDurationModel,
+    /// Order and visibility of the panels
+    pub layout: DashboardLayout,
+    /// Panel that the show/hide and move key bindings apply to
+    pub selected_panel: Panel,
     /// Animation tick counter
     pub tick: usize,
+    /// Help or command palette drawn over the dashboard, if open
+    pub overlay: Overlay,
+    /// Outcome of the last palette command, shown in the footer until the next key press
+    pub command_feedback: Option<String>,
 
     /// Timestamp of last successful proof submission
     last_submission_timestamp: Option<String>,
@@ -67,8 +133,10 @@ pub struct DashboardState {
     current_prover_state: ProverState,
     /// Track when Step 2 started for current task
     pub step2_start_time: Option<Instant>,
-    /// Track the start time and original wait duration for current waiting period
-    pub waiting_start_info: Option<(Instant, u64)>, // (start_time, original_wait_secs)
+    /// Difficulty of the task being proved
+    pub current_task_difficulty: Option<TaskDifficulty>,
+    /// Receives rate-limit budget updates from the task fetcher
+    fetch_state_receiver: Option<watch::Receiver<TaskFetchState>>,
 }
 
 impl DashboardState {
@@ -79,6 +147,7 @@ impl DashboardState {
         start_time: Instant,
         ui_config: UIConfig,
     ) -> Self {
+        let layout = ui_config.layout.normalized();
         Self {
             node_id,
             environment,
@@ -87,24 +156,58 @@ impl DashboardState {
             current_task: None,
             total_ram_gb: crate::system::total_memory_gb(),
             num_threads: ui_config.num_threads,
+            gpus: crate::system::detect_gpus().to_vec(),
+            pinned_cores: ui_config.pinned_cores,
             pending_events: VecDeque::new(),
             activity_logs: VecDeque::new(),
+            log_scroll: 0,
             update_available: ui_config.update_available,
             latest_version: ui_config.latest_version,
+            version_notice: ui_config.version_notice,
+            update_prompt_dismissed: false,
+            labels: ui_config.labels,
             with_background_color: ui_config.with_background_color,
+            ui_mode: ui_config.ui_mode,
+            theme: ui_config.theme,
 
             system_metrics: SystemMetrics::default(),
             zkvm_metrics: ZkVMMetrics::default(),
             task_fetch_info: TaskFetchInfo::default(),
+            task_fetch_state: TaskFetchState::default(),
+            points: ui_config.points,
+            network_stats: ui_config.network_stats,
+            network: NetworkMonitor::global().clone(),
+            thermal: None,
+            metrics_sampled_at: None,
+            history: ProofHistory::new(start_time),
+            proof_durations: ProofDurationModel::new(),
+            selected_panel: layout.panels[0].panel,
+            layout,
             tick: 0,
+            overlay: Overlay::None,
+            command_feedback: None,
             last_submission_timestamp: None,
             fetching_state: FetchingState::Idle,
             sysinfo: System::new_all(), // Initialize with all data for first refresh
-            current_prover_state: ProverState::Waiting,
+            current_prover_state: ProverState::Idle,
             step2_start_time: None,
-            waiting_start_info: None,
+            current_task_difficulty: None,
+            fetch_state_receiver: ui_config.fetch_state,
         }
     }
+    /// Whether to draw the compact dashboard in `area`: with `--ui compact`, or when the
+    /// terminal is too small for the panels.
+    pub fn is_compact(&self, area: ratatui::layout::Rect) -> bool {
+        self.ui_mode == UiMode::Compact
+            || area.width < COMPACT_BELOW_WIDTH
+            || area.height < COMPACT_BELOW_HEIGHT
+    }
+
+    /// Whether to show the banner offering to install the new version with `U`
+    pub fn show_update_prompt(&self) -> bool {
+        self.update_available && !self.update_prompt_dismissed
+    }
+
     // Getter methods for private fields
     pub fn fetching_state(&self) -> &FetchingState {
         &self.fetching_state
@@ -135,12 +238,57 @@ impl DashboardState {
         &mut self.sysinfo
     }
 
+    /// Whether the dashboard animates, and so needs redrawing at the full frame rate: a proof
+    /// without a progress estimate shows a looping gauge instead.
+    pub fn is_animating(&self) -> bool {
+        self.current_prover_state == ProverState::Proving && self.proving_progress().is_none()
+    }
+
+    /// Estimated progress of the proof in flight, once a proof of its difficulty has finished
+    pub fn proving_progress(&self) -> Option<ProofProgress> {
+        let started = self.step2_start_time?;
+        let difficulty = self.current_task_difficulty?;
+        self.proof_durations.progress(difficulty, started.elapsed())
+    }
+
+    /// Copy the latest rate-limit budget from the task fetcher, if it changed
+    pub fn refresh_task_fetch_state(&mut self) {
+        if let Some(receiver) = &mut self.fetch_state_receiver {
+            if receiver.has_changed().unwrap_or(false) {
+                self.task_fetch_state = receiver.borrow_and_update().clone();
+            }
+        }
+    }
+
     /// Add an event to activity logs with size limit
     pub fn add_to_activity_log(&mut self, event: WorkerEvent) {
+        // Keep a scrolled-back log on the lines being read
+        if self.log_scroll > 0 && event.should_display() {
+            self.log_scroll += 1;
+        }
         if self.activity_logs.len() >= MAX_ACTIVITY_LOGS {
             self.activity_logs.pop_front();
         }
         self.activity_logs.push_back(event);
+        self.log_scroll = self.log_scroll.min(self.max_log_scroll());
+    }
+
+    /// Scroll the activity log back towards older lines (`up`) or forward to the newest.
+    pub fn scroll_logs(&mut self, up: bool) {
+        self.log_scroll = if up {
+            (self.log_scroll + LOG_SCROLL_LINES).min(self.max_log_scroll())
+        } else {
+            self.log_scroll.saturating_sub(LOG_SCROLL_LINES)
+        };
+    }
+
+    /// Furthest the activity log can be scrolled back: to its oldest line
+    fn max_log_scroll(&self) -> usize {
+        self.activity_logs
+            .iter()
+            .filter(|event| event.should_display())
+            .count()
+            .saturating_sub(1)
     }
 
     /// Add an event to the processing queue
@@ -3,14 +3,15 @@
 //! Contains helper functions used across dashboard components
 
 use crate::events::Worker;
+use crate::ui::theme::Theme;
 use ratatui::prelude::Color;
 
 /// Get a ratatui color for a worker based on its type
-pub fn get_worker_color(worker: &Worker) -> Color {
+pub fn get_worker_color(worker: &Worker, theme: &Theme) -> Color {
     match worker {
-        Worker::TaskFetcher => Color::Cyan,
-        Worker::Prover(_) => Color::Yellow,
-        Worker::ProofSubmitter => Color::Green,
+        Worker::TaskFetcher => theme.primary,
+        Worker::Prover(_) => theme.warning,
+        Worker::ProofSubmitter => theme.success,
     }
 }
 
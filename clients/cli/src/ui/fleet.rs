@@ -0,0 +1,449 @@
+//! Fleet dashboard for `fleet start`
+//!
+//! Shows one row per node with its state, current task, proof rate, points and last error. Enter
+//! opens the details and latest output of the selected node, Esc goes back to the list.
+
+use crate::consts::cli_consts::dashboard::{DEFAULT_UI_FPS, IDLE_REDRAW_INTERVAL_MS};
+use crate::fleet::{FleetStatus, NodeStatus, ProcessState};
+use crate::ipc::queue::QueueStats;
+use crate::labels::format_labels;
+use crate::points::format_points;
+use crate::tr;
+use crate::ui::UiMode;
+use crate::ui::plain;
+use crate::ui::theme::Theme;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Row, Table, Wrap};
+use ratatui::{Frame, Terminal, backend::Backend};
+use std::time::{Duration, Instant};
+
+/// Which node is selected, and whether its details are shown
+#[derive(Debug, Default)]
+struct FleetView {
+    selected: usize,
+    details: bool,
+}
+
+impl FleetView {
+    /// Apply a key press, returning `false` if the user quit.
+    fn handle_key(&mut self, code: KeyCode, num_nodes: usize) -> bool {
+        match code {
+            KeyCode::Char('q') => return false,
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Left if self.details => {
+                self.details = false
+            }
+            KeyCode::Esc => return false,
+            KeyCode::Enter | KeyCode::Right => self.details = true,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(num_nodes.saturating_sub(1))
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Show the fleet dashboard until the user quits.
+pub async fn run_fleet_ui<B: Backend>(
+    terminal: &mut Terminal<B>,
+    fleet: &FleetStatus,
+    ui_mode: UiMode,
+    theme: Theme,
+) -> std::io::Result<()> {
+    let frame_interval = Duration::from_secs(1) / DEFAULT_UI_FPS;
+    let idle_interval = Duration::from_millis(IDLE_REDRAW_INTERVAL_MS);
+    let mut view = FleetView::default();
+    // Version of the fleet status last drawn, and when
+    let mut drawn: Option<(u64, Instant)> = None;
+    let mut changed = true;
+    loop {
+        // Redraw on changes, and every second for the uptime clocks
+        let version = fleet.version();
+        if changed
+            || drawn.is_none_or(|(seen, at)| seen != version || at.elapsed() >= idle_interval)
+        {
+            terminal.draw(|f| {
+                fleet.with_nodes(|nodes| render(f, nodes, &view, &theme));
+                if ui_mode == UiMode::Plain {
+                    plain::flatten(f.buffer_mut());
+                }
+            })?;
+            drawn = Some((version, Instant::now()));
+            changed = false;
+        }
+
+        // Node updates do not wake the input poll, so never wait longer than a frame
+        if event::poll(frame_interval)? {
+            match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => {
+                    let num_nodes = fleet.with_nodes(|nodes| nodes.len());
+                    if !view.handle_key(key.code, num_nodes) {
+                        return Ok(());
+                    }
+                    changed = true;
+                }
+                Event::Resize(..) => changed = true,
+                _ => {}
+            }
+        }
+    }
+}
+
+fn render(f: &mut Frame, nodes: &[NodeStatus], view: &FleetView, theme: &Theme) {
+    let chunks = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Fill(1),
+        Constraint::Length(2),
+    ])
+    .margin(1)
+    .split(f.area());
+
+    render_header(f, chunks[0], nodes, theme);
+    let footer = match nodes.get(view.selected) {
+        Some(node) if view.details => {
+            render_details(f, chunks[1], node, theme);
+            tr!("fleet-footer-details")
+        }
+        _ => {
+            render_nodes(f, chunks[1], nodes, view.selected, theme);
+            tr!("fleet-footer")
+        }
+    };
+    let footer = Paragraph::new(footer)
+        .alignment(Alignment::Center)
+        .style(
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Thick),
+        );
+    f.render_widget(footer, chunks[2]);
+}
+
+fn render_header(f: &mut Frame, area: Rect, nodes: &[NodeStatus], theme: &Theme) {
+    let running = nodes
+        .iter()
+        .filter(|node| matches!(node.process, ProcessState::Running { .. }))
+        .count();
+    let statuses = || nodes.iter().filter_map(|node| node.stats.as_ref());
+    let submitted: u64 = statuses().map(|status| status.stats.submitted).sum();
+    let failed: u64 = statuses().map(|status| status.stats.failed).sum();
+    let points: u64 = statuses().filter_map(|status| status.points).sum();
+    let summary = tr!(
+        "fleet-summary",
+        running = running,
+        nodes = nodes.len(),
+        submitted = submitted,
+        failed = failed,
+        points = format_points(points)
+    );
+    let header = Paragraph::new(summary)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.text))
+        .block(
+            Block::default()
+                .title(tr!("fleet-title", version = env!("CARGO_PKG_VERSION")))
+                .title_alignment(Alignment::Center)
+                .title_style(
+                    Style::default()
+                        .fg(theme.primary)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .border_style(Style::default().fg(theme.border)),
+        );
+    f.render_widget(header, area);
+}
+
+fn render_nodes(f: &mut Frame, area: Rect, nodes: &[NodeStatus], selected: usize, theme: &Theme) {
+    let header = Row::new([
+        tr!("fleet-column-node"),
+        tr!("fleet-column-state"),
+        tr!("fleet-column-task"),
+        tr!("fleet-column-rate"),
+        tr!("fleet-column-points"),
+        tr!("fleet-column-restarts"),
+        tr!("fleet-column-last-error"),
+    ])
+    .style(
+        Style::default()
+            .fg(theme.label)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let rows = nodes.iter().enumerate().map(|(index, node)| {
+        let (state, color) = node_state(node, theme);
+        let status = node.stats.as_ref();
+        // A marker rather than only a highlight, so the selection survives `--ui plain`
+        let marker = if index == selected { ">" } else { " " };
+        let row = Row::new([
+            format!("{} {}", marker, node.spec.node_id),
+            state,
+            status
+                .and_then(|status| status.current_task.as_ref())
+                .map_or_else(
+                    || "-".to_string(),
+                    |task| format!("{} ({})", task.task_id, task.stage),
+                ),
+            proof_rate(node),
+            points(node),
+            node.restarts.to_string(),
+            status
+                .and_then(|status| status.last_error.clone())
+                .unwrap_or_else(|| "-".to_string()),
+        ])
+        .style(Style::default().fg(color));
+        if index == selected {
+            row.add_modifier(Modifier::REVERSED)
+        } else {
+            row
+        }
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(14),
+            Constraint::Length(20),
+            Constraint::Length(24),
+            Constraint::Length(9),
+            Constraint::Length(12),
+            Constraint::Length(9),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border)),
+    );
+    f.render_widget(table, area);
+}
+
+fn render_details(f: &mut Frame, area: Rect, node: &NodeStatus, theme: &Theme) {
+    let chunks = Layout::vertical([Constraint::Length(11), Constraint::Fill(1)]).split(area);
+
+    let (state, color) = node_state(node, theme);
+    let pid = match node.process {
+        ProcessState::Running { pid, .. } => pid.to_string(),
+        _ => "-".to_string(),
+    };
+    let spec = &node.spec;
+    let mut lines = vec![
+        Line::styled(
+            tr!(
+                "fleet-details-process",
+                state = state,
+                pid = pid,
+                uptime = uptime(node),
+                restarts = node.restarts
+            ),
+            Style::default().fg(color),
+        ),
+        Line::from(tr!(
+            "fleet-details-settings",
+            threads = spec
+                .max_threads
+                .map_or_else(|| tr!("fleet-default"), |threads| threads.to_string()),
+            difficulty = spec.max_difficulty.map_or_else(
+                || tr!("fleet-default"),
+                |difficulty| difficulty.as_str_name().to_string()
+            ),
+            schedule = spec.schedule.map_or_else(
+                || tr!("fleet-schedule-always"),
+                |window| format!(
+                    "{}-{}",
+                    window.start.format("%H:%M"),
+                    window.end.format("%H:%M")
+                )
+            )
+        )),
+        Line::from(match &node.stats {
+            Some(status) => tr!(
+                "fleet-details-counters",
+                queued = status.stats.queued,
+                fetched = status.stats.fetched,
+                proved = status.stats.proved,
+                submitted = status.stats.submitted,
+                failed = status.stats.failed
+            ),
+            None => tr!("fleet-details-no-stats"),
+        }),
+        Line::from(tr!(
+            "fleet-details-task",
+            task = node
+                .stats
+                .as_ref()
+                .and_then(|status| status.current_task.as_ref())
+                .map_or_else(
+                    || "-".to_string(),
+                    |task| format!(
+                        "{} ({}, {}, {}s)",
+                        task.task_id, task.stage, task.difficulty, task.age_secs
+                    )
+                )
+        )),
+        Line::from(tr!(
+            "fleet-details-points",
+            points = points(node),
+            rate = proof_rate(node)
+        )),
+        Line::from(tr!(
+            "fleet-details-labels",
+            labels = format_labels(&spec.labels)
+        )),
+    ];
+    if let Some(error) = node
+        .stats
+        .as_ref()
+        .and_then(|status| status.last_error.as_ref())
+    {
+        lines.push(Line::styled(
+            tr!("fleet-details-last-error", error = error),
+            Style::default().fg(theme.warning),
+        ));
+    }
+    if let ProcessState::Restarting { exit, .. } | ProcessState::Stopped { exit } = &node.process {
+        lines.push(Line::styled(
+            tr!("fleet-details-last-exit", exit = exit),
+            Style::default().fg(theme.error),
+        ));
+    }
+    let details = Paragraph::new(lines)
+        .style(Style::default().fg(theme.text))
+        .block(
+            Block::default()
+                .title(tr!("fleet-details-title", id = spec.node_id))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.border)),
+        );
+    f.render_widget(details, chunks[0]);
+
+    // The latest lines that fit, oldest first
+    let visible = chunks[1].height.saturating_sub(2) as usize;
+    let output: Vec<Line> = if node.output.is_empty() {
+        vec![Line::from(tr!("fleet-no-output"))]
+    } else {
+        let skip = node.output.len().saturating_sub(visible);
+        node.output
+            .iter()
+            .skip(skip)
+            .map(|line| Line::from(line.as_str()))
+            .collect()
+    };
+    let output = Paragraph::new(output)
+        .style(Style::default().fg(theme.muted))
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(tr!("fleet-details-output"))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.border)),
+        );
+    f.render_widget(output, chunks[1]);
+}
+
+/// Short state of a node, and the color to show it in
+fn node_state(node: &NodeStatus, theme: &Theme) -> (String, Color) {
+    let paused = node.stats.as_ref().is_some_and(|status| status.paused);
+    match &node.process {
+        ProcessState::Starting => (tr!("fleet-state-starting"), theme.info),
+        ProcessState::Running { .. } if node.off_schedule => {
+            (tr!("fleet-state-off-schedule"), theme.muted)
+        }
+        ProcessState::Running { .. } if paused => (tr!("fleet-state-paused"), theme.warning),
+        ProcessState::Running { .. } => (tr!("fleet-state-running"), theme.success),
+        ProcessState::Restarting { at, .. } => (
+            tr!(
+                "fleet-state-restarting",
+                seconds = at.saturating_duration_since(Instant::now()).as_secs()
+            ),
+            theme.error,
+        ),
+        ProcessState::Stopped { .. } => (tr!("fleet-state-stopped"), theme.muted),
+    }
+}
+
+/// Points of the node, or `-` before they are known
+fn points(node: &NodeStatus) -> String {
+    node.stats
+        .as_ref()
+        .and_then(|status| status.points)
+        .map_or_else(|| "-".to_string(), format_points)
+}
+
+/// Proofs submitted per hour, or `-` before the rate is meaningful
+fn proof_rate(node: &NodeStatus) -> String {
+    node.stats
+        .as_ref()
+        .and_then(|status| proofs_per_hour(&status.stats))
+        .map_or_else(|| "-".to_string(), |rate| format!("{:.1}", rate))
+}
+
+/// Proofs submitted per hour over the node's session, once it has run for a minute
+fn proofs_per_hour(stats: &QueueStats) -> Option<f64> {
+    (stats.uptime_secs >= 60).then(|| stats.submitted as f64 * 3600.0 / stats.uptime_secs as f64)
+}
+
+/// Time the node's process has been running, e.g. `2h 5m`
+fn uptime(node: &NodeStatus) -> String {
+    let ProcessState::Running { since, .. } = node.process else {
+        return "-".to_string();
+    };
+    let secs = since.elapsed().as_secs();
+    if secs >= 86400 {
+        format!("{}d {}h", secs / 86400, (secs % 86400) / 3600)
+    } else if secs >= 3600 {
+        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
+    } else {
+        format!("{}m {}s", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_and_open_details() {
+        let mut view = FleetView::default();
+        assert!(view.handle_key(KeyCode::Up, 3));
+        assert_eq!(view.selected, 0);
+        view.handle_key(KeyCode::Down, 3);
+        view.handle_key(KeyCode::Down, 3);
+        view.handle_key(KeyCode::Down, 3);
+        assert_eq!(view.selected, 2);
+
+        // Esc leaves the details before it quits
+        view.handle_key(KeyCode::Enter, 3);
+        assert!(view.details);
+        assert!(view.handle_key(KeyCode::Esc, 3));
+        assert!(!view.details);
+        assert!(!view.handle_key(KeyCode::Esc, 3));
+        assert!(!view.handle_key(KeyCode::Char('q'), 3));
+    }
+
+    #[test]
+    fn test_proofs_per_hour() {
+        let stats = |submitted, uptime_secs| QueueStats {
+            submitted,
+            uptime_secs,
+            ..QueueStats::default()
+        };
+        assert_eq!(proofs_per_hour(&stats(3, 30)), None);
+        assert_eq!(proofs_per_hour(&stats(3, 1800)), Some(6.0));
+    }
+}
@@ -9,11 +9,16 @@ pub fn render_login(f: &mut Frame) {
     let size = f.area();
 
     let block = Block::default()
-        .title("Login")
+        .title(crate::tr!("login-title"))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan));
 
-    let paragraph = Paragraph::new("Press Enter to login\nPress Esc to exit").block(block);
+    let text = format!(
+        "{}\n{}",
+        crate::tr!("login-press-enter"),
+        crate::tr!("login-press-esc")
+    );
+    let paragraph = Paragraph::new(text).block(block);
 
     f.render_widget(paragraph, size);
 }
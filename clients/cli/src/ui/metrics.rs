@@ -1,5 +1,10 @@
 //! System metrics collection and display.
 
+use crate::events::EventPayload;
+use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+use crate::ui::theme::Theme;
+use ratatui::prelude::Color;
+use std::collections::BTreeMap;
 use std::time::Instant;
 use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
 
@@ -161,27 +166,25 @@ impl SystemMetrics {
     }
 
     /// Get CPU gauge color based on usage.
-    pub fn cpu_color(&self) -> ratatui::prelude::Color {
-        use ratatui::prelude::Color;
+    pub fn cpu_color(&self, theme: &Theme) -> Color {
         if self.cpu_percent >= 80.0 {
-            Color::Red
+            theme.error
         } else if self.cpu_percent >= 60.0 {
-            Color::Yellow
+            theme.warning
         } else {
-            Color::Green
+            theme.success
         }
     }
 
     /// Get RAM gauge color based on usage.
-    pub fn ram_color(&self) -> ratatui::prelude::Color {
-        use ratatui::prelude::Color;
+    pub fn ram_color(&self, theme: &Theme) -> Color {
         let ratio = self.ram_ratio();
         if ratio >= 0.8 {
-            Color::Red
+            theme.error
         } else if ratio >= 0.6 {
-            Color::Yellow
+            theme.warning
         } else {
-            Color::Green
+            theme.success
         }
     }
 }
@@ -197,8 +200,16 @@ pub struct ZkVMMetrics {
     pub zkvm_runtime_secs: u64,
     /// Status of the last task.
     pub last_task_status: String,
-    /// Total points earned from successful proofs (300 points each).
-    pub _total_points: u64,
+    /// Task counts and durations by task type.
+    pub by_task_type: BTreeMap<TaskType, TaskBreakdown>,
+    /// Task counts and durations by difficulty.
+    pub by_difficulty: BTreeMap<TaskDifficulty, TaskBreakdown>,
+    /// Number of times the worker recovered from a crash.
+    pub worker_restarts: usize,
+    /// Whether the worker is crash-looping; cleared by the next successful submission.
+    pub degraded: bool,
+    /// When the task being proved was fetched.
+    proving_since: Option<Instant>,
 }
 
 impl Default for ZkVMMetrics {
@@ -208,12 +219,105 @@ impl Default for ZkVMMetrics {
             tasks_submitted: 0,
             zkvm_runtime_secs: 0,
             last_task_status: "None".to_string(),
-            _total_points: 0,
+            by_task_type: BTreeMap::new(),
+            by_difficulty: BTreeMap::new(),
+            worker_restarts: 0,
+            degraded: false,
+            proving_since: None,
         }
     }
 }
 
+/// Task counts and durations for one kind of task.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskBreakdown {
+    /// Tasks of this kind started.
+    pub fetched: usize,
+    /// Tasks of this kind submitted successfully.
+    pub submitted: usize,
+    /// Sum of task durations of submitted tasks, in seconds.
+    pub total_duration_secs: u64,
+}
+
+impl TaskBreakdown {
+    /// Average task duration of submitted tasks, in seconds.
+    pub fn avg_duration_secs(&self) -> Option<u64> {
+        self.total_duration_secs.checked_div(self.submitted as u64)
+    }
+}
+
 impl ZkVMMetrics {
+    /// Update the counters with what a worker event reports.
+    pub fn observe(&mut self, payload: &EventPayload) {
+        match payload {
+            EventPayload::TaskFetched { .. } => {
+                self.tasks_fetched += 1;
+                self.proving_since = Some(Instant::now());
+            }
+            EventPayload::ProofStarted {
+                difficulty,
+                task_type,
+                ..
+            } => self.record_task_started(*task_type, *difficulty),
+            EventPayload::ProofGenerated { .. } => {
+                if let Some(started) = self.proving_since.take() {
+                    self.zkvm_runtime_secs += started.elapsed().as_secs();
+                    self.last_task_status = "Proved".to_string();
+                }
+            }
+            EventPayload::ProofFailed { .. } => {
+                self.last_task_status = "Proof Failed".to_string();
+                self.proving_since = None;
+            }
+            EventPayload::ProofSubmitted { .. } => {
+                // A submission without a matching fetch means the metrics started after the
+                // task began
+                self.tasks_submitted += 1;
+                self.tasks_fetched = self.tasks_fetched.max(self.tasks_submitted);
+                self.last_task_status = "Success".to_string();
+                self.degraded = false;
+            }
+            EventPayload::SubmissionFailed { .. } => {
+                self.last_task_status = "Submit Failed".to_string();
+            }
+            EventPayload::TaskCompleted {
+                duration_secs,
+                difficulty,
+                task_type,
+                ..
+            } => self.record_task_submitted(*task_type, *difficulty, *duration_secs),
+            EventPayload::WorkerCrashed { .. } => self.worker_restarts += 1,
+            EventPayload::CrashLoop { .. } => self.degraded = true,
+            EventPayload::FetchStarted
+            | EventPayload::FetchFailed
+            | EventPayload::RateLimited { .. }
+            | EventPayload::ProofCacheHit { .. }
+            | EventPayload::ProofSlowdown { .. } => {}
+        }
+    }
+
+    /// Count a task of the given type and difficulty as started.
+    pub fn record_task_started(&mut self, task_type: TaskType, difficulty: TaskDifficulty) {
+        self.by_task_type.entry(task_type).or_default().fetched += 1;
+        self.by_difficulty.entry(difficulty).or_default().fetched += 1;
+    }
+
+    /// Count a task of the given type and difficulty as submitted after `duration_secs`.
+    pub fn record_task_submitted(
+        &mut self,
+        task_type: TaskType,
+        difficulty: TaskDifficulty,
+        duration_secs: u64,
+    ) {
+        for breakdown in [
+            self.by_task_type.entry(task_type).or_default(),
+            self.by_difficulty.entry(difficulty).or_default(),
+        ] {
+            breakdown.submitted += 1;
+            breakdown.total_duration_secs += duration_secs;
+        }
+    }
+
     /// Calculate success rate as a percentage.
     pub fn success_rate(&self) -> f64 {
         if self.tasks_fetched == 0 {
@@ -223,28 +327,15 @@ impl ZkVMMetrics {
         }
     }
 
-    /// Format total points with commas for better readability.
-    pub fn _format_points(&self) -> String {
-        let points = self._total_points;
-        if points >= 1_000_000 {
-            format!("{:.1}M", points as f64 / 1_000_000.0)
-        } else if points >= 1_000 {
-            format!("{},{:03}", points / 1_000, points % 1_000)
-        } else {
-            points.to_string()
-        }
-    }
-
     /// Get success rate color based on performance.
-    pub fn success_rate_color(&self) -> ratatui::prelude::Color {
-        use ratatui::prelude::Color;
+    pub fn success_rate_color(&self, theme: &Theme) -> Color {
         let rate = self.success_rate();
         if rate >= 75.0 {
-            Color::Green
+            theme.success
         } else if rate >= 50.0 {
-            Color::Yellow
+            theme.warning
         } else {
-            Color::Red
+            theme.error
         }
     }
 
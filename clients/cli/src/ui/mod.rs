@@ -1,8 +1,13 @@
 // Module declarations
 mod app;
 pub mod dashboard;
+mod fleet;
 mod login;
-mod metrics;
+pub mod metrics;
+mod plain;
 pub mod splash;
+pub mod theme;
 // Re-exports for external use
-pub use app::{App, UIConfig, run};
+pub use app::{App, UIConfig, UiExit, UiMode, run};
+pub use dashboard::DashboardLayout;
+pub use fleet::run_fleet_ui;
@@ -0,0 +1,76 @@
+//! Plain-text rendering for `--ui plain`
+//!
+//! Screen readers and dumb terminals cannot make sense of colors, box-drawing characters and
+//! block-element gauges. In plain mode every frame is drawn as usual and then flattened:
+//! styles are reset to the terminal default and drawing symbols are replaced by ASCII
+//! look-alikes. Letters are kept, so translated messages still read correctly.
+
+use ratatui::buffer::Buffer;
+use ratatui::style::Style;
+
+/// Reset styles and replace drawing symbols in every cell of `buffer`.
+pub fn flatten(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        cell.set_style(Style::reset());
+        if let Some(ascii) = ascii_symbol(cell.symbol()) {
+            cell.set_symbol(ascii);
+        }
+    }
+}
+
+/// ASCII replacement for a non-ASCII drawing symbol, or `None` if the symbol can stay.
+fn ascii_symbol(symbol: &str) -> Option<&'static str> {
+    let mut chars = symbol.chars();
+    let c = chars.next()?;
+    if symbol.is_ascii() || (c.is_alphanumeric() && chars.next().is_none()) {
+        return None;
+    }
+    Some(match c {
+        '─' | '━' | '═' | '╌' | '╍' | '┄' | '┅' => "-",
+        '│' | '┃' | '║' | '╎' | '╏' | '┆' | '┇' => "|",
+        // Corners and junctions
+        '┌'..='╋' | '╒'..='╬' | '╭'..='╰' => "+",
+        // Gauges, sparklines and bar charts
+        '▀'..='▟' => "#",
+        '→' | '⟶' => ">",
+        '←' => "<",
+        '•' | '·' => "*",
+        '…' => "...",
+        '°' => " ",
+        // Emoji and any other pictographs
+        _ => "?",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Color;
+
+    #[test]
+    fn test_ascii_symbol() {
+        assert_eq!(ascii_symbol("a"), None);
+        assert_eq!(ascii_symbol("Ä"), None);
+        assert_eq!(ascii_symbol("─"), Some("-"));
+        assert_eq!(ascii_symbol("│"), Some("|"));
+        assert_eq!(ascii_symbol("╭"), Some("+"));
+        assert_eq!(ascii_symbol("┼"), Some("+"));
+        assert_eq!(ascii_symbol("█"), Some("#"));
+        assert_eq!(ascii_symbol("▁"), Some("#"));
+        assert_eq!(ascii_symbol("→"), Some(">"));
+        assert_eq!(ascii_symbol("°"), Some(" "));
+        assert_eq!(ascii_symbol("✅"), Some("?"));
+    }
+
+    #[test]
+    fn test_flatten() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buffer.set_string(0, 0, "╭█a", Style::default().fg(Color::Cyan));
+        flatten(&mut buffer);
+
+        let symbols: String = buffer.content.iter().map(|cell| cell.symbol()).collect();
+        assert_eq!(symbols, "+#a");
+        assert!(buffer.content.iter().all(|cell| cell.fg == Color::Reset));
+    }
+}
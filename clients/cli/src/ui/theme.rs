@@ -0,0 +1,435 @@
+//! Dashboard color themes
+//!
+//! A theme assigns a color to each role the dashboard draws with, such as borders, labels or
+//! errors. A few themes are built in; more can be added as TOML files in `~/.nexus/themes/`:
+//!
+//! ```toml
+//! # ~/.nexus/themes/ocean.toml
+//! base = "default"     # built-in theme to start from (optional)
+//! primary = "#5fd7ff"  # color names, `#rrggbb` or 256-color indices
+//! border = "blue"
+//! ```
+//!
+//! The theme is named after its file. The selected theme is stored as `theme` in the config
+//! file; it is chosen with `nexus-cli theme set` or by pressing `T` in the dashboard.
+
+use crate::cli_messages::{print_success, print_warn};
+use crate::config::{Config, ConfigError};
+use crate::exit_code::UsageError;
+use crate::tr;
+use crossterm::style::Stylize;
+use ratatui::style::Color;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const THEMES_DIR: &str = "themes";
+
+/// Theme used when none is selected or the selected one no longer exists
+pub const DEFAULT_THEME: &str = "default";
+
+/// Color roles, as named in theme files.
+pub const ROLES: [&str; 13] = [
+    "background",
+    "text",
+    "label",
+    "muted",
+    "border",
+    "primary",
+    "active",
+    "info",
+    "highlight",
+    "accent",
+    "success",
+    "warning",
+    "error",
+];
+
+/// A user theme file that could not be loaded
+#[derive(Debug, thiserror::Error)]
+pub enum ThemeError {
+    #[error("{} {}: {}", tr!("theme-error-unreadable"), .path.display(), .source)]
+    Unreadable {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("{} {}: {}", tr!("theme-error-invalid"), .path.display(), .source)]
+    Invalid {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+
+    #[error("{} {}: {}", tr!("theme-error-unknown-role"), .path.display(), .role)]
+    UnknownRole { path: PathBuf, role: String },
+
+    #[error("{} {}: {} = {}", tr!("theme-error-invalid-color"), .path.display(), .role, .color)]
+    InvalidColor {
+        path: PathBuf,
+        role: String,
+        color: String,
+    },
+
+    #[error("{} {}: {}", tr!("theme-error-unknown-base"), .path.display(), .base)]
+    UnknownBase { path: PathBuf, base: String },
+}
+
+/// Colors the dashboard draws with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    /// Background with `--with-background`, and text on filled bars
+    pub background: Color,
+    /// Plain values
+    pub text: Color,
+    /// Metric labels and secondary text
+    pub label: Color,
+    /// Timestamps and separators
+    pub muted: Color,
+    /// Panel borders
+    pub border: Color,
+    /// Titles, the task fetcher and bar charts
+    pub primary: Color,
+    /// Proving in progress and uptime
+    pub active: Color,
+    /// Node details and waiting for tasks
+    pub info: Color,
+    /// Update notices and points
+    pub highlight: Color,
+    /// GPUs and the rate limit panel
+    pub accent: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_THEME.to_string(),
+            background: Color::Rgb(16, 20, 24),
+            text: Color::White,
+            label: Color::Gray,
+            muted: Color::DarkGray,
+            border: Color::Cyan,
+            primary: Color::Cyan,
+            active: Color::LightGreen,
+            info: Color::LightBlue,
+            highlight: Color::LightYellow,
+            accent: Color::LightMagenta,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+        }
+    }
+}
+
+impl Theme {
+    /// Color for `role`, one of [`ROLES`].
+    pub fn color(&self, role: &str) -> Option<Color> {
+        Some(match role {
+            "background" => self.background,
+            "text" => self.text,
+            "label" => self.label,
+            "muted" => self.muted,
+            "border" => self.border,
+            "primary" => self.primary,
+            "active" => self.active,
+            "info" => self.info,
+            "highlight" => self.highlight,
+            "accent" => self.accent,
+            "success" => self.success,
+            "warning" => self.warning,
+            "error" => self.error,
+            _ => return None,
+        })
+    }
+
+    fn color_mut(&mut self, role: &str) -> Option<&mut Color> {
+        Some(match role {
+            "background" => &mut self.background,
+            "text" => &mut self.text,
+            "label" => &mut self.label,
+            "muted" => &mut self.muted,
+            "border" => &mut self.border,
+            "primary" => &mut self.primary,
+            "active" => &mut self.active,
+            "info" => &mut self.info,
+            "highlight" => &mut self.highlight,
+            "accent" => &mut self.accent,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            _ => return None,
+        })
+    }
+
+    /// Parse a theme file named `name`, starting from the built-in theme it names as `base`.
+    fn parse(name: &str, source: &str, path: &Path) -> Result<Self, ThemeError> {
+        let mut values: BTreeMap<String, String> =
+            toml::from_str(source).map_err(|source| ThemeError::Invalid {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        let base = values
+            .remove("base")
+            .unwrap_or_else(|| DEFAULT_THEME.to_string());
+        let mut theme = builtin_themes()
+            .into_iter()
+            .find(|theme| theme.name == base)
+            .ok_or_else(|| ThemeError::UnknownBase {
+                path: path.to_path_buf(),
+                base,
+            })?;
+        theme.name = name.to_string();
+
+        for (role, value) in values {
+            let color = Color::from_str(&value).map_err(|_| ThemeError::InvalidColor {
+                path: path.to_path_buf(),
+                role: role.clone(),
+                color: value.clone(),
+            })?;
+            match theme.color_mut(&role) {
+                Some(slot) => *slot = color,
+                None => {
+                    return Err(ThemeError::UnknownRole {
+                        path: path.to_path_buf(),
+                        role,
+                    });
+                }
+            }
+        }
+        Ok(theme)
+    }
+}
+
+/// Built-in themes: the default dark theme, one for light terminals and a high-contrast one.
+pub fn builtin_themes() -> Vec<Theme> {
+    let light = Theme {
+        name: "light".to_string(),
+        background: Color::White,
+        text: Color::Black,
+        label: Color::DarkGray,
+        muted: Color::Gray,
+        border: Color::Blue,
+        primary: Color::Blue,
+        active: Color::Green,
+        info: Color::Blue,
+        highlight: Color::Magenta,
+        accent: Color::Magenta,
+        success: Color::Green,
+        warning: Color::Rgb(181, 137, 0),
+        error: Color::Red,
+    };
+    let high_contrast = Theme {
+        name: "high-contrast".to_string(),
+        background: Color::Black,
+        text: Color::White,
+        label: Color::White,
+        muted: Color::Gray,
+        border: Color::White,
+        primary: Color::LightCyan,
+        active: Color::LightGreen,
+        info: Color::LightCyan,
+        highlight: Color::LightYellow,
+        accent: Color::LightMagenta,
+        success: Color::LightGreen,
+        warning: Color::LightYellow,
+        error: Color::LightRed,
+    };
+    vec![Theme::default(), light, high_contrast]
+}
+
+/// Directory of user themes, `~/.nexus/themes` next to the config file.
+pub fn themes_dir(config_path: &Path) -> PathBuf {
+    config_path.with_file_name(THEMES_DIR)
+}
+
+/// User themes from `*.toml` files in `dir`, sorted by name. A missing directory has no themes.
+pub fn load_user_themes(dir: &Path) -> Vec<Result<Theme, ThemeError>> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            let source =
+                std::fs::read_to_string(&path).map_err(|source| ThemeError::Unreadable {
+                    path: path.clone(),
+                    source,
+                })?;
+            Theme::parse(&name, &source, &path)
+        })
+        .collect()
+}
+
+/// Built-in and valid user themes. A user theme replaces a built-in theme of the same name.
+pub fn available_themes(dir: &Path) -> Vec<Theme> {
+    let mut themes = builtin_themes();
+    for theme in load_user_themes(dir).into_iter().flatten() {
+        match themes.iter_mut().find(|t| t.name == theme.name) {
+            Some(existing) => *existing = theme,
+            None => themes.push(theme),
+        }
+    }
+    themes
+}
+
+/// The theme named `name`, or the default theme if it is unset or no longer available.
+pub fn resolve(dir: &Path, name: Option<&str>) -> Theme {
+    let name = name.unwrap_or(DEFAULT_THEME);
+    available_themes(dir)
+        .into_iter()
+        .find(|theme| theme.name == name)
+        .unwrap_or_default()
+}
+
+/// Print the available themes, marking the selected one, and any user theme that failed to load.
+pub fn list_themes(config_path: &Path) -> Result<(), Box<dyn Error>> {
+    let config = Config::load_or_default(config_path)?;
+    let selected = config.theme.as_deref().unwrap_or(DEFAULT_THEME);
+    let dir = themes_dir(config_path);
+    for theme in available_themes(&dir) {
+        let marker = if theme.name == selected { "*" } else { " " };
+        println!("{} {}", marker, theme.name);
+    }
+    for error in load_user_themes(&dir).into_iter().filter_map(Result::err) {
+        print_warn(&tr!("theme-skipped"), &error.to_string());
+    }
+    Ok(())
+}
+
+/// Select the theme the dashboard starts with.
+pub fn set_theme(config_path: &Path, name: &str) -> Result<(), Box<dyn Error>> {
+    let dir = themes_dir(config_path);
+    if !available_themes(&dir)
+        .iter()
+        .any(|theme| theme.name == name)
+    {
+        return Err(UsageError(tr!("theme-not-found", name = name)).into());
+    }
+    let mut config = Config::load_or_default(config_path)?;
+    config.theme = Some(name.to_string());
+    config.save(config_path).map_err(ConfigError::from)?;
+    print_success(&tr!("theme-selected"), name);
+    Ok(())
+}
+
+/// Print a swatch of every color in the theme named `name`.
+pub fn preview_theme(config_path: &Path, name: &str) -> Result<(), Box<dyn Error>> {
+    let theme = available_themes(&themes_dir(config_path))
+        .into_iter()
+        .find(|theme| theme.name == name)
+        .ok_or_else(|| UsageError(tr!("theme-not-found", name = name)))?;
+    println!("{}", theme.name);
+    for role in ROLES {
+        let color = theme.color(role).unwrap_or_default();
+        println!(
+            "  {} {:<12} {}",
+            "██████".with(to_crossterm(color)),
+            role,
+            color
+        );
+    }
+    Ok(())
+}
+
+/// The crossterm equivalent of a ratatui color, for printing outside the dashboard.
+fn to_crossterm(color: Color) -> crossterm::style::Color {
+    use crossterm::style::Color as C;
+    match color {
+        Color::Reset => C::Reset,
+        Color::Black => C::Black,
+        Color::Red => C::DarkRed,
+        Color::Green => C::DarkGreen,
+        Color::Yellow => C::DarkYellow,
+        Color::Blue => C::DarkBlue,
+        Color::Magenta => C::DarkMagenta,
+        Color::Cyan => C::DarkCyan,
+        Color::Gray => C::Grey,
+        Color::DarkGray => C::DarkGrey,
+        Color::LightRed => C::Red,
+        Color::LightGreen => C::Green,
+        Color::LightYellow => C::Yellow,
+        Color::LightBlue => C::Blue,
+        Color::LightMagenta => C::Magenta,
+        Color::LightCyan => C::Cyan,
+        Color::White => C::White,
+        Color::Rgb(r, g, b) => C::Rgb { r, g, b },
+        Color::Indexed(i) => C::AnsiValue(i),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_theme_file() {
+        let path = Path::new("ocean.toml");
+        let theme = Theme::parse(
+            "ocean",
+            "base = \"light\"\nprimary = \"#5fd7ff\"\nborder = \"blue\"\n",
+            path,
+        )
+        .unwrap();
+        assert_eq!(theme.name, "ocean");
+        assert_eq!(theme.primary, Color::Rgb(0x5f, 0xd7, 0xff));
+        assert_eq!(theme.border, Color::Blue);
+        // Unset roles come from the base theme
+        assert_eq!(theme.text, Color::Black);
+
+        assert!(matches!(
+            Theme::parse("x", "primay = \"red\"", path),
+            Err(ThemeError::UnknownRole { .. })
+        ));
+        assert!(matches!(
+            Theme::parse("x", "primary = \"reddish\"", path),
+            Err(ThemeError::InvalidColor { .. })
+        ));
+        assert!(matches!(
+            Theme::parse("x", "base = \"nope\"", path),
+            Err(ThemeError::UnknownBase { .. })
+        ));
+        assert!(matches!(
+            Theme::parse("x", "primary = 3", path),
+            Err(ThemeError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_user_themes_and_selection() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        let themes = themes_dir(&config_path);
+        std::fs::create_dir_all(&themes).unwrap();
+        std::fs::write(themes.join("ocean.toml"), "primary = \"blue\"").unwrap();
+        std::fs::write(themes.join("light.toml"), "text = \"red\"").unwrap();
+        std::fs::write(themes.join("broken.toml"), "text = \"nope\"").unwrap();
+        std::fs::write(themes.join("notes.txt"), "ignored").unwrap();
+
+        let names: Vec<String> = available_themes(&themes)
+            .into_iter()
+            .map(|theme| theme.name)
+            .collect();
+        assert_eq!(names, vec!["default", "light", "high-contrast", "ocean"]);
+        assert_eq!(resolve(&themes, Some("light")).text, Color::Red);
+        assert_eq!(resolve(&themes, Some("gone")), Theme::default());
+
+        set_theme(&config_path, "ocean").unwrap();
+        assert!(set_theme(&config_path, "broken").is_err());
+        let config = Config::load_from_file(&config_path).unwrap();
+        assert_eq!(config.theme.as_deref(), Some("ocean"));
+    }
+}
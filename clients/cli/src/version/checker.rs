@@ -1,6 +1,11 @@
 //! Version Checking Module
 //!
-//! Checks for new versions of the CLI by querying the GitHub API
+//! Checks for new versions of the CLI by querying the GitHub API. Releases are resolved per update
+//! channel (`update.channel` in the config file):
+//!
+//! - `stable`: the latest full release
+//! - `beta`: the newest release including pre-releases, i.e. release candidates
+//! - `nightly`: the rolling release tagged `nightly`, newer when published after this build
 //!
 //! # Mock Testing
 //!
@@ -77,6 +82,8 @@
 use reqwest::{Client, ClientBuilder};
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 
 #[cfg(test)]
@@ -86,6 +93,58 @@ use mockall::{automock, predicate::*};
 const GITHUB_RELEASES_URL: &str =
     "https://api.github.com/repos/nexus-xyz/nexus-cli/releases/latest";
 
+// GitHub API endpoint for recent releases, including pre-releases
+const GITHUB_RECENT_RELEASES_URL: &str =
+    "https://api.github.com/repos/nexus-xyz/nexus-cli/releases?per_page=30";
+
+// GitHub API endpoint for the rolling nightly release
+const GITHUB_NIGHTLY_RELEASE_URL: &str =
+    "https://api.github.com/repos/nexus-xyz/nexus-cli/releases/tags/nightly";
+
+/// Release stream that update checks and `nexus-cli update` follow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+    Nightly,
+}
+
+impl fmt::Display for UpdateChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Stable => "stable",
+            Self::Beta => "beta",
+            Self::Nightly => "nightly",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for UpdateChannel {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "stable" => Ok(Self::Stable),
+            "beta" => Ok(Self::Beta),
+            "nightly" => Ok(Self::Nightly),
+            _ => Err(format!(
+                "unknown update channel '{}', expected stable, beta or nightly",
+                value
+            )),
+        }
+    }
+}
+
+/// `update` section of the config file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateSettings {
+    pub channel: UpdateChannel,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitHubRelease {
     pub tag_name: String,
@@ -93,6 +152,39 @@ pub struct GitHubRelease {
     pub published_at: String,
     pub html_url: String,
     pub prerelease: bool,
+    /// Binaries attached to the release
+    #[serde(default)]
+    pub assets: Vec<GitHubAsset>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl GitHubRelease {
+    /// Download URL of the binary for this platform, if the release has one
+    pub fn platform_asset(&self) -> Option<&GitHubAsset> {
+        self.asset(platform_asset_name()?)
+    }
+
+    /// Asset of this release, by file name
+    pub fn asset(&self, name: &str) -> Option<&GitHubAsset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// Name of the release binary for this platform, as published by the release workflow
+pub fn platform_asset_name() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Some("nexus-network-linux-x86_64"),
+        ("linux", "aarch64") => Some("nexus-network-linux-arm64"),
+        ("macos", "x86_64") => Some("nexus-network-macos-x86_64"),
+        ("macos", "aarch64") => Some("nexus-network-macos-arm64"),
+        ("windows", "x86_64") => Some("nexus-network-windows-x86_64.exe"),
+        _ => None,
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -116,12 +208,24 @@ impl VersionInfo {
     }
 
     pub fn update_from_release(&mut self, release: GitHubRelease) {
-        self.latest_version = Some(release.tag_name.clone());
+        self.update_available = self.is_newer_release(&release);
+        self.latest_version = Some(release.tag_name);
         self.release_url = Some(release.html_url);
-        self.update_available = self.is_newer_version(&release.tag_name);
         self.last_check = Some(Instant::now());
     }
 
+    /// Whether the release is newer than this build. Releases without a version, such as the
+    /// nightly release, are newer if they were published after this build.
+    fn is_newer_release(&self, release: &GitHubRelease) -> bool {
+        if parse_version(&release.tag_name).is_ok() {
+            return self.is_newer_version(&release.tag_name);
+        }
+        let published_at = chrono::DateTime::parse_from_rfc3339(&release.published_at)
+            .map(|time| time.timestamp_millis());
+        let built_at = crate::orchestrator::client::BUILD_TIMESTAMP.parse::<i64>();
+        matches!((published_at, built_at), (Ok(published), Ok(built)) if published > built)
+    }
+
     /// Compare semantic versions to determine if the latest version is newer
     fn is_newer_version(&self, latest: &str) -> bool {
         match (parse_version(&self.current_version), parse_version(latest)) {
@@ -150,6 +254,7 @@ pub trait VersionCheckable: Send + Sync {
 /// Version checker client for making GitHub API requests
 pub struct VersionChecker {
     client: Client,
+    channel: UpdateChannel,
 }
 
 impl VersionChecker {
@@ -160,30 +265,64 @@ impl VersionChecker {
             .build()
             .expect("Failed to create HTTP client for version checker");
 
-        Self { client }
+        Self {
+            client,
+            channel: UpdateChannel::Stable,
+        }
+    }
+
+    /// Resolve releases on `channel` instead of the stable channel.
+    pub fn with_channel(mut self, channel: UpdateChannel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+        let response = self.client.get(url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(format!("GitHub API returned status: {}", response.status()).into());
+        }
+
+        Ok(response.json().await?)
     }
 }
 
+/// Newest release by version, pre-releases included
+fn newest_release(releases: Vec<GitHubRelease>) -> Option<GitHubRelease> {
+    releases
+        .into_iter()
+        .filter_map(|release| Some((parse_version(&release.tag_name).ok()?, release)))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, release)| release)
+}
+
 #[async_trait::async_trait]
 impl VersionCheckable for VersionChecker {
-    /// Check for latest version from GitHub API
+    /// Check for latest version on the channel from GitHub API
     async fn check_latest_version(
         &self,
     ) -> Result<GitHubRelease, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.client.get(GITHUB_RELEASES_URL).send().await?;
-
-        if !response.status().is_success() {
-            return Err(format!("GitHub API returned status: {}", response.status()).into());
+        match self.channel {
+            UpdateChannel::Stable => self.get_json(GITHUB_RELEASES_URL).await,
+            UpdateChannel::Beta => {
+                let releases = self.get_json(GITHUB_RECENT_RELEASES_URL).await?;
+                newest_release(releases).ok_or_else(|| "No beta release found".into())
+            }
+            UpdateChannel::Nightly => self.get_json(GITHUB_NIGHTLY_RELEASE_URL).await,
         }
-
-        let release: GitHubRelease = response.json().await?;
-        Ok(release)
     }
 }
 
-/// Check if a new version is available and return notification message
-pub async fn check_for_new_version(current_version: &str) -> Option<String> {
-    let version_checker = VersionChecker::new(current_version.to_string());
+/// Check if a new version is available on the channel and return notification message
+pub async fn check_for_new_version(
+    current_version: &str,
+    channel: UpdateChannel,
+) -> Option<String> {
+    let version_checker = VersionChecker::new(current_version.to_string()).with_channel(channel);
 
     if let Ok(release) = version_checker.check_latest_version().await {
         let mut version_info = VersionInfo::new(current_version.to_string());
@@ -236,6 +375,7 @@ mod tests {
             published_at: "2024-01-01T00:00:00Z".to_string(),
             html_url: "https://github.com/nexus-xyz/nexus-cli/releases/tag/v0.9.1".to_string(),
             prerelease: false,
+            assets: Vec::new(),
         };
 
         info.update_from_release(release);
@@ -268,4 +408,46 @@ mod tests {
         assert!(!info_100.is_newer_version("not.a.version"));
         assert!(!info_100.is_newer_version(""));
     }
+
+    fn release(tag_name: &str, prerelease: bool) -> GitHubRelease {
+        GitHubRelease {
+            tag_name: tag_name.to_string(),
+            name: tag_name.to_string(),
+            published_at: "2024-01-01T00:00:00Z".to_string(),
+            html_url: String::new(),
+            prerelease,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_beta_channel_picks_newest_prerelease() {
+        let newest = newest_release(vec![
+            release("v0.10.0", false),
+            release("v0.11.0-rc.2", true),
+            release("nightly", true),
+            release("v0.11.0-rc.1", true),
+        ])
+        .unwrap();
+        assert_eq!(newest.tag_name, "v0.11.0-rc.2");
+
+        // A final release supersedes its release candidates
+        let newest = newest_release(vec![
+            release("v0.11.0-rc.2", true),
+            release("v0.11.0", false),
+        ]);
+        assert_eq!(newest.unwrap().tag_name, "v0.11.0");
+        assert!(newest_release(vec![release("nightly", true)]).is_none());
+    }
+
+    #[test]
+    fn test_update_channel_parsing() {
+        assert_eq!("beta".parse(), Ok(UpdateChannel::Beta));
+        assert_eq!(" Nightly ".parse(), Ok(UpdateChannel::Nightly));
+        assert!("canary".parse::<UpdateChannel>().is_err());
+        assert_eq!(UpdateChannel::default().to_string(), "stable");
+
+        let settings: UpdateSettings = serde_json::from_str(r#"{"channel":"beta"}"#).unwrap();
+        assert_eq!(settings.channel, UpdateChannel::Beta);
+    }
 }
@@ -1,15 +1,94 @@
 //! Version management and validation with improved error messages
+//!
+//! Version requirements are gates on starting a session. Warnings and notices are soft gates:
+//! the session starts and the requirement is shown in the dashboard banner or the headless log.
+//! Blocking requirements are hard gates that stop `start`, unless overridden with
+//! `--override-version-check`, in which case they are shown like a soft gate.
+use super::requirements::VersionCheckResult;
 use super::{ConstraintType, VersionRequirements};
+use crate::exit_code::ExitCode;
 use std::error::Error;
 
-/// Validates version requirements before application startup
-pub async fn validate_version_requirements() -> Result<(), Box<dyn Error>> {
+/// Outcome of checking this version against the version requirements
+#[derive(Debug, Clone, PartialEq)]
+pub enum VersionGate {
+    /// No requirement is violated
+    Pass,
+    /// A soft requirement is violated; start, but show it
+    Warn(VersionCheckResult),
+    /// A hard requirement is violated; do not start
+    Block(VersionCheckResult),
+    /// A hard requirement is violated, but the check was overridden; start, but show it
+    Overridden(VersionCheckResult),
+}
+
+impl VersionGate {
+    /// Gate for the most severe violated requirement, if any.
+    pub fn new(violation: Option<VersionCheckResult>, override_check: bool) -> Self {
+        match violation {
+            None => Self::Pass,
+            Some(violation) => match violation.constraint_type {
+                ConstraintType::Blocking if override_check => Self::Overridden(violation),
+                ConstraintType::Blocking => Self::Block(violation),
+                ConstraintType::Warning | ConstraintType::Notice => Self::Warn(violation),
+            },
+        }
+    }
+
+    /// Requirement to keep showing while the session runs
+    pub fn into_notice(self) -> Option<VersionNotice> {
+        match self {
+            Self::Pass | Self::Block(_) => None,
+            Self::Warn(violation) => Some(VersionNotice {
+                overridden: false,
+                violation,
+            }),
+            Self::Overridden(violation) => Some(VersionNotice {
+                overridden: true,
+                violation,
+            }),
+        }
+    }
+}
+
+/// A violated version requirement that the session was started despite
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionNotice {
+    pub violation: VersionCheckResult,
+    /// Whether this is a blocking requirement bypassed with `--override-version-check`
+    pub overridden: bool,
+}
+
+impl VersionNotice {
+    /// One-line description for the headless log
+    pub fn log_line(&self) -> String {
+        let kind = match (&self.violation.constraint_type, self.overridden) {
+            (ConstraintType::Blocking, true) => "blocked version, check overridden",
+            (ConstraintType::Blocking, false) => "blocked version",
+            (ConstraintType::Warning, _) => "warning",
+            (ConstraintType::Notice, _) => "notice",
+        };
+        format!(
+            "Version requirement ({}): {}",
+            kind,
+            self.violation.message.replace('\n', " ")
+        )
+    }
+}
+
+/// Validates version requirements before application startup.
+///
+/// Exits the process if this version is blocked and `override_check` is not set. Returns the
+/// violated requirement the session starts despite, if any.
+pub async fn validate_version_requirements(
+    override_check: bool,
+) -> Result<Option<VersionNotice>, Box<dyn Error>> {
     // Single attempt since VersionRequirements::fetch already tries multiple hostnames
     let requirements = match VersionRequirements::fetch().await {
         Ok(requirements) => requirements,
         Err(e) => {
             handle_fetch_error(&e);
-            std::process::exit(1);
+            ExitCode::Network.exit();
         }
     };
 
@@ -33,26 +112,23 @@ pub async fn validate_version_requirements() -> Result<(), Box<dyn Error>> {
             "Due to OFAC regulations, this service is not available in {}.\nSee https://nexus.xyz/terms-of-use for more information.",
             display_name
         );
-        std::process::exit(1);
+        ExitCode::RegionBlocked.exit();
     }
 
-    match requirements.check_version_constraints(current_version, None, None) {
-        Ok(Some(violation)) => {
-            handle_version_violation(&violation.constraint_type, &violation.message);
-        }
-        Ok(None) => {
-            // No violations found, continue normally
-        }
+    let violation = match requirements.check_version_constraints(current_version, None, None) {
+        Ok(violation) => violation,
         Err(e) => {
             eprintln!("❌ Failed to parse version requirements: {}", e);
             eprintln!(
                 "If this issue persists, please file a bug report at: https://github.com/nexus-xyz/nexus-cli/issues/new"
             );
-            std::process::exit(1);
+            ExitCode::Failure.exit();
         }
-    }
+    };
 
-    Ok(())
+    let gate = VersionGate::new(violation, override_check);
+    handle_version_gate(&gate);
+    Ok(gate.into_notice())
 }
 
 /// Provides user-friendly error messages for fetch failures
@@ -63,25 +139,78 @@ fn handle_fetch_error(error: &dyn Error) {
     eprintln!("If the issue persists, report it at: https://github.com/nexus-xyz/nexus-cli/issues");
 }
 
-/// Handles different types of version constraint violations
-fn handle_version_violation(constraint_type: &ConstraintType, message: &str) {
-    match constraint_type {
-        ConstraintType::Blocking => {
+/// Print the outcome of the version check, exiting if this version is blocked
+fn handle_version_gate(gate: &VersionGate) {
+    match gate {
+        VersionGate::Pass => {}
+        VersionGate::Block(violation) => {
             eprintln!("❌ Version requirement not met\n");
-            eprintln!("{}\n", message);
+            eprintln!("{}\n", violation.message);
             eprintln!("To resolve this issue:");
             eprintln!("  • Download the latest version from:");
             eprintln!("    https://github.com/nexus-xyz/nexus-cli/releases");
-            std::process::exit(1);
+            eprintln!("  • Or, at your own risk, start anyway with --override-version-check");
+            ExitCode::VersionBlocked.exit();
         }
-        ConstraintType::Warning => {
-            eprintln!("⚠️  Version Warning");
-            eprintln!("{}", message);
-            eprintln!("Consider updating your CLI for the best experience.\n");
+        VersionGate::Overridden(violation) => {
+            eprintln!(
+                "⚠️  Version requirement not met, starting anyway (--override-version-check)"
+            );
+            eprintln!("{}", violation.message);
+            eprintln!("Proofs from this version may be rejected.\n");
         }
-        ConstraintType::Notice => {
-            eprintln!("ℹ️  Notice");
-            eprintln!("{}\n", message);
+        VersionGate::Warn(violation) => match violation.constraint_type {
+            ConstraintType::Notice => {
+                eprintln!("ℹ️  Notice");
+                eprintln!("{}\n", violation.message);
+            }
+            _ => {
+                eprintln!("⚠️  Version Warning");
+                eprintln!("{}", violation.message);
+                eprintln!("Consider updating your CLI for the best experience.\n");
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(constraint_type: ConstraintType) -> Option<VersionCheckResult> {
+        Some(VersionCheckResult {
+            constraint_type,
+            message: "Please update".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_hard_gates_block_unless_overridden() {
+        assert_eq!(VersionGate::new(None, false), VersionGate::Pass);
+        assert!(matches!(
+            VersionGate::new(violation(ConstraintType::Blocking), false),
+            VersionGate::Block(_)
+        ));
+
+        let notice = VersionGate::new(violation(ConstraintType::Blocking), true)
+            .into_notice()
+            .unwrap();
+        assert!(notice.overridden);
+        assert!(notice.log_line().contains("check overridden"));
+    }
+
+    #[test]
+    fn test_soft_gates_start_with_notice() {
+        for constraint_type in [ConstraintType::Warning, ConstraintType::Notice] {
+            // Overriding makes no difference to soft gates
+            for override_check in [false, true] {
+                let gate = VersionGate::new(violation(constraint_type.clone()), override_check);
+                assert!(matches!(gate, VersionGate::Warn(_)));
+                let notice = gate.into_notice().unwrap();
+                assert!(!notice.overridden);
+                assert_eq!(notice.violation.message, "Please update");
+            }
         }
+        assert_eq!(VersionGate::Pass.into_notice(), None);
     }
 }
@@ -1,5 +1,7 @@
 pub mod checker;
 pub mod manager;
 pub mod requirements;
+pub mod updater;
+pub mod verify;
 
 pub use requirements::{ConstraintType, VersionRequirements};
@@ -0,0 +1,138 @@
+//! Self-update
+//!
+//! `nexus-cli update` resolves the newest release on the update channel and replaces the running
+//! binary with the release binary for this platform, once it passes [`super::verify`].
+
+use super::checker::{GitHubAsset, UpdateChannel, VersionCheckable, VersionChecker, VersionInfo};
+use super::verify::{ReleaseFiles, verify_release};
+use crate::cli_messages::{print_info, print_success};
+use crate::config::Config;
+use crate::tr;
+use std::error::Error;
+use std::path::Path;
+use std::time::Duration;
+
+/// Upper bound for downloading a release binary
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Update to the newest release on `channel`, or on the channel from the config file.
+///
+/// With `check_only`, only reports whether an update is available.
+pub async fn update(
+    config_path: &Path,
+    channel: Option<UpdateChannel>,
+    check_only: bool,
+) -> Result<(), Box<dyn Error>> {
+    let channel = match channel {
+        Some(channel) => channel,
+        None => Config::load_or_default(config_path)?.update.channel,
+    };
+    let current_version = env!("CARGO_PKG_VERSION");
+    let release = VersionChecker::new(current_version.to_string())
+        .with_channel(channel)
+        .check_latest_version()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut info = VersionInfo::new(current_version.to_string());
+    info.update_from_release(release.clone());
+    if !info.update_available {
+        print_success(
+            &tr!("update-up-to-date"),
+            &tr!(
+                "update-up-to-date-details",
+                version = current_version,
+                channel = channel
+            ),
+        );
+        return Ok(());
+    }
+
+    print_info(
+        &tr!("update-available"),
+        &tr!(
+            "update-available-details",
+            latest = release.tag_name,
+            channel = channel,
+            version = current_version
+        ),
+    );
+    if check_only {
+        println!("{}", tr!("update-install-hint"));
+        return Ok(());
+    }
+
+    let asset = release.platform_asset().ok_or_else(|| {
+        tr!(
+            "update-no-asset",
+            latest = release.tag_name,
+            url = release.html_url
+        )
+    })?;
+    // Fail closed: without a checksum and signature, the binary is not installed
+    let unverified = |reason: String| {
+        tr!(
+            "update-unverified",
+            latest = release.tag_name,
+            reason = reason
+        )
+    };
+    let checksum_name = format!("{}.sha256", asset.name);
+    let signature_name = format!("{}.sig", asset.name);
+    let (Some(checksum_asset), Some(signature_asset)) = (
+        release.asset(&checksum_name),
+        release.asset(&signature_name),
+    ) else {
+        return Err(unverified(format!("missing {} or {}", checksum_name, signature_name)).into());
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .user_agent(format!("nexus-cli/{}", current_version))
+        .build()?;
+    let binary = download(&client, asset).await?;
+    let checksum = download(&client, checksum_asset).await?;
+    let signature = download(&client, signature_asset).await?;
+    // The signature names the release and asset, so another signed binary does not pass
+    verify_release(&ReleaseFiles {
+        tag: &release.tag_name,
+        asset_name: &asset.name,
+        binary: &binary,
+        checksum_file: &String::from_utf8_lossy(&checksum),
+        signature_file: &String::from_utf8_lossy(&signature),
+    })
+    .map_err(|e| unverified(e.to_string()))?;
+    replace_current_exe(&binary)?;
+
+    print_success(
+        &tr!("update-installed"),
+        &tr!("update-installed-details", latest = release.tag_name),
+    );
+    Ok(())
+}
+
+async fn download(client: &reqwest::Client, asset: &GitHubAsset) -> reqwest::Result<Vec<u8>> {
+    let response = client
+        .get(&asset.browser_download_url)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Replace the running executable with `binary`.
+fn replace_current_exe(binary: &[u8]) -> std::io::Result<()> {
+    let exe = std::env::current_exe()?;
+    // Write next to the executable, so the final rename stays on one file system
+    let staged = exe.with_extension("new");
+    std::fs::write(&staged, binary)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+    // Windows cannot overwrite a running executable, but it can move it out of the way
+    #[cfg(windows)]
+    std::fs::rename(&exe, exe.with_extension("old"))?;
+    std::fs::rename(&staged, &exe)
+}
@@ -0,0 +1,248 @@
+//! Release verification
+//!
+//! Every release binary is published with a `.sha256` file holding its SHA-256 checksum and a
+//! `.sig` file holding the hex-encoded ed25519 signature of `<asset name>|<tag>|<hex digest>`,
+//! made by the release workflow with the release signing key. Signing the asset name and tag along
+//! with the digest keeps a signed binary from being passed off as another release or as the build
+//! for another platform. `nexus-cli update` only installs a binary whose checksum matches and whose
+//! signature verifies, for the release and asset it asked for, against one of the public keys
+//! embedded below; a missing or malformed file fails the update the same way a mismatch does.
+
+use crate::failures::{from_hex, to_hex};
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Public halves of the release signing keys, hex; a release signed with any of them installs
+///
+/// Empty until the maintainer holding the release key adds it; until then `nexus-cli update`
+/// installs nothing and the release workflow refuses to sign. Each entry is added in the same
+/// change that sets the `RELEASE_SIGNING_KEY` secret, with a comment naming the maintainer who
+/// holds the private key and the date it was generated.
+///
+/// Each key pair is generated offline by that maintainer, and its PEM private key stored only as
+/// the `RELEASE_SIGNING_KEY` secret of the release workflow:
+///
+/// ```text
+/// openssl genpkey -algorithm ed25519 -out release-signing-key.pem
+/// openssl pkey -in release-signing-key.pem -pubout -outform DER | tail -c 32 | xxd -p -c 64
+/// ```
+///
+/// The second command prints the entry for this list. The release workflow derives the public key
+/// from the secret the same way, and refuses to sign if it is not listed here.
+///
+/// To rotate the key, add the new public key here and publish a release, still signed with the old
+/// key, so that users update to a version that accepts the new one. Then replace the secret with
+/// the new private key, and remove the old public key in a later release.
+pub const RELEASE_PUBLIC_KEYS: &[&str] = &[];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum VerifyError {
+    #[error("This build has no release signing key; download the release from GitHub instead")]
+    NoReleaseKey,
+
+    #[error("Malformed checksum file")]
+    MalformedChecksum,
+
+    #[error("Checksum mismatch: expected {expected}, downloaded {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+
+    #[error("Malformed signature file")]
+    MalformedSignature,
+
+    #[error("Signature does not match this release or the release signing key")]
+    BadSignature,
+}
+
+/// A downloaded release binary with its published checksum and signature
+pub struct ReleaseFiles<'a> {
+    /// Tag of the release the binary was downloaded from, e.g. `v0.10.3`
+    pub tag: &'a str,
+    /// Name of the release asset, e.g. `nexus-network-linux-x86_64`
+    pub asset_name: &'a str,
+    pub binary: &'a [u8],
+    pub checksum_file: &'a str,
+    pub signature_file: &'a str,
+}
+
+/// The message the release workflow signs for an asset of a release.
+fn signed_message(asset_name: &str, tag: &str, digest: &[u8]) -> String {
+    format!("{}|{}|{}", asset_name, tag, to_hex(digest))
+}
+
+/// Check a downloaded release binary against its published checksum and signature.
+pub fn verify_release(files: &ReleaseFiles) -> Result<(), VerifyError> {
+    let mut result = Err(VerifyError::NoReleaseKey);
+    for key in release_keys() {
+        result = verify_with_key(&key, files);
+        if result != Err(VerifyError::BadSignature) {
+            break;
+        }
+    }
+    result
+}
+
+fn release_keys() -> Vec<VerifyingKey> {
+    RELEASE_PUBLIC_KEYS
+        .iter()
+        .map(|hex| {
+            let bytes: [u8; 32] = from_hex(hex)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .expect("embedded release key is 32 bytes of hex");
+            VerifyingKey::from_bytes(&bytes).expect("embedded release key is a valid ed25519 key")
+        })
+        .collect()
+}
+
+fn verify_with_key(key: &VerifyingKey, files: &ReleaseFiles) -> Result<(), VerifyError> {
+    // `sha256sum` output: the digest, optionally followed by the file name
+    let expected = files
+        .checksum_file
+        .split_whitespace()
+        .next()
+        .ok_or(VerifyError::MalformedChecksum)?
+        .to_ascii_lowercase();
+    let expected_digest = from_hex(&expected).map_err(|_| VerifyError::MalformedChecksum)?;
+    if expected_digest.len() != 32 {
+        return Err(VerifyError::MalformedChecksum);
+    }
+
+    let digest = Sha256::digest(files.binary);
+    if digest.as_slice() != expected_digest.as_slice() {
+        return Err(VerifyError::ChecksumMismatch {
+            expected,
+            actual: to_hex(&digest),
+        });
+    }
+
+    let signature: [u8; 64] = from_hex(files.signature_file.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(VerifyError::MalformedSignature)?;
+    let message = signed_message(files.asset_name, files.tag, &digest);
+    key.verify_strict(message.as_bytes(), &Signature::from_bytes(&signature))
+        .map_err(|_| VerifyError::BadSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const BINARY: &[u8] = b"nexus-network release binary";
+    const ASSET: &str = "nexus-network-linux-x86_64";
+    const TAG: &str = "v0.10.3";
+
+    /// Checksum and signature files as the release workflow writes them
+    fn signed(key: &SigningKey, binary: &[u8]) -> (String, String) {
+        let digest = Sha256::digest(binary);
+        let checksum = format!("{}  {}\n", to_hex(&digest), ASSET);
+        let message = signed_message(ASSET, TAG, &digest);
+        let signature = to_hex(&key.sign(message.as_bytes()).to_bytes());
+        (checksum, signature)
+    }
+
+    fn files<'a>(binary: &'a [u8], checksum: &'a str, signature: &'a str) -> ReleaseFiles<'a> {
+        ReleaseFiles {
+            tag: TAG,
+            asset_name: ASSET,
+            binary,
+            checksum_file: checksum,
+            signature_file: signature,
+        }
+    }
+
+    #[test]
+    fn test_embedded_keys_are_valid() {
+        assert_eq!(release_keys().len(), RELEASE_PUBLIC_KEYS.len());
+    }
+
+    #[test]
+    fn test_accepts_signed_release() {
+        let key = SigningKey::generate(&mut rand::thread_rng());
+        let (checksum, signature) = signed(&key, BINARY);
+        assert_eq!(
+            verify_with_key(&key.verifying_key(), &files(BINARY, &checksum, &signature)),
+            Ok(())
+        );
+        // The release workflow writes the bare digest
+        let bare = checksum.split_whitespace().next().unwrap().to_uppercase();
+        assert_eq!(
+            verify_with_key(&key.verifying_key(), &files(BINARY, &bare, &signature)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_rejects_tampered_binary() {
+        let key = SigningKey::generate(&mut rand::thread_rng());
+        let (checksum, signature) = signed(&key, BINARY);
+        assert!(matches!(
+            verify_with_key(
+                &key.verifying_key(),
+                &files(b"tampered", &checksum, &signature)
+            ),
+            Err(VerifyError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_foreign_signature() {
+        let key = SigningKey::generate(&mut rand::thread_rng());
+        let other = SigningKey::generate(&mut rand::thread_rng());
+        // A matching checksum signed by someone else, e.g. a swapped release asset
+        let (checksum, signature) = signed(&other, BINARY);
+        assert_eq!(
+            verify_with_key(&key.verifying_key(), &files(BINARY, &checksum, &signature)),
+            Err(VerifyError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn test_rejects_other_release_or_platform() {
+        let key = SigningKey::generate(&mut rand::thread_rng());
+        let (checksum, signature) = signed(&key, BINARY);
+        // An older signed release served in place of the newest one
+        let rollback = ReleaseFiles {
+            tag: "v0.11.0",
+            ..files(BINARY, &checksum, &signature)
+        };
+        assert_eq!(
+            verify_with_key(&key.verifying_key(), &rollback),
+            Err(VerifyError::BadSignature)
+        );
+        // The signed build for another platform
+        let swapped = ReleaseFiles {
+            asset_name: "nexus-network-macos-arm64",
+            ..files(BINARY, &checksum, &signature)
+        };
+        assert_eq!(
+            verify_with_key(&key.verifying_key(), &swapped),
+            Err(VerifyError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_files() {
+        let key = SigningKey::generate(&mut rand::thread_rng());
+        let (checksum, signature) = signed(&key, BINARY);
+        let verifying_key = key.verifying_key();
+        assert_eq!(
+            verify_with_key(&verifying_key, &files(BINARY, "", &signature)),
+            Err(VerifyError::MalformedChecksum)
+        );
+        assert_eq!(
+            verify_with_key(&verifying_key, &files(BINARY, "abcd", &signature)),
+            Err(VerifyError::MalformedChecksum)
+        );
+        assert_eq!(
+            verify_with_key(&verifying_key, &files(BINARY, &checksum, "")),
+            Err(VerifyError::MalformedSignature)
+        );
+        assert_eq!(
+            verify_with_key(&verifying_key, &files(BINARY, &checksum, &signature[..64])),
+            Err(VerifyError::MalformedSignature)
+        );
+    }
+}
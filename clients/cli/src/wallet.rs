@@ -0,0 +1,150 @@
+//! Wallet address book.
+//!
+//! Wallet addresses are stored by alias in the config file, so users with several wallets can
+//! register with `--wallet-address <alias>` or pick one from a list instead of pasting addresses.
+
+use crate::cli_messages::{print_info, print_success};
+use crate::config::Config;
+use crate::exit_code::UsageError;
+use crate::keys;
+use crate::tr;
+use std::error::Error;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+
+/// Add a wallet address to the address book, replacing any address stored under `alias`.
+pub fn add_wallet(config_path: &Path, alias: &str, address: &str) -> Result<(), Box<dyn Error>> {
+    if alias.is_empty() || keys::is_valid_eth_address(alias) {
+        return Err(UsageError(tr!("wallet-invalid-alias", alias = alias)).into());
+    }
+    if !keys::is_valid_eth_address(address) {
+        return Err(UsageError(tr!("error-invalid-wallet", address = address)).into());
+    }
+
+    let mut config = Config::load_or_default(config_path)?;
+    config
+        .wallets
+        .insert(alias.to_string(), address.to_string());
+    config.save(config_path)?;
+    print_success(&tr!("wallet-added"), &format!("{} = {}", alias, address));
+    Ok(())
+}
+
+/// Print the address book, marking the wallet the config is registered with.
+pub fn list_wallets(config_path: &Path) -> Result<(), Box<dyn Error>> {
+    let config = Config::load_or_default(config_path)?;
+    if config.wallets.is_empty() {
+        print_info(&tr!("wallet-none-saved"), &tr!("wallet-none-saved-hint"));
+        return Ok(());
+    }
+    for (alias, address) in &config.wallets {
+        let marker = if address.eq_ignore_ascii_case(&config.wallet_address) {
+            "*"
+        } else {
+            " "
+        };
+        println!("{} {:<16} {}", marker, alias, address);
+    }
+    Ok(())
+}
+
+/// Address stored under `alias`.
+pub fn lookup(config_path: &Path, alias: &str) -> Result<String, Box<dyn Error>> {
+    let config = Config::load_or_default(config_path)?;
+    config
+        .wallets
+        .get(alias)
+        .cloned()
+        .ok_or_else(|| UsageError(tr!("wallet-not-found", alias = alias)).into())
+}
+
+/// Wallet address to register with: `arg` is either an address or an alias from the address
+/// book. Without `arg`, the user picks from the address book, which requires a terminal.
+pub fn resolve_wallet_arg(
+    config_path: &Path,
+    arg: Option<&str>,
+    interactive: bool,
+) -> Result<String, Box<dyn Error>> {
+    let config = Config::load_or_default(config_path)?;
+    if let Some(arg) = arg {
+        return Ok(config
+            .wallets
+            .get(arg)
+            .cloned()
+            .unwrap_or_else(|| arg.to_string()));
+    }
+
+    let wallets: Vec<(&String, &String)> = config.wallets.iter().collect();
+    let stdin = std::io::stdin();
+    if wallets.is_empty() || !interactive || !stdin.is_terminal() {
+        return Err(UsageError(tr!("wallet-required")).into());
+    }
+
+    println!("{}", tr!("wallet-select"));
+    for (i, (alias, address)) in wallets.iter().enumerate() {
+        println!("  {}) {:<16} {}", i + 1, alias, address);
+    }
+    print!("{} [1-{}]: ", tr!("wallet-choice"), wallets.len());
+    let _ = std::io::stdout().flush();
+    let mut answer = String::new();
+    stdin
+        .lock()
+        .read_line(&mut answer)
+        .map_err(|e| UsageError(tr!("prompt-read-failed", error = e)))?;
+    pick(&wallets, answer.trim())
+        .map(|address| address.to_string())
+        .ok_or_else(|| UsageError(tr!("wallet-invalid-choice", choice = answer.trim())).into())
+}
+
+/// Wallet chosen by a picker answer, either its number in the list or its alias.
+fn pick<'a>(wallets: &[(&String, &'a String)], answer: &str) -> Option<&'a str> {
+    if let Ok(index) = answer.parse::<usize>() {
+        return wallets
+            .get(index.checked_sub(1)?)
+            .map(|(_, address)| address.as_str());
+    }
+    wallets
+        .iter()
+        .find(|(alias, _)| alias.as_str() == answer)
+        .map(|(_, address)| address.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WALLET: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    #[test]
+    fn test_add_and_resolve() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        add_wallet(&path, "work", WALLET).unwrap();
+        assert!(add_wallet(&path, "bad", "0x1234").is_err());
+        assert!(add_wallet(&path, WALLET, WALLET).is_err());
+
+        assert_eq!(lookup(&path, "work").unwrap(), WALLET);
+        assert!(lookup(&path, "home").is_err());
+        assert_eq!(
+            resolve_wallet_arg(&path, Some("work"), false).unwrap(),
+            WALLET
+        );
+        assert_eq!(
+            resolve_wallet_arg(&path, Some("0xabc"), false).unwrap(),
+            "0xabc"
+        );
+        assert!(resolve_wallet_arg(&path, None, false).is_err());
+    }
+
+    #[test]
+    fn test_pick() {
+        let (home, work) = ("home".to_string(), "work".to_string());
+        let (a, b) = ("0xa".to_string(), "0xb".to_string());
+        let wallets = vec![(&home, &a), (&work, &b)];
+        assert_eq!(pick(&wallets, "1"), Some("0xa"));
+        assert_eq!(pick(&wallets, "work"), Some("0xb"));
+        assert_eq!(pick(&wallets, "0"), None);
+        assert_eq!(pick(&wallets, "3"), None);
+    }
+}
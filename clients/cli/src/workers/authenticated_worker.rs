@@ -2,14 +2,25 @@
 
 use super::core::{EventSender, WorkerConfig};
 use super::fetcher::TaskFetcher;
-use super::prover::TaskProver;
+use super::prover::{ProveError, TaskProver};
 use super::submitter::ProofSubmitter;
-use crate::events::{Event, ProverState};
-use crate::orchestrator::OrchestratorClient;
+use super::supervisor::Supervisor;
+use crate::consts::cli_consts::difficulty::ASSUMED_TASK_EXPIRY_SECS;
+use crate::control::RuntimeControl;
+use crate::events::{Event, EventPayload, EventType, ProverState, TaskFetchState};
+use crate::ipc::TaskQueue;
+use crate::ipc::queue::{TaskOutcome, TaskStage};
+use crate::logging::LogLevel;
+use crate::orchestrator::Orchestrator;
+use crate::power::PowerSaving;
+use crate::prover::ProverError;
+use crate::session::state_machine::{ProverStateMachine, TransitionHooks};
+use crate::thermal::ThermalState;
 
 use ed25519_dalek::SigningKey;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, watch};
 use tokio::task::JoinHandle;
 
 /// Single authenticated worker that handles the complete task lifecycle
@@ -21,17 +32,26 @@ pub struct AuthenticatedWorker {
     max_tasks: Option<u32>,
     tasks_completed: u32,
     shutdown_sender: broadcast::Sender<()>,
+    queue: TaskQueue,
+    control: RuntimeControl,
+    /// Paces restarts after prover subprocess crashes
+    supervisor: Supervisor,
+    /// Where the work loop is in the task lifecycle
+    state: ProverStateMachine,
 }
 
 impl AuthenticatedWorker {
     pub fn new(
         node_id: u64,
         signing_key: SigningKey,
-        orchestrator: OrchestratorClient,
+        orchestrator: Arc<dyn Orchestrator>,
         config: WorkerConfig,
         event_sender: mpsc::Sender<Event>,
         max_tasks: Option<u32>,
         shutdown_sender: broadcast::Sender<()>,
+        queue: TaskQueue,
+        control: RuntimeControl,
+        transition_hooks: TransitionHooks,
     ) -> Self {
         let event_sender_helper = EventSender::new(event_sender);
 
@@ -61,24 +81,33 @@ impl AuthenticatedWorker {
             max_tasks,
             tasks_completed: 0,
             shutdown_sender,
+            queue,
+            control,
+            supervisor: Supervisor::default(),
+            state: ProverStateMachine::new(event_sender_helper.clone(), transition_hooks),
         }
     }
 
+    /// Subscribe to the task fetcher's rate-limit budget
+    pub fn subscribe_fetch_state(&self) -> watch::Receiver<TaskFetchState> {
+        self.fetcher.subscribe_fetch_state()
+    }
+
     /// Start the worker
     pub async fn run(mut self, mut shutdown: broadcast::Receiver<()>) -> Vec<JoinHandle<()>> {
         let mut join_handles = Vec::new();
 
         // Send initial state
-        self.event_sender
-            .send_event(Event::state_change(
-                ProverState::Waiting,
-                "Ready to fetch tasks".to_string(),
-            ))
+        self.state
+            .transition(ProverState::Idle, "Ready to fetch tasks")
             .await;
 
         // Main work loop
         let worker_handle = tokio::spawn(async move {
+            let _guard = crate::chaos::WorkerGuard::acquire();
             loop {
+                crate::chaos::heartbeat();
+                crate::chaos::maybe_stall_worker().await;
                 tokio::select! {
                     _ = shutdown.recv() => break,
                     should_exit = self.work_cycle() => {
@@ -97,13 +126,49 @@ impl AuthenticatedWorker {
     }
 
     /// Complete work cycle: fetch→prove→submit
-    /// Returns true if the worker should exit (max tasks reached)
+    /// Returns true if the worker should exit (max tasks or another target reached)
     async fn work_cycle(&mut self) -> bool {
+        // Drain: a stop requested while a task was in flight takes effect before the next fetch
+        if let Some(reason) = self.control.stop_reason() {
+            self.state
+                .transition(ProverState::Idle, format!("{}, shutting down", reason))
+                .await;
+            let _ = self.shutdown_sender.send(());
+            return true;
+        }
+        // Hold off fetching while paused through the control API, to save battery, while the
+        // machine is in use or while it cools down
+        if self.control.is_held() {
+            let reason = if self.control.is_paused() {
+                "Paused via control API"
+            } else if self.control.is_user_active() {
+                "Paused while the machine is in use"
+            } else if self.control.thermal_state() == ThermalState::Cooling {
+                "Paused to let the machine cool down"
+            } else {
+                "Paused on battery power"
+            };
+            self.state.transition(ProverState::Idle, reason).await;
+            self.control.wait_until_resumed().await;
+        }
+        self.fetcher
+            .set_max_difficulty(self.control.max_difficulty());
+        // Prove on a single worker while throttled on battery power or running hot
+        let throttled = self.control.power_saving() == PowerSaving::Throttled
+            || self.control.thermal_state() != ThermalState::Normal;
+        self.prover.set_worker_limit(throttled.then_some(1));
+
         // Step 1: Fetch task
+        self.state
+            .transition_quietly(ProverState::Fetching, "Fetching a task")
+            .await;
         let task = match self.fetcher.fetch_task().await {
             Ok(task) => task,
             Err(_) => {
                 // Error already logged in fetcher, wait before retry
+                self.state
+                    .transition_quietly(ProverState::Cooldown, "Fetching again in 1s")
+                    .await;
                 tokio::time::sleep(Duration::from_secs(1)).await;
                 return false; // Don't exit on fetch error, just retry
             }
@@ -114,29 +179,77 @@ impl AuthenticatedWorker {
 
         // Step 2: Prove task
         // Send state change to Proving
-        self.event_sender
-            .send_event(Event::state_change(
+        self.state
+            .transition_with_payload(
                 ProverState::Proving,
-                format!("Step 2 of 4: Proving task {}", task.task_id),
-            ))
+                format!(
+                    "Step 2 of 4: Proving task {}, Difficulty: {}, Type: {}",
+                    task.task_id,
+                    task.difficulty.as_str_name(),
+                    task.task_type.as_str_name()
+                ),
+                EventPayload::ProofStarted {
+                    task_id: task.task_id.clone(),
+                    difficulty: task.difficulty,
+                    task_type: task.task_type,
+                },
+            )
             .await;
 
-        let proof_result = match self.prover.prove_task(&task).await {
-            Ok(proof_result) => proof_result,
-            Err(_) => {
-                // Send state change back to Waiting on proof failure
-                self.event_sender
-                    .send_event(Event::state_change(
-                        ProverState::Waiting,
-                        "Proof generation failed, ready for next task".to_string(),
-                    ))
+        // Make the task visible to `nexus-cli queue`
+        let cancel = self.queue.push(&task);
+        self.queue.set_stage(&task.task_id, TaskStage::Proving);
+
+        let proof_result = tokio::select! {
+            result = self.prover.prove_task(&task) => Some(result),
+            _ = cancel.cancelled() => None,
+        };
+        let proof_result = match proof_result {
+            Some(Ok(proof_result)) => proof_result,
+            Some(Err(ProveError::Generation(ProverError::Crashed(message)))) => {
+                self.queue.finish(&task.task_id, TaskOutcome::Failed);
+                let backoff = self.recover_from_crash(&message).await;
+                tokio::time::sleep(backoff).await;
+                return false;
+            }
+            Some(Err(_)) => {
+                self.queue.finish(&task.task_id, TaskOutcome::Failed);
+                // Back to Idle on proof failure
+                self.state
+                    .transition(
+                        ProverState::Idle,
+                        "Proof generation failed, ready for next task",
+                    )
                     .await;
                 return false; // Don't exit on proof error, just retry
             }
+            None => {
+                self.drop_task(&task.task_id).await;
+                return false;
+            }
         };
 
+        // The task may have been dropped while its proof was being verified
+        if cancel.is_cancelled() {
+            self.drop_task(&task.task_id).await;
+            return false;
+        }
+
         // Step 3: Submit proof
+        self.state
+            .transition_quietly(
+                ProverState::Submitting,
+                format!("Submitting proof for task {}", task.task_id),
+            )
+            .await;
+        self.queue.set_stage(&task.task_id, TaskStage::Submitting);
         let submission_result = self.submitter.submit_proof(&task, &proof_result).await;
+        let outcome = if submission_result.is_ok() {
+            TaskOutcome::Submitted
+        } else {
+            TaskOutcome::Failed
+        };
+        self.queue.finish(&task.task_id, outcome);
 
         // Only increment task counter on successful submission
         if submission_result.is_ok() {
@@ -145,22 +258,44 @@ impl AuthenticatedWorker {
             // Update success tracking for difficulty promotion
             let duration_secs = start_time.elapsed().as_secs();
             self.fetcher.update_success_tracking(duration_secs);
+            // Tasks this slow are likely to expire before they are submitted next time
+            if duration_secs >= ASSUMED_TASK_EXPIRY_SECS {
+                self.event_sender
+                    .send_task_event(
+                        format!(
+                            "{} took {} minutes, close to when tasks expire; lower --max-difficulty \
+                             or try --profile low-memory",
+                            task.difficulty.as_str_name(),
+                            duration_secs / 60
+                        ),
+                        EventType::Error,
+                        LogLevel::Warn,
+                    )
+                    .await;
+            }
 
             // Send information about completing the task
-            self.event_sender
-                .send_event(Event::state_change(
-                    ProverState::Waiting,
+            self.state
+                .transition_with_payload(
+                    ProverState::Cooldown,
                     format!(
-                        "{} completed, Task size: {}, Duration: {}s, Difficulty: {}",
+                        "{} completed, Task size: {}, Duration: {}s, Difficulty: {}, Type: {}",
                         task.task_id,
                         task.public_inputs_list.len(),
                         self.fetcher.last_success_duration_secs.unwrap_or(0),
                         self.fetcher
                             .last_success_difficulty
                             .map(|difficulty| difficulty.as_str_name())
-                            .unwrap_or("Unknown")
+                            .unwrap_or("Unknown"),
+                        task.task_type.as_str_name()
                     ),
-                ))
+                    EventPayload::TaskCompleted {
+                        task_id: task.task_id.clone(),
+                        duration_secs,
+                        difficulty: task.difficulty,
+                        task_type: task.task_type,
+                    },
+                )
                 .await;
             // Check if we've reached the maximum number of tasks
             if let Some(max) = self.max_tasks {
@@ -169,11 +304,11 @@ impl AuthenticatedWorker {
                     // before triggering shutdown
                     tokio::time::sleep(std::time::Duration::from_millis(100)).await;
 
-                    self.event_sender
-                        .send_event(Event::state_change(
-                            ProverState::Waiting,
+                    self.state
+                        .transition(
+                            ProverState::Idle,
                             format!("Completed {} tasks, shutting down", self.tasks_completed),
-                        ))
+                        )
                         .await;
 
                     // Send shutdown signal to trigger application exit
@@ -183,14 +318,76 @@ impl AuthenticatedWorker {
             }
         }
 
-        // Send state change back to Waiting at the end of the work cycle
-        self.event_sender
-            .send_event(Event::state_change(
-                ProverState::Waiting,
-                "Task completed, ready for next task".to_string(),
-            ))
+        // Cool down at the end of the work cycle
+        self.state
+            .transition(ProverState::Cooldown, "Task completed, ready for next task")
             .await;
 
         false // Continue with more tasks
     }
+
+    /// Report a crashed prover subprocess, returning how long to wait before the next task
+    async fn recover_from_crash(&mut self, message: &str) -> Duration {
+        let decision = self.supervisor.record_crash(std::time::Instant::now());
+        self.event_sender
+            .send_event(
+                Event::task_fetcher_with_level(
+                    format!(
+                        "Prover crashed: {}; restarting in {}s (restart {})",
+                        message,
+                        decision.backoff.as_secs(),
+                        self.supervisor.total_restarts()
+                    ),
+                    EventType::Error,
+                    LogLevel::Error,
+                )
+                .with_payload(EventPayload::WorkerCrashed {
+                    restarts: self.supervisor.total_restarts(),
+                }),
+            )
+            .await;
+        if decision.degraded {
+            self.event_sender
+                .send_event(
+                    Event::task_fetcher_with_level(
+                        format!(
+                            "Crash loop detected: {} crashes in the last {} minutes. Restarting every {} minutes until the worker recovers; check the logs or run `nexus-cli doctor`",
+                            decision.recent_crashes,
+                            self.supervisor.crash_loop_window().as_secs() / 60,
+                            decision.backoff.as_secs() / 60
+                        ),
+                        EventType::Error,
+                        LogLevel::Error,
+                    )
+                    .with_payload(EventPayload::CrashLoop {
+                        recent_crashes: decision.recent_crashes,
+                    }),
+                )
+                .await;
+        }
+        self.state
+            .transition(
+                ProverState::Cooldown,
+                format!(
+                    "Worker restarting in {}s after a crash",
+                    decision.backoff.as_secs()
+                ),
+            )
+            .await;
+        decision.backoff
+    }
+
+    /// Abandon a task dropped through the control socket
+    async fn drop_task(&mut self, task_id: &str) {
+        self.queue.finish(task_id, TaskOutcome::Dropped);
+        self.state
+            .transition(
+                ProverState::Idle,
+                format!(
+                    "Task {} dropped via control socket, ready for next task",
+                    task_id
+                ),
+            )
+            .await;
+    }
 }
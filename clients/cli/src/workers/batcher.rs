@@ -0,0 +1,327 @@
+//! Batching of proof submissions
+//!
+//! Proofs that finish close together are collected for up to `max_delay` (or until
+//! `max_batch_size` are pending) and sent in a single request, which keeps multi-worker
+//! machines under the submission rate limit. Proofs are submitted one by one if the orchestrator
+//! does not support batch submission, if the batch request fails, or if a proof is missing
+//! from the batch response.
+
+use crate::consts::cli_consts::proof_submission;
+use crate::network::{NetworkClient, ProofSubmission};
+use crate::nexus_orchestrator::SubmitProofResult;
+use crate::orchestrator::Orchestrator;
+use crate::orchestrator::error::OrchestratorError;
+use ed25519_dalek::SigningKey;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::Instant;
+
+/// Outcome of a submission: the number of attempts, or the final error and attempt count
+pub type SubmitOutcome = Result<u32, (OrchestratorError, u32)>;
+
+/// When to send a batch of pending proofs
+#[derive(Debug, Clone, Copy)]
+pub struct BatchPolicy {
+    /// Send as soon as this many proofs are pending
+    pub max_batch_size: usize,
+    /// Send at most this long after the first proof of a batch arrived
+    pub max_delay: Duration,
+}
+
+impl Default for BatchPolicy {
+    fn default() -> Self {
+        Self {
+            max_batch_size: proof_submission::MAX_BATCH_SIZE,
+            max_delay: proof_submission::batch_max_delay(),
+        }
+    }
+}
+
+struct PendingSubmission {
+    submission: ProofSubmission,
+    reply: oneshot::Sender<SubmitOutcome>,
+}
+
+/// Handle to the background task that batches and submits proofs
+#[derive(Clone)]
+pub struct SubmissionBatcher {
+    sender: mpsc::Sender<PendingSubmission>,
+}
+
+impl SubmissionBatcher {
+    /// Spawn the batching task. It runs until every handle has been dropped.
+    pub fn spawn(
+        orchestrator: Box<dyn Orchestrator>,
+        network_client: NetworkClient,
+        signing_key: SigningKey,
+        policy: BatchPolicy,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(policy.max_batch_size.max(1));
+        tokio::spawn(run_batcher(
+            receiver,
+            orchestrator,
+            network_client,
+            signing_key,
+            policy,
+        ));
+        Self { sender }
+    }
+
+    /// Queue a proof for submission and wait for its outcome
+    pub async fn submit(&self, submission: ProofSubmission) -> SubmitOutcome {
+        let (reply, outcome) = oneshot::channel();
+        let closed = || {
+            (
+                OrchestratorError::Transport("proof submission queue closed".to_string()),
+                0,
+            )
+        };
+        self.sender
+            .send(PendingSubmission { submission, reply })
+            .await
+            .map_err(|_| closed())?;
+        outcome.await.map_err(|_| closed())?
+    }
+}
+
+async fn run_batcher(
+    mut receiver: mpsc::Receiver<PendingSubmission>,
+    orchestrator: Box<dyn Orchestrator>,
+    mut network_client: NetworkClient,
+    signing_key: SigningKey,
+    policy: BatchPolicy,
+) {
+    let mut batch_supported = true;
+
+    while let Some(first) = receiver.recv().await {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + policy.max_delay;
+        while batch.len() < policy.max_batch_size {
+            match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(pending)) => batch.push(pending),
+                _ => break,
+            }
+        }
+
+        if batch.len() > 1 && batch_supported {
+            let submissions: Vec<ProofSubmission> =
+                batch.iter().map(|p| p.submission.clone()).collect();
+            match network_client
+                .submit_proofs_batch(
+                    orchestrator.as_ref(),
+                    &submissions,
+                    signing_key.clone(),
+                    1, // num_provers (single worker)
+                )
+                .await
+            {
+                Ok((results, attempts)) => {
+                    // Answer every proof the server reported on; the rest go out on their own
+                    batch = answer_from_results(batch, &results, attempts);
+                }
+                Err((e, _)) => {
+                    if e.is_unsupported() {
+                        batch_supported = false;
+                    }
+                }
+            }
+        }
+
+        for pending in batch {
+            let outcome = network_client
+                .submit_proof(
+                    orchestrator.as_ref(),
+                    pending.submission,
+                    signing_key.clone(),
+                    1, // num_provers (single worker)
+                )
+                .await;
+            let _ = pending.reply.send(outcome);
+        }
+    }
+}
+
+/// Reply to each pending submission that has a result, returning those that do not.
+fn answer_from_results(
+    batch: Vec<PendingSubmission>,
+    results: &[SubmitProofResult],
+    attempts: u32,
+) -> Vec<PendingSubmission> {
+    let mut unanswered = Vec::new();
+    for pending in batch {
+        match results
+            .iter()
+            .find(|r| r.task_id == pending.submission.task_id)
+        {
+            Some(result) if result.accepted => {
+                let _ = pending.reply.send(Ok(attempts));
+            }
+            Some(result) => {
+                let error = OrchestratorError::Http {
+                    status: 422,
+                    message: result.error.clone(),
+                    headers: HashMap::new(),
+                };
+                let _ = pending.reply.send(Err((error, attempts)));
+            }
+            None => unanswered.push(pending),
+        }
+    }
+    unanswered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{RequestTimer, RequestTimerConfig};
+    use crate::nexus_orchestrator::TaskType;
+    use crate::orchestrator::MockOrchestrator;
+    use bytes::Bytes;
+
+    fn network_client() -> NetworkClient {
+        let timer = RequestTimer::new(RequestTimerConfig::combined(
+            Duration::from_millis(1),
+            100,
+            Duration::from_secs(1),
+            Duration::from_millis(1),
+        ));
+        NetworkClient::new(timer, 1)
+    }
+
+    fn submission(task_id: &str) -> ProofSubmission {
+        ProofSubmission::new(
+            task_id.to_string(),
+            "hash".to_string(),
+            Bytes::from_static(&[1, 2, 3]),
+            TaskType::ProofHash,
+        )
+    }
+
+    fn spawn(
+        orchestrator: MockOrchestrator,
+        max_batch_size: usize,
+        max_delay: Duration,
+    ) -> SubmissionBatcher {
+        SubmissionBatcher::spawn(
+            Box::new(orchestrator),
+            network_client(),
+            SigningKey::from_bytes(&[7u8; 32]),
+            BatchPolicy {
+                max_batch_size,
+                max_delay,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_full_batch_is_sent_in_one_request() {
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator.expect_submit_proof().never();
+        orchestrator
+            .expect_submit_proofs_batch()
+            .times(1)
+            .returning(|submissions, _, _| {
+                assert_eq!(submissions.len(), 2);
+                Ok(vec![
+                    SubmitProofResult {
+                        task_id: "a".to_string(),
+                        accepted: true,
+                        error: String::new(),
+                    },
+                    SubmitProofResult {
+                        task_id: "b".to_string(),
+                        accepted: false,
+                        error: "invalid proof".to_string(),
+                    },
+                ])
+            });
+        let batcher = spawn(orchestrator, 2, Duration::from_secs(5));
+
+        let (a, b) = tokio::join!(
+            batcher.submit(submission("a")),
+            batcher.submit(submission("b"))
+        );
+        assert_eq!(a.unwrap(), 1);
+        assert!(matches!(
+            b,
+            Err((OrchestratorError::Http { status: 422, .. }, 1))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_single_submissions() {
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_submit_proofs_batch()
+            .times(1)
+            .returning(|_, _, _| {
+                Err(OrchestratorError::Http {
+                    status: 404,
+                    message: "Not Found".to_string(),
+                    headers: HashMap::new(),
+                })
+            });
+        orchestrator
+            .expect_submit_proof()
+            .times(2)
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(()));
+        let batcher = spawn(orchestrator, 2, Duration::from_secs(5));
+
+        let (a, b) = tokio::join!(
+            batcher.submit(submission("a")),
+            batcher.submit(submission("b"))
+        );
+        assert_eq!(a.unwrap(), 1);
+        assert_eq!(b.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_single_proof_is_sent_after_max_delay() {
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator.expect_submit_proofs_batch().never();
+        orchestrator
+            .expect_submit_proof()
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _, _| Ok(()));
+        let batcher = spawn(orchestrator, 4, Duration::from_millis(10));
+
+        assert_eq!(batcher.submit(submission("a")).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_already_accepted_retry_is_success() {
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator.expect_submit_proofs_batch().never();
+        let mut timed_out = false;
+        orchestrator
+            .expect_submit_proof()
+            .times(2)
+            .returning(move |_, _, _, _, _, _, _, _, _| {
+                if std::mem::replace(&mut timed_out, true) {
+                    return Err(OrchestratorError::AlreadyAccepted);
+                }
+                Err(OrchestratorError::Http {
+                    status: 504,
+                    message: String::new(),
+                    headers: HashMap::new(),
+                })
+            });
+        let batcher = spawn(orchestrator, 4, Duration::from_millis(10));
+
+        assert_eq!(batcher.submit(submission("a")).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_already_accepted_first_attempt_is_error() {
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator.expect_submit_proofs_batch().never();
+        orchestrator
+            .expect_submit_proof()
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _, _| Err(OrchestratorError::AlreadyAccepted));
+        let batcher = spawn(orchestrator, 4, Duration::from_millis(10));
+
+        assert!(batcher.submit(submission("a")).await.is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! Core worker utilities and traits
 
+use crate::chaos::Fault;
 use crate::events::{Event, EventType};
 use crate::logging::LogLevel;
 use tokio::sync::mpsc;
@@ -17,6 +18,9 @@ impl EventSender {
 
     /// Send a generic event
     pub async fn send_event(&self, event: Event) {
+        if crate::chaos::inject(Fault::DropEvent) {
+            return;
+        }
         let _ = self.sender.send(event).await;
     }
 
@@ -26,12 +30,10 @@ impl EventSender {
         event_type: EventType,
         log_level: LogLevel,
     ) {
-        let _ = self
-            .sender
-            .send(Event::task_fetcher_with_level(
-                message, event_type, log_level,
-            ))
-            .await;
+        self.send_event(Event::task_fetcher_with_level(
+            message, event_type, log_level,
+        ))
+        .await;
     }
 
     pub async fn send_proof_event(
@@ -40,12 +42,10 @@ impl EventSender {
         event_type: EventType,
         log_level: LogLevel,
     ) {
-        let _ = self
-            .sender
-            .send(Event::proof_submitter_with_level(
-                message, event_type, log_level,
-            ))
-            .await;
+        self.send_event(Event::proof_submitter_with_level(
+            message, event_type, log_level,
+        ))
+        .await;
     }
 
     pub async fn send_prover_event(
@@ -55,12 +55,10 @@ impl EventSender {
         event_type: EventType,
         log_level: LogLevel,
     ) {
-        let _ = self
-            .sender
-            .send(Event::prover_with_level(
-                thread_id, message, event_type, log_level,
-            ))
-            .await;
+        self.send_event(Event::prover_with_level(
+            thread_id, message, event_type, log_level,
+        ))
+        .await;
     }
 }
 
@@ -71,6 +69,22 @@ pub struct WorkerConfig {
     pub client_id: String,
     pub max_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
     pub num_workers: usize,
+    /// Where to look up and store proofs of previously seen inputs; `None` disables caching
+    pub proof_cache: Option<crate::prover::ProofCache>,
+    /// Core each proving slot is pinned to; `None` leaves scheduling to the OS
+    pub pinned_cores: Option<Vec<usize>>,
+    /// Where to write a failure bundle for each failed proof; `None` disables capture
+    pub failure_dir: Option<std::path::PathBuf>,
+    /// Where to export each accepted proof; `None` disables export
+    pub proof_export_dir: Option<std::path::PathBuf>,
+    /// Tasks already submitted, skipped when offered again; `None` disables deduplication
+    pub task_cache: Option<crate::task_cache::TaskCache>,
+    /// Nonces for proof submissions; `None` submits without a nonce
+    pub submission_nonces: Option<crate::orchestrator::nonce::SubmissionNonces>,
+    /// Fetch schedule shared with other instances on the host; `None` fetches independently
+    pub coordinator: Option<crate::coordinator::Coordinator>,
+    /// Tasks to take, previewed before taking them; `None` takes every task offered
+    pub accept_policy: Option<crate::accept_policy::AcceptPolicy>,
 }
 
 impl WorkerConfig {
@@ -80,6 +94,14 @@ impl WorkerConfig {
             client_id,
             max_difficulty: None,
             num_workers: 1,
+            proof_cache: None,
+            pinned_cores: None,
+            failure_dir: None,
+            proof_export_dir: None,
+            task_cache: None,
+            submission_nonces: None,
+            coordinator: None,
+            accept_policy: None,
         }
     }
 }
@@ -1,22 +1,37 @@
 //! Task fetching with network retry logic
+//!
+//! Tasks are received over a task subscription (long-poll) where the orchestrator offers one,
+//! falling back to rate-limited polling otherwise.
 
 use super::core::{EventSender, WorkerConfig};
+use crate::accept_policy::AcceptPolicy;
 use crate::analytics::track_got_task;
 use crate::consts::cli_consts::{difficulty, rate_limiting, task_fetching};
-use crate::events::EventType;
+use crate::events::{
+    Event, EventPayload, EventType, MaintenanceWindow, RateLimitHit, TaskFetchState,
+};
 use crate::logging::LogLevel;
 use crate::network::{NetworkClient, RequestTimer, RequestTimerConfig};
 use crate::orchestrator::Orchestrator;
+use crate::orchestrator::client::ProofTaskResult;
+use crate::orchestrator::error::OrchestratorError;
 use crate::task::Task;
+use chrono::Local;
 use ed25519_dalek::VerifyingKey;
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use thiserror::Error;
+use tokio::sync::watch;
 use tokio::time::sleep;
 
 #[derive(Error, Debug)]
 pub enum FetchError {
     #[error("Network error: {0}")]
     Network(#[from] crate::orchestrator::error::OrchestratorError),
+    #[error("Task {0} was already submitted")]
+    AlreadySubmitted(String),
+    #[error("Offered task {0} does not match the accept policy")]
+    Declined(String),
 }
 
 /// Task fetcher with built-in retry and error handling
@@ -30,6 +45,18 @@ pub struct TaskFetcher {
     pub last_success_duration_secs: Option<u64>,
     pub last_success_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
     last_requested_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
+    /// Cleared once the orchestrator reports that it does not offer task subscriptions
+    subscription_available: bool,
+    /// Cleared once the orchestrator reports that it does not offer task previews
+    preview_available: bool,
+    /// Offers declined since a task was last taken, see [`task_fetching::decline_backoff`]
+    declined_in_a_row: u32,
+    /// Recent 429 responses, oldest first
+    rate_limits: VecDeque<RateLimitHit>,
+    /// Set while the orchestrator reports planned maintenance
+    maintenance: Option<MaintenanceWindow>,
+    /// Publishes the rate-limit budget after every fetch attempt
+    fetch_state: watch::Sender<TaskFetchState>,
 }
 
 impl TaskFetcher {
@@ -62,28 +89,299 @@ impl TaskFetcher {
             last_success_duration_secs: None,
             last_success_difficulty: None,
             last_requested_difficulty: None,
+            subscription_available: true,
+            preview_available: true,
+            declined_in_a_row: 0,
+            rate_limits: VecDeque::new(),
+            maintenance: None,
+            fetch_state: watch::Sender::new(TaskFetchState::default()),
         }
     }
 
-    /// Fetch a single task with automatic retry and proper logging
+    /// Subscribe to the rate-limit budget, updated after every fetch attempt
+    pub fn subscribe_fetch_state(&self) -> watch::Receiver<TaskFetchState> {
+        self.fetch_state.subscribe()
+    }
+
+    /// Override the max difficulty for subsequent fetches; `None` restores adaptive difficulty
+    pub fn set_max_difficulty(
+        &mut self,
+        max_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
+    ) {
+        self.config.max_difficulty = max_difficulty;
+    }
+
+    /// Fetch a single task with automatic retry and proper logging.
+    /// Tasks found in the task cache were submitted before and are skipped.
     pub async fn fetch_task(&mut self) -> Result<Task, FetchError> {
+        let desired = self.desired_difficulty();
+
+        // Subscriptions hand out tasks without a preview, so a policy always polls
+        let offered = match self.config.accept_policy.clone() {
+            Some(policy) if self.preview_available => self.poll_offer(desired, &policy).await,
+            _ => None,
+        };
+        let task = match offered {
+            Some(result) => result?,
+            None => {
+                let subscribed =
+                    if self.subscription_available && self.config.accept_policy.is_none() {
+                        self.subscribe_task(desired).await
+                    } else {
+                        None
+                    };
+                match subscribed {
+                    Some(proof_task_result) => self.accept_task(proof_task_result, desired).await,
+                    None => self.poll_task(desired).await?,
+                }
+            }
+        };
+        self.end_maintenance().await;
+
+        if let Some(offset_secs) = crate::clock::take_skew_warning() {
+            let direction = if offset_secs > 0 {
+                "behind"
+            } else {
+                "ahead of"
+            };
+            self.event_sender
+                .send_task_event(
+                    format!(
+                        "Local clock is {}s {} the orchestrator, correcting timestamps; sync the system clock",
+                        offset_secs.abs(),
+                        direction
+                    ),
+                    EventType::Refresh,
+                    LogLevel::Warn,
+                )
+                .await;
+        }
+
+        if let Some(cache) = &self.config.task_cache {
+            if cache.contains(&task.task_id) {
+                self.event_sender
+                    .send_task_event(
+                        format!(
+                            "Skipping task {}: a proof was already submitted",
+                            task.task_id
+                        ),
+                        EventType::Refresh,
+                        LogLevel::Info,
+                    )
+                    .await;
+                return Err(FetchError::AlreadySubmitted(task.task_id));
+            }
+        }
+
+        Ok(task)
+    }
+
+    /// Wait for a task over the task subscription.
+    /// Returns `None` if the subscription is unavailable, so the caller falls back to polling.
+    async fn subscribe_task(
+        &mut self,
+        desired: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Option<ProofTaskResult> {
+        self.event_sender
+            .send_task_event(
+                "Step 1 of 4: Waiting for a task...".to_string(),
+                EventType::Refresh,
+                LogLevel::Info,
+            )
+            .await;
+
+        loop {
+            match self
+                .orchestrator
+                .subscribe_proof_task(
+                    &self.node_id.to_string(),
+                    self.verifying_key,
+                    desired,
+                    task_fetching::long_poll_wait(),
+                )
+                .await
+            {
+                Ok(Some(proof_task_result)) => return Some(proof_task_result),
+                // No task within the hold time; subscribe again
+                Ok(None) => continue,
+                Err(e) => {
+                    self.record_rate_limit(&e);
+                    self.share_rate_limit(&e).await;
+                    self.note_maintenance(&e).await;
+                    self.publish_fetch_state();
+                    let message = if e.is_unsupported() {
+                        self.subscription_available = false;
+                        "Task subscription not supported by the orchestrator, polling for tasks"
+                            .to_string()
+                    } else {
+                        format!(
+                            "Task subscription failed, polling for a task instead: {}",
+                            e
+                        )
+                    };
+                    self.event_sender
+                        .send_task_event(message, EventType::Refresh, LogLevel::Debug)
+                        .await;
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Poll for a task, waiting out the request timer between attempts
+    async fn poll_task(
+        &mut self,
+        desired: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Result<Task, FetchError> {
         // Check if we can proceed immediately
         let can_proceed_immediately = self.network_client.request_timer_mut().can_proceed();
 
         if can_proceed_immediately {
+            self.event_sender
+                .send_event(
+                    Event::task_fetcher_with_level(
+                        "Step 1 of 4: Fetching task...".to_string(),
+                        EventType::Refresh,
+                        LogLevel::Info,
+                    )
+                    .with_payload(EventPayload::FetchStarted),
+                )
+                .await;
+        }
+
+        self.wait_for_turn().await;
+
+        match self
+            .network_client
+            .fetch_task(
+                self.orchestrator.as_ref(),
+                &self.node_id.to_string(),
+                self.verifying_key,
+                desired,
+            )
+            .await
+        {
+            Ok(proof_task_result) => {
+                self.publish_fetch_state();
+                Ok(self.accept_task(proof_task_result, desired).await)
+            }
+            Err(e) => Err(self.fetch_failed(e, "Failed to fetch task").await),
+        }
+    }
+
+    /// Preview the next task and take it if the accept policy allows, declining it otherwise.
+    /// Returns `None` if the orchestrator does not offer task previews or cannot take an offered
+    /// task, so the caller fetches without one.
+    async fn poll_offer(
+        &mut self,
+        desired: crate::nexus_orchestrator::TaskDifficulty,
+        policy: &AcceptPolicy,
+    ) -> Option<Result<Task, FetchError>> {
+        self.event_sender
+            .send_event(
+                Event::task_fetcher_with_level(
+                    "Step 1 of 4: Previewing task...".to_string(),
+                    EventType::Refresh,
+                    LogLevel::Info,
+                )
+                .with_payload(EventPayload::FetchStarted),
+            )
+            .await;
+        self.wait_for_turn().await;
+
+        let node_id = self.node_id.to_string();
+        let offer = match self
+            .network_client
+            .preview_task(
+                self.orchestrator.as_ref(),
+                &node_id,
+                self.verifying_key,
+                desired,
+            )
+            .await
+        {
+            Ok(offer) => offer,
+            Err(e) if e.is_unsupported() => {
+                self.preview_available = false;
+                self.event_sender
+                    .send_task_event(
+                        "Task previews not supported by the orchestrator, taking tasks without --accept-policy".to_string(),
+                        EventType::Refresh,
+                        LogLevel::Warn,
+                    )
+                    .await;
+                return None;
+            }
+            Err(e) => return Some(Err(self.fetch_failed(e, "Failed to preview task").await)),
+        };
+        self.publish_fetch_state();
+
+        if !policy.accepts(&offer) {
+            let reason = format!("accept policy: {}", policy);
+            // Best-effort: an offer that is not declined expires on the server
+            let _ = self
+                .network_client
+                .decline_task(
+                    self.orchestrator.as_ref(),
+                    &node_id,
+                    &offer.offer_id,
+                    &reason,
+                )
+                .await;
+            // Offers keep missing the policy; preview less often until one is taken
+            self.declined_in_a_row += 1;
+            self.network_client
+                .request_timer_mut()
+                .hold_off(task_fetching::decline_backoff(self.declined_in_a_row));
+            self.publish_fetch_state();
             self.event_sender
                 .send_task_event(
-                    "Step 1 of 4: Fetching task...".to_string(),
+                    format!("Declined task ({}), not within the {}", offer, reason),
                     EventType::Refresh,
                     LogLevel::Info,
                 )
                 .await;
+            return Some(Err(FetchError::Declined(offer.offer_id)));
         }
 
-        // Wait until we can proceed with accurate timing
+        let taken = self
+            .network_client
+            .take_task(
+                self.orchestrator.as_ref(),
+                &node_id,
+                self.verifying_key,
+                desired,
+                &offer.offer_id,
+            )
+            .await;
+        Some(match taken {
+            Ok(proof_task_result) => {
+                self.declined_in_a_row = 0;
+                self.publish_fetch_state();
+                Ok(self.accept_task(proof_task_result, desired).await)
+            }
+            Err(e) if e.is_unsupported() => {
+                // The offer expires on the server; fetch without previews from now on
+                self.preview_available = false;
+                self.event_sender
+                    .send_task_event(
+                        "Taking offered tasks not supported by the orchestrator, taking tasks without --accept-policy".to_string(),
+                        EventType::Refresh,
+                        LogLevel::Warn,
+                    )
+                    .await;
+                return None;
+            }
+            Err(e) => Err(self.fetch_failed(e, "Failed to take task").await),
+        })
+    }
+
+    /// Wait out the request timer, and take turns with the other instances on this host
+    async fn wait_for_turn(&mut self) {
         while !self.network_client.request_timer_mut().can_proceed() {
             let wait_time = self.network_client.request_timer_mut().time_until_next();
             if wait_time > Duration::ZERO {
+                self.publish_fetch_state();
                 // Log the accurate wait time here
                 self.event_sender
                     .send_task_event(
@@ -99,9 +397,177 @@ impl TaskFetcher {
             }
         }
 
-        // Attempt to fetch task through network client
-        // Determine desired max difficulty
-        let desired = if let Some(override_diff) = self.config.max_difficulty {
+        // Take turns with the other instances on this host, if coordinating
+        if let Some(coordinator) = &self.config.coordinator {
+            coordinator.wait_for_fetch_slot().await;
+        }
+    }
+
+    /// Record and log a failed request for a task, prefixing the log line with `context`
+    async fn fetch_failed(&mut self, e: OrchestratorError, context: &str) -> FetchError {
+        self.record_rate_limit(&e);
+        self.share_rate_limit(&e).await;
+        let in_maintenance = self.note_maintenance(&e).await;
+        self.publish_fetch_state();
+
+        // Maintenance was announced once; keep retries out of the error log
+        if in_maintenance {
+            self.event_sender
+                .send_task_event(
+                    format!("Orchestrator still under maintenance: {}", e),
+                    EventType::Refresh,
+                    LogLevel::Debug,
+                )
+                .await;
+            return FetchError::Network(e);
+        }
+
+        // Log fetch failure with appropriate level
+        let log_level = self.network_client.classify_error(&e);
+        let payload = match &e {
+            OrchestratorError::Http { status: 429, .. } => EventPayload::RateLimited {
+                retry_after_secs: e.get_retry_after_seconds(),
+            },
+            _ => EventPayload::FetchFailed,
+        };
+        self.event_sender
+            .send_event(
+                Event::task_fetcher_with_level(
+                    format!("{}: {}", context, e),
+                    EventType::Error,
+                    log_level,
+                )
+                .with_payload(payload),
+            )
+            .await;
+
+        FetchError::Network(e)
+    }
+
+    /// Remember a 429 response for the rate-limit history
+    fn record_rate_limit(&mut self, error: &OrchestratorError) {
+        if !matches!(error, OrchestratorError::Http { status: 429, .. }) {
+            return;
+        }
+        if self.rate_limits.len() >= rate_limiting::RATE_LIMIT_HISTORY_SIZE {
+            self.rate_limits.pop_front();
+        }
+        self.rate_limits.push_back(RateLimitHit {
+            at: Instant::now(),
+            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            retry_after_secs: error.get_retry_after_seconds(),
+        });
+    }
+
+    /// Track a maintenance window announced by `error`, announcing it when it starts.
+    /// Returns whether the orchestrator is under maintenance.
+    async fn note_maintenance(&mut self, error: &OrchestratorError) -> bool {
+        let Some(maintenance) = error.maintenance() else {
+            return false;
+        };
+        let window = MaintenanceWindow {
+            message: maintenance.message,
+            ends_at: maintenance
+                .remaining
+                .map(|remaining| Instant::now() + remaining),
+        };
+        if self.maintenance.is_none() {
+            let until = match maintenance
+                .remaining
+                .and_then(|r| chrono::Duration::from_std(r).ok())
+            {
+                Some(remaining) => format!(
+                    " until about {}",
+                    (Local::now() + remaining).format("%H:%M")
+                ),
+                None => String::new(),
+            };
+            let reason = match &window.message {
+                Some(message) => format!(": {}", message),
+                None => String::new(),
+            };
+            self.event_sender
+                .send_task_event(
+                    format!(
+                        "Orchestrator is down for maintenance{}{}; waiting before fetching tasks",
+                        until, reason
+                    ),
+                    EventType::Refresh,
+                    LogLevel::Warn,
+                )
+                .await;
+        }
+        self.maintenance = Some(window);
+        true
+    }
+
+    /// Clear the maintenance window once a fetch succeeds again
+    async fn end_maintenance(&mut self) {
+        if self.maintenance.take().is_none() {
+            return;
+        }
+        self.publish_fetch_state();
+        self.event_sender
+            .send_task_event(
+                "Orchestrator maintenance is over, fetching tasks again".to_string(),
+                EventType::Refresh,
+                LogLevel::Info,
+            )
+            .await;
+    }
+
+    /// Hold back the other instances on this host after a 429, if coordinating
+    async fn share_rate_limit(&self, error: &OrchestratorError) {
+        let Some(coordinator) = &self.config.coordinator else {
+            return;
+        };
+        if matches!(error, OrchestratorError::Http { status: 429, .. }) {
+            let retry_after = error
+                .get_retry_after_seconds()
+                .map(|secs| Duration::from_secs(secs.into()));
+            coordinator.report_rate_limit(retry_after).await;
+        }
+    }
+
+    /// Publish the current rate-limit budget to subscribers
+    fn publish_fetch_state(&mut self) {
+        let now = Instant::now();
+        self.rate_limits
+            .retain(|hit| now.duration_since(hit.at) <= rate_limiting::rate_limit_history());
+
+        let timer = self.network_client.request_timer_mut();
+        let backoff = timer.time_until_next();
+        let requests_in_window = timer.requests_in_window();
+        let config = timer.config();
+        let state = TaskFetchState {
+            requests_in_window,
+            max_requests_per_window: config.max_requests.unwrap_or(0),
+            window: config.time_window.unwrap_or_default(),
+            backoff,
+            next_fetch_at: (backoff > Duration::ZERO).then(|| now + backoff),
+            rate_limits: self.rate_limits.iter().cloned().collect(),
+            maintenance: self.maintenance.clone(),
+        };
+        self.fetch_state.send_replace(state);
+    }
+
+    /// Max difficulty to request next, capped at the hardest difficulty the accept policy takes
+    fn desired_difficulty(&self) -> crate::nexus_orchestrator::TaskDifficulty {
+        let desired = self.uncapped_difficulty();
+        match self
+            .config
+            .accept_policy
+            .as_ref()
+            .and_then(AcceptPolicy::max_difficulty)
+        {
+            Some(max) if (max as i32) < (desired as i32) => max,
+            _ => desired,
+        }
+    }
+
+    /// Max difficulty to request next: the override if set, otherwise adaptive
+    fn uncapped_difficulty(&self) -> crate::nexus_orchestrator::TaskDifficulty {
+        if let Some(override_diff) = self.config.max_difficulty {
             override_diff
         } else {
             // Adaptive difficulty system:
@@ -153,72 +619,68 @@ impl TaskFetcher {
                 // No previous success - start at SmallMedium
                 crate::nexus_orchestrator::TaskDifficulty::SmallMedium
             }
-        };
+        }
+    }
 
-        // Log the difficulty we're requesting vs what we receive
-        let requested_difficulty = desired;
+    /// Log and track a received task, returning it for proving
+    async fn accept_task(
+        &mut self,
+        proof_task_result: ProofTaskResult,
+        requested: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Task {
+        crate::redact::protect_task(&proof_task_result.task.task_id);
 
-        match self
-            .network_client
-            .fetch_task(
-                self.orchestrator.as_ref(),
-                &self.node_id.to_string(),
-                self.verifying_key,
-                desired,
-            )
-            .await
-        {
-            Ok(proof_task_result) => {
-                // Log difficulty adjustment if server overrides our request
-                if proof_task_result.actual_difficulty != requested_difficulty {
-                    self.event_sender
-                        .send_task_event(
-                            format!(
-                                "Server adjusted difficulty: requested {:?}, assigned {:?} (reputation gating)",
-                                requested_difficulty,
-                                proof_task_result.actual_difficulty
-                            ),
-                            EventType::Success,
-                            LogLevel::Info,
-                        )
-                        .await;
-                }
+        // Log difficulty adjustment if server overrides our request
+        if proof_task_result.actual_difficulty != requested {
+            self.event_sender
+                .send_task_event(
+                    format!(
+                        "Server adjusted difficulty: requested {:?}, assigned {:?} (reputation gating)",
+                        requested,
+                        proof_task_result.actual_difficulty
+                    ),
+                    EventType::Success,
+                    LogLevel::Info,
+                )
+                .await;
+        }
 
-                // Log successful fetch
-                self.event_sender
-                    .send_task_event(
-                        format!("Step 1 of 4: Got task {}", proof_task_result.task.task_id),
-                        EventType::Success,
-                        LogLevel::Info,
-                    )
-                    .await;
+        // Log successful fetch
+        self.event_sender
+            .send_event(
+                Event::task_fetcher_with_level(
+                    format!("Step 1 of 4: Got task {}", proof_task_result.task.task_id),
+                    EventType::Success,
+                    LogLevel::Info,
+                )
+                .with_payload(EventPayload::TaskFetched {
+                    task_id: proof_task_result.task.task_id.clone(),
+                }),
+            )
+            .await;
 
-                // Track analytics for successful fetch
-                tokio::spawn(track_got_task(
-                    proof_task_result.task.clone(),
-                    self.config.environment.clone(),
-                    self.config.client_id.clone(),
-                ));
+        // Track analytics for successful fetch
+        tokio::spawn(track_got_task(
+            proof_task_result.task.clone(),
+            self.config.environment.clone(),
+            self.config.client_id.clone(),
+        ));
 
-                // Store the actual difficulty received from server for success tracking
-                self.last_requested_difficulty = Some(proof_task_result.actual_difficulty);
+        // Store the actual difficulty received from server for success tracking
+        self.last_requested_difficulty = Some(proof_task_result.actual_difficulty);
 
-                Ok(proof_task_result.task)
-            }
-            Err(e) => {
-                // Log fetch failure with appropriate level
-                let log_level = self.network_client.classify_error(&e);
-                self.event_sender
-                    .send_task_event(
-                        format!("Failed to fetch task: {}", e),
-                        EventType::Error,
-                        log_level,
-                    )
-                    .await;
-
-                Err(FetchError::Network(e))
-            }
+        let mut task = proof_task_result.task;
+        if crate::chaos::maybe_corrupt_task(&mut task) {
+            self.event_sender
+                .send_task_event(
+                    format!("[CHAOS] Corrupted inputs of task {}", task.task_id),
+                    EventType::Refresh,
+                    LogLevel::Debug,
+                )
+                .await;
         }
+
+        task
     }
 
     /// Update success tracking after completing a task
@@ -242,11 +704,41 @@ mod tests {
     use tokio::sync::mpsc;
 
     // Mock orchestrator for testing
-    struct MockOrchestrator;
+    struct MockOrchestrator {
+        /// Whether tasks are offered over a task subscription
+        streaming: bool,
+        /// Whether tasks can be previewed and taken by offer
+        offers: bool,
+    }
 
     impl MockOrchestrator {
         fn new() -> Self {
-            Self
+            Self {
+                streaming: false,
+                offers: true,
+            }
+        }
+
+        fn streaming() -> Self {
+            Self {
+                streaming: true,
+                offers: true,
+            }
+        }
+
+        fn without_offers() -> Self {
+            Self {
+                streaming: false,
+                offers: false,
+            }
+        }
+    }
+
+    fn not_found() -> OrchestratorError {
+        OrchestratorError::Http {
+            status: 404,
+            message: "Not Found".to_string(),
+            headers: Default::default(),
         }
     }
 
@@ -274,6 +766,73 @@ mod tests {
             })
         }
 
+        async fn subscribe_proof_task(
+            &self,
+            _node_id: &str,
+            _verifying_key: VerifyingKey,
+            max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+            _wait: Duration,
+        ) -> Result<Option<crate::orchestrator::client::ProofTaskResult>, OrchestratorError>
+        {
+            if !self.streaming {
+                return Err(not_found());
+            }
+            let task = Task {
+                task_id: "streamed_task".to_string(),
+                program_id: "test_program".to_string(),
+                public_inputs: vec![1, 2, 3],
+                public_inputs_list: vec![vec![1, 2, 3]],
+                task_type: crate::nexus_orchestrator::TaskType::ProofHash,
+                difficulty: crate::nexus_orchestrator::TaskDifficulty::Medium,
+            };
+            Ok(Some(crate::orchestrator::client::ProofTaskResult {
+                task,
+                actual_difficulty: max_difficulty,
+            }))
+        }
+
+        async fn preview_proof_task(
+            &self,
+            _node_id: &str,
+            _verifying_key: VerifyingKey,
+            _max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        ) -> Result<crate::task::TaskDescriptor, OrchestratorError> {
+            if !self.offers {
+                return Err(not_found());
+            }
+            Ok(crate::task::TaskDescriptor {
+                offer_id: "test_offer".to_string(),
+                difficulty: crate::nexus_orchestrator::TaskDifficulty::Medium,
+                task_type: crate::nexus_orchestrator::TaskType::ProofHash,
+                input_size: 12,
+                expected_cycles: 300_000,
+                reward_weight: 10,
+            })
+        }
+
+        async fn take_proof_task(
+            &self,
+            node_id: &str,
+            verifying_key: VerifyingKey,
+            max_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+            _offer_id: &str,
+        ) -> Result<crate::orchestrator::client::ProofTaskResult, OrchestratorError> {
+            if !self.offers {
+                return Err(not_found());
+            }
+            self.get_proof_task(node_id, verifying_key, max_difficulty)
+                .await
+        }
+
+        async fn decline_proof_task(
+            &self,
+            _node_id: &str,
+            _offer_id: &str,
+            _reason: &str,
+        ) -> Result<(), OrchestratorError> {
+            Ok(())
+        }
+
         fn environment(&self) -> &Environment {
             &Environment::Production
         }
@@ -282,6 +841,14 @@ mod tests {
             Ok("test_user".to_string())
         }
 
+        async fn get_user_nodes(
+            &self,
+            _wallet_address: &str,
+            _nodes_cursor: &str,
+        ) -> Result<crate::nexus_orchestrator::UserResponse, OrchestratorError> {
+            Ok(Default::default())
+        }
+
         async fn register_user(
             &self,
             _user_id: &str,
@@ -290,30 +857,78 @@ mod tests {
             Ok(())
         }
 
-        async fn register_node(&self, _user_id: &str) -> Result<String, OrchestratorError> {
+        async fn register_node(
+            &self,
+            _user_id: &str,
+            _verifying_key: VerifyingKey,
+        ) -> Result<String, OrchestratorError> {
             Ok("test_node".to_string())
         }
 
+        async fn deregister_node(
+            &self,
+            _node_id: &str,
+            _signing_key: SigningKey,
+        ) -> Result<(), OrchestratorError> {
+            Ok(())
+        }
+
         async fn submit_proof(
             &self,
             _task_id: &str,
             _proof_hash: &str,
-            _proof: Vec<u8>,
-            _proofs: Vec<Vec<u8>>,
+            _proof: bytes::Bytes,
+            _proofs: Vec<bytes::Bytes>,
             _signing_key: SigningKey,
             _num_provers: usize,
             _task_type: crate::nexus_orchestrator::TaskType,
             _individual_proof_hashes: &[String],
+            _nonce: u64,
         ) -> Result<(), OrchestratorError> {
             Ok(())
         }
 
+        async fn submit_proofs_batch(
+            &self,
+            _submissions: &[crate::network::ProofSubmission],
+            _signing_key: SigningKey,
+            _num_provers: usize,
+        ) -> Result<Vec<crate::nexus_orchestrator::SubmitProofResult>, OrchestratorError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_node_points(
+            &self,
+            _node_id: &str,
+        ) -> Result<crate::nexus_orchestrator::GetNodePointsResponse, OrchestratorError> {
+            Ok(Default::default())
+        }
+
+        async fn get_network_stats(
+            &self,
+            _node_id: &str,
+        ) -> Result<crate::nexus_orchestrator::GetNetworkStatsResponse, OrchestratorError> {
+            Ok(Default::default())
+        }
+
         async fn get_node(&self, _node_id: &str) -> Result<String, OrchestratorError> {
             Ok("test_node".to_string())
         }
+
+        async fn get_task_receipts(
+            &self,
+            _node_id: &str,
+            _task_ids: &[String],
+        ) -> Result<Vec<crate::nexus_orchestrator::TaskReceipt>, OrchestratorError> {
+            Ok(Vec::new())
+        }
     }
 
     fn create_test_fetcher() -> TaskFetcher {
+        create_test_fetcher_with(MockOrchestrator::new())
+    }
+
+    fn create_test_fetcher_with(orchestrator: MockOrchestrator) -> TaskFetcher {
         let (event_sender, _event_receiver) = mpsc::channel(100);
         let event_sender = crate::workers::core::EventSender::new(event_sender);
         let config = WorkerConfig::new(Environment::Production, "test_client".to_string());
@@ -322,12 +937,186 @@ mod tests {
             12345,
             VerifyingKey::from_bytes(&[0u8; 32])
                 .expect("failed to construct VerifyingKey from bytes"),
-            Box::new(MockOrchestrator::new()),
+            Box::new(orchestrator),
             event_sender,
             &config,
         )
     }
 
+    #[tokio::test]
+    async fn test_subscription_delivers_task() {
+        let mut fetcher = create_test_fetcher_with(MockOrchestrator::streaming());
+
+        let task = fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(task.task_id, "streamed_task");
+        assert!(fetcher.subscription_available);
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::SmallMedium)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_polling_without_subscription() {
+        let mut fetcher = create_test_fetcher();
+
+        let task = fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(task.task_id, "test_task");
+        assert!(!fetcher.subscription_available);
+    }
+
+    #[tokio::test]
+    async fn test_accept_policy_takes_or_declines_offers() {
+        let mut fetcher = create_test_fetcher_with(MockOrchestrator::streaming());
+        fetcher.config.accept_policy = Some("reward>=10 && difficulty<=medium".parse().unwrap());
+        let task = fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        // Offers are taken by polling, never from the subscription
+        assert_eq!(task.task_id, "test_task");
+
+        let mut fetcher = create_test_fetcher();
+        fetcher.config.accept_policy = Some("reward>=20".parse().unwrap());
+        let error = fetcher.fetch_task().await.unwrap_err();
+        assert!(matches!(error, FetchError::Declined(id) if id == "test_offer"));
+        // Declines in a row push the next preview further out
+        assert_eq!(fetcher.declined_in_a_row, 1);
+        assert!(fetcher.network_client.request_timer_mut().time_until_next() > Duration::ZERO);
+        assert!(task_fetching::decline_backoff(2) > task_fetching::decline_backoff(1));
+        assert_eq!(
+            task_fetching::decline_backoff(100),
+            Duration::from_secs(task_fetching::MAX_DECLINE_BACKOFF_SECS)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_accept_policy_falls_back_without_offers() {
+        let mut fetcher = create_test_fetcher_with(MockOrchestrator::without_offers());
+        fetcher.config.accept_policy = Some("reward>=20".parse().unwrap());
+
+        let task = fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(task.task_id, "test_task");
+        assert!(!fetcher.preview_available);
+    }
+
+    #[tokio::test]
+    async fn test_accept_policy_caps_difficulty() {
+        let mut fetcher = create_test_fetcher();
+        fetcher.config.accept_policy = Some("difficulty<=medium".parse().unwrap());
+        fetcher.last_success_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Large);
+        fetcher.last_success_duration_secs = Some(300);
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        // The policy would decline anything harder than it asks for
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Medium)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skips_already_submitted_task() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = crate::task_cache::TaskCache::open(
+            dir.path().join("task-cache.json"),
+            Default::default(),
+        );
+        cache.insert("test_task").unwrap();
+        let mut fetcher = create_test_fetcher();
+        fetcher.config.task_cache = Some(cache);
+
+        let error = fetcher.fetch_task().await.unwrap_err();
+        assert!(matches!(error, FetchError::AlreadySubmitted(id) if id == "test_task"));
+    }
+
+    #[tokio::test]
+    async fn test_publishes_fetch_state() {
+        let mut fetcher = create_test_fetcher();
+        let fetch_state = fetcher.subscribe_fetch_state();
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        let state = fetch_state.borrow().clone();
+        assert_eq!(state.requests_in_window, 1);
+        assert_eq!(
+            state.max_requests_per_window,
+            rate_limiting::TASK_FETCH_MAX_REQUESTS_PER_WINDOW
+        );
+        assert!(state.rate_limits.is_empty());
+    }
+
+    #[test]
+    fn test_records_rate_limits() {
+        let mut fetcher = create_test_fetcher();
+        let fetch_state = fetcher.subscribe_fetch_state();
+        let error = |status| OrchestratorError::Http {
+            status,
+            message: String::new(),
+            headers: std::collections::HashMap::from([(
+                "retry-after".to_string(),
+                "30".to_string(),
+            )]),
+        };
+
+        fetcher.record_rate_limit(&error(503));
+        fetcher.record_rate_limit(&error(429));
+        fetcher.publish_fetch_state();
+
+        let state = fetch_state.borrow().clone();
+        assert_eq!(state.rate_limits.len(), 1);
+        assert_eq!(state.rate_limits[0].retry_after_secs, Some(30));
+
+        for _ in 0..rate_limiting::RATE_LIMIT_HISTORY_SIZE {
+            fetcher.record_rate_limit(&error(429));
+        }
+        assert_eq!(
+            fetcher.rate_limits.len(),
+            rate_limiting::RATE_LIMIT_HISTORY_SIZE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tracks_maintenance_window() {
+        let mut fetcher = create_test_fetcher();
+        let fetch_state = fetcher.subscribe_fetch_state();
+        let maintenance = OrchestratorError::Http {
+            status: 503,
+            message: r#"{"maintenance": true, "message": "Upgrade"}"#.to_string(),
+            headers: std::collections::HashMap::from([(
+                "retry-after".to_string(),
+                "600".to_string(),
+            )]),
+        };
+
+        assert!(fetcher.note_maintenance(&maintenance).await);
+        fetcher.publish_fetch_state();
+        let window = fetch_state.borrow().maintenance.clone().unwrap();
+        assert_eq!(window.message.as_deref(), Some("Upgrade"));
+        assert!(window.remaining(Instant::now()).unwrap() > Duration::from_secs(590));
+
+        // A successful fetch ends the window
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert!(fetch_state.borrow().maintenance.is_none());
+    }
+
     #[tokio::test]
     async fn test_default_difficulty_is_small_medium() {
         let mut fetcher = create_test_fetcher();
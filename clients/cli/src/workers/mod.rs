@@ -1,5 +1,7 @@
 pub mod authenticated_worker;
+pub mod batcher;
 pub mod core;
 pub mod fetcher;
 pub mod prover;
 pub mod submitter;
+pub mod supervisor;
@@ -2,10 +2,12 @@
 
 use super::core::{EventSender, WorkerConfig};
 use crate::analytics::track_authenticated_proof_analytics;
-use crate::events::EventType;
+use crate::events::{Event, EventPayload, EventType};
+use crate::failures::FailureBundle;
 use crate::logging::LogLevel;
 use crate::prover::{ProverError, ProverResult, authenticated_proving};
 use crate::task::Task;
+use std::path::Path;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,6 +20,8 @@ pub enum ProveError {
 pub struct TaskProver {
     event_sender: EventSender,
     config: WorkerConfig,
+    /// Upper bound on `config.num_workers`, e.g. while throttled on battery power
+    worker_limit: Option<usize>,
 }
 
 impl TaskProver {
@@ -25,31 +29,79 @@ impl TaskProver {
         Self {
             event_sender,
             config,
+            worker_limit: None,
+        }
+    }
+
+    /// Cap the number of workers used for the next proofs; `None` uses all configured workers
+    pub fn set_worker_limit(&mut self, worker_limit: Option<usize>) {
+        self.worker_limit = worker_limit;
+    }
+
+    /// Number of workers the next proof runs on
+    fn num_workers(&self) -> usize {
+        match self.worker_limit {
+            Some(limit) => self.config.num_workers.min(limit.max(1)),
+            None => self.config.num_workers,
         }
     }
 
     /// Generate proof for a task with proper logging
     pub async fn prove_task(&self, task: &Task) -> Result<ProverResult, ProveError> {
+        let num_workers = self.num_workers();
         // Use existing prover module for proof generation
         match authenticated_proving(
             task,
             &self.config.environment,
             &self.config.client_id,
-            self.config.num_workers,
+            num_workers,
+            self.config.proof_cache.as_ref(),
+            self.config.pinned_cores.as_deref(),
         )
         .await
         {
-            Ok((proofs, combined_hash, individual_proof_hashes)) => {
+            Ok(proof_result) => {
+                // Record reuse of cached proofs
+                if proof_result.cache_hits > 0 {
+                    self.event_sender
+                        .send_event(
+                            Event::prover_with_level(
+                                self.config.num_workers,
+                                format!(
+                                    "Proof cache hit for task {}: reused {} of {} proofs",
+                                    task.task_id,
+                                    proof_result.cache_hits,
+                                    proof_result.proofs.len()
+                                ),
+                                EventType::Success,
+                                LogLevel::Info,
+                            )
+                            .with_payload(
+                                EventPayload::ProofCacheHit {
+                                    task_id: task.task_id.clone(),
+                                    reused: proof_result.cache_hits,
+                                    total: proof_result.proofs.len(),
+                                },
+                            ),
+                        )
+                        .await;
+                }
+
                 // Log successful proof generation
                 self.event_sender
-                    .send_prover_event(
-                        self.config.num_workers, // Use num_workers as thread identifier for multi-threaded prover
-                        format!(
-                            "Proof generated for task {} (using {} workers)",
-                            task.task_id, self.config.num_workers
-                        ),
-                        EventType::Success,
-                        LogLevel::Info,
+                    .send_event(
+                        Event::prover_with_level(
+                            self.config.num_workers, // Use num_workers as thread identifier for multi-threaded prover
+                            format!(
+                                "Proof generated for task {} (using {} workers)",
+                                task.task_id, num_workers
+                            ),
+                            EventType::Success,
+                            LogLevel::Info,
+                        )
+                        .with_payload(EventPayload::ProofGenerated {
+                            task_id: task.task_id.clone(),
+                        }),
                     )
                     .await;
 
@@ -59,27 +111,69 @@ impl TaskProver {
                     self.config.client_id.clone(),
                 ));
 
-                Ok(ProverResult {
-                    proofs,
-                    combined_hash,
-                    individual_proof_hashes,
-                })
+                Ok(proof_result)
             }
             Err(e) => {
                 // Log proof generation failure
                 self.event_sender
-                    .send_prover_event(
-                        self.config.num_workers, // Use num_workers as thread identifier for multi-threaded prover
-                        format!(
-                            "Proof generation failed for task {} (using {} workers): {}",
-                            task.task_id, self.config.num_workers, e
-                        ),
-                        EventType::Error,
-                        LogLevel::Error,
+                    .send_event(
+                        Event::prover_with_level(
+                            self.config.num_workers, // Use num_workers as thread identifier for multi-threaded prover
+                            format!(
+                                "Proof generation failed for task {} (using {} workers): {}",
+                                task.task_id, num_workers, e
+                            ),
+                            EventType::Error,
+                            LogLevel::Error,
+                        )
+                        .with_payload(EventPayload::ProofFailed {
+                            task_id: task.task_id.clone(),
+                        }),
                     )
                     .await;
+                if let Some(dir) = &self.config.failure_dir {
+                    self.capture_failure(dir, task, &e, num_workers).await;
+                }
                 Err(ProveError::Generation(e))
             }
         }
     }
+
+    /// Write a failure bundle for `task` to `dir` and log where it went
+    async fn capture_failure(
+        &self,
+        dir: &Path,
+        task: &Task,
+        error: &ProverError,
+        num_workers: usize,
+    ) {
+        let bundle = FailureBundle::new(
+            task,
+            &error.to_string(),
+            &self.config.environment,
+            num_workers,
+        );
+        let (message, event_type, log_level) = match bundle.write(dir).await {
+            Ok(path) => (
+                format!(
+                    "Failure bundle for task {} written to {}",
+                    task.task_id,
+                    path.display()
+                ),
+                EventType::Refresh,
+                LogLevel::Info,
+            ),
+            Err(e) => (
+                format!(
+                    "Failed to write failure bundle for task {}: {}",
+                    task.task_id, e
+                ),
+                EventType::Error,
+                LogLevel::Warn,
+            ),
+        };
+        self.event_sender
+            .send_prover_event(self.config.num_workers, message, event_type, log_level)
+            .await;
+    }
 }
@@ -1,17 +1,21 @@
 //! Proof submission with network retry logic
 
+use super::batcher::{BatchPolicy, SubmissionBatcher};
 use super::core::{EventSender, WorkerConfig};
 use crate::analytics::{
     track_proof_accepted, track_proof_submission_error, track_proof_submission_success,
 };
 use crate::consts::cli_consts::{proof_submission, rate_limiting};
-use crate::events::EventType;
+use crate::events::{Event, EventPayload, EventType};
 use crate::logging::LogLevel;
+use crate::network::error_handler::ErrorHandler;
 use crate::network::{NetworkClient, ProofSubmission, RequestTimer, RequestTimerConfig};
 use crate::orchestrator::Orchestrator;
 use crate::prover::ProverResult;
+use crate::prover::export::export_proofs;
 use crate::task::Task;
 use ed25519_dalek::SigningKey;
+use std::path::Path;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -20,13 +24,14 @@ pub enum SubmitError {
     Network(#[from] crate::orchestrator::error::OrchestratorError),
     #[error("Serialization error: {0}")]
     Serialization(#[from] postcard::Error),
+    #[error("Task {0} was already submitted")]
+    AlreadySubmitted(String),
 }
 
-/// Proof submitter with built-in retry and error handling
+/// Proof submitter with built-in retry, batching and error handling
 pub struct ProofSubmitter {
-    signing_key: SigningKey,
-    orchestrator: Box<dyn Orchestrator>,
-    network_client: NetworkClient,
+    batcher: SubmissionBatcher,
+    error_handler: ErrorHandler,
     event_sender: EventSender,
     config: WorkerConfig,
 }
@@ -50,10 +55,17 @@ impl ProofSubmitter {
         // Create network client with more retries for critical submissions
         let network_client = NetworkClient::new(request_timer, proof_submission::MAX_RETRIES);
 
-        Self {
-            signing_key,
+        // Proofs finishing close together share a request
+        let batcher = SubmissionBatcher::spawn(
             orchestrator,
             network_client,
+            signing_key,
+            BatchPolicy::default(),
+        );
+
+        Self {
+            batcher,
+            error_handler: ErrorHandler::new(),
             event_sender,
             config: config.clone(),
         }
@@ -65,6 +77,23 @@ impl ProofSubmitter {
         task: &Task,
         proof_result: &ProverResult,
     ) -> Result<(), SubmitError> {
+        // Never submit a task twice, e.g. when it was offered again while being proven
+        if let Some(cache) = &self.config.task_cache {
+            if cache.contains(&task.task_id) {
+                self.event_sender
+                    .send_proof_event(
+                        format!(
+                            "Not submitting proof for task {}: a proof was already submitted",
+                            task.task_id
+                        ),
+                        EventType::Refresh,
+                        LogLevel::Info,
+                    )
+                    .await;
+                return Err(SubmitError::AlreadySubmitted(task.task_id.clone()));
+            }
+        }
+
         // Log start of submission
         self.event_sender
             .send_proof_event(
@@ -74,12 +103,8 @@ impl ProofSubmitter {
             )
             .await;
 
-        // Serialize proofs
-        let proofs_bytes: Vec<Vec<u8>> = proof_result
-            .proofs
-            .iter()
-            .map(postcard::to_allocvec)
-            .collect::<Result<_, _>>()?;
+        // Proofs were serialized once when they were generated; the submission shares them
+        let proofs_bytes = proof_result.proof_bytes.clone();
         let legacy_proof_bytes = proofs_bytes.first().cloned().unwrap_or_default();
 
         // Submit through network client with retry logic
@@ -101,16 +126,19 @@ impl ProofSubmitter {
             submission = submission.with_proofs(proofs_bytes);
         }
 
-        match self
-            .network_client
-            .submit_proof(
-                self.orchestrator.as_ref(),
-                submission,
-                self.signing_key.clone(),
-                1, // num_provers (single worker)
-            )
-            .await
+        // One nonce per submission, shared by its retries
+        if let Some(nonces) = self
+            .config
+            .submission_nonces
+            .as_ref()
+            .filter(|_| crate::orchestrator::nonce::nonces_enabled())
         {
+            submission = submission.with_nonce(nonces.next().await);
+        }
+
+        crate::chaos::maybe_delay_submission().await;
+
+        match self.batcher.submit(submission).await {
             Ok(attempts) => {
                 // Log successful submission with attempt count
                 let attempt_text = if attempts == 1 {
@@ -120,16 +148,37 @@ impl ProofSubmitter {
                 };
 
                 self.event_sender
-                    .send_proof_event(
-                        format!(
-                            "Step 4 of 4: Proof submitted successfully for task {}{}\n",
-                            task.task_id, attempt_text
-                        ),
-                        EventType::Success,
-                        LogLevel::Info,
+                    .send_event(
+                        Event::proof_submitter_with_level(
+                            format!(
+                                "Step 4 of 4: Proof submitted successfully for task {}{}\n",
+                                task.task_id, attempt_text
+                            ),
+                            EventType::Success,
+                            LogLevel::Info,
+                        )
+                        .with_payload(EventPayload::ProofSubmitted {
+                            task_id: task.task_id.clone(),
+                        }),
                     )
                     .await;
 
+                if let Some(cache) = &self.config.task_cache {
+                    if let Err(e) = cache.insert(&task.task_id) {
+                        self.event_sender
+                            .send_proof_event(
+                                format!("Failed to update the task cache: {}", e),
+                                EventType::Error,
+                                LogLevel::Warn,
+                            )
+                            .await;
+                    }
+                }
+
+                if let Some(dir) = &self.config.proof_export_dir {
+                    self.export_proofs(dir, task, proof_result).await;
+                }
+
                 // Track analytics for successful submission
                 self.track_successful_submission(task).await;
 
@@ -139,15 +188,20 @@ impl ProofSubmitter {
             }
             Err((e, attempts)) => {
                 // Log submission failure with attempt count and appropriate level
-                let log_level = self.network_client.classify_error(&e);
+                let log_level = self.error_handler.classify_error(&e);
                 self.event_sender
-                    .send_proof_event(
-                        format!(
-                            "Failed to submit proof for task {} after {} attempts: {}",
-                            task.task_id, attempts, e
-                        ),
-                        EventType::Error,
-                        log_level,
+                    .send_event(
+                        Event::proof_submitter_with_level(
+                            format!(
+                                "Failed to submit proof for task {} after {} attempts: {}",
+                                task.task_id, attempts, e
+                            ),
+                            EventType::Error,
+                            log_level,
+                        )
+                        .with_payload(EventPayload::SubmissionFailed {
+                            task_id: task.task_id.clone(),
+                        }),
                     )
                     .await;
 
@@ -165,6 +219,30 @@ impl ProofSubmitter {
         }
     }
 
+    /// Export the proofs of an accepted task to `dir` and log the outcome
+    async fn export_proofs(&self, dir: &Path, task: &Task, proof_result: &ProverResult) {
+        let (message, event_type, log_level) = match export_proofs(dir, task, proof_result).await {
+            Ok(paths) => (
+                format!(
+                    "Exported {} proofs for task {} to {}",
+                    paths.len(),
+                    task.task_id,
+                    dir.display()
+                ),
+                EventType::Refresh,
+                LogLevel::Debug,
+            ),
+            Err(e) => (
+                format!("Failed to export proofs for task {}: {}", task.task_id, e),
+                EventType::Error,
+                LogLevel::Warn,
+            ),
+        };
+        self.event_sender
+            .send_proof_event(message, event_type, log_level)
+            .await;
+    }
+
     /// Track successful submission analytics based on task type
     async fn track_successful_submission(&self, task: &Task) {
         if task.task_type == crate::nexus_orchestrator::TaskType::ProofHash {
@@ -0,0 +1,154 @@
+//! Restart policy for crashed workers
+//!
+//! Proving runs in subprocesses, so a crash there, including a panic in a release build, which
+//! aborts on panic, fails only the task being proven. The worker then restarts with a fresh prover
+//! subprocess. The supervisor decides how long to wait before each restart, doubling the delay on
+//! consecutive crashes, and flags a crash loop once too many crashes happen within a window. A worker in a crash loop is degraded: it keeps restarting,
+//! but only at the maximum delay, until the crashes age out of the window.
+
+use crate::consts::cli_consts::supervisor::{
+    CRASH_LOOP_THRESHOLD, CRASH_LOOP_WINDOW_SECS, INITIAL_RESTART_BACKOFF_SECS,
+    MAX_RESTART_BACKOFF_SECS,
+};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// What to do after a crash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartDecision {
+    /// Delay before restarting the work loop
+    pub backoff: Duration,
+    /// Crashes within the crash-loop window, including this one
+    pub recent_crashes: usize,
+    /// Whether the worker is crash-looping
+    pub degraded: bool,
+}
+
+/// Tracks crashes of a worker and paces its restarts
+#[derive(Debug)]
+pub struct Supervisor {
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    crash_loop_threshold: usize,
+    crash_loop_window: Duration,
+    /// Crash times within the crash-loop window, oldest first
+    crashes: VecDeque<Instant>,
+    total_restarts: u32,
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self::new(
+            Duration::from_secs(INITIAL_RESTART_BACKOFF_SECS),
+            Duration::from_secs(MAX_RESTART_BACKOFF_SECS),
+            CRASH_LOOP_THRESHOLD,
+            Duration::from_secs(CRASH_LOOP_WINDOW_SECS),
+        )
+    }
+}
+
+impl Supervisor {
+    pub fn new(
+        initial_backoff: Duration,
+        max_backoff: Duration,
+        crash_loop_threshold: usize,
+        crash_loop_window: Duration,
+    ) -> Self {
+        Self {
+            initial_backoff,
+            max_backoff,
+            crash_loop_threshold,
+            crash_loop_window,
+            crashes: VecDeque::new(),
+            total_restarts: 0,
+        }
+    }
+
+    /// Record a crash at `now` and decide how to restart.
+    pub fn record_crash(&mut self, now: Instant) -> RestartDecision {
+        while self
+            .crashes
+            .front()
+            .is_some_and(|at| now.saturating_duration_since(*at) > self.crash_loop_window)
+        {
+            self.crashes.pop_front();
+        }
+        self.crashes.push_back(now);
+        self.total_restarts += 1;
+
+        let recent_crashes = self.crashes.len();
+        let degraded = recent_crashes >= self.crash_loop_threshold;
+        let backoff = if degraded {
+            self.max_backoff
+        } else {
+            // Double the delay for every earlier crash still in the window
+            let exponent = (recent_crashes - 1).min(16) as u32;
+            self.initial_backoff
+                .saturating_mul(2u32.pow(exponent))
+                .min(self.max_backoff)
+        };
+        RestartDecision {
+            backoff,
+            recent_crashes,
+            degraded,
+        }
+    }
+
+    /// Number of restarts since the worker started
+    pub fn total_restarts(&self) -> u32 {
+        self.total_restarts
+    }
+
+    /// Length of the window over which crashes are counted
+    pub fn crash_loop_window(&self) -> Duration {
+        self.crash_loop_window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supervisor() -> Supervisor {
+        Supervisor::new(
+            Duration::from_secs(1),
+            Duration::from_secs(60),
+            3,
+            Duration::from_secs(600),
+        )
+    }
+
+    #[test]
+    fn test_backoff_doubles_until_crash_loop() {
+        let mut supervisor = supervisor();
+        let start = Instant::now();
+
+        let first = supervisor.record_crash(start);
+        assert_eq!(first.backoff, Duration::from_secs(1));
+        assert!(!first.degraded);
+
+        let second = supervisor.record_crash(start + Duration::from_secs(10));
+        assert_eq!(second.backoff, Duration::from_secs(2));
+        assert!(!second.degraded);
+
+        let third = supervisor.record_crash(start + Duration::from_secs(20));
+        assert_eq!(third.backoff, Duration::from_secs(60));
+        assert_eq!(third.recent_crashes, 3);
+        assert!(third.degraded);
+        assert_eq!(supervisor.total_restarts(), 3);
+    }
+
+    #[test]
+    fn test_old_crashes_age_out() {
+        let mut supervisor = supervisor();
+        let start = Instant::now();
+        supervisor.record_crash(start);
+        supervisor.record_crash(start + Duration::from_secs(1));
+
+        let later = supervisor.record_crash(start + Duration::from_secs(700));
+        assert_eq!(later.recent_crashes, 1);
+        assert_eq!(later.backoff, Duration::from_secs(1));
+        assert!(!later.degraded);
+        assert_eq!(supervisor.total_restarts(), 3);
+    }
+}
@@ -71,3 +71,152 @@ fn logout_deletes_config_file() {
     // Confirm the file was deleted
     assert!(!config_path.exists());
 }
+
+#[test]
+/// Registering a node without a registered user should exit with the "config missing" code.
+fn register_node_without_config_exits_with_config_missing() {
+    let tmp = temp_config_dir();
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.arg("register-node")
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .code(10);
+}
+
+#[test]
+/// An invalid wallet address should exit with the usage error code before any network call.
+fn register_user_with_invalid_wallet_exits_with_usage_error() {
+    let tmp = temp_config_dir();
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.arg("register-user")
+        .arg("--wallet-address")
+        .arg("not-a-wallet")
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .code(2);
+}
+
+#[test]
+/// With `--if-not-exists`, an already registered node is kept and reported as JSON.
+fn register_node_if_not_exists_reports_existing_node_as_json() {
+    let tmp = temp_config_dir();
+    let config_path = config_file_path(&tmp);
+    fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    fs::write(
+        &config_path,
+        r#"{"user_id": "existing-user-id", "wallet_address": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", "node_id": "42", "environment": "Production"}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.arg("register-node")
+        .arg("--node-id")
+        .arg("7")
+        .arg("--if-not-exists")
+        .arg("--output")
+        .arg("json")
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .success()
+        .stdout(contains(r#""node_id":"42""#))
+        .stdout(contains(r#""created":false"#));
+}
+
+#[test]
+/// Wallets added to the address book are listed by alias.
+fn wallet_add_and_list() {
+    let tmp = temp_config_dir();
+    let wallet = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.args(["wallet", "add", "work", wallet])
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.args(["wallet", "list"])
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .success()
+        .stdout(contains("work"))
+        .stdout(contains(wallet));
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.args(["wallet", "add", "typo", "0x1234"])
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .code(2);
+}
+
+#[test]
+/// `theme set` should select a built-in or user theme, and reject unknown ones.
+fn theme_set_and_list() {
+    let tmp = temp_config_dir();
+    let themes = tmp.path().join(".nexus").join("themes");
+    std::fs::create_dir_all(&themes).unwrap();
+    std::fs::write(themes.join("ocean.toml"), "primary = \"#5fd7ff\"").unwrap();
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.args(["theme", "set", "ocean"])
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.args(["theme", "list"])
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .success()
+        .stdout(contains("* ocean"))
+        .stdout(contains("high-contrast"));
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.args(["theme", "set", "nope"])
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .code(2);
+}
+
+#[test]
+/// An unsupported `--lang` should exit with the usage error code.
+fn unsupported_language_exits_with_usage_error() {
+    let tmp = temp_config_dir();
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.args(["--lang", "xx", "wallet", "list"])
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .code(2);
+}
+
+#[test]
+/// `report` should show a failure bundle without uploading it, and reject unreadable ones.
+fn report_shows_failure_bundle() {
+    let tmp = temp_config_dir();
+    let bundle = tmp.path().join("failure.json");
+    std::fs::write(
+        &bundle,
+        r#"{"cli_version":"0.0.0","captured_at":"2025-01-01T00:00:00+00:00","task_id":"task-1",
+        "program_id":"fib_input_initial","task_type":"PROOF_REQUIRED","difficulty":"SMALL",
+        "inputs":["01ab"],"error":"guest panicked","environment":"http://localhost",
+        "system":{"os":"linux","arch":"x86_64","cpu_cores":4,"total_memory_gb":8.0,"num_workers":1}}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.args(["report", bundle.to_str().unwrap()])
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .success()
+        .stdout(contains("guest panicked"))
+        .stdout(contains("--upload"));
+
+    let mut cmd = Command::cargo_bin(BINARY_NAME).unwrap();
+    cmd.args(["report", "missing.json"])
+        .env("HOME", tmp.path()) // simulate different $HOME
+        .assert()
+        .code(2);
+}